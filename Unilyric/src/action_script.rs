@@ -0,0 +1,238 @@
+//! 可重放的操作脚本。
+//!
+//! 录制期间，每个成功处理的可重放事件都会被转换为 [`ScriptStep`] 记录下来，
+//! 并以 JSON 保存，方便手动编辑。重放时按顺序逐步发送对应的事件，
+//! 每一步都等上一步触发的转换完成后再执行。
+
+use std::collections::VecDeque;
+
+use lyrics_helper_core::{ChineseConversionConfig, LyricFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::app_actions::{FileAction, LyricsAction, ProcessorType, UserAction};
+
+/// 脚本中的一步操作。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ScriptStep {
+    /// 简繁转换
+    ConvertChinese { config: ChineseConversionConfig },
+    /// 只对翻译做简繁转换
+    ConvertChineseTranslation { config: ChineseConversionConfig },
+    /// 应用后处理器
+    ApplyProcessor { processor: ProcessorType },
+    /// 切换目标格式
+    SetTargetFormat { format: LyricFormat },
+    /// 整体缩放时间轴
+    ScaleTimings { factor: f64 },
+    /// 裁剪首尾静音
+    TrimSilence { start_at_ms: u64, trim_tail: bool },
+    /// 保存输出，重放时会弹出保存对话框
+    SaveOutput,
+}
+
+impl ScriptStep {
+    /// 将事件转换为脚本步骤。不可重放的事件返回 `None`。
+    pub fn from_action(action: &UserAction) -> Option<Self> {
+        match action {
+            UserAction::Lyrics(lyrics_action) => match lyrics_action.as_ref() {
+                LyricsAction::ConvertChinese(config) => {
+                    Some(Self::ConvertChinese { config: *config })
+                }
+                LyricsAction::ConvertChineseTranslation(config) => {
+                    Some(Self::ConvertChineseTranslation { config: *config })
+                }
+                LyricsAction::ApplyProcessor(processor) => Some(Self::ApplyProcessor {
+                    processor: *processor,
+                }),
+                LyricsAction::TargetFormatChanged(format) => {
+                    Some(Self::SetTargetFormat { format: *format })
+                }
+                LyricsAction::ScaleTimings(factor) => Some(Self::ScaleTimings { factor: *factor }),
+                LyricsAction::TrimSilence(start_at_ms, trim_tail) => Some(Self::TrimSilence {
+                    start_at_ms: *start_at_ms,
+                    trim_tail: *trim_tail,
+                }),
+                _ => None,
+            },
+            UserAction::File(FileAction::Save) => Some(Self::SaveOutput),
+            _ => None,
+        }
+    }
+
+    /// 生成重放该步骤时发送的事件。
+    pub fn to_action(&self) -> UserAction {
+        match self {
+            Self::ConvertChinese { config } => {
+                UserAction::Lyrics(Box::new(LyricsAction::ConvertChinese(*config)))
+            }
+            Self::ConvertChineseTranslation { config } => {
+                UserAction::Lyrics(Box::new(LyricsAction::ConvertChineseTranslation(*config)))
+            }
+            Self::ApplyProcessor { processor } => {
+                UserAction::Lyrics(Box::new(LyricsAction::ApplyProcessor(*processor)))
+            }
+            Self::SetTargetFormat { format } => {
+                UserAction::Lyrics(Box::new(LyricsAction::TargetFormatChanged(*format)))
+            }
+            Self::ScaleTimings { factor } => {
+                UserAction::Lyrics(Box::new(LyricsAction::ScaleTimings(*factor)))
+            }
+            Self::TrimSilence {
+                start_at_ms,
+                trim_tail,
+            } => UserAction::Lyrics(Box::new(LyricsAction::TrimSilence(
+                *start_at_ms,
+                *trim_tail,
+            ))),
+            Self::SaveOutput => UserAction::File(FileAction::Save),
+        }
+    }
+
+    /// 在界面上显示的步骤描述。
+    pub fn describe(&self) -> String {
+        match self {
+            Self::ConvertChinese { config } => format!("简繁转换 ({config:?})"),
+            Self::ConvertChineseTranslation { config } => {
+                format!("翻译简繁转换 ({config:?})")
+            }
+            Self::ApplyProcessor { processor } => {
+                format!("应用后处理器: {}", processor.display_name())
+            }
+            Self::SetTargetFormat { format } => format!("目标格式改为 {format:?}"),
+            Self::ScaleTimings { factor } => format!("时间缩放 × {factor}"),
+            Self::TrimSilence {
+                start_at_ms,
+                trim_tail,
+            } => format!(
+                "裁剪首尾静音，第一行开始于 {start_at_ms} ms{}",
+                if *trim_tail {
+                    "，裁掉结尾静音"
+                } else {
+                    ""
+                }
+            ),
+            Self::SaveOutput => "保存输出".to_string(),
+        }
+    }
+}
+
+/// 保存到文件中的操作脚本。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionScript {
+    pub steps: Vec<ScriptStep>,
+}
+
+/// 操作脚本的录制与重放状态。
+#[derive(Debug, Default)]
+pub struct ActionScriptState {
+    pub recording: bool,
+    /// 最近一次录制的步骤
+    pub recorded: Vec<ScriptStep>,
+    /// 等待重放的步骤
+    pub pending: VecDeque<ScriptStep>,
+}
+
+impl ActionScriptState {
+    pub fn is_replaying(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// 录制中且不在重放时记录一个已成功处理的事件。
+    pub fn record(&mut self, step: ScriptStep) {
+        if self.recording && !self.is_replaying() {
+            self.recorded.push(step);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_steps() -> Vec<ScriptStep> {
+        vec![
+            ScriptStep::ConvertChinese {
+                config: ChineseConversionConfig::S2t,
+            },
+            ScriptStep::ConvertChineseTranslation {
+                config: ChineseConversionConfig::T2s,
+            },
+            ScriptStep::ApplyProcessor {
+                processor: ProcessorType::SyllableSmoother,
+            },
+            ScriptStep::SetTargetFormat {
+                format: LyricFormat::Lrc,
+            },
+            ScriptStep::ScaleTimings { factor: 1.25 },
+            ScriptStep::TrimSilence {
+                start_at_ms: 500,
+                trim_tail: true,
+            },
+            ScriptStep::SaveOutput,
+        ]
+    }
+
+    #[test]
+    fn test_script_json_round_trip() {
+        let script = ActionScript { steps: all_steps() };
+        let json = serde_json::to_string_pretty(&script).unwrap();
+        let restored: ActionScript = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, script);
+    }
+
+    #[test]
+    fn test_script_step_json_format() {
+        let step: ScriptStep = serde_json::from_str(
+            r#"{"action": "trim_silence", "start_at_ms": 0, "trim_tail": false}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            step,
+            ScriptStep::TrimSilence {
+                start_at_ms: 0,
+                trim_tail: false,
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(ScriptStep::SaveOutput).unwrap(),
+            serde_json::json!({ "action": "save_output" })
+        );
+        assert_eq!(
+            serde_json::to_value(ScriptStep::SetTargetFormat {
+                format: LyricFormat::Ttml,
+            })
+            .unwrap(),
+            serde_json::json!({ "action": "set_target_format", "format": "Ttml" })
+        );
+        assert!(serde_json::from_str::<ScriptStep>(r#"{"action": "unknown"}"#).is_err());
+    }
+
+    #[test]
+    fn test_script_step_action_round_trip() {
+        for step in all_steps() {
+            assert_eq!(ScriptStep::from_action(&step.to_action()), Some(step));
+        }
+    }
+
+    #[test]
+    fn test_non_replayable_actions_are_not_recorded() {
+        let actions = [
+            UserAction::Lyrics(Box::new(LyricsAction::Convert)),
+            UserAction::Lyrics(Box::new(LyricsAction::MainInputChanged("text".to_string()))),
+            UserAction::File(FileAction::Open),
+        ];
+        for action in &actions {
+            assert_eq!(ScriptStep::from_action(action), None);
+        }
+
+        let mut state = ActionScriptState::default();
+        state.record(ScriptStep::SaveOutput);
+        assert!(state.recorded.is_empty());
+        state.recording = true;
+        state.record(ScriptStep::SaveOutput);
+        state.pending.push_back(ScriptStep::SaveOutput);
+        state.record(ScriptStep::SaveOutput);
+        assert_eq!(state.recorded, vec![ScriptStep::SaveOutput]);
+    }
+}
@@ -1,6 +1,9 @@
 pub mod types;
 pub mod websocket_client;
 pub mod worker;
-pub use types::{AMLLConnectorConfig, ConnectorCommand, ConnectorUpdate, WebsocketStatus};
+pub use types::{
+    AMLLConnectorConfig, ConnectionErrorKind, ConnectionEventCause, ConnectionTimelineEntry,
+    ConnectorCommand, ConnectorUpdate, LyricDeliveryStatus, WebsocketStatus,
+};
 pub mod protocol_v2;
 pub mod translation;
@@ -147,4 +147,6 @@ pub fn to_binary_v2(payload: &BinaryV2) -> anyhow::Result<Vec<u8>> {
 pub enum OutgoingMessage {
     Json(MessageV2),
     Binary(BinaryV2),
+    /// 送达探测，由客户端以 Ping 的形式发出，收到对应的 Pong 时报告携带的编号
+    DeliveryProbe(u64),
 }
@@ -137,7 +137,7 @@ pub(super) fn convert_to_protocol_lyrics(
 
     let mut agent_duet_map: HashMap<String, bool> = HashMap::new();
 
-    source_data
+    let mut protocol_lines: Vec<LyricLine> = source_data
         .lines
         .iter()
         .flat_map(|helper_line| {
@@ -255,6 +255,58 @@ pub(super) fn convert_to_protocol_lyrics(
 
             main_line_iter.chain(background_line_iter)
         })
+        .collect();
+
+    // 间奏标记行本身不发送，只需保证之前的行不会延伸进间奏，Player 才会显示间奏动画
+    for interlude in source_data.lines.iter().filter(|line| line.is_interlude()) {
+        for line in &mut protocol_lines {
+            if line.start_time < interlude.start_ms && line.end_time > interlude.start_ms {
+                line.end_time = interlude.start_ms;
+                for word in &mut line.words {
+                    word.end_time = word.end_time.min(interlude.start_ms).max(word.start_time);
+                }
+            }
+        }
+    }
+
+    protocol_lines
+}
+
+/// 将歌词转换为不含时间轴的纯文本行。
+///
+/// 每行只保留一个包含整行文本的词，逐字音译合并为整行音译，所有时间均为 0。
+pub(super) fn convert_to_plain_text_lyrics(
+    source_data: &helper_types::ParsedSourceData,
+) -> Vec<LyricLine> {
+    convert_to_protocol_lyrics(source_data)
+        .into_iter()
+        .map(|line| {
+            let text: String = line.words.iter().map(|word| word.word.as_ref()).collect();
+            let roman_text = line
+                .words
+                .iter()
+                .map(|word| word.roman_word.as_ref())
+                .filter(|roman| !roman.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let roman_lyric = if line.roman_lyric.is_empty() {
+                NullString(roman_text)
+            } else {
+                line.roman_lyric.clone()
+            };
+            LyricLine {
+                start_time: 0,
+                end_time: 0,
+                words: vec![LyricWord {
+                    start_time: 0,
+                    end_time: 0,
+                    word: NullString(text.trim_end().to_string()),
+                    roman_word: NullString::default(),
+                }],
+                roman_lyric,
+                ..line
+            }
+        })
         .collect()
 }
 
@@ -352,4 +404,21 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_plain_text_lyrics_drop_timing() {
+        let mut line = helper_types::LyricLine::new(1000, 3000);
+        line.add_content_track(helper_types::ContentType::Main, "hello world");
+        let source_data = helper_types::ParsedSourceData {
+            lines: vec![line],
+            ..Default::default()
+        };
+
+        let lines = convert_to_plain_text_lyrics(&source_data);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!((lines[0].start_time, lines[0].end_time), (0, 0));
+        assert_eq!(lines[0].words.len(), 1);
+        assert_eq!(lines[0].words[0].word.as_ref(), "hello world");
+    }
 }
@@ -6,6 +6,10 @@ use smtc_suite::MediaUpdate;
 pub struct AMLLConnectorConfig {
     pub enabled: bool,
     pub websocket_url: String,
+    /// 应用层心跳 (Ping) 的发送间隔，单位为秒
+    pub heartbeat_interval_secs: u64,
+    /// 发送心跳后等待 Pong 的超时时长，单位为秒
+    pub heartbeat_timeout_secs: u64,
 }
 
 impl Default for AMLLConnectorConfig {
@@ -13,6 +17,8 @@ impl Default for AMLLConnectorConfig {
         Self {
             enabled: false,
             websocket_url: "ws://localhost:11444".to_string(),
+            heartbeat_interval_secs: 5,
+            heartbeat_timeout_secs: 10,
         }
     }
 }
@@ -24,11 +30,76 @@ pub enum WebsocketStatus {
     Disconnected,
     Connecting,
     Connected,
-    Error(String),
+    Error {
+        kind: ConnectionErrorKind,
+        message: String,
+    },
 }
 
+/// 连接出错的类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionErrorKind {
+    /// 启动失败、连接握手失败、连接流错误或服务器关闭连接
+    Network,
+    /// 在超时时间内没有收到心跳回应
+    HeartbeatTimeout,
+}
+
+/// 连接状态变化的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionEventCause {
+    /// 用户点击连接、断开或重试
+    UserAction,
+    /// 连接握手失败、连接流错误或服务器关闭连接
+    NetworkError,
+    /// 在超时时间内没有收到心跳回应
+    HeartbeatTimeout,
+    /// 连接失败后自动发起的重连
+    AutoReconnect,
+}
+
+impl ConnectionEventCause {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConnectionEventCause::UserAction => "用户操作",
+            ConnectionEventCause::NetworkError => "网络错误",
+            ConnectionEventCause::HeartbeatTimeout => "心跳超时",
+            ConnectionEventCause::AutoReconnect => "自动重连",
+        }
+    }
+}
+
+/// 连接事件时间线中的一条记录。
 #[derive(Debug, Clone)]
-pub struct ActorSettings {}
+pub struct ConnectionTimelineEntry {
+    pub time: chrono::DateTime<chrono::Local>,
+    pub status: WebsocketStatus,
+    pub cause: ConnectionEventCause,
+}
+
+/// 向 AMLL Player 发送歌词的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LyricSendMode {
+    /// 发送带逐字时间轴的结构化歌词
+    #[default]
+    Structured,
+    /// 只发送每行的文本，不含时间轴，适配只能显示静态歌词的客户端
+    PlainText,
+}
+
+impl LyricSendMode {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Structured => "逐字歌词（含时间轴）",
+            Self::PlainText => "纯文本（不含时间轴）",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ActorSettings {
+    pub lyric_send_mode: LyricSendMode,
+}
 
 /// Unilyric 主应用发送给 amll_connector worker 的命令
 #[derive(Debug, Clone)]
@@ -43,11 +114,28 @@ pub enum ConnectorCommand {
     DisconnectWebsocket,
 }
 
+/// 最近一次发送的歌词是否已被 AMLL Player 接收。
+///
+/// 协议中没有针对歌词的确认消息。发送歌词后会紧接着发送一个 Ping，
+/// Player 按顺序处理同一连接上的消息，收到对应的 Pong 即说明歌词已被 Player 接收。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LyricDeliveryStatus {
+    /// 已发送，等待 Player 确认
+    Pending,
+    /// Player 已确认收到
+    Delivered,
+    /// 超时未收到确认，发送可能失败
+    TimedOut,
+    /// 没能发出或在确认前连接已断开
+    Failed(String),
+}
+
 /// amll_connector worker 发送给 Unilyric 主应用的更新/事件
 #[derive(Debug, Clone)]
 pub enum ConnectorUpdate {
     WebsocketStatusChanged(WebsocketStatus),
     SmtcUpdate(MediaUpdate),
+    LyricDelivery(LyricDeliveryStatus),
 }
 
 /// 发送给 UI 的更新包
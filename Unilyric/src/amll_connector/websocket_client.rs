@@ -1,6 +1,8 @@
 use futures_util::{SinkExt, StreamExt, stream::SplitSink};
 use smtc_suite::{RepeatMode as SmtcRepeatMode, SmtcControlCommand};
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
+use thiserror::Error;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc::{Receiver as TokioReceiver, Sender as TokioSender};
 use tokio::sync::oneshot::Receiver as OneshotReceiver;
@@ -10,13 +12,25 @@ use tokio_tungstenite::{
 use tracing::{debug, error, info, trace, warn};
 
 use super::protocol_v2::*;
-use crate::amll_connector::WebsocketStatus;
+use crate::amll_connector::{ConnectionErrorKind, WebsocketStatus};
 
 /// 连接结束的原因枚举
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Error)]
 enum LifecycleEndReason {
+    #[error("连接流错误: {0}")]
     StreamFailure(String),
+    #[error("服务器关闭了连接")]
     ServerClosed,
+    #[error("心跳超时")]
+    HeartbeatTimeout,
+}
+
+/// 根据客户端退出时返回的错误判断连接出错的类别。
+pub fn connection_error_kind(error: &anyhow::Error) -> ConnectionErrorKind {
+    match error.downcast_ref::<LifecycleEndReason>() {
+        Some(LifecycleEndReason::HeartbeatTimeout) => ConnectionErrorKind::HeartbeatTimeout,
+        _ => ConnectionErrorKind::Network,
+    }
 }
 
 type ActualWebSocketStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
@@ -30,14 +44,51 @@ const SEEK_DEBOUNCE_DURATION: Duration = Duration::from_millis(500);
 /// 设置音量的最小间隔，用于节流
 const MIN_VOLUME_SET_INTERVAL: Duration = Duration::from_millis(100);
 
-/// 应用层 Ping 消息的发送间隔
-const APP_PING_INTERVAL: Duration = Duration::from_secs(5);
+/// 应用层 Ping 消息的最小发送间隔
+const MIN_APP_PING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 送达探测等待 Pong 的超时时长
+const DELIVERY_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 送达探测的结果
+#[derive(Debug, Clone, Copy)]
+pub enum DeliveryReport {
+    /// 收到了探测对应的 Pong
+    Acked(u64),
+    /// 超时未收到探测对应的 Pong
+    TimedOut(u64),
+}
+
+/// 一个等待确认的送达探测
+struct PendingProbe {
+    id: u64,
+    /// 探测 Ping 是本次连接发出的第几个 Ping，收到同样数量的 Pong 时即确认
+    ping_index: u64,
+    deadline: tokio::time::Instant,
+}
+
+/// 应用层心跳的配置
+#[derive(Debug, Clone, Copy)]
+pub struct HeartbeatConfig {
+    /// Ping 消息的发送间隔
+    pub interval: Duration,
+    /// 等待 Pong 的超时时长
+    pub timeout: Duration,
+}
 
 /// 用于封装单个活跃连接期间所有状态的结构体
 struct ConnectionState {
     last_seek_request_info: Option<(u64, Instant)>,
     last_volume_set_processed_time: Option<Instant>,
     waiting_for_app_pong: bool,
+    /// 等待 Pong 的截止时间
+    app_pong_deadline: Option<tokio::time::Instant>,
+    /// 本次连接已发出的应用层 Ping 数量（包括心跳和送达探测）
+    pings_sent: u64,
+    /// 本次连接已收到的 Pong 数量
+    pongs_received: u64,
+    /// 按发出顺序排列的送达探测
+    pending_probes: VecDeque<PendingProbe>,
 }
 
 impl ConnectionState {
@@ -46,6 +97,23 @@ impl ConnectionState {
             last_seek_request_info: None,
             last_volume_set_processed_time: None,
             waiting_for_app_pong: false,
+            app_pong_deadline: None,
+            pings_sent: 0,
+            pongs_received: 0,
+            pending_probes: VecDeque::new(),
+        }
+    }
+
+    /// 移除已收到对应 Pong 的探测，并报告给 Actor。
+    fn report_acked_probes(&mut self, delivery_tx: &TokioSender<DeliveryReport>) {
+        while let Some(probe) = self
+            .pending_probes
+            .front()
+            .filter(|probe| probe.ping_index <= self.pongs_received)
+        {
+            trace!("[WebSocket 客户端] 送达探测 #{} 已确认。", probe.id);
+            let _ = delivery_tx.try_send(DeliveryReport::Acked(probe.id));
+            self.pending_probes.pop_front();
         }
     }
 }
@@ -73,7 +141,9 @@ async fn handle_v2_message(
         }
         Payload::Pong => {
             trace!("[WebSocket 客户端] 收到服务器的 Pong。");
+            state.pongs_received += 1;
             state.waiting_for_app_pong = false;
+            state.app_pong_deadline = None;
         }
         Payload::Command(command) => match command {
             Command::Pause => {
@@ -229,11 +299,15 @@ async fn handle_connection(
     ws_stream: ActualWebSocketStream,
     outgoing_rx: &mut TokioReceiver<OutgoingMessage>,
     media_cmd_tx: &TokioSender<SmtcControlCommand>,
+    delivery_tx: &TokioSender<DeliveryReport>,
     mut shutdown_rx: OneshotReceiver<()>,
+    heartbeat: HeartbeatConfig,
 ) -> Result<(), LifecycleEndReason> {
     let (mut ws_writer, mut ws_reader) = ws_stream.split();
     let mut state = ConnectionState::new();
-    let mut app_ping_interval_timer = tokio::time::interval(APP_PING_INTERVAL);
+    let mut app_ping_interval_timer =
+        tokio::time::interval(heartbeat.interval.max(MIN_APP_PING_INTERVAL));
+    app_ping_interval_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
     app_ping_interval_timer.tick().await;
 
     loop {
@@ -250,6 +324,10 @@ async fn handle_connection(
             // 2. 处理待发送消息 (来自Actor)
             maybe_body_to_send = outgoing_rx.recv() => {
                 if let Some(body_to_send) = maybe_body_to_send {
+                    let probe_id = match &body_to_send {
+                        OutgoingMessage::DeliveryProbe(id) => Some(*id),
+                        _ => None,
+                    };
                     let ws_message = match body_to_send {
                         OutgoingMessage::Json(v2_msg) => {
                             match serde_json::to_string(&v2_msg) {
@@ -269,11 +347,29 @@ async fn handle_connection(
                                 }
                             }
                         }
+                        OutgoingMessage::DeliveryProbe(_) => {
+                            let ping_msg = MessageV2 { payload: Payload::Ping };
+                            match serde_json::to_string(&ping_msg) {
+                                Ok(text) => WsMessage::Text(text.into()),
+                                Err(e) => {
+                                    error!("[WebSocket 客户端] 序列化 Ping 失败: {e:?}");
+                                    continue;
+                                }
+                            }
+                        }
                     };
 
                     if ws_writer.send(ws_message).await.is_err() {
                         return Err(LifecycleEndReason::StreamFailure("发送主通道消息失败".to_string()));
                     }
+                    if let Some(id) = probe_id {
+                        state.pings_sent += 1;
+                        state.pending_probes.push_back(PendingProbe {
+                            id,
+                            ping_index: state.pings_sent,
+                            deadline: tokio::time::Instant::now() + DELIVERY_ACK_TIMEOUT,
+                        });
+                    }
                 } else {
                     return Err(LifecycleEndReason::StreamFailure("主发送通道已关闭".to_string()));
                 }
@@ -286,7 +382,55 @@ async fn handle_connection(
                     &mut ws_writer,
                     media_cmd_tx,
                     &mut state,
-                ).await?
+                ).await?;
+                state.report_acked_probes(delivery_tx);
+            }
+
+            // 4. 送达探测超时检测
+            _ = tokio::time::sleep_until(
+                state.pending_probes.front().map_or_else(tokio::time::Instant::now, |probe| probe.deadline)
+            ), if !state.pending_probes.is_empty() => {
+                if let Some(probe) = state.pending_probes.pop_front() {
+                    debug!(
+                        "[WebSocket 客户端] 送达探测 #{} 在 {} 秒内未得到确认。",
+                        probe.id,
+                        DELIVERY_ACK_TIMEOUT.as_secs()
+                    );
+                    let _ = delivery_tx.try_send(DeliveryReport::TimedOut(probe.id));
+                }
+            }
+
+            // 5. 心跳超时检测
+            _ = tokio::time::sleep_until(
+                state.app_pong_deadline.unwrap_or_else(tokio::time::Instant::now)
+            ), if state.app_pong_deadline.is_some() => {
+                warn!(
+                    "[WebSocket 客户端] 在 {} 秒内未收到 Pong，判定连接已失去响应。",
+                    heartbeat.timeout.as_secs()
+                );
+                ws_writer.close().await.ok();
+                return Err(LifecycleEndReason::HeartbeatTimeout);
+            }
+
+            // 6. 定期发送应用层心跳
+            _ = app_ping_interval_timer.tick() => {
+                if state.waiting_for_app_pong {
+                    continue;
+                }
+                let ping_msg = MessageV2 { payload: Payload::Ping };
+                match serde_json::to_string(&ping_msg) {
+                    Ok(text) => {
+                        if ws_writer.send(WsMessage::Text(text.into())).await.is_err() {
+                            return Err(LifecycleEndReason::StreamFailure("发送 Ping 失败".to_string()));
+                        }
+                        trace!("[WebSocket 客户端] 已发送应用层 Ping。");
+                        state.pings_sent += 1;
+                        state.waiting_for_app_pong = true;
+                        state.app_pong_deadline =
+                            Some(tokio::time::Instant::now() + heartbeat.timeout);
+                    }
+                    Err(e) => error!("[WebSocket 客户端] 序列化 Ping 失败: {e:?}"),
+                }
             }
         }
     }
@@ -298,7 +442,9 @@ pub async fn run_websocket_client(
     mut outgoing_rx: TokioReceiver<OutgoingMessage>,
     status_tx: TokioSender<WebsocketStatus>,
     media_cmd_tx: TokioSender<SmtcControlCommand>,
+    delivery_tx: TokioSender<DeliveryReport>,
     mut shutdown_rx: OneshotReceiver<()>,
+    heartbeat: HeartbeatConfig,
 ) -> anyhow::Result<()> {
     info!("[WebSocket 客户端] 启动，目标 URL: {websocket_url}");
 
@@ -340,7 +486,15 @@ pub async fn run_websocket_client(
         ));
     }
 
-    let reason = handle_connection(ws_stream, &mut outgoing_rx, &media_cmd_tx, shutdown_rx).await;
+    let reason = handle_connection(
+        ws_stream,
+        &mut outgoing_rx,
+        &media_cmd_tx,
+        &delivery_tx,
+        shutdown_rx,
+        heartbeat,
+    )
+    .await;
 
     match reason {
         Ok(_) => {
@@ -348,12 +502,8 @@ pub async fn run_websocket_client(
             Ok(())
         }
         Err(lifecycle_reason) => {
-            let error_message = match lifecycle_reason {
-                LifecycleEndReason::StreamFailure(msg) => format!("连接流错误: {msg}"),
-                LifecycleEndReason::ServerClosed => "服务器关闭了连接".to_string(),
-            };
-            warn!("[WebSocket 客户端] 连接因 '{error_message}' 而异常终止。");
-            Err(anyhow::anyhow!(error_message))
+            warn!("[WebSocket 客户端] 连接因 '{lifecycle_reason}' 而异常终止。");
+            Err(lifecycle_reason.into())
         }
     }
 }
@@ -21,13 +21,16 @@ use tokio::{
 
 use crate::amll_connector::{
     protocol_v2::*,
-    types::{ActorSettings, UiUpdate},
+    types::{ActorSettings, LyricDeliveryStatus, LyricSendMode, UiUpdate},
 };
 
 use super::{
-    translation::convert_to_protocol_lyrics,
-    types::{AMLLConnectorConfig, ConnectorCommand, ConnectorUpdate, WebsocketStatus},
-    websocket_client,
+    translation::{convert_to_plain_text_lyrics, convert_to_protocol_lyrics},
+    types::{
+        AMLLConnectorConfig, ConnectionErrorKind, ConnectorCommand, ConnectorUpdate,
+        WebsocketStatus,
+    },
+    websocket_client::{self, DeliveryReport},
 };
 
 type ClientTaskComponents = (
@@ -43,6 +46,12 @@ enum StateFutureResult {
 
 const CHANNEL_BUFFER_SIZE: usize = 32;
 
+/// 超过此大小的封面会在发送前被压缩
+const MAX_COVER_BYTES: usize = 512 * 1024;
+/// 压缩封面时的最大边长
+const MAX_COVER_DIMENSION: u32 = 1024;
+const COVER_JPEG_QUALITY: u8 = 85;
+
 enum PostShutdownAction {
     DoNothing,
     Restart,
@@ -69,7 +78,15 @@ struct ActorState {
     session_ready: bool,
     retry_attempts: u32,
     last_track_info: Option<smtc_suite::NowPlayingInfo>,
+    /// 最近一次的封面数据（已按需压缩），用于在发送歌词或重新连接时一并发送
+    last_cover: Option<Vec<u8>>,
     last_audio_sent_time: Option<Instant>,
+    /// 传给 WebSocket 客户端，用于报告送达探测的结果
+    delivery_tx: TokioSender<DeliveryReport>,
+    /// 最近一次发送歌词时使用的送达探测编号
+    last_lyric_probe_id: u64,
+    /// 正在等待确认的歌词探测编号
+    pending_lyric_probe: Option<u64>,
 }
 
 fn handle_websocket_send_error<T>(result: Result<(), TrySendError<T>>, message_type: &str) {
@@ -131,6 +148,81 @@ fn send_cover_to_ws(tx: &TokioSender<OutgoingMessage>, cover_data: &[u8]) {
     }
 }
 
+/// 封面过大时将其缩放并重新编码为 JPEG，以免占满 WebSocket 发送队列。
+///
+/// 压缩失败或压缩后反而更大时返回原始数据。
+fn prepare_cover_data(cover_data: &[u8]) -> Vec<u8> {
+    if cover_data.len() <= MAX_COVER_BYTES {
+        return cover_data.to_vec();
+    }
+
+    let image = match image::load_from_memory(cover_data) {
+        Ok(image) => image,
+        Err(e) => {
+            warn!("[AMLL Actor] 无法解码封面图片，将发送原始数据: {}", e);
+            return cover_data.to_vec();
+        }
+    };
+
+    let resized = if image.width() > MAX_COVER_DIMENSION || image.height() > MAX_COVER_DIMENSION {
+        image.resize(
+            MAX_COVER_DIMENSION,
+            MAX_COVER_DIMENSION,
+            image::imageops::FilterType::Triangle,
+        )
+    } else {
+        image
+    };
+
+    let mut compressed = Vec::new();
+    let encoder =
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut compressed, COVER_JPEG_QUALITY);
+    if let Err(e) = resized.to_rgb8().write_with_encoder(encoder) {
+        warn!("[AMLL Actor] 压缩封面失败，将发送原始数据: {}", e);
+        return cover_data.to_vec();
+    }
+
+    if compressed.len() >= cover_data.len() {
+        return cover_data.to_vec();
+    }
+
+    debug!(
+        "[AMLL Actor] 封面已压缩: {} 字节 -> {} 字节",
+        cover_data.len(),
+        compressed.len()
+    );
+    compressed
+}
+
+fn send_lyric_delivery_status(update_tx: &StdSender<UiUpdate>, status: LyricDeliveryStatus) {
+    let _ = update_tx.send(UiUpdate {
+        payload: ConnectorUpdate::LyricDelivery(status),
+        repaint_needed: true,
+    });
+}
+
+/// 只处理最近一次发送歌词对应的探测结果，更早的探测已被新的发送取代。
+fn handle_delivery_report(
+    report: DeliveryReport,
+    state: &mut ActorState,
+    update_tx: &StdSender<UiUpdate>,
+) {
+    let (id, status) = match report {
+        DeliveryReport::Acked(id) => (id, LyricDeliveryStatus::Delivered),
+        DeliveryReport::TimedOut(id) => (id, LyricDeliveryStatus::TimedOut),
+    };
+    if state.pending_lyric_probe != Some(id) {
+        return;
+    }
+    state.pending_lyric_probe = None;
+    if status == LyricDeliveryStatus::TimedOut {
+        warn!("[AMLL Actor] AMLL Player 未在超时时间内确认收到歌词，发送可能失败。");
+    } else {
+        debug!("[AMLL Actor] AMLL Player 已确认收到歌词。");
+    }
+    send_lyric_delivery_status(update_tx, status);
+}
+
 fn send_play_state_to_ws(tx: &TokioSender<OutgoingMessage>, info: &smtc_suite::NowPlayingInfo) {
     if let Some(status) = info.playback_status {
         let state_update = match status {
@@ -157,6 +249,7 @@ fn start_websocket_client_task(
     config: &AMLLConnectorConfig,
     status_tx: TokioSender<WebsocketStatus>,
     media_cmd_tx: TokioSender<SmtcControlCommand>,
+    delivery_tx: TokioSender<DeliveryReport>,
 ) -> Result<ClientTaskComponents, anyhow::Error> {
     if config.websocket_url.is_empty() {
         return Err(anyhow!("WebSocket URL 不能为空"));
@@ -171,6 +264,10 @@ fn start_websocket_client_task(
     let (ws_outgoing_tx, ws_outgoing_rx) = tokio_channel(CHANNEL_BUFFER_SIZE);
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let url = config.websocket_url.clone();
+    let heartbeat = websocket_client::HeartbeatConfig {
+        interval: Duration::from_secs(config.heartbeat_interval_secs),
+        timeout: Duration::from_secs(config.heartbeat_timeout_secs),
+    };
 
     let handle = tokio::spawn(async move {
         websocket_client::run_websocket_client(
@@ -178,7 +275,9 @@ fn start_websocket_client_task(
             ws_outgoing_rx,
             status_tx,
             media_cmd_tx,
+            delivery_tx,
             shutdown_rx,
+            heartbeat,
         )
         .await
     });
@@ -199,7 +298,12 @@ fn try_start_connection(
         return;
     }
 
-    match start_websocket_client_task(&state.config, status_tx.clone(), media_cmd_tx.clone()) {
+    match start_websocket_client_task(
+        &state.config,
+        status_tx.clone(),
+        media_cmd_tx.clone(),
+        state.delivery_tx.clone(),
+    ) {
         Ok((tx, shutdown_tx, handle)) => {
             state.connection = ConnectionState::Running {
                 tx,
@@ -215,10 +319,10 @@ fn try_start_connection(
             error!("[AMLL Actor] 启动 WebSocket 客户端失败: {}", e);
             state.connection = ConnectionState::Disconnected;
             let _ = update_tx.send(UiUpdate {
-                payload: ConnectorUpdate::WebsocketStatusChanged(WebsocketStatus::Error(format!(
-                    "启动失败: {}",
-                    e
-                ))),
+                payload: ConnectorUpdate::WebsocketStatusChanged(WebsocketStatus::Error {
+                    kind: ConnectionErrorKind::Network,
+                    message: format!("启动失败: {}", e),
+                }),
                 repaint_needed: true,
             });
         }
@@ -260,6 +364,8 @@ fn handle_smtc_update(
                     || cached.duration_ms != new_info.duration_ms
             });
 
+            let new_cover = new_info.cover_data.as_deref().map(prepare_cover_data);
+
             if let (ConnectionState::Running { tx, .. }, true) =
                 (&state.connection, state.session_ready)
             {
@@ -268,7 +374,7 @@ fn handle_smtc_update(
                     send_music_info_to_ws(tx, &new_info);
                 }
 
-                if let Some(ref cover_data) = new_info.cover_data {
+                if let Some(ref cover_data) = new_cover {
                     debug!("[AMLL Actor] 检测到封面更新，发送封面数据。");
                     send_cover_to_ws(tx, cover_data);
                 }
@@ -302,6 +408,11 @@ fn handle_smtc_update(
                 send_play_state_to_ws(tx, &new_info);
                 send_progress_to_ws(tx, &new_info);
             }
+            if new_cover.is_some() {
+                state.last_cover = new_cover;
+            } else if is_new_song {
+                state.last_cover = None;
+            }
             state.last_track_info = Some(new_info.clone());
             repaint_needed = true;
             ConnectorUpdate::SmtcUpdate(MediaUpdate::TrackChanged(new_info))
@@ -326,6 +437,7 @@ fn handle_smtc_update(
                 MediaUpdate::SessionsChanged(_) | MediaUpdate::SelectedSessionVanished(_)
             ) {
                 state.last_track_info = None;
+                state.last_cover = None;
                 repaint_needed = true;
             }
             ConnectorUpdate::SmtcUpdate(other_update)
@@ -351,13 +463,18 @@ async fn handle_app_command(
         ConnectorCommand::UpdateConfig(new_config) => {
             let should_be_running = new_config.enabled;
             let url_changed = state.config.websocket_url != new_config.websocket_url;
+            let heartbeat_changed = state.config.heartbeat_interval_secs
+                != new_config.heartbeat_interval_secs
+                || state.config.heartbeat_timeout_secs != new_config.heartbeat_timeout_secs;
             state.config = new_config;
 
             let is_running = matches!(state.connection, ConnectionState::Running { .. });
             let is_waiting_to_retry =
                 matches!(state.connection, ConnectionState::WaitingToRetry(_));
 
-            if should_be_running && (!is_running || url_changed || is_waiting_to_retry) {
+            if should_be_running
+                && (!is_running || url_changed || heartbeat_changed || is_waiting_to_retry)
+            {
                 if let ConnectionState::Running {
                     shutdown_tx,
                     handle,
@@ -394,6 +511,12 @@ async fn handle_app_command(
         }
         ConnectorCommand::DisconnectWebsocket => {
             state.retry_attempts = 0;
+            if state.pending_lyric_probe.take().is_some() {
+                send_lyric_delivery_status(
+                    update_tx,
+                    LyricDeliveryStatus::Failed("确认前连接已断开".to_string()),
+                );
+            }
             if let ConnectionState::Running {
                 shutdown_tx,
                 handle,
@@ -429,20 +552,57 @@ async fn handle_app_command(
             .await;
         }
         ConnectorCommand::SendLyric(parsed_data) => {
-            if let ConnectionState::Running { tx, .. } = &state.connection {
-                let protocol_lyrics: Vec<LyricLine> = convert_to_protocol_lyrics(&parsed_data);
-                let lyric_content = LyricContent::Structured {
-                    lines: protocol_lyrics,
-                };
-                let payload = Payload::State(StateUpdate::SetLyric(lyric_content));
-                let msg = OutgoingMessage::Json(MessageV2 { payload });
-                handle_websocket_send_error(tx.try_send(msg), "SetLyric");
+            let ConnectionState::Running { tx, .. } = &state.connection else {
+                state.pending_lyric_probe = None;
+                send_lyric_delivery_status(
+                    update_tx,
+                    LyricDeliveryStatus::Failed("未连接到 AMLL Player".to_string()),
+                );
+                return;
+            };
+
+            let protocol_lyrics: Vec<LyricLine> = match state.actor_settings.lyric_send_mode {
+                LyricSendMode::Structured => convert_to_protocol_lyrics(&parsed_data),
+                LyricSendMode::PlainText => convert_to_plain_text_lyrics(&parsed_data),
+            };
+            let lyric_content = LyricContent::Structured {
+                lines: protocol_lyrics,
+            };
+            let payload = Payload::State(StateUpdate::SetLyric(lyric_content));
+            let msg = OutgoingMessage::Json(MessageV2 { payload });
+            let lyric_result = tx.try_send(msg);
+            let lyric_queued = lyric_result.is_ok();
+            handle_websocket_send_error(lyric_result, "SetLyric");
+
+            // 随歌词一并发送封面，保证 Player 端显示的封面与当前歌曲一致
+            if let Some(ref cover_data) = state.last_cover {
+                send_cover_to_ws(tx, cover_data);
             }
+
+            let status = if lyric_queued {
+                state.last_lyric_probe_id += 1;
+                let probe_id = state.last_lyric_probe_id;
+                let probe_result = tx.try_send(OutgoingMessage::DeliveryProbe(probe_id));
+                if probe_result.is_ok() {
+                    state.pending_lyric_probe = Some(probe_id);
+                    LyricDeliveryStatus::Pending
+                } else {
+                    handle_websocket_send_error(probe_result, "DeliveryProbe");
+                    state.pending_lyric_probe = None;
+                    LyricDeliveryStatus::Failed("发送队列已满，无法确认是否送达".to_string())
+                }
+            } else {
+                state.pending_lyric_probe = None;
+                LyricDeliveryStatus::Failed("发送队列已满或连接已关闭".to_string())
+            };
+            send_lyric_delivery_status(update_tx, status);
         }
         ConnectorCommand::SendCover(cover_data) => {
+            let cover_data = prepare_cover_data(&cover_data);
             if let ConnectionState::Running { tx, .. } = &state.connection {
                 send_cover_to_ws(tx, &cover_data);
             }
+            state.last_cover = Some(cover_data);
         }
     }
 }
@@ -464,20 +624,26 @@ pub async fn amll_connector_actor(
     mut command_rx: TokioReceiver<ConnectorCommand>,
     update_tx: StdSender<UiUpdate>,
     initial_config: AMLLConnectorConfig,
+    initial_actor_settings: ActorSettings,
     smtc_command_tx: TokioSender<MediaCommand>,
     mut smtc_update_rx: TokioReceiver<MediaUpdate>,
 ) {
     let (ws_status_tx, mut ws_status_rx) = tokio_channel(CHANNEL_BUFFER_SIZE);
     let (media_cmd_tx, mut media_cmd_rx) = tokio_channel(CHANNEL_BUFFER_SIZE);
+    let (delivery_tx, mut delivery_rx) = tokio_channel(CHANNEL_BUFFER_SIZE);
 
     let mut state = ActorState {
         config: initial_config,
-        actor_settings: ActorSettings {},
+        actor_settings: initial_actor_settings,
         connection: ConnectionState::Disconnected,
         session_ready: false,
         retry_attempts: 0,
         last_track_info: None,
+        last_cover: None,
         last_audio_sent_time: None,
+        delivery_tx,
+        last_lyric_probe_id: 0,
+        pending_lyric_probe: None,
     };
 
     if state.config.enabled {
@@ -505,6 +671,12 @@ pub async fn amll_connector_actor(
                 match state_result {
                     StateFutureResult::TaskFinished(result) => {
                         state.session_ready = false;
+                        if state.pending_lyric_probe.take().is_some() {
+                            send_lyric_delivery_status(
+                                &update_tx,
+                                LyricDeliveryStatus::Failed("确认前连接已断开".to_string()),
+                            );
+                        }
                         let command = MediaCommand::SetHighFrequencyProgressUpdates(false);
                         handle_smtc_send_error(smtc_command_tx.send(command).await, "禁用高频更新").await;
 
@@ -517,11 +689,21 @@ pub async fn amll_connector_actor(
                         let was_successful_close = result.as_ref().is_ok_and(|res| res.is_ok());
 
                         if !matches!(next_action, PostShutdownAction::Restart) && !was_successful_close {
-                            match &result {
-                                Ok(Err(e)) => warn!("[AMLL Actor] WebSocket 客户端异常终止: {}", e),
-                                Err(e) => error!("[AMLL Actor] WebSocket 任务 panicked: {}", e),
-                                _ => {}
-                            }
+                            let failure_kind = match &result {
+                                Ok(Err(e)) => websocket_client::connection_error_kind(e),
+                                _ => ConnectionErrorKind::Network,
+                            };
+                            let failure_reason = match &result {
+                                Ok(Err(e)) => {
+                                    warn!("[AMLL Actor] WebSocket 客户端异常终止: {}", e);
+                                    e.to_string()
+                                }
+                                Err(e) => {
+                                    error!("[AMLL Actor] WebSocket 任务 panicked: {}", e);
+                                    "连接任务异常退出".to_string()
+                                }
+                                _ => String::new(),
+                            };
 
                             state.retry_attempts += 1;
                             const MAX_RETRIES: u32 = 3;
@@ -529,7 +711,10 @@ pub async fn amll_connector_actor(
                             if state.retry_attempts > MAX_RETRIES {
                                 error!("[AMLL Actor] 已达到最大重连次数 ({})，将停止自动重连。", MAX_RETRIES);
                                 let _ = update_tx.send(UiUpdate {
-                                    payload: ConnectorUpdate::WebsocketStatusChanged(WebsocketStatus::Error("已达到最大重连次数".to_string())),
+                                    payload: ConnectorUpdate::WebsocketStatusChanged(WebsocketStatus::Error {
+                                        kind: failure_kind,
+                                        message: format!("{failure_reason}，已达到最大重连次数"),
+                                    }),
                                     repaint_needed: true,
                                 });
                             } else {
@@ -537,11 +722,14 @@ pub async fn amll_connector_actor(
                                 let delay_secs = base_delay_secs * 2_u64.pow(state.retry_attempts - 1);
                                 let reconnect_delay = Duration::from_secs(delay_secs);
 
-                                let status_msg = format!("连接失败，正在重试 ({}/{})", state.retry_attempts, MAX_RETRIES);
+                                let status_msg = format!("{failure_reason}，正在重试 ({}/{})", state.retry_attempts, MAX_RETRIES);
                                 info!("[AMLL Actor] {}将在 {:?} 后进行...", status_msg, reconnect_delay);
 
                                 let _ = update_tx.send(UiUpdate {
-                                    payload: ConnectorUpdate::WebsocketStatusChanged(WebsocketStatus::Error(status_msg)),
+                                    payload: ConnectorUpdate::WebsocketStatusChanged(WebsocketStatus::Error {
+                                        kind: failure_kind,
+                                        message: status_msg,
+                                    }),
                                     repaint_needed: true,
                                 });
 
@@ -590,13 +778,13 @@ pub async fn amll_connector_actor(
 
                     if let Some(track_info) = &state.last_track_info {
                         send_music_info_to_ws(tx, track_info);
-                        if let Some(ref cover_data) = track_info.cover_data {
+                        if let Some(ref cover_data) = state.last_cover {
                             send_cover_to_ws(tx, cover_data);
                         }
                         send_play_state_to_ws(tx, track_info);
                         send_progress_to_ws(tx, track_info);
                     }
-                } else if matches!(status, WebsocketStatus::Disconnected | WebsocketStatus::Error(_)) {
+                } else if matches!(status, WebsocketStatus::Disconnected | WebsocketStatus::Error { .. }) {
                     state.session_ready = false;
                     let command = MediaCommand::SetHighFrequencyProgressUpdates(false);
                     handle_smtc_send_error(smtc_command_tx.send(command).await, "禁用高频更新").await;
@@ -619,6 +807,10 @@ pub async fn amll_connector_actor(
                 handle_app_command(command, &mut state, &ws_status_tx, &media_cmd_tx, &update_tx, &smtc_command_tx).await;
             },
 
+            Some(report) = delivery_rx.recv() => {
+                handle_delivery_report(report, &mut state, &update_tx);
+            },
+
             Some(media_cmd) = media_cmd_rx.recv() => {
                 handle_player_control_command(media_cmd, &smtc_command_tx).await;
             },
@@ -17,6 +17,8 @@ impl eframe::App for UniLyricApp {
 
         app_update::process_connector_updates(self);
 
+        app_update::process_script_playback(self);
+
         if let Some(trigger_time) = self.auto_fetch_trigger_time {
             if std::time::Instant::now() >= trigger_time {
                 self.auto_fetch_trigger_time = None;
@@ -53,10 +55,22 @@ impl eframe::App for UniLyricApp {
             self.handle_actions(actions);
         }
 
-        if ctx.input(|i| i.viewport().close_requested()) && !self.shutdown_initiated {
+        self.autosave_session(false);
+
+        if ctx.input(|i| i.viewport().close_requested())
+            && !self.shutdown_initiated
+            && !self.ui.exit_confirmed
+            && self.lyrics.has_unsaved_documents()
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.ui.show_exit_unsaved_confirm = true;
+        } else if ctx.input(|i| i.viewport().close_requested()) && !self.shutdown_initiated {
             self.shutdown_initiated = true;
             tracing::debug!("[Shutdown] 检测到窗口关闭请求，发送关闭信号...");
 
+            self.autosave_session(true);
+            self.save_metadata_key_usage();
+
             self.send_shutdown_signals();
         }
     }
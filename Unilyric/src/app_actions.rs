@@ -1,9 +1,12 @@
 use std::fmt;
+use std::sync::Arc;
 
 use crate::app_definition::AppView;
-use crate::app_settings::AppSettings;
+use crate::app_settings::{AppSettings, FormatOptionsSettings};
+use crate::batch_download::BatchDownloadStatus;
 use crate::error::AppResult;
 use crate::types::LrcContentType;
+use crate::waveform::Waveform;
 use egui_toast::Toast;
 use lyrics_helper_core::BatchTaskUpdate;
 use lyrics_helper_core::CanonicalMetadataKey;
@@ -25,15 +28,39 @@ pub enum UserAction {
     AmllConnector(AmllConnectorAction),
     Downloader(Box<DownloaderAction>),
     BatchConverter(BatchConverterAction),
+    LocalCache(LocalCacheAction),
 }
 
 // 子事件枚举定义
 #[derive(Debug, Clone)]
 pub enum FileAction {
     Open,
+    /// 一次打开多个文件，加入已打开文件列表
+    OpenMultiple,
     Save,
     LoadTranslationLrc,
     LoadRomanizationLrc,
+    ExportTranslationLrc,
+    ExportRomanizationLrc,
+    /// 为 LRC 合并向导选择文件，`None` 表示主歌词
+    PickLrcMergeFile(Option<LrcContentType>),
+    /// 按时间戳对齐合并向导中选择的文件并生成 TTML
+    MergeLrcFiles,
+    /// 保存合并生成的 TTML 并在编辑器中打开
+    SaveLrcMergeResult,
+    /// 按导出图片窗口中的选项把歌词渲染为 PNG 长图并保存
+    ExportLyricImage,
+    /// 后台渲染并保存歌词图片完成，成功时携带保存的路径
+    LyricImageExported(Result<std::path::PathBuf, String>),
+    /// 将歌词统计报告保存为文本文件
+    SaveStatsReport,
+    /// 将录制的操作保存为脚本文件
+    SaveActionScript,
+    /// 选择一个脚本文件，对当前歌词依次重放其中的操作
+    RunActionScript,
+    /// 选择音频文件，在时间轴中显示其波形
+    LoadTimelineAudio,
+    TimelineAudioLoaded(AppResult<Arc<Waveform>>),
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +68,8 @@ pub enum LyricsAction {
     Convert,
     ConvertCompleted(AppResult<FullConversionResult>),
     ConvertChinese(ChineseConversionConfig),
+    /// 只转换翻译，主歌词保持不变
+    ConvertChineseTranslation(ChineseConversionConfig),
     SourceFormatChanged(LyricFormat),
     TargetFormatChanged(LyricFormat),
     AddMetadata(CanonicalMetadataKey),
@@ -48,13 +77,73 @@ pub enum LyricsAction {
     UpdateMetadataKey(usize, CanonicalMetadataKey),
     UpdateMetadataValue(usize, String),
     ToggleMetadataPinned(usize),
+    ClearUnpinnedMetadata,
+    CopyAsAmllSubmission,
+    /// 以“时间 | 歌词 | 翻译”的 Markdown 表格复制歌词
+    CopyAsMarkdownTable,
+    /// 基于已解析的歌词生成统计报告
+    GenerateStatsReport,
+    /// 复制单行歌词（行索引，复制格式）
+    CopyLine(usize, LineCopyFormat),
+    /// 开始或停止录制操作脚本
+    SetScriptRecording(bool),
     LrcInputChanged(String, LrcContentType),
+    /// 切换到指定索引的翻译轨道
+    SelectTranslationTrack(usize),
+    /// 新增一条指定语言的翻译轨道
+    AddTranslationTrack(String),
+    /// 删除指定索引的翻译轨道
+    RemoveTranslationTrack(usize),
     MainInputChanged(String),
     ClearAllData,
     LoadFetchedResult(FullLyricsResult),
     ApplyFetchedLyrics(Box<LyricsAndMetadata>),
     LoadFileContent(String, std::path::PathBuf),
     ApplyProcessor(ProcessorType),
+    /// 基于缓存的中间结果增减音节平滑的迭代次数
+    AdjustSmoothingIterations(isize),
+    /// 将指定行与下一行合并
+    MergeLineWithNext(usize),
+    /// 在指定行的第 N 个主歌词音节处拆分（行索引，音节索引）
+    SplitLineAt(usize, usize),
+    /// 插入间奏标记行（开始时间，结束时间）
+    InsertInterlude(u64, u64),
+    /// 设置指定行的校对备注（行索引，内容），内容为空时删除备注
+    SetLineNote(usize, String),
+    /// 删除指定的间奏标记行
+    RemoveInterlude(usize),
+    /// 将所有时间戳乘以给定的比例
+    ScaleTimings(f64),
+    /// 裁剪首尾静音（第一行的目标开始时间，是否裁掉结尾静音）
+    TrimSilence(u64, bool),
+    /// 在时间轴中调整一行的时间（行索引，开始时间，结束时间）
+    SetLineTiming(usize, u64, u64),
+    /// 在时间轴中调整主歌词音节的时间（行索引，音节索引，开始时间，结束时间）
+    SetSyllableTiming(usize, usize, u64, u64),
+    /// 将一行的逐字时间轴按相对偏移复制到文本相同的其他行（源行索引，目标行索引）
+    CopyTimingToRepeatedLines(usize, Vec<usize>),
+    /// 恢复上次保存的工作区会话
+    RestoreSession,
+    /// 丢弃上次保存的工作区会话
+    DiscardSession,
+    /// 对已解析的歌词运行有效性检查
+    LintLyrics,
+    /// 保存各目标格式的生成选项并重新生成输出
+    SaveFormatOptions(Box<FormatOptionsSettings>),
+    /// 扫描歌词中的元数据行，生成元数据建议
+    InferMetadataFromLyrics,
+    /// 将选中的元数据建议加入元数据
+    ApplyMetadataSuggestions,
+    /// 将多个已读取的文件加入已打开文件列表（路径，内容）
+    OpenDocuments(Vec<(std::path::PathBuf, String)>),
+    /// 切换当前编辑的文件
+    SwitchDocument(usize),
+    /// 关闭已打开的文件，有未保存的更改时先询问
+    CloseDocument(usize),
+    /// 关闭已打开的文件并丢弃未保存的更改
+    DiscardDocument(usize),
+    /// 按用户选择的编码重新读取编码检测不可靠的文件
+    ReloadWithEncoding(&'static encoding_rs::Encoding),
 }
 
 #[derive(Debug, Clone)]
@@ -62,20 +151,55 @@ pub enum DownloaderAction {
     SetTitle(String),
     SetArtist(String),
     FillFromSmtc,
+    /// 搜索输入发生变化，在防抖结束后自动搜索
+    ScheduleAutoSearch,
     PerformSearch,
     SearchCompleted(AppResult<Vec<SearchResult>>),
     SelectResultForPreview(SearchResult),
     PreviewDownloadCompleted(AppResult<FullLyricsResult>),
+    SetWordTimedOnly(bool),
+    /// 开始或停止试听当前预览的搜索结果
+    ToggleAudioPreview,
+    AudioPreviewLoaded(AppResult<Vec<u8>>),
     ApplyAndClose,
+    /// 应用当前预览的歌词，但停留在下载器中以便继续尝试其他结果
+    ApplyAndKeepOpen,
     Close,
+    /// 选择批量下载的输出目录
+    SelectBatchDownloadDir,
+    /// 按批量下载窗口中的列表依次搜索并保存最佳匹配
+    StartBatchDownload,
+    CancelBatchDownload,
+    /// 批量下载列表中第 n 首歌的状态更新
+    BatchDownloadProgress(usize, BatchDownloadStatus),
+    BatchDownloadFinished,
+}
+
+/// 本地缓存管理窗口中的操作，均以缓存文件名标识条目。
+#[derive(Debug, Clone)]
+pub enum LocalCacheAction {
+    /// 读取缓存文件用于预览
+    Preview(String),
+    /// 将缓存的歌词作为新文件打开到编辑器
+    LoadIntoEditor(String),
+    /// 删除缓存文件及其索引条目
+    Delete(String),
 }
 
 #[derive(Debug, Clone)]
 pub enum PlayerAction {
     /// 让 smtc-suite 选择一个新的媒体会话。
     SelectSmtcSession(String),
+    /// 请求 smtc-suite 重新枚举可用的媒体会话。
+    RefreshSmtcSessions,
+    /// 让播放器跳转到歌词中的指定时间（毫秒，会扣除时间轴偏移量）
+    SeekToLyricTime(u64),
     /// 设置时间轴偏移量
     SetSmtcTimeOffset(i64),
+    /// 将当前偏移量保存到 A/B 对比槽位（0 为 A，1 为 B）
+    StoreOffsetSlot(usize),
+    /// 应用 A/B 对比槽位中保存的偏移量
+    ApplyOffsetSlot(usize),
     /// 保存当前歌词到本地缓存。
     SaveToLocalCache,
     /// 更新封面数据。
@@ -93,6 +217,16 @@ pub enum PanelType {
     Romanization,
     AmllConnector,
     Warnings,
+    LineEditor,
+    Timeline,
+    KaraokePreview,
+    FormatOptions,
+    DocumentList,
+    Diff,
+    LocalCache,
+    LrcMerge,
+    LyricImage,
+    BatchDownload,
 }
 
 #[derive(Clone)]
@@ -100,11 +234,16 @@ pub enum UIAction {
     SetPanelVisibility(PanelType, bool),
     SetView(AppView),
     SetWrapText(bool),
+    SetShowLineNumbers(bool),
+    /// 在逐行编辑窗口中定位到指定行
+    LocateLine(usize),
     ShowPanel(PanelType),
     HidePanel(PanelType),
     ClearLogs,
     StopOtherSearches,
     ShowToast(Box<Toast>),
+    /// 确认在有未保存更改的情况下退出
+    ConfirmExit,
 }
 
 impl fmt::Debug for UIAction {
@@ -117,11 +256,16 @@ impl fmt::Debug for UIAction {
                 .finish(),
             Self::SetView(view) => f.debug_tuple("SetView").field(view).finish(),
             Self::SetWrapText(wrap) => f.debug_tuple("SetWrapText").field(wrap).finish(),
+            Self::SetShowLineNumbers(show) => {
+                f.debug_tuple("SetShowLineNumbers").field(show).finish()
+            }
+            Self::LocateLine(index) => f.debug_tuple("LocateLine").field(index).finish(),
             Self::ShowPanel(panel) => f.debug_tuple("ShowPanel").field(panel).finish(),
             Self::HidePanel(panel) => f.debug_tuple("HidePanel").field(panel).finish(),
             Self::ClearLogs => write!(f, "ClearLogs"),
             Self::StopOtherSearches => write!(f, "StopOtherSearches"),
             Self::ShowToast(_) => f.debug_tuple("ShowToast").field(&"<Box<Toast>>").finish(),
+            Self::ConfirmExit => write!(f, "ConfirmExit"),
         }
     }
 }
@@ -131,6 +275,38 @@ pub enum SettingsAction {
     Save(Box<AppSettings>),
     Cancel,
     Reset,
+    /// 将当前设置导出为可分享的配置文件
+    Export,
+    /// 选择一个配置文件，解析后等待确认
+    Import,
+    /// 确认导入，将合并后的设置填入设置窗口
+    ConfirmImport,
+    CancelImport,
+    /// 收藏或取消收藏一个简繁转换配置，收藏的配置显示为工具栏上的快捷按钮
+    ToggleChineseConversionFavorite(ChineseConversionConfig),
+}
+
+/// 复制单行歌词时的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCopyFormat {
+    /// 只复制主歌词文本
+    Text,
+    /// 只复制开始和结束时间
+    Timestamp,
+    /// 复制为带时间戳的 LRC 行
+    LrcLine,
+}
+
+impl LineCopyFormat {
+    pub const ALL: [Self; 3] = [Self::Text, Self::Timestamp, Self::LrcLine];
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Text => "复制文本",
+            Self::Timestamp => "复制时间戳",
+            Self::LrcLine => "复制为 LRC 行",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -142,11 +318,40 @@ pub enum AmllConnectorAction {
     ReloadProviders,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ProcessorType {
     MetadataStripper,
     SyllableSmoother,
     AgentRecognizer,
+    RegexReplace,
+    LineWrapper,
+    PunctuationNormalizer,
+    SyllableOverlapFixer,
+}
+
+impl ProcessorType {
+    /// 所有后处理器，顺序即批量转换中依次应用的顺序。
+    pub const ALL: [Self; 7] = [
+        Self::MetadataStripper,
+        Self::RegexReplace,
+        Self::PunctuationNormalizer,
+        Self::LineWrapper,
+        Self::SyllableOverlapFixer,
+        Self::SyllableSmoother,
+        Self::AgentRecognizer,
+    ];
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::MetadataStripper => "清理元数据行",
+            Self::SyllableSmoother => "音节平滑",
+            Self::AgentRecognizer => "演唱者识别",
+            Self::RegexReplace => "正则替换",
+            Self::LineWrapper => "智能换行",
+            Self::PunctuationNormalizer => "标点规范化",
+            Self::SyllableOverlapFixer => "修复音节重叠",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -154,9 +359,25 @@ pub enum BatchConverterAction {
     SelectInputDir,
     SelectOutputDir,
     SetTargetFormat(LyricFormat),
+    SetExtensionWhitelist(String),
+    /// 启用或停用按文件名自动配对翻译和罗马音文件
+    SetAutoPairEnabled(bool),
+    /// 以逗号分隔的翻译文件名后缀
+    SetTranslationSuffixes(String),
+    /// 以逗号分隔的罗马音文件名后缀
+    SetRomanizationSuffixes(String),
     ScanTasks,
     StartConversion,
+    /// 请求在当前任务完成后暂停
+    PauseConversion,
+    /// 将失败的任务重置为待处理并重新转换，其余任务保持不变
+    RetryFailed,
+    /// 切换试运行模式
+    SetDryRun(bool),
+    /// 启用或停用转换中自动应用的后处理器
+    SetPostprocessorEnabled(ProcessorType, bool),
     TaskUpdate(BatchTaskUpdate),
     ConversionCompleted,
+    ConversionPaused,
     Reset,
 }
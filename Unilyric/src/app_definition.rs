@@ -1,18 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::sync::{
     Arc, Mutex as StdMutex,
+    atomic::AtomicBool,
     mpsc::{Receiver as StdReceiver, Sender as StdSender, channel as std_channel},
 };
 
 use egui_toast::Toasts;
 use lyrics_helper_core::{
-    BatchConversionConfig, BatchFileId, BatchLoadedFile, CanonicalMetadataKey,
-    FullConversionResult, LyricFormat, MetadataStore, ParsedSourceData,
+    BatchConfigId, BatchConversionConfig, BatchEntryStatus, BatchFileId, BatchLoadedFile,
+    CanonicalMetadataKey, FilePairingOptions, FullConversionResult, LyricFormat, LyricLine,
+    MetadataStore, ParsedSourceData,
 };
 use lyrics_helper_core::{SearchResult, model::track::FullLyricsResult};
 use lyrics_helper_rs::LyricsHelperError;
+use lyrics_helper_rs::converter::processors::lyric_linter::LintIssue;
 use rand::Rng;
 use smtc_suite::{MediaCommand, NowPlayingInfo, SmtcSessionInfo, TextConversionMode};
 use tokio::{
@@ -23,13 +26,30 @@ use tokio::{
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-use crate::amll_connector::types::UiUpdate;
+use crate::action_script::ActionScriptState;
+use crate::amll_connector::types::{ActorSettings, UiUpdate};
 use crate::app_ui::SettingsCategory;
-use crate::types::{EditableMetadataEntry, ProviderState};
+use crate::audio_preview::AudioPreviewPlayer;
+use crate::batch_download::BatchDownloadState;
+use crate::lyric_image::LyricImageOptions;
+use crate::session::WorkspaceSession;
+use crate::settings_search::SettingsSearch;
+use crate::text_diff::DiffRow;
+use crate::text_search::FindBar;
+use crate::types::{
+    DEFAULT_TRANSLATION_LANGUAGE, DiffSource, EditableMetadataEntry, EncodingPrompt,
+    LrcMergeWizard, LyricWarning, MetadataSuggestion, OpenDocument, PreviewPlayback, ProviderState,
+    ResultPreviewSummary, SearchResultOrigin, SmoothingHistory, SmtcDebugInfo, TimelineDrag,
+    TranslationTrack, WarningLevel, WordTimingAvailability,
+};
+use crate::waveform::Waveform;
 use crate::{
-    amll_connector::{AMLLConnectorConfig, ConnectorCommand, WebsocketStatus},
-    app_actions::UserAction,
-    app_settings::AppSettings,
+    amll_connector::{
+        AMLLConnectorConfig, ConnectionErrorKind, ConnectionEventCause, ConnectionTimelineEntry,
+        ConnectorCommand, LyricDeliveryStatus, WebsocketStatus,
+    },
+    app_actions::{ProcessorType, UserAction},
+    app_settings::{AppSettings, FormatOptionsSettings, SettingsImport},
     types::{AutoFetchResult, AutoSearchStatus, LocalLyricCacheEntry, LogEntry},
     utils,
 };
@@ -72,6 +92,106 @@ pub(super) struct DownloaderState {
     pub(super) selected_result_for_preview: Option<SearchResult>,
     pub(super) preview_state: PreviewState,
     pub(super) selected_full_lyrics: Option<FullLyricsResult>,
+    pub(super) word_timed_only: bool,
+    /// 预览过的搜索结果的歌词概况，键为 (提供商名称, 提供商 ID)
+    pub(super) previewed_summaries: HashMap<(String, String), ResultPreviewSummary>,
+    /// 输入防抖结束后自动搜索的时间点
+    pub(super) auto_search_deadline: Option<std::time::Instant>,
+    /// 最近一次“应用但不关闭”的搜索结果，键为 (提供商名称, 提供商 ID)
+    pub(super) applied_result: Option<(String, String)>,
+    /// 正在下载试听音频的搜索结果，键为 (提供商名称, 提供商 ID)
+    pub(super) audio_preview_loading: Option<(String, String)>,
+    pub(super) audio_preview: AudioPreviewPlayer,
+    /// SMTC 自动搜索最近一次找到的候选结果
+    pub(super) auto_search_candidates: Vec<SearchResult>,
+    /// 上述候选结果对应的 (歌曲名, 艺术家)
+    pub(super) auto_search_query: Option<(String, String)>,
+    /// 最近一次手动搜索使用的 (歌曲名, 艺术家)
+    pub(super) manual_search_query: Option<(String, String)>,
+}
+
+/// 两次搜索的 (歌曲名, 艺术家) 是否指向同一首歌，忽略大小写和首尾空白。
+fn is_same_song_query(a: &(String, String), b: &(String, String)) -> bool {
+    let normalize = |s: &str| s.trim().to_lowercase();
+    normalize(&a.0) == normalize(&b.0) && normalize(&a.1) == normalize(&b.1)
+}
+
+impl DownloaderState {
+    pub(super) fn new(settings: &AppSettings) -> Self {
+        Self {
+            word_timed_only: settings.downloader_word_timed_only,
+            ..Default::default()
+        }
+    }
+
+    /// 判断搜索结果是否具备逐字时间轴，优先使用预览后得到的确切结果。
+    pub(super) fn word_timing_of(&self, result: &SearchResult) -> WordTimingAvailability {
+        match self.preview_summary_of(result) {
+            Some(summary) if summary.is_word_timed => WordTimingAvailability::WordTimed,
+            Some(_) => WordTimingAvailability::LineTimed,
+            None => WordTimingAvailability::from_provider_name(&result.provider_name),
+        }
+    }
+
+    /// 该结果是否因“仅显示逐字歌词”而被隐藏。
+    pub(super) fn is_result_hidden(&self, result: &SearchResult) -> bool {
+        self.word_timed_only && self.word_timing_of(result) == WordTimingAvailability::LineTimed
+    }
+
+    /// 返回结果列表中要显示的条目及其来源。
+    ///
+    /// 手动搜索的结果在前，之后追加自动搜索已找到但手动搜索没有的结果。
+    /// 自动搜索的结果只在尚未手动搜索，或手动搜索的是同一首歌时才会合并进来。
+    /// 正在搜索或搜索失败时返回空列表。
+    pub(super) fn displayed_results(&self) -> Vec<(&SearchResult, SearchResultOrigin)> {
+        let manual_results: &[SearchResult] = match &self.search_state {
+            SearchState::Success(results) => results,
+            SearchState::Idle => &[],
+            SearchState::Searching | SearchState::Error(_) => return Vec::new(),
+        };
+        let merges_auto_results = match &self.search_state {
+            SearchState::Success(_) => self
+                .manual_search_query
+                .as_ref()
+                .zip(self.auto_search_query.as_ref())
+                .is_some_and(|(manual, auto)| is_same_song_query(manual, auto)),
+            _ => true,
+        };
+        let auto_candidates: &[SearchResult] = if merges_auto_results {
+            &self.auto_search_candidates
+        } else {
+            &[]
+        };
+        let is_same = |a: &SearchResult, b: &SearchResult| {
+            a.provider_name == b.provider_name && a.provider_id == b.provider_id
+        };
+
+        let mut displayed: Vec<_> = manual_results
+            .iter()
+            .map(|result| {
+                let origin = if auto_candidates.iter().any(|auto| is_same(auto, result)) {
+                    SearchResultOrigin::Both
+                } else {
+                    SearchResultOrigin::Manual
+                };
+                (result, origin)
+            })
+            .collect();
+        displayed.extend(
+            auto_candidates
+                .iter()
+                .filter(|auto| !manual_results.iter().any(|result| is_same(auto, result)))
+                .map(|auto| (auto, SearchResultOrigin::Auto)),
+        );
+        displayed
+    }
+
+    /// 返回搜索结果预览后得到的歌词概况，未预览过时为 `None`。
+    pub(super) fn preview_summary_of(&self, result: &SearchResult) -> Option<ResultPreviewSummary> {
+        self.previewed_summaries
+            .get(&(result.provider_name.clone(), result.provider_id.clone()))
+            .copied()
+    }
 }
 
 pub(super) struct UiState {
@@ -79,16 +199,89 @@ pub(super) struct UiState {
     pub(super) new_trigger_log_exists: bool,
     pub(super) show_romanization_lrc_panel: bool,
     pub(super) show_translation_lrc_panel: bool,
+    /// 翻译面板中待新增的翻译语言
+    pub(super) new_translation_language: String,
+    /// 输入面板的查找栏
+    pub(super) input_find: FindBar,
+    /// 输出面板的查找栏
+    pub(super) output_find: FindBar,
     pub(super) wrap_text: bool,
+    pub(super) show_line_numbers: bool,
     pub(super) show_settings_window: bool,
     pub(super) show_amll_connector_sidebar: bool,
     pub(super) show_metadata_panel: bool,
     pub(super) show_warnings_panel: bool,
+    pub(super) show_clear_unpinned_metadata_confirm: bool,
+    /// “添加新元数据”菜单中按使用次数排好序的键，使用次数变化时更新
+    pub(super) metadata_keys_by_usage: Vec<CanonicalMetadataKey>,
+    pub(super) metadata_suggestions: Vec<MetadataSuggestion>,
+    pub(super) show_line_editor_window: bool,
+    pub(super) line_editor_selected_line: Option<usize>,
+    /// 逐行编辑中待插入间奏的起止时间（毫秒）
+    pub(super) line_editor_interlude_range: (u64, u64),
+    /// 整体时间缩放的比例
+    pub(super) line_editor_time_scale: f64,
+    /// 裁剪首尾静音后第一行的开始时间（毫秒）
+    pub(super) line_editor_trim_start_ms: u64,
+    /// 裁剪首尾静音时是否同时裁掉结尾的静音
+    pub(super) line_editor_trim_tail: bool,
+    pub(super) action_script: ActionScriptState,
+    pub(super) show_timeline_window: bool,
+    /// 时间轴的缩放（每秒对应的像素数）
+    pub(super) timeline_zoom: f32,
+    /// 拖动时是否吸附到相邻的边界
+    pub(super) timeline_snap: bool,
+    pub(super) timeline_drag: Option<TimelineDrag>,
+    /// 时间轴中作为对齐参考的音频波形
+    pub(super) timeline_waveform: Option<Arc<Waveform>>,
+    pub(super) timeline_waveform_loading: bool,
+    /// 吸附时是否包括波形中检测到的起音点
+    pub(super) timeline_snap_to_onsets: bool,
+    pub(super) show_karaoke_preview_window: bool,
+    pub(super) karaoke_preview: PreviewPlayback,
+    pub(super) show_format_options_window: bool,
+    pub(super) temp_format_options: FormatOptionsSettings,
+    pub(super) pending_session_restore: Option<WorkspaceSession>,
+    pub(super) show_document_list_panel: bool,
+    pub(super) show_diff_window: bool,
+    pub(super) diff_source: DiffSource,
+    /// 为 `true` 时使用行内视图，否则使用并排视图
+    pub(super) diff_inline: bool,
+    pub(super) diff_only_changes: bool,
+    /// 上次计算的 diff 结果及其对应的对比来源和文本修订号，避免每帧重复计算
+    pub(super) diff_cache: Option<(DiffSource, u64, Vec<DiffRow>)>,
+    /// 警告面板中隐藏的级别
+    pub(super) hidden_warning_levels: HashSet<WarningLevel>,
+    pub(super) show_local_cache_window: bool,
+    pub(super) local_cache_search: String,
+    /// 本地缓存窗口中正在预览的条目（文件名，内容）
+    pub(super) local_cache_preview: Option<(String, String)>,
+    pub(super) show_lrc_merge_window: bool,
+    pub(super) show_lyric_image_window: bool,
+    /// 导出歌词图片窗口中正在编辑的选项，导出时写回设置
+    pub(super) lyric_image_options: LyricImageOptions,
+    /// 是否正在后台渲染并保存歌词图片
+    pub(super) lyric_image_exporting: bool,
+    /// 最近一次生成的歌词统计报告，为 `Some` 时显示报告窗口
+    pub(super) stats_report: Option<String>,
+    pub(super) lrc_merge: LrcMergeWizard,
+    pub(super) show_batch_download_window: bool,
+    pub(super) batch_download: BatchDownloadState,
+    /// 等待用户确认是否丢弃未保存更改的待关闭文件
+    pub(super) pending_close_document: Option<usize>,
+    pub(super) show_exit_unsaved_confirm: bool,
+    /// 已解析、等待用户确认的配置导入
+    pub(super) pending_settings_import: Option<SettingsImport>,
+    /// 编码检测不可靠、等待用户选择编码重新读取的文件
+    pub(super) encoding_prompt: Option<EncodingPrompt>,
+    /// 用户已确认丢弃未保存的更改并退出
+    pub(super) exit_confirmed: bool,
     pub(super) log_display_buffer: Vec<LogEntry>,
     pub(super) temp_edit_settings: AppSettings,
     pub(super) toasts: Toasts,
     pub(super) available_system_fonts: Vec<String>,
     pub(super) current_settings_category: SettingsCategory,
+    pub(super) settings_search: SettingsSearch,
     pub(super) current_view: AppView,
 }
 
@@ -106,13 +299,67 @@ impl UiState {
             new_trigger_log_exists: false,
             show_romanization_lrc_panel: false,
             show_translation_lrc_panel: false,
+            new_translation_language: String::new(),
+            input_find: FindBar::default(),
+            output_find: FindBar::default(),
             wrap_text: true,
+            show_line_numbers: false,
             show_settings_window: false,
             show_metadata_panel: false,
             show_warnings_panel: false,
+            show_clear_unpinned_metadata_confirm: false,
+            metadata_keys_by_usage: settings.metadata_keys_by_usage(),
+            metadata_suggestions: Vec::new(),
+            show_line_editor_window: false,
+            line_editor_selected_line: None,
+            line_editor_interlude_range: (0, 0),
+            line_editor_time_scale: 1.0,
+            line_editor_trim_start_ms: 0,
+            line_editor_trim_tail: true,
+            action_script: ActionScriptState::default(),
+            show_timeline_window: false,
+            timeline_zoom: 100.0,
+            timeline_snap: true,
+            timeline_drag: None,
+            timeline_waveform: None,
+            timeline_waveform_loading: false,
+            timeline_snap_to_onsets: true,
+            show_karaoke_preview_window: false,
+            karaoke_preview: PreviewPlayback::default(),
+            show_format_options_window: false,
+            temp_format_options: settings.format_options.clone(),
+            pending_session_restore: None,
+            show_document_list_panel: true,
+            show_diff_window: false,
+            diff_source: DiffSource::default(),
+            diff_inline: false,
+            diff_only_changes: false,
+            diff_cache: None,
+            hidden_warning_levels: HashSet::new(),
+            show_local_cache_window: false,
+            local_cache_search: String::new(),
+            local_cache_preview: None,
+            show_lrc_merge_window: false,
+            show_lyric_image_window: false,
+            lyric_image_options: settings.lyric_image.clone(),
+            lyric_image_exporting: false,
+            stats_report: None,
+            lrc_merge: LrcMergeWizard::default(),
+            show_batch_download_window: false,
+            batch_download: BatchDownloadState {
+                output_dir: settings.batch_download_output_directory.clone(),
+                target_format: settings.batch_download_target_format,
+                ..Default::default()
+            },
+            pending_close_document: None,
+            show_exit_unsaved_confirm: false,
+            pending_settings_import: None,
+            encoding_prompt: None,
+            exit_confirmed: false,
             log_display_buffer: Vec::with_capacity(200),
             available_system_fonts: Vec::new(),
             current_settings_category: SettingsCategory::default(),
+            settings_search: SettingsSearch::default(),
             current_view: AppView::default(),
         }
     }
@@ -121,8 +368,21 @@ impl UiState {
 pub(super) struct LyricState {
     pub(super) input_text: String,
     pub(super) output_text: String,
+    /// 当前选中的翻译轨道的 LRC 文本
     pub(super) display_translation_lrc_output: String,
     pub(super) display_romanization_lrc_output: String,
+    /// 按语言区分的全部翻译轨道。选中轨道的内容只保存在 `display_translation_lrc_output` 中，
+    /// 它在这里的 `lrc` 始终为空；需要完整内容时使用 `all_translation_tracks`
+    pub(super) translation_tracks: Vec<TranslationTrack>,
+    /// 当前选中的翻译轨道在 `translation_tracks` 中的索引
+    pub(super) active_translation: usize,
+    /// 校对备注，以行索引为键。修改时间轴不影响对应关系，行数变化时由 `sync_line_notes`
+    /// 移到对应行的新索引上。只保存在会话中，不会被导出
+    pub(super) line_notes: BTreeMap<usize, String>,
+    /// 上次同步备注时各行的开始时间和文本
+    pub(super) line_note_anchors: Vec<crate::types::LineNoteAnchor>,
+    /// 旧版本会话中以开始时间为键的备注，等到歌词解析完成后再换算为行索引
+    pub(super) legacy_line_notes: BTreeMap<u64, String>,
     pub(super) parsed_lyric_data: Option<ParsedSourceData>,
     pub(super) loaded_translation_lrc: Option<Vec<crate::types::DisplayLrcLine>>,
     pub(super) loaded_romanization_lrc: Option<Vec<crate::types::DisplayLrcLine>>,
@@ -135,7 +395,29 @@ pub(super) struct LyricState {
     pub(super) last_saved_file_path: Option<std::path::PathBuf>,
     pub(super) conversion_in_progress: bool,
     pub(super) conversion_result_rx: Option<ConversionResultRx>,
-    pub(super) current_warnings: Vec<String>,
+    /// 当前转换任务的开始时间，用于显示已用时间
+    pub(super) conversion_started_at: Option<std::time::Instant>,
+    /// 转换进行中时输入发生了变化，当前任务完成后需要重新转换
+    pub(super) reconvert_after_current: bool,
+    /// 主输入停止编辑后触发转换的时间点
+    pub(super) input_convert_deadline: Option<std::time::Instant>,
+    /// 最近一次转换所用输入文本的哈希，内容未变化时跳过转换
+    pub(super) converted_input_hash: Option<u64>,
+    pub(super) current_warnings: Vec<LyricWarning>,
+    /// 简繁转换前的歌词行快照，用于在转换完成后统计变化
+    pub(super) chinese_conversion_baseline: Option<Vec<LyricLine>>,
+    /// 歌词检查的结果。为 `None` 表示尚未运行检查
+    pub(super) lint_issues: Option<Vec<LintIssue>>,
+    /// 最近一次简繁转换或后处理之前的输出文本，用于对比视图
+    pub(super) diff_baseline: Option<String>,
+    /// 输入、输出或对比基准文本的修订号，每次修改这些文本后递增
+    pub(super) text_revision: u64,
+    /// 最近一次音节平滑的逐次迭代缓存
+    pub(super) smoothing_history: Option<SmoothingHistory>,
+    /// 已打开的文件列表
+    pub(super) open_documents: Vec<OpenDocument>,
+    /// 当前正在编辑的文件在 `open_documents` 中的索引
+    pub(super) active_document: Option<usize>,
 }
 
 pub(super) struct LyricsHelperState {
@@ -174,6 +456,11 @@ impl LyricState {
             output_text: String::new(),
             display_translation_lrc_output: String::new(),
             display_romanization_lrc_output: String::new(),
+            translation_tracks: vec![TranslationTrack::new(DEFAULT_TRANSLATION_LANGUAGE)],
+            active_translation: 0,
+            line_notes: BTreeMap::new(),
+            line_note_anchors: Vec::new(),
+            legacy_line_notes: BTreeMap::new(),
             parsed_lyric_data: None,
             loaded_translation_lrc: None,
             loaded_romanization_lrc: None,
@@ -194,13 +481,149 @@ impl LyricState {
                 LyricFormat::Spl,
                 LyricFormat::Lqe,
                 LyricFormat::Krc,
+                LyricFormat::Vtt,
+                LyricFormat::Musixmatch,
+                LyricFormat::Lrcx,
             ],
             last_opened_file_path: None,
             last_saved_file_path: None,
             conversion_in_progress: false,
             conversion_result_rx: None,
+            conversion_started_at: None,
+            reconvert_after_current: false,
+            input_convert_deadline: None,
+            converted_input_hash: None,
             current_warnings: Vec::new(),
+            chinese_conversion_baseline: None,
+            lint_issues: None,
+            diff_baseline: None,
+            text_revision: 0,
+            smoothing_history: None,
+            open_documents: Vec::new(),
+            active_document: None,
+        }
+    }
+
+    /// 标记输入、输出或对比基准文本已被修改，使依赖它们的缓存失效。
+    pub(super) fn mark_texts_changed(&mut self) {
+        self.text_revision = self.text_revision.wrapping_add(1);
+    }
+
+    /// 是否有可供简繁转换的歌词：输入框不为空，或已有解析出的歌词行。
+    pub(super) fn has_lyrics_to_convert(&self) -> bool {
+        !self.input_text.is_empty()
+            || self
+                .parsed_lyric_data
+                .as_ref()
+                .is_some_and(|data| !data.lines.is_empty())
+    }
+
+    /// 当前编辑内容是否属于正在编辑的文件。
+    ///
+    /// 加载下载的歌词等操作会替换编辑内容，但不会改变已打开文件列表，此时返回 `false`。
+    pub(super) fn editing_active_document(&self) -> Option<usize> {
+        let index = self.active_document?;
+        let document = self.open_documents.get(index)?;
+        (self.last_opened_file_path.as_ref() == Some(&document.path)).then_some(index)
+    }
+
+    /// 将当前编辑内容写回正在编辑的文件，以便切换回来时恢复。
+    pub(super) fn sync_active_document(&mut self) {
+        let Some(index) = self.editing_active_document() else {
+            return;
+        };
+        let document = &mut self.open_documents[index];
+        document.input_text.clone_from(&self.input_text);
+        document.source_format = self.source_format;
+        document.translation_tracks = self.all_translation_tracks();
+        document.line_notes.clone_from(&self.line_notes);
+        document
+            .romanization_lrc
+            .clone_from(&self.display_romanization_lrc_output);
+    }
+
+    /// 可以生成的格式。Musixmatch JSON 目前仅支持导入，不能作为转换或保存的目标。
+    pub(super) fn target_formats(&self) -> Vec<LyricFormat> {
+        self.available_formats
+            .iter()
+            .copied()
+            .filter(|format| *format != LyricFormat::Musixmatch)
+            .collect()
+    }
+
+    /// 返回包含选中轨道最新内容的全部翻译轨道。
+    pub(super) fn all_translation_tracks(&self) -> Vec<TranslationTrack> {
+        let mut tracks = self.translation_tracks.clone();
+        if let Some(track) = tracks.get_mut(self.active_translation) {
+            track.lrc.clone_from(&self.display_translation_lrc_output);
+        }
+        tracks
+    }
+
+    /// 替换全部翻译轨道。
+    ///
+    /// 如果之前选中的语言仍然存在则继续选中它，否则选中第一条轨道。
+    /// 列表为空时保留一条默认语言的空轨道，供直接粘贴翻译。
+    pub(super) fn set_translation_tracks(&mut self, mut tracks: Vec<TranslationTrack>) {
+        if tracks.is_empty() {
+            tracks.push(TranslationTrack::new(DEFAULT_TRANSLATION_LANGUAGE));
+        }
+        let active_language = self
+            .translation_tracks
+            .get(self.active_translation)
+            .map(|track| track.language.clone());
+        self.active_translation = active_language
+            .and_then(|language| tracks.iter().position(|track| track.language == language))
+            .unwrap_or(0);
+        self.display_translation_lrc_output =
+            std::mem::take(&mut tracks[self.active_translation].lrc);
+        self.translation_tracks = tracks;
+    }
+
+    /// 切换到指定的翻译轨道。
+    pub(super) fn select_translation(&mut self, index: usize) {
+        if index >= self.translation_tracks.len() || index == self.active_translation {
+            return;
         }
+        self.translation_tracks[self.active_translation].lrc =
+            std::mem::take(&mut self.display_translation_lrc_output);
+        self.active_translation = index;
+        self.display_translation_lrc_output =
+            std::mem::take(&mut self.translation_tracks[index].lrc);
+    }
+
+    /// 选中的翻译轨道的语言标签。
+    pub(super) fn active_translation_language(&self) -> &str {
+        self.translation_tracks
+            .get(self.active_translation)
+            .map_or(DEFAULT_TRANSLATION_LANGUAGE, |track| {
+                track.language.as_str()
+            })
+    }
+
+    /// 将正在编辑的文件标记为已保存。
+    pub(super) fn mark_active_document_saved(&mut self) {
+        if let Some(index) = self.editing_active_document() {
+            self.open_documents[index]
+                .saved_input_text
+                .clone_from(&self.input_text);
+        }
+    }
+
+    /// 指定的已打开文件是否有未保存的更改。正在编辑的文件以当前编辑内容为准。
+    pub(super) fn is_document_modified(&self, index: usize) -> bool {
+        let Some(document) = self.open_documents.get(index) else {
+            return false;
+        };
+        if self.editing_active_document() == Some(index) {
+            self.input_text != document.saved_input_text
+        } else {
+            document.is_modified()
+        }
+    }
+
+    pub(super) fn has_unsaved_documents(&self) -> bool {
+        (0..self.open_documents.len()).any(|index| self.is_document_modified(index))
     }
 }
 
@@ -209,8 +632,13 @@ pub(super) struct PlayerState {
     pub(super) current_now_playing: NowPlayingInfo,
     pub(super) available_sessions: Vec<SmtcSessionInfo>,
     pub(super) smtc_time_offset_ms: i64,
+    /// 用于 A/B 快速对比的两个偏移量槽位
+    pub(super) offset_slots: [Option<i64>; 2],
+    /// 最近一次应用的槽位
+    pub(super) active_offset_slot: Option<usize>,
     pub(super) last_requested_session_id: Option<String>,
     pub(super) is_first_song_processed: bool,
+    pub(super) smtc_debug: SmtcDebugInfo,
 }
 
 impl PlayerState {
@@ -220,18 +648,30 @@ impl PlayerState {
             current_now_playing: NowPlayingInfo::default(),
             available_sessions: Vec::new(),
             smtc_time_offset_ms: settings.smtc_time_offset_ms,
+            offset_slots: [None; 2],
+            active_offset_slot: None,
             last_requested_session_id: None,
             is_first_song_processed: false,
+            smtc_debug: SmtcDebugInfo::default(),
         }
     }
 }
 
+/// 连接事件时间线保留的最大条数。
+const CONNECTION_TIMELINE_CAPACITY: usize = 200;
+
 pub(super) struct AmllConnectorState {
     pub command_tx: Option<TokioSender<ConnectorCommand>>,
     pub actor_handle: Option<JoinHandle<()>>,
     pub status: Arc<StdMutex<WebsocketStatus>>,
     pub config: Arc<StdMutex<AMLLConnectorConfig>>,
     pub update_rx: std::sync::mpsc::Receiver<UiUpdate>,
+    /// 连接状态变化的历史记录
+    pub timeline: VecDeque<ConnectionTimelineEntry>,
+    /// 用户刚发起了连接或断开，下一次状态变化归因于用户操作
+    pub user_action_pending: bool,
+    /// 最近一次发送歌词的送达状态及其更新时间
+    pub lyric_delivery: Option<(LyricDeliveryStatus, chrono::DateTime<chrono::Local>)>,
 }
 
 impl AmllConnectorState {
@@ -247,6 +687,9 @@ impl AmllConnectorState {
             status: Arc::new(StdMutex::new(WebsocketStatus::default())),
             config: Arc::new(StdMutex::new(config)),
             update_rx,
+            timeline: VecDeque::new(),
+            user_action_pending: false,
+            lyric_delivery: None,
         }
     }
     fn new_disabled() -> Self {
@@ -260,8 +703,44 @@ impl AmllConnectorState {
                 ..Default::default()
             })),
             update_rx: rx,
+            timeline: VecDeque::new(),
+            user_action_pending: false,
+            lyric_delivery: None,
         }
     }
+
+    /// 将一次状态变化记入时间线，并推断其原因。
+    pub fn record_status_change(&mut self, status: &WebsocketStatus) {
+        let previous_cause = self.timeline.back().map(|entry| entry.cause);
+        let cause = match status {
+            WebsocketStatus::Error {
+                kind: ConnectionErrorKind::HeartbeatTimeout,
+                ..
+            } => ConnectionEventCause::HeartbeatTimeout,
+            WebsocketStatus::Error {
+                kind: ConnectionErrorKind::Network,
+                ..
+            } => ConnectionEventCause::NetworkError,
+            _ if self.user_action_pending => ConnectionEventCause::UserAction,
+            WebsocketStatus::Connecting => ConnectionEventCause::AutoReconnect,
+            // 连接成功或断开沿用上一条记录的原因，例如重连成功仍记为自动重连
+            WebsocketStatus::Connected | WebsocketStatus::Disconnected => {
+                previous_cause.unwrap_or(ConnectionEventCause::NetworkError)
+            }
+        };
+        if !matches!(status, WebsocketStatus::Connecting) {
+            self.user_action_pending = false;
+        }
+
+        if self.timeline.len() >= CONNECTION_TIMELINE_CAPACITY {
+            self.timeline.pop_front();
+        }
+        self.timeline.push_back(ConnectionTimelineEntry {
+            time: chrono::Local::now(),
+            status: status.clone(),
+            cause,
+        });
+    }
 }
 
 pub(super) struct AutoFetchState {
@@ -276,10 +755,12 @@ pub(super) struct AutoFetchState {
     pub(super) kugou_status: Arc<StdMutex<AutoSearchStatus>>,
     pub(super) netease_status: Arc<StdMutex<AutoSearchStatus>>,
     pub(super) amll_db_status: Arc<StdMutex<AutoSearchStatus>>,
+    pub(super) custom_http_status: Arc<StdMutex<AutoSearchStatus>>,
     pub(super) last_qq_result: Arc<StdMutex<Option<FullLyricsResult>>>,
     pub(super) last_kugou_result: Arc<StdMutex<Option<FullLyricsResult>>>,
     pub(super) last_netease_result: Arc<StdMutex<Option<FullLyricsResult>>>,
     pub(super) last_amll_db_result: Arc<StdMutex<Option<FullLyricsResult>>>,
+    pub(super) last_custom_http_result: Arc<StdMutex<Option<FullLyricsResult>>>,
 }
 
 impl AutoFetchState {
@@ -295,10 +776,12 @@ impl AutoFetchState {
             kugou_status: Arc::new(StdMutex::new(AutoSearchStatus::default())),
             netease_status: Arc::new(StdMutex::new(AutoSearchStatus::default())),
             amll_db_status: Arc::new(StdMutex::new(AutoSearchStatus::default())),
+            custom_http_status: Arc::new(StdMutex::new(AutoSearchStatus::default())),
             last_qq_result: Arc::new(StdMutex::new(None)),
             last_kugou_result: Arc::new(StdMutex::new(None)),
             last_netease_result: Arc::new(StdMutex::new(None)),
             last_amll_db_result: Arc::new(StdMutex::new(None)),
+            last_custom_http_result: Arc::new(StdMutex::new(None)),
         }
     }
 }
@@ -337,6 +820,10 @@ pub(super) struct UniLyricApp {
     // --- 标记 ---
     pub(super) shutdown_initiated: bool,
     pub(super) auto_fetch_trigger_time: Option<std::time::Instant>,
+    pub(super) last_session_autosave: std::time::Instant,
+    pub(super) last_saved_session: Option<WorkspaceSession>,
+    /// 元数据键的使用次数有尚未保存的变化，退出时保存
+    pub(super) metadata_key_usage_dirty: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -347,17 +834,32 @@ pub(super) struct UiMetadataManager {
 
 impl UiMetadataManager {
     pub fn add_new_ui_entry(&mut self, key: CanonicalMetadataKey) {
+        self.add_ui_entry_with_value(key, String::new());
+    }
+
+    pub fn add_ui_entry_with_value(&mut self, key: CanonicalMetadataKey, value: String) {
         let new_entry_id_num = self.ui_entries.len() as u32 + rand::rng().random::<u32>();
         let new_id = egui::Id::new(format!("new_editable_meta_entry_{new_entry_id_num}"));
         self.ui_entries.push(EditableMetadataEntry {
             key,
-            value: "".to_string(),
+            value,
             is_pinned: false,
             is_from_file: false,
             id: new_id,
         });
     }
 
+    /// 移除所有未固定的元数据条目，返回被移除的条目数量。
+    pub fn remove_unpinned_ui_entries(&mut self) -> usize {
+        let original_len = self.ui_entries.len();
+        self.ui_entries.retain(|entry| entry.is_pinned);
+        let removed_count = original_len - self.ui_entries.len();
+        if removed_count > 0 {
+            self.sync_store_from_ui_entries();
+        }
+        removed_count
+    }
+
     pub fn remove_ui_entry(&mut self, index: usize) -> bool {
         if index < self.ui_entries.len() {
             self.ui_entries.remove(index);
@@ -368,9 +870,19 @@ impl UiMetadataManager {
         }
     }
 
+    /// 按固定的条目在前、其余条目在后的顺序遍历元数据条目。
+    fn entries_pinned_first(&self) -> impl Iterator<Item = &EditableMetadataEntry> {
+        let pinned = self.ui_entries.iter().filter(|e| e.is_pinned);
+        let unpinned = self.ui_entries.iter().filter(|e| !e.is_pinned);
+        pinned.chain(unpinned)
+    }
+
+    /// 按键分组的元数据，供生成器写入输出头部。
+    ///
+    /// 同一键下固定的条目排在前面，只能写入单个值的标签（如 LRC 的 `[ti:]`）会优先使用它们。
     pub fn get_metadata_for_backend(&self) -> std::collections::HashMap<String, Vec<String>> {
         let mut grouped_by_key = std::collections::HashMap::<String, Vec<String>>::new();
-        for entry in &self.ui_entries {
+        for entry in self.entries_pinned_first() {
             let key_string = entry.key.to_string();
             if !key_string.trim().is_empty() {
                 grouped_by_key
@@ -438,7 +950,7 @@ impl UiMetadataManager {
     pub fn sync_store_from_ui_entries(&mut self) {
         self.store.clear();
         let mut grouped_by_key = HashMap::<String, Vec<String>>::new();
-        for entry in &self.ui_entries {
+        for entry in self.entries_pinned_first() {
             let key_string = entry.key.to_string();
             grouped_by_key
                 .entry(key_string)
@@ -475,6 +987,7 @@ pub(super) enum BatchConverterStatus {
     Idle,
     Ready,
     Converting,
+    Paused,
     Completed,
     Failed(String),
 }
@@ -487,6 +1000,143 @@ pub(super) struct BatchConverterState {
     pub(super) tasks: Vec<BatchConversionConfig>,
     pub(super) file_lookup: HashMap<BatchFileId, BatchLoadedFile>,
     pub(super) status: BatchConverterStatus,
+    /// 以逗号分隔的主歌词扩展名白名单，例如 "lrc, ttml"。
+    pub(super) extension_whitelist_input: String,
+    /// 是否按文件名自动配对翻译和罗马音文件
+    pub(super) auto_pair_enabled: bool,
+    /// 以逗号分隔的翻译文件名后缀，例如 "_tr, .translation"。
+    pub(super) translation_suffixes_input: String,
+    /// 以逗号分隔的罗马音文件名后缀，例如 "_romaji, .romanization"。
+    pub(super) romanization_suffixes_input: String,
+    /// 暂停请求标志，转换线程在每个任务开始前检查。
+    pub(super) pause_requested: Arc<AtomicBool>,
+    /// 试运行模式：执行完整流程但不写入文件
+    pub(super) dry_run: bool,
+    /// 转换中自动应用的后处理器，按 `ProcessorType::ALL` 的顺序排列
+    pub(super) postprocessors: Vec<ProcessorType>,
+    /// 本次转换开始的时间，用于估算剩余时间
+    pub(super) run_started_at: Option<std::time::Instant>,
+    /// 本次转换中已处理完毕的任务数量
+    pub(super) finished_this_run: usize,
+    /// 仅重试失败任务时，本轮要运行的任务；为 `None` 时运行全部任务
+    pub(super) run_task_ids: Option<HashSet<BatchConfigId>>,
+}
+
+impl BatchConverterState {
+    pub(super) fn new(settings: &AppSettings) -> Self {
+        Self {
+            extension_whitelist_input: settings.batch_main_extension_whitelist.join(", "),
+            auto_pair_enabled: settings.batch_auto_pair_enabled,
+            translation_suffixes_input: settings.batch_translation_suffixes.join(", "),
+            romanization_suffixes_input: settings.batch_romanization_suffixes.join(", "),
+            postprocessors: settings.batch_postprocessors.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// 已处理（完成、失败或试运行完成）的任务数量。
+    pub(super) fn processed_task_count(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| {
+                matches!(
+                    task.status,
+                    BatchEntryStatus::Completed { .. }
+                        | BatchEntryStatus::Failed(_)
+                        | BatchEntryStatus::DryRun { .. }
+                )
+            })
+            .count()
+    }
+
+    /// 将失败的任务恢复为等待转换，并限定本轮只运行这些任务。返回被重置的任务数量。
+    pub(super) fn reset_failed_for_retry(&mut self) -> usize {
+        let mut retry_ids = HashSet::new();
+        for task in &mut self.tasks {
+            if matches!(task.status, BatchEntryStatus::Failed(_)) {
+                task.status = BatchEntryStatus::Pending;
+                retry_ids.insert(task.id);
+            }
+        }
+        let retry_count = retry_ids.len();
+        if retry_count > 0 {
+            self.run_task_ids = Some(retry_ids);
+        }
+        retry_count
+    }
+
+    /// 任务是否属于本轮运行。
+    fn is_in_current_run(&self, task: &BatchConversionConfig) -> bool {
+        self.run_task_ids
+            .as_ref()
+            .is_none_or(|ids| ids.contains(&task.id))
+    }
+
+    /// 交给转换线程的任务副本，仅重试失败任务时不包含其他任务。
+    pub(super) fn tasks_for_run(&self) -> Vec<BatchConversionConfig> {
+        self.tasks
+            .iter()
+            .filter(|task| self.is_in_current_run(task))
+            .cloned()
+            .collect()
+    }
+
+    /// 根据本次转换中已处理任务的平均耗时估算剩余时间。尚未处理完任何任务时返回 `None`。
+    pub(super) fn estimated_remaining(&self) -> Option<std::time::Duration> {
+        let started_at = self.run_started_at?;
+        if self.finished_this_run == 0 {
+            return None;
+        }
+        // 与转换线程跳过任务的规则一致：试运行的结果在正式运行时需要重新转换
+        let remaining = self
+            .tasks
+            .iter()
+            .filter(|task| self.is_in_current_run(task))
+            .filter(|task| match task.status {
+                BatchEntryStatus::Completed { .. }
+                | BatchEntryStatus::Failed(_)
+                | BatchEntryStatus::SkippedNoMatch => false,
+                BatchEntryStatus::DryRun { .. } => !self.dry_run,
+                _ => true,
+            })
+            .count();
+        let per_task = started_at.elapsed() / u32::try_from(self.finished_this_run).ok()?;
+        Some(per_task * u32::try_from(remaining).ok()?)
+    }
+
+    /// 转换失败的任务数量。
+    pub(super) fn failed_task_count(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| matches!(task.status, BatchEntryStatus::Failed(_)))
+            .count()
+    }
+
+    /// 将白名单输入框的内容解析为扩展名列表。
+    pub(super) fn parsed_extension_whitelist(&self) -> Vec<String> {
+        self.extension_whitelist_input
+            .split([',', ';', ' '])
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect()
+    }
+
+    /// 根据配对开关和两个后缀输入框的内容构建文件配对规则。
+    pub(super) fn parsed_pairing_options(&self) -> FilePairingOptions {
+        let parse_suffixes = |input: &str| -> Vec<String> {
+            input
+                .split([',', ';'])
+                .map(str::trim)
+                .filter(|suffix| !suffix.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+        FilePairingOptions {
+            enabled: self.auto_pair_enabled,
+            translation_suffixes: parse_suffixes(&self.translation_suffixes_input),
+            romanization_suffixes: parse_suffixes(&self.romanization_suffixes_input),
+        }
+    }
 }
 
 impl UniLyricApp {
@@ -539,6 +1189,8 @@ impl UniLyricApp {
             let mc_config = AMLLConnectorConfig {
                 enabled: settings.amll_connector_enabled,
                 websocket_url: settings.amll_connector_websocket_url.clone(),
+                heartbeat_interval_secs: settings.amll_connector_heartbeat_interval_secs,
+                heartbeat_timeout_secs: settings.amll_connector_heartbeat_timeout_secs,
             };
 
             let (amll_update_tx, amll_update_rx) = std_channel::<UiUpdate>();
@@ -549,6 +1201,9 @@ impl UniLyricApp {
                     amll_command_rx,
                     amll_update_tx,
                     mc_config.clone(),
+                    ActorSettings {
+                        lyric_send_mode: settings.amll_connector_lyric_send_mode,
+                    },
                     smtc_controller.command_tx.clone(),
                     smtc_update_rx,
                 ));
@@ -576,6 +1231,9 @@ impl UniLyricApp {
 
         let mut ui_state = UiState::new(&settings);
         ui_state.available_system_fonts = font_families;
+        if settings.session_autosave_enabled {
+            ui_state.pending_session_restore = WorkspaceSession::load();
+        }
 
         let helper = Arc::new(TokioMutex::new(lyrics_helper_rs::LyricsHelper::new()));
         let lyrics_helper_state = LyricsHelperState {
@@ -591,8 +1249,8 @@ impl UniLyricApp {
             amll_connector: amll_connector_state,
             fetcher: auto_fetch_state,
             local_cache,
-            downloader: DownloaderState::default(),
-            batch_converter: BatchConverterState::default(),
+            downloader: DownloaderState::new(&settings),
+            batch_converter: BatchConverterState::new(&settings),
             lyrics_helper_state,
             app_settings: Arc::new(StdMutex::new(settings)),
             tokio_runtime,
@@ -603,6 +1261,9 @@ impl UniLyricApp {
             actions_this_frame: Vec::new(),
             shutdown_initiated: false,
             auto_fetch_trigger_time: None,
+            last_session_autosave: std::time::Instant::now(),
+            last_saved_session: None,
+            metadata_key_usage_dirty: false,
         };
 
         app.load_local_cache();
@@ -616,8 +1277,7 @@ impl UniLyricApp {
         let mut fonts = egui::FontDefinitions::default();
         fonts.font_data.insert(
             "SarasaUiSC".to_owned(),
-            egui::FontData::from_static(include_bytes!("../assets/fonts/SarasaUiSC-Regular.ttf"))
-                .into(),
+            egui::FontData::from_static(crate::lyric_image::BUNDLED_FONT).into(),
         );
 
         let mut user_font_loaded = false;
@@ -802,3 +1462,251 @@ async fn cleanup_cover_cache(cache_dir: std::path::PathBuf, max_files: usize) {
 
     info!("[CacheCleanup] 封面缓存清理任务完成。");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search_result(provider_name: &str, provider_id: &str) -> SearchResult {
+        SearchResult {
+            provider_name: provider_name.to_string(),
+            provider_id: provider_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn downloader_with_auto_results(manual_query: (&str, &str)) -> DownloaderState {
+        DownloaderState {
+            search_state: SearchState::Success(vec![
+                search_result("qq", "1"),
+                search_result("netease", "2"),
+            ]),
+            manual_search_query: Some((manual_query.0.to_string(), manual_query.1.to_string())),
+            auto_search_candidates: vec![search_result("qq", "1"), search_result("kugou", "3")],
+            auto_search_query: Some(("晴天".to_string(), "周杰伦".to_string())),
+            ..Default::default()
+        }
+    }
+
+    fn origins(state: &DownloaderState) -> Vec<(String, SearchResultOrigin)> {
+        state
+            .displayed_results()
+            .into_iter()
+            .map(|(result, origin)| (result.provider_id.clone(), origin))
+            .collect()
+    }
+
+    fn metadata_entry(
+        key: CanonicalMetadataKey,
+        value: &str,
+        is_pinned: bool,
+    ) -> EditableMetadataEntry {
+        EditableMetadataEntry {
+            id: egui::Id::new(value),
+            key,
+            value: value.to_string(),
+            is_pinned,
+            is_from_file: false,
+        }
+    }
+
+    #[test]
+    fn test_metadata_for_backend_puts_pinned_values_first() {
+        let manager = UiMetadataManager {
+            ui_entries: vec![
+                metadata_entry(CanonicalMetadataKey::Title, "文件标题", false),
+                metadata_entry(CanonicalMetadataKey::Artist, "甲", false),
+                metadata_entry(CanonicalMetadataKey::Title, "固定标题", true),
+                metadata_entry(CanonicalMetadataKey::Artist, "乙", true),
+                metadata_entry(CanonicalMetadataKey::Artist, "丙", false),
+            ],
+            ..Default::default()
+        };
+
+        let entries: Vec<_> = manager
+            .entries_pinned_first()
+            .map(|entry| entry.value.as_str())
+            .collect();
+        assert_eq!(entries, vec!["固定标题", "乙", "文件标题", "甲", "丙"]);
+
+        let grouped = manager.get_metadata_for_backend();
+        assert_eq!(
+            grouped[&CanonicalMetadataKey::Title.to_string()],
+            vec!["固定标题", "文件标题"]
+        );
+        assert_eq!(
+            grouped[&CanonicalMetadataKey::Artist.to_string()],
+            vec!["乙", "甲", "丙"]
+        );
+    }
+
+    #[test]
+    fn test_displayed_results_merge_auto_results_for_same_song() {
+        let state = downloader_with_auto_results((" 晴天", "周杰伦 "));
+
+        assert_eq!(
+            origins(&state),
+            vec![
+                ("1".to_string(), SearchResultOrigin::Both),
+                ("2".to_string(), SearchResultOrigin::Manual),
+                ("3".to_string(), SearchResultOrigin::Auto),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_displayed_results_skip_auto_results_for_other_song() {
+        let state = downloader_with_auto_results(("稻香", "周杰伦"));
+
+        assert_eq!(
+            origins(&state),
+            vec![
+                ("1".to_string(), SearchResultOrigin::Manual),
+                ("2".to_string(), SearchResultOrigin::Manual),
+            ]
+        );
+    }
+
+    fn track(language: &str, lrc: &str) -> TranslationTrack {
+        TranslationTrack {
+            language: language.to_string(),
+            lrc: lrc.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_translation_tracks_keep_active_edits_across_switches() {
+        let mut state = LyricState::new(&AppSettings::default());
+        state.set_translation_tracks(vec![
+            track("zh-Hans", "[00:01.00]你好\n"),
+            track("en", "[00:01.00]Hello\n"),
+        ]);
+        assert_eq!(state.display_translation_lrc_output, "[00:01.00]你好\n");
+
+        state.display_translation_lrc_output = "[00:01.00]您好\n".to_string();
+        state.select_translation(1);
+        assert_eq!(state.active_translation_language(), "en");
+        assert_eq!(state.display_translation_lrc_output, "[00:01.00]Hello\n");
+
+        assert_eq!(
+            state.all_translation_tracks(),
+            vec![
+                track("zh-Hans", "[00:01.00]您好\n"),
+                track("en", "[00:01.00]Hello\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_translation_tracks_keeps_selected_language() {
+        let mut state = LyricState::new(&AppSettings::default());
+        state.set_translation_tracks(vec![track("zh-Hans", "中文"), track("en", "English")]);
+        state.select_translation(1);
+
+        state.set_translation_tracks(vec![track("ja", "日本語"), track("en", "New English")]);
+        assert_eq!(state.active_translation, 1);
+        assert_eq!(state.display_translation_lrc_output, "New English");
+
+        state.set_translation_tracks(vec![track("ja", "日本語")]);
+        assert_eq!(state.active_translation, 0);
+        assert_eq!(state.display_translation_lrc_output, "日本語");
+    }
+
+    #[test]
+    fn test_set_translation_tracks_keeps_an_empty_default_track() {
+        let mut state = LyricState::new(&AppSettings::default());
+        state.display_translation_lrc_output = "旧内容".to_string();
+        state.set_translation_tracks(Vec::new());
+
+        assert_eq!(
+            state.all_translation_tracks(),
+            vec![TranslationTrack::new(DEFAULT_TRANSLATION_LANGUAGE)]
+        );
+        assert!(state.display_translation_lrc_output.is_empty());
+    }
+
+    #[test]
+    fn test_record_status_change_uses_error_kind() {
+        let mut state = AmllConnectorState::new_disabled();
+        state.record_status_change(&WebsocketStatus::Error {
+            kind: ConnectionErrorKind::HeartbeatTimeout,
+            message: "连接已断开，正在重试 (1/3)".to_string(),
+        });
+        // 原因只取决于错误类别，与消息措辞无关
+        state.record_status_change(&WebsocketStatus::Error {
+            kind: ConnectionErrorKind::Network,
+            message: "心跳超时".to_string(),
+        });
+
+        let causes: Vec<_> = state.timeline.iter().map(|entry| entry.cause).collect();
+        assert_eq!(
+            causes,
+            vec![
+                ConnectionEventCause::HeartbeatTimeout,
+                ConnectionEventCause::NetworkError
+            ]
+        );
+    }
+
+    fn batch_task(status: BatchEntryStatus) -> BatchConversionConfig {
+        let mut task =
+            BatchConversionConfig::new(BatchFileId::new(), LyricFormat::Lrc, "a.lrc".to_string());
+        task.status = status;
+        task
+    }
+
+    #[test]
+    fn test_retry_failed_runs_only_reset_tasks() {
+        let dry_run = BatchEntryStatus::DryRun {
+            output_path: "a.lrc".into(),
+            output_size: 1,
+            overwrites_existing: false,
+            warnings: Vec::new(),
+        };
+        let mut state = BatchConverterState {
+            tasks: vec![
+                batch_task(BatchEntryStatus::Failed("解析失败".to_string())),
+                batch_task(BatchEntryStatus::Pending),
+                batch_task(dry_run.clone()),
+                batch_task(BatchEntryStatus::Failed("写入失败".to_string())),
+            ],
+            ..Default::default()
+        };
+        let failed_ids = [state.tasks[0].id, state.tasks[3].id];
+
+        assert_eq!(state.reset_failed_for_retry(), 2);
+
+        let run_ids: Vec<_> = state.tasks_for_run().iter().map(|task| task.id).collect();
+        assert_eq!(run_ids, failed_ids);
+        assert!(
+            state
+                .tasks_for_run()
+                .iter()
+                .all(|task| task.status == BatchEntryStatus::Pending)
+        );
+        // 不属于重试范围的任务保持原状态
+        assert_eq!(state.tasks[1].status, BatchEntryStatus::Pending);
+        assert_eq!(state.tasks[2].status, dry_run);
+    }
+
+    #[test]
+    fn test_retry_without_failed_tasks_keeps_full_run() {
+        let mut state = BatchConverterState {
+            tasks: vec![batch_task(BatchEntryStatus::Pending)],
+            ..Default::default()
+        };
+        assert_eq!(state.reset_failed_for_retry(), 0);
+        assert!(state.run_task_ids.is_none());
+        assert_eq!(state.tasks_for_run().len(), 1);
+    }
+
+    #[test]
+    fn test_target_formats_exclude_import_only_musixmatch() {
+        let state = LyricState::new(&AppSettings::default());
+        assert!(state.available_formats.contains(&LyricFormat::Musixmatch));
+
+        let targets = state.target_formats();
+        assert!(!targets.contains(&LyricFormat::Musixmatch));
+        assert_eq!(targets.len(), state.available_formats.len() - 1);
+    }
+}
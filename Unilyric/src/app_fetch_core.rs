@@ -1,4 +1,5 @@
 use crate::app_definition::UniLyricApp;
+use crate::app_settings::AppSettings;
 use crate::types::{AutoFetchResult, AutoSearchSource, AutoSearchStatus};
 use image_hasher::HasherConfig;
 use lyrics_helper_core::model::track::FullLyricsResult;
@@ -187,6 +188,9 @@ pub(super) fn initial_auto_fetch_and_send_lyrics(
     *app.fetcher.kugou_status.lock().unwrap() = AutoSearchStatus::Searching;
     *app.fetcher.netease_status.lock().unwrap() = AutoSearchStatus::Searching;
     *app.fetcher.amll_db_status.lock().unwrap() = AutoSearchStatus::Searching;
+    if app_settings.custom_http_source_enabled {
+        *app.fetcher.custom_http_status.lock().unwrap() = AutoSearchStatus::Searching;
+    }
 
     runtime.spawn(async move {
         let artists_slices: Vec<&str> = smtc_artists.iter().map(|s| s.as_str()).collect();
@@ -233,25 +237,9 @@ pub(super) fn initial_auto_fetch_and_send_lyrics(
                 }
         }
 
-        let regular_search_mode = {
-            let mut providers = lyrics_helper_rs::ProviderName::all();
-            providers.retain(|p| *p != lyrics_helper_rs::ProviderName::AmllTtmlDatabase);
-
-            if app_settings.use_provider_subset {
-                let user_subset: Vec<_> = app_settings
-                    .auto_search_provider_subset
-                    .iter()
-                    .filter_map(|s| s.parse().ok())
-                    .collect();
-                providers.retain(|p| user_subset.contains(p));
-            }
-
-            if app_settings.always_search_all_sources {
-                SearchMode::Subset(providers)
-            } else {
-                SearchMode::Ordered
-            }
-        };
+        // AMLL DB 已在上面单独优先搜索过
+        let regular_search_mode =
+            regular_search_mode(&app_settings, app_settings.prioritize_amll_db);
 
         let regular_search_result = {
             let future_res = {
@@ -291,6 +279,17 @@ pub(super) fn initial_auto_fetch_and_send_lyrics(
             }
         }
 
+        if !final_candidates.is_empty() {
+            let candidates_result = AutoFetchResult::CandidatesFound {
+                title: smtc_title.clone(),
+                artist: smtc_artists.join("/"),
+                candidates: final_candidates.clone(),
+            };
+            if result_tx.send(candidates_result).is_err() {
+                error!("[AutoFetch Task] 发送候选结果到主线程失败。");
+            }
+        }
+
         if let Some(mut lyrics_and_metadata) = final_lyrics {
             let source: AutoSearchSource =
                 lyrics_and_metadata.source_track.provider_name.clone().into();
@@ -303,8 +302,9 @@ pub(super) fn initial_auto_fetch_and_send_lyrics(
                 );
             }
             if app_settings.auto_apply_agent_recognizer {
-                lyrics_helper_rs::converter::processors::agent_recognizer::recognize_agents(
+                lyrics_helper_rs::converter::processors::agent_recognizer::recognize_agents_with_options(
                     &mut lyrics_and_metadata.lyrics.parsed,
+                    &app_settings.agent_recognizer,
                 );
             }
 
@@ -380,6 +380,37 @@ pub(super) fn initial_auto_fetch_and_send_lyrics(
     });
 }
 
+/// 按用户设置的源顺序和范围生成常规搜索使用的搜索模式。
+///
+/// 搜索所有源时，匹配度相同的结果中靠前的源优先；否则按顺序逐个搜索，
+/// 找到足够匹配的歌词后就不再搜索后面的源。`skip_amll_db` 为 `true` 时排除 AMLL DB，
+/// 用于已经单独优先搜索过 AMLL DB 的情况。
+pub(super) fn regular_search_mode(app_settings: &AppSettings, skip_amll_db: bool) -> SearchMode {
+    let mut providers: Vec<_> =
+        AutoSearchSource::normalize_order(&app_settings.auto_search_source_order)
+            .into_iter()
+            .filter_map(AutoSearchSource::to_provider_name)
+            .collect();
+    if skip_amll_db {
+        providers.retain(|p| *p != lyrics_helper_rs::ProviderName::AmllTtmlDatabase);
+    }
+
+    if app_settings.use_provider_subset {
+        let user_subset: Vec<_> = app_settings
+            .auto_search_provider_subset
+            .iter()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+        providers.retain(|p| user_subset.contains(p));
+    }
+
+    if app_settings.always_search_all_sources {
+        SearchMode::Subset(providers)
+    } else {
+        SearchMode::OrderedSubset(providers)
+    }
+}
+
 /// 触发对特定源的手动重新搜索。
 pub(super) fn trigger_manual_refetch_for_source(
     app: &mut UniLyricApp,
@@ -416,6 +447,7 @@ pub(super) fn trigger_manual_refetch_for_source(
         AutoSearchSource::Kugou => Arc::clone(&app.fetcher.kugou_status),
         AutoSearchSource::Netease => Arc::clone(&app.fetcher.netease_status),
         AutoSearchSource::AmllDb => Arc::clone(&app.fetcher.amll_db_status),
+        AutoSearchSource::CustomHttp => Arc::clone(&app.fetcher.custom_http_status),
         _ => return,
     };
     *status_arc_to_update.lock().unwrap() = AutoSearchStatus::Searching;
@@ -442,6 +474,7 @@ pub(super) fn trigger_manual_refetch_for_source(
             AutoSearchSource::Netease => lyrics_helper_rs::ProviderName::Netease,
             AutoSearchSource::Kugou => lyrics_helper_rs::ProviderName::Kugou,
             AutoSearchSource::AmllDb => lyrics_helper_rs::ProviderName::AmllTtmlDatabase,
+            AutoSearchSource::CustomHttp => lyrics_helper_rs::ProviderName::CustomHttp,
             _ => {
                 *status_arc_to_update.lock().unwrap() =
                     AutoSearchStatus::Error("不支持的重搜源".to_string());
@@ -483,8 +516,9 @@ pub(super) fn trigger_manual_refetch_for_source(
                     );
                 }
                 if app_settings.auto_apply_agent_recognizer {
-                    lyrics_helper_rs::converter::processors::agent_recognizer::recognize_agents(
+                    lyrics_helper_rs::converter::processors::agent_recognizer::recognize_agents_with_options(
                         &mut lyrics_and_metadata.lyrics.parsed,
+                        &app_settings.agent_recognizer,
                     );
                 }
 
@@ -571,6 +605,7 @@ pub(super) fn clear_last_fetch_results(app: &mut UniLyricApp) {
     *app.fetcher.last_kugou_result.lock().unwrap() = None;
     *app.fetcher.last_netease_result.lock().unwrap() = None;
     *app.fetcher.last_amll_db_result.lock().unwrap() = None;
+    *app.fetcher.last_custom_http_result.lock().unwrap() = None;
     app.fetcher.current_ui_populated = false;
 }
 
@@ -679,3 +714,50 @@ async fn fetch_and_validate_cover(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lyrics_helper_rs::ProviderName;
+
+    fn settings_with_order(always_search_all_sources: bool) -> AppSettings {
+        AppSettings {
+            auto_search_source_order: vec![AutoSearchSource::Kugou, AutoSearchSource::AmllDb],
+            always_search_all_sources,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_regular_search_mode_keeps_custom_order() {
+        let expected = vec![
+            ProviderName::Kugou,
+            ProviderName::Netease,
+            ProviderName::QQMusic,
+            ProviderName::CustomHttp,
+        ];
+
+        assert_eq!(
+            regular_search_mode(&settings_with_order(true), true),
+            SearchMode::Subset(expected.clone())
+        );
+        assert_eq!(
+            regular_search_mode(&settings_with_order(false), true),
+            SearchMode::OrderedSubset(expected)
+        );
+    }
+
+    #[test]
+    fn test_regular_search_mode_applies_provider_subset() {
+        let settings = AppSettings {
+            use_provider_subset: true,
+            auto_search_provider_subset: vec!["netease".to_string(), "kugou".to_string()],
+            ..settings_with_order(false)
+        };
+
+        assert_eq!(
+            regular_search_mode(&settings, false),
+            SearchMode::OrderedSubset(vec![ProviderName::Kugou, ProviderName::Netease])
+        );
+    }
+}
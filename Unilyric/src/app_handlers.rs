@@ -1,25 +1,43 @@
+use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::sync::Arc;
 
+use crate::action_script::ScriptStep;
 use crate::amll_connector::types::ActorSettings;
 use crate::amll_connector::{AMLLConnectorConfig, ConnectorCommand};
 use crate::app_actions::{
-    AmllConnectorAction, BatchConverterAction, DownloaderAction, FileAction, LyricsAction,
-    PanelType, PlayerAction, ProcessorType, SettingsAction, UIAction, UserAction,
+    AmllConnectorAction, BatchConverterAction, DownloaderAction, FileAction, LineCopyFormat,
+    LocalCacheAction, LyricsAction, PanelType, PlayerAction, ProcessorType, SettingsAction,
+    UIAction, UserAction,
 };
 use crate::app_definition::{
-    AppView, BatchConverterStatus, DownloaderState, PreviewState, SearchState, UniLyricApp,
+    AppView, BatchConverterState, BatchConverterStatus, DownloaderState, PreviewState, SearchState,
+    UniLyricApp,
 };
 use crate::app_handlers::ConnectorCommand::SendLyric;
 use crate::app_handlers::ConnectorCommand::UpdateActorSettings;
-use crate::app_settings::AppAmllMirror;
+use crate::app_settings::{AppAmllMirror, AppSettings};
+use crate::audio_preview::PREVIEW_MAX_BYTES;
+use crate::batch_download::{self, BatchDownloadJob, BatchDownloadStatus};
 use crate::error::{AppError, AppResult};
-use crate::types::{AutoSearchStatus, LrcContentType, ProviderState};
+use crate::lyric_image::{LyricImageContent, LyricImageLine};
+use crate::session::WorkspaceSession;
+use crate::types::{
+    AutoSearchStatus, DEFAULT_ROMANIZATION_LANGUAGE, DEFAULT_TRANSLATION_LANGUAGE, LineNoteAnchor,
+    LrcContentType, LyricWarning, MetadataSuggestion, OpenDocument, ProviderState,
+    ResultPreviewSummary, SmoothingHistory, TranslationTrack, WarningLevel,
+    line_notes_from_start_times, remap_line_notes,
+};
 use lyrics_helper_core::{
-    ChineseConversionConfig, ChineseConversionMode, ChineseConversionOptions, ContentType,
-    ConversionInput, ConversionOptions, InputFile, LyricFormat, LyricTrack, Track,
+    AuxiliaryLineMatchingStrategy, BatchEntryStatus, CanonicalMetadataKey, ChineseConversionConfig,
+    ChineseConversionMode, ChineseConversionOptions, ContentType, ConversionInput,
+    ConversionOptions, ConvertError, InputFile, LrcTimestampPrecision, LyricFormat, LyricLine,
+    LyricTrack, MetadataStore, MetadataStripperFlags, ParsedSourceData, Track, TrackMetadataKey,
 };
-use smtc_suite::{MediaCommand, TextConversionMode};
+use lyrics_helper_rs::converter::generators::lrc_generator::format_lrc_time;
+use lyrics_helper_rs::converter::processors::batch_processor::BatchRunOutcome;
+use smtc_suite::{MediaCommand, SmtcControlCommand, TextConversionMode};
+use tokio_util::sync::CancellationToken;
 use tracing::warn;
 use tracing::{debug, error, info};
 
@@ -30,6 +48,113 @@ pub enum ActionResult {
     Error(AppError),
 }
 
+/// 下载器输入停止后触发自动搜索前的等待时间。
+const DOWNLOADER_AUTO_SEARCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// 主输入停止编辑后触发转换前的等待时间。
+const MAIN_INPUT_CONVERT_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// AMLL 提交格式相关警告的前缀，用于在重新复制时清除旧的警告。
+const AMLL_SUBMISSION_WARNING_PREFIX: &str = "[AMLL 提交] ";
+
+/// 检查 AMLL TTML 数据库要求的必填元数据，返回缺失项的名称列表。
+fn missing_amll_submission_metadata(store: &MetadataStore) -> Vec<&'static str> {
+    let has_value = |key: &CanonicalMetadataKey| {
+        store
+            .get_multiple_values(key)
+            .is_some_and(|values| values.iter().any(|v| !v.trim().is_empty()))
+    };
+
+    let mut missing = Vec::new();
+    if !has_value(&CanonicalMetadataKey::Title) {
+        missing.push("musicName");
+    }
+    if !has_value(&CanonicalMetadataKey::Artist) {
+        missing.push("artists");
+    }
+    let has_platform_id = [
+        CanonicalMetadataKey::NcmMusicId,
+        CanonicalMetadataKey::QqMusicId,
+        CanonicalMetadataKey::SpotifyId,
+        CanonicalMetadataKey::AppleMusicId,
+    ]
+    .iter()
+    .any(has_value);
+    if !has_platform_id {
+        missing.push("ncmMusicId / qqMusicId / spotifyId / appleMusicId (至少一项)");
+    }
+    if !has_value(&CanonicalMetadataKey::TtmlAuthorGithub) {
+        missing.push("ttmlAuthorGithub");
+    }
+    if !has_value(&CanonicalMetadataKey::TtmlAuthorGithubLogin) {
+        missing.push("ttmlAuthorGithubLogin");
+    }
+    missing
+}
+
+/// 将歌词行转换为“时间 | 歌词 | 翻译”三列的 Markdown 表格，时间与 LRC 时间戳一样四舍五入到 `mm:ss.xx`。
+///
+/// 没有主歌词文本的行（如间奏标记）会被跳过，多个翻译以 ` / ` 连接。
+fn lyrics_to_markdown_table(lines: &[LyricLine]) -> String {
+    let escape = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+
+    let mut table = String::from("| 时间 | 歌词 | 翻译 |\n| --- | --- | --- |\n");
+    for line in lines {
+        let Some(main_track) = line.main_track() else {
+            continue;
+        };
+        let text = main_track.content.text();
+        if text.trim().is_empty() {
+            continue;
+        }
+        let translation = main_track
+            .translations
+            .iter()
+            .map(LyricTrack::text)
+            .filter(|t| !t.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" / ");
+
+        let timestamp = format_lrc_time(line.start_ms, LrcTimestampPrecision::Centiseconds);
+        let _ = writeln!(
+            table,
+            "| {} | {} | {} |",
+            timestamp.trim_matches(['[', ']']),
+            escape(text.trim()),
+            escape(translation.trim()),
+        );
+    }
+    table
+}
+
+/// 按指定格式生成单行歌词的复制文本。LRC 时间戳四舍五入到 `mm:ss.xx`，单独复制时间戳时精确到毫秒。
+fn format_line_for_copy(line: &LyricLine, format: LineCopyFormat) -> String {
+    let precise_time = |ms: u64| {
+        format!(
+            "{:02}:{:02}.{:03}",
+            ms / 60000,
+            (ms % 60000) / 1000,
+            ms % 1000
+        )
+    };
+    let text = line.main_text().unwrap_or_default();
+    let text = text.trim();
+
+    match format {
+        LineCopyFormat::Text => text.to_string(),
+        LineCopyFormat::Timestamp => format!(
+            "{} - {}",
+            precise_time(line.start_ms),
+            precise_time(line.end_ms)
+        ),
+        LineCopyFormat::LrcLine => format!(
+            "{}{}",
+            format_lrc_time(line.start_ms, LrcTimestampPrecision::Centiseconds),
+            text
+        ),
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 struct CoreAmllConfig {
     mirror: CoreAmllMirror,
@@ -92,19 +217,173 @@ impl UniLyricApp {
     }
 
     pub fn trigger_convert(&mut self) {
+        self.lyrics.converted_input_hash = Some(input_text_hash(&self.lyrics.input_text));
         let options = self.build_conversion_options();
         self.dispatch_conversion_task(options);
     }
 
+    /// 主输入防抖结束后调用。只有输入内容与上次转换时不同，才清理旧状态并重新转换。
+    pub(super) fn convert_input_if_changed(&mut self) {
+        self.lyrics.input_convert_deadline = None;
+
+        let hash = input_text_hash(&self.lyrics.input_text);
+        if self.lyrics.converted_input_hash == Some(hash) {
+            debug!("[Convert] 输入内容未变化，跳过转换。");
+            return;
+        }
+
+        let text = std::mem::take(&mut self.lyrics.input_text);
+        self.clear_lyrics_state_for_new_song_internal();
+        self.lyrics.input_text = text;
+        self.lyrics.converted_input_hash = Some(hash);
+        if self.lyrics.conversion_in_progress {
+            self.lyrics.reconvert_after_current = true;
+        } else if !self.lyrics.input_text.trim().is_empty() {
+            self.trigger_convert();
+        }
+    }
+
+    /// 对当前已解析的歌词运行有效性检查，并返回发现的问题数量。
+    fn run_lyrics_lint(&mut self) -> usize {
+        let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_ref() else {
+            self.lyrics.lint_issues = None;
+            return 0;
+        };
+
+        let issues = lyrics_helper_rs::converter::processors::lyric_linter::lint_lyrics(
+            &parsed_data.lines,
+            &Default::default(),
+        );
+        info!("[Lint] 歌词检查完成，发现 {} 个问题。", issues.len());
+
+        let count = issues.len();
+        self.lyrics.lint_issues = Some(issues);
+        count
+    }
+
+    /// 以 toast 显示简繁转换前后的字数变化统计。
+    ///
+    /// 如果转换前后字符总数不一致，以警告形式提示，便于发现转换配置选错的情况。
+    fn show_chinese_conversion_stats(&mut self, before: &[LyricLine], after: &[LyricLine]) {
+        let stats =
+            lyrics_helper_rs::converter::processors::chinese_conversion_processor::compare_conversion(
+                before, after,
+            );
+        info!("[Convert] 简繁转换统计: {:?}", stats);
+
+        let mut text = format!(
+            "转换了 {} 个字符，{} 行受影响",
+            stats.changed_chars, stats.affected_lines
+        );
+        let kind = if stats.char_count_changed() {
+            text.push_str(&format!(
+                "\n字符总数发生变化: {} → {}",
+                stats.total_chars_before, stats.total_chars_after
+            ));
+            egui_toast::ToastKind::Warning
+        } else {
+            egui_toast::ToastKind::Info
+        };
+
+        self.ui.toasts.add(egui_toast::Toast {
+            text: text.into(),
+            kind,
+            options: egui_toast::ToastOptions::default()
+                .duration_in_seconds(5.0)
+                .show_progress(true),
+            style: Default::default(),
+        });
+    }
+
+    /// 按 AMLL TTML 数据库的提交要求生成 TTML 并复制到剪贴板。
+    ///
+    /// 缺失的必填元数据会被追加到当前警告列表中。
+    fn copy_as_amll_submission(&mut self) -> ActionResult {
+        let Some(parsed_data) = self.lyrics.parsed_lyric_data.clone() else {
+            return ActionResult::Warning("没有可用于提交的歌词数据".to_string());
+        };
+
+        self.lyrics.metadata_manager.sync_store_from_ui_entries();
+        let missing_keys = missing_amll_submission_metadata(&self.lyrics.metadata_manager.store);
+        let metadata_overrides = Some(self.lyrics.metadata_manager.get_metadata_for_backend());
+        let options = self.build_conversion_options();
+
+        let result = lyrics_helper_rs::converter::generate_from_parsed(
+            parsed_data,
+            LyricFormat::Ttml,
+            &options,
+            &metadata_overrides,
+        );
+
+        let ttml = match result {
+            Ok(full_result) => full_result.output_lyrics,
+            Err(e) => {
+                return ActionResult::Error(AppError::Custom(format!(
+                    "生成 AMLL 提交格式失败: {e}"
+                )));
+            }
+        };
+
+        self.egui_ctx.copy_text(ttml);
+
+        self.lyrics
+            .current_warnings
+            .retain(|w| !w.message.starts_with(AMLL_SUBMISSION_WARNING_PREFIX));
+
+        if missing_keys.is_empty() {
+            self.ui.toasts.add(egui_toast::Toast {
+                text: "已复制为 AMLL 提交格式".into(),
+                kind: egui_toast::ToastKind::Success,
+                options: egui_toast::ToastOptions::default().duration_in_seconds(2.0),
+                style: Default::default(),
+            });
+            ActionResult::Success
+        } else {
+            for key in &missing_keys {
+                self.lyrics.current_warnings.push(LyricWarning::new(
+                    WarningLevel::Warning,
+                    format!("{AMLL_SUBMISSION_WARNING_PREFIX}缺少必填元数据: {key}"),
+                ));
+            }
+            ActionResult::Warning(format!(
+                "已复制，但缺少 {} 项必填元数据，详见警告面板",
+                missing_keys.len()
+            ))
+        }
+    }
+
     fn build_conversion_options(&self) -> ConversionOptions {
-        let settings = self.app_settings.lock().unwrap();
-        ConversionOptions {
-            metadata_stripper: settings.metadata_stripper.clone(),
-            ..Default::default()
+        self.app_settings.lock().unwrap().conversion_options()
+    }
+
+    /// 歌词行变化后让校对备注继续对应原来的行。
+    ///
+    /// 行数不变时（修改时间轴或文本）备注留在原索引上；行数变化时按原行的文本和开始时间
+    /// 找到它的新位置。旧版本会话中以开始时间为键的备注也在这里换算为行索引。
+    pub(super) fn sync_line_notes(&mut self) {
+        let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_ref() else {
+            return;
+        };
+        let anchors = LineNoteAnchor::from_lines(&parsed_data.lines);
+        let previous = std::mem::replace(&mut self.lyrics.line_note_anchors, anchors);
+
+        if !self.lyrics.legacy_line_notes.is_empty() {
+            let legacy = std::mem::take(&mut self.lyrics.legacy_line_notes);
+            let converted = line_notes_from_start_times(&legacy, &self.lyrics.line_note_anchors);
+            self.lyrics.line_notes.extend(converted);
+        }
+
+        if !previous.is_empty() && previous.len() != self.lyrics.line_note_anchors.len() {
+            self.lyrics.line_notes = remap_line_notes(
+                &self.lyrics.line_notes,
+                &previous,
+                &self.lyrics.line_note_anchors,
+            );
         }
     }
 
     fn dispatch_regeneration_task(&mut self) {
+        self.sync_line_notes();
         if self.lyrics.conversion_in_progress {
             warn!("[Regenerate] 重新生成已在进行中，跳过新的请求。");
             return;
@@ -118,20 +397,17 @@ impl UniLyricApp {
         let (tx, rx) = std::sync::mpsc::channel();
         self.lyrics.conversion_result_rx = Some(rx);
         self.lyrics.conversion_in_progress = true;
+        self.lyrics.conversion_started_at = Some(std::time::Instant::now());
 
         let target_format = self.lyrics.target_format;
+        let options = self.build_conversion_options();
 
         let metadata_overrides = Some(self.lyrics.metadata_manager.get_metadata_for_backend());
 
         self.tokio_runtime.spawn(async move {
             let result = lyrics_helper_rs::LyricsHelper::generate_lyrics_from_parsed::<
                 std::hash::RandomState,
-            >(
-                parsed_data,
-                target_format,
-                Default::default(),
-                metadata_overrides,
-            )
+            >(parsed_data, target_format, options, metadata_overrides)
             .await;
 
             if tx.send(result).is_err() {
@@ -150,7 +426,7 @@ impl UniLyricApp {
         let (tx, rx) = std::sync::mpsc::channel();
         self.lyrics.conversion_result_rx = Some(rx);
         self.lyrics.conversion_in_progress = true;
-        let helper = self.lyrics_helper_state.helper.clone();
+        self.lyrics.conversion_started_at = Some(std::time::Instant::now());
 
         // 1. 准备主歌词文件
         let main_lyric = InputFile::new(
@@ -160,17 +436,14 @@ impl UniLyricApp {
             None,
         );
 
-        // 2. 准备翻译文件列表
-        let translations = if !self.lyrics.display_translation_lrc_output.trim().is_empty() {
-            vec![InputFile::new(
-                self.lyrics.display_translation_lrc_output.clone(),
-                LyricFormat::Lrc,
-                Some("zh-Hans".to_string()),
-                None,
-            )]
-        } else {
-            vec![]
-        };
+        // 2. 准备翻译文件列表，每种语言一个文件
+        let translations = self
+            .lyrics
+            .all_translation_tracks()
+            .into_iter()
+            .filter(|track| !track.lrc.trim().is_empty())
+            .map(|track| InputFile::new(track.lrc, LyricFormat::Lrc, Some(track.language), None))
+            .collect();
 
         // 3. 准备罗马音文件列表
         let romanizations = if !self
@@ -182,7 +455,7 @@ impl UniLyricApp {
             vec![InputFile::new(
                 self.lyrics.display_romanization_lrc_output.clone(),
                 LyricFormat::Lrc,
-                Some("ja-Latn".to_string()),
+                Some(DEFAULT_ROMANIZATION_LANGUAGE.to_string()),
                 None,
             )]
         } else {
@@ -213,7 +486,8 @@ impl UniLyricApp {
         };
 
         self.tokio_runtime.spawn(async move {
-            let result = helper.lock().await.convert_lyrics(&input, &options);
+            let result =
+                lyrics_helper_rs::LyricsHelper::convert_lyrics_in_background(input, options).await;
             if tx.send(result).is_err() {
                 warn!("[Convert Task] 发送转换结果失败，接收端可能已关闭。");
             }
@@ -231,7 +505,14 @@ impl UniLyricApp {
 
         for action in actions {
             debug!("处理事件: {:?}", std::mem::discriminant(&action));
-            results.push(self.handle_single_action(action));
+            let script_step = ScriptStep::from_action(&action);
+            let result = self.handle_single_action(action);
+            if let Some(step) = script_step
+                && matches!(result, ActionResult::Success)
+            {
+                self.ui.action_script.record(step);
+            }
+            results.push(result);
         }
 
         // 统一处理结果
@@ -277,10 +558,81 @@ impl UniLyricApp {
             UserAction::BatchConverter(batch_action) => {
                 self.handle_batch_converter_action(batch_action)
             }
+            UserAction::LocalCache(cache_action) => self.handle_local_cache_action(cache_action),
+        }
+    }
+
+    fn handle_local_cache_action(&mut self, action: LocalCacheAction) -> ActionResult {
+        let Some(cache_dir) = self.local_cache.dir_path.clone() else {
+            return ActionResult::Error(AppError::Custom("缺少缓存目录路径".to_string()));
+        };
+
+        match action {
+            LocalCacheAction::Preview(filename) => {
+                match std::fs::read_to_string(cache_dir.join(&filename)) {
+                    Ok(content) => {
+                        self.ui.local_cache_preview = Some((filename, content));
+                        ActionResult::Success
+                    }
+                    Err(e) => {
+                        self.ui.local_cache_preview = None;
+                        ActionResult::Error(AppError::Custom(format!("读取缓存文件失败: {e}")))
+                    }
+                }
+            }
+            LocalCacheAction::LoadIntoEditor(filename) => {
+                let path = cache_dir.join(&filename);
+                match std::fs::read_to_string(&path) {
+                    Ok(content) => {
+                        info!("[LocalCache] 从本地缓存加载歌词: {path:?}");
+                        self.open_documents(vec![(path, content)]);
+                        ActionResult::Success
+                    }
+                    Err(e) => {
+                        ActionResult::Error(AppError::Custom(format!("读取缓存文件失败: {e}")))
+                    }
+                }
+            }
+            LocalCacheAction::Delete(filename) => {
+                let Some(index_path) = self.local_cache.index_path.clone() else {
+                    return ActionResult::Error(AppError::Custom("缺少缓存索引路径".to_string()));
+                };
+
+                let mut index_guard = self.local_cache.index.lock().unwrap();
+                index_guard.retain(|entry| entry.ttml_filename != filename);
+                if let Err(e) = write_local_cache_index(&index_path, &index_guard) {
+                    return ActionResult::Error(e);
+                }
+                drop(index_guard);
+
+                let file_path = cache_dir.join(&filename);
+                if let Err(e) = std::fs::remove_file(&file_path) {
+                    warn!("[LocalCache] 删除缓存文件 {file_path:?} 失败: {e}");
+                }
+                if self
+                    .ui
+                    .local_cache_preview
+                    .as_ref()
+                    .is_some_and(|(previewed, _)| *previewed == filename)
+                {
+                    self.ui.local_cache_preview = None;
+                }
+
+                info!("[LocalCache] 已删除缓存条目: {filename}");
+                ActionResult::Success
+            }
         }
     }
 
     fn handle_amll_connector_action(&mut self, action: AmllConnectorAction) -> ActionResult {
+        if matches!(
+            action,
+            AmllConnectorAction::Connect
+                | AmllConnectorAction::Disconnect
+                | AmllConnectorAction::Retry
+        ) {
+            self.amll_connector.user_action_pending = true;
+        }
         if let Some(tx) = &self.amll_connector.command_tx {
             let command = match action {
                 AmllConnectorAction::Connect | AmllConnectorAction::Retry => {
@@ -374,6 +726,28 @@ impl UniLyricApp {
         }
     }
 
+    /// 累加元数据键的使用次数并更新菜单排序。
+    ///
+    /// 使用次数只是排序的参考，不必每次都写入磁盘，退出时再保存。
+    fn record_metadata_key_usage(&mut self, key: &CanonicalMetadataKey) {
+        if let Ok(mut settings) = self.app_settings.lock() {
+            settings.record_metadata_key_usage(key);
+            self.ui.metadata_keys_by_usage = settings.metadata_keys_by_usage();
+            self.metadata_key_usage_dirty = true;
+        }
+    }
+
+    /// 保存尚未写入磁盘的元数据键使用次数。
+    pub(super) fn save_metadata_key_usage(&mut self) {
+        if !self.metadata_key_usage_dirty {
+            return;
+        }
+        self.metadata_key_usage_dirty = false;
+        if let Err(e) = self.app_settings.lock().unwrap().save() {
+            warn!("[Settings] 保存元数据键使用频率失败: {}", e);
+        }
+    }
+
     fn handle_load_full_lyrics_result(
         &mut self,
         result: lyrics_helper_core::model::track::FullLyricsResult,
@@ -383,7 +757,7 @@ impl UniLyricApp {
         self.lyrics.metadata_source_is_download = true;
         self.lyrics.input_text = result.raw.content;
         self.lyrics.source_format = result.parsed.source_format;
-        self.lyrics.current_warnings = result.parsed.warnings.clone();
+        self.lyrics.current_warnings = LyricWarning::from_parser_warnings(&result.parsed.warnings);
         self.lyrics
             .metadata_manager
             .load_from_parsed_data(&result.parsed);
@@ -399,39 +773,144 @@ impl UniLyricApp {
     fn handle_lyrics_action(&mut self, action: LyricsAction) -> ActionResult {
         match action {
             LyricsAction::LoadFileContent(content, path) => {
-                self.clear_lyrics_state_for_new_song_internal();
-                self.lyrics.last_opened_file_path = Some(path.clone());
-                self.lyrics.metadata_source_is_download = false;
-                self.lyrics.input_text = content;
-                if let Some(ext) = path.extension().and_then(|s| s.to_str())
-                    && let Some(format) = LyricFormat::from_string(ext)
+                self.open_documents(vec![(path, content)]);
+                ActionResult::Success
+            }
+            LyricsAction::OpenDocuments(files) => {
+                if files.is_empty() {
+                    return ActionResult::Warning("没有可打开的文件".to_string());
+                }
+                self.open_documents(files);
+                ActionResult::Success
+            }
+            LyricsAction::ReloadWithEncoding(encoding) => {
+                let Some(prompt) = self.ui.encoding_prompt.take() else {
+                    return ActionResult::Success;
+                };
+                let content = match crate::io::read_text_file_with_encoding(&prompt.path, encoding)
                 {
-                    self.lyrics.source_format = format;
+                    Ok(content) => content,
+                    Err(e) => {
+                        return ActionResult::Error(AppError::Custom(format!(
+                            "重新读取文件失败: {e}"
+                        )));
+                    }
+                };
+                info!(
+                    "[IO] 已按 {} 编码重新读取文件: {:?}",
+                    encoding.name(),
+                    prompt.path
+                );
+
+                match prompt.target {
+                    Some(LrcContentType::Translation) => {
+                        self.lyrics.display_translation_lrc_output = content;
+                    }
+                    Some(LrcContentType::Romanization) => {
+                        self.lyrics.display_romanization_lrc_output = content;
+                    }
+                    None => {
+                        let Some(index) = self
+                            .lyrics
+                            .open_documents
+                            .iter()
+                            .position(|document| document.path == prompt.path)
+                        else {
+                            return ActionResult::Warning("要重新读取的文件已关闭".to_string());
+                        };
+                        let document = &mut self.lyrics.open_documents[index];
+                        document.input_text = content.clone();
+                        document.saved_input_text = content.clone();
+                        if self.lyrics.active_document != Some(index) {
+                            return ActionResult::Success;
+                        }
+                        self.lyrics.input_text = content;
+                        self.lyrics.mark_texts_changed();
+                    }
                 }
                 self.trigger_convert();
                 ActionResult::Success
             }
+            LyricsAction::SwitchDocument(index) => {
+                if index >= self.lyrics.open_documents.len() {
+                    return ActionResult::Warning("要切换的文件不存在".to_string());
+                }
+                self.switch_document(index);
+                ActionResult::Success
+            }
+            LyricsAction::CloseDocument(index) => {
+                if index >= self.lyrics.open_documents.len() {
+                    return ActionResult::Warning("要关闭的文件不存在".to_string());
+                }
+                if self.lyrics.is_document_modified(index) {
+                    self.ui.pending_close_document = Some(index);
+                } else {
+                    self.close_document(index);
+                }
+                ActionResult::Success
+            }
+            LyricsAction::DiscardDocument(index) => {
+                self.ui.pending_close_document = None;
+                if index >= self.lyrics.open_documents.len() {
+                    return ActionResult::Warning("要关闭的文件不存在".to_string());
+                }
+                self.close_document(index);
+                ActionResult::Success
+            }
             LyricsAction::Convert => {
                 self.trigger_convert();
                 ActionResult::Success
             }
             LyricsAction::ConvertCompleted(result) => {
                 self.lyrics.conversion_in_progress = false;
+                if let Some(started_at) = self.lyrics.conversion_started_at.take() {
+                    debug!("[Convert] 转换任务耗时 {:?}", started_at.elapsed());
+                }
+
+                // 转换期间输入又发生了变化，这次的结果已经过时，直接按最新输入重新转换
+                if std::mem::take(&mut self.lyrics.reconvert_after_current) {
+                    info!("[Convert] 转换期间输入已变化，丢弃旧结果并重新转换。");
+                    self.lyrics.chinese_conversion_baseline = None;
+                    if !self.lyrics.input_text.trim().is_empty() {
+                        self.trigger_convert();
+                    }
+                    return ActionResult::Success;
+                }
+
                 match result {
                     Ok(full_result) => {
                         self.lyrics.output_text = full_result.output_lyrics;
+                        self.lyrics.mark_texts_changed();
                         self.lyrics.parsed_lyric_data = Some(full_result.source_data.clone());
-                        self.lyrics.current_warnings = full_result.source_data.warnings.clone();
+                        self.sync_line_notes();
+                        self.lyrics.current_warnings =
+                            LyricWarning::from_parser_warnings(&full_result.source_data.warnings);
+                        self.lyrics.current_warnings.extend(
+                            full_result
+                                .warnings
+                                .iter()
+                                .map(|w| LyricWarning::new(WarningLevel::Warning, w.as_str())),
+                        );
 
                         self.lyrics
                             .metadata_manager
                             .load_from_parsed_data(&full_result.source_data);
 
-                        self.lyrics.display_translation_lrc_output =
-                            self.generate_lrc_from_aux_track(&full_result.source_data, true);
+                        self.load_translation_tracks(&full_result.source_data);
                         self.lyrics.display_romanization_lrc_output =
                             self.generate_lrc_from_aux_track(&full_result.source_data, false);
 
+                        if self.lyrics.lint_issues.is_some() {
+                            self.run_lyrics_lint();
+                        }
+
+                        if let Some(baseline) = self.lyrics.chinese_conversion_baseline.take() {
+                            self.show_chinese_conversion_stats(
+                                &baseline,
+                                &full_result.source_data.lines,
+                            );
+                        }
+
                         if self.amll_connector.config.lock().unwrap().enabled
                             && let Some(tx) = &self.amll_connector.command_tx
                             && tx.try_send(SendLyric(full_result.source_data)).is_err()
@@ -443,6 +922,8 @@ impl UniLyricApp {
                     Err(e) => {
                         error!("[Convert Result] 转换任务返回了一个错误: {e}");
                         self.lyrics.output_text.clear();
+                        self.lyrics.mark_texts_changed();
+                        self.lyrics.chinese_conversion_baseline = None;
                         ActionResult::Error(AppError::Custom("转换失败: {e}".to_string()))
                     }
                 }
@@ -460,9 +941,53 @@ impl UniLyricApp {
                 options.chinese_conversion = ChineseConversionOptions {
                     config: Some(variant),
                     mode: ChineseConversionMode::Replace,
+                    custom_dictionary: self
+                        .app_settings
+                        .lock()
+                        .unwrap()
+                        .load_chinese_conversion_dictionary(),
+                    ..Default::default()
+                };
+
+                if !self.lyrics.conversion_in_progress {
+                    self.lyrics.chinese_conversion_baseline = self
+                        .lyrics
+                        .parsed_lyric_data
+                        .as_ref()
+                        .map(|data| data.lines.clone());
+                    self.lyrics.diff_baseline = Some(self.lyrics.output_text.clone());
+                    self.lyrics.mark_texts_changed();
+                }
+
+                self.dispatch_conversion_task(options);
+                ActionResult::Success
+            }
+            LyricsAction::ConvertChineseTranslation(variant) => {
+                info!("[Convert] 请求翻译简繁转换，变体: {variant:?}");
+
+                if self.lyrics.input_text.trim().is_empty()
+                    && self.lyrics.parsed_lyric_data.is_none()
+                {
+                    return ActionResult::Warning("没有歌词内容可以转换".to_string());
+                }
+
+                let mut options = self.build_conversion_options();
+                options.chinese_conversion = ChineseConversionOptions {
+                    translation_config: Some(variant),
+                    custom_dictionary: self
+                        .app_settings
+                        .lock()
+                        .unwrap()
+                        .load_chinese_conversion_dictionary(),
                     ..Default::default()
                 };
 
+                // 主歌词不变，转换统计没有意义，只记录输出差异
+                if !self.lyrics.conversion_in_progress {
+                    self.lyrics.diff_baseline = Some(self.lyrics.output_text.clone());
+                    self.lyrics.mark_texts_changed();
+                }
+
                 self.dispatch_conversion_task(options);
                 ActionResult::Success
             }
@@ -511,6 +1036,7 @@ impl UniLyricApp {
                 ActionResult::Success
             }
             LyricsAction::AddMetadata(key_to_add) => {
+                self.record_metadata_key_usage(&key_to_add);
                 self.lyrics.metadata_manager.add_new_ui_entry(key_to_add);
                 self.sync_and_regenerate_metadata();
                 ActionResult::Success
@@ -524,7 +1050,13 @@ impl UniLyricApp {
                     ActionResult::Error(AppError::Custom("无效的元数据索引".to_string()))
                 }
             }
-            LyricsAction::UpdateMetadataKey(..) | LyricsAction::UpdateMetadataValue(..) => {
+            LyricsAction::UpdateMetadataKey(_, new_key) => {
+                self.record_metadata_key_usage(&new_key);
+                self.sync_and_regenerate_metadata();
+                self.update_and_save_pinned_metadata();
+                ActionResult::Success
+            }
+            LyricsAction::UpdateMetadataValue(..) => {
                 self.sync_and_regenerate_metadata();
                 self.update_and_save_pinned_metadata();
                 ActionResult::Success
@@ -533,6 +1065,134 @@ impl UniLyricApp {
                 self.update_and_save_pinned_metadata();
                 ActionResult::Success
             }
+            LyricsAction::CopyAsAmllSubmission => self.copy_as_amll_submission(),
+            LyricsAction::CopyAsMarkdownTable => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_ref() else {
+                    return ActionResult::Warning("没有可复制的歌词数据".to_string());
+                };
+                self.egui_ctx
+                    .copy_text(lyrics_to_markdown_table(&parsed_data.lines));
+                self.ui.toasts.add(egui_toast::Toast {
+                    text: "已复制为 Markdown 表格".into(),
+                    kind: egui_toast::ToastKind::Success,
+                    options: egui_toast::ToastOptions::default().duration_in_seconds(2.0),
+                    style: Default::default(),
+                });
+                ActionResult::Success
+            }
+            LyricsAction::CopyLine(index, format) => {
+                let Some(line) = self
+                    .lyrics
+                    .parsed_lyric_data
+                    .as_ref()
+                    .and_then(|data| data.lines.get(index))
+                else {
+                    return ActionResult::Warning("要复制的行不存在".to_string());
+                };
+                self.egui_ctx.copy_text(format_line_for_copy(line, format));
+                self.ui.toasts.add(egui_toast::Toast {
+                    text: format!("已复制第 {} 行", index + 1).into(),
+                    kind: egui_toast::ToastKind::Success,
+                    options: egui_toast::ToastOptions::default().duration_in_seconds(2.0),
+                    style: Default::default(),
+                });
+                ActionResult::Success
+            }
+            LyricsAction::SetScriptRecording(recording) => {
+                let script = &mut self.ui.action_script;
+                if recording {
+                    if script.is_replaying() {
+                        return ActionResult::Warning("脚本正在运行，无法开始录制".to_string());
+                    }
+                    script.recorded.clear();
+                    info!("[ActionScript] 开始录制操作脚本。");
+                } else {
+                    info!(
+                        "[ActionScript] 停止录制，共录制 {} 个步骤。",
+                        script.recorded.len()
+                    );
+                }
+                script.recording = recording;
+                ActionResult::Success
+            }
+            LyricsAction::GenerateStatsReport => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_ref() else {
+                    return ActionResult::Warning("没有已解析的歌词可供统计".to_string());
+                };
+                let stats = lyrics_helper_rs::converter::processors::lyric_stats::compute_stats(
+                    &parsed_data.lines,
+                );
+                self.ui.stats_report = Some(stats.to_report());
+                ActionResult::Success
+            }
+            LyricsAction::InferMetadataFromLyrics => self.infer_metadata_from_lyrics(),
+            LyricsAction::ApplyMetadataSuggestions => {
+                let selected: Vec<MetadataSuggestion> =
+                    std::mem::take(&mut self.ui.metadata_suggestions)
+                        .into_iter()
+                        .filter(|suggestion| suggestion.selected)
+                        .collect();
+                if selected.is_empty() {
+                    return ActionResult::Warning("没有选中的元数据建议".to_string());
+                }
+
+                info!("[Metadata] 从歌词推断添加了 {} 条元数据。", selected.len());
+                for suggestion in selected {
+                    self.lyrics
+                        .metadata_manager
+                        .add_ui_entry_with_value(suggestion.key, suggestion.value);
+                }
+                self.sync_and_regenerate_metadata();
+                ActionResult::Success
+            }
+            LyricsAction::ClearUnpinnedMetadata => {
+                let removed_count = self.lyrics.metadata_manager.remove_unpinned_ui_entries();
+                if removed_count > 0 {
+                    info!("[Metadata] 已清除 {} 条非固定元数据。", removed_count);
+                    self.sync_and_regenerate_metadata();
+                    self.update_and_save_pinned_metadata();
+                }
+                ActionResult::Success
+            }
+            LyricsAction::SelectTranslationTrack(index) => {
+                self.lyrics.select_translation(index);
+                ActionResult::Success
+            }
+            LyricsAction::AddTranslationTrack(language) => {
+                let language = language.trim();
+                if language.is_empty() {
+                    return ActionResult::Warning("请输入翻译的语言标签".to_string());
+                }
+                if let Some(index) = self
+                    .lyrics
+                    .translation_tracks
+                    .iter()
+                    .position(|track| track.language.eq_ignore_ascii_case(language))
+                {
+                    self.lyrics.select_translation(index);
+                    return ActionResult::Warning(format!("已存在语言为 {language} 的翻译"));
+                }
+                self.lyrics
+                    .translation_tracks
+                    .push(TranslationTrack::new(language));
+                self.lyrics
+                    .select_translation(self.lyrics.translation_tracks.len() - 1);
+                self.ui.new_translation_language.clear();
+                ActionResult::Success
+            }
+            LyricsAction::RemoveTranslationTrack(index) => {
+                if index >= self.lyrics.translation_tracks.len() {
+                    return ActionResult::Success;
+                }
+                let mut tracks = self.lyrics.all_translation_tracks();
+                let removed = tracks.remove(index);
+                info!("[Translation] 已删除语言为 {} 的翻译。", removed.language);
+                self.lyrics.set_translation_tracks(tracks);
+                if !removed.lrc.trim().is_empty() {
+                    self.trigger_convert();
+                }
+                ActionResult::Success
+            }
             LyricsAction::LrcInputChanged(text, content_type) => {
                 let lrc_lines = match lyrics_helper_rs::converter::parsers::lrc_parser::parse_lrc(
                     &text,
@@ -561,18 +1221,17 @@ impl UniLyricApp {
                 ActionResult::Success
             }
             LyricsAction::MainInputChanged(text) => {
-                self.clear_lyrics_state_for_new_song_internal();
                 self.lyrics.input_text = text;
-                if !self.lyrics.conversion_in_progress && !self.lyrics.input_text.trim().is_empty()
-                {
-                    self.trigger_convert();
-                }
+                self.lyrics.mark_texts_changed();
+                self.lyrics.input_convert_deadline =
+                    Some(std::time::Instant::now() + MAIN_INPUT_CONVERT_DEBOUNCE);
                 ActionResult::Success
             }
             LyricsAction::LoadFetchedResult(result) => self.handle_load_full_lyrics_result(result),
             LyricsAction::ApplyFetchedLyrics(lyrics_and_metadata_box) => {
-                self.lyrics.current_warnings =
-                    lyrics_and_metadata_box.lyrics.parsed.warnings.clone();
+                self.lyrics.current_warnings = LyricWarning::from_parser_warnings(
+                    &lyrics_and_metadata_box.lyrics.parsed.warnings,
+                );
                 self.handle_load_full_lyrics_result(lyrics_and_metadata_box.lyrics)
             }
             LyricsAction::ApplyProcessor(processor) => {
@@ -582,11 +1241,24 @@ impl UniLyricApp {
 
                 info!("[Processor] 应用后处理器: {:?}", processor);
 
-                let (stripper_options, smoother_options) = {
+                let (
+                    stripper_options,
+                    smoother_options,
+                    regex_replace_options,
+                    line_wrap_options,
+                    punctuation_options,
+                    agent_recognizer_options,
+                    overlap_fix_options,
+                ) = {
                     let settings = self.app_settings.lock().unwrap();
                     (
                         settings.metadata_stripper.clone(),
                         settings.syllable_smoothing,
+                        settings.regex_replace.clone(),
+                        settings.line_wrap,
+                        settings.punctuation_normalize,
+                        settings.agent_recognizer.clone(),
+                        settings.syllable_overlap_fix,
                     )
                 };
 
@@ -598,26 +1270,362 @@ impl UniLyricApp {
                         );
                     }
                     ProcessorType::SyllableSmoother => {
-                        lyrics_helper_rs::converter::processors::syllable_smoothing::apply_smoothing(
-                            &mut parsed_data.lines,
-                            &smoother_options,
-                        );
+                        let history = SmoothingHistory::new(&parsed_data.lines, smoother_options);
+                        parsed_data.lines = history.current_lines().to_vec();
+                        self.lyrics.smoothing_history = Some(history);
                     }
                     ProcessorType::AgentRecognizer => {
-                        lyrics_helper_rs::converter::processors::agent_recognizer::recognize_agents(
+                        lyrics_helper_rs::converter::processors::agent_recognizer::recognize_agents_with_options(
                             parsed_data,
+                            &agent_recognizer_options,
                         );
                     }
-                }
-                self.dispatch_regeneration_task();
-                ActionResult::Success
-            }
-        }
-    }
-
-    fn handle_downloader_action(&mut self, action: DownloaderAction) -> ActionResult {
-        match action {
-            DownloaderAction::SetTitle(title) => {
+                    ProcessorType::RegexReplace => {
+                        match lyrics_helper_rs::converter::processors::regex_replacer::apply_regex_replacements(
+                            &mut parsed_data.lines,
+                            &regex_replace_options,
+                        ) {
+                            Ok(changed_lines) => {
+                                info!("[Processor] 正则替换修改了 {} 行。", changed_lines);
+                            }
+                            Err(e) => {
+                                return ActionResult::Warning(format!("正则替换规则无效: {e}"));
+                            }
+                        }
+                    }
+                    ProcessorType::LineWrapper => {
+                        let added_lines =
+                            lyrics_helper_rs::converter::processors::line_wrapper::wrap_long_lines(
+                                &mut parsed_data.lines,
+                                &line_wrap_options,
+                            );
+                        info!(
+                            "[Processor] 智能换行拆分出了 {} 行（上限 {} 字）。",
+                            added_lines, line_wrap_options.max_chars
+                        );
+                    }
+                    ProcessorType::PunctuationNormalizer => {
+                        let changed_lines = lyrics_helper_rs::converter::processors::punctuation_normalizer::normalize_punctuation(
+                            &mut parsed_data.lines,
+                            &punctuation_options,
+                        );
+                        info!("[Processor] 标点规范化修改了 {} 行。", changed_lines);
+                    }
+                    ProcessorType::SyllableOverlapFixer => {
+                        let changed_syllables = lyrics_helper_rs::converter::processors::syllable_overlap_fixer::fix_syllable_overlaps(
+                            &mut parsed_data.lines,
+                            &overlap_fix_options,
+                        );
+                        info!("[Processor] 修复了 {} 个音节的时间重叠。", changed_syllables);
+                    }
+                }
+                if processor != ProcessorType::SyllableSmoother {
+                    self.lyrics.smoothing_history = None;
+                }
+                self.lyrics.diff_baseline = Some(self.lyrics.output_text.clone());
+                self.lyrics.mark_texts_changed();
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::AdjustSmoothingIterations(delta) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供处理".to_string());
+                };
+                let Some(history) = self.lyrics.smoothing_history.as_mut() else {
+                    return ActionResult::Warning("尚未应用音节平滑".to_string());
+                };
+                if parsed_data.lines.as_slice() != history.current_lines() {
+                    self.lyrics.smoothing_history = None;
+                    return ActionResult::Warning(
+                        "歌词在平滑后已被修改，无法再调整平滑次数".to_string(),
+                    );
+                }
+
+                let target = history.current.saturating_add_signed(delta);
+                parsed_data.lines = history.step_to(target).to_vec();
+                info!("[Processor] 音节平滑迭代次数调整为 {}。", target);
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::MergeLineWithNext(index) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                if !lyrics_helper_rs::converter::processors::line_editor::merge_with_next_line(
+                    &mut parsed_data.lines,
+                    index,
+                ) {
+                    return ActionResult::Warning("该行没有可合并的下一行".to_string());
+                }
+
+                info!("[LineEditor] 已将第 {} 行与下一行合并。", index + 1);
+                self.ui.line_editor_selected_line = Some(index);
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::SplitLineAt(line_index, syllable_index) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                if !lyrics_helper_rs::converter::processors::line_editor::split_line_at_syllable(
+                    &mut parsed_data.lines,
+                    line_index,
+                    syllable_index,
+                ) {
+                    return ActionResult::Warning("无法在该位置拆分此行".to_string());
+                }
+
+                info!(
+                    "[LineEditor] 已在第 {} 行的第 {} 个音节处拆分。",
+                    line_index + 1,
+                    syllable_index + 1
+                );
+                self.ui.line_editor_selected_line = Some(line_index);
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::SetLineNote(line_index, note) => {
+                if note.trim().is_empty() {
+                    self.lyrics.line_notes.remove(&line_index);
+                } else {
+                    self.lyrics.line_notes.insert(line_index, note);
+                }
+                ActionResult::Success
+            }
+            LyricsAction::InsertInterlude(start_ms, end_ms) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                let Some(index) =
+                    lyrics_helper_rs::converter::processors::line_editor::insert_interlude(
+                        &mut parsed_data.lines,
+                        start_ms,
+                        end_ms,
+                    )
+                else {
+                    return ActionResult::Warning("间奏的结束时间必须晚于开始时间".to_string());
+                };
+
+                info!(
+                    "[LineEditor] 已在第 {} 行插入间奏标记 ({}ms - {}ms)。",
+                    index + 1,
+                    start_ms,
+                    end_ms
+                );
+                self.ui.line_editor_selected_line = Some(index);
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::RemoveInterlude(index) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                if !lyrics_helper_rs::converter::processors::line_editor::remove_interlude(
+                    &mut parsed_data.lines,
+                    index,
+                ) {
+                    return ActionResult::Warning("该行不是间奏标记".to_string());
+                }
+
+                info!("[LineEditor] 已删除第 {} 行的间奏标记。", index + 1);
+                self.ui.line_editor_selected_line = None;
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::ScaleTimings(factor) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                if !lyrics_helper_rs::converter::processors::line_editor::scale_timings(
+                    &mut parsed_data.lines,
+                    factor,
+                ) {
+                    return ActionResult::Warning("缩放比例必须大于 0".to_string());
+                }
+
+                info!("[LineEditor] 已将所有时间戳缩放为原来的 {} 倍。", factor);
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::TrimSilence(start_at_ms, trim_tail) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                let Some(trim) = lyrics_helper_rs::converter::processors::line_editor::trim_silence(
+                    &mut parsed_data.lines,
+                    start_at_ms,
+                    trim_tail,
+                ) else {
+                    return ActionResult::Warning("没有可裁剪的静音".to_string());
+                };
+
+                info!(
+                    "[LineEditor] 已裁剪首尾静音：时间轴平移 {}ms，删除 {} 个间奏标记行，结尾静音{}裁剪。",
+                    trim.shift_ms,
+                    trim.removed_lines,
+                    if trim.tail_trimmed { "已" } else { "未" }
+                );
+                self.ui.line_editor_selected_line = None;
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::SetLineTiming(index, start_ms, end_ms) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                if !lyrics_helper_rs::converter::processors::line_editor::set_line_timing(
+                    &mut parsed_data.lines,
+                    index,
+                    start_ms,
+                    end_ms,
+                ) {
+                    return ActionResult::Warning("行的结束时间必须晚于开始时间".to_string());
+                }
+
+                debug!(
+                    "[Timeline] 第 {} 行的时间调整为 {}ms - {}ms。",
+                    index + 1,
+                    start_ms,
+                    end_ms
+                );
+                self.ui.line_editor_selected_line = Some(index);
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::SetSyllableTiming(line_index, syllable_index, start_ms, end_ms) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                if !lyrics_helper_rs::converter::processors::line_editor::set_syllable_timing(
+                    &mut parsed_data.lines,
+                    line_index,
+                    syllable_index,
+                    start_ms,
+                    end_ms,
+                ) {
+                    return ActionResult::Warning("无法调整该音节的时间".to_string());
+                }
+
+                debug!(
+                    "[Timeline] 第 {} 行第 {} 个音节的时间调整为 {}ms - {}ms。",
+                    line_index + 1,
+                    syllable_index + 1,
+                    start_ms,
+                    end_ms
+                );
+                self.ui.line_editor_selected_line = Some(line_index);
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::CopyTimingToRepeatedLines(source, targets) => {
+                let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_mut() else {
+                    return ActionResult::Warning("没有已解析的歌词可供编辑".to_string());
+                };
+
+                let updated =
+                    lyrics_helper_rs::converter::processors::repeated_lines::copy_syllable_timing(
+                        &mut parsed_data.lines,
+                        source,
+                        &targets,
+                    );
+                if updated == 0 {
+                    return ActionResult::Warning("没有可复制逐字时间的相同行".to_string());
+                }
+
+                info!(
+                    "[LineEditor] 已将第 {} 行的逐字时间复制到 {} 个相同行。",
+                    source + 1,
+                    updated
+                );
+                self.ui.toasts.add(egui_toast::Toast {
+                    text: format!("已将逐字时间复制到 {updated} 个相同行").into(),
+                    kind: egui_toast::ToastKind::Success,
+                    options: egui_toast::ToastOptions::default().duration_in_seconds(2.0),
+                    style: Default::default(),
+                });
+                self.ui.line_editor_selected_line = Some(source);
+                self.dispatch_regeneration_task();
+                ActionResult::Success
+            }
+            LyricsAction::RestoreSession => {
+                let Some(session) = self.ui.pending_session_restore.take() else {
+                    return ActionResult::Warning("没有可恢复的会话".to_string());
+                };
+
+                info!("[Session] 正在恢复上次的工作区会话。");
+                self.clear_lyrics_state_for_new_song_internal();
+                self.lyrics.input_text = session.input_text.clone();
+                self.lyrics.source_format = session.source_format;
+                self.lyrics.target_format = session.target_format;
+                self.lyrics
+                    .set_translation_tracks(session.restored_translation_tracks());
+                self.lyrics.display_romanization_lrc_output = session.romanization_lrc.clone();
+                self.lyrics.line_notes = session.line_notes.clone();
+                self.lyrics
+                    .legacy_line_notes
+                    .clone_from(&session.legacy_line_notes);
+                self.lyrics.last_opened_file_path = session.last_opened_file_path.clone();
+                self.last_saved_session = Some(session);
+
+                if !self.lyrics.input_text.trim().is_empty() {
+                    self.trigger_convert();
+                }
+                ActionResult::Success
+            }
+            LyricsAction::LintLyrics => {
+                if self.lyrics.parsed_lyric_data.is_none() {
+                    return ActionResult::Warning("没有已解析的歌词可供检查".to_string());
+                }
+
+                let issue_count = self.run_lyrics_lint();
+                if issue_count == 0 {
+                    self.ui.toasts.add(egui_toast::Toast {
+                        text: "歌词检查完成，未发现问题".into(),
+                        kind: egui_toast::ToastKind::Success,
+                        options: egui_toast::ToastOptions::default().duration_in_seconds(3.0),
+                        style: Default::default(),
+                    });
+                } else {
+                    self.ui.show_warnings_panel = true;
+                }
+                ActionResult::Success
+            }
+            LyricsAction::DiscardSession => {
+                self.ui.pending_session_restore = None;
+                if let Err(e) = WorkspaceSession::clear() {
+                    warn!("[Session] 删除会话文件失败: {e}");
+                }
+                ActionResult::Success
+            }
+            LyricsAction::SaveFormatOptions(format_options) => {
+                self.ui.show_format_options_window = false;
+
+                if let Ok(mut settings) = self.app_settings.lock() {
+                    settings.format_options = *format_options;
+                    if let Err(e) = settings.save() {
+                        return ActionResult::Warning(format!("保存格式选项失败: {e}"));
+                    }
+                }
+
+                if self.lyrics.parsed_lyric_data.is_some() {
+                    self.dispatch_regeneration_task();
+                }
+                ActionResult::Success
+            }
+        }
+    }
+
+    fn handle_downloader_action(&mut self, action: DownloaderAction) -> ActionResult {
+        match action {
+            DownloaderAction::SetTitle(title) => {
                 self.downloader.title_input = title;
                 ActionResult::Success
             }
@@ -638,11 +1646,26 @@ impl UniLyricApp {
 
                 ActionResult::Success
             }
+            DownloaderAction::ScheduleAutoSearch => {
+                let auto_search_enabled = self
+                    .app_settings
+                    .lock()
+                    .is_ok_and(|settings| settings.downloader_auto_search);
+                self.downloader.auto_search_deadline = (auto_search_enabled
+                    && !self.downloader.title_input.trim().is_empty())
+                .then(|| std::time::Instant::now() + DOWNLOADER_AUTO_SEARCH_DEBOUNCE);
+                ActionResult::Success
+            }
             DownloaderAction::PerformSearch => {
+                self.downloader.auto_search_deadline = None;
                 if self.downloader.title_input.trim().is_empty() {
                     return ActionResult::Warning("歌曲名不能为空".to_string());
                 }
                 self.downloader.search_state = SearchState::Searching;
+                self.downloader.manual_search_query = Some((
+                    self.downloader.title_input.clone(),
+                    self.downloader.artist_input.clone(),
+                ));
                 self.downloader.preview_state = PreviewState::Idle;
                 self.downloader.selected_result_for_preview = None;
                 self.downloader.selected_full_lyrics = None;
@@ -682,13 +1705,32 @@ impl UniLyricApp {
                 ActionResult::Success
             }
             DownloaderAction::SearchCompleted(result) => {
+                let auto_preview_first = self
+                    .app_settings
+                    .lock()
+                    .is_ok_and(|settings| settings.downloader_auto_preview_first);
+                let first_result = result.as_ref().ok().and_then(|results| {
+                    results
+                        .iter()
+                        .find(|result| !self.downloader.is_result_hidden(result))
+                        .cloned()
+                });
+
                 self.downloader.search_state = match result {
                     Ok(results) => SearchState::Success(results),
                     Err(e) => SearchState::Error(e.to_string()),
                 };
+
+                if auto_preview_first && let Some(first_result) = first_result {
+                    self.send_action(UserAction::Downloader(Box::new(
+                        DownloaderAction::SelectResultForPreview(first_result),
+                    )));
+                }
                 ActionResult::Success
             }
             DownloaderAction::SelectResultForPreview(search_result) => {
+                self.downloader.audio_preview.stop();
+                self.downloader.audio_preview_loading = None;
                 self.downloader.selected_result_for_preview = Some(search_result.clone());
                 self.downloader.preview_state = PreviewState::Loading;
                 self.downloader.selected_full_lyrics = None;
@@ -721,6 +1763,13 @@ impl UniLyricApp {
                     Ok(full_lyrics) => {
                         let main_text = self.generate_lrc_from_main_track(&full_lyrics.parsed);
 
+                        if let Some(result) = &self.downloader.selected_result_for_preview {
+                            self.downloader.previewed_summaries.insert(
+                                (result.provider_name.clone(), result.provider_id.clone()),
+                                ResultPreviewSummary::from_lines(&full_lyrics.parsed.lines),
+                            );
+                        }
+
                         self.downloader.preview_state =
                             PreviewState::Success(main_text.to_string());
                         self.downloader.selected_full_lyrics = Some(full_lyrics);
@@ -731,6 +1780,78 @@ impl UniLyricApp {
                 }
                 ActionResult::Success
             }
+            DownloaderAction::SetWordTimedOnly(enabled) => {
+                self.downloader.word_timed_only = enabled;
+
+                if let Ok(mut settings) = self.app_settings.lock() {
+                    settings.downloader_word_timed_only = enabled;
+                    if let Err(e) = settings.save() {
+                        return ActionResult::Warning(format!("保存下载器设置失败: {e}"));
+                    }
+                }
+                ActionResult::Success
+            }
+            DownloaderAction::ToggleAudioPreview => {
+                if self.downloader.audio_preview.is_playing()
+                    || self.downloader.audio_preview_loading.is_some()
+                {
+                    self.downloader.audio_preview.stop();
+                    self.downloader.audio_preview_loading = None;
+                    return ActionResult::Success;
+                }
+                let Some(search_result) = self.downloader.selected_result_for_preview.clone()
+                else {
+                    return ActionResult::Warning("请先选择一个搜索结果".to_string());
+                };
+                self.downloader.audio_preview_loading = Some((
+                    search_result.provider_name.clone(),
+                    search_result.provider_id.clone(),
+                ));
+
+                let helper = self.lyrics_helper_state.helper.clone();
+                let action_tx = self.action_tx.clone();
+
+                self.tokio_runtime.spawn(async move {
+                    let result = {
+                        let future_result = {
+                            helper.lock().await.get_audio_preview(
+                                &search_result.provider_name,
+                                &search_result.provider_id,
+                                PREVIEW_MAX_BYTES,
+                            )
+                        };
+                        match future_result {
+                            Ok(future) => future.await,
+                            Err(e) => Err(e),
+                        }
+                    };
+                    let _ = action_tx.send(UserAction::Downloader(Box::new(
+                        DownloaderAction::AudioPreviewLoaded(result.map_err(AppError::from)),
+                    )));
+                });
+
+                ActionResult::Success
+            }
+            DownloaderAction::AudioPreviewLoaded(result) => {
+                // 下载期间切换了结果或取消了试听时，丢弃过时的音频
+                let current_key = self
+                    .downloader
+                    .selected_result_for_preview
+                    .as_ref()
+                    .map(|r| (r.provider_name.clone(), r.provider_id.clone()));
+                let loading_key = self.downloader.audio_preview_loading.take();
+                if loading_key.is_none() || loading_key != current_key {
+                    return ActionResult::Success;
+                }
+
+                match result
+                    .map_err(|e| e.to_string())
+                    .and_then(|audio_data| self.downloader.audio_preview.play(audio_data))
+                {
+                    Ok(()) => ActionResult::Success,
+                    Err(e) => ActionResult::Warning(format!("试听失败: {e}")),
+                }
+            }
             DownloaderAction::ApplyAndClose => {
                 if let Some(lyrics_to_apply) = self.downloader.selected_full_lyrics.clone() {
                     self.send_action(UserAction::Lyrics(Box::new(
@@ -742,20 +1863,377 @@ impl UniLyricApp {
                 }
                 ActionResult::Success
             }
+            DownloaderAction::ApplyAndKeepOpen => {
+                let Some(lyrics_to_apply) = self.downloader.selected_full_lyrics.clone() else {
+                    return ActionResult::Warning("没有可应用的歌词".to_string());
+                };
+                self.downloader.applied_result = self
+                    .downloader
+                    .selected_result_for_preview
+                    .as_ref()
+                    .map(|r| (r.provider_name.clone(), r.provider_id.clone()));
+                self.send_action(UserAction::Lyrics(Box::new(
+                    LyricsAction::LoadFetchedResult(lyrics_to_apply),
+                )));
+                self.ui.toasts.add(egui_toast::Toast {
+                    text: "已应用到编辑器，可继续尝试其他结果".into(),
+                    kind: egui_toast::ToastKind::Success,
+                    options: egui_toast::ToastOptions::default()
+                        .duration_in_seconds(2.0)
+                        .show_progress(true),
+                    style: Default::default(),
+                });
+                ActionResult::Success
+            }
             DownloaderAction::Close => {
+                self.downloader.audio_preview.stop();
                 self.ui.current_view = AppView::Editor;
-                self.downloader = DownloaderState::default();
+                self.downloader = DownloaderState::new(&self.app_settings.lock().unwrap());
+                ActionResult::Success
+            }
+            DownloaderAction::SelectBatchDownloadDir => {
+                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                    self.ui.batch_download.output_dir = Some(path);
+                }
+                ActionResult::Success
+            }
+            DownloaderAction::StartBatchDownload => self.start_batch_download(),
+            DownloaderAction::CancelBatchDownload => {
+                if let Some(token) = &self.ui.batch_download.cancellation_token {
+                    info!("[BatchDownload] 请求取消批量下载。");
+                    token.cancel();
+                }
+                ActionResult::Success
+            }
+            DownloaderAction::BatchDownloadProgress(index, status) => {
+                if let Some(entry) = self.ui.batch_download.entries.get_mut(index) {
+                    entry.status = status;
+                }
+                ActionResult::Success
+            }
+            DownloaderAction::BatchDownloadFinished => {
+                self.ui.batch_download.cancellation_token = None;
+                let (saved, failed) = self.ui.batch_download.summary();
+                info!("[BatchDownload] 批量下载结束，成功 {saved} 首，失败 {failed} 首。");
+                self.ui.toasts.add(egui_toast::Toast {
+                    text: format!("批量下载结束：成功 {saved} 首，失败 {failed} 首").into(),
+                    kind: if failed == 0 {
+                        egui_toast::ToastKind::Success
+                    } else {
+                        egui_toast::ToastKind::Warning
+                    },
+                    options: egui_toast::ToastOptions::default()
+                        .duration_in_seconds(3.0)
+                        .show_progress(true),
+                    style: Default::default(),
+                });
                 ActionResult::Success
             }
         }
     }
 
+    /// 解析批量下载列表，在后台依次搜索每首歌并把最佳匹配保存到输出目录。
+    ///
+    /// 每首歌处理前后都会发送进度事件，全部结束或被取消后发送 `BatchDownloadFinished`。
+    fn start_batch_download(&mut self) -> ActionResult {
+        if self.ui.batch_download.is_running() {
+            return ActionResult::Warning("批量下载正在进行中".to_string());
+        }
+        let Some(output_dir) = self.ui.batch_download.output_dir.clone() else {
+            return ActionResult::Warning("请先选择输出目录".to_string());
+        };
+        let entries = batch_download::parse_queries(&self.ui.batch_download.queries_input);
+        if entries.is_empty() {
+            return ActionResult::Warning(
+                "请输入要下载的歌曲，每行一首：标题 - 艺术家".to_string(),
+            );
+        }
+        if self.lyrics_helper_state.provider_state != ProviderState::Ready {
+            self.trigger_provider_loading();
+            return ActionResult::Warning("歌词提供商尚未就绪，请稍后再试".to_string());
+        }
+
+        let target_format = self.ui.batch_download.target_format;
+        let job = {
+            let mut settings = self.app_settings.lock().unwrap();
+            settings.batch_download_output_directory = Some(output_dir.clone());
+            settings.batch_download_target_format = target_format;
+            if let Err(e) = settings.save() {
+                warn!("[BatchDownload] 保存批量下载设置失败: {e}");
+            }
+            BatchDownloadJob {
+                search_mode: crate::app_fetch_core::regular_search_mode(&settings, false),
+                target_format,
+                conversion_options: settings.conversion_options(),
+                output_dir,
+                output_text_options: settings.output_text_options(),
+            }
+        };
+
+        info!("[BatchDownload] 开始批量下载 {} 首歌。", entries.len());
+        let cancellation_token = CancellationToken::new();
+        self.ui.batch_download.entries = entries.clone();
+        self.ui.batch_download.cancellation_token = Some(cancellation_token.clone());
+
+        let helper = self.lyrics_helper_state.helper.clone();
+        let action_tx = self.action_tx.clone();
+        self.tokio_runtime.spawn(async move {
+            let send_progress = |index, status| {
+                let _ = action_tx.send(UserAction::Downloader(Box::new(
+                    DownloaderAction::BatchDownloadProgress(index, status),
+                )));
+            };
+            for (index, entry) in entries.iter().enumerate() {
+                if cancellation_token.is_cancelled() {
+                    break;
+                }
+                send_progress(index, BatchDownloadStatus::Searching);
+                match job.download(&helper, entry, &cancellation_token).await {
+                    Some(status) => send_progress(index, status),
+                    None => {
+                        send_progress(index, BatchDownloadStatus::Pending);
+                        break;
+                    }
+                }
+            }
+            let _ = action_tx.send(UserAction::Downloader(Box::new(
+                DownloaderAction::BatchDownloadFinished,
+            )));
+        });
+
+        ActionResult::Success
+    }
+
+    /// 将文件加入已打开文件列表，并切换到其中第一个文件。
+    ///
+    /// 已经打开过的文件不会被重新加载，以保留其中未保存的编辑。
+    fn open_documents(&mut self, files: Vec<(std::path::PathBuf, String)>) {
+        let mut first_index = None;
+        for (path, content) in files {
+            let index = if let Some(index) = self
+                .lyrics
+                .open_documents
+                .iter()
+                .position(|document| document.path == path)
+            {
+                index
+            } else {
+                let source_format = path
+                    .extension()
+                    .and_then(|s| s.to_str())
+                    .and_then(LyricFormat::from_string)
+                    .map(|format| {
+                        // Apple Music 与 Musixmatch 的歌词都以 .json 保存
+                        if format == LyricFormat::AppleMusicJson
+                            && lyrics_helper_rs::converter::parsers::musixmatch_parser::looks_like_musixmatch(&content)
+                        {
+                            LyricFormat::Musixmatch
+                        } else {
+                            format
+                        }
+                    })
+                    .unwrap_or(self.lyrics.source_format);
+                info!("[Documents] 已打开文件: {path:?}");
+                self.lyrics
+                    .open_documents
+                    .push(OpenDocument::new(path, content, source_format));
+                self.lyrics.open_documents.len() - 1
+            };
+            first_index.get_or_insert(index);
+        }
+
+        if let Some(index) = first_index {
+            self.switch_document(index);
+        }
+    }
+
+    /// 保存当前文件的编辑状态，并将指定的已打开文件载入编辑器。
+    fn switch_document(&mut self, index: usize) {
+        if self.lyrics.active_document == Some(index)
+            && self.lyrics.editing_active_document() == Some(index)
+        {
+            return;
+        }
+        self.lyrics.sync_active_document();
+
+        let Some(document) = self.lyrics.open_documents.get(index).cloned() else {
+            return;
+        };
+        self.clear_lyrics_state_for_new_song_internal();
+        self.lyrics.active_document = Some(index);
+        self.lyrics.last_opened_file_path = Some(document.path);
+        self.lyrics.metadata_source_is_download = false;
+        self.lyrics.input_text = document.input_text;
+        self.lyrics.source_format = document.source_format;
+        self.lyrics
+            .set_translation_tracks(document.translation_tracks);
+        self.lyrics.display_romanization_lrc_output = document.romanization_lrc;
+        self.lyrics.line_notes = document.line_notes;
+        self.trigger_convert();
+    }
+
+    /// 从已打开文件列表中移除文件。关闭的是当前文件时，切换到相邻的文件。
+    fn close_document(&mut self, index: usize) {
+        let removed = self.lyrics.open_documents.remove(index);
+        info!("[Documents] 已关闭文件: {:?}", removed.path);
+
+        match self.lyrics.active_document {
+            Some(active) if active == index => {
+                self.lyrics.active_document = None;
+                if self.lyrics.open_documents.is_empty() {
+                    self.clear_lyrics_state_for_new_song_internal();
+                    self.lyrics.last_opened_file_path = None;
+                } else {
+                    self.switch_document(index.min(self.lyrics.open_documents.len() - 1));
+                }
+            }
+            Some(active) if active > index => {
+                self.lyrics.active_document = Some(active - 1);
+            }
+            _ => {}
+        }
+    }
+
+    /// 重新解析输入（不清理元数据行），从被清理器识别为元数据的行中生成元数据建议。
+    ///
+    /// 转换时元数据行可能已被清理，因此不能直接使用已解析的歌词。
+    fn infer_metadata_from_lyrics(&mut self) -> ActionResult {
+        if self.lyrics.input_text.trim().is_empty() {
+            return ActionResult::Warning("没有歌词内容可供推断".to_string());
+        }
+
+        let mut options = self.build_conversion_options();
+        let stripper_options = options.metadata_stripper.clone();
+        options
+            .metadata_stripper
+            .flags
+            .remove(MetadataStripperFlags::ENABLED);
+        options.chinese_conversion = ChineseConversionOptions::default();
+
+        let input = ConversionInput {
+            main_lyric: InputFile::new(
+                self.lyrics.input_text.clone(),
+                self.lyrics.source_format,
+                None,
+                None,
+            ),
+            translations: vec![],
+            romanizations: vec![],
+            target_format: self.lyrics.target_format,
+            user_metadata_overrides: None,
+            additional_metadata: None,
+        };
+        let parsed = match lyrics_helper_rs::converter::parse_and_merge(&input, &options) {
+            Ok(parsed) => parsed,
+            Err(e) => return ActionResult::Warning(format!("解析歌词失败: {e}")),
+        };
+
+        let candidates =
+            lyrics_helper_rs::converter::processors::metadata_stripper::extract_metadata_candidates(
+                &parsed.lines,
+                &stripper_options,
+            );
+
+        let mut suggestions: Vec<MetadataSuggestion> = Vec::new();
+        for candidate in candidates {
+            let Ok(key) = candidate.key.parse::<CanonicalMetadataKey>() else {
+                continue;
+            };
+            let already_present = self
+                .lyrics
+                .metadata_manager
+                .ui_entries
+                .iter()
+                .any(|entry| entry.key == key && entry.value == candidate.value)
+                || suggestions
+                    .iter()
+                    .any(|s| s.key == key && s.value == candidate.value);
+            if !already_present {
+                suggestions.push(MetadataSuggestion {
+                    key,
+                    value: candidate.value,
+                    source_key: candidate.key,
+                    selected: true,
+                });
+            }
+        }
+
+        if suggestions.is_empty() {
+            return ActionResult::Warning("未在歌词中找到新的元数据".to_string());
+        }
+
+        info!(
+            "[Metadata] 从歌词中推断出 {} 条元数据建议。",
+            suggestions.len()
+        );
+        self.ui.metadata_suggestions = suggestions;
+        self.ui.show_metadata_panel = true;
+        ActionResult::Success
+    }
+
+    /// 根据当前编辑状态构建工作区会话快照。
+    fn current_workspace_session(&self) -> WorkspaceSession {
+        WorkspaceSession {
+            input_text: self.lyrics.input_text.clone(),
+            source_format: self.lyrics.source_format,
+            target_format: self.lyrics.target_format,
+            translation_lrc: String::new(),
+            translation_tracks: self.lyrics.all_translation_tracks(),
+            line_notes: self.lyrics.line_notes.clone(),
+            legacy_line_notes: BTreeMap::new(),
+            romanization_lrc: self.lyrics.display_romanization_lrc_output.clone(),
+            last_opened_file_path: self.lyrics.last_opened_file_path.clone(),
+        }
+    }
+
+    /// 按设置的间隔自动保存工作区会话。
+    ///
+    /// `force` 为 `true` 时忽略间隔立即保存（例如在关闭窗口时）。
+    /// 在用户决定是否恢复上次会话之前不会保存，以免覆盖尚未恢复的内容。
+    pub(super) fn autosave_session(&mut self, force: bool) {
+        if self.ui.pending_session_restore.is_some() {
+            return;
+        }
+
+        let (enabled, interval_secs) = {
+            let settings = self.app_settings.lock().unwrap();
+            (
+                settings.session_autosave_enabled,
+                settings.session_autosave_interval_secs,
+            )
+        };
+        if !enabled {
+            return;
+        }
+        if !force
+            && self.last_session_autosave.elapsed()
+                < std::time::Duration::from_secs(interval_secs.max(1))
+        {
+            return;
+        }
+        self.last_session_autosave = std::time::Instant::now();
+
+        let session = self.current_workspace_session();
+        if self.last_saved_session.as_ref() == Some(&session) {
+            return;
+        }
+
+        match session.save() {
+            Ok(()) => self.last_saved_session = Some(session),
+            Err(e) => warn!("[Session] 保存工作区会话失败: {e}"),
+        }
+    }
+
     pub(super) fn clear_lyrics_state_for_new_song_internal(&mut self) {
         info!("[State] 正在为新歌曲清理歌词状态。");
         self.lyrics.input_text.clear();
         self.lyrics.output_text.clear();
-        self.lyrics.display_translation_lrc_output.clear();
+        self.lyrics.mark_texts_changed();
+        self.lyrics.set_translation_tracks(Vec::new());
         self.lyrics.display_romanization_lrc_output.clear();
+        self.lyrics.line_notes.clear();
+        self.lyrics.line_note_anchors.clear();
+        self.lyrics.legacy_line_notes.clear();
         self.lyrics.parsed_lyric_data = None;
         self.lyrics.loaded_translation_lrc = None;
         self.lyrics.loaded_romanization_lrc = None;
@@ -765,16 +2243,22 @@ impl UniLyricApp {
             .retain(|entry| entry.is_pinned);
         self.lyrics.metadata_manager.store.clear();
         self.lyrics.current_warnings.clear();
+        self.lyrics.lint_issues = None;
+        self.lyrics.diff_baseline = None;
+        self.lyrics.smoothing_history = None;
+        self.lyrics.converted_input_hash = None;
     }
 
     fn handle_file_action(&mut self, action: FileAction) -> ActionResult {
         match action {
             FileAction::Open => {
-                self.clear_lyrics_state_for_new_song_internal();
-                self.lyrics.metadata_source_is_download = false;
                 crate::io::handle_open_file(self);
                 ActionResult::Success
             }
+            FileAction::OpenMultiple => {
+                crate::io::handle_open_multiple_files(self);
+                ActionResult::Success
+            }
             FileAction::Save => {
                 crate::io::handle_save_file(self);
                 ActionResult::Success
@@ -789,7 +2273,303 @@ impl UniLyricApp {
                 self.trigger_convert();
                 ActionResult::Success
             }
+            FileAction::ExportTranslationLrc => {
+                self.export_auxiliary_lrc(LrcContentType::Translation)
+            }
+            FileAction::ExportRomanizationLrc => {
+                self.export_auxiliary_lrc(LrcContentType::Romanization)
+            }
+            FileAction::PickLrcMergeFile(target) => {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("LRC File", &["lrc"])
+                    .pick_file()
+                {
+                    *self.ui.lrc_merge.path_mut(target) = Some(path);
+                    self.ui.lrc_merge.result = None;
+                }
+                ActionResult::Success
+            }
+            FileAction::MergeLrcFiles => self.merge_lrc_files(),
+            FileAction::SaveLrcMergeResult => {
+                crate::io::handle_save_lrc_merge_result(self);
+                ActionResult::Success
+            }
+            FileAction::ExportLyricImage => self.export_lyric_image(),
+            FileAction::LyricImageExported(result) => {
+                self.ui.lyric_image_exporting = false;
+                match result {
+                    Ok(_) => ActionResult::Success,
+                    Err(e) => ActionResult::Warning(e),
+                }
+            }
+            FileAction::SaveStatsReport => {
+                crate::io::handle_save_stats_report(self);
+                ActionResult::Success
+            }
+            FileAction::SaveActionScript => {
+                crate::io::handle_save_action_script(self);
+                ActionResult::Success
+            }
+            FileAction::RunActionScript => {
+                if self.ui.action_script.recording {
+                    return ActionResult::Warning("录制操作脚本时不能运行脚本".to_string());
+                }
+                if self.lyrics.parsed_lyric_data.is_none() {
+                    return ActionResult::Warning("没有已解析的歌词可供处理".to_string());
+                }
+                match crate::io::handle_run_action_script(self) {
+                    Ok(()) => ActionResult::Success,
+                    Err(msg) => ActionResult::Warning(msg),
+                }
+            }
+            FileAction::LoadTimelineAudio => {
+                let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Audio", &["mp3", "flac", "wav", "ogg", "m4a"])
+                    .pick_file()
+                else {
+                    return ActionResult::Success;
+                };
+
+                info!("[Timeline] 正在解码音频波形: {path:?}");
+                self.ui.timeline_waveform_loading = true;
+                let action_tx = self.action_tx.clone();
+                self.tokio_runtime.spawn_blocking(move || {
+                    let result = crate::waveform::Waveform::from_file(&path)
+                        .map(Arc::new)
+                        .map_err(AppError::Custom);
+                    let _ =
+                        action_tx.send(UserAction::File(FileAction::TimelineAudioLoaded(result)));
+                });
+                ActionResult::Success
+            }
+            FileAction::TimelineAudioLoaded(result) => {
+                self.ui.timeline_waveform_loading = false;
+                match result {
+                    Ok(waveform) => {
+                        info!(
+                            "[Timeline] 已加载音频波形 {}，检测到 {} 个起音点。",
+                            waveform.file_name,
+                            waveform.onsets.len()
+                        );
+                        self.ui.timeline_waveform = Some(waveform);
+                        ActionResult::Success
+                    }
+                    Err(e) => ActionResult::Warning(format!("加载音频波形失败: {e}")),
+                }
+            }
+        }
+    }
+
+    /// 按时间戳对齐合并向导中选择的主歌词、翻译和罗马音 LRC，生成 TTML。
+    ///
+    /// 超出容差、无法对应到主歌词行的辅助行会被丢弃，并在对齐统计中报告。
+    fn merge_lrc_files(&mut self) -> ActionResult {
+        fn read_input(path: &std::path::Path, language: Option<&str>) -> AppResult<InputFile> {
+            let decoded = crate::io::read_lyric_file(path)
+                .ok_or_else(|| AppError::Custom(format!("读取文件 {} 失败", path.display())))?;
+            Ok(InputFile::new(
+                decoded.content,
+                LyricFormat::Lrc,
+                language.map(str::to_string),
+                None,
+            ))
+        }
+
+        fn build_input(
+            wizard: &crate::types::LrcMergeWizard,
+            main_path: &std::path::Path,
+        ) -> AppResult<ConversionInput> {
+            let read_optional = |target: LrcContentType| -> AppResult<Vec<InputFile>> {
+                let path = match target {
+                    LrcContentType::Translation => &wizard.translation_path,
+                    LrcContentType::Romanization => &wizard.romanization_path,
+                };
+                path.as_deref()
+                    .map(|path| read_input(path, wizard.language(target)))
+                    .transpose()
+                    .map(|file| file.into_iter().collect())
+            };
+            Ok(ConversionInput {
+                main_lyric: read_input(main_path, None)?,
+                translations: read_optional(LrcContentType::Translation)?,
+                romanizations: read_optional(LrcContentType::Romanization)?,
+                target_format: LyricFormat::Ttml,
+                user_metadata_overrides: None,
+                additional_metadata: None,
+            })
+        }
+
+        let wizard = &self.ui.lrc_merge;
+        let Some(main_path) = wizard.main_path.as_deref() else {
+            return ActionResult::Warning("请先选择主歌词 LRC 文件".to_string());
+        };
+        let input = match build_input(wizard, main_path) {
+            Ok(input) => input,
+            Err(e) => return ActionResult::Error(e),
+        };
+
+        let mut options = self.build_conversion_options();
+        options.matching_strategy = AuxiliaryLineMatchingStrategy::SortedSync {
+            tolerance_ms: wizard.tolerance_ms,
+        };
+
+        let result = lyrics_helper_rs::converter::parse_and_merge_with_report(&input, &options)
+            .and_then(|(source_data, report)| {
+                lyrics_helper_rs::converter::generate_from_parsed(
+                    source_data,
+                    LyricFormat::Ttml,
+                    &options,
+                    &None::<std::collections::HashMap<String, Vec<String>>>,
+                )
+                .map(|full_result| (full_result.output_lyrics, report))
+            });
+
+        match result {
+            Ok((ttml, report)) => {
+                info!("[LrcMerge] 合并完成: {report:?}");
+                self.ui.lrc_merge.result = Some((ttml, report));
+                ActionResult::Success
+            }
+            Err(e) => {
+                self.ui.lrc_merge.result = None;
+                ActionResult::Error(AppError::Custom(format!("合并 LRC 失败: {e}")))
+            }
+        }
+    }
+
+    /// 从已解析的歌词中抽取翻译或罗马音，生成与主歌词时间戳对齐的 LRC 并保存为独立文件。
+    fn export_auxiliary_lrc(&mut self, content_type: LrcContentType) -> ActionResult {
+        let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_ref() else {
+            return ActionResult::Warning("没有已解析的歌词可供导出".to_string());
+        };
+
+        let is_translation = content_type == LrcContentType::Translation;
+        let lrc_content = if is_translation {
+            let language = self.lyrics.active_translation_language();
+            self.generate_translation_tracks(parsed_data)
+                .into_iter()
+                .find(|track| track.language == language)
+                .map(|track| track.lrc)
+                .unwrap_or_default()
+        } else {
+            self.generate_lrc_from_aux_track(parsed_data, false)
+        };
+        if lrc_content.trim().is_empty() {
+            let kind = if is_translation {
+                "翻译"
+            } else {
+                "罗马音"
+            };
+            return ActionResult::Warning(format!("当前歌词没有可导出的{kind}"));
+        }
+
+        crate::io::handle_export_lrc_file(self, &lrc_content, content_type);
+        ActionResult::Success
+    }
+
+    /// 收集渲染歌词图片所需的内容。间奏行和没有主歌词的行会被跳过。
+    fn lyric_image_content(&self) -> Option<LyricImageContent> {
+        let parsed_data = self.lyrics.parsed_lyric_data.as_ref()?;
+        let non_empty = |text: String| (!text.trim().is_empty()).then_some(text);
+
+        let lines = parsed_data
+            .lines
+            .iter()
+            .filter(|line| !line.is_interlude())
+            .filter_map(|line| {
+                let track = line.main_track()?;
+                Some(LyricImageLine {
+                    main: non_empty(track.content.text())?,
+                    translation: track.translations.first().and_then(|t| non_empty(t.text())),
+                    romanization: track
+                        .romanizations
+                        .first()
+                        .and_then(|t| non_empty(t.text())),
+                })
+            })
+            .collect();
+
+        let (title, artist) = self.lyric_image_title_artist();
+        Some(LyricImageContent {
+            title,
+            artist,
+            cover: self.lyric_image_cover().map(<[u8]>::to_vec),
+            lines,
+        })
+    }
+
+    /// 歌词元数据中的歌名和艺术家，多个值用 ` / ` 连接。
+    fn lyric_image_title_artist(&self) -> (Option<String>, Option<String>) {
+        let store = &self.lyrics.metadata_manager.store;
+        let joined_values = |key: &CanonicalMetadataKey| {
+            store
+                .get_multiple_values(key)
+                .map(|values| values.join(" / "))
+                .filter(|text| !text.trim().is_empty())
+        };
+        (
+            joined_values(&CanonicalMetadataKey::Title),
+            joined_values(&CanonicalMetadataKey::Artist),
+        )
+    }
+
+    /// 当前播放歌曲的封面，仅在它与歌词的歌名和艺术家一致时返回。
+    pub(super) fn lyric_image_cover(&self) -> Option<&[u8]> {
+        let (title, artist) = self.lyric_image_title_artist();
+        let now_playing = &self.player.current_now_playing;
+        let is_same_song = crate::lyric_image::is_same_song(
+            (title.as_deref(), artist.as_deref()),
+            (now_playing.title.as_deref(), now_playing.artist.as_deref()),
+        );
+        now_playing
+            .cover_data
+            .as_deref()
+            .filter(|bytes| is_same_song && !bytes.is_empty())
+    }
+
+    fn export_lyric_image(&mut self) -> ActionResult {
+        let Some(content) = self
+            .lyric_image_content()
+            .filter(|content| !content.lines.is_empty())
+        else {
+            return ActionResult::Warning("没有已解析的歌词可供导出".to_string());
+        };
+
+        let options = self.ui.lyric_image_options.clone();
+        {
+            let mut settings = self.app_settings.lock().unwrap();
+            if settings.lyric_image != options {
+                settings.lyric_image = options.clone();
+                if let Err(e) = settings.save() {
+                    warn!("[LyricImage] 保存图片导出选项失败: {}", e);
+                }
+            }
         }
+
+        let Some(path) = crate::io::pick_lyric_image_path(self) else {
+            return ActionResult::Success;
+        };
+
+        // 字形光栅化、封面缩放和 PNG 编码都比较耗时，放到后台线程进行
+        self.ui.lyric_image_exporting = true;
+        let action_tx = self.action_tx.clone();
+        self.tokio_runtime.spawn_blocking(move || {
+            let result = crate::lyric_image::render_lyric_image(&content, &options)
+                .map_err(|e| format!("渲染歌词图片失败: {e}"))
+                .and_then(|image| {
+                    image
+                        .save_with_format(&path, image::ImageFormat::Png)
+                        .map_err(|e| format!("保存歌词图片 {path:?} 失败: {e}"))?;
+                    info!(
+                        "[LyricImage] 已导出歌词图片 ({}x{}): {path:?}",
+                        image.width(),
+                        image.height()
+                    );
+                    Ok(path)
+                });
+            let _ = action_tx.send(UserAction::File(FileAction::LyricImageExported(result)));
+        });
+        ActionResult::Success
     }
 
     fn handle_ui_action(&mut self, action: UIAction) -> ActionResult {
@@ -803,6 +2583,16 @@ impl UniLyricApp {
                     PanelType::Metadata => &mut self.ui.show_metadata_panel,
                     PanelType::AmllConnector => &mut self.ui.show_amll_connector_sidebar,
                     PanelType::Warnings => &mut self.ui.show_warnings_panel,
+                    PanelType::LineEditor => &mut self.ui.show_line_editor_window,
+                    PanelType::Timeline => &mut self.ui.show_timeline_window,
+                    PanelType::KaraokePreview => &mut self.ui.show_karaoke_preview_window,
+                    PanelType::FormatOptions => &mut self.ui.show_format_options_window,
+                    PanelType::DocumentList => &mut self.ui.show_document_list_panel,
+                    PanelType::Diff => &mut self.ui.show_diff_window,
+                    PanelType::LocalCache => &mut self.ui.show_local_cache_window,
+                    PanelType::LrcMerge => &mut self.ui.show_lrc_merge_window,
+                    PanelType::LyricImage => &mut self.ui.show_lyric_image_window,
+                    PanelType::BatchDownload => &mut self.ui.show_batch_download_window,
                 };
 
                 // 用事件携带的值来更新核心状态
@@ -830,6 +2620,23 @@ impl UniLyricApp {
                     PanelType::Metadata => self.ui.show_metadata_panel = true,
                     PanelType::AmllConnector => self.ui.show_amll_connector_sidebar = true,
                     PanelType::Warnings => self.ui.show_warnings_panel = true,
+                    PanelType::LineEditor => self.ui.show_line_editor_window = true,
+                    PanelType::Timeline => self.ui.show_timeline_window = true,
+                    PanelType::KaraokePreview => self.ui.show_karaoke_preview_window = true,
+                    PanelType::FormatOptions => {
+                        self.ui.temp_format_options =
+                            self.app_settings.lock().unwrap().format_options.clone();
+                        self.ui.show_format_options_window = true;
+                    }
+                    PanelType::DocumentList => self.ui.show_document_list_panel = true,
+                    PanelType::Diff => self.ui.show_diff_window = true,
+                    PanelType::LocalCache => self.ui.show_local_cache_window = true,
+                    PanelType::LrcMerge => self.ui.show_lrc_merge_window = true,
+                    PanelType::LyricImage => self.ui.show_lyric_image_window = true,
+                    PanelType::BatchDownload => {
+                        self.ui.show_batch_download_window = true;
+                        self.trigger_provider_loading();
+                    }
                 }
                 ActionResult::Success
             }
@@ -845,6 +2652,25 @@ impl UniLyricApp {
                     PanelType::Metadata => self.ui.show_metadata_panel = false,
                     PanelType::AmllConnector => self.ui.show_amll_connector_sidebar = false,
                     PanelType::Warnings => self.ui.show_warnings_panel = false,
+                    PanelType::LineEditor => self.ui.show_line_editor_window = false,
+                    PanelType::Timeline => {
+                        self.ui.show_timeline_window = false;
+                        self.ui.timeline_drag = None;
+                    }
+                    PanelType::KaraokePreview => {
+                        self.ui.show_karaoke_preview_window = false;
+                        self.ui.karaoke_preview.pause();
+                    }
+                    PanelType::FormatOptions => self.ui.show_format_options_window = false,
+                    PanelType::DocumentList => self.ui.show_document_list_panel = false,
+                    PanelType::Diff => self.ui.show_diff_window = false,
+                    PanelType::LocalCache => {
+                        self.ui.show_local_cache_window = false;
+                        self.ui.local_cache_preview = None;
+                    }
+                    PanelType::LrcMerge => self.ui.show_lrc_merge_window = false,
+                    PanelType::LyricImage => self.ui.show_lyric_image_window = false,
+                    PanelType::BatchDownload => self.ui.show_batch_download_window = false,
                 }
                 ActionResult::Success
             }
@@ -852,6 +2678,15 @@ impl UniLyricApp {
                 self.ui.wrap_text = wrap;
                 ActionResult::Success
             }
+            UIAction::SetShowLineNumbers(show) => {
+                self.ui.show_line_numbers = show;
+                ActionResult::Success
+            }
+            UIAction::LocateLine(index) => {
+                self.ui.line_editor_selected_line = Some(index);
+                self.ui.show_line_editor_window = true;
+                ActionResult::Success
+            }
             UIAction::ClearLogs => {
                 self.ui.log_display_buffer.clear();
                 ActionResult::Success
@@ -864,6 +2699,13 @@ impl UniLyricApp {
                 self.ui.toasts.add(*toast);
                 ActionResult::Success
             }
+            UIAction::ConfirmExit => {
+                self.ui.show_exit_unsaved_confirm = false;
+                self.ui.exit_confirmed = true;
+                self.egui_ctx
+                    .send_viewport_cmd(egui::ViewportCommand::Close);
+                ActionResult::Success
+            }
         }
     }
 
@@ -896,6 +2738,31 @@ impl UniLyricApp {
                     return ActionResult::Error(AppError::Custom("发送命令失败".to_string()));
                 }
             }
+            PlayerAction::RefreshSmtcSessions => {
+                tracing::info!("[PlayerAction] 请求刷新 SMTC 会话列表。");
+                if let Err(e) = command_tx.try_send(MediaCommand::RequestUpdate) {
+                    error!("[PlayerAction] 发送刷新命令到 smtc-suite 失败: {}", e);
+                    return ActionResult::Error(AppError::Custom("发送命令失败".to_string()));
+                }
+            }
+            PlayerAction::SeekToLyricTime(lyric_time_ms) => {
+                if self.player.current_now_playing.title.is_none() {
+                    return ActionResult::Warning("没有正在播放的媒体会话".to_string());
+                }
+                let position_ms =
+                    (lyric_time_ms as i64 - self.player.smtc_time_offset_ms).max(0) as u64;
+                tracing::info!(
+                    "[PlayerAction] 跳转到歌词时间 {}ms (播放器位置 {}ms)。",
+                    lyric_time_ms,
+                    position_ms
+                );
+                if let Err(e) = command_tx.try_send(MediaCommand::Control(
+                    SmtcControlCommand::SeekTo(position_ms),
+                )) {
+                    error!("[PlayerAction] 发送跳转命令到 smtc-suite 失败: {}", e);
+                    return ActionResult::Error(AppError::Custom("发送命令失败".to_string()));
+                }
+            }
             PlayerAction::SaveToLocalCache => {
                 return match self.save_lyrics_to_local_cache() {
                     Ok(()) => ActionResult::Success,
@@ -946,6 +2813,20 @@ impl UniLyricApp {
                     return ActionResult::Error(AppError::Custom("发送命令失败".to_string()));
                 }
             }
+            PlayerAction::StoreOffsetSlot(slot) => {
+                let Some(stored) = self.player.offset_slots.get_mut(slot) else {
+                    return ActionResult::Warning(format!("无效的偏移槽位: {slot}"));
+                };
+                *stored = Some(self.player.smtc_time_offset_ms);
+                self.player.active_offset_slot = Some(slot);
+            }
+            PlayerAction::ApplyOffsetSlot(slot) => {
+                let Some(offset) = self.player.offset_slots.get(slot).copied().flatten() else {
+                    return ActionResult::Warning("该偏移槽位尚未保存偏移量".to_string());
+                };
+                self.player.active_offset_slot = Some(slot);
+                return self.handle_player_action(PlayerAction::SetSmtcTimeOffset(offset));
+            }
         }
         ActionResult::Success
     }
@@ -968,13 +2849,47 @@ impl UniLyricApp {
                 self.batch_converter.target_format = format;
                 ActionResult::Success
             }
+            BatchConverterAction::SetExtensionWhitelist(input) => {
+                self.batch_converter.extension_whitelist_input = input;
+                ActionResult::Success
+            }
+            BatchConverterAction::SetAutoPairEnabled(enabled) => {
+                self.batch_converter.auto_pair_enabled = enabled;
+                ActionResult::Success
+            }
+            BatchConverterAction::SetTranslationSuffixes(input) => {
+                self.batch_converter.translation_suffixes_input = input;
+                ActionResult::Success
+            }
+            BatchConverterAction::SetRomanizationSuffixes(input) => {
+                self.batch_converter.romanization_suffixes_input = input;
+                ActionResult::Success
+            }
             BatchConverterAction::ScanTasks => {
                 let Some(input_dir) = self.batch_converter.input_dir.clone() else {
                     return ActionResult::Warning("输入目录未设置".to_string());
                 };
                 let target_format = self.batch_converter.target_format;
+                let whitelist = self.batch_converter.parsed_extension_whitelist();
+                let pairing = self.batch_converter.parsed_pairing_options();
+
+                {
+                    let mut settings = self.app_settings.lock().unwrap();
+                    if settings.batch_main_extension_whitelist != whitelist
+                        || settings.batch_pairing_options() != pairing
+                    {
+                        settings.batch_main_extension_whitelist = whitelist.clone();
+                        settings.batch_auto_pair_enabled = pairing.enabled;
+                        settings.batch_translation_suffixes = pairing.translation_suffixes.clone();
+                        settings.batch_romanization_suffixes =
+                            pairing.romanization_suffixes.clone();
+                        if let Err(e) = settings.save() {
+                            warn!("[BatchConvert] 保存扫描设置失败: {}", e);
+                        }
+                    }
+                }
 
-                match lyrics_helper_rs::converter::processors::batch_processor::discover_and_pair_files(&input_dir) {
+                match lyrics_helper_rs::converter::processors::batch_processor::discover_and_pair_files(&input_dir, &whitelist, &pairing) {
                     Ok(file_groups) => {
                         let (tasks, file_lookup) =
                             lyrics_helper_rs::converter::processors::batch_processor::create_batch_tasks(
@@ -983,6 +2898,7 @@ impl UniLyricApp {
                             );
                         self.batch_converter.tasks = tasks;
                         self.batch_converter.file_lookup = file_lookup;
+                        self.batch_converter.run_task_ids = None;
                         self.batch_converter.status = BatchConverterStatus::Ready;
                     }
                     Err(e) => {
@@ -992,44 +2908,21 @@ impl UniLyricApp {
                 ActionResult::Success
             }
             BatchConverterAction::StartConversion => {
-                if self.batch_converter.status != BatchConverterStatus::Ready {
+                if !matches!(
+                    self.batch_converter.status,
+                    BatchConverterStatus::Ready | BatchConverterStatus::Paused
+                ) {
                     return ActionResult::Warning("当前状态无法开始转换。".to_string());
                 }
 
-                self.batch_converter.status = BatchConverterStatus::Converting;
-
-                let mut tasks = self.batch_converter.tasks.clone();
-                let file_lookup = self.batch_converter.file_lookup.clone();
-                let output_dir = self.batch_converter.output_dir.clone().unwrap();
-                let options = self.build_conversion_options();
-                let action_tx = self.action_tx.clone();
-
-                self.tokio_runtime.spawn(async move {
-                    // Execute the conversion in a background thread.
-                    let result = lyrics_helper_rs::converter::processors::batch_processor::execute_batch_conversion(
-                        &mut tasks,
-                        &file_lookup,
-                        &output_dir,
-                        &options
+                // 从暂停处继续时保留各任务的状态，否则重新开始一轮
+                if self.batch_converter.status == BatchConverterStatus::Ready {
+                    lyrics_helper_rs::converter::processors::batch_processor::reset_tasks_for_new_run(
+                        &mut self.batch_converter.tasks,
                     );
-
-                    match result {
-                        Ok(()) => {
-                            for task in tasks {
-                                 let update_msg = lyrics_helper_core::BatchTaskUpdate {
-                                     entry_config_id: task.id,
-                                     new_status: task.status,
-                                 };
-                                 let _ = action_tx.send(UserAction::BatchConverter(BatchConverterAction::TaskUpdate(update_msg)));
-                            }
-                            let _ = action_tx.send(UserAction::BatchConverter(BatchConverterAction::ConversionCompleted));
-                        }
-                        Err(e) => {
-                             error!("[BatchConvert] 批量转换执行失败: {}", e);
-                        }
-                    }
-                });
-
+                    self.batch_converter.run_task_ids = None;
+                }
+                self.spawn_batch_conversion();
                 ActionResult::Success
             }
             BatchConverterAction::TaskUpdate(update) => {
@@ -1040,15 +2933,96 @@ impl UniLyricApp {
                     .find(|t| t.id == update.entry_config_id)
                 {
                     task.status = update.new_status;
+                    self.batch_converter.finished_this_run += 1;
+                }
+                ActionResult::Success
+            }
+            BatchConverterAction::PauseConversion => {
+                if self.batch_converter.status == BatchConverterStatus::Converting {
+                    info!("[BatchConvert] 已请求暂停，将在当前任务完成后停止。");
+                    self.batch_converter
+                        .pause_requested
+                        .store(true, std::sync::atomic::Ordering::Relaxed);
+                }
+                ActionResult::Success
+            }
+            BatchConverterAction::RetryFailed => {
+                if !matches!(
+                    self.batch_converter.status,
+                    BatchConverterStatus::Ready | BatchConverterStatus::Completed
+                ) {
+                    return ActionResult::Warning("当前状态无法重试失败的任务。".to_string());
                 }
+
+                let retry_count = self.batch_converter.reset_failed_for_retry();
+                if retry_count == 0 {
+                    return ActionResult::Warning("没有失败的任务需要重试。".to_string());
+                }
+
+                info!("[BatchConvert] 重试 {retry_count} 个失败的任务。");
+                self.spawn_batch_conversion();
                 ActionResult::Success
             }
             BatchConverterAction::ConversionCompleted => {
-                self.batch_converter.status = BatchConverterStatus::Completed;
+                if self.batch_converter.dry_run {
+                    let (dry_run_count, overwrite_count) = self
+                        .batch_converter
+                        .tasks
+                        .iter()
+                        .filter_map(|task| match task.status {
+                            BatchEntryStatus::DryRun {
+                                overwrites_existing,
+                                ..
+                            } => Some(overwrites_existing),
+                            _ => None,
+                        })
+                        .fold((0, 0), |(total, overwrites), overwrites_existing| {
+                            (total + 1, overwrites + usize::from(overwrites_existing))
+                        });
+                    info!(
+                        "[BatchConvert] 试运行完成：{dry_run_count} 个任务可以转换，其中 {overwrite_count} 个会覆盖已有文件。"
+                    );
+                    // 试运行不产生任何文件，回到就绪状态以便正式运行
+                    self.batch_converter.status = BatchConverterStatus::Ready;
+                } else {
+                    self.batch_converter.status = BatchConverterStatus::Completed;
+                }
+                ActionResult::Success
+            }
+            BatchConverterAction::SetDryRun(dry_run) => {
+                self.batch_converter.dry_run = dry_run;
+                ActionResult::Success
+            }
+            BatchConverterAction::SetPostprocessorEnabled(processor, enabled) => {
+                let mut postprocessors = self.batch_converter.postprocessors.clone();
+                postprocessors.retain(|&p| p != processor);
+                if enabled {
+                    postprocessors.push(processor);
+                }
+                self.batch_converter.postprocessors = ProcessorType::ALL
+                    .into_iter()
+                    .filter(|p| postprocessors.contains(p))
+                    .collect();
+
+                let mut settings = self.app_settings.lock().unwrap();
+                settings.batch_postprocessors = self.batch_converter.postprocessors.clone();
+                if let Err(e) = settings.save() {
+                    warn!("[BatchConvert] 保存批量后处理器设置失败: {}", e);
+                }
+                ActionResult::Success
+            }
+            BatchConverterAction::ConversionPaused => {
+                info!(
+                    "[BatchConvert] 批量转换已暂停，已处理 {}/{} 个任务。",
+                    self.batch_converter.processed_task_count(),
+                    self.batch_converter.tasks.len()
+                );
+                self.batch_converter.status = BatchConverterStatus::Paused;
                 ActionResult::Success
             }
             BatchConverterAction::Reset => {
-                self.batch_converter = Default::default();
+                let settings = self.app_settings.lock().unwrap();
+                self.batch_converter = BatchConverterState::new(&settings);
                 ActionResult::Success
             }
         }
@@ -1091,6 +3065,7 @@ impl UniLyricApp {
         let media_info = self.player.current_now_playing.clone();
 
         let title = media_info.title.as_deref().unwrap_or("unknown_title");
+        let album = media_info.album_title.clone();
         let artists: Vec<String> = media_info
             .artist
             .map(|s| {
@@ -1113,18 +3088,14 @@ impl UniLyricApp {
         let entry = crate::types::LocalLyricCacheEntry {
             smtc_title: title.to_string(),
             smtc_artists: artists,
+            smtc_album: album,
             ttml_filename: final_filename,
             original_source_format: self.fetcher.last_source_format.map(|f| f.to_string()),
             saved_timestamp: chrono::Utc::now().timestamp(),
         };
 
         index_guard.push(entry);
-
-        let lines: Vec<String> = index_guard
-            .iter()
-            .filter_map(|e| serde_json::to_string(e).ok())
-            .collect();
-        std::fs::write(&index_path, lines.join("\n")).map_err(AppError::from)?;
+        write_local_cache_index(&index_path, &index_guard)?;
 
         tracing::info!("[LocalCache] 成功保存歌词到本地缓存: {file_path:?}");
         self.ui.toasts.add(egui_toast::Toast {
@@ -1137,149 +3108,263 @@ impl UniLyricApp {
         Ok(())
     }
 
+    /// 在后台线程中运行本轮批量转换。仅重试失败任务时只处理 `run_task_ids` 中的任务。
+    fn spawn_batch_conversion(&mut self) {
+        self.batch_converter.status = BatchConverterStatus::Converting;
+        self.batch_converter
+            .pause_requested
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        self.batch_converter.run_started_at = Some(std::time::Instant::now());
+        self.batch_converter.finished_this_run = 0;
+
+        let mut tasks = self.batch_converter.tasks_for_run();
+        let file_lookup = self.batch_converter.file_lookup.clone();
+        let output_dir = self.batch_converter.output_dir.clone().unwrap();
+        let options = self.build_conversion_options();
+        let action_tx = self.action_tx.clone();
+        let pause_requested = self.batch_converter.pause_requested.clone();
+        let dry_run = self.batch_converter.dry_run;
+        if dry_run {
+            info!("[BatchConvert] 以试运行模式开始批量转换，不会写入任何文件。");
+        }
+        let postprocessors = self.batch_converter.postprocessors.clone();
+        let settings = self.app_settings.lock().unwrap().clone();
+        if !postprocessors.is_empty() {
+            info!(
+                "[BatchConvert] 转换中将依次应用后处理器: {:?}",
+                postprocessors
+            );
+        }
+
+        self.tokio_runtime.spawn(async move {
+            // Execute the conversion in a background thread.
+            let result = lyrics_helper_rs::converter::processors::batch_processor::execute_batch_conversion_pausable(
+                &mut tasks,
+                &file_lookup,
+                &output_dir,
+                &options,
+                dry_run,
+                crate::io::read_text_file,
+                || pause_requested.load(std::sync::atomic::Ordering::Relaxed),
+                |parsed_data| apply_processor_chain(parsed_data, &postprocessors, &settings),
+                |task| {
+                    let update_msg = lyrics_helper_core::BatchTaskUpdate {
+                        entry_config_id: task.id,
+                        new_status: task.status.clone(),
+                    };
+                    let _ = action_tx.send(UserAction::BatchConverter(BatchConverterAction::TaskUpdate(update_msg)));
+                },
+            );
+
+            match result {
+                Ok(outcome) => {
+                    let finished_action = match outcome {
+                        BatchRunOutcome::Finished => BatchConverterAction::ConversionCompleted,
+                        BatchRunOutcome::Paused => BatchConverterAction::ConversionPaused,
+                    };
+                    let _ = action_tx.send(UserAction::BatchConverter(finished_action));
+                }
+                Err(e) => {
+                     error!("[BatchConvert] 批量转换执行失败: {}", e);
+                }
+            }
+        });
+    }
+
     fn handle_settings_action(&mut self, action: SettingsAction) -> ActionResult {
         match action {
-            SettingsAction::Save(settings) => match settings.save() {
-                Ok(_) => {
-                    let mut mirror_changed = false;
+            SettingsAction::Save(mut settings) => {
+                if let Err(e) = settings.network.validate_proxy() {
+                    return ActionResult::Error(AppError::Custom(format!("无法保存设置: {e}")));
+                }
+                // 设置窗口编辑的是打开时的副本，使用次数以当前记录为准
+                settings.metadata_key_usage =
+                    self.app_settings.lock().unwrap().metadata_key_usage.clone();
+                match settings.save() {
+                    Ok(_) => {
+                        self.metadata_key_usage_dirty = false;
+                        let mut mirror_changed = false;
+                        let custom_http_source_changed;
+                        let network_changed;
 
-                    {
-                        let old_settings = self.app_settings.lock().unwrap();
-                        let new_mirror = &settings.amll_mirror;
-                        if &old_settings.amll_mirror != new_mirror {
-                            let core_config = CoreAmllConfig {
-                                mirror: new_mirror.clone().into(),
-                            };
-                            match serde_json::to_string_pretty(&core_config) {
-                                Ok(json_string) => {
-                                    if let Ok(config_path) =
-                                        lyrics_helper_rs::config::native::get_config_file_path(
-                                            "amll_config.json",
-                                        )
-                                    {
-                                        if let Err(e) = std::fs::write(&config_path, json_string) {
-                                            error!("[Settings] 写入 amll_config.json 失败: {}", e);
+                        {
+                            let old_settings = self.app_settings.lock().unwrap();
+                            custom_http_source_changed = old_settings.custom_http_source_enabled
+                                != settings.custom_http_source_enabled
+                                || old_settings.custom_http_source != settings.custom_http_source;
+                            network_changed = old_settings.network != settings.network;
+                            let new_mirror = &settings.amll_mirror;
+                            if &old_settings.amll_mirror != new_mirror {
+                                let core_config = CoreAmllConfig {
+                                    mirror: new_mirror.clone().into(),
+                                };
+                                match serde_json::to_string_pretty(&core_config) {
+                                    Ok(json_string) => {
+                                        if let Ok(config_path) =
+                                            lyrics_helper_rs::config::native::get_config_file_path(
+                                                "amll_config.json",
+                                            )
+                                        {
+                                            if let Err(e) =
+                                                std::fs::write(&config_path, json_string)
+                                            {
+                                                error!(
+                                                    "[Settings] 写入 amll_config.json 失败: {}",
+                                                    e
+                                                );
+                                            } else {
+                                                mirror_changed = true;
+                                            }
                                         } else {
-                                            mirror_changed = true;
+                                            error!("[Settings] 无法获取 amll_config.json 的路径");
                                         }
-                                    } else {
-                                        error!("[Settings] 无法获取 amll_config.json 的路径");
                                     }
-                                }
-                                Err(e) => {
-                                    error!("[Settings] 序列化核心库 AMLL 配置失败: {}", e);
+                                    Err(e) => {
+                                        error!("[Settings] 序列化核心库 AMLL 配置失败: {}", e);
+                                    }
                                 }
                             }
                         }
-                    }
-
-                    {
-                        let mut app_settings_guard = self.app_settings.lock().unwrap();
-                        *app_settings_guard = *settings.clone();
-                        self.player.smtc_time_offset_ms = app_settings_guard.smtc_time_offset_ms;
-                    }
 
-                    let old_offset = self.app_settings.lock().unwrap().smtc_time_offset_ms;
-                    let old_audio_capture_setting =
-                        self.app_settings.lock().unwrap().send_audio_data_to_player;
+                        {
+                            let mut app_settings_guard = self.app_settings.lock().unwrap();
+                            *app_settings_guard = *settings.clone();
+                            self.player.smtc_time_offset_ms =
+                                app_settings_guard.smtc_time_offset_ms;
+                        }
 
-                    {
-                        let mut app_settings_guard = self.app_settings.lock().unwrap();
-                        *app_settings_guard = *settings.clone();
-                    }
+                        let old_offset = self.app_settings.lock().unwrap().smtc_time_offset_ms;
+                        let old_audio_capture_setting =
+                            self.app_settings.lock().unwrap().send_audio_data_to_player;
 
-                    if settings.smtc_time_offset_ms != old_offset {
-                        self.send_action(UserAction::Player(PlayerAction::SetSmtcTimeOffset(
-                            settings.smtc_time_offset_ms,
-                        )));
-                    }
+                        {
+                            let mut app_settings_guard = self.app_settings.lock().unwrap();
+                            *app_settings_guard = *settings.clone();
+                        }
 
-                    if settings.send_audio_data_to_player != old_audio_capture_setting {
-                        self.send_action(UserAction::Player(PlayerAction::ToggleAudioCapture(
-                            settings.send_audio_data_to_player,
-                        )));
-                    }
+                        if settings.smtc_time_offset_ms != old_offset {
+                            self.send_action(UserAction::Player(PlayerAction::SetSmtcTimeOffset(
+                                settings.smtc_time_offset_ms,
+                            )));
+                        }
 
-                    let new_mc_config_from_settings = AMLLConnectorConfig {
-                        enabled: settings.amll_connector_enabled,
-                        websocket_url: settings.amll_connector_websocket_url.clone(),
-                    };
+                        if settings.send_audio_data_to_player != old_audio_capture_setting {
+                            self.send_action(UserAction::Player(PlayerAction::ToggleAudioCapture(
+                                settings.send_audio_data_to_player,
+                            )));
+                        }
 
-                    let new_actor_settings = ActorSettings {};
+                        let new_mc_config_from_settings = AMLLConnectorConfig {
+                            enabled: settings.amll_connector_enabled,
+                            websocket_url: settings.amll_connector_websocket_url.clone(),
+                            heartbeat_interval_secs: settings
+                                .amll_connector_heartbeat_interval_secs,
+                            heartbeat_timeout_secs: settings.amll_connector_heartbeat_timeout_secs,
+                        };
 
-                    let conversion_mode = if settings.enable_t2s_for_auto_search {
-                        TextConversionMode::TraditionalToSimplified
-                    } else {
-                        TextConversionMode::Off
-                    };
+                        let new_actor_settings = ActorSettings {
+                            lyric_send_mode: settings.amll_connector_lyric_send_mode,
+                        };
 
-                    if let Some(tx) = &self.player.command_tx {
-                        let command = MediaCommand::SetTextConversion(conversion_mode);
-                        if let Err(e) = tx.try_send(command) {
-                            error!("[Settings] 发送 SetTextConversion 命令失败: {}", e);
+                        let conversion_mode = if settings.enable_t2s_for_auto_search {
+                            TextConversionMode::TraditionalToSimplified
                         } else {
-                            info!(
-                                "[Settings] 已发送 SetTextConversion 命令: {:?}",
-                                conversion_mode
-                            );
+                            TextConversionMode::Off
+                        };
+
+                        if let Some(tx) = &self.player.command_tx {
+                            let command = MediaCommand::SetTextConversion(conversion_mode);
+                            if let Err(e) = tx.try_send(command) {
+                                error!("[Settings] 发送 SetTextConversion 命令失败: {}", e);
+                            } else {
+                                info!(
+                                    "[Settings] 已发送 SetTextConversion 命令: {:?}",
+                                    conversion_mode
+                                );
+                            }
                         }
-                    }
 
-                    if let Some(tx) = &self.amll_connector.command_tx {
-                        debug!(
-                            "[Settings] 发送 UpdateActorSettings 命令给 AMLL Connector worker。"
-                        );
-                        if tx
-                            .try_send(UpdateActorSettings(new_actor_settings))
-                            .is_err()
-                        {
-                            error!(
-                                "[Settings] 发送 UpdateActorSettings 命令给 AMLL Connector worker 失败。"
+                        if let Some(tx) = &self.amll_connector.command_tx {
+                            debug!(
+                                "[Settings] 发送 UpdateActorSettings 命令给 AMLL Connector worker。"
                             );
+                            if tx
+                                .try_send(UpdateActorSettings(new_actor_settings))
+                                .is_err()
+                            {
+                                error!(
+                                    "[Settings] 发送 UpdateActorSettings 命令给 AMLL Connector worker 失败。"
+                                );
+                            }
                         }
-                    }
 
-                    let old_mc_config = self.amll_connector.config.lock().unwrap().clone();
-                    if new_mc_config_from_settings.enabled
-                        && old_mc_config != new_mc_config_from_settings
-                        && let Some(tx) = &self.amll_connector.command_tx
-                    {
-                        debug!("[Settings] 发送 UpdateConfig 命令给AMLL Connector worker。");
-                        if tx
-                            .try_send(crate::amll_connector::ConnectorCommand::UpdateConfig(
-                                new_mc_config_from_settings.clone(),
-                            ))
-                            .is_err()
+                        let old_mc_config = self.amll_connector.config.lock().unwrap().clone();
+                        if new_mc_config_from_settings.enabled
+                            && old_mc_config != new_mc_config_from_settings
+                            && let Some(tx) = &self.amll_connector.command_tx
                         {
-                            error!(
-                                "[Settings] 发送 UpdateConfig 命令给AMLL Connector worker 失败。"
-                            );
+                            debug!("[Settings] 发送 UpdateConfig 命令给AMLL Connector worker。");
+                            self.amll_connector.user_action_pending = true;
+                            if tx
+                                .try_send(crate::amll_connector::ConnectorCommand::UpdateConfig(
+                                    new_mc_config_from_settings.clone(),
+                                ))
+                                .is_err()
+                            {
+                                error!(
+                                    "[Settings] 发送 UpdateConfig 命令给AMLL Connector worker 失败。"
+                                );
+                            }
                         }
-                    }
 
-                    *self.amll_connector.config.lock().unwrap() = new_mc_config_from_settings;
+                        *self.amll_connector.config.lock().unwrap() = new_mc_config_from_settings;
 
-                    if mirror_changed {
-                        let toast = egui_toast::Toast {
-                            text: "AMLL 镜像设置已保存。\n需要重新启动才能生效。".into(),
-                            kind: egui_toast::ToastKind::Info,
-                            options: egui_toast::ToastOptions::default()
-                                .duration_in_seconds(10.0)
-                                .show_progress(true),
-                            style: Default::default(),
-                        };
-                        self.ui.toasts.add(toast);
-                    }
+                        if mirror_changed {
+                            let toast = egui_toast::Toast {
+                                text: "AMLL 镜像设置已保存。\n需要重新启动才能生效。".into(),
+                                kind: egui_toast::ToastKind::Info,
+                                options: egui_toast::ToastOptions::default()
+                                    .duration_in_seconds(10.0)
+                                    .show_progress(true),
+                                style: Default::default(),
+                            };
+                            self.ui.toasts.add(toast);
+                        }
 
-                    self.ui.show_settings_window = false;
-                    ActionResult::Success
-                }
-                Err(e) => {
-                    error!("[Settings] 保存应用设置失败: {e}");
-                    self.ui.show_settings_window = false;
-                    ActionResult::Error(AppError::Custom(format!("保存设置失败: {e}")))
+                        if custom_http_source_changed {
+                            let toast = egui_toast::Toast {
+                                text: "自定义歌词源设置已保存。\n需要重新启动才能生效。".into(),
+                                kind: egui_toast::ToastKind::Info,
+                                options: egui_toast::ToastOptions::default()
+                                    .duration_in_seconds(10.0)
+                                    .show_progress(true),
+                                style: Default::default(),
+                            };
+                            self.ui.toasts.add(toast);
+                        }
+
+                        if network_changed {
+                            let toast = egui_toast::Toast {
+                                text: "网络设置已保存。\n需要重新启动才能生效。".into(),
+                                kind: egui_toast::ToastKind::Info,
+                                options: egui_toast::ToastOptions::default()
+                                    .duration_in_seconds(10.0)
+                                    .show_progress(true),
+                                style: Default::default(),
+                            };
+                            self.ui.toasts.add(toast);
+                        }
+
+                        self.ui.show_settings_window = false;
+                        ActionResult::Success
+                    }
+                    Err(e) => {
+                        error!("[Settings] 保存应用设置失败: {e}");
+                        self.ui.show_settings_window = false;
+                        ActionResult::Error(AppError::Custom(format!("保存设置失败: {e}")))
+                    }
                 }
-            },
+            }
             SettingsAction::Cancel => {
                 self.ui.show_settings_window = false;
                 ActionResult::Success
@@ -1288,6 +3373,88 @@ impl UniLyricApp {
                 self.ui.temp_edit_settings = self.app_settings.lock().unwrap().clone();
                 ActionResult::Success
             }
+            SettingsAction::Export => {
+                let Some(path) = rfd::FileDialog::new()
+                    .add_filter("UniLyric 配置", &["json"])
+                    .set_file_name("unilyric-settings.json")
+                    .save_file()
+                else {
+                    return ActionResult::Success;
+                };
+
+                let json = match self.app_settings.lock().unwrap().to_portable_json() {
+                    Ok(json) => json,
+                    Err(e) => {
+                        return ActionResult::Error(AppError::Custom(format!(
+                            "序列化设置失败: {e}"
+                        )));
+                    }
+                };
+                if let Err(e) = std::fs::write(&path, json) {
+                    return ActionResult::Error(AppError::Custom(format!("写入配置文件失败: {e}")));
+                }
+                info!("[Settings] 已导出配置到 {:?}", path);
+                ActionResult::Success
+            }
+            SettingsAction::Import => {
+                let Some(path) = rfd::FileDialog::new()
+                    .add_filter("UniLyric 配置", &["json"])
+                    .pick_file()
+                else {
+                    return ActionResult::Success;
+                };
+
+                let content = match std::fs::read_to_string(&path) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        return ActionResult::Error(AppError::Custom(format!(
+                            "读取配置文件失败: {e}"
+                        )));
+                    }
+                };
+                match self.ui.temp_edit_settings.merge_portable_json(&content) {
+                    Ok(import) if import.changes.is_empty() => {
+                        ActionResult::Warning("导入的配置与当前设置相同".to_string())
+                    }
+                    Ok(import) => {
+                        self.ui.pending_settings_import = Some(import);
+                        ActionResult::Success
+                    }
+                    Err(e) => ActionResult::Warning(format!("无法解析配置文件: {e}")),
+                }
+            }
+            SettingsAction::ConfirmImport => {
+                if let Some(import) = self.ui.pending_settings_import.take() {
+                    info!("[Settings] 已导入 {} 项设置。", import.changes.len());
+                    self.ui.temp_edit_settings = import.settings;
+                    self.ui.toasts.add(egui_toast::Toast {
+                        text: "配置已导入，点击“保存并应用”后生效".into(),
+                        kind: egui_toast::ToastKind::Info,
+                        options: egui_toast::ToastOptions::default().duration_in_seconds(4.0),
+                        style: Default::default(),
+                    });
+                }
+                ActionResult::Success
+            }
+            SettingsAction::CancelImport => {
+                self.ui.pending_settings_import = None;
+                ActionResult::Success
+            }
+            SettingsAction::ToggleChineseConversionFavorite(config) => {
+                let mut settings = self.app_settings.lock().unwrap();
+                let favorites = &mut settings.favorite_chinese_conversions;
+                if let Some(index) = favorites.iter().position(|&c| c == config) {
+                    favorites.remove(index);
+                } else {
+                    favorites.push(config);
+                }
+                // 设置窗口中尚未保存的副本也同步更新，避免保存时覆盖收藏
+                self.ui.temp_edit_settings.favorite_chinese_conversions = favorites.clone();
+                if let Err(e) = settings.save() {
+                    return ActionResult::Warning(format!("保存简繁转换收藏失败: {e}"));
+                }
+                ActionResult::Success
+            }
         }
     }
 
@@ -1298,15 +3465,31 @@ impl UniLyricApp {
         label: &str,
         enabled: bool,
     ) {
-        if ui
-            .add_enabled(enabled, egui::Button::new(label))
-            .on_disabled_hover_text("请先加载主歌词")
-            .clicked()
-        {
-            self.send_action(UserAction::Lyrics(Box::new(LyricsAction::ConvertChinese(
-                variant,
-            ))));
-        }
+        let is_favorite = self
+            .app_settings
+            .lock()
+            .is_ok_and(|settings| settings.favorite_chinese_conversions.contains(&variant));
+        ui.horizontal(|h_ui| {
+            if h_ui
+                .add_enabled(enabled, egui::Button::new(label))
+                .on_disabled_hover_text("请先加载主歌词")
+                .clicked()
+            {
+                self.send_action(UserAction::Lyrics(Box::new(LyricsAction::ConvertChinese(
+                    variant,
+                ))));
+            }
+            let (star, hover) = if is_favorite {
+                ("★", "从工具栏移除")
+            } else {
+                ("☆", "添加到工具栏")
+            };
+            if h_ui.small_button(star).on_hover_text(hover).clicked() {
+                self.send_action(UserAction::Settings(
+                    SettingsAction::ToggleChineseConversionFavorite(variant),
+                ));
+            }
+        });
     }
 
     pub fn trigger_provider_loading(&mut self) {
@@ -1321,9 +3504,21 @@ impl UniLyricApp {
         self.lyrics_helper_state.provider_load_result_rx = Some(rx);
 
         let helper_clone = Arc::clone(&self.lyrics_helper_state.helper);
+        let (custom_http_source, network_config) = {
+            let settings = self.app_settings.lock().unwrap();
+            (
+                settings
+                    .custom_http_source_enabled
+                    .then(|| settings.custom_http_source.clone()),
+                settings.network.clone(),
+            )
+        };
 
         self.tokio_runtime.spawn(async move {
-            let result = match helper_clone.lock().await.load_providers().await {
+            let mut helper = helper_clone.lock().await;
+            helper.set_custom_http_source(custom_http_source);
+            helper.set_network_config(network_config);
+            let result = match helper.load_providers().await {
                 Ok(_) => Ok(()),
                 Err(e) => Err(e.to_string()),
             };
@@ -1341,6 +3536,7 @@ impl UniLyricApp {
             &self.fetcher.kugou_status,
             &self.fetcher.netease_status,
             &self.fetcher.amll_db_status,
+            &self.fetcher.custom_http_status,
         ];
 
         for status_arc in all_search_status_arcs {
@@ -1371,25 +3567,63 @@ impl UniLyricApp {
                 };
 
                 if let Some(first_aux_track) = aux_tracks.first() {
-                    let text: String = first_aux_track
-                        .words
-                        .iter()
-                        .flat_map(|w| &w.syllables)
-                        .map(|s| s.text.as_str())
-                        .collect();
+                    push_lrc_line(&mut lrc_output, line.start_ms, &first_aux_track.text());
+                }
+            }
+        }
+        lrc_output
+    }
 
-                    if !text.is_empty() {
-                        let minutes = line.start_ms / 60000;
-                        let seconds = (line.start_ms % 60000) / 1000;
-                        let millis = (line.start_ms % 1000) / 10;
-                        let timestamp = format!("[{:02}:{:02}.{:02}]", minutes, seconds, millis);
+    /// 按语言标签把已解析歌词中的翻译拆分为多条 LRC 翻译轨道，按语言首次出现的顺序排列。
+    ///
+    /// 没有语言标签的翻译归入默认语言。
+    pub(super) fn generate_translation_tracks(
+        &self,
+        parsed_data: &lyrics_helper_core::ParsedSourceData,
+    ) -> Vec<TranslationTrack> {
+        let mut tracks: Vec<TranslationTrack> = Vec::new();
 
-                        let _ = writeln!(&mut lrc_output, "{}{}", timestamp, text);
-                    }
+        for line in &parsed_data.lines {
+            let Some(main_track) = line.main_track() else {
+                continue;
+            };
+            for translation in &main_track.translations {
+                let text = translation.text();
+                if text.is_empty() {
+                    continue;
                 }
+                let language = translation
+                    .metadata
+                    .get(&TrackMetadataKey::Language)
+                    .map_or(DEFAULT_TRANSLATION_LANGUAGE, String::as_str);
+                let index = match tracks.iter().position(|t| t.language == language) {
+                    Some(index) => index,
+                    None => {
+                        tracks.push(TranslationTrack::new(language));
+                        tracks.len() - 1
+                    }
+                };
+
+                push_lrc_line(&mut tracks[index].lrc, line.start_ms, &text);
             }
         }
-        lrc_output
+        tracks
+    }
+
+    /// 用已解析歌词中的翻译替换各翻译轨道。
+    ///
+    /// 用户新增但还没有内容的语言会被保留。
+    pub(super) fn load_translation_tracks(
+        &mut self,
+        parsed_data: &lyrics_helper_core::ParsedSourceData,
+    ) {
+        let mut tracks = self.generate_translation_tracks(parsed_data);
+        for track in self.lyrics.all_translation_tracks() {
+            if track.lrc.trim().is_empty() && !tracks.iter().any(|t| t.language == track.language) {
+                tracks.push(track);
+            }
+        }
+        self.lyrics.set_translation_tracks(tracks);
     }
 
     pub(super) fn generate_lrc_from_main_track(
@@ -1404,17 +3638,146 @@ impl UniLyricApp {
                 .iter()
                 .find(|t| t.content_type == ContentType::Main)
             {
-                let text: String = main_track.content.text();
-                if !text.is_empty() {
-                    let minutes = line.start_ms / 60000;
-                    let seconds = (line.start_ms % 60000) / 1000;
-                    let millis = (line.start_ms % 1000) / 10;
-                    let timestamp = format!("[{:02}:{:02}.{:02}]", minutes, seconds, millis);
-
-                    let _ = writeln!(&mut lrc_output, "{}{}", timestamp, text);
-                }
+                push_lrc_line(&mut lrc_output, line.start_ms, &main_track.content.text());
             }
         }
         lrc_output
     }
 }
+
+/// 写入一行 LRC，时间戳与 LRC 生成器一样四舍五入到百分之一秒。文本为空时不写入。
+fn push_lrc_line(lrc: &mut String, start_ms: u64, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let timestamp = format_lrc_time(start_ms, LrcTimestampPrecision::Centiseconds);
+    let _ = writeln!(lrc, "{timestamp}{text}");
+}
+
+fn input_text_hash(text: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::hash::DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 按顺序对解析后的歌词应用一组后处理器，选项取自 `settings`。
+///
+/// 与编辑器中的“后处理”菜单不同，音节平滑在这里直接应用，不记录平滑历史。
+pub(super) fn apply_processor_chain(
+    parsed_data: &mut ParsedSourceData,
+    processors: &[ProcessorType],
+    settings: &AppSettings,
+) -> Result<(), ConvertError> {
+    use lyrics_helper_rs::converter::processors::{
+        agent_recognizer, line_wrapper, metadata_stripper, punctuation_normalizer, regex_replacer,
+        syllable_overlap_fixer, syllable_smoothing,
+    };
+
+    for processor in processors {
+        match processor {
+            ProcessorType::MetadataStripper => {
+                metadata_stripper::strip_descriptive_metadata_lines(
+                    &mut parsed_data.lines,
+                    &settings.metadata_stripper,
+                );
+            }
+            ProcessorType::SyllableSmoother => {
+                syllable_smoothing::apply_smoothing(
+                    &mut parsed_data.lines,
+                    &settings.syllable_smoothing,
+                );
+            }
+            ProcessorType::AgentRecognizer => {
+                agent_recognizer::recognize_agents_with_options(
+                    parsed_data,
+                    &settings.agent_recognizer,
+                );
+            }
+            ProcessorType::RegexReplace => {
+                regex_replacer::apply_regex_replacements(
+                    &mut parsed_data.lines,
+                    &settings.regex_replace,
+                )?;
+            }
+            ProcessorType::LineWrapper => {
+                line_wrapper::wrap_long_lines(&mut parsed_data.lines, &settings.line_wrap);
+            }
+            ProcessorType::PunctuationNormalizer => {
+                punctuation_normalizer::normalize_punctuation(
+                    &mut parsed_data.lines,
+                    &settings.punctuation_normalize,
+                );
+            }
+            ProcessorType::SyllableOverlapFixer => {
+                syllable_overlap_fixer::fix_syllable_overlaps(
+                    &mut parsed_data.lines,
+                    &settings.syllable_overlap_fix,
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 将本地缓存索引以 JSON Lines 格式写回磁盘。
+fn write_local_cache_index(
+    index_path: &std::path::Path,
+    entries: &[crate::types::LocalLyricCacheEntry],
+) -> AppResult<()> {
+    let lines: Vec<String> = entries
+        .iter()
+        .filter_map(|e| serde_json::to_string(e).ok())
+        .collect();
+    std::fs::write(index_path, lines.join("\n")).map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_lrc_line_rounds_to_centiseconds() {
+        let mut lrc = String::new();
+        push_lrc_line(&mut lrc, 1_999, "第一句");
+        push_lrc_line(&mut lrc, 59_996, "进位到下一分钟");
+        push_lrc_line(&mut lrc, 3_000, "");
+
+        assert_eq!(lrc, "[00:02.00]第一句\n[01:00.00]进位到下一分钟\n");
+    }
+
+    #[test]
+    fn test_format_line_for_copy() {
+        let mut line = LyricLine::new(59_996, 61_234);
+        line.add_content_track(ContentType::Main, " 进位 ");
+
+        assert_eq!(format_line_for_copy(&line, LineCopyFormat::Text), "进位");
+        assert_eq!(
+            format_line_for_copy(&line, LineCopyFormat::Timestamp),
+            "00:59.996 - 01:01.234"
+        );
+        assert_eq!(
+            format_line_for_copy(&line, LineCopyFormat::LrcLine),
+            "[01:00.00]进位"
+        );
+    }
+
+    #[test]
+    fn test_lyrics_to_markdown_table() {
+        let mut first = LyricLine::new(59_996, 61_000);
+        first.add_content_track(ContentType::Main, "a|b");
+        first.add_translation(ContentType::Main, "甲", Some("zh-Hans"));
+        first.add_translation(ContentType::Main, "乙", None);
+        let mut interlude = LyricLine::new(61_000, 62_000);
+        interlude.add_content_track(ContentType::Main, "  ");
+        let mut last = LyricLine::new(62_004, 63_000);
+        last.add_content_track(ContentType::Main, "结尾");
+
+        assert_eq!(
+            lyrics_to_markdown_table(&[first, interlude, last]),
+            "| 时间 | 歌词 | 翻译 |\n| --- | --- | --- |\n\
+             | 01:00.00 | a\\|b | 甲 / 乙 |\n\
+             | 01:02.00 | 结尾 |  |\n"
+        );
+    }
+}
@@ -1,24 +1,71 @@
 use std::str::FromStr;
 
-use crate::amll_connector::WebsocketStatus;
+use crate::amll_connector::types::LyricSendMode;
+use crate::amll_connector::{LyricDeliveryStatus, WebsocketStatus};
 use crate::app_definition::{
     AppView, BatchConverterStatus, PreviewState, SearchState, UniLyricApp,
 };
 
-use crate::app_settings::AppAmllMirror;
-use crate::types::{AutoSearchSource, AutoSearchStatus};
+use crate::app_settings::{AppAmllMirror, FormatOptionsSettings, NEW_CUSTOM_METADATA_KEY};
+use crate::batch_download::BatchDownloadStatus;
+use crate::text_diff::{self, DiffLine};
+use crate::text_search::{self, FindBar};
+use crate::types::{
+    AutoSearchSource, AutoSearchStatus, DiffSource, LrcContentType, ResultPreviewSummary,
+    SearchResultOrigin, TimelineDrag, TimelineDragHandle, WarningLevel, WordTimingAvailability,
+};
+use crate::waveform::Waveform;
 
 use crate::app_actions::{
-    AmllConnectorAction, DownloaderAction, LyricsAction, PanelType, PlayerAction, ProcessorType,
-    SettingsAction, UIAction, UserAction,
+    AmllConnectorAction, DownloaderAction, FileAction, LineCopyFormat, LocalCacheAction,
+    LyricsAction, PanelType, PlayerAction, ProcessorType, SettingsAction, UIAction, UserAction,
 };
 use eframe::egui::{self, Align, Button, ComboBox, Layout, ScrollArea, Spinner, TextEdit};
 use egui::Color32;
 use log::LevelFilter;
-use lyrics_helper_core::{CanonicalMetadataKey, ChineseConversionConfig, FullLyricsResult};
+use lyrics_helper_core::{
+    AgentType, BackgroundSmoothingMode, BracketAgentRule, CanonicalMetadataKey,
+    ChineseConversionConfig, FullLyricsResult, LineEnding, LrcEndTimeOutputMode,
+    LrcSubLinesOutputMode, LrcTimestampPrecision, LyricFormat, OverlapFixMode, PunctuationWidth,
+    TtmlTimingMode,
+};
+use lyrics_helper_rs::converter::processors::line_editor::{self, TimingTarget};
+use lyrics_helper_rs::converter::processors::lyric_linter::LintSeverity;
+use lyrics_helper_rs::converter::processors::repeated_lines::find_repeated_lines;
+use lyrics_helper_rs::converter::processors::syllable_overlap_fixer;
 
 const TITLE_ALIGNMENT_OFFSET: f32 = 6.0;
 const BUTTON_STRIP_SPACING: f32 = 4.0;
+const LINE_NUMBER_GUTTER_PADDING: f32 = 8.0;
+const DIFF_DELETED_BACKGROUND: Color32 = Color32::from_rgba_premultiplied(120, 30, 30, 120);
+const DIFF_INSERTED_BACKGROUND: Color32 = Color32::from_rgba_premultiplied(30, 100, 30, 120);
+const TIMELINE_ZOOM_RANGE: std::ops::RangeInclusive<f32> = 20.0..=1000.0;
+const TIMELINE_LABEL_WIDTH: f32 = 36.0;
+const TIMELINE_RULER_HEIGHT: f32 = 20.0;
+const TIMELINE_WAVEFORM_HEIGHT: f32 = 48.0;
+const TIMELINE_WAVEFORM_COLOR: Color32 = Color32::from_rgb(90, 150, 110);
+const TIMELINE_ONSET_COLOR: Color32 = Color32::from_rgba_premultiplied(200, 160, 60, 140);
+const TIMELINE_ROW_HEIGHT: f32 = 34.0;
+const TIMELINE_LINE_BAR_HEIGHT: f32 = 8.0;
+const TIMELINE_HANDLE_WIDTH: f32 = 6.0;
+const TIMELINE_SNAP_TOLERANCE_PX: f32 = 8.0;
+/// 最后一行之后额外显示的时长
+const TIMELINE_TAIL_MS: u64 = 3000;
+const TIMELINE_LINE_COLOR: Color32 = Color32::from_rgb(70, 110, 160);
+const KARAOKE_FONT_SIZE: f32 = 16.0;
+const KARAOKE_ACTIVE_FONT_SIZE: f32 = 24.0;
+const KARAOKE_LINE_SPACING: f32 = 8.0;
+/// 按演唱者着色时依次使用的颜色
+const AGENT_COLORS: [Color32; 8] = [
+    Color32::from_rgb(90, 160, 230),
+    Color32::from_rgb(230, 120, 100),
+    Color32::from_rgb(110, 190, 110),
+    Color32::from_rgb(210, 170, 60),
+    Color32::from_rgb(170, 120, 220),
+    Color32::from_rgb(80, 190, 190),
+    Color32::from_rgb(220, 110, 170),
+    Color32::from_rgb(150, 150, 150),
+];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SettingsCategory {
@@ -54,6 +101,13 @@ impl UniLyricApp {
                         crate::app_actions::FileAction::Open,
                     ));
                 }
+                if file_menu
+                    .add(egui::Button::new("打开多个文件..."))
+                    .on_hover_text("在文件列表中快速切换编辑对象")
+                    .clicked()
+                {
+                    self.send_action(UserAction::File(FileAction::OpenMultiple));
+                }
                 file_menu.separator();
                 let main_lyrics_loaded = (self.lyrics.parsed_lyric_data.is_some()
                     && self.lyrics.parsed_lyric_data.as_ref().is_some())
@@ -101,12 +155,29 @@ impl UniLyricApp {
                     }
                 });
 
+                if file_menu
+                    .button("合并 LRC 为 TTML...")
+                    .on_hover_text("将分开的主歌词、翻译、罗马音 LRC 按时间戳对齐合并为一个 TTML")
+                    .clicked()
+                {
+                    self.send_action(UserAction::UI(UIAction::ShowPanel(PanelType::LrcMerge)));
+                }
+
                 file_menu.menu_button("批量处理...", |batch_menu| {
                     if batch_menu.button("批量转换...").clicked() {
                         self.send_action(UserAction::UI(UIAction::SetView(
                             AppView::BatchConverter,
                         )));
                     }
+                    if batch_menu
+                        .button("批量下载歌词...")
+                        .on_hover_text("按“标题 - 艺术家”列表批量搜索并下载最佳匹配")
+                        .clicked()
+                    {
+                        self.send_action(UserAction::UI(UIAction::ShowPanel(
+                            PanelType::BatchDownload,
+                        )));
+                    }
                 });
 
                 file_menu.separator();
@@ -121,6 +192,30 @@ impl UniLyricApp {
                         crate::app_actions::FileAction::Save,
                     ));
                 }
+
+                let lyrics_parsed = self.lyrics.parsed_lyric_data.is_some();
+                if file_menu
+                    .add_enabled(lyrics_parsed, egui::Button::new("导出翻译为 LRC..."))
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::File(FileAction::ExportTranslationLrc));
+                }
+                if file_menu
+                    .add_enabled(lyrics_parsed, egui::Button::new("导出罗马音为 LRC..."))
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::File(FileAction::ExportRomanizationLrc));
+                }
+                if file_menu
+                    .add_enabled(lyrics_parsed, egui::Button::new("导出为图片..."))
+                    .on_hover_text("把歌词渲染为一张 PNG 长图，便于分享")
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::UI(UIAction::ShowPanel(PanelType::LyricImage)));
+                }
             });
 
             ui_bar.menu_button("后处理", |postprocess_menu| {
@@ -146,6 +241,27 @@ impl UniLyricApp {
                     ))));
                 }
 
+                if let Some(current) = self.lyrics.smoothing_history.as_ref().map(|h| h.current) {
+                    let mut delta = None;
+                    postprocess_menu.horizontal(|h_ui| {
+                        if h_ui
+                            .add_enabled(current > 0, egui::Button::new("少平滑一次"))
+                            .clicked()
+                        {
+                            delta = Some(-1);
+                        }
+                        h_ui.label(format!("{current} 次"));
+                        if h_ui.button("多平滑一次").clicked() {
+                            delta = Some(1);
+                        }
+                    });
+                    if let Some(delta) = delta {
+                        self.send_action(UserAction::Lyrics(Box::new(
+                            LyricsAction::AdjustSmoothingIterations(delta),
+                        )));
+                    }
+                }
+
                 if postprocess_menu
                     .add_enabled(lyrics_loaded, egui::Button::new("演唱者识别"))
                     .on_disabled_hover_text("需要先成功解析歌词")
@@ -155,15 +271,191 @@ impl UniLyricApp {
                         ProcessorType::AgentRecognizer,
                     ))));
                 }
+
+                let has_regex_rules = self
+                    .app_settings
+                    .lock()
+                    .is_ok_and(|settings| settings.regex_replace.rules.iter().any(|r| r.enabled));
+                if postprocess_menu
+                    .add_enabled(
+                        lyrics_loaded && has_regex_rules,
+                        egui::Button::new("正则替换"),
+                    )
+                    .on_hover_text("按设置中的规则依次替换每行主歌词文本")
+                    .on_disabled_hover_text("需要先成功解析歌词，并在设置中添加替换规则")
+                    .clicked()
+                {
+                    self.send_action(UserAction::Lyrics(Box::new(LyricsAction::ApplyProcessor(
+                        ProcessorType::RegexReplace,
+                    ))));
+                }
+
+                if postprocess_menu
+                    .add_enabled(lyrics_loaded, egui::Button::new("智能换行"))
+                    .on_hover_text("将主歌词超过设置中字数上限的行按标点或空格拆分为多行")
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::Lyrics(Box::new(LyricsAction::ApplyProcessor(
+                        ProcessorType::LineWrapper,
+                    ))));
+                }
+
+                if postprocess_menu
+                    .add_enabled(lyrics_loaded, egui::Button::new("标点规范化"))
+                    .on_hover_text("按设置将主歌词的标点统一为全角或半角")
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::Lyrics(Box::new(LyricsAction::ApplyProcessor(
+                        ProcessorType::PunctuationNormalizer,
+                    ))));
+                }
+
+                let fixable_syllables = self.lyrics.parsed_lyric_data.as_ref().map_or(0, |data| {
+                    let options = self
+                        .app_settings
+                        .lock()
+                        .map(|settings| settings.syllable_overlap_fix)
+                        .unwrap_or_default();
+                    syllable_overlap_fixer::count_fixable_syllables(&data.lines, &options)
+                });
+                if postprocess_menu
+                    .add_enabled(
+                        fixable_syllables > 0,
+                        egui::Button::new(format!("修复音节重叠 ({fixable_syllables})")),
+                    )
+                    .on_hover_text(format!(
+                        "将修改 {fixable_syllables} 个与下一个音节时间重叠的音节，修复方式可在设置中选择"
+                    ))
+                    .on_disabled_hover_text("没有时间重叠的音节")
+                    .clicked()
+                {
+                    self.send_action(UserAction::Lyrics(Box::new(LyricsAction::ApplyProcessor(
+                        ProcessorType::SyllableOverlapFixer,
+                    ))));
+                }
+
+                postprocess_menu.separator();
+
+                if postprocess_menu
+                    .add_enabled(lyrics_loaded, egui::Button::new("检查歌词"))
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::Lyrics(Box::new(LyricsAction::LintLyrics)));
+                }
+
+                if postprocess_menu
+                    .add_enabled(lyrics_loaded, egui::Button::new("统计报告..."))
+                    .on_hover_text("统计行数、音节密度、最长/最短音节和翻译覆盖率")
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::Lyrics(Box::new(
+                        LyricsAction::GenerateStatsReport,
+                    )));
+                }
+
+                if postprocess_menu
+                    .add_enabled(lyrics_loaded, egui::Button::new("逐行编辑..."))
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::UI(UIAction::ShowPanel(PanelType::LineEditor)));
+                }
+
+                if postprocess_menu
+                    .add_enabled(lyrics_loaded, egui::Button::new("时间轴编辑..."))
+                    .on_hover_text("在时间轴上拖动调整行与音节的时间")
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::UI(UIAction::ShowPanel(PanelType::Timeline)));
+                }
+
+                if postprocess_menu
+                    .add_enabled(lyrics_loaded, egui::Button::new("歌词预览..."))
+                    .on_hover_text("在本地模拟播放并逐字高亮，无需连接播放器")
+                    .on_disabled_hover_text("需要先成功解析歌词")
+                    .clicked()
+                {
+                    self.send_action(UserAction::UI(UIAction::ShowPanel(
+                        PanelType::KaraokePreview,
+                    )));
+                }
+
+                if postprocess_menu
+                    .add_enabled(
+                        !self.lyrics.output_text.is_empty(),
+                        egui::Button::new("对比视图..."),
+                    )
+                    .on_hover_text("对比处理前后或输入与输出的文本差异")
+                    .clicked()
+                {
+                    self.send_action(UserAction::UI(UIAction::ShowPanel(PanelType::Diff)));
+                }
+
+                postprocess_menu.separator();
+
+                postprocess_menu.menu_button("操作脚本", |script_menu| {
+                    let script = &self.ui.action_script;
+                    let recording = script.recording;
+                    let replaying = script.is_replaying();
+                    let recorded_count = script.recorded.len();
+
+                    let record_label = if recording {
+                        format!("停止录制 ({recorded_count} 步)")
+                    } else {
+                        "开始录制".to_string()
+                    };
+                    if script_menu
+                        .add_enabled(!replaying, egui::Button::new(record_label))
+                        .on_hover_text("录制简繁转换、后处理、时间调整、切换目标格式和保存等操作")
+                        .clicked()
+                    {
+                        self.send_action(UserAction::Lyrics(Box::new(
+                            LyricsAction::SetScriptRecording(!recording),
+                        )));
+                        script_menu.close_menu();
+                    }
+
+                    if script_menu
+                        .add_enabled(
+                            !recording && recorded_count > 0,
+                            egui::Button::new("保存录制的脚本..."),
+                        )
+                        .on_disabled_hover_text("需要先录制并停止录制")
+                        .clicked()
+                    {
+                        self.send_action(UserAction::File(FileAction::SaveActionScript));
+                        script_menu.close_menu();
+                    }
+
+                    if script_menu
+                        .add_enabled(
+                            lyrics_loaded && !recording && !replaying,
+                            egui::Button::new("运行脚本..."),
+                        )
+                        .on_hover_text("对当前歌词依次重放脚本中的操作")
+                        .on_disabled_hover_text("需要先成功解析歌词，且不在录制或运行脚本")
+                        .clicked()
+                    {
+                        self.send_action(UserAction::File(FileAction::RunActionScript));
+                        script_menu.close_menu();
+                    }
+
+                    if replaying {
+                        script_menu.weak(format!(
+                            "正在运行，剩余 {} 步",
+                            self.ui.action_script.pending.len()
+                        ));
+                    }
+                });
             });
 
             ui_bar.menu_button("简繁转换", |tools_menu| {
-                let conversion_enabled = !self.lyrics.input_text.is_empty()
-                    || self
-                        .lyrics
-                        .parsed_lyric_data
-                        .as_ref()
-                        .is_some_and(|d| !d.lines.is_empty());
+                let conversion_enabled = self.lyrics.has_lyrics_to_convert();
 
                 tools_menu.label(egui::RichText::new("通用简繁转换").strong());
                 self.draw_chinese_conversion_menu_item(
@@ -267,8 +559,68 @@ impl UniLyricApp {
                     "繁体旧字体 → 日语新字体",
                     conversion_enabled,
                 );
+                tools_menu.separator();
+
+                tools_menu.label(egui::RichText::new("翻译转换").strong());
+                tools_menu.menu_button("仅转换翻译", |sub_menu| {
+                    for (variant, label) in [
+                        (ChineseConversionConfig::S2t, "简体 → 繁体 (通用)"),
+                        (ChineseConversionConfig::T2s, "繁体 → 简体 (通用)"),
+                        (ChineseConversionConfig::S2twp, "简体 → 台湾正体"),
+                        (ChineseConversionConfig::S2hk, "简体 → 香港繁体"),
+                        (
+                            ChineseConversionConfig::Tw2sp,
+                            "台湾正体 → 大陆简体 (含用语)",
+                        ),
+                        (ChineseConversionConfig::Hk2s, "香港繁体 → 简体"),
+                    ] {
+                        if sub_menu
+                            .add_enabled(conversion_enabled, Button::new(label))
+                            .on_hover_text("只转换翻译，主歌词保持不变")
+                            .on_disabled_hover_text("请先加载主歌词")
+                            .clicked()
+                        {
+                            self.send_action(UserAction::Lyrics(Box::new(
+                                LyricsAction::ConvertChineseTranslation(variant),
+                            )));
+                            sub_menu.close_menu();
+                        }
+                    }
+                });
+                tools_menu.separator();
+                tools_menu.weak("点击 ☆ 可将转换添加到工具栏");
             });
 
+            let favorite_conversions = self
+                .app_settings
+                .lock()
+                .unwrap()
+                .favorite_chinese_conversions
+                .clone();
+            let conversion_enabled = self.lyrics.has_lyrics_to_convert();
+            for config in favorite_conversions {
+                let response = ui_bar
+                    .add_enabled(
+                        conversion_enabled,
+                        Button::new(chinese_conversion_short_label(config)),
+                    )
+                    .on_hover_text("收藏的简繁转换，右键可从工具栏移除")
+                    .on_disabled_hover_text("请先加载主歌词");
+                if response.clicked() {
+                    self.send_action(UserAction::Lyrics(Box::new(LyricsAction::ConvertChinese(
+                        config,
+                    ))));
+                }
+                response.context_menu(|menu| {
+                    if menu.button("从工具栏移除").clicked() {
+                        self.send_action(UserAction::Settings(
+                            SettingsAction::ToggleChineseConversionFavorite(config),
+                        ));
+                        menu.close_menu();
+                    }
+                });
+            }
+
             ui_bar.add_space(16.0);
             ui_bar.label("源格式:");
             let mut temp_source_format = self.lyrics.source_format;
@@ -278,7 +630,8 @@ impl UniLyricApp {
                 .show_ui(ui_bar, |ui_combo| {
                     for fmt_option in &self.lyrics.available_formats {
                         let display_text = fmt_option.to_string();
-                        let is_selectable_source = true;
+                        // WebVTT 目前仅支持导出
+                        let is_selectable_source = *fmt_option != LyricFormat::Vtt;
 
                         let response = ui_combo
                             .add_enabled_ui(is_selectable_source, |ui_selectable| {
@@ -312,10 +665,20 @@ impl UniLyricApp {
                 .show_ui(ui_bar, |ui_combo| {
                     for fmt_option in &self.lyrics.available_formats {
                         let display_text = fmt_option.to_string();
-                        if ui_combo
-                            .selectable_value(&mut temp_target_format, *fmt_option, display_text)
-                            .clicked()
-                        {
+                        // Musixmatch JSON 目前仅支持导入
+                        let is_selectable_target = *fmt_option != LyricFormat::Musixmatch;
+
+                        let response = ui_combo
+                            .add_enabled_ui(is_selectable_target, |ui_selectable| {
+                                ui_selectable.selectable_value(
+                                    &mut temp_target_format,
+                                    *fmt_option,
+                                    display_text,
+                                )
+                            })
+                            .inner;
+
+                        if response.clicked() && is_selectable_target {
                             ui_combo.close_menu();
                         }
                     }
@@ -327,6 +690,20 @@ impl UniLyricApp {
                 )));
             }
 
+            if ui_bar
+                .add_enabled(
+                    FormatOptionsSettings::has_options_for(self.lyrics.target_format),
+                    Button::new("选项"),
+                )
+                .on_hover_text("配置当前目标格式的生成选项")
+                .on_disabled_hover_text("该格式没有可配置的选项")
+                .clicked()
+            {
+                self.send_action(UserAction::UI(UIAction::ShowPanel(
+                    PanelType::FormatOptions,
+                )));
+            }
+
             ui_bar.with_layout(Layout::right_to_left(Align::Center), |ui_right| {
                 ui_right.menu_button("视图", |view_menu| {
                     let mut show_translation_lrc_panel_copy = self.ui.show_translation_lrc_panel;
@@ -355,6 +732,21 @@ impl UniLyricApp {
                         ));
                     }
 
+                    let mut show_document_list_copy = self.ui.show_document_list_panel;
+                    if view_menu
+                        .add_enabled(
+                            self.lyrics.open_documents.len() > 1,
+                            egui::Checkbox::new(&mut show_document_list_copy, "已打开文件列表"),
+                        )
+                        .on_disabled_hover_text("打开多个文件后可用")
+                        .changed()
+                    {
+                        self.send_action(UserAction::UI(UIAction::SetPanelVisibility(
+                            PanelType::DocumentList,
+                            show_document_list_copy,
+                        )));
+                    }
+
                     view_menu.separator();
 
                     let amll_connector_feature_enabled =
@@ -379,6 +771,18 @@ impl UniLyricApp {
 
                     view_menu.separator();
 
+                    let mut show_line_numbers_copy = self.ui.show_line_numbers;
+                    if view_menu
+                        .checkbox(&mut show_line_numbers_copy, "行号")
+                        .changed()
+                    {
+                        self.send_action(crate::app_actions::UserAction::UI(
+                            crate::app_actions::UIAction::SetShowLineNumbers(
+                                show_line_numbers_copy,
+                            ),
+                        ));
+                    }
+
                     let mut show_log_panel_copy = self.ui.show_bottom_log_panel;
                     if view_menu
                         .checkbox(&mut show_log_panel_copy, "日志面板")
@@ -429,6 +833,23 @@ impl UniLyricApp {
             .default_width(700.0)
             .max_height(450.0)
             .show(ctx, |ui| {
+                let search_response = ui.add(
+                    TextEdit::singleline(&mut self.ui.settings_search.query)
+                        .hint_text("🔍 搜索设置项...")
+                        .desired_width(f32::INFINITY),
+                );
+                let search_matches = self.ui.settings_search.matches();
+                if search_response.changed()
+                    && let Some(first) = search_matches.first()
+                    && !search_matches
+                        .iter()
+                        .any(|entry| entry.category == self.ui.current_settings_category)
+                {
+                    // 当前分类中没有命中项时，自动切换到第一个有命中项的分类
+                    self.ui.current_settings_category = first.category;
+                }
+                ui.add_space(4.0);
+
                 ui.horizontal_top(|h_ui| {
                     egui::SidePanel::left("settings_category_panel")
                         .exact_width(140.0)
@@ -445,13 +866,47 @@ impl UniLyricApp {
                                 SettingsCategory::Postprocessors,
                             ];
 
+                            let searching = !self.ui.settings_search.query.trim().is_empty();
                             for category in categories {
+                                let hits = search_matches
+                                    .iter()
+                                    .filter(|entry| entry.category == category)
+                                    .count();
+                                let text = match (searching, hits) {
+                                    (false, _) => egui::RichText::new(category.display_name()),
+                                    (true, 0) => {
+                                        egui::RichText::new(category.display_name()).weak()
+                                    }
+                                    (true, _) => egui::RichText::new(format!(
+                                        "{} ({hits})",
+                                        category.display_name()
+                                    ))
+                                    .strong(),
+                                };
                                 nav_ui.selectable_value(
                                     &mut self.ui.current_settings_category,
                                     category,
-                                    category.display_name(),
+                                    text,
                                 );
                             }
+
+                            if searching {
+                                nav_ui.separator();
+                                if search_matches.is_empty() {
+                                    nav_ui.weak("没有匹配的设置项");
+                                }
+                                for entry in &search_matches {
+                                    let name = entry.label.trim_end_matches([':', '：']);
+                                    if nav_ui
+                                        .selectable_label(false, name)
+                                        .on_hover_text(entry.category.display_name())
+                                        .clicked()
+                                    {
+                                        self.ui.current_settings_category = entry.category;
+                                        self.ui.settings_search.jump_target = Some(entry.label);
+                                    }
+                                }
+                            }
                         });
 
                     egui::CentralPanel::default().show_inside(h_ui, |content_ui| {
@@ -503,6 +958,25 @@ impl UniLyricApp {
                                 )),
                             ));
                         }
+                        bottom_buttons_ui.with_layout(
+                            Layout::left_to_right(egui::Align::Center),
+                            |left_ui| {
+                                if left_ui
+                                    .button("导出配置...")
+                                    .on_hover_text("将已保存的设置导出为可在其他机器导入的文件")
+                                    .clicked()
+                                {
+                                    self.send_action(UserAction::Settings(SettingsAction::Export));
+                                }
+                                if left_ui
+                                    .button("导入配置...")
+                                    .on_hover_text("从配置文件合并设置，导入前会列出将被改变的项")
+                                    .clicked()
+                                {
+                                    self.send_action(UserAction::Settings(SettingsAction::Import));
+                                }
+                            },
+                        );
                     },
                 );
             });
@@ -521,14 +995,14 @@ impl UniLyricApp {
             .spacing([40.0, 4.0])
             .striped(true)
             .show(ui, |grid_ui| {
-                grid_ui.label("启用文件日志:");
+                self.ui.settings_search.label(grid_ui, "启用文件日志:");
                 grid_ui.checkbox(
                     &mut self.ui.temp_edit_settings.log_settings.enable_file_log,
                     "",
                 );
                 grid_ui.end_row();
 
-                grid_ui.label("文件日志级别:");
+                self.ui.settings_search.label(grid_ui, "文件日志级别:");
                 ComboBox::from_id_salt("file_log_level_combo_settings")
                     .selected_text(format!(
                         "{:?}",
@@ -568,7 +1042,7 @@ impl UniLyricApp {
                     });
                 grid_ui.end_row();
 
-                grid_ui.label("控制台日志级别:");
+                self.ui.settings_search.label(grid_ui, "控制台日志级别:");
                 ComboBox::from_id_salt("console_log_level_combo_settings")
                     .selected_text(format!(
                         "{:?}",
@@ -608,6 +1082,80 @@ impl UniLyricApp {
                     });
                 grid_ui.end_row();
             });
+
+        ui.add_space(10.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        let session_autosave_enabled = self.ui.temp_edit_settings.session_autosave_enabled;
+        self.ui
+            .settings_search
+            .checkbox(
+                ui,
+                &mut self.ui.temp_edit_settings.session_autosave_enabled,
+                "自动保存工作区会话",
+            )
+            .on_hover_text("定期保存正在编辑的歌词和格式选择，下次启动时可以恢复");
+
+        ui.add_enabled_ui(session_autosave_enabled, |enabled_ui| {
+            enabled_ui.horizontal(|h_ui| {
+                self.ui.settings_search.label(h_ui, "保存间隔:");
+                h_ui.add(
+                    egui::DragValue::new(
+                        &mut self.ui.temp_edit_settings.session_autosave_interval_secs,
+                    )
+                    .range(5..=600)
+                    .suffix(" 秒"),
+                );
+            });
+        });
+
+        ui.add_space(10.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        self.ui
+            .settings_search
+            .checkbox(
+                ui,
+                &mut self.ui.temp_edit_settings.save_output_with_bom,
+                "保存输出时添加 UTF-8 BOM",
+            )
+            .on_hover_text("部分 Windows 程序需要 BOM 才能正确识别 UTF-8 文件");
+
+        ui.horizontal(|h_ui| {
+            self.ui.settings_search.label(h_ui, "保存输出时的换行符:");
+            let line_ending = &mut self.ui.temp_edit_settings.output_line_ending;
+            let options = [
+                (LineEnding::Lf, "LF (\\n)"),
+                (LineEnding::Crlf, "CRLF (\\r\\n)"),
+                (LineEnding::Keep, "与输入文件一致"),
+            ];
+            let selected_text = options
+                .iter()
+                .find(|(value, _)| *value == *line_ending)
+                .map_or("", |(_, label)| *label);
+            ComboBox::from_id_salt("output_line_ending_combo_settings")
+                .selected_text(selected_text)
+                .show_ui(h_ui, |ui_combo| {
+                    for (value, label) in options {
+                        ui_combo.selectable_value(line_ending, value, label);
+                    }
+                })
+                .response
+                .on_hover_text("默认跟随当前平台。选择“与输入文件一致”时按输入歌词的换行符保存");
+        });
+
+        self.ui
+            .settings_search
+            .checkbox(
+                ui,
+                &mut self.ui.temp_edit_settings.lrc_parsing.keep_offset_tag,
+                "导入 LRC 时保留 [offset:] 标签",
+            )
+            .on_hover_text(
+                "默认把偏移量应用到所有时间戳并移除该标签。\n勾选后时间戳保持不变，标签作为元数据保留并在导出 LRC 时写回，\n但转换为其他格式时偏移量不会生效。",
+            );
     }
 
     fn draw_settings_interface(&mut self, ui: &mut egui::Ui) {
@@ -615,7 +1163,7 @@ impl UniLyricApp {
         ui.add_space(10.0);
 
         ui.horizontal(|h_ui| {
-            h_ui.label("界面字体:");
+            self.ui.settings_search.label(h_ui, "界面字体:");
 
             let mut selected = self
                 .ui
@@ -652,11 +1200,15 @@ impl UniLyricApp {
 
         let auto_cache_enabled = self.ui.temp_edit_settings.auto_cache;
 
-        ui.checkbox(&mut self.ui.temp_edit_settings.auto_cache, "自动缓存歌词");
+        self.ui.settings_search.checkbox(
+            ui,
+            &mut self.ui.temp_edit_settings.auto_cache,
+            "自动缓存歌词",
+        );
 
         ui.add_enabled_ui(auto_cache_enabled, |enabled_ui| {
             enabled_ui.horizontal(|h_ui| {
-                h_ui.label("最多缓存数量:");
+                self.ui.settings_search.label(h_ui, "最多缓存数量:");
                 h_ui.add(
                     egui::DragValue::new(&mut self.ui.temp_edit_settings.auto_cache_max_count)
                         .speed(1.0),
@@ -665,20 +1217,26 @@ impl UniLyricApp {
         });
 
         ui.separator();
-        ui.checkbox(
+        self.ui.settings_search.checkbox(
+            ui,
             &mut self.ui.temp_edit_settings.prioritize_amll_db,
             "优先搜索 AMLL TTML 数据库 (推荐)",
         );
-        ui.checkbox(
+        self.ui.settings_search.checkbox(
+            ui,
             &mut self.ui.temp_edit_settings.enable_t2s_for_auto_search,
             "将繁体 SMTC 信息转为简体再搜索 (推荐)",
         );
-        ui.checkbox(
+        self.ui.settings_search.checkbox(
+            ui,
             &mut self.ui.temp_edit_settings.always_search_all_sources,
             "始终搜索所有源 (推荐)",
         );
         ui.add_space(10.0);
-        ui.checkbox(
+        self.draw_auto_search_source_order_editor(ui);
+        ui.add_space(10.0);
+        self.ui.settings_search.checkbox(
+            ui,
             &mut self.ui.temp_edit_settings.use_provider_subset,
             "只在以下选择的源中搜索:",
         );
@@ -716,18 +1274,259 @@ impl UniLyricApp {
                 });
             },
         );
+        ui.separator();
+        self.draw_custom_http_source_settings(ui);
+        ui.separator();
+        self.draw_network_settings(ui);
+        ui.separator();
+        self.ui
+            .settings_search
+            .checkbox(
+                ui,
+                &mut self.ui.temp_edit_settings.downloader_auto_search,
+                "下载器中输入后自动搜索",
+            )
+            .on_hover_text("停止输入 500 毫秒后自动搜索，歌曲名为空时不会触发");
+        self.ui
+            .settings_search
+            .checkbox(
+                ui,
+                &mut self.ui.temp_edit_settings.downloader_auto_preview_first,
+                "搜索完成后自动预览首个结果",
+            )
+            .on_hover_text("开启“仅显示逐字歌词”时会跳过被隐藏的结果");
     }
 
-    fn draw_settings_amll_connector(&mut self, ui: &mut egui::Ui) {
-        ui.heading("AMLL Connector 设置");
-        ui.add_space(10.0);
-
-        egui::Grid::new("amll_connector_settings_grid")
-            .num_columns(2)
-            .spacing([40.0, 4.0])
+    /// 绘制请求超时和代理的配置。
+    fn draw_network_settings(&mut self, ui: &mut egui::Ui) {
+        let network = &mut self.ui.temp_edit_settings.network;
+        ui.horizontal(|h_ui| {
+            self.ui.settings_search.label(h_ui, "请求超时:");
+            h_ui.add(
+                egui::DragValue::new(&mut network.timeout_secs)
+                    .range(1..=120)
+                    .suffix(" 秒"),
+            );
+        });
+        ui.horizontal(|h_ui| {
+            self.ui
+                .settings_search
+                .label(h_ui, "代理地址:")
+                .on_hover_text("应用到所有歌词源和 AMLL 镜像请求，留空时使用系统默认设置");
+            h_ui.add(
+                egui::TextEdit::singleline(&mut network.proxy)
+                    .hint_text("http://127.0.0.1:7890 或 socks5://127.0.0.1:1080")
+                    .desired_width(260.0),
+            );
+        });
+        if let Err(e) = network.validate_proxy() {
+            ui.colored_label(ui.visuals().error_fg_color, e);
+        }
+    }
+
+    /// 绘制自定义 HTTP 歌词源的配置。
+    fn draw_custom_http_source_settings(&mut self, ui: &mut egui::Ui) {
+        self.ui
+            .settings_search
+            .checkbox(
+                ui,
+                &mut self.ui.temp_edit_settings.custom_http_source_enabled,
+                "启用自定义歌词源",
+            )
+            .on_hover_text("通过 HTTP 接口搜索和获取歌词，搜索接口需要返回 JSON");
+
+        let enabled = self.ui.temp_edit_settings.custom_http_source_enabled;
+        let available_formats: Vec<LyricFormat> = self
+            .lyrics
+            .target_formats()
+            .into_iter()
+            .filter(|format| *format != LyricFormat::Vtt)
+            .collect();
+        let config = &mut self.ui.temp_edit_settings.custom_http_source;
+
+        ui.add_enabled_ui(enabled, |enabled_ui| {
+            egui::Grid::new("custom_http_source_grid")
+                .num_columns(2)
+                .spacing([10.0, 6.0])
+                .show(enabled_ui, |grid_ui| {
+                    grid_ui
+                        .label("搜索 URL:")
+                        .on_hover_text("可用占位符: {title} {artist} {album} {duration}（毫秒）");
+                    grid_ui.add(
+                        egui::TextEdit::singleline(&mut config.search_url_template)
+                            .hint_text("https://example.com/search?q={title}+{artist}")
+                            .desired_width(320.0),
+                    );
+                    grid_ui.end_row();
+
+                    grid_ui
+                        .label("歌词 URL:")
+                        .on_hover_text("{id} 会被替换为搜索结果的 ID");
+                    grid_ui.add(
+                        egui::TextEdit::singleline(&mut config.lyrics_url_template)
+                            .hint_text("https://example.com/lyrics/{id}")
+                            .desired_width(320.0),
+                    );
+                    grid_ui.end_row();
+
+                    let path_row = |row_ui: &mut egui::Ui, label: &str, value: &mut String| {
+                        row_ui.label(label);
+                        row_ui.add(egui::TextEdit::singleline(value).desired_width(200.0));
+                        row_ui.end_row();
+                    };
+                    path_row(grid_ui, "结果数组路径:", &mut config.results_path);
+                    path_row(grid_ui, "ID 字段:", &mut config.id_field);
+                    path_row(grid_ui, "标题字段:", &mut config.title_field);
+                    path_row(grid_ui, "艺术家字段:", &mut config.artists_field);
+                    path_row(grid_ui, "专辑字段:", &mut config.album_field);
+                    path_row(grid_ui, "时长字段 (毫秒):", &mut config.duration_field);
+                    path_row(grid_ui, "歌词字段:", &mut config.lyrics_field);
+
+                    grid_ui.label("歌词格式:");
+                    ComboBox::from_id_salt("custom_http_lyrics_format")
+                        .selected_text(config.lyrics_format.to_string())
+                        .show_ui(grid_ui, |combo_ui| {
+                            for format in available_formats {
+                                combo_ui.selectable_value(
+                                    &mut config.lyrics_format,
+                                    format,
+                                    format.to_string(),
+                                );
+                            }
+                        });
+                    grid_ui.end_row();
+                });
+            enabled_ui
+                .weak("字段路径以点分隔，如 data.songs；歌词字段留空表示响应体即为歌词文本。");
+        });
+    }
+
+    /// 绘制可拖拽排序的自动搜索源优先级列表。
+    fn draw_auto_search_source_order_editor(&mut self, ui: &mut egui::Ui) {
+        let response = ui
+            .strong("搜索源优先级 (拖拽排序):")
+            .on_hover_text("匹配度相同时，排在前面的源优先被选为最佳结果，侧边栏也按此顺序显示");
+        self.ui
+            .settings_search
+            .mark(ui, &response, "搜索源优先级 (拖拽排序):");
+
+        let order =
+            AutoSearchSource::normalize_order(&self.ui.temp_edit_settings.auto_search_source_order);
+        let mut move_request: Option<(usize, usize)> = None;
+
+        egui::Frame::group(ui.style()).show(ui, |group_ui| {
+            for (index, source) in order.iter().enumerate() {
+                let item_id = egui::Id::new("auto_search_source_order_item").with(index);
+                let response = group_ui
+                    .dnd_drag_source(item_id, index, |drag_ui| {
+                        drag_ui.horizontal(|h_ui| {
+                            h_ui.label("☰");
+                            h_ui.label(format!("{}. {}", index + 1, source.display_name()));
+                        });
+                    })
+                    .response;
+
+                if let (Some(pointer), Some(hovered_index)) = (
+                    group_ui.input(|i| i.pointer.interact_pos()),
+                    response.dnd_hover_payload::<usize>(),
+                ) && *hovered_index != index
+                {
+                    let rect = response.rect;
+                    let y = if pointer.y < rect.center().y {
+                        rect.top()
+                    } else {
+                        rect.bottom()
+                    };
+                    group_ui.painter().hline(
+                        rect.x_range(),
+                        y,
+                        group_ui.visuals().selection.stroke,
+                    );
+                }
+
+                if let Some(dragged_index) = response.dnd_release_payload::<usize>() {
+                    move_request = Some((*dragged_index, index));
+                }
+            }
+        });
+
+        if let Some((from, to)) = move_request
+            && from != to
+        {
+            let mut new_order = order;
+            let item = new_order.remove(from);
+            new_order.insert(to, item);
+            self.ui.temp_edit_settings.auto_search_source_order = new_order;
+        }
+
+        if ui.button("恢复默认顺序").clicked() {
+            self.ui.temp_edit_settings.auto_search_source_order = AutoSearchSource::default_order();
+        }
+    }
+
+    /// 绘制连接事件时间线，最新的事件在最上方。
+    fn draw_connection_timeline(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(format!(
+            "连接事件时间线 ({})",
+            self.amll_connector.timeline.len()
+        ))
+        .id_salt("amll_connection_timeline")
+        .show(ui, |ui| {
+            if self.amll_connector.timeline.is_empty() {
+                ui.weak("暂无连接事件");
+                return;
+            }
+            if ui.button("清空").clicked() {
+                self.amll_connector.timeline.clear();
+                return;
+            }
+
+            ScrollArea::vertical()
+                .id_salt("amll_connection_timeline_scroll")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("amll_connection_timeline_grid")
+                        .num_columns(3)
+                        .spacing([16.0, 2.0])
+                        .striped(true)
+                        .show(ui, |grid| {
+                            for entry in self.amll_connector.timeline.iter().rev() {
+                                grid.monospace(entry.time.format("%m-%d %H:%M:%S").to_string());
+                                let (status_text, color) = match &entry.status {
+                                    WebsocketStatus::Connected => {
+                                        ("已连接".to_string(), Color32::GREEN)
+                                    }
+                                    WebsocketStatus::Connecting => {
+                                        ("连接中".to_string(), grid.visuals().text_color())
+                                    }
+                                    WebsocketStatus::Disconnected => {
+                                        ("已断开".to_string(), grid.visuals().weak_text_color())
+                                    }
+                                    WebsocketStatus::Error { message, .. } => {
+                                        (format!("错误: {message}"), Color32::RED)
+                                    }
+                                };
+                                grid.label(entry.cause.label());
+                                grid.colored_label(color, status_text);
+                                grid.end_row();
+                            }
+                        });
+                });
+        });
+    }
+
+    fn draw_settings_amll_connector(&mut self, ui: &mut egui::Ui) {
+        ui.heading("AMLL Connector 设置");
+        ui.add_space(10.0);
+
+        egui::Grid::new("amll_connector_settings_grid")
+            .num_columns(2)
+            .spacing([40.0, 4.0])
             .striped(true)
             .show(ui, |grid_ui| {
-                grid_ui.label("启用 AMLL Connector 功能:");
+                self.ui
+                    .settings_search
+                    .label(grid_ui, "启用 AMLL Connector 功能:");
                 grid_ui
                     .checkbox(&mut self.ui.temp_edit_settings.amll_connector_enabled, "")
                     .on_hover_text(
@@ -735,7 +1534,7 @@ impl UniLyricApp {
                     );
                 grid_ui.end_row();
 
-                grid_ui.label("WebSocket URL:");
+                self.ui.settings_search.label(grid_ui, "WebSocket URL:");
                 grid_ui
                     .add(
                         TextEdit::singleline(
@@ -747,15 +1546,64 @@ impl UniLyricApp {
                     .on_hover_text("需点击“保存并应用”");
                 grid_ui.end_row();
 
-                grid_ui.label("将音频数据发送到 AMLL Player");
+                self.ui
+                    .settings_search
+                    .label(grid_ui, "歌词发送模式:")
+                    .on_hover_text("纯文本模式只发送每行文字，适配只能显示静态歌词的客户端");
+                let send_mode = &mut self.ui.temp_edit_settings.amll_connector_lyric_send_mode;
+                ComboBox::from_id_salt("amll_lyric_send_mode")
+                    .selected_text(send_mode.display_name())
+                    .show_ui(grid_ui, |combo_ui| {
+                        for mode in [LyricSendMode::Structured, LyricSendMode::PlainText] {
+                            combo_ui.selectable_value(send_mode, mode, mode.display_name());
+                        }
+                    });
+                grid_ui.end_row();
+
+                self.ui
+                    .settings_search
+                    .label(grid_ui, "心跳间隔 (秒):")
+                    .on_hover_text("定期向 AMLL Player 发送 Ping 以检测连接是否仍然有效");
+                grid_ui.add(
+                    egui::DragValue::new(
+                        &mut self
+                            .ui
+                            .temp_edit_settings
+                            .amll_connector_heartbeat_interval_secs,
+                    )
+                    .range(1..=300)
+                    .suffix(" s"),
+                );
+                grid_ui.end_row();
+
+                self.ui
+                    .settings_search
+                    .label(grid_ui, "心跳超时 (秒):")
+                    .on_hover_text("超过该时长未收到 Pong 时，将视为连接失效并尝试重连");
+                grid_ui.add(
+                    egui::DragValue::new(
+                        &mut self
+                            .ui
+                            .temp_edit_settings
+                            .amll_connector_heartbeat_timeout_secs,
+                    )
+                    .range(1..=300)
+                    .suffix(" s"),
+                );
+                grid_ui.end_row();
+
+                self.ui
+                    .settings_search
+                    .label(grid_ui, "将音频数据发送到 AMLL Player");
                 grid_ui.checkbox(
                     &mut self.ui.temp_edit_settings.send_audio_data_to_player,
                     "",
                 );
                 grid_ui.end_row();
 
-                grid_ui
-                    .label("时间轴偏移量 (毫秒):")
+                self.ui
+                    .settings_search
+                    .label(grid_ui, "时间轴偏移量 (毫秒):")
                     .on_hover_text("调整SMTC报告的时间戳以匹配歌词");
                 grid_ui.add(
                     egui::DragValue::new(&mut self.ui.temp_edit_settings.smtc_time_offset_ms)
@@ -764,8 +1612,9 @@ impl UniLyricApp {
                 );
                 grid_ui.end_row();
 
-                grid_ui
-                    .label("校准时间轴")
+                self.ui
+                    .settings_search
+                    .label(grid_ui, "校准时间轴")
                     .on_hover_text("切歌时立刻跳转到0ms，可能对 Spotify 有奇效");
                 grid_ui.checkbox(
                     &mut self.ui.temp_edit_settings.calibrate_timeline_on_song_change,
@@ -773,8 +1622,9 @@ impl UniLyricApp {
                 );
                 grid_ui.end_row();
 
-                grid_ui
-                    .label("在新曲目开始时快速暂停/播放")
+                self.ui
+                    .settings_search
+                    .label(grid_ui, "在新曲目开始时快速暂停/播放")
                     .on_hover_text("更强力地校准时间轴");
                 grid_ui.checkbox(
                     &mut self.ui.temp_edit_settings.flicker_play_pause_on_song_change,
@@ -783,7 +1633,10 @@ impl UniLyricApp {
                 grid_ui.end_row();
             });
         ui.add_space(10.0);
-        ui.strong("AMLL DB 镜像");
+        self.draw_connection_timeline(ui);
+        ui.add_space(10.0);
+        let response = ui.strong("AMLL DB 镜像");
+        self.ui.settings_search.mark(ui, &response, "AMLL DB 镜像");
 
         ui.horizontal(|h_ui| {
             if h_ui.button("立即检查更新").clicked() {
@@ -847,115 +1700,500 @@ impl UniLyricApp {
 
         ui.strong("自动应用");
         ui.label("自动获取歌词后，运行以下后处理器：");
-        ui.checkbox(
+        self.ui.settings_search.checkbox(
+            ui,
             &mut self.ui.temp_edit_settings.auto_apply_metadata_stripper,
             "清理元数据行",
         );
-        ui.checkbox(
+        self.ui.settings_search.checkbox(
+            ui,
             &mut self.ui.temp_edit_settings.auto_apply_agent_recognizer,
             "识别演唱者",
         );
         ui.separator();
 
-        ui.collapsing("元数据清理器", |stripper_ui| {
-            let options = &mut self.ui.temp_edit_settings.metadata_stripper;
+        let section = egui::CollapsingHeader::new("元数据清理器")
+            .open(self.ui.settings_search.section_open("元数据清理器"))
+            .show(ui, |stripper_ui| {
+                let options = &mut self.ui.temp_edit_settings.metadata_stripper;
+
+                let mut is_enabled = options
+                    .flags
+                    .contains(lyrics_helper_core::MetadataStripperFlags::ENABLED);
+                if self
+                    .ui
+                    .settings_search
+                    .checkbox(stripper_ui, &mut is_enabled, "启用元数据清理")
+                    .changed()
+                {
+                    options.flags.set(
+                        lyrics_helper_core::MetadataStripperFlags::ENABLED,
+                        is_enabled,
+                    );
+                }
 
-            let mut is_enabled = options
-                .flags
-                .contains(lyrics_helper_core::MetadataStripperFlags::ENABLED);
-            if stripper_ui
-                .checkbox(&mut is_enabled, "启用元数据清理")
-                .changed()
-            {
-                options.flags.set(
-                    lyrics_helper_core::MetadataStripperFlags::ENABLED,
-                    is_enabled,
-                );
-            }
+                stripper_ui
+                    .horizontal(|targets_ui| {
+                        self.ui.settings_search.label(targets_ui, "作用于:");
+                        for (target, label) in [
+                            (lyrics_helper_core::MetadataStripperTargets::MAIN, "主歌词"),
+                            (
+                                lyrics_helper_core::MetadataStripperTargets::TRANSLATION,
+                                "翻译",
+                            ),
+                        ] {
+                            let mut is_selected = options.targets.contains(target);
+                            if targets_ui.checkbox(&mut is_selected, label).changed() {
+                                options.targets.set(target, is_selected);
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("主歌词中的元数据行会被整行移除；翻译中的元数据只移除该条翻译");
+
+                let mut keyword_case_sensitive = options
+                    .flags
+                    .contains(lyrics_helper_core::MetadataStripperFlags::KEYWORD_CASE_SENSITIVE);
+                if self
+                    .ui
+                    .settings_search
+                    .checkbox(
+                        stripper_ui,
+                        &mut keyword_case_sensitive,
+                        "关键词匹配区分大小写",
+                    )
+                    .changed()
+                {
+                    options.flags.set(
+                        lyrics_helper_core::MetadataStripperFlags::KEYWORD_CASE_SENSITIVE,
+                        keyword_case_sensitive,
+                    );
+                }
 
-            let mut keyword_case_sensitive = options
-                .flags
-                .contains(lyrics_helper_core::MetadataStripperFlags::KEYWORD_CASE_SENSITIVE);
-            if stripper_ui
-                .checkbox(&mut keyword_case_sensitive, "关键词匹配区分大小写")
-                .changed()
-            {
-                options.flags.set(
-                    lyrics_helper_core::MetadataStripperFlags::KEYWORD_CASE_SENSITIVE,
-                    keyword_case_sensitive,
-                );
-            }
+                let mut regex_enabled = options
+                    .flags
+                    .contains(lyrics_helper_core::MetadataStripperFlags::ENABLE_REGEX_STRIPPING);
+                if self
+                    .ui
+                    .settings_search
+                    .checkbox(stripper_ui, &mut regex_enabled, "启用正则表达式清理")
+                    .changed()
+                {
+                    options.flags.set(
+                        lyrics_helper_core::MetadataStripperFlags::ENABLE_REGEX_STRIPPING,
+                        regex_enabled,
+                    );
+                }
 
-            let mut regex_enabled = options
-                .flags
-                .contains(lyrics_helper_core::MetadataStripperFlags::ENABLE_REGEX_STRIPPING);
-            if stripper_ui
-                .checkbox(&mut regex_enabled, "启用正则表达式清理")
-                .changed()
-            {
-                options.flags.set(
-                    lyrics_helper_core::MetadataStripperFlags::ENABLE_REGEX_STRIPPING,
-                    regex_enabled,
-                );
-            }
+                let mut regex_case_sensitive = options
+                    .flags
+                    .contains(lyrics_helper_core::MetadataStripperFlags::REGEX_CASE_SENSITIVE);
+                if self
+                    .ui
+                    .settings_search
+                    .checkbox(
+                        stripper_ui,
+                        &mut regex_case_sensitive,
+                        "正则表达式匹配区分大小写",
+                    )
+                    .changed()
+                {
+                    options.flags.set(
+                        lyrics_helper_core::MetadataStripperFlags::REGEX_CASE_SENSITIVE,
+                        regex_case_sensitive,
+                    );
+                }
 
-            let mut regex_case_sensitive = options
-                .flags
-                .contains(lyrics_helper_core::MetadataStripperFlags::REGEX_CASE_SENSITIVE);
-            if stripper_ui
-                .checkbox(&mut regex_case_sensitive, "正则表达式匹配区分大小写")
-                .changed()
-            {
-                options.flags.set(
-                    lyrics_helper_core::MetadataStripperFlags::REGEX_CASE_SENSITIVE,
-                    regex_case_sensitive,
+                let mut short_line_heuristic = options.flags.contains(
+                    lyrics_helper_core::MetadataStripperFlags::ENABLE_SHORT_LINE_HEURISTIC,
                 );
-            }
-
-            stripper_ui.label("关键词 (每行一个):");
-            let mut keywords_text = options.keywords.join("\n");
-            if stripper_ui
-                .add(TextEdit::multiline(&mut keywords_text).desired_rows(3))
-                .changed()
-            {
-                options.keywords = keywords_text.lines().map(String::from).collect();
-            }
+                stripper_ui.horizontal(|h_ui| {
+                    if self
+                        .ui
+                        .settings_search
+                        .checkbox(h_ui, &mut short_line_heuristic, "将头部的短行视为署名")
+                        .on_hover_text(
+                            "紧接在头部元数据之后至多 2 行、形如“职务 人名”且没有翻译和句读的短行也会被移除。可能误删短歌词，默认关闭",
+                        )
+                        .changed()
+                    {
+                        options.flags.set(
+                            lyrics_helper_core::MetadataStripperFlags::ENABLE_SHORT_LINE_HEURISTIC,
+                            short_line_heuristic,
+                        );
+                    }
+                    h_ui.add_enabled(
+                        short_line_heuristic,
+                        egui::DragValue::new(&mut options.short_line_max_chars)
+                            .range(1..=50)
+                            .prefix("≤ ")
+                            .suffix(" 字"),
+                    );
+                });
 
-            stripper_ui.label("正则表达式 (每行一个):");
-            let mut regex_text = options.regex_patterns.join("\n");
-            if stripper_ui
-                .add(TextEdit::multiline(&mut regex_text).desired_rows(3))
-                .changed()
-            {
-                options.regex_patterns = regex_text.lines().map(String::from).collect();
-            }
-        });
+                self.ui
+                    .settings_search
+                    .label(stripper_ui, "关键词 (每行一个):");
+                let mut keywords_text = options.keywords.join("\n");
+                if stripper_ui
+                    .add(TextEdit::multiline(&mut keywords_text).desired_rows(3))
+                    .changed()
+                {
+                    options.keywords = keywords_text.lines().map(String::from).collect();
+                }
 
-        ui.collapsing("音节平滑", |smoothing_ui| {
-            let options = &mut self.ui.temp_edit_settings.syllable_smoothing;
+                self.ui
+                    .settings_search
+                    .label(stripper_ui, "正则表达式 (每行一个):");
+                let mut regex_text = options.regex_patterns.join("\n");
+                if stripper_ui
+                    .add(TextEdit::multiline(&mut regex_text).desired_rows(3))
+                    .changed()
+                {
+                    options.regex_patterns = regex_text.lines().map(String::from).collect();
+                }
+            });
+        self.ui
+            .settings_search
+            .mark(ui, &section.header_response, "元数据清理器");
+
+        let section = egui::CollapsingHeader::new("音节平滑")
+            .open(self.ui.settings_search.section_open("音节平滑"))
+            .show(ui, |smoothing_ui| {
+                let options = &mut self.ui.temp_edit_settings.syllable_smoothing;
+
+                smoothing_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "平滑因子 (0.0-0.5):");
+                    h_ui.add(egui::Slider::new(&mut options.factor, 0.0..=0.5));
+                });
+                smoothing_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "平滑迭代次数:");
+                    h_ui.add(egui::DragValue::new(&mut options.smoothing_iterations).speed(1.0));
+                });
+                smoothing_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "时长差异阈值 (ms):");
+                    h_ui.add(egui::DragValue::new(&mut options.duration_threshold_ms).speed(1.0));
+                });
+                smoothing_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "间隔阈值 (ms):");
+                    h_ui.add(egui::DragValue::new(&mut options.gap_threshold_ms).speed(1.0));
+                });
 
-            smoothing_ui.horizontal(|h_ui| {
-                h_ui.label("平滑因子 (0.0-0.5):");
-                h_ui.add(egui::Slider::new(&mut options.factor, 0.0..=0.5));
+                smoothing_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "背景人声:");
+                    ComboBox::from_id_salt("background_smoothing_mode")
+                        .selected_text(background_smoothing_mode_label(options.background_mode))
+                        .show_ui(h_ui, |combo_ui| {
+                            for mode in [
+                                BackgroundSmoothingMode::Skip,
+                                BackgroundSmoothingMode::SameAsMain,
+                                BackgroundSmoothingMode::Custom,
+                            ] {
+                                combo_ui.selectable_value(
+                                    &mut options.background_mode,
+                                    mode,
+                                    background_smoothing_mode_label(mode),
+                                );
+                            }
+                        });
+                });
+                if options.background_mode == BackgroundSmoothingMode::Custom {
+                    smoothing_ui.indent("background_smoothing_options", |indent_ui| {
+                        indent_ui.horizontal(|h_ui| {
+                            h_ui.label("背景人声平滑因子:");
+                            h_ui.add(egui::Slider::new(&mut options.background_factor, 0.0..=0.5));
+                        });
+                        indent_ui.horizontal(|h_ui| {
+                            h_ui.label("背景人声时长差异阈值 (ms):");
+                            h_ui.add(
+                                egui::DragValue::new(&mut options.background_duration_threshold_ms)
+                                    .speed(1.0),
+                            );
+                        });
+                        indent_ui.horizontal(|h_ui| {
+                            h_ui.label("背景人声间隔阈值 (ms):");
+                            h_ui.add(
+                                egui::DragValue::new(&mut options.background_gap_threshold_ms)
+                                    .speed(1.0),
+                            );
+                        });
+                        indent_ui.weak("迭代次数与主歌词相同。");
+                    });
+                }
+            });
+        self.ui
+            .settings_search
+            .mark(ui, &section.header_response, "音节平滑");
+
+        let section = egui::CollapsingHeader::new("智能换行")
+            .open(self.ui.settings_search.section_open("智能换行"))
+            .show(ui, |wrap_ui| {
+                wrap_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "每行最多字数:");
+                    h_ui.add(
+                        egui::DragValue::new(&mut self.ui.temp_edit_settings.line_wrap.max_chars)
+                            .range(4..=200)
+                            .speed(1.0),
+                    );
+                });
+                wrap_ui.weak("超过的行优先在标点后拆分，其次是空格处。");
             });
-            smoothing_ui.horizontal(|h_ui| {
-                h_ui.label("平滑迭代次数:");
-                h_ui.add(egui::DragValue::new(&mut options.smoothing_iterations).speed(1.0));
+        self.ui
+            .settings_search
+            .mark(ui, &section.header_response, "智能换行");
+
+        let section = egui::CollapsingHeader::new("标点规范化")
+            .open(self.ui.settings_search.section_open("标点规范化"))
+            .show(ui, |punctuation_ui| {
+                let options = &mut self.ui.temp_edit_settings.punctuation_normalize;
+                punctuation_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "标点统一为:");
+                    h_ui.radio_value(&mut options.target, PunctuationWidth::FullWidth, "全角");
+                    h_ui.radio_value(&mut options.target, PunctuationWidth::HalfWidth, "半角");
+                });
+                punctuation_ui.add_enabled(
+                    options.target == PunctuationWidth::FullWidth,
+                    egui::Checkbox::new(
+                        &mut options.fullwidth_only_cjk_lines,
+                        "仅处理含中日韩文字的行",
+                    ),
+                );
+                self.ui.settings_search.checkbox(
+                    punctuation_ui,
+                    &mut options.halfwidth_digits,
+                    "全角数字转为半角",
+                );
+                self.ui.settings_search.checkbox(
+                    punctuation_ui,
+                    &mut options.halfwidth_latin,
+                    "全角字母转为半角",
+                );
+                punctuation_ui.weak("只修改主歌词，翻译和音译保持不变。");
             });
-            smoothing_ui.horizontal(|h_ui| {
-                h_ui.label("时长差异阈值 (ms):");
-                h_ui.add(egui::DragValue::new(&mut options.duration_threshold_ms).speed(1.0));
+        self.ui
+            .settings_search
+            .mark(ui, &section.header_response, "标点规范化");
+
+        let section = egui::CollapsingHeader::new("音节重叠修复")
+            .open(self.ui.settings_search.section_open("音节重叠修复"))
+            .show(ui, |overlap_ui| {
+                let mode = &mut self.ui.temp_edit_settings.syllable_overlap_fix.mode;
+                overlap_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "修复方式:");
+                    h_ui.radio_value(mode, OverlapFixMode::TrimToNextStart, "裁到下一音节开始");
+                    h_ui.radio_value(mode, OverlapFixMode::Redistribute, "按比例重新分配");
+                });
+                overlap_ui.weak(
+                    "按比例重新分配时，相互重叠的一段音节会在原有时间范围内按各自时长依次排开。",
+                );
             });
-            smoothing_ui.horizontal(|h_ui| {
-                h_ui.label("间隔阈值 (ms):");
-                h_ui.add(egui::DragValue::new(&mut options.gap_threshold_ms).speed(1.0));
+        self.ui
+            .settings_search
+            .mark(ui, &section.header_response, "音节重叠修复");
+
+        let section = egui::CollapsingHeader::new("简繁转换自定义词典")
+.open(self.ui.settings_search.section_open("简繁转换自定义词典"))
+.show(ui, |dictionary_ui| {
+            dictionary_ui.label(
+                "在标准简繁转换之后追加应用的替换表。每行一个词条，原词与替换词之间用制表符、= 或空格分隔。",
+            );
+            let path = &mut self.ui.temp_edit_settings.chinese_conversion_dictionary_path;
+            dictionary_ui.horizontal(|h_ui| {
+                let mut path_text = path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default();
+                if h_ui
+                    .add(
+                        TextEdit::singleline(&mut path_text)
+                            .hint_text("未设置")
+                            .desired_width(260.0),
+                    )
+                    .changed()
+                {
+                    *path = (!path_text.trim().is_empty())
+                        .then(|| std::path::PathBuf::from(path_text.trim()));
+                }
+                if h_ui.button("浏览...").clicked()
+                    && let Some(picked) = rfd::FileDialog::new()
+                        .add_filter("词典文件", &["txt", "dict"])
+                        .pick_file()
+                {
+                    *path = Some(picked);
+                }
+                if h_ui
+                    .add_enabled(path.is_some(), Button::new("清除"))
+                    .clicked()
+                {
+                    *path = None;
+                }
             });
+            if path.as_ref().is_some_and(|p| !p.is_file()) {
+                dictionary_ui.colored_label(dictionary_ui.visuals().warn_fg_color, "文件不存在");
+            }
         });
+        self.ui
+            .settings_search
+            .mark(ui, &section.header_response, "简繁转换自定义词典");
+
+        let section = egui::CollapsingHeader::new("正则替换")
+            .open(self.ui.settings_search.section_open("正则替换"))
+            .show(ui, |regex_ui| {
+                let rules = &mut self.ui.temp_edit_settings.regex_replace.rules;
+                regex_ui.label("规则按顺序作用于每行主歌词，替换文本支持 $1 形式的捕获组引用。");
+
+                let mut rule_to_remove: Option<usize> = None;
+                let mut rule_to_move_up: Option<usize> = None;
+                let rule_count = rules.len();
+                for (index, rule) in rules.iter_mut().enumerate() {
+                    regex_ui.horizontal(|h_ui| {
+                        h_ui.checkbox(&mut rule.enabled, "");
+                        h_ui.add(
+                            TextEdit::singleline(&mut rule.pattern)
+                                .hint_text("查找正则")
+                                .desired_width(150.0),
+                        );
+                        h_ui.label("→");
+                        h_ui.add(
+                            TextEdit::singleline(&mut rule.replacement)
+                                .hint_text("替换为")
+                                .desired_width(100.0),
+                        );
+                        if h_ui
+                            .add_enabled(index > 0, Button::new("⬆").small())
+                            .on_hover_text("上移")
+                            .clicked()
+                        {
+                            rule_to_move_up = Some(index);
+                        }
+                        if h_ui
+                            .add_enabled(index + 1 < rule_count, Button::new("⬇").small())
+                            .on_hover_text("下移")
+                            .clicked()
+                        {
+                            rule_to_move_up = Some(index + 1);
+                        }
+                        if h_ui.small_button("🗑").on_hover_text("删除规则").clicked() {
+                            rule_to_remove = Some(index);
+                        }
+                    });
+                    if !rule.pattern.is_empty()
+                    && let Err(e) =
+                        lyrics_helper_rs::converter::processors::regex_replacer::validate_pattern(
+                            &rule.pattern,
+                        )
+                {
+                    regex_ui.colored_label(
+                        regex_ui.visuals().warn_fg_color,
+                        format!("正则表达式无效: {e}"),
+                    );
+                }
+                }
+
+                if let Some(index) = rule_to_move_up {
+                    rules.swap(index - 1, index);
+                }
+                if let Some(index) = rule_to_remove {
+                    rules.remove(index);
+                }
+                if regex_ui.button("添加规则").clicked() {
+                    rules.push(lyrics_helper_core::RegexReplaceRule::default());
+                }
+            });
+        self.ui
+            .settings_search
+            .mark(ui, &section.header_response, "正则替换");
+
+        let section = egui::CollapsingHeader::new("演唱者括号标记")
+            .open(self.ui.settings_search.section_open("演唱者括号标记"))
+            .show(ui, |agent_ui| {
+                let rules = &mut self.ui.temp_edit_settings.agent_recognizer.bracket_rules;
+                agent_ui.label(
+                    "演唱者识别时，行首的 (合)、【男】 等括号标记按以下规则分配演唱者并从歌词中去掉。",
+                );
+
+                let mut rule_to_remove: Option<usize> = None;
+                for (index, rule) in rules.iter_mut().enumerate() {
+                    agent_ui.horizontal(|h_ui| {
+                        h_ui.add(
+                            TextEdit::singleline(&mut rule.marker)
+                                .hint_text("标记")
+                                .desired_width(60.0),
+                        );
+                        h_ui.label("→");
+                        h_ui.add(
+                            TextEdit::singleline(&mut rule.agent_name)
+                                .hint_text("演唱者名称")
+                                .desired_width(120.0),
+                        );
+                        ComboBox::from_id_salt(("bracket_agent_rule_type", index))
+                            .selected_text(agent_type_label(&rule.agent_type))
+                            .show_ui(h_ui, |combo_ui| {
+                                for agent_type in
+                                    [AgentType::Person, AgentType::Group, AgentType::Other]
+                                {
+                                    let label = agent_type_label(&agent_type);
+                                    combo_ui.selectable_value(
+                                        &mut rule.agent_type,
+                                        agent_type,
+                                        label,
+                                    );
+                                }
+                            });
+                        if h_ui.small_button("🗑").on_hover_text("删除规则").clicked() {
+                            rule_to_remove = Some(index);
+                        }
+                    });
+                }
+
+                if let Some(index) = rule_to_remove {
+                    rules.remove(index);
+                }
+                if agent_ui.button("添加规则").clicked() {
+                    rules.push(BracketAgentRule::default());
+                }
+                agent_ui.weak("演唱者名称留空时使用标记文本；未命中规则的行仍按“名称：”格式识别。");
+
+                agent_ui.separator();
+                agent_ui.horizontal(|h_ui| {
+                    self.ui.settings_search.label(h_ui, "识别置信度阈值 (0.0-1.0):");
+                    h_ui.add(egui::Slider::new(
+                        &mut self.ui.temp_edit_settings.agent_recognizer.min_confidence,
+                        0.0..=1.0,
+                    ));
+                });
+                agent_ui.weak(
+                    "“名称：”标记的置信度低于阈值时不视为演唱者，该行保留原文并沿用之前的演唱者。调高可减少误识别，0 表示不过滤。",
+                );
+            });
+        self.ui
+            .settings_search
+            .mark(ui, &section.header_response, "演唱者括号标记");
     }
 
     pub fn draw_metadata_editor_window_contents(&mut self, ui: &mut egui::Ui, _open: &mut bool) {
         let mut actions_to_send = Vec::new();
 
+        ui.horizontal(|h_ui| {
+            if h_ui
+                .add_enabled(
+                    !self.lyrics.input_text.trim().is_empty(),
+                    egui::Button::new("从歌词推断"),
+                )
+                .on_hover_text("扫描歌词开头和结尾的元数据行（如作词、作曲署名），生成元数据建议")
+                .clicked()
+            {
+                actions_to_send.push(UserAction::Lyrics(Box::new(
+                    LyricsAction::InferMetadataFromLyrics,
+                )));
+            }
+        });
+
+        if !self.ui.metadata_suggestions.is_empty() {
+            self.draw_metadata_suggestions(ui, &mut actions_to_send);
+        }
+        ui.separator();
+
         egui::ScrollArea::vertical().show(ui, |scroll_ui| {
             if self.lyrics.metadata_manager.ui_entries.is_empty() {
                 scroll_ui.label(
@@ -965,144 +2203,1925 @@ impl UniLyricApp {
             }
 
             let mut deletion_index: Option<usize> = None;
-            let mut previous_key: Option<&CanonicalMetadataKey> = None;
-
-            for (index, entry) in self
-                .lyrics
-                .metadata_manager
-                .ui_entries
-                .iter_mut()
-                .enumerate()
-            {
-                let item_id = entry.id;
-                let is_first_in_group = previous_key != Some(&entry.key);
-                if is_first_in_group && index > 0 {
+            let entries = &mut self.lyrics.metadata_manager.ui_entries;
+
+            // 相邻的同键条目组成一组，多值的组默认折叠
+            let mut group_start = 0;
+            while group_start < entries.len() {
+                let group_len = entries[group_start..]
+                    .iter()
+                    .take_while(|e| e.key == entries[group_start].key)
+                    .count();
+                if group_start > 0 {
                     scroll_ui.separator();
                 }
-                scroll_ui.horizontal(|row_ui| {
-                    if row_ui.checkbox(&mut entry.is_pinned, "").changed() {
-                        actions_to_send.push(UserAction::Lyrics(Box::new(
-                            LyricsAction::ToggleMetadataPinned(index),
-                        )));
-                    }
-                    row_ui
-                        .label("固定")
-                        .on_hover_text("勾选后, 此条元数据在加载新歌词时将尝试保留其值");
-
-                    let key_editor_width = row_ui.available_width() * 0.3;
-                    let mut key_changed_this_frame = false;
-
-                    if is_first_in_group {
-                        row_ui.add_space(5.0);
-                        row_ui.label("键:");
-                        if let CanonicalMetadataKey::Custom(custom_key_str) = &mut entry.key {
-                            let response = row_ui.add_sized(
-                                [key_editor_width, 0.0],
-                                egui::TextEdit::singleline(custom_key_str)
-                                    .id_salt(item_id.with("key_edit_custom")),
-                            );
-                            if response.lost_focus() && response.changed() {
-                                if let Ok(parsed_key) =
-                                    CanonicalMetadataKey::from_str(custom_key_str)
-                                {
-                                    entry.key = parsed_key;
-                                }
-                                key_changed_this_frame = true;
-                            }
-                        } else {
-                            egui::ComboBox::from_id_salt(item_id.with("key_combo"))
-                                .selected_text(entry.key.to_string())
-                                .width(key_editor_width)
-                                .show_ui(row_ui, |combo_ui| {
-                                    use strum::IntoEnumIterator;
-                                    for key_variant in CanonicalMetadataKey::iter() {
-                                        if combo_ui
-                                            .selectable_value(
-                                                &mut entry.key,
-                                                key_variant.clone(),
-                                                key_variant.to_string(),
-                                            )
-                                            .changed()
-                                        {
-                                            key_changed_this_frame = true;
-                                        }
-                                    }
-                                    combo_ui.separator();
-                                    if combo_ui.selectable_label(false, "自定义").clicked() {
-                                        entry.key =
-                                            CanonicalMetadataKey::Custom("custom".to_string());
-                                        key_changed_this_frame = true;
-                                    }
-                                });
-                        }
-                    } else {
-                        let style = row_ui.style();
-                        let space_for_pin_label = row_ui.text_style_height(&egui::TextStyle::Body);
-                        let space_for_key_label =
-                            style.spacing.item_spacing.x + style.spacing.interact_size.x;
 
-                        row_ui.add_space(
-                            space_for_pin_label + space_for_key_label + key_editor_width,
+                if group_len == 1 {
+                    scroll_ui.horizontal(|row_ui| {
+                        draw_metadata_entry_row(
+                            row_ui,
+                            group_start,
+                            &mut entries[group_start],
+                            true,
+                            &mut actions_to_send,
+                            &mut deletion_index,
                         );
-                    }
-
-                    if key_changed_this_frame {
-                        actions_to_send.push(UserAction::Lyrics(Box::new(
-                            LyricsAction::UpdateMetadataKey(index, entry.key.clone()),
-                        )));
-                    }
+                    });
+                } else {
+                    let header_id = entries[group_start].id.with("value_group");
+                    let header_text = format!("{} ({} 个值)", entries[group_start].key, group_len);
+                    egui::CollapsingHeader::new(header_text)
+                        .id_salt(header_id)
+                        .default_open(false)
+                        .show(scroll_ui, |group_ui| {
+                            for offset in 0..group_len {
+                                let index = group_start + offset;
+                                group_ui.horizontal(|row_ui| {
+                                    draw_metadata_entry_row(
+                                        row_ui,
+                                        index,
+                                        &mut entries[index],
+                                        offset == 0,
+                                        &mut actions_to_send,
+                                        &mut deletion_index,
+                                    );
+                                });
+                            }
+                        });
+                }
 
-                    row_ui.add_space(5.0);
-                    row_ui.label("值:");
-                    let value_edit_response = row_ui.add(
-                        egui::TextEdit::singleline(&mut entry.value)
-                            .id_salt(item_id.with("value_edit"))
-                            .hint_text("元数据值"),
-                    );
-                    if value_edit_response.lost_focus() {
-                        actions_to_send.push(UserAction::Lyrics(Box::new(
-                            LyricsAction::UpdateMetadataValue(index, entry.value.clone()),
-                        )));
-                    }
+                group_start += group_len;
+            }
 
-                    if row_ui.button("🗑").on_hover_text("删除此条元数据").clicked() {
-                        deletion_index = Some(index);
+            if let Some(index_to_delete) = deletion_index {
+                actions_to_send.push(UserAction::Lyrics(Box::new(LyricsAction::DeleteMetadata(
+                    index_to_delete,
+                ))));
+            }
+
+            scroll_ui.separator();
+
+            scroll_ui.horizontal(|h_ui| {
+                h_ui.menu_button("添加新元数据...", |menu| {
+                    for key_variant in &self.ui.metadata_keys_by_usage {
+                        if menu.button(key_variant.to_string()).clicked() {
+                            actions_to_send.push(UserAction::Lyrics(Box::new(
+                                LyricsAction::AddMetadata(key_variant.clone()),
+                            )));
+                            menu.close_menu();
+                        }
+                    }
+                    menu.separator();
+                    if menu.button("自定义键").clicked() {
+                        actions_to_send.push(UserAction::Lyrics(Box::new(
+                            LyricsAction::AddMetadata(CanonicalMetadataKey::Custom(
+                                NEW_CUSTOM_METADATA_KEY.to_string(),
+                            )),
+                        )));
+                        menu.close_menu();
+                    }
+                });
+
+                let has_unpinned = self
+                    .lyrics
+                    .metadata_manager
+                    .ui_entries
+                    .iter()
+                    .any(|entry| !entry.is_pinned);
+                if h_ui
+                    .add_enabled(has_unpinned, egui::Button::new("清除非固定"))
+                    .on_hover_text("删除所有未勾选“固定”的元数据")
+                    .clicked()
+                {
+                    self.ui.show_clear_unpinned_metadata_confirm = true;
+                }
+            });
+        });
+
+        if self.ui.show_clear_unpinned_metadata_confirm {
+            let modal = egui::Modal::new(egui::Id::new("clear_unpinned_metadata_confirm_modal"))
+                .show(ui.ctx(), |modal_ui| {
+                    modal_ui.heading("清除非固定元数据");
+                    modal_ui.add_space(5.0);
+                    modal_ui.label("将删除所有未固定的元数据条目，固定项会被保留。确定继续吗？");
+                    modal_ui.add_space(10.0);
+                    modal_ui.horizontal(|h_ui| {
+                        if h_ui.button("确定").clicked() {
+                            actions_to_send.push(UserAction::Lyrics(Box::new(
+                                LyricsAction::ClearUnpinnedMetadata,
+                            )));
+                            self.ui.show_clear_unpinned_metadata_confirm = false;
+                        }
+                        if h_ui.button("取消").clicked() {
+                            self.ui.show_clear_unpinned_metadata_confirm = false;
+                        }
+                    });
+                });
+            if modal.should_close() {
+                self.ui.show_clear_unpinned_metadata_confirm = false;
+            }
+        }
+
+        for action in actions_to_send {
+            self.send_action(action);
+        }
+    }
+
+    /// 绘制启动时询问是否恢复上次工作区会话的对话框。
+    pub fn draw_session_restore_modal(&mut self, ctx: &egui::Context) {
+        let Some(session) = self.ui.pending_session_restore.as_ref() else {
+            return;
+        };
+
+        let mut action_to_send: Option<LyricsAction> = None;
+        let modal =
+            egui::Modal::new(egui::Id::new("session_restore_modal")).show(ctx, |modal_ui| {
+                modal_ui.heading("恢复上次会话");
+                modal_ui.add_space(5.0);
+                modal_ui.label("检测到上次未关闭的编辑内容，是否恢复？");
+                modal_ui.add_space(5.0);
+
+                egui::Grid::new("session_restore_summary_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 4.0])
+                    .show(modal_ui, |grid_ui| {
+                        grid_ui.label("格式:");
+                        grid_ui.label(format!(
+                            "{} → {}",
+                            session.source_format, session.target_format
+                        ));
+                        grid_ui.end_row();
+
+                        grid_ui.label("主歌词:");
+                        grid_ui.label(format!("{} 行", session.input_text.lines().count()));
+                        grid_ui.end_row();
+
+                        let languages: Vec<String> = session
+                            .restored_translation_tracks()
+                            .into_iter()
+                            .filter(|track| !track.lrc.trim().is_empty())
+                            .map(|track| track.language)
+                            .collect();
+                        if !languages.is_empty() {
+                            grid_ui.label("翻译:");
+                            grid_ui.label(languages.join(", "));
+                            grid_ui.end_row();
+                        }
+                        if !session.romanization_lrc.trim().is_empty() {
+                            grid_ui.label("罗马音:");
+                            grid_ui.label("已加载");
+                            grid_ui.end_row();
+                        }
+                        if let Some(path) = &session.last_opened_file_path {
+                            grid_ui.label("文件:");
+                            grid_ui.label(path.display().to_string());
+                            grid_ui.end_row();
+                        }
+                    });
+
+                modal_ui.add_space(10.0);
+                modal_ui.horizontal(|h_ui| {
+                    if h_ui.button("恢复").clicked() {
+                        action_to_send = Some(LyricsAction::RestoreSession);
+                    }
+                    if h_ui.button("丢弃").clicked() {
+                        action_to_send = Some(LyricsAction::DiscardSession);
+                    }
+                });
+            });
+
+        if modal.should_close() && action_to_send.is_none() {
+            action_to_send = Some(LyricsAction::DiscardSession);
+        }
+        if let Some(action) = action_to_send {
+            self.send_action(UserAction::Lyrics(Box::new(action)));
+        }
+    }
+
+    /// 绘制已打开文件列表，点击条目切换当前编辑的文件。
+    pub fn draw_document_list_panel_contents(&mut self, ui: &mut egui::Ui) {
+        let mut action_to_send: Option<LyricsAction> = None;
+
+        ui.horizontal(|h_ui| {
+            h_ui.heading("已打开文件");
+            h_ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |btn_ui| {
+                if btn_ui
+                    .small_button("➕")
+                    .on_hover_text("打开更多文件")
+                    .clicked()
+                {
+                    self.send_action(UserAction::File(FileAction::OpenMultiple));
+                }
+            });
+        });
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |scroll_ui| {
+                for (index, document) in self.lyrics.open_documents.iter().enumerate() {
+                    let is_active = self.lyrics.active_document == Some(index);
+                    let is_modified = self.lyrics.is_document_modified(index);
+                    let label = if is_modified {
+                        format!("● {}", document.display_name())
+                    } else {
+                        document.display_name()
+                    };
+
+                    scroll_ui.horizontal(|h_ui| {
+                        if h_ui.small_button("✖").on_hover_text("关闭此文件").clicked() {
+                            action_to_send = Some(LyricsAction::CloseDocument(index));
+                        }
+                        let response = h_ui
+                            .selectable_label(is_active, label)
+                            .on_hover_text(document.path.display().to_string());
+                        if response.clicked() && !is_active {
+                            action_to_send = Some(LyricsAction::SwitchDocument(index));
+                        }
+                    });
+                }
+            });
+
+        if let Some(action) = action_to_send {
+            self.send_action(UserAction::Lyrics(Box::new(action)));
+        }
+    }
+
+    /// 绘制关闭有未保存更改的文件前的确认对话框。
+    pub fn draw_close_document_modal(&mut self, ctx: &egui::Context) {
+        let Some(index) = self.ui.pending_close_document else {
+            return;
+        };
+        let Some(document) = self.lyrics.open_documents.get(index) else {
+            self.ui.pending_close_document = None;
+            return;
+        };
+        let name = document.display_name();
+
+        let mut action_to_send: Option<LyricsAction> = None;
+        let modal = egui::Modal::new(egui::Id::new("close_document_modal")).show(ctx, |modal_ui| {
+            modal_ui.heading("未保存的更改");
+            modal_ui.add_space(5.0);
+            modal_ui.label(format!(
+                "“{name}” 有未保存的更改，关闭后将丢失。确定关闭吗？"
+            ));
+            modal_ui.add_space(10.0);
+            modal_ui.horizontal(|h_ui| {
+                if h_ui.button("关闭并丢弃").clicked() {
+                    action_to_send = Some(LyricsAction::DiscardDocument(index));
+                }
+                if h_ui.button("取消").clicked() {
+                    self.ui.pending_close_document = None;
+                }
+            });
+        });
+
+        if modal.should_close() {
+            self.ui.pending_close_document = None;
+        }
+        if let Some(action) = action_to_send {
+            self.send_action(UserAction::Lyrics(Box::new(action)));
+        }
+    }
+
+    /// 绘制退出程序前提示未保存更改的确认对话框。
+    pub fn draw_exit_unsaved_modal(&mut self, ctx: &egui::Context) {
+        let unsaved_names: Vec<String> = self
+            .lyrics
+            .open_documents
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.lyrics.is_document_modified(*index))
+            .map(|(_, document)| document.display_name())
+            .collect();
+
+        let mut confirm_exit = false;
+        let modal = egui::Modal::new(egui::Id::new("exit_unsaved_modal")).show(ctx, |modal_ui| {
+            modal_ui.heading("未保存的更改");
+            modal_ui.add_space(5.0);
+            modal_ui.label("以下文件有未保存的更改，退出后将丢失：");
+            for name in &unsaved_names {
+                modal_ui.label(format!("• {name}"));
+            }
+            modal_ui.add_space(10.0);
+            modal_ui.horizontal(|h_ui| {
+                if h_ui.button("仍然退出").clicked() {
+                    confirm_exit = true;
+                }
+                if h_ui.button("取消").clicked() {
+                    self.ui.show_exit_unsaved_confirm = false;
+                }
+            });
+        });
+
+        if modal.should_close() {
+            self.ui.show_exit_unsaved_confirm = false;
+        }
+        if confirm_exit {
+            self.send_action(UserAction::UI(UIAction::ConfirmExit));
+        }
+    }
+
+    /// 绘制编码检测不可靠时的编码选择对话框。
+    pub fn draw_encoding_prompt_modal(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = self.ui.encoding_prompt.as_mut() else {
+            return;
+        };
+        let name = prompt
+            .path
+            .file_name()
+            .map_or_else(|| prompt.path.to_string_lossy(), |n| n.to_string_lossy())
+            .into_owned();
+
+        let mut action_to_send = None;
+        let mut dismiss = false;
+        let modal = egui::Modal::new(egui::Id::new("encoding_prompt_modal")).show(ctx, |modal_ui| {
+            modal_ui.heading("无法确定文件编码");
+            modal_ui.add_space(5.0);
+            modal_ui.label(format!(
+                "“{name}” 的编码无法可靠识别，已暂按 {} 读取。如果出现乱码，请选择正确的编码重新读取。",
+                prompt.detected.name()
+            ));
+            modal_ui.add_space(5.0);
+            ComboBox::from_id_salt("encoding_prompt_combo")
+                .selected_text(prompt.selected.name())
+                .show_ui(modal_ui, |combo_ui| {
+                    for &encoding in crate::io::SELECTABLE_ENCODINGS {
+                        combo_ui.selectable_value(&mut prompt.selected, encoding, encoding.name());
+                    }
+                });
+            modal_ui.add_space(10.0);
+            modal_ui.horizontal(|h_ui| {
+                if h_ui.button("重新读取").clicked() {
+                    action_to_send = Some(LyricsAction::ReloadWithEncoding(prompt.selected));
+                }
+                if h_ui.button("保持当前结果").clicked() {
+                    dismiss = true;
+                }
+            });
+        });
+
+        if dismiss || (modal.should_close() && action_to_send.is_none()) {
+            self.ui.encoding_prompt = None;
+        }
+        if let Some(action) = action_to_send {
+            self.send_action(UserAction::Lyrics(Box::new(action)));
+        }
+    }
+
+    /// 绘制配置导入确认对话框，列出所有将被改变的设置项。
+    pub fn draw_settings_import_modal(&mut self, ctx: &egui::Context) {
+        let Some(import) = self.ui.pending_settings_import.as_ref() else {
+            return;
+        };
+
+        let mut action_to_send = None;
+        let modal =
+            egui::Modal::new(egui::Id::new("settings_import_modal")).show(ctx, |modal_ui| {
+                modal_ui.heading("导入配置");
+                modal_ui.add_space(5.0);
+                modal_ui.label(format!("以下 {} 项设置将被改变：", import.changes.len()));
+                ScrollArea::vertical()
+                    .max_height(300.0)
+                    .auto_shrink([false, true])
+                    .show(modal_ui, |scroll_ui| {
+                        egui::Grid::new("settings_import_changes_grid")
+                            .num_columns(3)
+                            .striped(true)
+                            .spacing([10.0, 4.0])
+                            .show(scroll_ui, |grid| {
+                                grid.strong("设置项");
+                                grid.strong("当前值");
+                                grid.strong("导入值");
+                                grid.end_row();
+                                for change in &import.changes {
+                                    grid.monospace(&change.key);
+                                    grid.label(&change.old_value);
+                                    grid.label(&change.new_value);
+                                    grid.end_row();
+                                }
+                            });
+                    });
+                modal_ui.add_space(10.0);
+                modal_ui.horizontal(|h_ui| {
+                    if h_ui.button("导入").clicked() {
+                        action_to_send = Some(SettingsAction::ConfirmImport);
+                    }
+                    if h_ui.button("取消").clicked() {
+                        action_to_send = Some(SettingsAction::CancelImport);
+                    }
+                });
+            });
+
+        if modal.should_close() && action_to_send.is_none() {
+            action_to_send = Some(SettingsAction::CancelImport);
+        }
+        if let Some(action) = action_to_send {
+            self.send_action(UserAction::Settings(action));
+        }
+    }
+
+    /// 绘制待确认的元数据建议列表。
+    fn draw_metadata_suggestions(&mut self, ui: &mut egui::Ui, actions: &mut Vec<UserAction>) {
+        egui::Frame::group(ui.style()).show(ui, |frame_ui| {
+            frame_ui.label(egui::RichText::new("从歌词推断的元数据").strong());
+            egui::Grid::new("metadata_suggestions_grid")
+                .num_columns(3)
+                .spacing([10.0, 4.0])
+                .show(frame_ui, |grid| {
+                    for suggestion in &mut self.ui.metadata_suggestions {
+                        grid.checkbox(&mut suggestion.selected, suggestion.key.to_string())
+                            .on_hover_text(format!("原文中的键: {}", suggestion.source_key));
+                        grid.text_edit_singleline(&mut suggestion.value);
+                        grid.end_row();
+                    }
+                });
+
+            frame_ui.horizontal(|h_ui| {
+                let any_selected = self.ui.metadata_suggestions.iter().any(|s| s.selected);
+                if h_ui
+                    .add_enabled(any_selected, egui::Button::new("添加选中项"))
+                    .clicked()
+                {
+                    actions.push(UserAction::Lyrics(Box::new(
+                        LyricsAction::ApplyMetadataSuggestions,
+                    )));
+                }
+                if h_ui.button("忽略").clicked() {
+                    self.ui.metadata_suggestions.clear();
+                }
+            });
+        });
+    }
+
+    /// 绘制歌词统计报告窗口的内容。
+    pub fn draw_stats_report_window_contents(&mut self, ui: &mut egui::Ui) {
+        let Some(report) = self.ui.stats_report.clone() else {
+            return;
+        };
+        ui.add(egui::Label::new(egui::RichText::new(&report).monospace()).selectable(true));
+        ui.separator();
+        ui.horizontal(|h_ui| {
+            if h_ui.button("复制").clicked() {
+                h_ui.ctx().copy_text(report.clone());
+            }
+            if h_ui.button("保存为文本...").clicked() {
+                self.send_action(UserAction::File(FileAction::SaveStatsReport));
+            }
+            if h_ui
+                .button("刷新")
+                .on_hover_text("按当前歌词重新统计")
+                .clicked()
+            {
+                self.send_action(UserAction::Lyrics(Box::new(
+                    LyricsAction::GenerateStatsReport,
+                )));
+            }
+        });
+    }
+
+    /// 绘制 LRC 合并向导：选择三个文件、设置对齐容差，合并后显示对齐统计。
+    pub fn draw_lyric_image_window_contents(&mut self, ui: &mut egui::Ui) {
+        let has_cover = self.lyric_image_cover().is_some();
+        let options = &mut self.ui.lyric_image_options;
+
+        egui::Grid::new("lyric_image_options_grid")
+            .num_columns(2)
+            .spacing([10.0, 6.0])
+            .show(ui, |grid_ui| {
+                grid_ui.label("图片宽度:");
+                grid_ui.add(egui::Slider::new(&mut options.width, 480..=2160).suffix(" px"));
+                grid_ui.end_row();
+
+                grid_ui.label("字号:");
+                grid_ui.add(egui::Slider::new(&mut options.font_size, 20.0..=96.0).suffix(" px"));
+                grid_ui.end_row();
+
+                grid_ui.label("背景色:");
+                grid_ui.color_edit_button_srgb(&mut options.background_color);
+                grid_ui.end_row();
+
+                grid_ui.label("文字颜色:");
+                grid_ui.color_edit_button_srgb(&mut options.text_color);
+                grid_ui.end_row();
+            });
+
+        ui.add_space(5.0);
+        ui.checkbox(&mut options.include_translation, "包含翻译");
+        ui.checkbox(&mut options.include_romanization, "包含罗马音");
+        ui.checkbox(&mut options.include_metadata, "包含歌名和艺术家");
+        ui.add_enabled(
+            has_cover,
+            egui::Checkbox::new(&mut options.include_cover, "包含封面"),
+        )
+        .on_disabled_hover_text("当前播放的歌曲没有封面，或与歌词不是同一首歌");
+
+        ui.add_space(10.0);
+        ui.horizontal(|h_ui| {
+            if h_ui
+                .add_enabled(
+                    self.lyrics.parsed_lyric_data.is_some() && !self.ui.lyric_image_exporting,
+                    egui::Button::new("导出 PNG..."),
+                )
+                .on_disabled_hover_text("需要先成功解析歌词")
+                .clicked()
+            {
+                self.send_action(UserAction::File(FileAction::ExportLyricImage));
+            }
+            if self.ui.lyric_image_exporting {
+                h_ui.spinner();
+            }
+            if h_ui.button("恢复默认").clicked() {
+                self.ui.lyric_image_options = crate::lyric_image::LyricImageOptions::default();
+            }
+        });
+    }
+
+    pub fn draw_batch_download_window_contents(&mut self, ui: &mut egui::Ui) {
+        let is_running = self.ui.batch_download.is_running();
+
+        ui.label("每行一首歌，格式为“标题 - 艺术家”，艺术家可省略：");
+        ui.add_enabled(
+            !is_running,
+            TextEdit::multiline(&mut self.ui.batch_download.queries_input)
+                .hint_text("晴天 - 周杰伦\nLemon - 米津玄師")
+                .desired_rows(6)
+                .desired_width(f32::INFINITY),
+        );
+
+        ui.add_space(5.0);
+        ui.horizontal(|h_ui| {
+            h_ui.label("输出目录:");
+            match &self.ui.batch_download.output_dir {
+                Some(dir) => h_ui.label(dir.to_string_lossy()),
+                None => h_ui.weak("未选择"),
+            };
+            if h_ui
+                .add_enabled(!is_running, Button::new("选择..."))
+                .clicked()
+            {
+                self.send_action(UserAction::Downloader(Box::new(
+                    DownloaderAction::SelectBatchDownloadDir,
+                )));
+            }
+        });
+        ui.horizontal(|h_ui| {
+            h_ui.label("保存格式:");
+            h_ui.add_enabled_ui(!is_running, |h_ui| {
+                egui::ComboBox::from_id_salt("batch_download_format")
+                    .selected_text(self.ui.batch_download.target_format.to_string())
+                    .show_ui(h_ui, |ui| {
+                        for format in self.lyrics.target_formats() {
+                            ui.selectable_value(
+                                &mut self.ui.batch_download.target_format,
+                                format,
+                                format.to_string(),
+                            );
+                        }
+                    });
+            });
+        });
+
+        ui.add_space(5.0);
+        ui.horizontal(|h_ui| {
+            let providers_ready = matches!(
+                self.lyrics_helper_state.provider_state,
+                crate::types::ProviderState::Ready
+            );
+            if is_running {
+                let cancelling = self
+                    .ui
+                    .batch_download
+                    .cancellation_token
+                    .as_ref()
+                    .is_some_and(|token| token.is_cancelled());
+                if h_ui.add_enabled(!cancelling, Button::new("取消")).clicked() {
+                    self.send_action(UserAction::Downloader(Box::new(
+                        DownloaderAction::CancelBatchDownload,
+                    )));
+                }
+                h_ui.add(Spinner::new());
+                h_ui.label(if cancelling {
+                    "正在取消..."
+                } else {
+                    "正在下载..."
+                });
+            } else if h_ui
+                .add_enabled(providers_ready, Button::new("开始下载"))
+                .on_disabled_hover_text("歌词提供商正在加载")
+                .clicked()
+            {
+                self.send_action(UserAction::Downloader(Box::new(
+                    DownloaderAction::StartBatchDownload,
+                )));
+            }
+        });
+
+        if self.ui.batch_download.entries.is_empty() {
+            return;
+        }
+        ui.separator();
+        let (saved, failed) = self.ui.batch_download.summary();
+        ui.label(format!(
+            "共 {} 首，成功 {saved} 首，失败 {failed} 首。",
+            self.ui.batch_download.entries.len()
+        ));
+        ScrollArea::vertical()
+            .max_height(300.0)
+            .auto_shrink([false, true])
+            .show(ui, |scroll_ui| {
+                egui::Grid::new("batch_download_results_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .spacing([10.0, 4.0])
+                    .show(scroll_ui, |grid_ui| {
+                        for entry in &self.ui.batch_download.entries {
+                            if entry.artist.is_empty() {
+                                grid_ui.label(&entry.title);
+                            } else {
+                                grid_ui.label(format!("{} - {}", entry.title, entry.artist));
+                            }
+                            match &entry.status {
+                                BatchDownloadStatus::Pending => {
+                                    grid_ui.label("等待中");
+                                    grid_ui.label("");
+                                }
+                                BatchDownloadStatus::Searching => {
+                                    grid_ui.horizontal(|h| {
+                                        h.add(Spinner::new());
+                                        h.label("搜索中...");
+                                    });
+                                    grid_ui.label("");
+                                }
+                                BatchDownloadStatus::Saved {
+                                    file_name,
+                                    provider,
+                                } => {
+                                    grid_ui.colored_label(egui::Color32::GREEN, "成功");
+                                    grid_ui.label(format!("{file_name} (来自 {provider})"));
+                                }
+                                BatchDownloadStatus::NotFound => {
+                                    grid_ui.colored_label(egui::Color32::YELLOW, "未找到");
+                                    grid_ui.label("没有匹配度足够高的结果");
+                                }
+                                BatchDownloadStatus::Failed(err_msg) => {
+                                    grid_ui.colored_label(egui::Color32::RED, "失败");
+                                    grid_ui.label(err_msg);
+                                }
+                            }
+                            grid_ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    pub fn draw_lrc_merge_window_contents(&mut self, ui: &mut egui::Ui) {
+        let slots = [
+            (None, "主歌词"),
+            (Some(LrcContentType::Translation), "翻译"),
+            (Some(LrcContentType::Romanization), "罗马音"),
+        ];
+
+        egui::Grid::new("lrc_merge_files_grid")
+            .num_columns(4)
+            .spacing([10.0, 6.0])
+            .show(ui, |grid_ui| {
+                for (target, label) in slots {
+                    grid_ui.label(label);
+                    let path = self.ui.lrc_merge.path_mut(target);
+                    match path.as_ref().and_then(|p| p.file_name()) {
+                        Some(name) => grid_ui.label(name.to_string_lossy()),
+                        None => grid_ui.weak(if target.is_none() {
+                            "未选择"
+                        } else {
+                            "未选择（可选）"
+                        }),
+                    };
+                    grid_ui.horizontal(|h_ui| {
+                        if h_ui.button("选择...").clicked() {
+                            self.send_action(UserAction::File(FileAction::PickLrcMergeFile(
+                                target,
+                            )));
+                        }
+                        let path = self.ui.lrc_merge.path_mut(target);
+                        if target.is_some() && path.is_some() && h_ui.button("清除").clicked() {
+                            *path = None;
+                            self.ui.lrc_merge.result = None;
+                        }
+                    });
+                    match target {
+                        Some(target) => {
+                            let response = grid_ui
+                                .add(
+                                    egui::TextEdit::singleline(
+                                        self.ui.lrc_merge.language_mut(target),
+                                    )
+                                    .hint_text("语言标签")
+                                    .desired_width(80.0),
+                                )
+                                .on_hover_text(
+                                    "BCP-47 语言标签，例如 zh-Hans、ja-Latn，留空则不标记",
+                                );
+                            if response.changed() {
+                                self.ui.lrc_merge.result = None;
+                            }
+                        }
+                        None => {
+                            grid_ui.label("");
+                        }
+                    }
+                    grid_ui.end_row();
+                }
+            });
+
+        ui.add_space(5.0);
+        ui.horizontal(|h_ui| {
+            h_ui.label("对齐容差:");
+            if h_ui
+                .add(
+                    egui::DragValue::new(&mut self.ui.lrc_merge.tolerance_ms)
+                        .range(0..=2000)
+                        .suffix(" ms"),
+                )
+                .on_hover_text("翻译和罗马音行与主歌词行的时间戳相差不超过此值时视为同一行")
+                .changed()
+            {
+                self.ui.lrc_merge.result = None;
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.horizontal(|h_ui| {
+            if h_ui
+                .add_enabled(self.ui.lrc_merge.main_path.is_some(), Button::new("合并"))
+                .clicked()
+            {
+                self.send_action(UserAction::File(FileAction::MergeLrcFiles));
+            }
+            if h_ui
+                .add_enabled(
+                    self.ui.lrc_merge.result.is_some(),
+                    Button::new("保存为 TTML..."),
+                )
+                .on_hover_text("保存后在编辑器中打开")
+                .clicked()
+            {
+                self.send_action(UserAction::File(FileAction::SaveLrcMergeResult));
+            }
+        });
+
+        let Some((_, report)) = &self.ui.lrc_merge.result else {
+            return;
+        };
+        ui.separator();
+        ui.label(format!("主歌词共 {} 行。", report.main_lines));
+        let aux_rows = [
+            (
+                "翻译",
+                self.ui.lrc_merge.translation_path.is_some(),
+                report.matched_translation_lines,
+                report.unmatched_translation_lines,
+            ),
+            (
+                "罗马音",
+                self.ui.lrc_merge.romanization_path.is_some(),
+                report.matched_romanization_lines,
+                report.unmatched_romanization_lines,
+            ),
+        ];
+        for (label, selected, matched, unmatched) in aux_rows {
+            if !selected {
+                continue;
+            }
+            ui.label(format!(
+                "{label}: {matched} 行主歌词已对齐，{} 行主歌词缺少{label}。",
+                report.main_lines.saturating_sub(matched)
+            ));
+            if unmatched > 0 {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    format!(
+                        "{unmatched} 行{label}无法对应到主歌词行，已被丢弃。可尝试增大对齐容差。"
+                    ),
+                );
+            }
+        }
+        if report.preserved_lines > 0 {
+            ui.label(format!(
+                "{} 行翻译或罗马音对应的主歌词为空，已保留为单独的行。",
+                report.preserved_lines
+            ));
+        }
+    }
+
+    /// 绘制对比视图窗口的内容，并排或行内显示两段文本的差异。
+    /// 绘制本地缓存管理窗口：左侧按歌手/专辑分组列出缓存，右侧预览选中的条目。
+    pub fn draw_local_cache_window_contents(&mut self, ui: &mut egui::Ui) {
+        let entries = self.local_cache.index.lock().unwrap().clone();
+
+        ui.horizontal(|ui| {
+            ui.label("搜索:");
+            ui.add(
+                TextEdit::singleline(&mut self.ui.local_cache_search)
+                    .hint_text("标题、歌手或专辑")
+                    .desired_width(220.0),
+            );
+            ui.weak(format!("共 {} 条缓存", entries.len()));
+        });
+        ui.separator();
+
+        let mut groups: std::collections::BTreeMap<
+            String,
+            std::collections::BTreeMap<String, Vec<&crate::types::LocalLyricCacheEntry>>,
+        > = std::collections::BTreeMap::new();
+        for entry in entries
+            .iter()
+            .filter(|e| e.matches_query(&self.ui.local_cache_search))
+        {
+            groups
+                .entry(entry.artist_display())
+                .or_default()
+                .entry(entry.album_display().to_string())
+                .or_default()
+                .push(entry);
+        }
+
+        let previewed = self
+            .ui
+            .local_cache_preview
+            .as_ref()
+            .map(|(filename, _)| filename.clone());
+        let searching = !self.ui.local_cache_search.trim().is_empty();
+
+        egui::SidePanel::left("local_cache_list_panel")
+            .resizable(true)
+            .default_width(280.0)
+            .show_inside(ui, |ui| {
+                if groups.is_empty() {
+                    ui.weak("没有匹配的缓存");
+                    return;
+                }
+                ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        for (artist, albums) in &groups {
+                            egui::CollapsingHeader::new(artist)
+                                .id_salt(("local_cache_artist", artist))
+                                .open(searching.then_some(true))
+                                .show(ui, |ui| {
+                                    for (album, album_entries) in albums {
+                                        egui::CollapsingHeader::new(album)
+                                            .id_salt(("local_cache_album", artist, album))
+                                            .default_open(true)
+                                            .show(ui, |ui| {
+                                                for entry in album_entries {
+                                                    self.draw_local_cache_entry(
+                                                        ui,
+                                                        entry,
+                                                        previewed.as_deref(),
+                                                    );
+                                                }
+                                            });
+                                    }
+                                });
+                        }
+                    });
+            });
+
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            let Some((filename, content)) = self.ui.local_cache_preview.clone() else {
+                ui.centered_and_justified(|ui| {
+                    ui.weak("选择左侧的缓存条目以预览");
+                });
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                ui.strong(&filename);
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui
+                        .button("删除")
+                        .on_hover_text("删除此缓存文件及其索引条目")
+                        .clicked()
+                    {
+                        self.send_action(UserAction::LocalCache(LocalCacheAction::Delete(
+                            filename.clone(),
+                        )));
+                    }
+                    if ui.button("加载到编辑器").clicked() {
+                        self.send_action(UserAction::LocalCache(LocalCacheAction::LoadIntoEditor(
+                            filename.clone(),
+                        )));
+                    }
+                });
+            });
+            ui.separator();
+
+            ScrollArea::both()
+                .id_salt("local_cache_preview_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(content).monospace().size(12.0))
+                            .extend(),
+                    );
+                });
+        });
+    }
+
+    fn draw_local_cache_entry(
+        &mut self,
+        ui: &mut egui::Ui,
+        entry: &crate::types::LocalLyricCacheEntry,
+        previewed: Option<&str>,
+    ) {
+        let saved_at = chrono::DateTime::from_timestamp(entry.saved_timestamp, 0)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let is_selected = previewed == Some(entry.ttml_filename.as_str());
+
+        if ui
+            .add(egui::SelectableLabel::new(is_selected, &entry.smtc_title))
+            .on_hover_text(format!("保存于 {saved_at}\n{}", entry.ttml_filename))
+            .clicked()
+        {
+            self.send_action(UserAction::LocalCache(LocalCacheAction::Preview(
+                entry.ttml_filename.clone(),
+            )));
+        }
+    }
+
+    pub fn draw_diff_window_contents(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|h_ui| {
+            ComboBox::from_id_salt("diff_source_combo")
+                .selected_text(self.ui.diff_source.display_name())
+                .show_ui(h_ui, |combo_ui| {
+                    for source in [DiffSource::BeforeProcessing, DiffSource::InputOutput] {
+                        combo_ui.selectable_value(
+                            &mut self.ui.diff_source,
+                            source,
+                            source.display_name(),
+                        );
+                    }
+                });
+            h_ui.separator();
+            h_ui.radio_value(&mut self.ui.diff_inline, false, "并排");
+            h_ui.radio_value(&mut self.ui.diff_inline, true, "行内");
+            h_ui.separator();
+            h_ui.checkbox(&mut self.ui.diff_only_changes, "仅显示变化");
+        });
+        ui.separator();
+
+        let (old_text, new_text) = match self.ui.diff_source {
+            DiffSource::BeforeProcessing => {
+                let Some(baseline) = self.lyrics.diff_baseline.as_deref() else {
+                    ui.weak("尚未进行简繁转换或后处理。");
+                    return;
+                };
+                (baseline, self.lyrics.output_text.as_str())
+            }
+            DiffSource::InputOutput => (
+                self.lyrics.input_text.as_str(),
+                self.lyrics.output_text.as_str(),
+            ),
+        };
+
+        let diff_source = self.ui.diff_source;
+        let revision = self.lyrics.text_revision;
+        if self
+            .ui
+            .diff_cache
+            .as_ref()
+            .is_none_or(|(source, cached, _)| *source != diff_source || *cached != revision)
+        {
+            self.ui.diff_cache = Some((
+                diff_source,
+                revision,
+                text_diff::diff_texts(old_text, new_text),
+            ));
+        }
+        let Some((_, _, rows)) = self.ui.diff_cache.as_ref() else {
+            return;
+        };
+
+        let changed_count = rows.iter().filter(|row| !row.is_unchanged()).count();
+        if changed_count == 0 {
+            ui.label("两段文本没有差异。");
+            return;
+        }
+        ui.label(format!("共 {changed_count} 处变化"));
+
+        let only_changes = self.ui.diff_only_changes;
+        let visible_rows = rows
+            .iter()
+            .filter(|row| !only_changes || !row.is_unchanged());
+
+        // 两侧放在同一个滚动区域中，滚动始终同步
+        ScrollArea::both()
+            .auto_shrink([false, false])
+            .show(ui, |scroll_ui| {
+                if self.ui.diff_inline {
+                    egui::Grid::new("diff_inline_grid")
+                        .num_columns(3)
+                        .spacing([8.0, 2.0])
+                        .show(scroll_ui, |grid| {
+                            for row in visible_rows {
+                                if row.is_unchanged() {
+                                    draw_diff_cell(grid, row.new.as_ref(), ' ', None);
+                                    grid.end_row();
+                                    continue;
+                                }
+                                if row.old.is_some() {
+                                    draw_diff_cell(
+                                        grid,
+                                        row.old.as_ref(),
+                                        '-',
+                                        Some(DIFF_DELETED_BACKGROUND),
+                                    );
+                                    grid.end_row();
+                                }
+                                if row.new.is_some() {
+                                    draw_diff_cell(
+                                        grid,
+                                        row.new.as_ref(),
+                                        '+',
+                                        Some(DIFF_INSERTED_BACKGROUND),
+                                    );
+                                    grid.end_row();
+                                }
+                            }
+                        });
+                } else {
+                    egui::Grid::new("diff_side_by_side_grid")
+                        .num_columns(6)
+                        .spacing([8.0, 2.0])
+                        .show(scroll_ui, |grid| {
+                            for row in visible_rows {
+                                let unchanged = row.is_unchanged();
+                                draw_diff_cell(
+                                    grid,
+                                    row.old.as_ref(),
+                                    if unchanged { ' ' } else { '-' },
+                                    (!unchanged).then_some(DIFF_DELETED_BACKGROUND),
+                                );
+                                draw_diff_cell(
+                                    grid,
+                                    row.new.as_ref(),
+                                    if unchanged { ' ' } else { '+' },
+                                    (!unchanged).then_some(DIFF_INSERTED_BACKGROUND),
+                                );
+                                grid.end_row();
+                            }
+                        });
+                }
+            });
+    }
+
+    /// 绘制逐行编辑窗口的内容，用于合并相邻行、在音节边界拆分行，以及插入间奏标记。
+    pub fn draw_line_editor_window_contents(&mut self, ui: &mut egui::Ui) {
+        let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_ref() else {
+            ui.label("没有已解析的歌词。");
+            return;
+        };
+
+        let line_count = parsed_data.lines.len();
+        let selected = self
+            .ui
+            .line_editor_selected_line
+            .filter(|&index| index < line_count);
+        let mut new_selection = selected;
+        let mut action_to_send: Option<LyricsAction> = None;
+        let mut seek_to: Option<u64> = None;
+        let can_seek =
+            self.player.command_tx.is_some() && self.player.current_now_playing.title.is_some();
+
+        ui.label("选择一行后，可将其与下一行合并、点击音节前的 ✂ 在该处拆分，或在其后插入间奏。");
+
+        let mut time_scale = self.ui.line_editor_time_scale;
+        ui.horizontal(|h_ui| {
+            h_ui.label("时间缩放:");
+            h_ui.add(
+                egui::DragValue::new(&mut time_scale)
+                    .speed(0.001)
+                    .range(0.1..=10.0)
+                    .fixed_decimals(4)
+                    .prefix("× "),
+            )
+            .on_hover_text("所有时间戳乘以该比例。例如适配 1.25 倍速的音频时填 0.8");
+            if h_ui
+                .add_enabled(
+                    (time_scale - 1.0).abs() > f64::EPSILON,
+                    Button::new("应用缩放"),
+                )
+                .clicked()
+            {
+                action_to_send = Some(LyricsAction::ScaleTimings(time_scale));
+                time_scale = 1.0;
+            }
+        });
+        self.ui.line_editor_time_scale = time_scale;
+
+        ui.horizontal(|h_ui| {
+            h_ui.label("首尾静音裁剪: 第一行开始于");
+            h_ui.add(
+                egui::DragValue::new(&mut self.ui.line_editor_trim_start_ms)
+                    .speed(10.0)
+                    .suffix(" ms"),
+            );
+            h_ui.checkbox(&mut self.ui.line_editor_trim_tail, "裁掉结尾静音")
+                .on_hover_text("删除结尾的间奏标记，并把最后一行的结束时间收紧到最后一个音节");
+            if h_ui
+                .button("应用裁剪")
+                .on_hover_text("删除开头的间奏标记并整体平移时间轴，行间的相对间隔保持不变")
+                .clicked()
+            {
+                action_to_send = Some(LyricsAction::TrimSilence(
+                    self.ui.line_editor_trim_start_ms,
+                    self.ui.line_editor_trim_tail,
+                ));
+            }
+        });
+
+        let repeated_groups = find_repeated_lines(&parsed_data.lines);
+        let repeated_group_of = |index: usize| {
+            repeated_groups
+                .iter()
+                .find(|group| group.line_indices.contains(&index))
+        };
+        let other_line_numbers = |indices: &[usize], index: usize| {
+            indices
+                .iter()
+                .filter(|&&other| other != index)
+                .map(|other| (other + 1).to_string())
+                .collect::<Vec<_>>()
+                .join("、")
+        };
+
+        let agent_colors = agent_color_map(&parsed_data.lines);
+        let agent_name = |id: &str| {
+            parsed_data
+                .agents
+                .agents_by_id
+                .get(id)
+                .and_then(|agent| agent.name.clone())
+                .unwrap_or_else(|| id.to_string())
+        };
+        if !agent_colors.is_empty() {
+            ui.horizontal_wrapped(|legend_ui| {
+                legend_ui.label("演唱者:");
+                for (id, color) in &agent_colors {
+                    legend_ui.colored_label(*color, format!("■ {}", agent_name(id)));
+                }
+            });
+        }
+        if !repeated_groups.is_empty() {
+            ui.weak(format!(
+                "检测到 {} 组文本相同的重复行（🔁），选中其中一行可把它的逐字时间复制到其他相同行。",
+                repeated_groups.len()
+            ));
+        }
+        let orphaned_notes = self
+            .lyrics
+            .line_notes
+            .keys()
+            .filter(|&&line_index| line_index >= parsed_data.lines.len())
+            .count();
+        if orphaned_notes > 0 {
+            ui.weak(format!(
+                "有 {orphaned_notes} 条备注对应的行已不存在，重新添加歌词行后会重新显示。"
+            ));
+        }
+        ui.separator();
+
+        let list_height = (ui.available_height() * 0.6).max(120.0);
+        ScrollArea::vertical()
+            .id_salt("line_editor_list_scroll")
+            .max_height(list_height)
+            .auto_shrink([false, true])
+            .show(ui, |scroll_ui| {
+                for (index, line) in parsed_data.lines.iter().enumerate() {
+                    let text = if line.is_interlude() {
+                        format!("♪ 间奏 (至 {})", format_timestamp_ms(line.end_ms))
+                    } else {
+                        line.main_text().unwrap_or_default()
+                    };
+                    let label = format!("[{}]  {}", format_timestamp_ms(line.start_ms), text);
+                    scroll_ui.horizontal(|row_ui| {
+                        let number = egui::RichText::new(format!("{:>3}", index + 1)).monospace();
+                        if can_seek {
+                            if row_ui
+                                .add(egui::Label::new(number).sense(egui::Sense::click()))
+                                .on_hover_text("在播放器中跳转到该行的开始时间")
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .clicked()
+                            {
+                                seek_to = Some(line.start_ms);
+                            }
+                        } else {
+                            row_ui.label(number);
+                        }
+                        if !agent_colors.is_empty() {
+                            match line.agent.as_deref().and_then(|id| {
+                                agent_colors
+                                    .iter()
+                                    .find(|(agent_id, _)| agent_id == id)
+                                    .map(|(_, color)| (id, *color))
+                            }) {
+                                Some((id, color)) => {
+                                    row_ui
+                                        .colored_label(color, "■")
+                                        .on_hover_text(agent_name(id));
+                                }
+                                None => {
+                                    row_ui.weak("□").on_hover_text("未指定演唱者");
+                                }
+                            }
+                        }
+                        if row_ui
+                            .selectable_label(selected == Some(index), label)
+                            .clicked()
+                        {
+                            new_selection = Some(index);
+                        }
+                        if let Some(group) = repeated_group_of(index) {
+                            row_ui.weak("🔁").on_hover_text(format!(
+                                "与第 {} 行文本相同",
+                                other_line_numbers(&group.line_indices, index)
+                            ));
+                        }
+                        if let Some(note) = self.lyrics.line_notes.get(&index) {
+                            row_ui.label("📝").on_hover_text(note);
+                        }
+                        row_ui
+                            .menu_button("📋", |menu_ui| {
+                                for format in LineCopyFormat::ALL {
+                                    if menu_ui.button(format.display_name()).clicked() {
+                                        action_to_send =
+                                            Some(LyricsAction::CopyLine(index, format));
+                                        menu_ui.close_menu();
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("复制该行");
+                    });
+                }
+            });
+
+        ui.separator();
+
+        if let Some(index) = selected
+            && let Some(line) = parsed_data.lines.get(index)
+        {
+            ui.horizontal(|h_ui| {
+                h_ui.strong(format!("第 {} 行", index + 1));
+                h_ui.label(format!(
+                    "{} - {}",
+                    format_timestamp_ms(line.start_ms),
+                    format_timestamp_ms(line.end_ms)
+                ));
+                h_ui.with_layout(Layout::right_to_left(Align::Center), |right_ui| {
+                    if right_ui
+                        .add_enabled(index + 1 < line_count, Button::new("与下一行合并"))
+                        .on_disabled_hover_text("这是最后一行")
+                        .clicked()
+                    {
+                        action_to_send = Some(LyricsAction::MergeLineWithNext(index));
+                    }
+                });
+            });
+
+            let (mut interlude_start, mut interlude_end) = self.ui.line_editor_interlude_range;
+            ui.horizontal(|h_ui| {
+                h_ui.label("间奏:");
+                h_ui.add(
+                    egui::DragValue::new(&mut interlude_start)
+                        .speed(10.0)
+                        .suffix(" ms"),
+                );
+                h_ui.label("-");
+                h_ui.add(
+                    egui::DragValue::new(&mut interlude_end)
+                        .speed(10.0)
+                        .suffix(" ms"),
+                );
+                if h_ui
+                    .add_enabled(interlude_start < interlude_end, Button::new("插入间奏"))
+                    .on_hover_text("插入一个间奏标记行，导出到 TTML 并发送给 AMLL Player")
+                    .on_disabled_hover_text("结束时间必须晚于开始时间")
+                    .clicked()
+                {
+                    action_to_send = Some(LyricsAction::InsertInterlude(
+                        interlude_start,
+                        interlude_end,
+                    ));
+                }
+                if line.is_interlude() && h_ui.button("删除此间奏").clicked() {
+                    action_to_send = Some(LyricsAction::RemoveInterlude(index));
+                }
+            });
+            self.ui.line_editor_interlude_range = (interlude_start, interlude_end);
+
+            let mut note = self
+                .lyrics
+                .line_notes
+                .get(&index)
+                .cloned()
+                .unwrap_or_default();
+            ui.horizontal(|h_ui| {
+                h_ui.label("备注:");
+                let response = h_ui
+                    .add(
+                        TextEdit::singleline(&mut note)
+                            .hint_text("校对备注，如“此处存疑”")
+                            .desired_width(f32::INFINITY),
+                    )
+                    .on_hover_text("备注只保存在工作区会话中，导出时会被忽略");
+                if response.changed() {
+                    action_to_send = Some(LyricsAction::SetLineNote(index, note));
+                }
+            });
+
+            if let Some(group) = repeated_group_of(index) {
+                let targets: Vec<usize> = group
+                    .line_indices
+                    .iter()
+                    .copied()
+                    .filter(|&other| other != index)
+                    .collect();
+                ui.horizontal_wrapped(|h_ui| {
+                    h_ui.label(format!(
+                        "🔁 第 {} 行与该行文本相同。",
+                        other_line_numbers(&group.line_indices, index)
+                    ));
+                    if h_ui
+                        .button(format!("将逐字时间复制到 {} 个相同行", targets.len()))
+                        .on_hover_text(
+                            "按相对行开始时间的偏移复制该行主歌词的音节时间，覆盖这些行原有的逐字时间",
+                        )
+                        .clicked()
+                    {
+                        action_to_send =
+                            Some(LyricsAction::CopyTimingToRepeatedLines(index, targets));
                     }
                 });
-                previous_key = Some(&entry.key);
             }
 
-            if let Some(index_to_delete) = deletion_index {
-                actions_to_send.push(UserAction::Lyrics(Box::new(LyricsAction::DeleteMetadata(
-                    index_to_delete,
-                ))));
+            match line.main_track() {
+                _ if line.is_interlude() => {
+                    ui.weak("这是一个间奏标记行。");
+                }
+                Some(track) if !track.content.is_empty() => {
+                    ui.horizontal_wrapped(|wrap_ui| {
+                        for (syllable_index, syllable) in track.content.syllables().enumerate() {
+                            if syllable_index > 0
+                                && wrap_ui
+                                    .small_button("✂")
+                                    .on_hover_text(format!(
+                                        "在此处拆分 ({})",
+                                        format_timestamp_ms(syllable.start_ms)
+                                    ))
+                                    .clicked()
+                            {
+                                action_to_send =
+                                    Some(LyricsAction::SplitLineAt(index, syllable_index));
+                            }
+                            wrap_ui.label(&syllable.text).on_hover_text(format!(
+                                "{} - {}",
+                                format_timestamp_ms(syllable.start_ms),
+                                format_timestamp_ms(syllable.end_ms)
+                            ));
+                        }
+                    });
+                }
+                _ => {
+                    ui.weak("该行没有主歌词音节，无法拆分。");
+                }
             }
+        } else {
+            ui.weak("未选择任何行。");
+        }
 
-            scroll_ui.separator();
+        if new_selection != selected
+            && let Some(index) = new_selection
+            && let Some(line) = parsed_data.lines.get(index)
+        {
+            // 默认填入该行与下一行之间的空隙
+            let next_start = parsed_data
+                .lines
+                .get(index + 1)
+                .map_or(line.end_ms, |next| next.start_ms);
+            self.ui.line_editor_interlude_range = (line.end_ms, next_start.max(line.end_ms));
+        }
+        self.ui.line_editor_selected_line = new_selection;
+        if let Some(action) = action_to_send {
+            self.send_action(UserAction::Lyrics(Box::new(action)));
+        }
+        if let Some(start_ms) = seek_to {
+            self.send_action(UserAction::Player(PlayerAction::SeekToLyricTime(start_ms)));
+        }
+    }
 
-            scroll_ui.menu_button("添加新元数据...", |menu| {
-                use strum::IntoEnumIterator;
-                for key_variant in CanonicalMetadataKey::iter() {
-                    if menu.button(key_variant.to_string()).clicked() {
-                        actions_to_send.push(UserAction::Lyrics(Box::new(
-                            LyricsAction::AddMetadata(key_variant),
-                        )));
-                        menu.close_menu();
+    /// 绘制时间轴编辑器。每行歌词占一条轨道，上方的细条是行本身，下方是主歌词音节。
+    pub fn draw_timeline_window_contents(&mut self, ui: &mut egui::Ui) {
+        let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_ref() else {
+            ui.label("没有已解析的歌词。");
+            return;
+        };
+        let lines = &parsed_data.lines;
+        let mut load_audio = false;
+        let mut clear_waveform = false;
+
+        ui.horizontal(|h_ui| {
+            h_ui.label("缩放:");
+            h_ui.add(
+                egui::Slider::new(&mut self.ui.timeline_zoom, TIMELINE_ZOOM_RANGE)
+                    .logarithmic(true)
+                    .suffix(" px/s"),
+            );
+            h_ui.checkbox(&mut self.ui.timeline_snap, "吸附")
+                .on_hover_text("拖动时自动对齐到附近的行或音节边界");
+
+            h_ui.separator();
+            if self.ui.timeline_waveform_loading {
+                h_ui.add(Spinner::new());
+                h_ui.label("正在解码音频...");
+            } else if h_ui
+                .button("加载音频...")
+                .on_hover_text("显示音频波形作为对齐参考")
+                .clicked()
+            {
+                load_audio = true;
+            }
+            if let Some(waveform) = &self.ui.timeline_waveform {
+                h_ui.weak(&waveform.file_name);
+                if h_ui.small_button("✖").on_hover_text("移除波形").clicked() {
+                    clear_waveform = true;
+                }
+                h_ui.add_enabled(
+                    self.ui.timeline_snap,
+                    egui::Checkbox::new(&mut self.ui.timeline_snap_to_onsets, "吸附到起音点"),
+                )
+                .on_hover_text("拖动时也对齐到波形中检测到的起音点");
+            }
+        });
+        ui.weak("拖动块的两端调整开始或结束时间，拖动中间部分整体平移。按住 Ctrl 滚动滚轮可缩放。");
+        ui.separator();
+
+        let waveform = self.ui.timeline_waveform.as_deref();
+        let mut drag = self.ui.timeline_drag;
+        let canvas = TimelineCanvas {
+            origin_x: 0.0,
+            zoom: self.ui.timeline_zoom,
+            boundaries: match drag {
+                Some(d) if self.ui.timeline_snap => {
+                    let mut boundaries = line_editor::timing_boundaries(lines, Some(d.target));
+                    if self.ui.timeline_snap_to_onsets
+                        && let Some(waveform) = waveform
+                    {
+                        boundaries.extend(&waveform.onsets);
+                        boundaries.sort_unstable();
+                        boundaries.dedup();
                     }
+                    boundaries
                 }
-                menu.separator();
-                if menu.button("自定义键").clicked() {
-                    actions_to_send.push(UserAction::Lyrics(Box::new(LyricsAction::AddMetadata(
-                        CanonicalMetadataKey::Custom("custom".to_string()),
-                    ))));
-                    menu.close_menu();
+                _ => Vec::new(),
+            },
+        };
+        let header_height = TIMELINE_RULER_HEIGHT
+            + if waveform.is_some() {
+                TIMELINE_WAVEFORM_HEIGHT
+            } else {
+                0.0
+            };
+        let lyrics_end_ms = lines.iter().map(|l| l.end_ms).max().unwrap_or(0);
+        let total_ms =
+            lyrics_end_ms.max(waveform.map_or(0, |w| w.duration_ms())) + TIMELINE_TAIL_MS;
+        let content_size = egui::vec2(
+            TIMELINE_LABEL_WIDTH + canvas.width_of(total_ms),
+            header_height + lines.len() as f32 * TIMELINE_ROW_HEIGHT,
+        );
+
+        let scroll_output = ScrollArea::both()
+            .id_salt("timeline_scroll")
+            .auto_shrink([false, false])
+            .show_viewport(ui, |scroll_ui, viewport| {
+                let (rect, _) = scroll_ui.allocate_exact_size(content_size, egui::Sense::hover());
+                let canvas = TimelineCanvas {
+                    origin_x: rect.left() + TIMELINE_LABEL_WIDTH,
+                    ..canvas
+                };
+                let clip_x = scroll_ui.clip_rect().x_range();
+
+                // 只绘制可见的行
+                let first_row = ((viewport.min.y - header_height) / TIMELINE_ROW_HEIGHT)
+                    .floor()
+                    .max(0.0) as usize;
+                let last_row = ((viewport.max.y - header_height) / TIMELINE_ROW_HEIGHT)
+                    .ceil()
+                    .max(0.0) as usize;
+
+                for (line_index, line) in
+                    lines.iter().enumerate().take(last_row + 1).skip(first_row)
+                {
+                    let row_top =
+                        rect.top() + header_height + line_index as f32 * TIMELINE_ROW_HEIGHT;
+                    scroll_ui.painter().text(
+                        egui::pos2(clip_x.min + 4.0, row_top + TIMELINE_ROW_HEIGHT / 2.0),
+                        egui::Align2::LEFT_CENTER,
+                        (line_index + 1).to_string(),
+                        egui::FontId::monospace(11.0),
+                        scroll_ui.visuals().weak_text_color(),
+                    );
+
+                    canvas.block(
+                        scroll_ui,
+                        TimingTarget::Line(line_index),
+                        (line.start_ms, line.end_ms),
+                        egui::Rangef::new(row_top + 2.0, row_top + 2.0 + TIMELINE_LINE_BAR_HEIGHT),
+                        "",
+                        &mut drag,
+                    );
+
+                    // 整体拖动行时，音节随行一起预览平移
+                    let syllable_shift = match drag {
+                        Some(d)
+                            if d.target == TimingTarget::Line(line_index)
+                                && d.handle == TimelineDragHandle::Body =>
+                        {
+                            d.current.0 as i64 - d.original.0 as i64
+                        }
+                        _ => 0,
+                    };
+                    let syllable_y = egui::Rangef::new(
+                        row_top + TIMELINE_LINE_BAR_HEIGHT + 4.0,
+                        row_top + TIMELINE_ROW_HEIGHT - 2.0,
+                    );
+                    let Some(track) = line.main_track() else {
+                        continue;
+                    };
+                    for (syllable_index, syllable) in track.content.syllables().enumerate() {
+                        let range = (
+                            syllable.start_ms.saturating_add_signed(syllable_shift),
+                            syllable.end_ms.saturating_add_signed(syllable_shift),
+                        );
+                        if canvas.x_of(range.1) < clip_x.min || canvas.x_of(range.0) > clip_x.max {
+                            continue;
+                        }
+                        canvas.block(
+                            scroll_ui,
+                            TimingTarget::Syllable(line_index, syllable_index),
+                            range,
+                            syllable_y,
+                            syllable.text.trim(),
+                            &mut drag,
+                        );
+                    }
+                }
+
+                // 标尺固定在可视区域顶部
+                let ruler_rect = egui::Rect::from_x_y_ranges(
+                    clip_x,
+                    egui::Rangef::new(
+                        rect.top() + viewport.min.y,
+                        rect.top() + viewport.min.y + TIMELINE_RULER_HEIGHT,
+                    ),
+                );
+                canvas.paint_ruler(scroll_ui, ruler_rect, total_ms);
+
+                // 波形固定在标尺下方
+                if let Some(waveform) = waveform {
+                    let waveform_rect = egui::Rect::from_x_y_ranges(
+                        clip_x,
+                        egui::Rangef::new(
+                            ruler_rect.bottom(),
+                            ruler_rect.bottom() + TIMELINE_WAVEFORM_HEIGHT,
+                        ),
+                    );
+                    canvas.paint_waveform(scroll_ui, waveform_rect, waveform);
                 }
             });
+
+        if ui.rect_contains_pointer(scroll_output.inner_rect) {
+            let zoom_delta = ui.input(|i| i.zoom_delta());
+            if zoom_delta != 1.0 {
+                self.ui.timeline_zoom = (self.ui.timeline_zoom * zoom_delta)
+                    .clamp(*TIMELINE_ZOOM_RANGE.start(), *TIMELINE_ZOOM_RANGE.end());
+            }
+        }
+
+        // 松开鼠标时才把拖动结果写回歌词
+        let mut action_to_send = None;
+        if let Some(finished) = drag
+            && !ui.input(|i| i.pointer.any_down())
+        {
+            drag = None;
+            let (start_ms, end_ms) = finished.current;
+            if finished.current != finished.original {
+                action_to_send = Some(match finished.target {
+                    TimingTarget::Line(index) => {
+                        LyricsAction::SetLineTiming(index, start_ms, end_ms)
+                    }
+                    TimingTarget::Syllable(line_index, syllable_index) => {
+                        LyricsAction::SetSyllableTiming(
+                            line_index,
+                            syllable_index,
+                            start_ms,
+                            end_ms,
+                        )
+                    }
+                });
+            }
+        }
+        self.ui.timeline_drag = drag;
+        if clear_waveform {
+            self.ui.timeline_waveform = None;
+        }
+        if load_audio {
+            self.send_action(UserAction::File(FileAction::LoadTimelineAudio));
+        }
+        if let Some(action) = action_to_send {
+            self.send_action(UserAction::Lyrics(Box::new(action)));
+        }
+    }
+
+    /// 绘制歌词预览。按本地时钟模拟播放并逐字高亮，可拖动进度条或点击某行跳转。
+    pub fn draw_karaoke_preview_window_contents(&mut self, ui: &mut egui::Ui) {
+        let Some(parsed_data) = self.lyrics.parsed_lyric_data.as_ref() else {
+            ui.label("没有已解析的歌词。");
+            return;
+        };
+        let lines = &parsed_data.lines;
+        let total_ms = lines.iter().map(|line| line.end_ms).max().unwrap_or(0);
+
+        let playback = &mut self.ui.karaoke_preview;
+        if playback.is_playing() && playback.position_ms() >= total_ms {
+            playback.pause();
+            playback.seek(total_ms);
+        }
+        let position_ms = playback.position_ms();
+
+        ui.horizontal(|h_ui| {
+            let label = if playback.is_playing() {
+                "⏸ 暂停"
+            } else {
+                "▶ 播放"
+            };
+            if h_ui.button(label).clicked() {
+                if playback.is_playing() {
+                    playback.pause();
+                } else {
+                    if position_ms >= total_ms {
+                        playback.seek(0);
+                    }
+                    playback.play();
+                }
+            }
+            let mut slider_ms = position_ms;
+            if h_ui
+                .add(egui::Slider::new(&mut slider_ms, 0..=total_ms).show_value(false))
+                .changed()
+            {
+                playback.seek(slider_ms);
+            }
+            h_ui.monospace(format!(
+                "{} / {}",
+                format_timestamp_ms(position_ms),
+                format_timestamp_ms(total_ms)
+            ));
         });
+        ui.weak("点击任意一行可跳转到该行开始处。");
+        ui.separator();
 
-        for action in actions_to_send {
-            self.send_action(action);
+        let first_active = lines
+            .iter()
+            .position(|line| (line.start_ms..line.end_ms).contains(&position_ms));
+        let mut seek_to = None;
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |scroll_ui| {
+                for (index, line) in lines.iter().enumerate() {
+                    let is_active = (line.start_ms..line.end_ms).contains(&position_ms);
+                    let response = draw_karaoke_line(scroll_ui, line, position_ms, is_active);
+                    if response.clicked() {
+                        seek_to = Some(line.start_ms);
+                    }
+                    if playback.is_playing() && first_active == Some(index) {
+                        response.scroll_to_me(Some(Align::Center));
+                    }
+                    scroll_ui.add_space(KARAOKE_LINE_SPACING);
+                }
+            });
+
+        if let Some(start_ms) = seek_to {
+            playback.seek(start_ms);
+        }
+        if playback.is_playing() {
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// 绘制当前目标格式的生成选项窗口。
+    pub fn draw_format_options_window_contents(&mut self, ui: &mut egui::Ui) {
+        let target_format = self.lyrics.target_format;
+        let options = &mut self.ui.temp_format_options;
+
+        match target_format {
+            LyricFormat::Lrc => {
+                // 增强模式交给增强型 LRC 生成器，它不支持括号合并背景人声、结束时间和两位精度
+                let word_timing = options.lrc.word_timing;
+                let word_timing_hint = "增强模式下不可用";
+                egui::Grid::new("lrc_format_options_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label("背景人声:");
+                        ComboBox::from_id_salt("lrc_sub_lines_mode")
+                            .selected_text(lrc_sub_lines_mode_label(
+                                options.lrc.sub_lines_output_mode,
+                            ))
+                            .show_ui(ui, |ui| {
+                                for mode in [
+                                    LrcSubLinesOutputMode::Ignore,
+                                    LrcSubLinesOutputMode::MergeWithParentheses,
+                                    LrcSubLinesOutputMode::SeparateLines,
+                                ] {
+                                    let supported = !word_timing
+                                        || mode != LrcSubLinesOutputMode::MergeWithParentheses;
+                                    ui.add_enabled_ui(supported, |ui| {
+                                        ui.selectable_value(
+                                            &mut options.lrc.sub_lines_output_mode,
+                                            mode,
+                                            lrc_sub_lines_mode_label(mode),
+                                        )
+                                        .on_disabled_hover_text(word_timing_hint);
+                                    });
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("行结束时间:");
+                        let mut on_long_pause = matches!(
+                            options.lrc.end_time_output_mode,
+                            LrcEndTimeOutputMode::OnLongPause { .. }
+                        );
+                        ui.add_enabled_ui(!word_timing, |ui| {
+                            ComboBox::from_id_salt("lrc_end_time_mode")
+                                .selected_text(match options.lrc.end_time_output_mode {
+                                    LrcEndTimeOutputMode::Never => "不输出",
+                                    LrcEndTimeOutputMode::Always => "每行都输出",
+                                    LrcEndTimeOutputMode::OnLongPause { .. } => "仅长间隔时输出",
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut options.lrc.end_time_output_mode,
+                                        LrcEndTimeOutputMode::Never,
+                                        "不输出",
+                                    );
+                                    ui.selectable_value(
+                                        &mut options.lrc.end_time_output_mode,
+                                        LrcEndTimeOutputMode::Always,
+                                        "每行都输出",
+                                    );
+                                    if ui
+                                        .selectable_label(on_long_pause, "仅长间隔时输出")
+                                        .clicked()
+                                        && !on_long_pause
+                                    {
+                                        options.lrc.end_time_output_mode =
+                                            LrcEndTimeOutputMode::OnLongPause {
+                                                threshold_ms: 5000,
+                                            };
+                                        on_long_pause = true;
+                                    }
+                                })
+                                .response
+                                .on_disabled_hover_text(word_timing_hint);
+                        });
+                        ui.end_row();
+
+                        if on_long_pause
+                            && !word_timing
+                            && let LrcEndTimeOutputMode::OnLongPause { threshold_ms } =
+                                &mut options.lrc.end_time_output_mode
+                        {
+                            ui.label("间隔阈值 (毫秒):");
+                            ui.add(egui::DragValue::new(threshold_ms).speed(100.0));
+                            ui.end_row();
+                        }
+
+                        ui.label("时间精度:");
+                        ui.add_enabled_ui(!word_timing, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.radio_value(
+                                    &mut options.lrc.timestamp_precision,
+                                    LrcTimestampPrecision::Centiseconds,
+                                    "2 位 [mm:ss.xx]",
+                                )
+                                .on_hover_text("兼容只支持两位小数的播放器，时间按四舍五入取整");
+                                ui.radio_value(
+                                    &mut options.lrc.timestamp_precision,
+                                    LrcTimestampPrecision::Milliseconds,
+                                    "3 位 [mm:ss.xxx]",
+                                );
+                            })
+                            .response
+                            .on_disabled_hover_text("增强模式下时间标签固定为 3 位精度");
+                        });
+                        ui.end_row();
+
+                        ui.label("增强模式:");
+                        ui.checkbox(&mut options.lrc.word_timing, "输出逐字时间标签")
+                            .on_hover_text(
+                                "按增强型 LRC 格式输出，与目标格式选择 Enhanced LRC 相同；\n时间标签固定为 3 位精度",
+                            );
+                        ui.end_row();
+                    });
+            }
+            LyricFormat::Ttml => {
+                ui.horizontal(|ui| {
+                    ui.label("计时模式:");
+                    ui.radio_value(&mut options.ttml.timing_mode, TtmlTimingMode::Word, "逐字");
+                    ui.radio_value(&mut options.ttml.timing_mode, TtmlTimingMode::Line, "逐行");
+                });
+                ui.checkbox(
+                    &mut options.ttml.use_apple_format_rules,
+                    "遵循 Apple Music 格式规则",
+                )
+                .on_hover_text("例如将翻译写入 <head> 而不是内联");
+                ui.checkbox(&mut options.ttml.format, "格式化输出");
+                ui.checkbox(&mut options.ttml.auto_word_splitting, "自动分词");
+                if options.ttml.auto_word_splitting {
+                    ui.horizontal(|ui| {
+                        ui.label("标点权重:");
+                        ui.add(
+                            egui::DragValue::new(&mut options.ttml.punctuation_weight)
+                                .range(0.0..=1.0)
+                                .speed(0.05),
+                        );
+                    });
+                }
+            }
+            LyricFormat::Ass => {
+                draw_optional_text_option(ui, &mut options.ass.script_info, "自定义 [Script Info]");
+                draw_optional_text_option(ui, &mut options.ass.styles, "自定义 [V4+ Styles]");
+            }
+            LyricFormat::Lqe => {
+                egui::Grid::new("lqe_format_options_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label("主歌词格式:");
+                        ComboBox::from_id_salt("lqe_main_format")
+                            .selected_text(options.lqe.main_lyric_format.to_string())
+                            .show_ui(ui, |ui| {
+                                for format in [LyricFormat::Lys, LyricFormat::Lrc] {
+                                    ui.selectable_value(
+                                        &mut options.lqe.main_lyric_format,
+                                        format,
+                                        format.to_string(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("翻译/音译格式:");
+                        ComboBox::from_id_salt("lqe_auxiliary_format")
+                            .selected_text(options.lqe.auxiliary_format.to_string())
+                            .show_ui(ui, |ui| {
+                                for format in [LyricFormat::Lrc, LyricFormat::Lys] {
+                                    ui.selectable_value(
+                                        &mut options.lqe.auxiliary_format,
+                                        format,
+                                        format.to_string(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                    });
+            }
+            _ => {
+                ui.label(format!("{target_format} 格式没有可配置的选项。"));
+            }
         }
+
+        ui.separator();
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            if ui.button("取消").clicked() {
+                self.send_action(UserAction::UI(UIAction::HidePanel(
+                    PanelType::FormatOptions,
+                )));
+            }
+            if ui.button("保存并应用").clicked() {
+                self.send_action(UserAction::Lyrics(Box::new(
+                    LyricsAction::SaveFormatOptions(Box::new(self.ui.temp_format_options.clone())),
+                )));
+            }
+        });
     }
 
     /// 绘制底部日志面板。
@@ -1137,13 +4156,20 @@ impl UniLyricApp {
                     .auto_shrink([false, false])
                     .stick_to_bottom(true)
                     .show(ui, |scroll_ui| {
+                        let mut text_to_copy = None;
                         if self.ui.log_display_buffer.is_empty() {
                             scroll_ui.add_space(5.0);
                             scroll_ui.label(egui::RichText::new("暂无日志。").weak().italics());
                             scroll_ui.add_space(5.0);
                         } else {
-                            for entry in &self.ui.log_display_buffer {
-                                scroll_ui.horizontal_wrapped(|line_ui| {
+                            for (index, entry) in self.ui.log_display_buffer.iter().enumerate() {
+                                // 复制按钮只在悬停时显示，悬停状态取自上一帧
+                                let row_id = scroll_ui.id().with(("log_row_hovered", index));
+                                let was_hovered = scroll_ui
+                                    .ctx()
+                                    .data(|d| d.get_temp::<bool>(row_id))
+                                    .unwrap_or(false);
+                                let row = scroll_ui.horizontal_wrapped(|line_ui| {
                                     line_ui.label(
                                         egui::RichText::new(
                                             entry.timestamp.format("[%H:%M:%S.%3f]").to_string(),
@@ -1159,10 +4185,41 @@ impl UniLyricApp {
                                     );
                                     line_ui.add_space(4.0);
                                     line_ui.label(egui::RichText::new(&entry.message).monospace());
+                                    if was_hovered
+                                        && line_ui
+                                            .small_button("📋")
+                                            .on_hover_text("复制此条日志")
+                                            .clicked()
+                                    {
+                                        text_to_copy = Some(entry.to_copy_text());
+                                    }
                                 });
+                                let is_hovered = row.response.contains_pointer();
+                                scroll_ui
+                                    .ctx()
+                                    .data_mut(|d| d.insert_temp(row_id, is_hovered));
+                                row.response.interact(egui::Sense::click()).context_menu(
+                                    |menu_ui| {
+                                        if menu_ui.button("复制此条日志").clicked() {
+                                            text_to_copy = Some(entry.to_copy_text());
+                                            menu_ui.close_menu();
+                                        }
+                                    },
+                                );
                             }
                         }
                         scroll_ui.allocate_space(scroll_ui.available_size_before_wrap());
+
+                        if let Some(text) = text_to_copy {
+                            scroll_ui.ctx().copy_text(text);
+                            self.ui.toasts.add(egui_toast::Toast {
+                                text: "日志已复制到剪贴板".into(),
+                                kind: egui_toast::ToastKind::Success,
+                                options: egui_toast::ToastOptions::default()
+                                    .duration_in_seconds(2.0),
+                                style: Default::default(),
+                            });
+                        }
                     });
             });
     }
@@ -1195,10 +4252,19 @@ impl UniLyricApp {
                     btn_ui.ctx().copy_text(self.lyrics.input_text.clone());
                 }
                 btn_ui.add_space(BUTTON_STRIP_SPACING);
+                if btn_ui
+                    .selectable_label(self.ui.input_find.open, "🔍")
+                    .on_hover_text("查找")
+                    .clicked()
+                {
+                    self.ui.input_find.open = !self.ui.input_find.open;
+                }
+                btn_ui.add_space(BUTTON_STRIP_SPACING);
                 if btn_ui.button("粘贴").clicked() {
                     if let Ok(mut clipboard) = arboard::Clipboard::new() {
                         if let Ok(text) = clipboard.get_text() {
                             self.lyrics.input_text = text.clone();
+                            self.lyrics.mark_texts_changed();
                             self.send_action(crate::app_actions::UserAction::Lyrics(Box::new(
                                 crate::app_actions::LyricsAction::MainInputChanged(text),
                             )));
@@ -1212,6 +4278,11 @@ impl UniLyricApp {
             });
         });
         ui.separator();
+        if self.ui.input_find.open {
+            draw_find_bar(ui, &mut self.ui.input_find, &self.lyrics.input_text);
+        }
+        let find_query = self.ui.input_find.active_query().map(str::to_owned);
+        let find_current = self.ui.input_find.current;
 
         let scroll_area = if self.ui.wrap_text {
             egui::ScrollArea::vertical().id_salt("input_scroll_vertical_only")
@@ -1222,31 +4293,72 @@ impl UniLyricApp {
         };
 
         scroll_area.auto_shrink([false, false]).show(ui, |s_ui| {
+            let show_line_numbers = self.ui.show_line_numbers;
+            let line_count = self.lyrics.input_text.lines().count().max(1);
             let text_edit_widget = egui::TextEdit::multiline(&mut self.lyrics.input_text)
                 .hint_text("在此处粘贴或拖放主歌词文件")
                 .font(egui::TextStyle::Monospace)
                 .desired_width(f32::INFINITY);
 
-            let response = if !self.ui.wrap_text {
-                let font_id = egui::TextStyle::Monospace.resolve(s_ui.style());
-                let text_color = s_ui.visuals().text_color();
+            let response = s_ui
+                .horizontal_top(|row_ui| {
+                    let font_id = egui::TextStyle::Monospace.resolve(row_ui.style());
+                    let gutter_width = if show_line_numbers {
+                        line_number_gutter_width(row_ui, &font_id, line_count)
+                    } else {
+                        0.0
+                    };
+                    row_ui.add_space(gutter_width);
+
+                    let output = if !self.ui.wrap_text || find_query.is_some() {
+                        let text_color = row_ui.visuals().text_color();
+                        let current_background = row_ui.visuals().selection.bg_fill;
+                        let wrap_text = self.ui.wrap_text;
+
+                        let mut layouter = |ui: &egui::Ui, string: &str, wrap_width: f32| {
+                            let matches = find_query
+                                .as_deref()
+                                .map(|query| text_search::find_matches(string, query))
+                                .unwrap_or_default();
+                            let layout_job = text_search::highlighted_layout_job(
+                                string,
+                                &matches,
+                                find_current,
+                                font_id.clone(),
+                                text_color,
+                                current_background,
+                                if wrap_text { wrap_width } else { f32::INFINITY },
+                            );
+                            ui.fonts(|f| f.layout_job(layout_job))
+                        };
 
-                let mut layouter = |ui: &egui::Ui, string: &str, _wrap_width: f32| {
-                    let layout_job = egui::text::LayoutJob::simple(
-                        string.to_string(),
-                        font_id.clone(),
-                        text_color,
-                        f32::INFINITY,
+                        text_edit_widget.layouter(&mut layouter).show(row_ui)
+                    } else {
+                        text_edit_widget.show(row_ui)
+                    };
+                    scroll_to_current_match(
+                        row_ui,
+                        &mut self.ui.input_find,
+                        &self.lyrics.input_text,
+                        &output.galley,
+                        output.galley_pos,
                     );
-                    ui.fonts(|f| f.layout_job(layout_job))
-                };
 
-                s_ui.add(text_edit_widget.layouter(&mut layouter))
-            } else {
-                s_ui.add(text_edit_widget)
-            };
+                    if show_line_numbers {
+                        paint_line_numbers(
+                            row_ui,
+                            &output.galley,
+                            output.galley_pos,
+                            output.response.rect.left() - row_ui.spacing().item_spacing.x,
+                            &font_id,
+                        );
+                    }
+                    output.response
+                })
+                .inner;
 
-            if response.changed() && !self.lyrics.conversion_in_progress {
+            if response.changed() {
+                self.lyrics.mark_texts_changed();
                 self.send_action(crate::app_actions::UserAction::Lyrics(Box::new(
                     crate::app_actions::LyricsAction::MainInputChanged(
                         self.lyrics.input_text.clone(),
@@ -1283,6 +4395,16 @@ impl UniLyricApp {
                     crate::app_actions::FileAction::LoadTranslationLrc,
                 ));
             }
+            if button_strip_ui
+                .add_enabled(
+                    self.lyrics.parsed_lyric_data.is_some(),
+                    egui::Button::new("导出"),
+                )
+                .on_hover_text("从已解析的歌词中导出为独立的 LRC 文件")
+                .clicked()
+            {
+                self.send_action(UserAction::File(FileAction::ExportTranslationLrc));
+            }
 
             button_strip_ui.allocate_ui_with_layout(
                 button_strip_ui.available_size_before_wrap(),
@@ -1319,6 +4441,53 @@ impl UniLyricApp {
             );
         });
 
+        let mut track_action: Option<LyricsAction> = None;
+        ui.horizontal_wrapped(|tabs_ui| {
+            for (index, track) in self.lyrics.translation_tracks.iter().enumerate() {
+                let is_active = index == self.lyrics.active_translation;
+                if tabs_ui
+                    .selectable_label(is_active, &track.language)
+                    .clicked()
+                    && !is_active
+                {
+                    track_action = Some(LyricsAction::SelectTranslationTrack(index));
+                }
+            }
+            if tabs_ui
+                .add_enabled(
+                    self.lyrics.translation_tracks.len() > 1,
+                    egui::Button::new("✖").small(),
+                )
+                .on_hover_text("删除当前语言的翻译")
+                .clicked()
+            {
+                track_action = Some(LyricsAction::RemoveTranslationTrack(
+                    self.lyrics.active_translation,
+                ));
+            }
+            tabs_ui.separator();
+            let response = tabs_ui.add(
+                egui::TextEdit::singleline(&mut self.ui.new_translation_language)
+                    .hint_text("语言，如 en")
+                    .desired_width(80.0),
+            );
+            let submitted =
+                response.lost_focus() && tabs_ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if tabs_ui
+                .button("添加语言")
+                .on_hover_text("新增一条翻译，导出 TTML 时以 xml:lang 区分")
+                .clicked()
+                || submitted
+            {
+                track_action = Some(LyricsAction::AddTranslationTrack(
+                    self.ui.new_translation_language.clone(),
+                ));
+            }
+        });
+        if let Some(action) = track_action {
+            self.send_action(UserAction::Lyrics(Box::new(action)));
+        }
+
         let scroll_area = if self.ui.wrap_text {
             egui::ScrollArea::vertical().id_salt("translation_lrc_scroll_vertical")
         } else {
@@ -1407,6 +4576,16 @@ impl UniLyricApp {
                     crate::app_actions::FileAction::LoadRomanizationLrc,
                 ));
             }
+            if button_strip_ui
+                .add_enabled(
+                    self.lyrics.parsed_lyric_data.is_some(),
+                    egui::Button::new("导出"),
+                )
+                .on_hover_text("从已解析的歌词中导出为独立的 LRC 文件")
+                .clicked()
+            {
+                self.send_action(UserAction::File(FileAction::ExportRomanizationLrc));
+            }
 
             button_strip_ui.allocate_ui_with_layout(
                 button_strip_ui.available_size_before_wrap(),
@@ -1500,6 +4679,14 @@ impl UniLyricApp {
     pub fn draw_output_panel_contents(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|title_ui| {
             title_ui.heading("输出结果");
+            if self.lyrics.conversion_in_progress {
+                title_ui.add(Spinner::new());
+                let elapsed = self
+                    .lyrics
+                    .conversion_started_at
+                    .map_or(0.0, |started_at| started_at.elapsed().as_secs_f32());
+                title_ui.weak(format!("正在转换… {elapsed:.1}s"));
+            }
             title_ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |btn_ui| {
                 let send_to_player_enabled;
                 {
@@ -1538,6 +4725,50 @@ impl UniLyricApp {
 
                 btn_ui.add_space(BUTTON_STRIP_SPACING);
 
+                if btn_ui
+                    .add_enabled(
+                        self.lyrics.parsed_lyric_data.is_some()
+                            && !self.lyrics.conversion_in_progress,
+                        Button::new("复制为 AMLL 提交格式"),
+                    )
+                    .on_hover_text(
+                        "生成符合 AMLL TTML 数据库要求的 TTML 并复制，缺失的必填元数据会列在警告中",
+                    )
+                    .clicked()
+                {
+                    self.send_action(UserAction::Lyrics(Box::new(
+                        LyricsAction::CopyAsAmllSubmission,
+                    )));
+                }
+
+                btn_ui.add_space(BUTTON_STRIP_SPACING);
+
+                if btn_ui
+                    .add_enabled(
+                        self.lyrics.parsed_lyric_data.is_some()
+                            && !self.lyrics.conversion_in_progress,
+                        Button::new("复制为 Markdown"),
+                    )
+                    .on_hover_text("复制为包含“时间 | 歌词 | 翻译”三列的 Markdown 表格")
+                    .clicked()
+                {
+                    self.send_action(UserAction::Lyrics(Box::new(
+                        LyricsAction::CopyAsMarkdownTable,
+                    )));
+                }
+
+                btn_ui.add_space(BUTTON_STRIP_SPACING);
+
+                if btn_ui
+                    .selectable_label(self.ui.output_find.open, "🔍")
+                    .on_hover_text("查找")
+                    .clicked()
+                {
+                    self.ui.output_find.open = !self.ui.output_find.open;
+                }
+
+                btn_ui.add_space(BUTTON_STRIP_SPACING);
+
                 if btn_ui
                     .add_enabled(
                         !self.lyrics.output_text.is_empty() && !self.lyrics.conversion_in_progress,
@@ -1556,6 +4787,11 @@ impl UniLyricApp {
             });
         });
         ui.separator();
+        let find_matches = if self.ui.output_find.open {
+            draw_find_bar(ui, &mut self.ui.output_find, &self.lyrics.output_text)
+        } else {
+            Vec::new()
+        };
 
         let scroll_area = if self.ui.wrap_text {
             ScrollArea::vertical().id_salt("output_scroll_vertical_label")
@@ -1566,6 +4802,52 @@ impl UniLyricApp {
         };
 
         scroll_area.auto_shrink([false, false]).show(ui, |s_ui| {
+            if self.ui.show_line_numbers || !find_matches.is_empty() {
+                s_ui.horizontal_top(|row_ui| {
+                    let font_id = egui::FontId::monospace(13.0);
+                    if self.ui.show_line_numbers {
+                        let line_count = self.lyrics.output_text.lines().count().max(1);
+                        let gutter_width = line_number_gutter_width(row_ui, &font_id, line_count);
+                        row_ui.add_space(gutter_width);
+                    }
+
+                    let wrap_width = if self.ui.wrap_text {
+                        row_ui.available_width()
+                    } else {
+                        f32::INFINITY
+                    };
+                    let layout_job = text_search::highlighted_layout_job(
+                        &self.lyrics.output_text,
+                        &find_matches,
+                        self.ui.output_find.current,
+                        font_id.clone(),
+                        row_ui.visuals().text_color(),
+                        row_ui.visuals().selection.bg_fill,
+                        wrap_width,
+                    );
+                    let galley = row_ui.fonts(|f| f.layout_job(layout_job));
+                    let response = row_ui.add(egui::Label::new(galley.clone()).selectable(true));
+
+                    if self.ui.show_line_numbers {
+                        paint_line_numbers(
+                            row_ui,
+                            &galley,
+                            response.rect.left_top(),
+                            response.rect.left() - row_ui.spacing().item_spacing.x,
+                            &font_id,
+                        );
+                    }
+                    scroll_to_current_match(
+                        row_ui,
+                        &mut self.ui.output_find,
+                        &self.lyrics.output_text,
+                        &galley,
+                        response.rect.left_top(),
+                    );
+                });
+                return;
+            }
+
             let mut label_widget = egui::Label::new(
                 egui::RichText::new(&self.lyrics.output_text)
                     .monospace()
@@ -1582,6 +4864,75 @@ impl UniLyricApp {
         });
     }
 
+    /// 绘制侧边栏中可折叠的 SMTC 调试信息区。
+    fn draw_smtc_debug_info(&self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("调试信息")
+            .id_salt("smtc_debug_info")
+            .default_open(false)
+            .show(ui, |ui| {
+                let debug = &self.player.smtc_debug;
+                let offset = self.player.smtc_time_offset_ms;
+
+                egui::Grid::new("smtc_debug_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 2.0])
+                    .show(ui, |grid| {
+                        grid.label("更新次数:");
+                        grid.monospace(debug.update_count.to_string());
+                        grid.end_row();
+
+                        grid.label("原始位置:");
+                        grid.monospace(
+                            debug
+                                .last_position_ms
+                                .map_or_else(|| "-".to_string(), |pos| format!("{pos} ms")),
+                        );
+                        grid.end_row();
+
+                        grid.label("应用偏移后:");
+                        grid.monospace(debug.last_position_ms.map_or_else(
+                            || "-".to_string(),
+                            |pos| format!("{} ms ({offset:+} ms)", pos as i64 + offset),
+                        ));
+                        grid.end_row();
+
+                        grid.label("报告时间戳:");
+                        grid.monospace(debug.last_position_report_time.as_deref().unwrap_or("-"));
+                        grid.end_row();
+
+                        grid.label("距上次更新:");
+                        grid.monospace(debug.last_update_at.map_or_else(
+                            || "-".to_string(),
+                            |at| format!("{} ms", at.elapsed().as_millis()),
+                        ));
+                        grid.end_row();
+
+                        grid.label("平均更新间隔:");
+                        grid.monospace(debug.average_interval().map_or_else(
+                            || "-".to_string(),
+                            |avg| format!("{} ms", avg.as_millis()),
+                        ));
+                        grid.end_row();
+
+                        let min = debug.recent_intervals.iter().min();
+                        let max = debug.recent_intervals.iter().max();
+                        grid.label("间隔范围:");
+                        grid.monospace(match (min, max) {
+                            (Some(min), Some(max)) => {
+                                format!("{} - {} ms", min.as_millis(), max.as_millis())
+                            }
+                            _ => "-".to_string(),
+                        });
+                        grid.end_row();
+                    });
+
+                ui.small(format!(
+                    "统计最近 {} 次更新的间隔",
+                    debug.recent_intervals.len()
+                ));
+            });
+    }
+
     pub fn draw_amll_connector_sidebar(&mut self, ui: &mut egui::Ui) {
         ui.add_space(TITLE_ALIGNMENT_OFFSET);
         ui.heading("AMLL Connector");
@@ -1622,7 +4973,10 @@ impl UniLyricApp {
                     }
                     ui.colored_label(Color32::GREEN, "状态: 已连接");
                 }
-                WebsocketStatus::Error(err_msg_ref) => {
+                WebsocketStatus::Error {
+                    message: err_msg_ref,
+                    ..
+                } => {
                     if ui.button("重试连接").clicked() {
                         self.send_action(UserAction::AmllConnector(AmllConnectorAction::Retry));
                     }
@@ -1630,6 +4984,30 @@ impl UniLyricApp {
                     ui.small(err_msg_ref);
                 }
             }
+
+            if let Some((delivery, time)) = &self.amll_connector.lyric_delivery {
+                let time = time.format("%H:%M:%S");
+                match delivery {
+                    LyricDeliveryStatus::Pending => {
+                        ui.horizontal(|h_ui| {
+                            h_ui.add(Spinner::new());
+                            h_ui.label("歌词已发送，等待 Player 确认...");
+                        });
+                    }
+                    LyricDeliveryStatus::Delivered => {
+                        ui.colored_label(Color32::GREEN, format!("歌词已送达 ({time})"))
+                            .on_hover_text("AMLL Player 已回应发送歌词后的 Ping，说明歌词已被接收");
+                    }
+                    LyricDeliveryStatus::TimedOut => {
+                        ui.colored_label(Color32::ORANGE, format!("歌词发送可能失败 ({time})"))
+                            .on_hover_text("AMLL Player 未在超时时间内确认，可以尝试重新发送");
+                    }
+                    LyricDeliveryStatus::Failed(reason) => {
+                        ui.colored_label(Color32::RED, format!("歌词发送失败 ({time})"));
+                        ui.small(reason);
+                    }
+                }
+            }
         });
 
         ui.separator();
@@ -1650,33 +5028,48 @@ impl UniLyricApp {
             None => "自动 (系统默认)".to_string(),
         };
 
-        let combo_changed = egui::ComboBox::from_id_salt("smtc_source_selector")
-            .selected_text(combo_label_text)
-            .show_ui(ui, |combo_ui| {
-                let mut changed_in_combo = false;
-                if combo_ui
-                    .selectable_label(selected_id.is_none(), "自动 (系统默认)")
-                    .clicked()
-                {
-                    selected_id = None;
-                    changed_in_combo = true;
-                }
-                for session_info in &available_sessions {
-                    if combo_ui
-                        .selectable_label(
-                            selected_id.as_ref() == Some(&session_info.session_id),
-                            &session_info.display_name,
-                        )
-                        .clicked()
-                    {
-                        selected_id = Some(session_info.session_id.clone());
-                        changed_in_combo = true;
-                    }
-                }
-                changed_in_combo
+        let mut refresh_requested = false;
+        let combo_changed = ui
+            .horizontal(|h_ui| {
+                let changed = egui::ComboBox::from_id_salt("smtc_source_selector")
+                    .selected_text(combo_label_text)
+                    .show_ui(h_ui, |combo_ui| {
+                        let mut changed_in_combo = false;
+                        if combo_ui
+                            .selectable_label(selected_id.is_none(), "自动 (系统默认)")
+                            .clicked()
+                        {
+                            selected_id = None;
+                            changed_in_combo = true;
+                        }
+                        for session_info in &available_sessions {
+                            if combo_ui
+                                .selectable_label(
+                                    selected_id.as_ref() == Some(&session_info.session_id),
+                                    &session_info.display_name,
+                                )
+                                .clicked()
+                            {
+                                selected_id = Some(session_info.session_id.clone());
+                                changed_in_combo = true;
+                            }
+                        }
+                        changed_in_combo
+                    })
+                    .inner
+                    .unwrap_or(false);
+
+                refresh_requested = h_ui
+                    .button("🔄")
+                    .on_hover_text("重新获取可用的 SMTC 会话列表")
+                    .clicked();
+                changed
             })
-            .inner
-            .unwrap_or(false);
+            .inner;
+
+        if refresh_requested {
+            self.send_action(UserAction::Player(PlayerAction::RefreshSmtcSessions));
+        }
 
         if combo_changed {
             self.send_action(UserAction::Player(PlayerAction::SelectSmtcSession(
@@ -1747,9 +5140,55 @@ impl UniLyricApp {
                 }
             });
 
+            ui.horizontal(|h_ui| {
+                for (slot, name) in ["A", "B"].into_iter().enumerate() {
+                    let stored = self.player.offset_slots[slot];
+                    let is_active = self.player.active_offset_slot == Some(slot)
+                        && stored == Some(self.player.smtc_time_offset_ms);
+                    let label = stored.map_or_else(
+                        || format!("{name}: 未设置"),
+                        |offset| format!("{name}: {offset:+} ms"),
+                    );
+                    let response = h_ui
+                        .add_enabled(
+                            stored.is_some(),
+                            egui::SelectableLabel::new(is_active, label),
+                        )
+                        .on_hover_text(format!("应用槽位 {name} 的偏移量"));
+                    if response.clicked() {
+                        offset_action_to_send =
+                            Some(UserAction::Player(PlayerAction::ApplyOffsetSlot(slot)));
+                    }
+                    if h_ui
+                        .small_button("存")
+                        .on_hover_text(format!("将当前偏移量保存到槽位 {name}"))
+                        .clicked()
+                    {
+                        offset_action_to_send =
+                            Some(UserAction::Player(PlayerAction::StoreOffsetSlot(slot)));
+                    }
+                }
+
+                let both_stored = self.player.offset_slots.iter().all(Option::is_some);
+                if h_ui
+                    .add_enabled(both_stored, egui::Button::new("⇄"))
+                    .on_hover_text("在 A/B 两个偏移量之间切换")
+                    .clicked()
+                {
+                    let next_slot = match self.player.active_offset_slot {
+                        Some(0) => 1,
+                        _ => 0,
+                    };
+                    offset_action_to_send =
+                        Some(UserAction::Player(PlayerAction::ApplyOffsetSlot(next_slot)));
+                }
+            });
+
             if let Some(action) = offset_action_to_send {
                 self.send_action(action);
             }
+
+            self.draw_smtc_debug_info(ui);
         } else {
             ui.weak("无SMTC信息 / 未选择特定源");
         }
@@ -1768,11 +5207,14 @@ impl UniLyricApp {
         if response.clicked() {
             self.send_action(UserAction::Player(PlayerAction::SaveToLocalCache));
         }
+        if ui.button("🗂 管理本地缓存...").clicked() {
+            self.send_action(UserAction::UI(UIAction::ShowPanel(PanelType::LocalCache)));
+        }
 
         ui.separator();
 
         ui.strong("自动歌词搜索状态:");
-        let sources_config = vec![
+        let mut sources_config = vec![
             (
                 AutoSearchSource::LocalCache,
                 &self.fetcher.local_cache_status,
@@ -1798,8 +5240,30 @@ impl UniLyricApp {
                 &self.fetcher.amll_db_status,
                 Some(&self.fetcher.last_amll_db_result),
             ),
+            (
+                AutoSearchSource::CustomHttp,
+                &self.fetcher.custom_http_status,
+                Some(&self.fetcher.last_custom_http_result),
+            ),
         ];
 
+        let (source_order, custom_http_enabled) = {
+            let settings = self.app_settings.lock().unwrap();
+            (
+                AutoSearchSource::normalize_order(&settings.auto_search_source_order),
+                settings.custom_http_source_enabled,
+            )
+        };
+        if !custom_http_enabled {
+            sources_config.retain(|(source, ..)| *source != AutoSearchSource::CustomHttp);
+        }
+        sources_config.sort_by_key(|(source, ..)| {
+            source_order
+                .iter()
+                .position(|s| s == source)
+                .unwrap_or(usize::MAX)
+        });
+
         let mut action_load_lyrics: Option<(AutoSearchSource, FullLyricsResult)> = None;
         let mut action_refetch: Option<AutoSearchSource> = None;
 
@@ -1892,6 +5356,7 @@ impl UniLyricApp {
                 let is_searching = matches!(self.downloader.search_state, SearchState::Searching);
 
                 let mut perform_search = false;
+                let mut inputs_changed = false;
 
                 egui::Grid::new("search_inputs_grid")
                     .num_columns(2)
@@ -1902,6 +5367,9 @@ impl UniLyricApp {
                             TextEdit::singleline(&mut self.downloader.title_input)
                                 .hint_text("必填"),
                         );
+                        if title_edit.changed() {
+                            inputs_changed = true;
+                        }
                         if title_edit.lost_focus()
                             && grid_ui.input(|i| i.key_pressed(egui::Key::Enter))
                         {
@@ -1915,6 +5383,9 @@ impl UniLyricApp {
                             TextEdit::singleline(&mut self.downloader.artist_input)
                                 .hint_text("可选"),
                         );
+                        if artist_edit.changed() {
+                            inputs_changed = true;
+                        }
                         if artist_edit.lost_focus()
                             && grid_ui.input(|i| i.key_pressed(egui::Key::Enter))
                         {
@@ -1928,6 +5399,9 @@ impl UniLyricApp {
                             TextEdit::singleline(&mut self.downloader.album_input)
                                 .hint_text("可选"),
                         );
+                        if album_edit.changed() {
+                            inputs_changed = true;
+                        }
                         if album_edit.lost_focus()
                             && grid_ui.input(|i| i.key_pressed(egui::Key::Enter))
                         {
@@ -1965,15 +5439,42 @@ impl UniLyricApp {
                         )));
                     }
 
+                    if h_ui
+                        .button("批量...")
+                        .on_hover_text("一次为多首歌下载歌词")
+                        .clicked()
+                    {
+                        action_to_send = Some(UserAction::UI(UIAction::ShowPanel(
+                            PanelType::BatchDownload,
+                        )));
+                    }
+
                     if is_searching {
                         h_ui.add(Spinner::new());
                     }
                 });
 
+                let mut word_timed_only = self.downloader.word_timed_only;
+                if left_ui
+                    .checkbox(&mut word_timed_only, "仅显示逐字歌词")
+                    .on_hover_text(
+                        "隐藏确定只有逐行时间轴的结果。\n无法预先判断的来源会标注为“未知”，预览后即可确定。",
+                    )
+                    .changed()
+                {
+                    action_to_send = Some(UserAction::Downloader(Box::new(
+                        DownloaderAction::SetWordTimedOnly(word_timed_only),
+                    )));
+                }
+
                 if perform_search {
                     action_to_send = Some(UserAction::Downloader(Box::new(
                         DownloaderAction::PerformSearch,
                     )));
+                } else if inputs_changed {
+                    action_to_send = Some(UserAction::Downloader(Box::new(
+                        DownloaderAction::ScheduleAutoSearch,
+                    )));
                 }
 
                 left_ui.add_space(10.0);
@@ -1983,9 +5484,6 @@ impl UniLyricApp {
                 ScrollArea::vertical().auto_shrink([false, false]).show(
                     left_ui,
                     |s_ui| match &self.downloader.search_state {
-                        SearchState::Idle => {
-                            s_ui.label("请输入关键词进行搜索。");
-                        }
                         SearchState::Searching => {
                             s_ui.label("正在搜索...");
                         }
@@ -1993,14 +5491,40 @@ impl UniLyricApp {
                             s_ui.colored_label(Color32::RED, "搜索失败:");
                             s_ui.label(err);
                         }
-                        SearchState::Success(results) => {
+                        SearchState::Idle | SearchState::Success(_) => {
+                            let results = self.downloader.displayed_results();
+                            let is_idle = matches!(self.downloader.search_state, SearchState::Idle);
                             if results.is_empty() {
-                                s_ui.label("未找到结果。");
+                                s_ui.label(if is_idle {
+                                    "请输入关键词进行搜索。"
+                                } else {
+                                    "未找到结果。"
+                                });
                             } else {
-                                for result in results {
+                                if is_idle
+                                    && let Some((title, artist)) = &self.downloader.auto_search_query
+                                {
+                                    s_ui.weak(format!(
+                                        "以下为自动搜索“{title} - {artist}”已找到的结果，无需重复搜索。"
+                                    ));
+                                }
+                                let mut hidden_count = 0;
+                                for (result, origin) in results {
+                                    if self.downloader.is_result_hidden(result) {
+                                        hidden_count += 1;
+                                        continue;
+                                    }
+                                    let word_timing = self.downloader.word_timing_of(result);
+
                                     let is_selected =
                                         self.downloader.selected_result_for_preview.as_ref()
                                             == Some(result);
+                                    let is_applied = self.downloader.applied_result.as_ref().is_some_and(
+                                        |(provider_name, provider_id)| {
+                                            *provider_name == result.provider_name
+                                                && *provider_id == result.provider_id
+                                        },
+                                    );
 
                                     let artists_str = result
                                         .artists
@@ -2020,13 +5544,14 @@ impl UniLyricApp {
                                     );
 
                                     let display_text = format!(
-                                        "{} - {}\n专辑: {}\n时长: {} | 来源: {} | 匹配度: {:?}",
+                                        "{}{} - {}\n专辑: {}\n时长: {} | 来源: {} | 匹配度: {:?}",
+                                        if is_applied { "✔ 已应用 | " } else { "" },
                                         result.title,
                                         artists_str,
                                         album_str,
                                         duration_str,
                                         result.provider_name,
-                                        result.match_type
+                                        result.match_type,
                                     );
                                     if s_ui.selectable_label(is_selected, display_text).clicked() {
                                         action_to_send = Some(UserAction::Downloader(Box::new(
@@ -2035,6 +5560,16 @@ impl UniLyricApp {
                                             ),
                                         )));
                                     }
+                                    draw_result_tags(
+                                        s_ui,
+                                        origin,
+                                        word_timing,
+                                        self.downloader.preview_summary_of(result),
+                                    );
+                                }
+
+                                if hidden_count > 0 {
+                                    s_ui.weak(format!("已隐藏 {hidden_count} 条逐行歌词结果。"));
                                 }
                             }
                         }
@@ -2071,10 +5606,29 @@ impl UniLyricApp {
                                         DownloaderAction::ApplyAndClose,
                                     )));
                                 }
+                                if btn_ui
+                                    .add_enabled(can_apply, Button::new("应用但不关闭"))
+                                    .on_hover_text(
+                                        "应用到编辑器后停留在下载器，方便继续对比其他结果",
+                                    )
+                                    .clicked()
+                                {
+                                    action_to_send = Some(UserAction::Downloader(Box::new(
+                                        DownloaderAction::ApplyAndKeepOpen,
+                                    )));
+                                }
+                                if let Some(action) = self.draw_audio_preview_button(btn_ui) {
+                                    action_to_send = Some(action);
+                                }
                             });
                         },
                     );
 
+                    egui::TopBottomPanel::top("preview_details_panel")
+                        .show_inside(right_ui, |details_ui| {
+                            self.draw_downloader_preview_details(details_ui)
+                        });
+
                     egui::CentralPanel::default().show_inside(right_ui, |text_panel_ui| {
                         ScrollArea::vertical().auto_shrink([false, false]).show(
                             text_panel_ui,
@@ -2096,6 +5650,145 @@ impl UniLyricApp {
         }
     }
 
+    /// 绘制试听按钮，所选结果的来源不提供播放链接时禁用。
+    fn draw_audio_preview_button(&self, ui: &mut egui::Ui) -> Option<UserAction> {
+        let can_preview = self
+            .downloader
+            .selected_result_for_preview
+            .as_ref()
+            .and_then(|r| lyrics_helper_rs::ProviderName::try_from_str(&r.provider_name))
+            .is_some_and(|provider| provider.supports_song_link());
+        let is_loading = self.downloader.audio_preview_loading.is_some();
+        let is_playing = self.downloader.audio_preview.is_playing();
+        if is_playing {
+            // 播放结束由后台线程标记，需要定期重绘以更新按钮状态
+            ui.ctx()
+                .request_repaint_after(std::time::Duration::from_millis(250));
+        }
+
+        let label = if is_loading {
+            "⏳ 加载中..."
+        } else if is_playing {
+            "⏹ 停止试听"
+        } else {
+            "▶ 试听"
+        };
+        ui.add_enabled(can_preview, Button::new(label))
+            .on_hover_text(format!(
+                "播放前 {} 秒",
+                crate::audio_preview::PREVIEW_DURATION.as_secs()
+            ))
+            .on_disabled_hover_text("该来源不提供试听")
+            .clicked()
+            .then(|| UserAction::Downloader(Box::new(DownloaderAction::ToggleAudioPreview)))
+    }
+
+    /// 在下载器预览区顶部绘制所选结果的元数据摘要。
+    fn draw_downloader_preview_details(&self, ui: &mut egui::Ui) {
+        let Some(result) = self.downloader.selected_result_for_preview.as_ref() else {
+            return;
+        };
+        let parsed = self
+            .downloader
+            .selected_full_lyrics
+            .as_ref()
+            .map(|full| &full.parsed);
+
+        egui::CollapsingHeader::new("结果详情")
+            .id_salt("downloader_preview_details")
+            .default_open(true)
+            .show(ui, |details_ui| {
+                egui::Grid::new("downloader_preview_details_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(details_ui, |grid| {
+                        let mut row = |key: &str, value: String| {
+                            grid.strong(key);
+                            grid.label(value);
+                            grid.end_row();
+                        };
+
+                        row("标题", result.title.clone());
+                        row(
+                            "艺术家",
+                            result
+                                .artists
+                                .iter()
+                                .map(|a| a.name.as_str())
+                                .collect::<Vec<_>>()
+                                .join(" / "),
+                        );
+                        row(
+                            "专辑",
+                            result.album.clone().unwrap_or_else(|| "未知".to_string()),
+                        );
+                        if let Some(year) = parsed.and_then(|p| release_year_of(&p.raw_metadata)) {
+                            row("发行年份", year);
+                        }
+                        row(
+                            "时长",
+                            result
+                                .duration
+                                .map_or_else(|| "未知".to_string(), format_timestamp_ms),
+                        );
+                        if let Some(language) = &result.language {
+                            row("语言", format!("{language:?}"));
+                        }
+                        row(
+                            "来源",
+                            format!("{} (ID: {})", result.provider_name, result.provider_id),
+                        );
+                        row("匹配度", format!("{:?}", result.match_type));
+
+                        let Some(parsed) = parsed else {
+                            return;
+                        };
+                        let main_tracks = || parsed.lines.iter().flat_map(|l| l.main_tracks());
+                        let has_translation = main_tracks().any(|t| !t.translations.is_empty());
+                        let has_romanization = main_tracks().any(|t| !t.romanizations.is_empty());
+                        let has_background = parsed
+                            .lines
+                            .iter()
+                            .any(|l| l.background_tracks().next().is_some());
+                        let yes_no = |value: bool| if value { "有" } else { "无" }.to_string();
+
+                        row(
+                            "时间轴",
+                            if parsed.is_line_timed_source {
+                                "逐行"
+                            } else {
+                                "逐字"
+                            }
+                            .to_string(),
+                        );
+                        row("行数", parsed.lines.len().to_string());
+                        row("翻译", yes_no(has_translation));
+                        row("罗马音", yes_no(has_romanization));
+                        row("背景人声", yes_no(has_background));
+                        if !parsed.agents.agents_by_id.is_empty() {
+                            row("演唱者", parsed.agents.agents_by_id.len().to_string());
+                        }
+                    });
+
+                if let Some(parsed) = parsed
+                    && !parsed.raw_metadata.is_empty()
+                {
+                    egui::CollapsingHeader::new(format!(
+                        "原始元数据 ({})",
+                        parsed.raw_metadata.len()
+                    ))
+                    .id_salt("downloader_preview_raw_metadata")
+                    .show(details_ui, |raw_ui| {
+                        let mut entries: Vec<_> = parsed.raw_metadata.iter().collect();
+                        entries.sort_by(|a, b| a.0.cmp(b.0));
+                        for (key, values) in entries {
+                            raw_ui.label(format!("{key}: {}", values.join(", ")));
+                        }
+                    });
+                }
+            });
+    }
+
     pub fn draw_batch_converter_view(&mut self, ctx: &egui::Context) {
         use crate::app_actions::BatchConverterAction;
 
@@ -2139,6 +5832,98 @@ impl UniLyricApp {
                 }
             });
 
+            ui.horizontal(|h_ui| {
+                h_ui.strong("扩展名白名单:");
+                let mut whitelist_input = self.batch_converter.extension_whitelist_input.clone();
+                let response = h_ui.add(
+                    egui::TextEdit::singleline(&mut whitelist_input)
+                        .hint_text("lrc, ttml")
+                        .desired_width(240.0),
+                );
+                if response.changed() {
+                    self.send_action(UserAction::BatchConverter(
+                        BatchConverterAction::SetExtensionWhitelist(whitelist_input),
+                    ));
+                }
+                response.on_hover_text(
+                    "只有这些扩展名的文件会被当作主歌词候选，以逗号分隔。留空则不过滤。",
+                );
+            });
+
+            ui.horizontal(|h_ui| {
+                let mut auto_pair = self.batch_converter.auto_pair_enabled;
+                if h_ui
+                    .checkbox(&mut auto_pair, "自动配对翻译/罗马音")
+                    .on_hover_text(
+                        "扫描时把文件名带有下列后缀或语言标签（如 song.zh.lrc）的文件合并到同名主歌词中一起转换",
+                    )
+                    .changed()
+                {
+                    self.send_action(UserAction::BatchConverter(
+                        BatchConverterAction::SetAutoPairEnabled(auto_pair),
+                    ));
+                }
+            });
+
+            ui.add_enabled_ui(self.batch_converter.auto_pair_enabled, |ui| {
+                ui.horizontal(|h_ui| {
+                    h_ui.strong("翻译后缀:");
+                    let mut suffixes_input =
+                        self.batch_converter.translation_suffixes_input.clone();
+                    let response = h_ui.add(
+                        egui::TextEdit::singleline(&mut suffixes_input)
+                            .hint_text("_tr, .translation")
+                            .desired_width(240.0),
+                    );
+                    if response.changed() {
+                        self.send_action(UserAction::BatchConverter(
+                            BatchConverterAction::SetTranslationSuffixes(suffixes_input),
+                        ));
+                    }
+                    response.on_hover_text(
+                        "文件名（不含扩展名）以这些后缀结尾的文件作为翻译，以逗号分隔，不区分大小写",
+                    );
+                });
+                ui.horizontal(|h_ui| {
+                    h_ui.strong("罗马音后缀:");
+                    let mut suffixes_input =
+                        self.batch_converter.romanization_suffixes_input.clone();
+                    let response = h_ui.add(
+                        egui::TextEdit::singleline(&mut suffixes_input)
+                            .hint_text("_romaji, .romanization")
+                            .desired_width(240.0),
+                    );
+                    if response.changed() {
+                        self.send_action(UserAction::BatchConverter(
+                            BatchConverterAction::SetRomanizationSuffixes(suffixes_input),
+                        ));
+                    }
+                    response.on_hover_text(
+                        "文件名（不含扩展名）以这些后缀结尾的文件作为罗马音，以逗号分隔，不区分大小写",
+                    );
+                });
+            });
+
+            ui.horizontal_wrapped(|h_ui| {
+                h_ui.strong("转换中应用:")
+                    .on_hover_text("每个任务解析后按此顺序应用所选的后处理器，再生成目标格式");
+                let can_edit = self.batch_converter.status != BatchConverterStatus::Converting;
+                for processor in ProcessorType::ALL {
+                    let mut enabled = self.batch_converter.postprocessors.contains(&processor);
+                    if h_ui
+                        .add_enabled(
+                            can_edit,
+                            egui::Checkbox::new(&mut enabled, processor.display_name()),
+                        )
+                        .changed()
+                    {
+                        self.send_action(UserAction::BatchConverter(
+                            BatchConverterAction::SetPostprocessorEnabled(processor, enabled),
+                        ));
+                    }
+                }
+            });
+
             ui.add_space(10.0);
 
             let can_scan = self.batch_converter.input_dir.is_some()
@@ -2165,13 +5950,36 @@ impl UniLyricApp {
                     "已扫描 {} 个任务, 等待开始。",
                     self.batch_converter.tasks.len()
                 ),
-                BatchConverterStatus::Converting => "正在转换...".to_string(),
+                BatchConverterStatus::Converting => {
+                    let progress = format!(
+                        "正在转换... 已处理 {}/{} 个任务",
+                        self.batch_converter.processed_task_count(),
+                        self.batch_converter.tasks.len()
+                    );
+                    match self.batch_converter.estimated_remaining() {
+                        Some(remaining) => {
+                            let secs = remaining.as_secs();
+                            format!("{progress}，预计剩余 {}:{:02}", secs / 60, secs % 60)
+                        }
+                        None => format!("{progress}，正在估算剩余时间..."),
+                    }
+                }
+                BatchConverterStatus::Paused => format!(
+                    "已暂停，已处理 {}/{} 个任务。",
+                    self.batch_converter.processed_task_count(),
+                    self.batch_converter.tasks.len()
+                ),
                 BatchConverterStatus::Completed => {
                     format!("已完成所有 {} 个任务。", self.batch_converter.tasks.len())
                 }
                 BatchConverterStatus::Failed(ref err) => format!("失败: {}", err),
             };
             ui.label(status_text);
+            if self.batch_converter.status == BatchConverterStatus::Converting {
+                // 任务状态由后台线程更新，需要定期重绘以刷新进度和剩余时间
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_secs(1));
+            }
 
             egui::ScrollArea::vertical()
                 .auto_shrink([false, false])
@@ -2189,7 +5997,26 @@ impl UniLyricApp {
                                 if let Some(main_file) =
                                     self.batch_converter.file_lookup.get(&task.main_lyric_id)
                                 {
-                                    grid_ui.label(&main_file.filename);
+                                    let paired_count = task.translation_lyric_ids.len()
+                                        + task.romanization_lyric_ids.len();
+                                    if paired_count == 0 {
+                                        grid_ui.label(&main_file.filename);
+                                    } else {
+                                        let paired_names = task
+                                            .translation_lyric_ids
+                                            .iter()
+                                            .chain(&task.romanization_lyric_ids)
+                                            .filter_map(|id| self.batch_converter.file_lookup.get(id))
+                                            .map(|file| file.filename.as_str())
+                                            .collect::<Vec<_>>()
+                                            .join("\n");
+                                        grid_ui
+                                            .label(format!(
+                                                "{} (+{paired_count})",
+                                                main_file.filename
+                                            ))
+                                            .on_hover_text(format!("已配对:\n{paired_names}"));
+                                    }
                                 } else {
                                     grid_ui.label("未知文件");
                                 }
@@ -2216,6 +6043,9 @@ impl UniLyricApp {
                                     lyrics_helper_core::BatchEntryStatus::SkippedNoMatch => {
                                         grid_ui.label("已跳过");
                                     }
+                                    lyrics_helper_core::BatchEntryStatus::DryRun { .. } => {
+                                        grid_ui.colored_label(egui::Color32::LIGHT_BLUE, "试运行");
+                                    }
                                 };
 
                                 if let lyrics_helper_core::BatchEntryStatus::Failed(err_msg) =
@@ -2224,10 +6054,48 @@ impl UniLyricApp {
                                     grid_ui.label(err_msg);
                                 } else if let lyrics_helper_core::BatchEntryStatus::Completed {
                                     output_path,
-                                    ..
+                                    warnings,
                                 } = &task.status
                                 {
-                                    grid_ui.label(output_path.to_string_lossy());
+                                    if warnings.is_empty() {
+                                        grid_ui.label(output_path.to_string_lossy());
+                                    } else {
+                                        grid_ui
+                                            .colored_label(
+                                                egui::Color32::YELLOW,
+                                                format!(
+                                                    "⚠ {}",
+                                                    output_path.to_string_lossy()
+                                                ),
+                                            )
+                                            .on_hover_text(warnings.join("\n"));
+                                    }
+                                } else if let lyrics_helper_core::BatchEntryStatus::DryRun {
+                                    output_path,
+                                    output_size,
+                                    overwrites_existing,
+                                    warnings,
+                                } = &task.status
+                                {
+                                    let mut detail = format!(
+                                        "{} ({output_size} 字节)",
+                                        output_path.to_string_lossy()
+                                    );
+                                    if *overwrites_existing {
+                                        detail.push_str("，将覆盖已有文件");
+                                    }
+                                    if !warnings.is_empty() {
+                                        grid_ui
+                                            .colored_label(
+                                                egui::Color32::YELLOW,
+                                                format!("⚠ {detail}"),
+                                            )
+                                            .on_hover_text(warnings.join("\n"));
+                                    } else if *overwrites_existing {
+                                        grid_ui.colored_label(egui::Color32::YELLOW, detail);
+                                    } else {
+                                        grid_ui.label(detail);
+                                    }
                                 } else {
                                     grid_ui.label("");
                                 }
@@ -2239,19 +6107,82 @@ impl UniLyricApp {
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |bottom_ui| {
                 bottom_ui.add_space(10.0);
                 bottom_ui.horizontal(|h_ui| {
-                    let can_start_conversion =
-                        self.batch_converter.status == BatchConverterStatus::Ready;
+                    match self.batch_converter.status {
+                        BatchConverterStatus::Converting => {
+                            let pause_pending = self
+                                .batch_converter
+                                .pause_requested
+                                .load(std::sync::atomic::Ordering::Relaxed);
+                            let label = if pause_pending {
+                                "正在暂停..."
+                            } else {
+                                "暂停"
+                            };
+                            if h_ui
+                                .add_enabled(!pause_pending, egui::Button::new(label))
+                                .on_hover_text("在当前任务完成后暂停，已完成的任务会保留")
+                                .clicked()
+                            {
+                                self.send_action(UserAction::BatchConverter(
+                                    BatchConverterAction::PauseConversion,
+                                ));
+                            }
+                        }
+                        BatchConverterStatus::Paused => {
+                            if h_ui.button("继续转换").clicked() {
+                                self.send_action(UserAction::BatchConverter(
+                                    BatchConverterAction::StartConversion,
+                                ));
+                            }
+                        }
+                        _ => {
+                            let can_start_conversion =
+                                self.batch_converter.status == BatchConverterStatus::Ready;
+                            if h_ui
+                                .add_enabled(can_start_conversion, egui::Button::new("开始转换"))
+                                .clicked()
+                            {
+                                self.send_action(UserAction::BatchConverter(
+                                    BatchConverterAction::StartConversion,
+                                ));
+                            }
+
+                            let failed_count = self.batch_converter.failed_task_count();
+                            if failed_count > 0
+                                && h_ui
+                                    .button(format!("仅重试失败项 ({failed_count})"))
+                                    .on_hover_text("只重新转换失败的任务，已成功的任务保持不变")
+                                    .clicked()
+                            {
+                                self.send_action(UserAction::BatchConverter(
+                                    BatchConverterAction::RetryFailed,
+                                ));
+                            }
+                        }
+                    }
+                    if h_ui.button("重置").clicked() {
+                        self.send_action(UserAction::BatchConverter(BatchConverterAction::Reset));
+                    }
+
+                    let mut dry_run = self.batch_converter.dry_run;
+                    let can_toggle_dry_run = !matches!(
+                        self.batch_converter.status,
+                        BatchConverterStatus::Converting | BatchConverterStatus::Paused
+                    );
                     if h_ui
-                        .add_enabled(can_start_conversion, egui::Button::new("开始转换"))
-                        .clicked()
+                        .add_enabled(
+                            can_toggle_dry_run,
+                            egui::Checkbox::new(&mut dry_run, "试运行"),
+                        )
+                        .on_hover_text(
+                            "执行完整的转换流程但不写入文件，只记录每个任务的输出路径和大小",
+                        )
+                        .changed()
                     {
                         self.send_action(UserAction::BatchConverter(
-                            BatchConverterAction::StartConversion,
+                            BatchConverterAction::SetDryRun(dry_run),
                         ));
                     }
-                    if h_ui.button("重置").clicked() {
-                        self.send_action(UserAction::BatchConverter(BatchConverterAction::Reset));
-                    }
                 });
             });
         });
@@ -2260,13 +6191,30 @@ impl UniLyricApp {
     pub fn draw_status_bar(&mut self, ctx: &egui::Context) {
         egui::TopBottomPanel::bottom("app_status_bar").show(ctx, |ui| {
             ui.horizontal_centered(|h_ui| {
+                self.draw_status_summary(h_ui);
+
                 h_ui.with_layout(
                     egui::Layout::right_to_left(egui::Align::Center),
                     |right_ui| {
-                        let warnings_count = self.lyrics.current_warnings.len();
-                        if warnings_count > 0 {
-                            let button_text = format!("⚠️ {}", warnings_count);
-                            let button = right_ui.button(button_text);
+                        let counts = self.warning_counts_by_level();
+                        if counts.iter().any(|&(_, count)| count > 0) {
+                            let mut button_text = egui::text::LayoutJob::default();
+                            for (level, count) in counts.into_iter().filter(|&(_, c)| c > 0) {
+                                if !button_text.is_empty() {
+                                    button_text.append("  ", 0.0, egui::TextFormat::default());
+                                }
+                                button_text.append(
+                                    &format!("{} {count}", level.icon()),
+                                    0.0,
+                                    egui::TextFormat {
+                                        color: level.color(),
+                                        ..Default::default()
+                                    },
+                                );
+                            }
+                            let button = right_ui
+                                .button(button_text)
+                                .on_hover_text("错误 / 警告 / 提示的数量，点击打开警告面板");
                             if button.clicked() {
                                 self.send_action(UserAction::UI(UIAction::ShowPanel(
                                     PanelType::Warnings,
@@ -2279,6 +6227,55 @@ impl UniLyricApp {
         });
     }
 
+    /// 在状态栏左侧绘制源/目标格式、行数和 AMLL Connector 连接状态的摘要。
+    fn draw_status_summary(&self, ui: &mut egui::Ui) {
+        ui.label(format!(
+            "源: {} → 目标: {}",
+            self.lyrics.source_format, self.lyrics.target_format
+        ));
+
+        ui.separator();
+        match self.lyrics.parsed_lyric_data.as_ref() {
+            Some(parsed_data) => {
+                ui.label(format!("{} 行", parsed_data.lines.len()));
+            }
+            None => {
+                ui.weak("未解析");
+            }
+        }
+
+        if self.amll_connector.config.lock().unwrap().enabled {
+            ui.separator();
+            let (text, color) = match &*self.amll_connector.status.lock().unwrap() {
+                WebsocketStatus::Connected => ("已连接", Color32::GREEN),
+                WebsocketStatus::Connecting => ("连接中", ui.visuals().text_color()),
+                WebsocketStatus::Disconnected => ("未连接", ui.visuals().weak_text_color()),
+                WebsocketStatus::Error { .. } => ("连接错误", Color32::RED),
+            };
+            ui.colored_label(color, text)
+                .on_hover_text("AMLL Connector 连接状态");
+        }
+    }
+
+    /// 按级别统计解析警告与歌词检查问题的数量。
+    fn warning_counts_by_level(&self) -> [(WarningLevel, usize); 3] {
+        WarningLevel::ALL.map(|level| {
+            let warnings = self
+                .lyrics
+                .current_warnings
+                .iter()
+                .filter(|w| w.level == level)
+                .count();
+            let lint_issues = self.lyrics.lint_issues.as_ref().map_or(0, |issues| {
+                issues
+                    .iter()
+                    .filter(|issue| WarningLevel::from(issue.severity) == level)
+                    .count()
+            });
+            (level, warnings + lint_issues)
+        })
+    }
+
     pub fn draw_warnings_panel(&mut self, ctx: &egui::Context) {
         egui::TopBottomPanel::bottom("warnings_panel_id")
             .resizable(true)
@@ -2288,6 +6285,28 @@ impl UniLyricApp {
                 ui.vertical_centered_justified(|ui_header| {
                     ui_header.horizontal(|h_ui| {
                         h_ui.label(egui::RichText::new("解析警告").strong());
+                        h_ui.add_space(8.0);
+                        for (level, count) in self.warning_counts_by_level() {
+                            let mut visible = !self.ui.hidden_warning_levels.contains(&level);
+                            if h_ui
+                                .checkbox(
+                                    &mut visible,
+                                    egui::RichText::new(format!(
+                                        "{} {} ({count})",
+                                        level.icon(),
+                                        level.label()
+                                    ))
+                                    .color(level.color()),
+                                )
+                                .changed()
+                            {
+                                if visible {
+                                    self.ui.hidden_warning_levels.remove(&level);
+                                } else {
+                                    self.ui.hidden_warning_levels.insert(level);
+                                }
+                            }
+                        }
                         h_ui.with_layout(
                             egui::Layout::right_to_left(egui::Align::Center),
                             |btn_ui| {
@@ -2302,20 +6321,778 @@ impl UniLyricApp {
                 });
                 ui.separator();
 
+                let mut line_to_locate: Option<usize> = None;
+
                 egui::ScrollArea::vertical()
                     .auto_shrink([false, false])
                     .show(ui, |scroll_ui| {
+                        let hidden_levels = &self.ui.hidden_warning_levels;
                         if self.lyrics.current_warnings.is_empty() {
                             scroll_ui.label(egui::RichText::new("暂无警告。").weak().italics());
                         } else {
-                            for warning in &self.lyrics.current_warnings {
+                            for warning in self
+                                .lyrics
+                                .current_warnings
+                                .iter()
+                                .filter(|w| !hidden_levels.contains(&w.level))
+                            {
+                                scroll_ui.horizontal_wrapped(|line_ui| {
+                                    line_ui
+                                        .colored_label(warning.level.color(), warning.level.icon())
+                                        .on_hover_text(warning.level.label());
+                                    line_ui.label(&warning.message);
+                                });
+                            }
+                        }
+
+                        if let Some(issues) = &self.lyrics.lint_issues {
+                            scroll_ui.add_space(6.0);
+                            scroll_ui.label(egui::RichText::new("歌词检查").strong());
+                            scroll_ui.separator();
+
+                            if issues.is_empty() {
+                                scroll_ui
+                                    .label(egui::RichText::new("未发现问题。").weak().italics());
+                            }
+                            for issue in issues.iter().filter(|issue| {
+                                !hidden_levels.contains(&WarningLevel::from(issue.severity))
+                            }) {
                                 scroll_ui.horizontal_wrapped(|line_ui| {
-                                    line_ui.label("⚠️");
-                                    line_ui.label(warning);
+                                    match issue.severity {
+                                        LintSeverity::Error => {
+                                            line_ui.colored_label(Color32::RED, "错误");
+                                        }
+                                        LintSeverity::Hint => {
+                                            line_ui.colored_label(
+                                                line_ui.visuals().warn_fg_color,
+                                                "提示",
+                                            );
+                                        }
+                                    }
+                                    if let Some(index) = issue.line_index
+                                        && line_ui
+                                            .link(format!("第 {} 行", index + 1))
+                                            .on_hover_text("在逐行编辑中定位")
+                                            .clicked()
+                                    {
+                                        line_to_locate = Some(index);
+                                    }
+                                    line_ui.label(&issue.message);
                                 });
                             }
                         }
                     });
+
+                if let Some(index) = line_to_locate {
+                    self.send_action(UserAction::UI(UIAction::LocateLine(index)));
+                }
             });
     }
 }
+
+/// 计算行号边栏所需的宽度。
+fn line_number_gutter_width(ui: &egui::Ui, font_id: &egui::FontId, line_count: usize) -> f32 {
+    let digits = line_count.to_string().len().max(2);
+    let digit_width = ui.fonts(|f| f.glyph_width(font_id, '0'));
+    digit_width * digits as f32 + LINE_NUMBER_GUTTER_PADDING
+}
+
+/// 在文本左侧绘制与每个逻辑行首对齐的行号。
+///
+/// 行号直接绘制到画布上，不属于文本内容，因此不会被选中或复制。
+fn paint_line_numbers(
+    ui: &egui::Ui,
+    galley: &egui::Galley,
+    galley_pos: egui::Pos2,
+    right_x: f32,
+    font_id: &egui::FontId,
+) {
+    let painter = ui.painter();
+    let color = ui.visuals().weak_text_color();
+    let mut line_number = 1;
+    let mut is_line_start = true;
+
+    for row in &galley.rows {
+        if is_line_start {
+            let y = galley_pos.y + row.rect().top();
+            painter.text(
+                egui::pos2(right_x, y),
+                egui::Align2::RIGHT_TOP,
+                line_number.to_string(),
+                font_id.clone(),
+                color,
+            );
+            line_number += 1;
+        }
+        is_line_start = row.ends_with_newline;
+    }
+}
+
+/// 绘制查找栏，返回关键词在 `text` 中的所有匹配。
+fn draw_find_bar(ui: &mut egui::Ui, find: &mut FindBar, text: &str) -> Vec<std::ops::Range<usize>> {
+    let matches = text_search::find_matches(text, &find.query);
+    if find.current >= matches.len() {
+        find.current = 0;
+    }
+
+    ui.horizontal(|bar_ui| {
+        let response = bar_ui.add(
+            TextEdit::singleline(&mut find.query)
+                .hint_text("查找")
+                .desired_width(160.0),
+        );
+        if response.changed() {
+            find.current = 0;
+            find.scroll_pending = true;
+        }
+        if response.lost_focus() && bar_ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            let backward = bar_ui.input(|i| i.modifiers.shift);
+            find.step(matches.len(), !backward);
+            response.request_focus();
+        }
+        if bar_ui
+            .add_enabled(!matches.is_empty(), Button::new("▲"))
+            .on_hover_text("上一个 (Shift+Enter)")
+            .clicked()
+        {
+            find.step(matches.len(), false);
+        }
+        if bar_ui
+            .add_enabled(!matches.is_empty(), Button::new("▼"))
+            .on_hover_text("下一个 (Enter)")
+            .clicked()
+        {
+            find.step(matches.len(), true);
+        }
+        if matches.is_empty() {
+            if !find.query.is_empty() {
+                bar_ui.weak("无匹配");
+            }
+        } else {
+            bar_ui.label(format!("{}/{}", find.current + 1, matches.len()));
+        }
+        bar_ui.with_layout(Layout::right_to_left(Align::Center), |right_ui| {
+            if right_ui
+                .small_button("✖")
+                .on_hover_text("关闭查找")
+                .clicked()
+            {
+                find.open = false;
+            }
+        });
+    });
+    matches
+}
+
+/// 需要时把当前匹配滚动到可见区域。
+fn scroll_to_current_match(
+    ui: &egui::Ui,
+    find: &mut FindBar,
+    text: &str,
+    galley: &egui::Galley,
+    galley_pos: egui::Pos2,
+) {
+    if !std::mem::take(&mut find.scroll_pending) {
+        return;
+    }
+    let Some(query) = find.active_query() else {
+        return;
+    };
+    let matches = text_search::find_matches(text, query);
+    let Some(range) = matches.get(find.current) else {
+        return;
+    };
+    let cursor = galley.from_ccursor(egui::text::CCursor::new(text_search::char_index_of(
+        text, range,
+    )));
+    let rect = galley
+        .pos_from_cursor(&cursor)
+        .translate(galley_pos.to_vec2());
+    ui.scroll_to_rect(rect, Some(Align::Center));
+}
+
+/// 时间轴画布的坐标换算与吸附参数。
+struct TimelineCanvas {
+    /// 时间 0 所在的屏幕横坐标
+    origin_x: f32,
+    /// 每秒对应的像素数
+    zoom: f32,
+    /// 拖动时可吸附的边界，未启用吸附时为空
+    boundaries: Vec<u64>,
+}
+
+impl TimelineCanvas {
+    fn width_of(&self, ms: u64) -> f32 {
+        ms as f32 / 1000.0 * self.zoom
+    }
+
+    fn x_of(&self, ms: u64) -> f32 {
+        self.origin_x + self.width_of(ms)
+    }
+
+    fn snap(&self, ms: u64) -> u64 {
+        let tolerance_ms = (TIMELINE_SNAP_TOLERANCE_PX / self.zoom * 1000.0) as u64;
+        line_editor::snap_to_boundary(ms, &self.boundaries, tolerance_ms)
+    }
+
+    /// 绘制一个可拖动的块，并处理其两端与中间部分的拖动。
+    fn block(
+        &self,
+        ui: &egui::Ui,
+        target: TimingTarget,
+        range: (u64, u64),
+        y_range: egui::Rangef,
+        label: &str,
+        drag: &mut Option<TimelineDrag>,
+    ) {
+        let active = drag.filter(|d| d.target == target);
+        let (start, end) = active.map_or(range, |d| d.current);
+        let left = self.x_of(start);
+        let rect = egui::Rect::from_x_y_ranges(left..=self.x_of(end).max(left + 2.0), y_range);
+        let handle_width = TIMELINE_HANDLE_WIDTH.min(rect.width() / 3.0);
+
+        let visuals = ui.visuals();
+        let fill = match (active, target) {
+            (Some(_), _) => visuals.selection.bg_fill,
+            (None, TimingTarget::Line(_)) => TIMELINE_LINE_COLOR,
+            (None, TimingTarget::Syllable(..)) => visuals.widgets.inactive.bg_fill,
+        };
+        ui.painter().rect_filled(rect, 2.0, fill);
+        if !label.is_empty() {
+            ui.painter()
+                .with_clip_rect(rect.intersect(ui.clip_rect()))
+                .text(
+                    rect.left_center() + egui::vec2(3.0, 0.0),
+                    egui::Align2::LEFT_CENTER,
+                    label,
+                    egui::FontId::proportional(12.0),
+                    visuals.text_color(),
+                );
+        }
+
+        let parts = [
+            (
+                TimelineDragHandle::Start,
+                egui::Rect::from_x_y_ranges(rect.left()..=rect.left() + handle_width, y_range),
+            ),
+            (
+                TimelineDragHandle::End,
+                egui::Rect::from_x_y_ranges(rect.right() - handle_width..=rect.right(), y_range),
+            ),
+            (
+                TimelineDragHandle::Body,
+                rect.shrink2(egui::vec2(handle_width, 0.0)),
+            ),
+        ];
+        for (handle, part_rect) in parts {
+            let cursor = match handle {
+                TimelineDragHandle::Body => egui::CursorIcon::Grab,
+                _ => egui::CursorIcon::ResizeHorizontal,
+            };
+            let mut response = ui
+                .interact(
+                    part_rect,
+                    ui.id().with(("timeline_block", target, handle)),
+                    egui::Sense::drag(),
+                )
+                .on_hover_cursor(cursor);
+            if response.hovered() && active.is_none() {
+                response = response.on_hover_text(format!(
+                    "{} - {}",
+                    format_timestamp_ms(start),
+                    format_timestamp_ms(end)
+                ));
+            }
+
+            if response.drag_started() {
+                *drag = Some(TimelineDrag::new(target, handle, range));
+            } else if response.dragged()
+                && let Some(current) = drag
+                    .as_mut()
+                    .filter(|d| d.target == target && d.handle == handle)
+            {
+                current.drag_by(response.drag_delta().x / self.zoom * 1000.0, |ms| {
+                    self.snap(ms)
+                });
+            }
+        }
+    }
+
+    /// 在 `rect` 中绘制时间标尺，刻度间隔随缩放自动调整。
+    fn paint_ruler(&self, ui: &egui::Ui, rect: egui::Rect, total_ms: u64) {
+        let painter = ui.painter();
+        let visuals = ui.visuals();
+        painter.rect_filled(rect, 0.0, visuals.panel_fill);
+
+        let step_seconds = [1, 2, 5, 10, 15, 30, 60]
+            .into_iter()
+            .find(|&s| self.width_of(s * 1000) >= 60.0)
+            .unwrap_or(60);
+        let stroke = egui::Stroke::new(1.0, visuals.weak_text_color());
+        for tick_ms in (0..=total_ms).step_by(step_seconds as usize * 1000) {
+            let x = self.x_of(tick_ms);
+            if x < rect.left() || x > rect.right() {
+                continue;
+            }
+            painter.line_segment(
+                [
+                    egui::pos2(x, rect.bottom() - 5.0),
+                    egui::pos2(x, rect.bottom()),
+                ],
+                stroke,
+            );
+            painter.text(
+                egui::pos2(x + 2.0, rect.center().y),
+                egui::Align2::LEFT_CENTER,
+                format!("{:02}:{:02}", tick_ms / 60_000, (tick_ms / 1000) % 60),
+                egui::FontId::monospace(10.0),
+                visuals.weak_text_color(),
+            );
+        }
+    }
+
+    /// 在 `rect` 中绘制音频波形，并用竖线标出检测到的起音点。
+    fn paint_waveform(&self, ui: &egui::Ui, rect: egui::Rect, waveform: &Waveform) {
+        let painter = ui.painter();
+        painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+        let ms_at = |x: f32| ((x - self.origin_x).max(0.0) / self.zoom * 1000.0) as u64;
+        let half_height = rect.height() / 2.0 - 2.0;
+        let stroke = egui::Stroke::new(1.0, TIMELINE_WAVEFORM_COLOR);
+        let mut x = rect.left().max(self.origin_x);
+        while x < rect.right() {
+            let peak = waveform.peak_between(ms_at(x), ms_at(x + 1.0));
+            let amplitude = (peak * half_height).max(0.5);
+            painter.line_segment(
+                [
+                    egui::pos2(x, rect.center().y - amplitude),
+                    egui::pos2(x, rect.center().y + amplitude),
+                ],
+                stroke,
+            );
+            x += 1.0;
+        }
+
+        let onset_stroke = egui::Stroke::new(1.0, TIMELINE_ONSET_COLOR);
+        let first = waveform
+            .onsets
+            .partition_point(|&ms| self.x_of(ms) < rect.left());
+        for &onset_ms in &waveform.onsets[first..] {
+            let x = self.x_of(onset_ms);
+            if x > rect.right() {
+                break;
+            }
+            painter.line_segment(
+                [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+                onset_stroke,
+            );
+        }
+    }
+}
+
+/// 按首次出现的顺序为歌词中的演唱者分配颜色，演唱者多于颜色数时循环使用。
+fn agent_color_map(lines: &[lyrics_helper_core::LyricLine]) -> Vec<(String, Color32)> {
+    let mut colors: Vec<(String, Color32)> = Vec::new();
+    for agent in lines.iter().filter_map(|line| line.agent.as_deref()) {
+        if !colors.iter().any(|(id, _)| id == agent) {
+            let color = AGENT_COLORS[colors.len() % AGENT_COLORS.len()];
+            colors.push((agent.to_string(), color));
+        }
+    }
+    colors
+}
+
+/// 绘制预览中的一行歌词并返回可点击的区域。
+///
+/// 正在演唱的行使用大号字体，已唱过的部分按音节进度从左到右逐渐高亮；
+/// 逐行歌词只有一个音节，因此整行按时间匀速高亮。第一条翻译以小号字体显示在下方。
+fn draw_karaoke_line(
+    ui: &mut egui::Ui,
+    line: &lyrics_helper_core::LyricLine,
+    position_ms: u64,
+    is_active: bool,
+) -> egui::Response {
+    let font_id = egui::FontId::proportional(if is_active {
+        KARAOKE_ACTIVE_FONT_SIZE
+    } else {
+        KARAOKE_FONT_SIZE
+    });
+    let max_width = ui.available_width();
+
+    let galleys: Vec<_> = line
+        .main_track()
+        .map(|track| {
+            track
+                .content
+                .syllables()
+                .map(|syllable| {
+                    let mut text = syllable.text.clone();
+                    if syllable.ends_with_space {
+                        text.push(' ');
+                    }
+                    let galley = ui.fonts(|fonts| {
+                        fonts.layout_no_wrap(text, font_id.clone(), Color32::PLACEHOLDER)
+                    });
+                    (syllable.start_ms, syllable.end_ms, galley)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // 按可用宽度手动折行，记录每个音节相对于行左上角的位置
+    let row_height = ui.fonts(|fonts| fonts.row_height(&font_id));
+    let mut offsets = Vec::with_capacity(galleys.len());
+    let mut cursor = egui::Vec2::ZERO;
+    for (_, _, galley) in &galleys {
+        if cursor.x > 0.0 && cursor.x + galley.size().x > max_width {
+            cursor = egui::vec2(0.0, cursor.y + row_height);
+        }
+        offsets.push(cursor);
+        cursor.x += galley.size().x;
+    }
+    let main_height = cursor.y + row_height;
+
+    let translation = line
+        .main_track()
+        .and_then(|track| track.translations.first())
+        .map(|track| track.text())
+        .filter(|text| !text.trim().is_empty())
+        .map(|text| {
+            ui.fonts(|fonts| {
+                fonts.layout(
+                    text,
+                    egui::FontId::proportional(KARAOKE_FONT_SIZE * 0.8),
+                    ui.visuals().weak_text_color(),
+                    max_width,
+                )
+            })
+        });
+    let translation_height = translation.as_ref().map_or(0.0, |g| g.size().y);
+
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(max_width, main_height + translation_height),
+        egui::Sense::click(),
+    );
+    if !ui.is_rect_visible(rect) {
+        return response;
+    }
+
+    let painter = ui.painter();
+    let unsung_color = ui.visuals().weak_text_color();
+    let sung_color = ui.visuals().hyperlink_color;
+    for ((start_ms, end_ms, galley), offset) in galleys.into_iter().zip(offsets) {
+        let pos = rect.min + offset;
+        let size = galley.size();
+        painter.galley(pos, galley.clone(), unsung_color);
+        if !is_active {
+            continue;
+        }
+        let progress = if position_ms >= end_ms {
+            1.0
+        } else if position_ms <= start_ms {
+            0.0
+        } else {
+            (position_ms - start_ms) as f32 / (end_ms - start_ms) as f32
+        };
+        if progress > 0.0 {
+            let sung_rect = egui::Rect::from_min_size(pos, egui::vec2(size.x * progress, size.y));
+            painter
+                .with_clip_rect(sung_rect)
+                .galley(pos, galley, sung_color);
+        }
+    }
+    if let Some(galley) = translation {
+        painter.galley(
+            rect.min + egui::vec2(0.0, main_height),
+            galley,
+            unsung_color,
+        );
+    }
+
+    response.on_hover_cursor(egui::CursorIcon::PointingHand)
+}
+
+/// 将毫秒时间戳格式化为 `mm:ss.mmm`。
+fn format_timestamp_ms(ms: u64) -> String {
+    format!(
+        "{:02}:{:02}.{:03}",
+        ms / 60_000,
+        (ms / 1000) % 60,
+        ms % 1000
+    )
+}
+
+fn background_smoothing_mode_label(mode: BackgroundSmoothingMode) -> &'static str {
+    match mode {
+        BackgroundSmoothingMode::Skip => "不平滑",
+        BackgroundSmoothingMode::SameAsMain => "与主歌词相同",
+        BackgroundSmoothingMode::Custom => "使用单独参数",
+    }
+}
+
+fn agent_type_label(agent_type: &AgentType) -> &'static str {
+    match agent_type {
+        AgentType::Person => "单人",
+        AgentType::Group => "合唱",
+        AgentType::Other => "其它",
+    }
+}
+
+fn lrc_sub_lines_mode_label(mode: LrcSubLinesOutputMode) -> &'static str {
+    match mode {
+        LrcSubLinesOutputMode::Ignore => "忽略",
+        LrcSubLinesOutputMode::MergeWithParentheses => "用括号合并到主行",
+        LrcSubLinesOutputMode::SeparateLines => "作为独立行输出",
+    }
+}
+
+/// 工具栏上收藏的简繁转换按钮使用的简短名称。
+fn chinese_conversion_short_label(config: ChineseConversionConfig) -> &'static str {
+    match config {
+        ChineseConversionConfig::S2t => "简→繁",
+        ChineseConversionConfig::T2s => "繁→简",
+        ChineseConversionConfig::S2tw => "简→台",
+        ChineseConversionConfig::Tw2s => "台→简",
+        ChineseConversionConfig::S2hk => "简→港",
+        ChineseConversionConfig::Hk2s => "港→简",
+        ChineseConversionConfig::S2twp => "简→台 (含用语)",
+        ChineseConversionConfig::Tw2sp => "台→简 (含用语)",
+        ChineseConversionConfig::T2tw => "繁→台",
+        ChineseConversionConfig::Tw2t => "台→繁",
+        ChineseConversionConfig::T2hk => "繁→港",
+        ChineseConversionConfig::Hk2t => "港→繁",
+        ChineseConversionConfig::Jp2t => "日→繁",
+        ChineseConversionConfig::T2jp => "繁→日",
+    }
+}
+
+/// 绘制一个可选的多行文本选项：勾选后才可编辑，取消勾选则恢复为默认值。
+fn draw_optional_text_option(ui: &mut egui::Ui, value: &mut Option<String>, label: &str) {
+    let mut enabled = value.is_some();
+    if ui.checkbox(&mut enabled, label).changed() {
+        *value = enabled.then(String::new);
+    }
+    if let Some(text) = value.as_mut() {
+        ui.add(
+            TextEdit::multiline(text)
+                .code_editor()
+                .desired_rows(6)
+                .desired_width(f32::INFINITY),
+        );
+    }
+}
+
+/// 在网格中绘制 diff 的一侧：行号、标记和带高亮的文本，共占三列。
+///
+/// `line` 为 `None` 时绘制空白占位，`highlight` 为变化部分的背景色。
+fn draw_diff_cell(
+    grid: &mut egui::Ui,
+    line: Option<&DiffLine>,
+    marker: char,
+    highlight: Option<Color32>,
+) {
+    let Some(line) = line else {
+        grid.label("");
+        grid.label("");
+        grid.label("");
+        return;
+    };
+
+    grid.label(
+        egui::RichText::new(line.line_number.to_string())
+            .monospace()
+            .weak(),
+    );
+    grid.monospace(marker.to_string());
+
+    let font_id = egui::TextStyle::Monospace.resolve(grid.style());
+    let text_color = grid.visuals().text_color();
+    let mut job = egui::text::LayoutJob::default();
+    for segment in &line.segments {
+        let background = match highlight {
+            Some(color) if segment.changed => color,
+            _ => Color32::TRANSPARENT,
+        };
+        job.append(
+            &segment.text,
+            0.0,
+            egui::TextFormat {
+                font_id: font_id.clone(),
+                color: text_color,
+                background,
+                ..Default::default()
+            },
+        );
+    }
+    grid.label(job);
+}
+
+/// 从原始元数据中查找发行年份。不同来源使用的键名不同，这里按常见写法依次尝试。
+fn release_year_of(
+    raw_metadata: &std::collections::HashMap<String, Vec<String>>,
+) -> Option<String> {
+    const YEAR_KEYS: &[&str] = &["year", "date", "releasedate", "release_date", "publishtime"];
+    raw_metadata
+        .iter()
+        .filter(|(key, _)| YEAR_KEYS.contains(&key.to_ascii_lowercase().as_str()))
+        .flat_map(|(_, values)| values)
+        .find_map(|value| {
+            let year: String = value.trim().chars().take(4).collect();
+            (year.len() == 4 && year.chars().all(|c| c.is_ascii_digit())).then_some(year)
+        })
+}
+
+/// 绘制元数据编辑器中的一行。`show_key_editor` 为 `false` 时用空白占位，使值编辑框与上一行对齐。
+fn draw_metadata_entry_row(
+    ui: &mut egui::Ui,
+    index: usize,
+    entry: &mut crate::types::EditableMetadataEntry,
+    show_key_editor: bool,
+    actions: &mut Vec<UserAction>,
+    deletion_index: &mut Option<usize>,
+) {
+    let item_id = entry.id;
+    if ui.checkbox(&mut entry.is_pinned, "").changed() {
+        actions.push(UserAction::Lyrics(Box::new(
+            LyricsAction::ToggleMetadataPinned(index),
+        )));
+    }
+    ui.label("固定")
+        .on_hover_text("勾选后, 此条元数据在加载新歌词时将尝试保留其值");
+
+    let key_editor_width = ui.available_width() * 0.3;
+    let mut key_changed_this_frame = false;
+
+    if show_key_editor {
+        ui.add_space(5.0);
+        ui.label("键:");
+        if let CanonicalMetadataKey::Custom(custom_key_str) = &mut entry.key {
+            let response = ui.add_sized(
+                [key_editor_width, 0.0],
+                egui::TextEdit::singleline(custom_key_str).id_salt(item_id.with("key_edit_custom")),
+            );
+            if response.lost_focus() && response.changed() {
+                if let Ok(parsed_key) = CanonicalMetadataKey::from_str(custom_key_str) {
+                    entry.key = parsed_key;
+                }
+                key_changed_this_frame = true;
+            }
+        } else {
+            egui::ComboBox::from_id_salt(item_id.with("key_combo"))
+                .selected_text(entry.key.to_string())
+                .width(key_editor_width)
+                .show_ui(ui, |combo_ui| {
+                    use strum::IntoEnumIterator;
+                    for key_variant in CanonicalMetadataKey::iter() {
+                        if combo_ui
+                            .selectable_value(
+                                &mut entry.key,
+                                key_variant.clone(),
+                                key_variant.to_string(),
+                            )
+                            .changed()
+                        {
+                            key_changed_this_frame = true;
+                        }
+                    }
+                    combo_ui.separator();
+                    if combo_ui.selectable_label(false, "自定义").clicked() {
+                        entry.key =
+                            CanonicalMetadataKey::Custom(NEW_CUSTOM_METADATA_KEY.to_string());
+                        key_changed_this_frame = true;
+                    }
+                });
+        }
+    } else {
+        let style = ui.style();
+        let space_for_pin_label = ui.text_style_height(&egui::TextStyle::Body);
+        let space_for_key_label = style.spacing.item_spacing.x + style.spacing.interact_size.x;
+
+        ui.add_space(space_for_pin_label + space_for_key_label + key_editor_width);
+    }
+
+    if key_changed_this_frame {
+        actions.push(UserAction::Lyrics(Box::new(
+            LyricsAction::UpdateMetadataKey(index, entry.key.clone()),
+        )));
+    }
+
+    ui.add_space(5.0);
+    ui.label("值:");
+    let validation = entry.key.validate_value(&entry.value);
+    let mut value_edit = egui::TextEdit::singleline(&mut entry.value)
+        .id_salt(item_id.with("value_edit"))
+        .hint_text("元数据值");
+    if validation.is_err() {
+        value_edit = value_edit.text_color(ui.visuals().error_fg_color);
+    }
+    let mut value_edit_response = ui.add(value_edit);
+    if let Err(hint) = validation {
+        value_edit_response = value_edit_response.on_hover_text(hint);
+        ui.colored_label(ui.visuals().error_fg_color, "⚠")
+            .on_hover_text(hint);
+    }
+    if value_edit_response.lost_focus() {
+        actions.push(UserAction::Lyrics(Box::new(
+            LyricsAction::UpdateMetadataValue(index, entry.value.clone()),
+        )));
+    }
+
+    if ui.button("🗑").on_hover_text("删除此条元数据").clicked() {
+        *deletion_index = Some(index);
+    }
+}
+
+/// 在搜索结果下方绘制来源、时间轴、翻译和行数标签。未预览过的结果只能根据提供商推断时间轴。
+fn draw_result_tags(
+    ui: &mut egui::Ui,
+    origin: SearchResultOrigin,
+    word_timing: WordTimingAvailability,
+    summary: Option<ResultPreviewSummary>,
+) {
+    let tag = |ui: &mut egui::Ui, text: String, color: Color32| {
+        egui::Frame::new()
+            .stroke(egui::Stroke::new(1.0, color))
+            .corner_radius(3.0)
+            .inner_margin(egui::Margin::symmetric(4, 0))
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new(text).small().color(color))
+            });
+    };
+
+    ui.horizontal(|tags_ui| {
+        let weak = tags_ui.visuals().weak_text_color();
+        let origin_color = match origin {
+            SearchResultOrigin::Manual => weak,
+            SearchResultOrigin::Auto | SearchResultOrigin::Both => Color32::from_rgb(150, 110, 200),
+        };
+        tag(tags_ui, origin.label().to_string(), origin_color);
+
+        let timing_color = match word_timing {
+            WordTimingAvailability::WordTimed => Color32::from_rgb(80, 170, 90),
+            WordTimingAvailability::LineTimed => Color32::from_rgb(200, 140, 60),
+            WordTimingAvailability::Unknown => weak,
+        };
+        tag(tags_ui, word_timing.label().to_string(), timing_color);
+
+        match summary {
+            Some(summary) => {
+                if summary.has_translation {
+                    tag(
+                        tags_ui,
+                        "含翻译".to_string(),
+                        Color32::from_rgb(80, 140, 210),
+                    );
+                } else {
+                    tag(tags_ui, "无翻译".to_string(), weak);
+                }
+                tag(tags_ui, format!("{} 行", summary.line_count), weak);
+            }
+            None => {
+                tags_ui
+                    .weak("预览后显示翻译和行数")
+                    .on_hover_text("点击结果加载预览后，会记住该结果是否含翻译及其行数");
+            }
+        }
+    });
+}
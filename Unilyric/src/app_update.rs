@@ -1,9 +1,9 @@
 use eframe::egui;
 use tracing::{debug, error, info, warn};
 
-use crate::amll_connector::ConnectorUpdate;
-use crate::app_actions::{PlayerAction, UIAction, UserAction};
-use crate::app_definition::{AppView, UniLyricApp};
+use crate::amll_connector::{ConnectorUpdate, LyricDeliveryStatus};
+use crate::app_actions::{DownloaderAction, PanelType, PlayerAction, UIAction, UserAction};
+use crate::app_definition::{AppView, SearchState, UniLyricApp};
 use crate::error::AppError;
 use crate::types::{AutoFetchResult, AutoSearchSource, AutoSearchStatus, LogLevel, ProviderState};
 use egui_toast::{Toast, ToastKind, ToastOptions};
@@ -26,6 +26,8 @@ pub(super) fn process_log_messages(app: &mut UniLyricApp) {
         app.ui.log_display_buffer.push(log_entry);
     }
 
+    crate::crash_log::persist(false);
+
     if has_warn_or_higher_this_frame {
         let toast_message =
             first_warn_or_higher_message.unwrap_or_else(|| "收到新的警告/错误日志".to_string());
@@ -49,10 +51,25 @@ pub(super) fn process_connector_updates(app: &mut UniLyricApp) {
         match ui_update.payload {
             ConnectorUpdate::WebsocketStatusChanged(status) => {
                 tracing::info!("[App Update] 收到 AMLL Connector 状态更新: {:?}", status);
+                app.amll_connector.record_status_change(&status);
                 *app.amll_connector.status.lock().unwrap() = status;
             }
+            ConnectorUpdate::LyricDelivery(delivery) => {
+                if let LyricDeliveryStatus::Failed(reason) = &delivery {
+                    warn!("[App Update] 向 AMLL Player 发送歌词失败: {reason}");
+                }
+                app.amll_connector.lyric_delivery = Some((delivery, chrono::Local::now()));
+            }
             ConnectorUpdate::SmtcUpdate(media_update) => match media_update {
                 MediaUpdate::TrackChanged(new_info) => {
+                    app.player.smtc_debug.record(
+                        new_info.position_ms,
+                        new_info
+                            .position_report_time
+                            .as_ref()
+                            .map(|time| format!("{time:?}")),
+                    );
+
                     if new_info
                         .title
                         .as_deref()
@@ -239,6 +256,7 @@ pub(super) fn handle_auto_fetch_results(app: &mut UniLyricApp) {
                     AutoSearchSource::Kugou => Some(&app.fetcher.last_kugou_result),
                     AutoSearchSource::Netease => Some(&app.fetcher.last_netease_result),
                     AutoSearchSource::AmllDb => Some(&app.fetcher.last_amll_db_result),
+                    AutoSearchSource::CustomHttp => Some(&app.fetcher.last_custom_http_result),
                     AutoSearchSource::LocalCache => None,
                 };
                 if let Some(result_cache) = result_cache_opt {
@@ -251,6 +269,7 @@ pub(super) fn handle_auto_fetch_results(app: &mut UniLyricApp) {
                     AutoSearchSource::Kugou => Some(&app.fetcher.kugou_status),
                     AutoSearchSource::Netease => Some(&app.fetcher.netease_status),
                     AutoSearchSource::AmllDb => Some(&app.fetcher.amll_db_status),
+                    AutoSearchSource::CustomHttp => Some(&app.fetcher.custom_http_status),
                     AutoSearchSource::LocalCache => Some(&app.fetcher.local_cache_status),
                 };
                 if let Some(status_arc) = status_to_update {
@@ -267,8 +286,7 @@ pub(super) fn handle_auto_fetch_results(app: &mut UniLyricApp) {
                     app.lyrics.metadata_source_is_download = true;
                     app.fetcher.last_source_format = Some(source_format);
                     app.fetcher.current_ui_populated = true;
-                    app.lyrics.display_translation_lrc_output =
-                        app.generate_lrc_from_aux_track(&lyrics_and_metadata.lyrics.parsed, true);
+                    app.load_translation_tracks(&lyrics_and_metadata.lyrics.parsed);
                     app.lyrics.display_romanization_lrc_output =
                         app.generate_lrc_from_aux_track(&lyrics_and_metadata.lyrics.parsed, false);
 
@@ -330,6 +348,7 @@ pub(super) fn handle_auto_fetch_results(app: &mut UniLyricApp) {
                     AutoSearchSource::Kugou => Some(&app.fetcher.last_kugou_result),
                     AutoSearchSource::Netease => Some(&app.fetcher.last_netease_result),
                     AutoSearchSource::AmllDb => Some(&app.fetcher.last_amll_db_result),
+                    AutoSearchSource::CustomHttp => Some(&app.fetcher.last_custom_http_result),
                     AutoSearchSource::LocalCache => None,
                 };
                 if let Some(result_cache) = result_cache_opt {
@@ -342,6 +361,7 @@ pub(super) fn handle_auto_fetch_results(app: &mut UniLyricApp) {
                     AutoSearchSource::Kugou => Some(&app.fetcher.kugou_status),
                     AutoSearchSource::Netease => Some(&app.fetcher.netease_status),
                     AutoSearchSource::AmllDb => Some(&app.fetcher.amll_db_status),
+                    AutoSearchSource::CustomHttp => Some(&app.fetcher.custom_http_status),
                     AutoSearchSource::LocalCache => Some(&app.fetcher.local_cache_status),
                 };
                 if let Some(status_arc) = status_to_update {
@@ -406,6 +426,20 @@ pub(super) fn handle_auto_fetch_results(app: &mut UniLyricApp) {
                 }
                 app.send_action(UserAction::UI(UIAction::StopOtherSearches));
             }
+            AutoFetchResult::CandidatesFound {
+                title,
+                artist,
+                candidates,
+            } => {
+                debug!(
+                    "[AutoFetch] 自动搜索为 '{} - {}' 找到 {} 个候选结果。",
+                    title,
+                    artist,
+                    candidates.len()
+                );
+                app.downloader.auto_search_candidates = candidates;
+                app.downloader.auto_search_query = Some((title, artist));
+            }
             AutoFetchResult::RequestCache => {
                 app.send_action(UserAction::Player(PlayerAction::SaveToLocalCache));
             }
@@ -421,6 +455,7 @@ pub(super) fn draw_ui_elements(app: &mut UniLyricApp, ctx: &egui::Context) {
 
     match app.ui.current_view {
         AppView::Editor => {
+            process_main_input_debounce(app, ctx);
             app.draw_warnings_panel(ctx);
 
             app.draw_status_bar(ctx);
@@ -428,6 +463,7 @@ pub(super) fn draw_ui_elements(app: &mut UniLyricApp, ctx: &egui::Context) {
             draw_editor_view(app, ctx);
         }
         AppView::Downloader => {
+            process_downloader_auto_search(app, ctx);
             app.draw_downloader_view(ctx);
         }
         AppView::BatchConverter => {
@@ -438,12 +474,95 @@ pub(super) fn draw_ui_elements(app: &mut UniLyricApp, ctx: &egui::Context) {
     if app.ui.show_settings_window {
         app.draw_settings_window(ctx);
     }
+
+    if app.ui.pending_session_restore.is_some() {
+        app.draw_session_restore_modal(ctx);
+    }
+
+    if app.ui.pending_close_document.is_some() {
+        app.draw_close_document_modal(ctx);
+    }
+
+    if app.ui.show_exit_unsaved_confirm {
+        app.draw_exit_unsaved_modal(ctx);
+    }
+
+    if app.ui.pending_settings_import.is_some() {
+        app.draw_settings_import_modal(ctx);
+    }
+
+    if app.ui.encoding_prompt.is_some() {
+        app.draw_encoding_prompt_modal(ctx);
+    }
+}
+
+/// 逐步重放操作脚本。每一步都等上一步触发的转换完成后再发送。
+pub(super) fn process_script_playback(app: &mut UniLyricApp) {
+    if app.lyrics.conversion_in_progress {
+        return;
+    }
+    let Some(step) = app.ui.action_script.pending.pop_front() else {
+        return;
+    };
+
+    info!("[ActionScript] 重放步骤: {}", step.describe());
+    app.send_action(step.to_action());
+    if !app.ui.action_script.is_replaying() {
+        info!("[ActionScript] 脚本运行完毕。");
+    }
+}
+
+/// 主输入防抖结束后按需重新转换。
+fn process_main_input_debounce(app: &mut UniLyricApp, ctx: &egui::Context) {
+    let Some(deadline) = app.lyrics.input_convert_deadline else {
+        return;
+    };
+
+    let now = std::time::Instant::now();
+    if now < deadline {
+        ctx.request_repaint_after(deadline - now);
+        return;
+    }
+
+    app.convert_input_if_changed();
+}
+
+/// 下载器输入防抖结束后触发自动搜索。
+fn process_downloader_auto_search(app: &mut UniLyricApp, ctx: &egui::Context) {
+    let Some(deadline) = app.downloader.auto_search_deadline else {
+        return;
+    };
+
+    let now = std::time::Instant::now();
+    if now < deadline {
+        ctx.request_repaint_after(deadline - now);
+        return;
+    }
+
+    app.downloader.auto_search_deadline = None;
+
+    let providers_ready = matches!(app.lyrics_helper_state.provider_state, ProviderState::Ready);
+    let is_searching = matches!(app.downloader.search_state, SearchState::Searching);
+    if providers_ready && !is_searching && !app.downloader.title_input.trim().is_empty() {
+        debug!("[Downloader] 输入防抖结束，自动搜索。");
+        app.send_action(UserAction::Downloader(Box::new(
+            DownloaderAction::PerformSearch,
+        )));
+    }
 }
 
 fn draw_editor_view(app: &mut UniLyricApp, ctx: &egui::Context) {
     let available_width = ctx.screen_rect().width();
     let input_panel_width = (available_width * 0.25).clamp(200.0, 400.0);
 
+    if app.ui.show_document_list_panel && app.lyrics.open_documents.len() > 1 {
+        egui::SidePanel::left("document_list_panel")
+            .default_width(160.0)
+            .show(ctx, |ui| {
+                app.draw_document_list_panel_contents(ui);
+            });
+    }
+
     egui::SidePanel::left("input_panel")
         .default_width(input_panel_width)
         .show(ctx, |ui| {
@@ -507,12 +626,198 @@ fn draw_editor_view(app: &mut UniLyricApp, ctx: &egui::Context) {
             app.ui.show_metadata_panel = false;
         }
     }
+
+    if app.ui.show_line_editor_window {
+        let mut window_is_open = true;
+
+        egui::Window::new("逐行编辑")
+            .open(&mut window_is_open)
+            .default_width(500.0)
+            .default_height(450.0)
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_line_editor_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.ui.show_line_editor_window = false;
+        }
+    }
+
+    if app.ui.show_timeline_window {
+        let mut window_is_open = true;
+
+        egui::Window::new("时间轴编辑")
+            .open(&mut window_is_open)
+            .default_width(900.0)
+            .default_height(450.0)
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_timeline_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.ui.show_timeline_window = false;
+            app.ui.timeline_drag = None;
+        }
+    }
+
+    if app.ui.show_karaoke_preview_window {
+        let mut window_is_open = true;
+
+        egui::Window::new("歌词预览")
+            .open(&mut window_is_open)
+            .default_width(600.0)
+            .default_height(400.0)
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_karaoke_preview_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.ui.show_karaoke_preview_window = false;
+            app.ui.karaoke_preview.pause();
+        }
+    }
+
+    if app.ui.show_diff_window {
+        let mut window_is_open = true;
+
+        egui::Window::new("对比视图")
+            .open(&mut window_is_open)
+            .default_width(800.0)
+            .default_height(500.0)
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_diff_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.ui.show_diff_window = false;
+        }
+    }
+
+    if app.ui.show_local_cache_window {
+        let mut window_is_open = true;
+
+        egui::Window::new("本地缓存")
+            .open(&mut window_is_open)
+            .default_width(750.0)
+            .default_height(450.0)
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_local_cache_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.send_action(UserAction::UI(UIAction::HidePanel(PanelType::LocalCache)));
+        }
+    }
+
+    if app.ui.stats_report.is_some() {
+        let mut window_is_open = true;
+
+        egui::Window::new("歌词统计报告")
+            .open(&mut window_is_open)
+            .default_width(380.0)
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_stats_report_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.ui.stats_report = None;
+        }
+    }
+
+    if app.ui.show_lrc_merge_window {
+        let mut window_is_open = true;
+
+        egui::Window::new("合并 LRC 为 TTML")
+            .open(&mut window_is_open)
+            .default_width(450.0)
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_lrc_merge_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.send_action(UserAction::UI(UIAction::HidePanel(PanelType::LrcMerge)));
+        }
+    }
+
+    if app.ui.show_batch_download_window {
+        let mut window_is_open = true;
+
+        egui::Window::new("批量下载歌词")
+            .open(&mut window_is_open)
+            .default_width(520.0)
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_batch_download_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.send_action(UserAction::UI(UIAction::HidePanel(
+                PanelType::BatchDownload,
+            )));
+        }
+    }
+
+    if app.ui.show_lyric_image_window {
+        let mut window_is_open = true;
+
+        egui::Window::new("导出为图片")
+            .open(&mut window_is_open)
+            .default_width(360.0)
+            .resizable(false)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                app.draw_lyric_image_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.send_action(UserAction::UI(UIAction::HidePanel(PanelType::LyricImage)));
+        }
+    }
+
+    if app.ui.show_format_options_window {
+        let mut window_is_open = true;
+
+        egui::Window::new(format!("{} 选项", app.lyrics.target_format))
+            .id(egui::Id::new("format_options_window"))
+            .open(&mut window_is_open)
+            .default_width(400.0)
+            .resizable(true)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                app.draw_format_options_window_contents(ui);
+            });
+
+        if !window_is_open {
+            app.ui.show_format_options_window = false;
+        }
+    }
 }
 
 pub(super) fn handle_file_drops(app: &mut UniLyricApp, ctx: &egui::Context) {
     if !ctx.input(|i| i.raw.dropped_files.is_empty()) {
         let files = ctx.input(|i| i.raw.dropped_files.clone());
-        if let Some(file) = files.first() {
+        let paths: Vec<_> = files.iter().filter_map(|file| file.path.clone()).collect();
+        if paths.len() > 1 {
+            let documents = crate::io::read_lyric_files(app, paths);
+            app.send_action(crate::app_actions::UserAction::Lyrics(Box::new(
+                crate::app_actions::LyricsAction::OpenDocuments(documents),
+            )));
+        } else if let Some(file) = files.first() {
             if let Some(path) = &file.path {
                 crate::io::load_file_and_convert(app, path.clone());
             } else if let Some(bytes) = &file.bytes {
@@ -525,6 +830,7 @@ pub(super) fn handle_file_drops(app: &mut UniLyricApp, ctx: &egui::Context) {
                         crate::app_actions::LyricsAction::ClearAllData,
                     )));
                     app.lyrics.input_text = text_content;
+                    app.lyrics.mark_texts_changed();
                     app.lyrics.metadata_source_is_download = false;
                     app.send_action(crate::app_actions::UserAction::Lyrics(Box::new(
                         crate::app_actions::LyricsAction::Convert,
@@ -560,15 +866,32 @@ pub(super) fn handle_file_drops(app: &mut UniLyricApp, ctx: &egui::Context) {
 
 /// 处理来自歌词转换任务的结果。
 pub(super) fn handle_conversion_results(app: &mut UniLyricApp) {
-    if let Some(rx) = &app.lyrics.conversion_result_rx
-        && let Ok(result) = rx.try_recv()
-    {
-        app.lyrics.conversion_result_rx.take();
-
-        let converted_result = result.map_err(AppError::from);
-        app.send_action(crate::app_actions::UserAction::Lyrics(Box::new(
-            crate::app_actions::LyricsAction::ConvertCompleted(converted_result),
-        )));
+    let Some(rx) = &app.lyrics.conversion_result_rx else {
+        return;
+    };
+
+    match rx.try_recv() {
+        Ok(result) => {
+            app.lyrics.conversion_result_rx.take();
+
+            let converted_result = result.map_err(AppError::from);
+            app.send_action(crate::app_actions::UserAction::Lyrics(Box::new(
+                crate::app_actions::LyricsAction::ConvertCompleted(converted_result),
+            )));
+        }
+        // 转换仍在后台进行，保持刷新以便及时取回结果并更新进度显示
+        Err(std::sync::mpsc::TryRecvError::Empty) => {
+            app.egui_ctx
+                .request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+            app.lyrics.conversion_result_rx.take();
+            app.send_action(crate::app_actions::UserAction::Lyrics(Box::new(
+                crate::app_actions::LyricsAction::ConvertCompleted(Err(AppError::Custom(
+                    "转换任务意外终止".to_string(),
+                ))),
+            )));
+        }
     }
 }
 
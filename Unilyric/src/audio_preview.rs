@@ -0,0 +1,75 @@
+//! 下载器中搜索结果的音频试听。
+//!
+//! 音频数据下载完成后在独立线程中解码播放，播放线程持有输出设备，
+//! 通过共享的停止标志结束播放。
+
+use std::io::Cursor;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use rodio::{Decoder, OutputStream, Sink, Source};
+use tracing::{info, warn};
+
+/// 试听片段的最大时长。
+pub const PREVIEW_DURATION: Duration = Duration::from_secs(30);
+/// 试听时最多下载的字节数，足够容纳 30 秒的高码率音频。
+pub const PREVIEW_MAX_BYTES: usize = 6 * 1024 * 1024;
+
+/// 当前正在试听的播放任务。
+#[derive(Debug, Clone, Default)]
+pub struct AudioPreviewPlayer {
+    stop_flag: Option<Arc<AtomicBool>>,
+    finished: Arc<AtomicBool>,
+}
+
+impl AudioPreviewPlayer {
+    /// 停止当前的试听（如果有），然后在新线程中播放给定的音频数据。
+    pub fn play(&mut self, audio_data: Vec<u8>) -> Result<(), String> {
+        self.stop();
+
+        let source = Decoder::new(Cursor::new(audio_data))
+            .map_err(|e| format!("无法解码试听音频: {e}"))?
+            .take_duration(PREVIEW_DURATION);
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let finished = Arc::new(AtomicBool::new(false));
+        self.stop_flag = Some(Arc::clone(&stop_flag));
+        self.finished = Arc::clone(&finished);
+
+        std::thread::Builder::new()
+            .name("audio-preview".to_string())
+            .spawn(move || {
+                match OutputStream::try_default() {
+                    Ok((_stream, handle)) => match Sink::try_new(&handle) {
+                        Ok(sink) => {
+                            sink.append(source);
+                            while !sink.empty() && !stop_flag.load(Ordering::Relaxed) {
+                                std::thread::sleep(Duration::from_millis(50));
+                            }
+                            sink.stop();
+                        }
+                        Err(e) => warn!("[AudioPreview] 创建播放队列失败: {e}"),
+                    },
+                    Err(e) => warn!("[AudioPreview] 打开音频输出设备失败: {e}"),
+                }
+                finished.store(true, Ordering::Relaxed);
+                info!("[AudioPreview] 试听结束。");
+            })
+            .map_err(|e| format!("无法启动试听线程: {e}"))?;
+
+        Ok(())
+    }
+
+    /// 停止当前的试听。
+    pub fn stop(&mut self) {
+        if let Some(stop_flag) = self.stop_flag.take() {
+            stop_flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// 是否有试听正在播放。
+    pub fn is_playing(&self) -> bool {
+        self.stop_flag.is_some() && !self.finished.load(Ordering::Relaxed)
+    }
+}
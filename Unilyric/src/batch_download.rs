@@ -0,0 +1,303 @@
+//! 按“标题 - 艺术家”列表批量下载歌词。
+//!
+//! 每行一首歌，依次搜索并自动选择最佳匹配，转换为目标格式后保存到输出目录。
+//! 匹配度过低的结果视为未找到，避免保存明显无关的歌词。
+
+use std::collections::HashSet;
+use std::hash::RandomState;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use lyrics_helper_core::model::track::Track;
+use lyrics_helper_core::{ConversionOptions, LyricFormat, MatchType};
+use lyrics_helper_rs::{LyricsHelper, LyricsHelperError, SearchMode};
+use tokio::sync::Mutex as TokioMutex;
+use tokio_util::sync::CancellationToken;
+
+/// 自动选择的最佳匹配至少需要达到的匹配度。
+const MIN_MATCH_TYPE: MatchType = MatchType::Medium;
+
+/// 一首歌的下载状态。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchDownloadStatus {
+    Pending,
+    Searching,
+    /// 已保存，记录文件名和歌词来源
+    Saved {
+        file_name: String,
+        provider: String,
+    },
+    /// 没有找到匹配度足够高的结果
+    NotFound,
+    Failed(String),
+}
+
+/// 批量下载列表中的一首歌。
+#[derive(Debug, Clone)]
+pub struct BatchDownloadEntry {
+    pub title: String,
+    pub artist: String,
+    /// 保存的文件名（不含扩展名），同一列表中不会重复
+    pub file_stem: String,
+    pub status: BatchDownloadStatus,
+}
+
+/// 由艺术家和标题生成文件名（不含扩展名），去掉了文件系统不允许的字符。
+fn file_stem(title: &str, artist: &str) -> String {
+    let name = if artist.is_empty() {
+        title.to_string()
+    } else {
+        format!("{artist} - {title}")
+    };
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim_end_matches(['.', ' '])
+        .to_string()
+}
+
+/// 批量下载窗口的状态。
+#[derive(Debug, Clone)]
+pub struct BatchDownloadState {
+    /// 每行一首歌，格式为“标题 - 艺术家”
+    pub queries_input: String,
+    pub output_dir: Option<PathBuf>,
+    pub target_format: LyricFormat,
+    pub entries: Vec<BatchDownloadEntry>,
+    /// 下载进行中时用于取消的令牌
+    pub cancellation_token: Option<CancellationToken>,
+}
+
+impl Default for BatchDownloadState {
+    fn default() -> Self {
+        Self {
+            queries_input: String::new(),
+            output_dir: None,
+            target_format: LyricFormat::Ttml,
+            entries: Vec::new(),
+            cancellation_token: None,
+        }
+    }
+}
+
+impl BatchDownloadState {
+    pub fn is_running(&self) -> bool {
+        self.cancellation_token.is_some()
+    }
+
+    /// 统计（已保存，未找到或失败）的数量。
+    pub fn summary(&self) -> (usize, usize) {
+        self.entries
+            .iter()
+            .fold((0, 0), |(saved, failed), entry| match entry.status {
+                BatchDownloadStatus::Saved { .. } => (saved + 1, failed),
+                BatchDownloadStatus::NotFound | BatchDownloadStatus::Failed(_) => {
+                    (saved, failed + 1)
+                }
+                BatchDownloadStatus::Pending | BatchDownloadStatus::Searching => (saved, failed),
+            })
+    }
+}
+
+/// 把输入解析为下载列表，忽略空行。
+///
+/// 按最后一个“ - ”分隔标题和艺术家，没有时整行作为标题。不带空格的“-”视为标题的一部分，
+/// 例如 “Anti-Hero”。同名的条目在文件名后追加 “ (2)”、“ (3)” 等序号，避免互相覆盖。
+pub fn parse_queries(input: &str) -> Vec<BatchDownloadEntry> {
+    let mut used_stems = HashSet::new();
+    input
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            let (title, artist) = line.rsplit_once(" - ").unwrap_or((line, ""));
+            let (title, artist) = (title.trim(), artist.trim());
+            if title.is_empty() {
+                return None;
+            }
+
+            let base_stem = file_stem(title, artist);
+            let mut stem = base_stem.clone();
+            // 按小写比较，大小写不敏感的文件系统上同样不会冲突
+            let mut counter = 2;
+            while !used_stems.insert(stem.to_lowercase()) {
+                stem = format!("{base_stem} ({counter})");
+                counter += 1;
+            }
+
+            Some(BatchDownloadEntry {
+                title: title.to_string(),
+                artist: artist.to_string(),
+                file_stem: stem,
+                status: BatchDownloadStatus::Pending,
+            })
+        })
+        .collect()
+}
+
+/// 一次批量下载共用的参数。
+pub struct BatchDownloadJob {
+    pub search_mode: SearchMode,
+    pub target_format: LyricFormat,
+    pub conversion_options: ConversionOptions,
+    pub output_dir: PathBuf,
+    pub output_text_options: crate::io::OutputTextOptions,
+}
+
+impl BatchDownloadJob {
+    /// 搜索一首歌并保存最佳匹配。被取消时返回 `None`。
+    pub async fn download(
+        &self,
+        helper: &Arc<TokioMutex<LyricsHelper>>,
+        entry: &BatchDownloadEntry,
+        cancellation_token: &CancellationToken,
+    ) -> Option<BatchDownloadStatus> {
+        let artists: Vec<&str> = entry
+            .artist
+            .split(['/', '、', ',', ';'])
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect();
+        let track = Track {
+            title: Some(&entry.title),
+            artists: (!artists.is_empty()).then_some(&artists[..]),
+            album: None,
+            duration: None,
+        };
+
+        let search_result = {
+            let future_res = {
+                let helper_guard = helper.lock().await;
+                helper_guard.search_lyrics_comprehensive(
+                    &track,
+                    &self.search_mode,
+                    Some(cancellation_token.clone()),
+                )
+            };
+            match future_res {
+                Ok(future) => future.await,
+                Err(e) => Err(e),
+            }
+        };
+
+        let lyrics_and_metadata = match search_result {
+            Ok(Some(result))
+                if result.primary_lyric_result.source_track.match_type >= MIN_MATCH_TYPE =>
+            {
+                result.primary_lyric_result
+            }
+            Ok(_) => return Some(BatchDownloadStatus::NotFound),
+            Err(LyricsHelperError::Cancelled) => return None,
+            Err(e) => return Some(BatchDownloadStatus::Failed(e.to_string())),
+        };
+
+        let output = match LyricsHelper::generate_lyrics_from_parsed::<RandomState>(
+            lyrics_and_metadata.lyrics.parsed,
+            self.target_format,
+            self.conversion_options.clone(),
+            None,
+        )
+        .await
+        {
+            Ok(result) => result.output_lyrics,
+            Err(e) => return Some(BatchDownloadStatus::Failed(format!("转换失败: {e}"))),
+        };
+
+        let file_name = format!(
+            "{}.{}",
+            entry.file_stem,
+            self.target_format.to_extension_str()
+        );
+        let path = self.output_dir.join(&file_name);
+        Some(
+            match crate::io::write_lyrics_text(&path, &output, self.output_text_options, None) {
+                Ok(()) => BatchDownloadStatus::Saved {
+                    file_name,
+                    provider: lyrics_and_metadata.source_track.provider_name,
+                },
+                Err(e) => BatchDownloadStatus::Failed(format!("写入文件失败: {e}")),
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title_artist(entries: &[BatchDownloadEntry]) -> Vec<(&str, &str)> {
+        entries
+            .iter()
+            .map(|entry| (entry.title.as_str(), entry.artist.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_queries_splits_on_spaced_separator_only() {
+        let entries = parse_queries("Anti-Hero - Taylor Swift\n\n  晴天  \nAnti-Hero\n");
+        assert_eq!(
+            title_artist(&entries),
+            vec![
+                ("Anti-Hero", "Taylor Swift"),
+                ("晴天", ""),
+                ("Anti-Hero", "")
+            ]
+        );
+        assert!(
+            entries
+                .iter()
+                .all(|entry| entry.status == BatchDownloadStatus::Pending)
+        );
+    }
+
+    #[test]
+    fn test_parse_queries_uses_last_separator() {
+        let entries = parse_queries("Song - Live Version - Artist");
+        assert_eq!(
+            title_artist(&entries),
+            vec![("Song - Live Version", "Artist")]
+        );
+    }
+
+    #[test]
+    fn test_file_stem_replaces_reserved_characters() {
+        assert_eq!(file_stem("What?", "AC/DC"), "AC_DC - What_");
+        assert_eq!(file_stem("Title. ", ""), "Title");
+        assert_eq!(file_stem("a\tb", ""), "a_b");
+    }
+
+    #[test]
+    fn test_parse_queries_deduplicates_file_stems() {
+        let entries = parse_queries("晴天 - 周杰伦\n晴天 - 周杰伦\nwhat? - x\nWHAT_ - X");
+        let stems: Vec<&str> = entries.iter().map(|e| e.file_stem.as_str()).collect();
+        assert_eq!(
+            stems,
+            vec![
+                "周杰伦 - 晴天",
+                "周杰伦 - 晴天 (2)",
+                "x - what_",
+                "X - WHAT_ (2)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summary_counts_saved_and_failed() {
+        let mut state = BatchDownloadState {
+            entries: parse_queries("a\nb\nc\nd\ne"),
+            ..Default::default()
+        };
+        state.entries[0].status = BatchDownloadStatus::Saved {
+            file_name: "a.ttml".to_string(),
+            provider: "qq".to_string(),
+        };
+        state.entries[1].status = BatchDownloadStatus::NotFound;
+        state.entries[2].status = BatchDownloadStatus::Failed("网络错误".to_string());
+        state.entries[3].status = BatchDownloadStatus::Searching;
+
+        assert_eq!(state.summary(), (1, 2));
+    }
+}
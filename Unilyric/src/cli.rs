@@ -0,0 +1,452 @@
+//! 命令行无头模式。
+//!
+//! 以 `-` 开头的参数启动时不打开界面，而是按参数执行一次批量转换后退出，便于集成到脚本中。
+//! 未通过参数指定的选项沿用界面中保存的设置。
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use lyrics_helper_core::{
+    BatchEntryStatus, ChineseConversionConfig, ChineseConversionMode, ChineseConversionOptions,
+    LyricFormat, MetadataStripperFlags,
+};
+use lyrics_helper_rs::converter::processors::batch_processor;
+
+use crate::app_actions::ProcessorType;
+use crate::app_settings::AppSettings;
+
+pub const USAGE: &str = "\
+用法: Unilyric [选项]
+
+不带参数时启动图形界面。指定 --input 时以无头模式执行批量转换后退出。
+
+选项:
+  -i, --input <目录>         输入目录（必填）
+  -o, --output <目录>        输出目录（必填）
+  -f, --format <格式>        目标格式，如 ttml、lrc、ass（默认 ttml）
+      --extensions <列表>    主歌词文件的扩展名白名单，逗号分隔，如 lrc,qrc
+      --strip-metadata       启用元数据清理
+      --no-strip-metadata    禁用元数据清理
+      --agent-recognizer     应用演唱者识别
+      --no-agent-recognizer  不应用演唱者识别
+      --smoothing            应用音节平滑
+      --no-smoothing         不应用音节平滑
+      --chinese <配置>       简繁转换，如 s2t、t2s、s2tw
+      --chinese-translation <配置>
+                             只对翻译做简繁转换，配置同 --chinese
+      --dry-run              试运行，完成转换但不写入任何文件
+  -h, --help                 显示此帮助
+
+退出码: 0 全部成功，1 有任务失败，2 参数错误或无法扫描输入目录";
+
+/// 参数错误或无法开始转换时的退出码。
+pub const EXIT_USAGE_ERROR: u8 = 2;
+/// 有任务转换失败时的退出码。
+const EXIT_TASK_FAILED: u8 = 1;
+
+/// 命令行参数解析的结果。
+#[derive(Debug)]
+pub enum CliCommand {
+    /// 没有命令行选项，正常启动界面
+    Gui,
+    Help,
+    Batch(BatchCliArgs),
+}
+
+/// 无头批量转换的参数。
+#[derive(Debug)]
+pub struct BatchCliArgs {
+    pub input_dir: PathBuf,
+    pub output_dir: PathBuf,
+    pub target_format: LyricFormat,
+    /// 为 `None` 时使用设置中的扩展名白名单
+    pub extensions: Option<Vec<String>>,
+    /// 为 `None` 时使用设置中的元数据清理开关
+    pub strip_metadata: Option<bool>,
+    /// 为 `None` 时沿用设置中的批量后处理器是否包含演唱者识别
+    pub agent_recognizer: Option<bool>,
+    /// 为 `None` 时沿用设置中的批量后处理器是否包含音节平滑
+    pub smoothing: Option<bool>,
+    pub chinese_conversion: Option<ChineseConversionConfig>,
+    pub translation_chinese_conversion: Option<ChineseConversionConfig>,
+    pub dry_run: bool,
+}
+
+impl BatchCliArgs {
+    /// 在设置中的批量后处理器基础上应用命令行开关，按 [`ProcessorType::ALL`] 的顺序返回。
+    pub fn postprocessors(&self, configured: &[ProcessorType]) -> Vec<ProcessorType> {
+        ProcessorType::ALL
+            .into_iter()
+            .filter(|processor| {
+                let overridden = match processor {
+                    ProcessorType::AgentRecognizer => self.agent_recognizer,
+                    ProcessorType::SyllableSmoother => self.smoothing,
+                    _ => None,
+                };
+                overridden.unwrap_or_else(|| configured.contains(processor))
+            })
+            .collect()
+    }
+}
+
+/// 所有命令行选项。紧跟在需要值的选项后面的参数只有是这些选项之一时才视为缺少值，
+/// 因此以 `-` 开头的路径也可以作为值。
+const OPTION_NAMES: &[&str] = &[
+    "-h",
+    "--help",
+    "-i",
+    "--input",
+    "-o",
+    "--output",
+    "-f",
+    "--format",
+    "--extensions",
+    "--strip-metadata",
+    "--no-strip-metadata",
+    "--agent-recognizer",
+    "--no-agent-recognizer",
+    "--smoothing",
+    "--no-smoothing",
+    "--chinese",
+    "--chinese-translation",
+    "--dry-run",
+];
+
+/// 解析命令行参数（不含程序名）。
+///
+/// 第一个参数不以 `-` 开头时（例如通过“打开方式”传入的文件路径）视为普通启动。
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliCommand, String> {
+    let mut args = args.into_iter().peekable();
+    if !args.peek().is_some_and(|arg| arg.starts_with('-')) {
+        return Ok(CliCommand::Gui);
+    }
+
+    let mut input_dir = None;
+    let mut output_dir = None;
+    let mut target_format = LyricFormat::Ttml;
+    let mut extensions = None;
+    let mut strip_metadata = None;
+    let mut agent_recognizer = None;
+    let mut smoothing = None;
+    let mut chinese_conversion = None;
+    let mut translation_chinese_conversion = None;
+    let mut dry_run = false;
+
+    while let Some(arg) = args.next() {
+        let mut value_of = |name: &str| {
+            args.next()
+                .filter(|value| !OPTION_NAMES.contains(&value.as_str()))
+                .ok_or_else(|| format!("参数 {name} 需要一个值"))
+        };
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(CliCommand::Help),
+            "-i" | "--input" => input_dir = Some(PathBuf::from(value_of(&arg)?)),
+            "-o" | "--output" => output_dir = Some(PathBuf::from(value_of(&arg)?)),
+            "-f" | "--format" => {
+                let value = value_of(&arg)?;
+                target_format = LyricFormat::from_string(&value)
+                    .ok_or_else(|| format!("未知的目标格式: {value}"))?;
+            }
+            "--extensions" => {
+                extensions = Some(
+                    value_of(&arg)?
+                        .split(',')
+                        .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+                        .filter(|ext| !ext.is_empty())
+                        .collect(),
+                );
+            }
+            "--strip-metadata" => strip_metadata = Some(true),
+            "--no-strip-metadata" => strip_metadata = Some(false),
+            "--agent-recognizer" => agent_recognizer = Some(true),
+            "--no-agent-recognizer" => agent_recognizer = Some(false),
+            "--smoothing" => smoothing = Some(true),
+            "--no-smoothing" => smoothing = Some(false),
+            "--chinese" => {
+                let value = value_of(&arg)?;
+                chinese_conversion = Some(
+                    parse_chinese_conversion(&value)
+                        .ok_or_else(|| format!("未知的简繁转换配置: {value}"))?,
+                );
+            }
+            "--chinese-translation" => {
+                let value = value_of(&arg)?;
+                translation_chinese_conversion = Some(
+                    parse_chinese_conversion(&value)
+                        .ok_or_else(|| format!("未知的简繁转换配置: {value}"))?,
+                );
+            }
+            "--dry-run" => dry_run = true,
+            other => return Err(format!("未知参数: {other}")),
+        }
+    }
+
+    Ok(CliCommand::Batch(BatchCliArgs {
+        input_dir: input_dir.ok_or("缺少输入目录 (--input)")?,
+        output_dir: output_dir.ok_or("缺少输出目录 (--output)")?,
+        target_format,
+        extensions,
+        strip_metadata,
+        agent_recognizer,
+        smoothing,
+        chinese_conversion,
+        translation_chinese_conversion,
+        dry_run,
+    }))
+}
+
+fn parse_chinese_conversion(value: &str) -> Option<ChineseConversionConfig> {
+    use ChineseConversionConfig::*;
+    Some(match value.to_ascii_lowercase().as_str() {
+        "s2t" => S2t,
+        "t2s" => T2s,
+        "s2tw" => S2tw,
+        "tw2s" => Tw2s,
+        "s2hk" => S2hk,
+        "hk2s" => Hk2s,
+        "s2twp" => S2twp,
+        "tw2sp" => Tw2sp,
+        "t2tw" => T2tw,
+        "tw2t" => Tw2t,
+        "t2hk" => T2hk,
+        "hk2t" => Hk2t,
+        "jp2t" => Jp2t,
+        "t2jp" => T2jp,
+        _ => return None,
+    })
+}
+
+/// 把标准输出和标准错误连接到启动本程序的控制台。
+///
+/// 发布版在 Windows 上使用 GUI 子系统，不会自动获得控制台，从命令行启动时输出会丢失。
+/// 没有父控制台（例如双击启动）时调用会失败，此时什么也不做。
+#[cfg(windows)]
+pub fn attach_parent_console() {
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    // SAFETY: AttachConsole 只接受一个进程 ID，失败时返回 0，不涉及任何指针。
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+pub fn attach_parent_console() {}
+
+/// 执行批量转换，在标准输出中逐个报告结果，并返回反映成败的退出码。
+pub fn run_batch(args: &BatchCliArgs, settings: &AppSettings) -> ExitCode {
+    let whitelist = args
+        .extensions
+        .clone()
+        .unwrap_or_else(|| settings.batch_main_extension_whitelist.clone());
+
+    let pairing = settings.batch_pairing_options();
+
+    let file_groups =
+        match batch_processor::discover_and_pair_files(&args.input_dir, &whitelist, &pairing) {
+            Ok(groups) => groups,
+            Err(e) => {
+                eprintln!("扫描输入目录 {:?} 失败: {e}", args.input_dir);
+                return ExitCode::from(EXIT_USAGE_ERROR);
+            }
+        };
+    let (mut tasks, file_lookup) =
+        batch_processor::create_batch_tasks(file_groups, args.target_format);
+    if tasks.is_empty() {
+        println!("输入目录中没有可转换的歌词文件。");
+        return ExitCode::SUCCESS;
+    }
+
+    let mut options = settings.conversion_options();
+    if let Some(strip) = args.strip_metadata {
+        options
+            .metadata_stripper
+            .flags
+            .set(MetadataStripperFlags::ENABLED, strip);
+    }
+    if args.chinese_conversion.is_some() || args.translation_chinese_conversion.is_some() {
+        options.chinese_conversion = ChineseConversionOptions {
+            config: args.chinese_conversion,
+            mode: ChineseConversionMode::Replace,
+            translation_config: args.translation_chinese_conversion,
+            custom_dictionary: settings.load_chinese_conversion_dictionary(),
+            ..Default::default()
+        };
+    }
+    let postprocessors = args.postprocessors(&settings.batch_postprocessors);
+
+    println!(
+        "开始{}转换 {} 个文件: {:?} -> {:?} ({})",
+        if args.dry_run { "试运行" } else { "" },
+        tasks.len(),
+        args.input_dir,
+        args.output_dir,
+        args.target_format
+    );
+
+    if let Err(e) = batch_processor::execute_batch_conversion_pausable(
+        &mut tasks,
+        &file_lookup,
+        &args.output_dir,
+        &options,
+        args.dry_run,
+        crate::io::read_text_file,
+        || false,
+        |parsed_data| {
+            crate::app_handlers::apply_processor_chain(parsed_data, &postprocessors, settings)
+        },
+        |_| {},
+    ) {
+        eprintln!("批量转换失败: {e}");
+        return ExitCode::from(EXIT_USAGE_ERROR);
+    }
+
+    let mut succeeded = 0;
+    let mut failed = 0;
+    for task in &tasks {
+        match &task.status {
+            BatchEntryStatus::Completed {
+                output_path,
+                warnings,
+            } => {
+                succeeded += 1;
+                println!("[成功] {}", output_path.display());
+                for warning in warnings {
+                    println!("    警告: {warning}");
+                }
+            }
+            BatchEntryStatus::DryRun {
+                output_path,
+                output_size,
+                overwrites_existing,
+                warnings,
+            } => {
+                succeeded += 1;
+                println!(
+                    "[试运行] {} ({output_size} 字节{})",
+                    output_path.display(),
+                    if *overwrites_existing {
+                        "，将覆盖已有文件"
+                    } else {
+                        ""
+                    }
+                );
+                for warning in warnings {
+                    println!("    警告: {warning}");
+                }
+            }
+            BatchEntryStatus::Failed(e) => {
+                failed += 1;
+                eprintln!("[失败] {}: {e}", task.output_filename_preview);
+            }
+            _ => {}
+        }
+    }
+
+    println!("完成: 成功 {succeeded} 个，失败 {failed} 个。");
+    if failed > 0 {
+        ExitCode::from(EXIT_TASK_FAILED)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliCommand, String> {
+        parse_args(args.iter().map(ToString::to_string))
+    }
+
+    fn parse_batch(args: &[&str]) -> BatchCliArgs {
+        match parse(args) {
+            Ok(CliCommand::Batch(batch)) => batch,
+            other => panic!("应解析为批量转换: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_gui_and_help() {
+        assert!(matches!(parse(&[]), Ok(CliCommand::Gui)));
+        assert!(matches!(parse(&["song.lrc"]), Ok(CliCommand::Gui)));
+        assert!(matches!(parse(&["-h"]), Ok(CliCommand::Help)));
+        assert!(matches!(
+            parse(&["-i", "in", "--help"]),
+            Ok(CliCommand::Help)
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_batch() {
+        let args = parse_batch(&[
+            "-i",
+            "in",
+            "--output",
+            "-out",
+            "-f",
+            "lrc",
+            "--extensions",
+            ".LRC, qrc",
+            "--no-strip-metadata",
+            "--agent-recognizer",
+            "--no-smoothing",
+            "--chinese",
+            "S2T",
+            "--dry-run",
+        ]);
+
+        assert_eq!(args.input_dir, PathBuf::from("in"));
+        assert_eq!(args.output_dir, PathBuf::from("-out"));
+        assert_eq!(args.target_format, LyricFormat::Lrc);
+        assert_eq!(
+            args.extensions,
+            Some(vec!["lrc".to_string(), "qrc".to_string()])
+        );
+        assert_eq!(args.strip_metadata, Some(false));
+        assert_eq!(args.agent_recognizer, Some(true));
+        assert_eq!(args.smoothing, Some(false));
+        assert_eq!(args.chinese_conversion, Some(ChineseConversionConfig::S2t));
+        assert_eq!(args.translation_chinese_conversion, None);
+        assert!(args.dry_run);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse(&["-o", "out"]).is_err());
+        assert!(parse(&["-i", "in"]).is_err());
+        assert!(parse(&["-i", "--output", "out"]).is_err());
+        assert!(parse(&["-i", "in", "-o", "out", "-f", "unknown"]).is_err());
+        assert!(parse(&["-i", "in", "-o", "out", "--chinese", "x2y"]).is_err());
+        assert!(parse(&["-i", "in", "-o", "out", "--bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_postprocessor_switches_override_settings() {
+        let args = parse_batch(&[
+            "-i",
+            "in",
+            "-o",
+            "out",
+            "--no-agent-recognizer",
+            "--smoothing",
+        ]);
+        let configured = [
+            ProcessorType::AgentRecognizer,
+            ProcessorType::MetadataStripper,
+        ];
+
+        assert_eq!(
+            args.postprocessors(&configured),
+            vec![
+                ProcessorType::MetadataStripper,
+                ProcessorType::SyllableSmoother
+            ]
+        );
+    }
+}
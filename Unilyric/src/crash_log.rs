@@ -0,0 +1,137 @@
+//! 最近日志的环形缓冲与崩溃转储。
+//!
+//! 所有日志条目都会写入一个全局的环形缓冲区，并定期持久化到磁盘，
+//! 这样即使程序崩溃，也能看到崩溃前的日志。
+//! 发生 panic 时，panic hook 会把 panic 信息、堆栈和最近的日志写入一个单独的 crash 文件。
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::types::LogEntry;
+use crate::utils;
+
+/// 环形缓冲区保留的最大日志条数。
+const RING_CAPACITY: usize = 500;
+/// 两次持久化之间的最小间隔。
+const PERSIST_INTERVAL: Duration = Duration::from_secs(5);
+const RECENT_LOG_FILE_NAME: &str = "recent_logs.log";
+const CRASH_DIR_NAME: &str = "crash_reports";
+
+struct RingState {
+    lines: VecDeque<String>,
+    dirty: bool,
+    last_persist: Option<Instant>,
+}
+
+static RING: LazyLock<Mutex<RingState>> = LazyLock::new(|| {
+    Mutex::new(RingState {
+        lines: VecDeque::with_capacity(RING_CAPACITY),
+        dirty: false,
+        last_persist: None,
+    })
+});
+
+fn format_entry(entry: &LogEntry) -> String {
+    format!(
+        "{} [{}] {}",
+        entry.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+        entry.level.as_str(),
+        entry.message
+    )
+}
+
+/// 将一条日志追加到环形缓冲区。缓冲区满时丢弃最旧的条目。
+pub fn record(entry: &LogEntry) {
+    let Ok(mut ring) = RING.lock() else {
+        return;
+    };
+    if ring.lines.len() >= RING_CAPACITY {
+        ring.lines.pop_front();
+    }
+    ring.lines.push_back(format_entry(entry));
+    ring.dirty = true;
+}
+
+/// 最近日志文件的路径。
+///
+/// 必须在获取缓冲区锁之前初始化：获取路径失败时会记录日志，
+/// 而记录日志需要再次获取同一把锁。
+static RECENT_LOG_PATH: LazyLock<Option<PathBuf>> =
+    LazyLock::new(|| utils::get_app_data_dir().map(|dir| dir.join(RECENT_LOG_FILE_NAME)));
+
+fn write_ring(ring: &mut RingState, path: &Path) {
+    let content = ring.lines.iter().fold(String::new(), |mut acc, line| {
+        acc.push_str(line);
+        acc.push('\n');
+        acc
+    });
+    if let Err(e) = std::fs::write(path, content) {
+        // 这里不能使用 tracing，否则会产生新的日志条目并再次进入缓冲区
+        eprintln!("写入最近日志文件 {path:?} 失败: {e}");
+    }
+    ring.dirty = false;
+    ring.last_persist = Some(Instant::now());
+}
+
+/// 如果缓冲区有新内容，且距上次持久化已超过间隔（或 `force` 为 `true`），则写入磁盘。
+pub fn persist(force: bool) {
+    let Some(path) = RECENT_LOG_PATH.as_deref() else {
+        return;
+    };
+    let Ok(mut ring) = RING.lock() else {
+        return;
+    };
+    let due = ring
+        .last_persist
+        .is_none_or(|last| last.elapsed() >= PERSIST_INTERVAL);
+    if ring.dirty && (force || due) {
+        write_ring(&mut ring, path);
+    }
+}
+
+/// 安装 panic hook，在崩溃时将 panic 信息、堆栈和最近的日志写入 crash 文件。
+///
+/// 原有的 hook 仍会在之后被调用。
+pub fn install_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        let thread = std::thread::current();
+
+        let mut report = format!(
+            "UniLyric {} 崩溃报告\n时间: {}\n线程: {}\n\n{info}\n\n堆栈:\n{backtrace}\n\n最近的日志:\n",
+            env!("CARGO_PKG_VERSION"),
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            thread.name().unwrap_or("<unnamed>"),
+        );
+
+        let recent_path = RECENT_LOG_PATH.as_deref();
+        // 崩溃的线程可能正持有锁，因此只尝试获取，避免死锁
+        if let Ok(mut ring) = RING.try_lock() {
+            for line in &ring.lines {
+                report.push_str(line);
+                report.push('\n');
+            }
+            if let Some(path) = recent_path {
+                write_ring(&mut ring, path);
+            }
+        } else {
+            report.push_str("<日志缓冲区不可用>\n");
+        }
+
+        if let Some(dir) = utils::get_app_data_dir().map(|dir| dir.join(CRASH_DIR_NAME)) {
+            let path = dir.join(format!(
+                "crash-{}.log",
+                chrono::Local::now().format("%Y%m%d-%H%M%S")
+            ));
+            match std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, &report)) {
+                Ok(()) => eprintln!("崩溃报告已写入 {path:?}"),
+                Err(e) => eprintln!("写入崩溃报告 {path:?} 失败: {e}"),
+            }
+        }
+
+        previous_hook(info);
+    }));
+}
@@ -1,12 +1,122 @@
+use crate::action_script::ActionScript;
 use crate::app_actions::UserAction;
 use crate::app_definition::UniLyricApp;
-use crate::types::LrcContentType;
+use crate::types::{EncodingPrompt, LrcContentType};
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+use lyrics_helper_core::LineEnding;
 use lyrics_helper_rs::{
     providers::kugou::decrypter::decrypt_krc_from_bytes,
     providers::qq::qrc_codec::{decrypt_qrc, decrypt_qrc_local},
 };
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// UTF-8 字节顺序标记。
+const UTF8_BOM: &str = "\u{feff}";
+
+/// 编码检测不可靠时供用户手动选择的编码。
+pub const SELECTABLE_ENCODINGS: &[&Encoding] = &[
+    encoding_rs::UTF_8,
+    encoding_rs::GB18030,
+    encoding_rs::GBK,
+    encoding_rs::BIG5,
+    encoding_rs::SHIFT_JIS,
+    encoding_rs::EUC_JP,
+    encoding_rs::EUC_KR,
+    encoding_rs::UTF_16LE,
+    encoding_rs::UTF_16BE,
+    encoding_rs::WINDOWS_1252,
+];
+
+/// 文本文件的解码结果。
+pub struct DecodedText {
+    pub content: String,
+    pub encoding: &'static Encoding,
+    /// 检测结果不确定，或解码时遇到无法映射的字节时为 `false`
+    pub reliable: bool,
+}
+
+/// 检测文本的编码并解码为字符串。
+///
+/// 依次根据 BOM、UTF-8 有效性和字符统计判断编码，适用于 GBK、Shift-JIS 等编码的旧 LRC 文件。
+pub fn decode_text(bytes: &[u8]) -> DecodedText {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        let (content, had_errors) = encoding.decode_with_bom_removal(bytes);
+        return DecodedText {
+            content: content.into_owned(),
+            encoding,
+            reliable: !had_errors,
+        };
+    }
+    if let Ok(content) = std::str::from_utf8(bytes) {
+        return DecodedText {
+            content: content.to_string(),
+            encoding: encoding_rs::UTF_8,
+            reliable: true,
+        };
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    let (encoding, confident) = detector.guess_assess(None, true);
+    let (content, had_errors) = encoding.decode_without_bom_handling(bytes);
+    DecodedText {
+        content: content.into_owned(),
+        encoding,
+        reliable: confident && !had_errors,
+    }
+}
+
+/// 按指定的编码重新读取文本文件。
+pub fn read_text_file_with_encoding(
+    path: &Path,
+    encoding: &'static Encoding,
+) -> std::io::Result<String> {
+    let bytes = fs::read(path)?;
+    Ok(encoding.decode_with_bom_removal(&bytes).0.into_owned())
+}
+
+/// 读取文本文件并自动识别编码，用于批量转换等无法逐个询问编码的场景。
+pub fn read_text_file(path: &Path) -> std::io::Result<String> {
+    fs::read(path).map(|bytes| decode_text(&bytes).content)
+}
+
+/// 编码检测不可靠时记录下来，让用户手动选择编码重新读取。
+fn prompt_encoding_if_unreliable(
+    app: &mut UniLyricApp,
+    path: &Path,
+    target: Option<LrcContentType>,
+    decoded: &DecodedText,
+) {
+    if decoded.reliable {
+        return;
+    }
+    tracing::warn!(
+        "[IO] 无法可靠地识别文件 {path:?} 的编码，暂按 {} 读取。",
+        decoded.encoding.name()
+    );
+    if app.ui.encoding_prompt.is_none() {
+        app.ui.encoding_prompt = Some(EncodingPrompt {
+            path: path.to_path_buf(),
+            target,
+            detected: decoded.encoding,
+            selected: decoded.encoding,
+        });
+    }
+}
+
+/// 读取多个歌词文件，返回成功读取的（路径，内容）。
+pub fn read_lyric_files(app: &mut UniLyricApp, paths: Vec<PathBuf>) -> Vec<(PathBuf, String)> {
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let decoded = read_lyric_file(&path)?;
+            prompt_encoding_if_unreliable(app, &path, None, &decoded);
+            Some((path, decoded.content))
+        })
+        .collect()
+}
 
 /// 处理打开主歌词文件的逻辑。
 pub fn handle_open_file(app: &mut UniLyricApp) {
@@ -15,6 +125,51 @@ pub fn handle_open_file(app: &mut UniLyricApp) {
     }
 }
 
+/// 处理一次打开多个歌词文件的逻辑。
+pub fn handle_open_multiple_files(app: &mut UniLyricApp) {
+    let Some(paths) = rfd::FileDialog::new().pick_files() else {
+        return;
+    };
+
+    let files = read_lyric_files(app, paths);
+    if !files.is_empty() {
+        app.send_action(UserAction::Lyrics(Box::new(
+            crate::app_actions::LyricsAction::OpenDocuments(files),
+        )));
+    }
+}
+
+/// 保存输出文件时的文本格式选项。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutputTextOptions {
+    /// 在开头加上 UTF-8 BOM（内容已带 BOM 时不重复添加）
+    pub add_bom: bool,
+    pub line_ending: LineEnding,
+}
+
+/// 写入歌词文本。换行符为“保持”时跟随 `source`（通常是输入的歌词）。
+pub fn write_lyrics_text(
+    path: &Path,
+    content: &str,
+    options: OutputTextOptions,
+    source: Option<&str>,
+) -> std::io::Result<()> {
+    let content = options.line_ending.apply(content, source);
+    if options.add_bom && !content.starts_with(UTF8_BOM) {
+        fs::write(path, format!("{UTF8_BOM}{content}"))
+    } else {
+        fs::write(path, content)
+    }
+}
+
+/// 当前设置下保存输出使用的文本格式选项。
+fn output_text_options(app: &UniLyricApp) -> OutputTextOptions {
+    app.app_settings
+        .lock()
+        .map(|settings| settings.output_text_options())
+        .unwrap_or_default()
+}
+
 /// 处理保存输出文件的逻辑。
 pub fn handle_save_file(app: &mut UniLyricApp) {
     if let Some(path) = rfd::FileDialog::new()
@@ -28,10 +183,16 @@ pub fn handle_save_file(app: &mut UniLyricApp) {
         )
         .save_file()
     {
-        if let Err(e) = fs::write(&path, &app.lyrics.output_text) {
+        if let Err(e) = write_lyrics_text(
+            &path,
+            &app.lyrics.output_text,
+            output_text_options(app),
+            Some(&app.lyrics.input_text),
+        ) {
             tracing::error!("保存文件 {path:?} 失败: {e}");
         } else {
             app.lyrics.last_saved_file_path = Some(path);
+            app.lyrics.mark_active_document_saved();
         }
     }
 }
@@ -42,8 +203,11 @@ pub fn handle_open_lrc_file(app: &mut UniLyricApp, content_type: LrcContentType)
         .add_filter("LRC File", &["lrc"])
         .pick_file()
     {
-        match fs::read_to_string(&path) {
-            Ok(content) => {
+        match fs::read(&path) {
+            Ok(bytes) => {
+                let decoded = decode_text(&bytes);
+                prompt_encoding_if_unreliable(app, &path, Some(content_type), &decoded);
+                let content = decoded.content;
                 match content_type {
                     LrcContentType::Translation => {
                         app.lyrics.display_translation_lrc_output = content;
@@ -65,9 +229,171 @@ pub fn handle_open_lrc_file(app: &mut UniLyricApp, content_type: LrcContentType)
     }
 }
 
+/// 处理将翻译或罗马音导出为独立 LRC 文件的逻辑。
+pub fn handle_export_lrc_file(app: &UniLyricApp, content: &str, content_type: LrcContentType) {
+    let stem = app
+        .lyrics
+        .last_opened_file_path
+        .as_ref()
+        .and_then(|path| path.file_stem())
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("lyrics");
+    let (suffix, label) = match content_type {
+        LrcContentType::Translation => ("translation", "翻译"),
+        LrcContentType::Romanization => ("romanization", "罗马音"),
+    };
+
+    if let Some(path) = rfd::FileDialog::new()
+        .set_file_name(format!("{stem}_{suffix}.lrc"))
+        .add_filter("LRC File", &["lrc"])
+        .save_file()
+    {
+        if let Err(e) = write_lyrics_text(
+            &path,
+            content,
+            output_text_options(app),
+            Some(&app.lyrics.input_text),
+        ) {
+            tracing::error!("导出LRC文件 {path:?} 失败: {e}");
+        } else {
+            tracing::info!("已导出{label}LRC文件: {path:?}");
+        }
+    }
+}
+
+/// 保存 LRC 合并向导生成的 TTML，保存成功后在编辑器中打开。
+pub fn handle_save_lrc_merge_result(app: &mut UniLyricApp) {
+    let Some((ttml, _)) = app.ui.lrc_merge.result.as_ref() else {
+        return;
+    };
+    let stem = app
+        .ui
+        .lrc_merge
+        .main_path
+        .as_ref()
+        .and_then(|path| path.file_stem())
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("lyrics");
+
+    if let Some(path) = rfd::FileDialog::new()
+        .set_file_name(format!("{stem}.ttml"))
+        .add_filter("TTML File", &["ttml"])
+        .save_file()
+    {
+        if let Err(e) = fs::write(&path, ttml) {
+            tracing::error!("保存合并结果 {path:?} 失败: {e}");
+            return;
+        }
+        tracing::info!("已保存合并结果: {path:?}");
+        app.ui.show_lrc_merge_window = false;
+        load_file_and_convert(app, path);
+    }
+}
+
+/// 将歌词统计报告保存为文本文件。
+pub fn handle_save_stats_report(app: &UniLyricApp) {
+    let Some(report) = app.ui.stats_report.as_ref() else {
+        return;
+    };
+    let stem = app
+        .lyrics
+        .last_opened_file_path
+        .as_ref()
+        .and_then(|path| path.file_stem())
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("lyrics");
+
+    if let Some(path) = rfd::FileDialog::new()
+        .set_file_name(format!("{stem}_stats.txt"))
+        .add_filter("Text File", &["txt"])
+        .save_file()
+    {
+        if let Err(e) = fs::write(&path, report) {
+            tracing::error!("保存统计报告 {path:?} 失败: {e}");
+        } else {
+            tracing::info!("已保存统计报告: {path:?}");
+        }
+    }
+}
+
+/// 选择歌词图片的保存路径。
+pub fn pick_lyric_image_path(app: &UniLyricApp) -> Option<PathBuf> {
+    let stem = app
+        .lyrics
+        .last_opened_file_path
+        .as_ref()
+        .and_then(|path| path.file_stem())
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("lyrics");
+
+    rfd::FileDialog::new()
+        .set_file_name(format!("{stem}.png"))
+        .add_filter("PNG Image", &["png"])
+        .save_file()
+}
+
+/// 将录制的操作保存为 JSON 脚本文件。
+pub fn handle_save_action_script(app: &UniLyricApp) {
+    let script = ActionScript {
+        steps: app.ui.action_script.recorded.clone(),
+    };
+    if script.steps.is_empty() {
+        tracing::warn!("[ActionScript] 没有录制任何步骤，跳过保存。");
+        return;
+    }
+
+    if let Some(path) = rfd::FileDialog::new()
+        .set_file_name("unilyric_script.json")
+        .add_filter("JSON", &["json"])
+        .save_file()
+    {
+        let result = serde_json::to_string_pretty(&script)
+            .map_err(std::io::Error::other)
+            .and_then(|json| fs::write(&path, json));
+        match result {
+            Ok(()) => tracing::info!("[ActionScript] 已保存操作脚本: {path:?}"),
+            Err(e) => tracing::error!("[ActionScript] 保存操作脚本 {path:?} 失败: {e}"),
+        }
+    }
+}
+
+/// 选择一个操作脚本文件，将其中的步骤加入重放队列。用户取消选择时不做任何事。
+pub fn handle_run_action_script(app: &mut UniLyricApp) -> Result<(), String> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .pick_file()
+    else {
+        return Ok(());
+    };
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("读取脚本文件失败: {e}"))?;
+    let script: ActionScript =
+        serde_json::from_str(&content).map_err(|e| format!("解析脚本文件失败: {e}"))?;
+    if script.steps.is_empty() {
+        return Err("脚本中没有任何步骤".to_string());
+    }
+
+    tracing::info!(
+        "[ActionScript] 开始运行脚本 {path:?}，共 {} 个步骤。",
+        script.steps.len()
+    );
+    app.ui.action_script.pending = script.steps.into();
+    Ok(())
+}
+
 /// 从路径加载文件并触发转换。
 pub fn load_file_and_convert(app: &mut UniLyricApp, path: PathBuf) {
-    match fs::read(&path) {
+    if let Some(decoded) = read_lyric_file(&path) {
+        prompt_encoding_if_unreliable(app, &path, None, &decoded);
+        app.send_action(UserAction::Lyrics(Box::new(
+            crate::app_actions::LyricsAction::LoadFileContent(decoded.content, path),
+        )));
+    }
+}
+
+/// 读取歌词文件，对加密的 KRC 和 QRC 文件先进行解密，其余文件自动检测编码。读取失败时返回 `None`。
+pub fn read_lyric_file(path: &Path) -> Option<DecodedText> {
+    match fs::read(path) {
         Ok(bytes) => {
             let mut final_content: Option<String> = None;
             let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
@@ -131,15 +457,85 @@ pub fn load_file_and_convert(app: &mut UniLyricApp, path: PathBuf) {
                 _ => {}
             }
 
-            let content_to_load =
-                final_content.unwrap_or_else(|| String::from_utf8_lossy(&bytes).to_string());
-
-            app.send_action(UserAction::Lyrics(Box::new(
-                crate::app_actions::LyricsAction::LoadFileContent(content_to_load, path),
-            )));
+            Some(match final_content {
+                Some(content) => DecodedText {
+                    content,
+                    encoding: encoding_rs::UTF_8,
+                    reliable: true,
+                },
+                None => decode_text(&bytes),
+            })
         }
         Err(e) => {
             tracing::error!("无法读取文件 {:?}: {}", path, e);
+            None
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LYRIC: &str = "[00:01.00]春よ、遠き春よ\n[00:05.00]瞼閉じればそこに\n";
+
+    #[test]
+    fn test_decode_text_utf8() {
+        let decoded = decode_text(LYRIC.as_bytes());
+        assert_eq!(decoded.content, LYRIC);
+        assert_eq!(decoded.encoding, encoding_rs::UTF_8);
+        assert!(decoded.reliable);
+    }
+
+    #[test]
+    fn test_decode_text_strips_bom() {
+        let with_bom = format!("{UTF8_BOM}{LYRIC}");
+        let decoded = decode_text(with_bom.as_bytes());
+        assert_eq!(decoded.content, LYRIC);
+        assert_eq!(decoded.encoding, encoding_rs::UTF_8);
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        utf16.extend(LYRIC.encode_utf16().flat_map(u16::to_le_bytes));
+        let decoded = decode_text(&utf16);
+        assert_eq!(decoded.content, LYRIC);
+        assert_eq!(decoded.encoding, encoding_rs::UTF_16LE);
+        assert!(decoded.reliable);
+    }
+
+    #[test]
+    fn test_decode_text_detects_legacy_encodings() {
+        let (shift_jis, _, _) = encoding_rs::SHIFT_JIS.encode(LYRIC);
+        let decoded = decode_text(&shift_jis);
+        assert_eq!(decoded.encoding, encoding_rs::SHIFT_JIS);
+        assert_eq!(decoded.content, LYRIC);
+
+        let chinese = "[00:01.00]明月几时有\n[00:05.00]把酒问青天\n[00:09.00]不知天上宫阙\n";
+        let (gbk, _, _) = encoding_rs::GBK.encode(chinese);
+        let decoded = decode_text(&gbk);
+        assert_eq!(decoded.encoding, encoding_rs::GBK);
+        assert_eq!(decoded.content, chinese);
+    }
+
+    #[test]
+    fn test_read_text_files() {
+        let dir = std::env::temp_dir().join(format!("unilyric_io_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("legacy.lrc");
+        let (shift_jis, _, _) = encoding_rs::SHIFT_JIS.encode(LYRIC);
+        fs::write(&path, &shift_jis).unwrap();
+
+        assert_eq!(read_text_file(&path).unwrap(), LYRIC);
+        assert_eq!(
+            read_text_file_with_encoding(&path, encoding_rs::SHIFT_JIS).unwrap(),
+            LYRIC
+        );
+        assert_ne!(
+            read_text_file_with_encoding(&path, encoding_rs::GBK).unwrap(),
+            LYRIC
+        );
+        assert!(read_text_file(&dir.join("missing.lrc")).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
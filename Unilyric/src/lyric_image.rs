@@ -0,0 +1,355 @@
+//! 把歌词渲染为便于分享的长图。
+//!
+//! 使用内置字体把文字逐字绘制到 RGBA 画布上，按图片宽度自动换行，
+//! 可以在顶部加入封面以及歌名和艺术家。
+
+use ab_glyph::{Font, FontRef, PxScale, PxScaleFont, ScaleFont, point};
+use image::{Rgba, RgbaImage, imageops::FilterType};
+use serde::{Deserialize, Serialize};
+
+/// 内置的界面字体，同时用于渲染歌词图片。
+pub const BUNDLED_FONT: &[u8] = include_bytes!("../assets/fonts/SarasaUiSC-Regular.ttf");
+
+/// 图片的最小宽度（像素）。
+const MIN_WIDTH: u32 = 320;
+/// 左右和上下的留白占图片宽度的比例。
+const PADDING_RATIO: f32 = 0.08;
+/// 封面边长占图片宽度的比例。
+const COVER_RATIO: f32 = 0.4;
+/// 行距相对字体高度的倍数。
+const LINE_SPACING: f32 = 1.25;
+/// 翻译、罗马音等次要文字相对主歌词的字号比例。
+const SECONDARY_SCALE: f32 = 0.7;
+/// 次要文字的不透明度。
+const SECONDARY_ALPHA: u8 = 170;
+
+/// 歌词图片的导出选项。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LyricImageOptions {
+    /// 图片宽度（像素），高度随内容自动增长
+    pub width: u32,
+    /// 主歌词的字号（像素）
+    pub font_size: f32,
+    pub include_translation: bool,
+    pub include_romanization: bool,
+    /// 在顶部绘制当前播放歌曲的封面
+    pub include_cover: bool,
+    /// 在顶部绘制歌名和艺术家
+    pub include_metadata: bool,
+    /// 背景色 (sRGB)
+    pub background_color: [u8; 3],
+    /// 文字颜色 (sRGB)
+    pub text_color: [u8; 3],
+}
+
+impl Default for LyricImageOptions {
+    fn default() -> Self {
+        Self {
+            width: 1080,
+            font_size: 44.0,
+            include_translation: true,
+            include_romanization: false,
+            include_cover: true,
+            include_metadata: true,
+            background_color: [30, 30, 36],
+            text_color: [240, 240, 240],
+        }
+    }
+}
+
+/// 图片中的一行歌词。
+#[derive(Debug, Clone, Default)]
+pub struct LyricImageLine {
+    pub main: String,
+    pub translation: Option<String>,
+    pub romanization: Option<String>,
+}
+
+/// 要渲染成图片的内容。
+#[derive(Debug, Clone, Default)]
+pub struct LyricImageContent {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    /// 封面图片的原始字节
+    pub cover: Option<Vec<u8>>,
+    pub lines: Vec<LyricImageLine>,
+}
+
+/// 判断正在播放的歌曲与歌词是否为同一首歌，参数均为 (歌名, 艺术家)，忽略大小写和首尾空白。
+///
+/// 歌名必须一致。双方都有艺术家时，一方包含另一方即可，以兼容多位艺术家的不同连接方式。
+pub fn is_same_song(
+    lyrics: (Option<&str>, Option<&str>),
+    playing: (Option<&str>, Option<&str>),
+) -> bool {
+    let normalize = |s: Option<&str>| s.map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty());
+    let (Some(title), Some(playing_title)) = (normalize(lyrics.0), normalize(playing.0)) else {
+        return false;
+    };
+    if title != playing_title {
+        return false;
+    }
+    match (normalize(lyrics.1), normalize(playing.1)) {
+        (Some(artist), Some(playing_artist)) => {
+            artist.contains(&playing_artist) || playing_artist.contains(&artist)
+        }
+        _ => true,
+    }
+}
+
+/// 排版后的一行文字。
+struct TextRow {
+    text: String,
+    size: f32,
+    color: Rgba<u8>,
+    /// 行顶部的纵坐标
+    top: f32,
+}
+
+/// 自上而下排版文字，记录每一行的位置。
+struct Layout<'a> {
+    font: &'a FontRef<'a>,
+    max_width: f32,
+    y: f32,
+    rows: Vec<TextRow>,
+}
+
+impl Layout<'_> {
+    fn push_text(&mut self, text: &str, size: f32, color: Rgba<u8>) {
+        let scaled = self.font.as_scaled(PxScale::from(size));
+        for row in wrap_text(&scaled, text, self.max_width) {
+            self.rows.push(TextRow {
+                text: row,
+                size,
+                color,
+                top: self.y,
+            });
+            self.y += scaled.height() * LINE_SPACING;
+        }
+    }
+}
+
+fn text_width(font: &PxScaleFont<&FontRef<'_>>, text: &str) -> f32 {
+    text.chars()
+        .map(|ch| font.h_advance(font.glyph_id(ch)))
+        .sum()
+}
+
+/// 按最大宽度折行。优先在空白处断开，一行中没有空白（如中日文）时逐字断开。
+fn wrap_text(font: &PxScaleFont<&FontRef<'_>>, text: &str, max_width: f32) -> Vec<String> {
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    let mut row_width = 0.0;
+
+    for ch in text.trim().chars() {
+        let advance = font.h_advance(font.glyph_id(ch));
+        if row_width + advance > max_width && !row.trim().is_empty() {
+            let tail = match row.rfind(char::is_whitespace) {
+                Some(index) if index > 0 && !ch.is_whitespace() => row.split_off(index),
+                _ => String::new(),
+            };
+            rows.push(row.trim_end().to_string());
+            row = tail.trim_start().to_string();
+            row_width = text_width(font, &row);
+        }
+        if row.is_empty() && ch.is_whitespace() {
+            continue;
+        }
+        row.push(ch);
+        row_width += advance;
+    }
+    if !row.trim().is_empty() {
+        rows.push(row.trim_end().to_string());
+    }
+    rows
+}
+
+/// 以 `coverage` 为覆盖率把颜色混合到画布上的一个像素。
+fn blend_pixel(canvas: &mut RgbaImage, x: i64, y: i64, color: Rgba<u8>, coverage: f32) {
+    let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) else {
+        return;
+    };
+    if x >= canvas.width() || y >= canvas.height() {
+        return;
+    }
+    let alpha = coverage.clamp(0.0, 1.0) * f32::from(color[3]) / 255.0;
+    let pixel = canvas.get_pixel_mut(x, y);
+    for channel in 0..3 {
+        let blended = f32::from(pixel[channel]) * (1.0 - alpha) + f32::from(color[channel]) * alpha;
+        pixel[channel] = blended.round() as u8;
+    }
+}
+
+fn draw_row(canvas: &mut RgbaImage, font: &FontRef<'_>, row: &TextRow, x: f32) {
+    let scale = PxScale::from(row.size);
+    let scaled = font.as_scaled(scale);
+    let baseline = row.top + scaled.ascent();
+    let mut caret = x;
+
+    for ch in row.text.chars() {
+        let glyph_id = scaled.glyph_id(ch);
+        let glyph = glyph_id.with_scale_and_position(scale, point(caret, baseline));
+        caret += scaled.h_advance(glyph_id);
+
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            blend_pixel(
+                canvas,
+                bounds.min.x as i64 + i64::from(gx),
+                bounds.min.y as i64 + i64::from(gy),
+                row.color,
+                coverage,
+            );
+        });
+    }
+}
+
+/// 把歌词渲染为一张长图。
+pub fn render_lyric_image(
+    content: &LyricImageContent,
+    options: &LyricImageOptions,
+) -> Result<RgbaImage, String> {
+    if content.lines.is_empty() {
+        return Err("没有可以渲染的歌词行".to_string());
+    }
+    let font = FontRef::try_from_slice(BUNDLED_FONT).map_err(|e| format!("无法加载字体: {e}"))?;
+
+    let width = options.width.max(MIN_WIDTH);
+    let padding = width as f32 * PADDING_RATIO;
+    let font_size = options.font_size.max(8.0);
+    let secondary_size = font_size * SECONDARY_SCALE;
+    let [r, g, b] = options.text_color;
+    let primary_color = Rgba([r, g, b, 255]);
+    let secondary_color = Rgba([r, g, b, SECONDARY_ALPHA]);
+
+    let mut layout = Layout {
+        font: &font,
+        max_width: width as f32 - padding * 2.0,
+        y: padding,
+        rows: Vec::new(),
+    };
+
+    let cover = content
+        .cover
+        .as_deref()
+        .filter(|_| options.include_cover)
+        .and_then(|bytes| match image::load_from_memory(bytes) {
+            Ok(cover) => Some(cover),
+            Err(e) => {
+                tracing::warn!("[LyricImage] 无法解码封面，已跳过: {e}");
+                None
+            }
+        })
+        .map(|cover| {
+            let size = (width as f32 * COVER_RATIO) as u32;
+            let top = layout.y;
+            layout.y += size as f32 + font_size;
+            (
+                cover
+                    .resize_to_fill(size, size, FilterType::Lanczos3)
+                    .to_rgba8(),
+                top,
+            )
+        });
+
+    if options.include_metadata {
+        let mut has_metadata = false;
+        if let Some(title) = content.title.as_deref().filter(|s| !s.trim().is_empty()) {
+            layout.push_text(title, font_size * 1.3, primary_color);
+            has_metadata = true;
+        }
+        if let Some(artist) = content.artist.as_deref().filter(|s| !s.trim().is_empty()) {
+            layout.push_text(artist, secondary_size, secondary_color);
+            has_metadata = true;
+        }
+        if has_metadata {
+            layout.y += font_size;
+        }
+    }
+
+    for line in &content.lines {
+        layout.push_text(&line.main, font_size, primary_color);
+        if options.include_romanization
+            && let Some(romanization) = &line.romanization
+        {
+            layout.push_text(romanization, secondary_size, secondary_color);
+        }
+        if options.include_translation
+            && let Some(translation) = &line.translation
+        {
+            layout.push_text(translation, secondary_size, secondary_color);
+        }
+        layout.y += font_size * 0.6;
+    }
+
+    let height = (layout.y + padding).ceil() as u32;
+    let [r, g, b] = options.background_color;
+    let mut canvas = RgbaImage::from_pixel(width, height, Rgba([r, g, b, 255]));
+
+    if let Some((cover, top)) = &cover {
+        image::imageops::overlay(&mut canvas, cover, padding as i64, *top as i64);
+    }
+    for row in &layout.rows {
+        draw_row(&mut canvas, &font, row, padding);
+    }
+
+    Ok(canvas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scaled_font(font: &FontRef<'static>) -> PxScaleFont<&FontRef<'static>> {
+        font.as_scaled(PxScale::from(40.0))
+    }
+
+    #[test]
+    fn test_wrap_text_breaks_at_whitespace() {
+        let font = FontRef::try_from_slice(BUNDLED_FONT).unwrap();
+        let font = scaled_font(&font);
+
+        let max_width = text_width(&font, "hello world");
+        assert_eq!(
+            wrap_text(&font, "  hello world again  ", max_width),
+            vec!["hello world", "again"]
+        );
+
+        // 单词中途超出宽度时，整个单词移到下一行
+        let max_width = text_width(&font, "hello wor");
+        assert_eq!(
+            wrap_text(&font, "hello world", max_width),
+            vec!["hello", "world"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_text_breaks_cjk_per_character() {
+        let font = FontRef::try_from_slice(BUNDLED_FONT).unwrap();
+        let font = scaled_font(&font);
+
+        let max_width = text_width(&font, "你好");
+        assert_eq!(
+            wrap_text(&font, "你好世界", max_width),
+            vec!["你好", "世界"]
+        );
+        // 宽度不足一个字时每行至少放一个字
+        assert_eq!(wrap_text(&font, "你好", 1.0), vec!["你", "好"]);
+        assert!(wrap_text(&font, "   ", max_width).is_empty());
+    }
+
+    #[test]
+    fn test_is_same_song() {
+        let lyrics = (Some("Song"), Some("A / B"));
+
+        assert!(is_same_song(lyrics, (Some(" song "), Some("A"))));
+        assert!(is_same_song(lyrics, (Some("Song"), None)));
+        assert!(!is_same_song(lyrics, (Some("Other"), Some("A"))));
+        assert!(!is_same_song(lyrics, (Some("Song"), Some("C"))));
+        assert!(!is_same_song((None, Some("A")), (Some("Song"), Some("A"))));
+    }
+}
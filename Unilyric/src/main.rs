@@ -1,5 +1,6 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod action_script;
 mod amll_connector;
 mod app;
 mod app_actions;
@@ -9,12 +10,24 @@ mod app_handlers;
 mod app_settings;
 mod app_ui;
 mod app_update;
+mod audio_preview;
+mod batch_download;
+mod cli;
+mod crash_log;
 mod error;
 mod io;
+mod lyric_image;
+mod session;
+mod settings_search;
+mod text_diff;
+mod text_search;
 mod types;
 mod utils;
+mod waveform;
 
 use app_settings::AppSettings;
+use cli::CliCommand;
+use std::process::ExitCode;
 use std::sync::{LazyLock, Mutex, mpsc};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{EnvFilter, Layer, fmt};
@@ -43,6 +56,8 @@ where
             timestamp: chrono::Local::now(),
         };
 
+        crash_log::record(&entry);
+
         // 发送到UI，如果失败则打印到stderr
         if self.sender.send(entry).is_err() {
             eprintln!("UI日志通道已关闭。");
@@ -136,11 +151,37 @@ fn setup_tracing(
         .init();
 }
 
-fn main() {
+fn main() -> ExitCode {
+    let command = cli::parse_args(std::env::args().skip(1));
+    if !matches!(command, Ok(CliCommand::Gui)) {
+        cli::attach_parent_console();
+    }
+    let command = match command {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            return ExitCode::from(cli::EXIT_USAGE_ERROR);
+        }
+    };
+    if matches!(command, CliCommand::Help) {
+        println!("{}", cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+
     let app_settings = AppSettings::load();
     let (ui_log_sender, ui_log_receiver) = mpsc::channel();
 
     setup_tracing(ui_log_sender, &app_settings.log_settings);
+    crash_log::install_panic_hook();
+
+    if let CliCommand::Batch(args) = command {
+        tracing::info!(target: "unilyric_main", "以无头模式执行批量转换。");
+        let exit_code = cli::run_batch(&args, &app_settings);
+        crash_log::persist(true);
+        // 日志层在接收端关闭后会报错，因此保留到转换结束
+        drop(ui_log_receiver);
+        return exit_code;
+    }
 
     tracing::info!(target: "unilyric_main", "应用程序已启动。");
 
@@ -162,4 +203,7 @@ fn main() {
     ) {
         tracing::error!(target: "unilyric_main", "Eframe 运行错误: {e}");
     }
+
+    crash_log::persist(true);
+    ExitCode::SUCCESS
 }
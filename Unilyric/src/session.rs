@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use lyrics_helper_core::LyricFormat;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DEFAULT_TRANSLATION_LANGUAGE, TranslationTrack};
+use crate::utils;
+
+const SESSION_FILE_NAME: &str = "workspace_session.json";
+
+/// 工作区会话快照，用于在意外关闭后恢复正在编辑的内容。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceSession {
+    pub input_text: String,
+    pub source_format: LyricFormat,
+    pub target_format: LyricFormat,
+    /// 旧版本会话中唯一的翻译，新会话使用 `translation_tracks`
+    #[serde(default)]
+    pub translation_lrc: String,
+    #[serde(default)]
+    pub translation_tracks: Vec<TranslationTrack>,
+    #[serde(default)]
+    pub romanization_lrc: String,
+    /// 按行索引记录的校对备注，不会被导出
+    #[serde(default, rename = "line_notes_by_index")]
+    pub line_notes: BTreeMap<usize, String>,
+    /// 旧版本会话中按行开始时间（毫秒）记录的备注，恢复后按开始时间换算为行索引
+    #[serde(default, rename = "line_notes", skip_serializing)]
+    pub legacy_line_notes: BTreeMap<u64, String>,
+    #[serde(default)]
+    pub last_opened_file_path: Option<PathBuf>,
+}
+
+impl WorkspaceSession {
+    /// 会话中是否没有任何值得恢复的内容。
+    pub fn is_empty(&self) -> bool {
+        self.input_text.trim().is_empty()
+            && self.translation_lrc.trim().is_empty()
+            && self
+                .translation_tracks
+                .iter()
+                .all(|t| t.lrc.trim().is_empty())
+            && self.romanization_lrc.trim().is_empty()
+    }
+
+    /// 会话中的翻译轨道。旧版本会话只有一条翻译，按默认语言恢复。
+    pub fn restored_translation_tracks(&self) -> Vec<TranslationTrack> {
+        if !self.translation_tracks.is_empty() || self.translation_lrc.trim().is_empty() {
+            return self.translation_tracks.clone();
+        }
+        vec![TranslationTrack {
+            language: DEFAULT_TRANSLATION_LANGUAGE.to_string(),
+            lrc: self.translation_lrc.clone(),
+        }]
+    }
+
+    fn file_path() -> Option<PathBuf> {
+        utils::get_app_data_dir().map(|dir| dir.join(SESSION_FILE_NAME))
+    }
+
+    /// 从本地读取上次保存的会话。文件不存在、解析失败或内容为空时返回 `None`。
+    pub fn load() -> Option<Self> {
+        let path = Self::file_path()?;
+        if !path.exists() {
+            return None;
+        }
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("[Session] 读取会话文件 {path:?} 失败: {e}");
+                return None;
+            }
+        };
+
+        match serde_json::from_str::<Self>(&content) {
+            Ok(session) if !session.is_empty() => Some(session),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!("[Session] 解析会话文件 {path:?} 失败: {e}");
+                None
+            }
+        }
+    }
+
+    /// 将会话写入本地。内容为空时删除已有的会话文件。
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        if self.is_empty() {
+            return Self::clear();
+        }
+
+        let Some(path) = Self::file_path() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "无法确定会话文件路径",
+            ));
+        };
+
+        let json = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        std::fs::write(&path, json)?;
+        tracing::debug!("[Session] 工作区会话已保存到 {path:?}");
+        Ok(())
+    }
+
+    /// 删除本地保存的会话文件。
+    pub fn clear() -> Result<(), std::io::Error> {
+        match Self::file_path() {
+            Some(path) if path.exists() => std::fs::remove_file(path),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_from_json(json: &str) -> WorkspaceSession {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_legacy_translation_restores_as_default_language_track() {
+        let session = session_from_json(
+            r#"{
+                "input_text": "[00:01.00]歌词",
+                "source_format": "Lrc",
+                "target_format": "Lrc",
+                "translation_lrc": "[00:01.00]翻译"
+            }"#,
+        );
+
+        assert_eq!(
+            session.restored_translation_tracks(),
+            vec![TranslationTrack {
+                language: DEFAULT_TRANSLATION_LANGUAGE.to_string(),
+                lrc: "[00:01.00]翻译".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_translation_tracks_take_precedence_over_legacy_translation() {
+        let tracks = vec![TranslationTrack {
+            language: "en".to_string(),
+            lrc: "[00:01.00]Hello".to_string(),
+        }];
+        let session = WorkspaceSession {
+            input_text: String::new(),
+            source_format: LyricFormat::Lrc,
+            target_format: LyricFormat::Lrc,
+            translation_lrc: "[00:01.00]旧翻译".to_string(),
+            translation_tracks: tracks.clone(),
+            romanization_lrc: String::new(),
+            line_notes: BTreeMap::new(),
+            legacy_line_notes: BTreeMap::new(),
+            last_opened_file_path: None,
+        };
+
+        assert_eq!(session.restored_translation_tracks(), tracks);
+    }
+
+    #[test]
+    fn test_blank_legacy_translation_restores_no_tracks() {
+        let session = session_from_json(
+            r#"{
+                "input_text": "",
+                "source_format": "Lrc",
+                "target_format": "Lrc",
+                "translation_lrc": "  "
+            }"#,
+        );
+
+        assert!(session.restored_translation_tracks().is_empty());
+    }
+}
@@ -0,0 +1,383 @@
+//! 设置窗口的搜索。
+//!
+//! 每个可搜索的设置项在索引中登记它所在的分类、折叠分组和界面上显示的文字。
+//! 绘制设置项时调用 [`SettingsSearch::mark`]，命中搜索的项会被描边高亮，
+//! 被用户点选的项会滚动到可见位置。
+
+use crate::app_ui::SettingsCategory::{
+    self, AutoSearch, Connector, General, Interface, Postprocessors,
+};
+
+/// 可搜索的设置项。
+pub struct SettingsSearchEntry {
+    pub category: SettingsCategory,
+    /// 所在的折叠分组标题，没有时为 `None`
+    pub section: Option<&'static str>,
+    /// 界面上显示的文字，也是定位该项的标识
+    pub label: &'static str,
+    /// 额外的搜索关键词，以空格分隔
+    pub keywords: &'static str,
+}
+
+const fn entry(
+    category: SettingsCategory,
+    section: Option<&'static str>,
+    label: &'static str,
+    keywords: &'static str,
+) -> SettingsSearchEntry {
+    SettingsSearchEntry {
+        category,
+        section,
+        label,
+        keywords,
+    }
+}
+
+pub const SETTINGS_SEARCH_INDEX: &[SettingsSearchEntry] = &[
+    entry(General, None, "启用文件日志:", "log 日志"),
+    entry(General, None, "文件日志级别:", "log level 日志"),
+    entry(General, None, "控制台日志级别:", "console log level 日志"),
+    entry(General, None, "自动保存工作区会话", "session autosave 恢复"),
+    entry(General, None, "保存间隔:", "session autosave interval 会话"),
+    entry(
+        General,
+        None,
+        "保存输出时添加 UTF-8 BOM",
+        "bom utf8 encoding 编码 保存",
+    ),
+    entry(
+        General,
+        None,
+        "保存输出时的换行符:",
+        "line ending lf crlf newline 换行 保存",
+    ),
+    entry(
+        General,
+        None,
+        "导入 LRC 时保留 [offset:] 标签",
+        "lrc offset 偏移 时间",
+    ),
+    entry(Interface, None, "界面字体:", "font 字体"),
+    entry(AutoSearch, None, "自动缓存歌词", "cache 本地缓存"),
+    entry(AutoSearch, None, "最多缓存数量:", "cache 缓存"),
+    entry(
+        AutoSearch,
+        None,
+        "优先搜索 AMLL TTML 数据库 (推荐)",
+        "amll db ttml",
+    ),
+    entry(
+        AutoSearch,
+        None,
+        "将繁体 SMTC 信息转为简体再搜索 (推荐)",
+        "t2s 繁体 简体",
+    ),
+    entry(AutoSearch, None, "始终搜索所有源 (推荐)", "provider 提供商"),
+    entry(
+        AutoSearch,
+        None,
+        "搜索源优先级 (拖拽排序):",
+        "provider order 排序 提供商",
+    ),
+    entry(
+        AutoSearch,
+        None,
+        "只在以下选择的源中搜索:",
+        "provider subset 提供商",
+    ),
+    entry(
+        AutoSearch,
+        None,
+        "启用自定义歌词源",
+        "custom http 自定义 接口",
+    ),
+    entry(AutoSearch, None, "请求超时:", "timeout network 网络 超时"),
+    entry(
+        AutoSearch,
+        None,
+        "代理地址:",
+        "proxy http socks network 网络 代理",
+    ),
+    entry(
+        AutoSearch,
+        None,
+        "下载器中输入后自动搜索",
+        "downloader 下载",
+    ),
+    entry(
+        AutoSearch,
+        None,
+        "搜索完成后自动预览首个结果",
+        "downloader 下载 first result preview 第一个",
+    ),
+    entry(
+        Connector,
+        None,
+        "启用 AMLL Connector 功能:",
+        "amll player smtc",
+    ),
+    entry(Connector, None, "WebSocket URL:", "ws websocket 地址"),
+    entry(
+        Connector,
+        None,
+        "歌词发送模式:",
+        "plain text 纯文本 静态 时间轴",
+    ),
+    entry(Connector, None, "心跳间隔 (秒):", "heartbeat ping"),
+    entry(
+        Connector,
+        None,
+        "心跳超时 (秒):",
+        "heartbeat pong timeout 重连",
+    ),
+    entry(
+        Connector,
+        None,
+        "将音频数据发送到 AMLL Player",
+        "audio 音频",
+    ),
+    entry(Connector, None, "时间轴偏移量 (毫秒):", "offset smtc 偏移"),
+    entry(Connector, None, "校准时间轴", "calibrate spotify"),
+    entry(
+        Connector,
+        None,
+        "在新曲目开始时快速暂停/播放",
+        "flicker 暂停 播放",
+    ),
+    entry(Connector, None, "AMLL DB 镜像", "mirror github 镜像 索引"),
+    entry(
+        Postprocessors,
+        None,
+        "清理元数据行",
+        "auto 自动应用 metadata",
+    ),
+    entry(
+        Postprocessors,
+        None,
+        "识别演唱者",
+        "auto 自动应用 agent 演唱者",
+    ),
+    entry(Postprocessors, None, "元数据清理器", "metadata stripper"),
+    entry(
+        Postprocessors,
+        Some("元数据清理器"),
+        "启用元数据清理",
+        "metadata stripper",
+    ),
+    entry(
+        Postprocessors,
+        Some("元数据清理器"),
+        "作用于:",
+        "metadata translation 翻译",
+    ),
+    entry(
+        Postprocessors,
+        Some("元数据清理器"),
+        "关键词匹配区分大小写",
+        "keyword case",
+    ),
+    entry(
+        Postprocessors,
+        Some("元数据清理器"),
+        "启用正则表达式清理",
+        "regex",
+    ),
+    entry(
+        Postprocessors,
+        Some("元数据清理器"),
+        "正则表达式匹配区分大小写",
+        "regex case",
+    ),
+    entry(
+        Postprocessors,
+        Some("元数据清理器"),
+        "将头部的短行视为署名",
+        "short line heuristic 短行 署名 人名",
+    ),
+    entry(
+        Postprocessors,
+        Some("元数据清理器"),
+        "关键词 (每行一个):",
+        "keyword",
+    ),
+    entry(
+        Postprocessors,
+        Some("元数据清理器"),
+        "正则表达式 (每行一个):",
+        "regex",
+    ),
+    entry(Postprocessors, None, "音节平滑", "smoothing syllable"),
+    entry(
+        Postprocessors,
+        Some("音节平滑"),
+        "平滑因子 (0.0-0.5):",
+        "smoothing factor",
+    ),
+    entry(
+        Postprocessors,
+        Some("音节平滑"),
+        "平滑迭代次数:",
+        "smoothing iterations",
+    ),
+    entry(
+        Postprocessors,
+        Some("音节平滑"),
+        "时长差异阈值 (ms):",
+        "smoothing duration",
+    ),
+    entry(
+        Postprocessors,
+        Some("音节平滑"),
+        "间隔阈值 (ms):",
+        "smoothing gap",
+    ),
+    entry(
+        Postprocessors,
+        Some("音节平滑"),
+        "背景人声:",
+        "smoothing background bg 背景",
+    ),
+    entry(Postprocessors, None, "智能换行", "wrap line 换行"),
+    entry(
+        Postprocessors,
+        Some("智能换行"),
+        "每行最多字数:",
+        "wrap max chars 字数",
+    ),
+    entry(
+        Postprocessors,
+        None,
+        "标点规范化",
+        "punctuation fullwidth halfwidth 全角 半角 标点",
+    ),
+    entry(
+        Postprocessors,
+        Some("标点规范化"),
+        "标点统一为:",
+        "punctuation width 全角 半角",
+    ),
+    entry(
+        Postprocessors,
+        Some("标点规范化"),
+        "全角数字转为半角",
+        "digits number 数字",
+    ),
+    entry(
+        Postprocessors,
+        Some("标点规范化"),
+        "全角字母转为半角",
+        "latin letters 字母",
+    ),
+    entry(
+        Postprocessors,
+        None,
+        "音节重叠修复",
+        "overlap syllable fix trim 重叠 音节 修复",
+    ),
+    entry(
+        Postprocessors,
+        Some("音节重叠修复"),
+        "修复方式:",
+        "overlap mode redistribute 裁剪 比例",
+    ),
+    entry(
+        Postprocessors,
+        None,
+        "简繁转换自定义词典",
+        "chinese dictionary 词典 简繁",
+    ),
+    entry(Postprocessors, None, "正则替换", "regex replace 替换 规则"),
+    entry(
+        Postprocessors,
+        None,
+        "演唱者括号标记",
+        "agent singer bracket 演唱者 对唱 合唱 括号",
+    ),
+    entry(
+        Postprocessors,
+        Some("演唱者括号标记"),
+        "识别置信度阈值 (0.0-1.0):",
+        "agent confidence threshold 演唱者 置信度 阈值 误识别",
+    ),
+];
+
+/// 设置窗口的搜索状态。
+#[derive(Debug, Default)]
+pub struct SettingsSearch {
+    pub query: String,
+    /// 用户在搜索结果中点选、等待滚动到可见位置的设置项
+    pub jump_target: Option<&'static str>,
+}
+
+impl SettingsSearch {
+    fn normalized_query(&self) -> Option<String> {
+        let query = self.query.trim();
+        (!query.is_empty()).then(|| query.to_lowercase())
+    }
+
+    fn entry_matches(entry: &SettingsSearchEntry, query: &str) -> bool {
+        entry.label.to_lowercase().contains(query) || entry.keywords.to_lowercase().contains(query)
+    }
+
+    /// 返回所有命中当前搜索的设置项。搜索框为空时返回空列表。
+    pub fn matches(&self) -> Vec<&'static SettingsSearchEntry> {
+        let Some(query) = self.normalized_query() else {
+            return Vec::new();
+        };
+        SETTINGS_SEARCH_INDEX
+            .iter()
+            .filter(|entry| Self::entry_matches(entry, &query))
+            .collect()
+    }
+
+    /// 返回折叠分组是否需要强制展开：分组内有命中搜索的项时展开，没有搜索时不干预。
+    pub fn section_open(&self, section: &str) -> Option<bool> {
+        let query = self.normalized_query()?;
+        SETTINGS_SEARCH_INDEX
+            .iter()
+            .any(|entry| entry.section == Some(section) && Self::entry_matches(entry, &query))
+            .then_some(true)
+    }
+
+    /// 添加一个可搜索的标签。
+    pub fn label(&mut self, ui: &mut egui::Ui, text: &'static str) -> egui::Response {
+        let response = ui.label(text);
+        self.mark(ui, &response, text);
+        response
+    }
+
+    /// 添加一个可搜索的复选框。
+    pub fn checkbox(
+        &mut self,
+        ui: &mut egui::Ui,
+        checked: &mut bool,
+        text: &'static str,
+    ) -> egui::Response {
+        let response = ui.checkbox(checked, text);
+        self.mark(ui, &response, text);
+        response
+    }
+
+    /// 在设置项的控件周围绘制命中高亮，如果它是被点选的项则滚动到可见位置。
+    pub fn mark(&mut self, ui: &egui::Ui, response: &egui::Response, label: &str) {
+        if self.jump_target == Some(label) {
+            response.scroll_to_me(Some(egui::Align::Center));
+            self.jump_target = None;
+        }
+
+        let Some(query) = self.normalized_query() else {
+            return;
+        };
+        let is_match = SETTINGS_SEARCH_INDEX
+            .iter()
+            .any(|entry| entry.label == label && Self::entry_matches(entry, &query));
+        if is_match {
+            ui.painter().rect_stroke(
+                response.rect.expand(2.0),
+                3.0,
+                egui::Stroke::new(1.5, ui.visuals().warn_fg_color),
+                egui::StrokeKind::Outside,
+            );
+        }
+    }
+}
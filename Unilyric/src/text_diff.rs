@@ -0,0 +1,336 @@
+//! 文本层面的逐行 diff，用于并排或行内对比处理前后的歌词。
+//!
+//! 先按行计算最长公共子序列，再对相邻的删除/插入行逐字符比较，标出行内的具体变化。
+
+/// 超过此规模（旧行数 × 新行数）时不再计算 LCS，直接视为整体替换，避免占用过多内存。
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// 行内的一段文本。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffSegment {
+    pub text: String,
+    /// 这段文本是否发生了变化
+    pub changed: bool,
+}
+
+/// diff 中一侧的一行。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    /// 行号（从 1 开始）
+    pub line_number: usize,
+    pub segments: Vec<DiffSegment>,
+}
+
+/// 并排视图中的一行。某一侧为 `None` 表示该侧在此处没有对应的行。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRow {
+    pub old: Option<DiffLine>,
+    pub new: Option<DiffLine>,
+}
+
+impl DiffRow {
+    /// 两侧内容是否完全相同。
+    pub fn is_unchanged(&self) -> bool {
+        let side_unchanged = |line: &Option<DiffLine>| {
+            line.as_ref()
+                .is_some_and(|l| l.segments.iter().all(|s| !s.changed))
+        };
+        side_unchanged(&self.old) && side_unchanged(&self.new)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// 计算两段文本的逐行 diff，返回可直接用于并排显示的行。
+///
+/// 连续的删除行与插入行会按顺序两两配对，视为同一行被修改。
+pub fn diff_texts(old: &str, new: &str) -> Vec<DiffRow> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut rows = Vec::with_capacity(ops.len());
+    let mut deleted: Vec<usize> = Vec::new();
+    let mut inserted: Vec<usize> = Vec::new();
+
+    let flush = |rows: &mut Vec<DiffRow>, deleted: &mut Vec<usize>, inserted: &mut Vec<usize>| {
+        let paired = deleted.len().max(inserted.len());
+        for k in 0..paired {
+            let row = match (deleted.get(k), inserted.get(k)) {
+                (Some(&i), Some(&j)) => {
+                    let (old_segments, new_segments) = diff_chars(old_lines[i], new_lines[j]);
+                    DiffRow {
+                        old: Some(DiffLine {
+                            line_number: i + 1,
+                            segments: old_segments,
+                        }),
+                        new: Some(DiffLine {
+                            line_number: j + 1,
+                            segments: new_segments,
+                        }),
+                    }
+                }
+                (Some(&i), None) => DiffRow {
+                    old: Some(whole_line(i, old_lines[i], true)),
+                    new: None,
+                },
+                (None, Some(&j)) => DiffRow {
+                    old: None,
+                    new: Some(whole_line(j, new_lines[j], true)),
+                },
+                (None, None) => unreachable!(),
+            };
+            rows.push(row);
+        }
+        deleted.clear();
+        inserted.clear();
+    };
+
+    for op in ops {
+        match op {
+            LineOp::Equal(i, j) => {
+                flush(&mut rows, &mut deleted, &mut inserted);
+                rows.push(DiffRow {
+                    old: Some(whole_line(i, old_lines[i], false)),
+                    new: Some(whole_line(j, new_lines[j], false)),
+                });
+            }
+            LineOp::Delete(i) => deleted.push(i),
+            LineOp::Insert(j) => inserted.push(j),
+        }
+    }
+    flush(&mut rows, &mut deleted, &mut inserted);
+
+    rows
+}
+
+fn whole_line(index: usize, text: &str, changed: bool) -> DiffLine {
+    DiffLine {
+        line_number: index + 1,
+        segments: vec![DiffSegment {
+            text: text.to_string(),
+            changed,
+        }],
+    }
+}
+
+/// 按行计算编辑序列。先去掉相同的首尾行，再对中间部分计算 LCS。
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<LineOp> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut ops: Vec<LineOp> = (0..prefix).map(|i| LineOp::Equal(i, i)).collect();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    for op in lcs_ops(old_mid, new_mid) {
+        ops.push(match op {
+            LineOp::Equal(i, j) => LineOp::Equal(i + prefix, j + prefix),
+            LineOp::Delete(i) => LineOp::Delete(i + prefix),
+            LineOp::Insert(j) => LineOp::Insert(j + prefix),
+        });
+    }
+
+    let old_suffix_start = old.len() - suffix;
+    let new_suffix_start = new.len() - suffix;
+    ops.extend((0..suffix).map(|k| LineOp::Equal(old_suffix_start + k, new_suffix_start + k)));
+    ops
+}
+
+/// 通过动态规划计算最长公共子序列，并回溯出编辑序列。
+///
+/// 删除总是排在同一位置的插入之前，便于之后把它们配对成修改行。
+fn lcs_ops<T: PartialEq>(old: &[T], new: &[T]) -> Vec<LineOp> {
+    let (n, m) = (old.len(), new.len());
+    if n == 0 || m == 0 || n.saturating_mul(m) > MAX_LCS_CELLS {
+        let mut ops: Vec<LineOp> = (0..n).map(LineOp::Delete).collect();
+        ops.extend((0..m).map(LineOp::Insert));
+        return ops;
+    }
+
+    // table[i][j] 为 old[i..] 与 new[j..] 的 LCS 长度
+    let width = m + 1;
+    let mut table = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i * width + j] = if old[i] == new[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(LineOp::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            ops.push(LineOp::Delete(i));
+            i += 1;
+        } else {
+            ops.push(LineOp::Insert(j));
+            j += 1;
+        }
+    }
+    ops.extend((i..n).map(LineOp::Delete));
+    ops.extend((j..m).map(LineOp::Insert));
+    ops
+}
+
+/// 逐字符比较两行，返回两侧各自的分段结果。
+fn diff_chars(old: &str, new: &str) -> (Vec<DiffSegment>, Vec<DiffSegment>) {
+    let old_chars: Vec<char> = old.chars().collect();
+    let new_chars: Vec<char> = new.chars().collect();
+
+    let mut old_segments = Vec::new();
+    let mut new_segments = Vec::new();
+    for op in lcs_ops(&old_chars, &new_chars) {
+        match op {
+            LineOp::Equal(i, j) => {
+                push_char(&mut old_segments, old_chars[i], false);
+                push_char(&mut new_segments, new_chars[j], false);
+            }
+            LineOp::Delete(i) => push_char(&mut old_segments, old_chars[i], true),
+            LineOp::Insert(j) => push_char(&mut new_segments, new_chars[j], true),
+        }
+    }
+    (old_segments, new_segments)
+}
+
+fn push_char(segments: &mut Vec<DiffSegment>, ch: char, changed: bool) {
+    match segments.last_mut() {
+        Some(last) if last.changed == changed => last.text.push(ch),
+        _ => segments.push(DiffSegment {
+            text: ch.to_string(),
+            changed,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, changed: bool) -> DiffSegment {
+        DiffSegment {
+            text: text.to_string(),
+            changed,
+        }
+    }
+
+    /// 一行简化后的（行号，文本），`None` 表示该侧没有对应的行。
+    type Side = Option<(usize, String)>;
+
+    /// 把每一行的两侧简化为（行号，文本），便于断言行的配对关系。
+    fn sides(rows: &[DiffRow]) -> Vec<(Side, Side)> {
+        let side = |line: &Option<DiffLine>| {
+            line.as_ref().map(|l| {
+                (
+                    l.line_number,
+                    l.segments.iter().map(|s| s.text.as_str()).collect(),
+                )
+            })
+        };
+        rows.iter()
+            .map(|row| (side(&row.old), side(&row.new)))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_texts_identical() {
+        let rows = diff_texts("a\nb\nc", "a\nb\nc");
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(DiffRow::is_unchanged));
+        assert!(diff_texts("", "").is_empty());
+    }
+
+    #[test]
+    fn test_diff_texts_insertions_and_deletions() {
+        let rows = diff_texts("a\nb\nc", "a\nc\nd");
+        assert_eq!(
+            sides(&rows),
+            vec![
+                (Some((1, "a".into())), Some((1, "a".into()))),
+                (Some((2, "b".into())), None),
+                (Some((3, "c".into())), Some((2, "c".into()))),
+                (None, Some((3, "d".into()))),
+            ]
+        );
+        let changed: Vec<bool> = rows.iter().map(DiffRow::is_unchanged).collect();
+        assert_eq!(changed, vec![true, false, true, false]);
+
+        let rows = diff_texts("", "a\nb");
+        assert_eq!(
+            sides(&rows),
+            vec![(None, Some((1, "a".into()))), (None, Some((2, "b".into())))]
+        );
+    }
+
+    #[test]
+    fn test_diff_texts_pairs_modified_lines_with_char_segments() {
+        let rows = diff_texts(
+            "[00:01.00]头发\n[00:02.00]end",
+            "[00:01.00]頭髮\n[00:02.00]end",
+        );
+        assert_eq!(rows.len(), 2);
+        assert!(rows[1].is_unchanged());
+
+        let old = rows[0].old.as_ref().unwrap();
+        let new = rows[0].new.as_ref().unwrap();
+        assert_eq!((old.line_number, new.line_number), (1, 1));
+        assert_eq!(
+            old.segments,
+            vec![segment("[00:01.00]", false), segment("头发", true)]
+        );
+        assert_eq!(
+            new.segments,
+            vec![segment("[00:01.00]", false), segment("頭髮", true)]
+        );
+    }
+
+    #[test]
+    fn test_diff_chars_marks_only_changed_characters() {
+        let (old, new) = diff_chars("春よ、遠き春よ", "春よ 遠き春を");
+        assert_eq!(
+            old,
+            vec![
+                segment("春よ", false),
+                segment("、", true),
+                segment("遠き春", false),
+                segment("よ", true),
+            ]
+        );
+        assert_eq!(
+            new,
+            vec![
+                segment("春よ", false),
+                segment(" ", true),
+                segment("遠き春", false),
+                segment("を", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lcs_ops_orders_deletions_before_insertions() {
+        assert_eq!(
+            lcs_ops(&["a", "b"], &["a", "c"]),
+            vec![LineOp::Equal(0, 0), LineOp::Delete(1), LineOp::Insert(1)]
+        );
+        assert_eq!(lcs_ops::<&str>(&["a"], &[]), vec![LineOp::Delete(0)]);
+    }
+}
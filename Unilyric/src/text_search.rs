@@ -0,0 +1,195 @@
+//! 输入、输出面板中的关键词查找与高亮。
+//!
+//! 查找忽略大小写，匹配之间互不重叠。面板通过自定义的 `LayoutJob` 给所有匹配加上背景色，
+//! 当前匹配使用更醒目的颜色。
+
+use std::ops::Range;
+
+use egui::text::{LayoutJob, TextFormat};
+use egui::{Color32, FontId};
+
+/// 非当前匹配的背景色。
+const MATCH_BACKGROUND: Color32 = Color32::from_rgba_premultiplied(110, 90, 0, 110);
+
+/// 一个面板的查找栏状态。
+#[derive(Debug, Clone, Default)]
+pub struct FindBar {
+    pub open: bool,
+    pub query: String,
+    /// 当前匹配的序号
+    pub current: usize,
+    /// 是否需要在下一次绘制时把当前匹配滚动到可见区域
+    pub scroll_pending: bool,
+}
+
+impl FindBar {
+    /// 查找栏已打开且输入了关键词时返回关键词。
+    pub fn active_query(&self) -> Option<&str> {
+        (self.open && !self.query.is_empty()).then_some(self.query.as_str())
+    }
+
+    /// 跳转到下一个（`forward`）或上一个匹配，到达末尾后循环。
+    pub fn step(&mut self, match_count: usize, forward: bool) {
+        if match_count == 0 {
+            return;
+        }
+        self.current = if forward {
+            (self.current + 1) % match_count
+        } else {
+            (self.current + match_count - 1) % match_count
+        };
+        self.scroll_pending = true;
+    }
+}
+
+/// 如果 `text` 从 `start` 开始与 `query` 匹配（忽略大小写），返回匹配结束的字节位置。
+fn match_end_at(text: &str, start: usize, query: &str) -> Option<usize> {
+    let mut text_chars = text[start..].char_indices();
+    for query_char in query.chars() {
+        let (_, text_char) = text_chars.next()?;
+        if !text_char.to_lowercase().eq(query_char.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(
+        text_chars
+            .next()
+            .map_or(text.len(), |(offset, _)| start + offset),
+    )
+}
+
+/// 查找 `text` 中所有不重叠的匹配，返回各匹配的字节范围。
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+    let mut next_allowed = 0;
+    for (start, _) in text.char_indices() {
+        if start < next_allowed {
+            continue;
+        }
+        if let Some(end) = match_end_at(text, start, query) {
+            matches.push(start..end);
+            next_allowed = end;
+        }
+    }
+    matches
+}
+
+/// 匹配开始处的字符索引，用于在排版后的文本中定位。
+pub fn char_index_of(text: &str, range: &Range<usize>) -> usize {
+    text.get(..range.start)
+        .map_or(0, |prefix| prefix.chars().count())
+}
+
+/// 生成高亮了所有匹配的排版任务。
+pub fn highlighted_layout_job(
+    text: &str,
+    matches: &[Range<usize>],
+    current: usize,
+    font_id: FontId,
+    text_color: Color32,
+    current_background: Color32,
+    wrap_width: f32,
+) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    job.wrap.max_width = wrap_width;
+    let format = TextFormat::simple(font_id, text_color);
+
+    let mut last_end = 0;
+    for (index, range) in matches.iter().enumerate() {
+        job.append(&text[last_end..range.start], 0.0, format.clone());
+        let background = if index == current {
+            current_background
+        } else {
+            MATCH_BACKGROUND
+        };
+        job.append(
+            &text[range.clone()],
+            0.0,
+            TextFormat {
+                background,
+                ..format.clone()
+            },
+        );
+        last_end = range.end;
+    }
+    job.append(&text[last_end..], 0.0, format);
+    job
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matches_ignores_case() {
+        assert_eq!(
+            find_matches("Hello hello HELLO", "hello"),
+            vec![0..5, 6..11, 12..17]
+        );
+        assert_eq!(
+            find_matches("Straße", "STRASSE"),
+            Vec::<Range<usize>>::new()
+        );
+        assert!(find_matches("abc", "").is_empty());
+        assert!(find_matches("abc", "abcd").is_empty());
+    }
+
+    #[test]
+    fn test_find_matches_multi_byte() {
+        let text = "[00:01.00]春よ、遠き春よ";
+        let matches = find_matches(text, "春よ");
+        assert_eq!(matches.len(), 2);
+        for range in &matches {
+            assert_eq!(&text[range.clone()], "春よ");
+        }
+        assert_eq!(char_index_of(text, &matches[0]), 10);
+        assert_eq!(char_index_of(text, &matches[1]), 15);
+
+        assert_eq!(find_matches("ÄBC äbc", "äbc"), vec![0..4, 5..9]);
+        assert_eq!(match_end_at("日本語", 3, "本"), Some(6));
+        assert_eq!(match_end_at("日本語", 3, "語"), None);
+    }
+
+    #[test]
+    fn test_find_matches_does_not_overlap() {
+        assert_eq!(find_matches("aaaa", "aa"), vec![0..2, 2..4]);
+        assert_eq!(find_matches("aaa", "aa"), vec![0..2]);
+        assert_eq!(find_matches("ababab", "aba"), vec![0..3]);
+    }
+
+    #[test]
+    fn test_find_bar_step_wraps_around() {
+        let mut bar = FindBar::default();
+        bar.step(0, true);
+        assert_eq!(bar.current, 0);
+        assert!(!bar.scroll_pending);
+
+        bar.step(3, true);
+        bar.step(3, true);
+        assert_eq!(bar.current, 2);
+        bar.step(3, true);
+        assert_eq!(bar.current, 0);
+        assert!(bar.scroll_pending);
+
+        bar.step(3, false);
+        assert_eq!(bar.current, 2);
+        bar.step(3, false);
+        assert_eq!(bar.current, 1);
+    }
+
+    #[test]
+    fn test_find_bar_active_query() {
+        let mut bar = FindBar {
+            query: "春".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(bar.active_query(), None);
+        bar.open = true;
+        assert_eq!(bar.active_query(), Some("春"));
+        bar.query.clear();
+        assert_eq!(bar.active_query(), None);
+    }
+}
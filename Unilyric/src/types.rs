@@ -1,5 +1,14 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Local};
-use lyrics_helper_core::{CanonicalMetadataKey, LyricFormat, LyricLine, LyricsAndMetadata};
+use lyrics_helper_core::{
+    CanonicalMetadataKey, LyricFormat, LyricLine, LyricsAndMetadata, ParseWarning,
+    ParseWarningLevel, SearchResult, SyllableSmoothingOptions,
+};
+use lyrics_helper_rs::converter::MergeReport;
+use lyrics_helper_rs::converter::processors::line_editor::TimingTarget;
+use lyrics_helper_rs::converter::processors::lyric_linter::LintSeverity;
+use lyrics_helper_rs::converter::processors::syllable_smoothing;
 use serde::{Deserialize, Serialize};
 
 use crate::error::AppError;
@@ -17,17 +26,147 @@ pub struct EditableMetadataEntry {
 pub struct LocalLyricCacheEntry {
     pub smtc_title: String,
     pub smtc_artists: Vec<String>,
+    /// 保存时正在播放的专辑。旧版本的索引中没有此字段
+    #[serde(default)]
+    pub smtc_album: Option<String>,
     pub ttml_filename: String,
     pub original_source_format: Option<String>,
     pub saved_timestamp: i64,
 }
 
+impl LocalLyricCacheEntry {
+    /// 用于分组显示的歌手名。
+    pub fn artist_display(&self) -> String {
+        if self.smtc_artists.is_empty() {
+            "未知歌手".to_string()
+        } else {
+            self.smtc_artists.join(" / ")
+        }
+    }
+
+    /// 用于分组显示的专辑名。
+    pub fn album_display(&self) -> &str {
+        self.smtc_album
+            .as_deref()
+            .filter(|album| !album.trim().is_empty())
+            .unwrap_or("未知专辑")
+    }
+
+    /// 标题、歌手或专辑中是否包含搜索词（不区分大小写）。
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+        self.smtc_title.to_lowercase().contains(&query)
+            || self
+                .smtc_artists
+                .iter()
+                .any(|artist| artist.to_lowercase().contains(&query))
+            || self
+                .smtc_album
+                .as_deref()
+                .is_some_and(|album| album.to_lowercase().contains(&query))
+    }
+}
+
+/// 编码检测不可靠、等待用户手动选择编码的文件。
+#[derive(Debug, Clone)]
+pub struct EncodingPrompt {
+    pub path: std::path::PathBuf,
+    /// 为 `None` 时是主歌词文件
+    pub target: Option<LrcContentType>,
+    pub detected: &'static encoding_rs::Encoding,
+    pub selected: &'static encoding_rs::Encoding,
+}
+
+/// 将主歌词、翻译、罗马音 LRC 合并为 TTML 的向导状态。
+#[derive(Debug, Clone)]
+pub struct LrcMergeWizard {
+    pub main_path: Option<std::path::PathBuf>,
+    pub translation_path: Option<std::path::PathBuf>,
+    pub romanization_path: Option<std::path::PathBuf>,
+    /// 翻译文件的语言标签，为空时不标记语言
+    pub translation_language: String,
+    /// 罗马音文件的语言标签，为空时不标记语言
+    pub romanization_language: String,
+    /// 辅助行与主歌词行时间戳允许的最大差值（毫秒）
+    pub tolerance_ms: u64,
+    /// 最近一次合并生成的 TTML 及对齐统计
+    pub result: Option<(String, MergeReport)>,
+}
+
+impl Default for LrcMergeWizard {
+    fn default() -> Self {
+        Self {
+            main_path: None,
+            translation_path: None,
+            romanization_path: None,
+            translation_language: DEFAULT_TRANSLATION_LANGUAGE.to_string(),
+            romanization_language: DEFAULT_ROMANIZATION_LANGUAGE.to_string(),
+            tolerance_ms: 20,
+            result: None,
+        }
+    }
+}
+
+impl LrcMergeWizard {
+    /// 获取指定文件槽位的路径。`None` 表示主歌词。
+    pub fn path_mut(&mut self, target: Option<LrcContentType>) -> &mut Option<std::path::PathBuf> {
+        match target {
+            None => &mut self.main_path,
+            Some(LrcContentType::Translation) => &mut self.translation_path,
+            Some(LrcContentType::Romanization) => &mut self.romanization_path,
+        }
+    }
+
+    /// 获取指定辅助文件的语言标签输入框内容。
+    pub fn language_mut(&mut self, target: LrcContentType) -> &mut String {
+        match target {
+            LrcContentType::Translation => &mut self.translation_language,
+            LrcContentType::Romanization => &mut self.romanization_language,
+        }
+    }
+
+    /// 合并时为指定辅助文件使用的语言标签，输入为空时返回 `None`。
+    pub fn language(&self, target: LrcContentType) -> Option<&str> {
+        let language = match target {
+            LrcContentType::Translation => &self.translation_language,
+            LrcContentType::Romanization => &self.romanization_language,
+        };
+        Some(language.trim()).filter(|language| !language.is_empty())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LrcContentType {
     Translation,
     Romanization,
 }
 
+/// 没有语言标签的翻译默认使用的语言。
+pub const DEFAULT_TRANSLATION_LANGUAGE: &str = "zh-Hans";
+
+/// 没有语言标签的罗马音默认使用的语言。
+pub const DEFAULT_ROMANIZATION_LANGUAGE: &str = "ja-Latn";
+
+/// 一条按语言区分的翻译轨道，以 LRC 文本编辑。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationTrack {
+    /// BCP-47 语言标签，例如 `zh-Hans`、`en`
+    pub language: String,
+    pub lrc: String,
+}
+
+impl TranslationTrack {
+    pub fn new(language: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+            lrc: String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum DisplayLrcLine {
     Parsed(Box<LyricLine>),
@@ -54,6 +193,12 @@ pub enum AutoFetchResult {
         title: String,
         artist: String,
     },
+    /// 自动搜索得到的全部候选结果，供下载器直接展示
+    CandidatesFound {
+        title: String,
+        artist: String,
+        candidates: Vec<SearchResult>,
+    },
     RequestCache,
     NotFound,
     FetchError(AppError),
@@ -66,6 +211,7 @@ pub enum AutoSearchSource {
     Kugou,
     Netease,
     AmllDb,
+    CustomHttp,
 }
 
 impl AutoSearchSource {
@@ -76,6 +222,7 @@ impl AutoSearchSource {
             AutoSearchSource::Kugou => "酷狗音乐",
             AutoSearchSource::Netease => "网易云音乐",
             AutoSearchSource::AmllDb => "AMLL-DB",
+            AutoSearchSource::CustomHttp => "自定义源",
         }
     }
 
@@ -86,8 +233,32 @@ impl AutoSearchSource {
             Self::Netease,
             Self::QqMusic,
             Self::Kugou,
+            Self::CustomHttp,
         ]
     }
+
+    /// 规范化用户保存的优先级顺序：去除重复项，并把缺失的源按默认顺序追加到末尾。
+    pub fn normalize_order(order: &[Self]) -> Vec<Self> {
+        let mut normalized: Vec<Self> = Vec::with_capacity(order.len());
+        for source in order.iter().chain(Self::default_order().iter()) {
+            if !normalized.contains(source) {
+                normalized.push(*source);
+            }
+        }
+        normalized
+    }
+
+    /// 将该源转换为对应的在线提供商。本地缓存没有对应的提供商。
+    pub fn to_provider_name(self) -> Option<lyrics_helper_rs::ProviderName> {
+        match self {
+            Self::LocalCache => None,
+            Self::QqMusic => Some(lyrics_helper_rs::ProviderName::QQMusic),
+            Self::Kugou => Some(lyrics_helper_rs::ProviderName::Kugou),
+            Self::Netease => Some(lyrics_helper_rs::ProviderName::Netease),
+            Self::AmllDb => Some(lyrics_helper_rs::ProviderName::AmllTtmlDatabase),
+            Self::CustomHttp => Some(lyrics_helper_rs::ProviderName::CustomHttp),
+        }
+    }
 }
 
 impl From<String> for AutoSearchSource {
@@ -97,6 +268,7 @@ impl From<String> for AutoSearchSource {
             "kugou" => Self::Kugou,
             "netease" => Self::Netease,
             "amll-ttml-database" => Self::AmllDb,
+            "custom-http" => Self::CustomHttp,
             _ => {
                 tracing::warn!("未知的提供商名称 '{s}'，无法转换为 AutoSearchSource");
                 Self::QqMusic
@@ -112,6 +284,7 @@ impl From<AutoSearchSource> for &'static str {
             AutoSearchSource::Kugou => "kugou",
             AutoSearchSource::Netease => "netease",
             AutoSearchSource::AmllDb => "amll-ttml-database",
+            AutoSearchSource::CustomHttp => "custom-http",
             AutoSearchSource::LocalCache => "local",
         }
     }
@@ -132,6 +305,85 @@ impl Default for AutoSearchStatus {
     }
 }
 
+/// 搜索结果是否具备音节级（逐字）时间轴
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordTimingAvailability {
+    /// 具备逐字时间轴
+    WordTimed,
+    /// 只有逐行时间轴
+    LineTimed,
+    /// 无法判断
+    Unknown,
+}
+
+impl WordTimingAvailability {
+    /// 根据提供商通常返回的歌词格式推断。
+    ///
+    /// QQ 音乐 (QRC) 和酷狗 (KRC) 总是逐字的；网易云和 Musixmatch
+    /// 取决于具体歌曲是否有逐字歌词，需要预览后才能确定。
+    pub fn from_provider_name(provider_name: &str) -> Self {
+        match provider_name {
+            "qq" | "kugou" | "amll-ttml-database" => Self::WordTimed,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WordTimed => "逐字",
+            Self::LineTimed => "逐行",
+            Self::Unknown => "未知",
+        }
+    }
+}
+
+/// 下载器中搜索结果的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchResultOrigin {
+    /// 来自侧边栏的 SMTC 自动搜索
+    Auto,
+    /// 来自下载器中的手动搜索
+    Manual,
+    /// 两种搜索都找到了该结果
+    Both,
+}
+
+impl SearchResultOrigin {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Auto => "自动",
+            Self::Manual => "手动",
+            Self::Both => "自动+手动",
+        }
+    }
+}
+
+/// 预览搜索结果后得到的歌词概况，用于在结果列表中显示标签
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResultPreviewSummary {
+    pub is_word_timed: bool,
+    pub has_translation: bool,
+    /// 含有主歌词内容的行数，不包括间奏和空行
+    pub line_count: usize,
+}
+
+impl ResultPreviewSummary {
+    pub fn from_lines(lines: &[LyricLine]) -> Self {
+        let main_tracks = || {
+            lines
+                .iter()
+                .filter_map(LyricLine::main_track)
+                .filter(|track| !track.content.is_empty())
+        };
+        Self {
+            is_word_timed: main_tracks().any(|track| track.content.is_timed()),
+            has_translation: main_tracks()
+                .any(|track| track.translations.iter().any(|t| !t.is_empty())),
+            line_count: main_tracks().count(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
     Trace,
@@ -175,6 +427,93 @@ impl LogLevel {
     }
 }
 
+/// 警告面板中一条警告的级别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningLevel {
+    /// 内容被丢弃或无法解析，输出很可能不完整
+    Error,
+    Warning,
+    /// 可以安全忽略的提示
+    Hint,
+}
+
+impl WarningLevel {
+    pub const ALL: [WarningLevel; 3] = [
+        WarningLevel::Error,
+        WarningLevel::Warning,
+        WarningLevel::Hint,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WarningLevel::Error => "错误",
+            WarningLevel::Warning => "警告",
+            WarningLevel::Hint => "提示",
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            WarningLevel::Error => "❌",
+            WarningLevel::Warning => "⚠️",
+            WarningLevel::Hint => "ℹ",
+        }
+    }
+
+    pub fn color(self) -> egui::Color32 {
+        match self {
+            WarningLevel::Error => LogLevel::Error.color(),
+            WarningLevel::Warning => LogLevel::Warn.color(),
+            WarningLevel::Hint => LogLevel::Info.color(),
+        }
+    }
+}
+
+impl From<LintSeverity> for WarningLevel {
+    fn from(severity: LintSeverity) -> Self {
+        match severity {
+            LintSeverity::Error => WarningLevel::Error,
+            LintSeverity::Hint => WarningLevel::Hint,
+        }
+    }
+}
+
+/// 带级别的警告。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LyricWarning {
+    pub level: WarningLevel,
+    pub message: String,
+}
+
+impl LyricWarning {
+    pub fn new(level: WarningLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+        }
+    }
+
+    pub fn from_parser_warnings(warnings: &[ParseWarning]) -> Vec<Self> {
+        warnings.iter().map(Self::from).collect()
+    }
+}
+
+impl From<ParseWarningLevel> for WarningLevel {
+    fn from(level: ParseWarningLevel) -> Self {
+        match level {
+            ParseWarningLevel::Error => WarningLevel::Error,
+            ParseWarningLevel::Warning => WarningLevel::Warning,
+            ParseWarningLevel::Hint => WarningLevel::Hint,
+        }
+    }
+}
+
+impl From<&ParseWarning> for LyricWarning {
+    fn from(warning: &ParseWarning) -> Self {
+        Self::new(warning.level.into(), &warning.message)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LogEntry {
     pub level: LogLevel,
@@ -182,6 +521,18 @@ pub struct LogEntry {
     pub timestamp: DateTime<Local>,
 }
 
+impl LogEntry {
+    /// 复制到剪贴板的完整文本，格式与日志面板中的显示一致。
+    pub fn to_copy_text(&self) -> String {
+        format!(
+            "{} [{}] {}",
+            self.timestamp.format("[%H:%M:%S.%3f]"),
+            self.level.as_str(),
+            self.message
+        )
+    }
+}
+
 /// 歌词提供商的加载状态
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ProviderState {
@@ -194,3 +545,462 @@ pub enum ProviderState {
     /// 加载失败
     Failed(String),
 }
+
+/// 最近 SMTC 更新的原始值，用于在侧边栏中诊断时间轴校准问题。
+#[derive(Debug, Clone, Default)]
+pub struct SmtcDebugInfo {
+    /// 收到的 SMTC 曲目更新总数
+    pub update_count: u64,
+    /// 最近一次更新报告的原始播放位置
+    pub last_position_ms: Option<u64>,
+    /// 最近一次更新报告的位置时间戳（原样显示）
+    pub last_position_report_time: Option<String>,
+    /// 收到最近一次更新的本地时间
+    pub last_update_at: Option<std::time::Instant>,
+    /// 最近若干次更新之间的间隔
+    pub recent_intervals: std::collections::VecDeque<std::time::Duration>,
+}
+
+impl SmtcDebugInfo {
+    /// 保留的最近更新间隔数量
+    const MAX_INTERVALS: usize = 20;
+
+    /// 记录一次 SMTC 更新。
+    pub fn record(&mut self, position_ms: Option<u64>, position_report_time: Option<String>) {
+        let now = std::time::Instant::now();
+        if let Some(last) = self.last_update_at {
+            if self.recent_intervals.len() >= Self::MAX_INTERVALS {
+                self.recent_intervals.pop_front();
+            }
+            self.recent_intervals.push_back(now - last);
+        }
+
+        self.update_count += 1;
+        self.last_update_at = Some(now);
+        if position_ms.is_some() {
+            self.last_position_ms = position_ms;
+        }
+        if position_report_time.is_some() {
+            self.last_position_report_time = position_report_time;
+        }
+    }
+
+    /// 最近更新间隔的平均值
+    pub fn average_interval(&self) -> Option<std::time::Duration> {
+        let count = u32::try_from(self.recent_intervals.len()).ok()?;
+        if count == 0 {
+            return None;
+        }
+        Some(self.recent_intervals.iter().sum::<std::time::Duration>() / count)
+    }
+}
+
+/// 校对备注所在行的开始时间和文本，用于行数变化后重新找到备注对应的行。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineNoteAnchor {
+    pub start_ms: u64,
+    pub text: String,
+}
+
+impl LineNoteAnchor {
+    pub fn from_lines(lines: &[LyricLine]) -> Vec<Self> {
+        lines
+            .iter()
+            .map(|line| Self {
+                start_ms: line.start_ms,
+                text: line.main_text().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// 行数变化（拆分、合并、插入或删除行）后，把以行索引为键的备注移到对应行的新索引上。
+///
+/// 优先选择文本相同的行，有多行时取开始时间最接近的一行；没有文本相同的行（如拆分后）
+/// 时取开始时间最接近的行。多条备注落到同一行时（如合并两行）按原顺序拼接。
+pub fn remap_line_notes(
+    notes: &BTreeMap<usize, String>,
+    previous: &[LineNoteAnchor],
+    current: &[LineNoteAnchor],
+) -> BTreeMap<usize, String> {
+    if current.is_empty() {
+        return notes.clone();
+    }
+    let mut remapped = BTreeMap::new();
+    for (&index, note) in notes {
+        let target = previous.get(index).map_or(index, |anchor| {
+            nearest_line(current, anchor.start_ms, |line| line.text == anchor.text)
+                .or_else(|| nearest_line(current, anchor.start_ms, |_| true))
+                .unwrap_or(index)
+        });
+        append_line_note(&mut remapped, target, note);
+    }
+    remapped
+}
+
+/// 把旧版本会话中以行开始时间为键的备注换算为行索引。
+pub fn line_notes_from_start_times(
+    notes: &BTreeMap<u64, String>,
+    lines: &[LineNoteAnchor],
+) -> BTreeMap<usize, String> {
+    let mut converted = BTreeMap::new();
+    for (&start_ms, note) in notes {
+        if let Some(index) = nearest_line(lines, start_ms, |_| true) {
+            append_line_note(&mut converted, index, note);
+        }
+    }
+    converted
+}
+
+fn nearest_line(
+    lines: &[LineNoteAnchor],
+    start_ms: u64,
+    filter: impl Fn(&LineNoteAnchor) -> bool,
+) -> Option<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| filter(line))
+        .min_by_key(|(_, line)| line.start_ms.abs_diff(start_ms))
+        .map(|(index, _)| index)
+}
+
+fn append_line_note(notes: &mut BTreeMap<usize, String>, index: usize, note: &str) {
+    notes
+        .entry(index)
+        .and_modify(|existing| {
+            existing.push('\n');
+            existing.push_str(note);
+        })
+        .or_insert_with(|| note.to_string());
+}
+
+/// 多文件编辑时，一个已打开文件的编辑状态。
+///
+/// 当前正在编辑的文件的最新内容保存在 `LyricState` 中，切换文件时才会写回这里。
+#[derive(Debug, Clone)]
+pub struct OpenDocument {
+    pub path: std::path::PathBuf,
+    pub input_text: String,
+    pub source_format: LyricFormat,
+    pub translation_tracks: Vec<TranslationTrack>,
+    pub romanization_lrc: String,
+    /// 按行索引记录的校对备注
+    pub line_notes: BTreeMap<usize, String>,
+    /// 最近一次从磁盘加载或保存时的主歌词内容，用于判断是否有未保存的更改
+    pub saved_input_text: String,
+}
+
+impl OpenDocument {
+    pub fn new(path: std::path::PathBuf, content: String, source_format: LyricFormat) -> Self {
+        Self {
+            path,
+            saved_input_text: content.clone(),
+            input_text: content,
+            source_format,
+            translation_tracks: Vec::new(),
+            romanization_lrc: String::new(),
+            line_notes: BTreeMap::new(),
+        }
+    }
+
+    /// 在列表中显示的文件名。
+    pub fn display_name(&self) -> String {
+        self.path.file_name().map_or_else(
+            || self.path.to_string_lossy().into_owned(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.input_text != self.saved_input_text
+    }
+}
+
+/// 对比视图比较的两段文本。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffSource {
+    /// 最近一次简繁转换或后处理之前与之后的输出
+    #[default]
+    BeforeProcessing,
+    /// 输入与输出
+    InputOutput,
+}
+
+impl DiffSource {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DiffSource::BeforeProcessing => "处理前 / 处理后",
+            DiffSource::InputOutput => "输入 / 输出",
+        }
+    }
+}
+
+/// 从歌词中的元数据行推断出的元数据建议，等待用户确认后加入。
+#[derive(Debug, Clone)]
+pub struct MetadataSuggestion {
+    pub key: CanonicalMetadataKey,
+    pub value: String,
+    /// 元数据行中原始的键，例如“作词”
+    pub source_key: String,
+    pub selected: bool,
+}
+
+/// 音节平滑每次迭代的结果缓存，用于逐次增减迭代次数而不必从头重新处理。
+#[derive(Debug, Clone)]
+pub struct SmoothingHistory {
+    /// 第 k 项为迭代 k 次后的歌词行，第 0 项为平滑前的歌词
+    states: Vec<Vec<LyricLine>>,
+    options: SyllableSmoothingOptions,
+    /// 当前应用的迭代次数
+    pub current: usize,
+}
+
+impl SmoothingHistory {
+    /// 按设置中的迭代次数平滑歌词，并缓存每次迭代的中间结果。
+    pub fn new(lines: &[LyricLine], options: SyllableSmoothingOptions) -> Self {
+        Self {
+            states: syllable_smoothing::smoothing_iteration_states(
+                lines,
+                &options,
+                options.smoothing_iterations,
+            ),
+            options,
+            current: options.smoothing_iterations as usize,
+        }
+    }
+
+    pub fn current_lines(&self) -> &[LyricLine] {
+        &self.states[self.current]
+    }
+
+    /// 切换到指定的迭代次数，超出缓存范围时一次性计算并缓存到该次数为止的中间结果。
+    pub fn step_to(&mut self, iterations: usize) -> &[LyricLine] {
+        if iterations >= self.states.len() {
+            self.states = syllable_smoothing::smoothing_iteration_states(
+                &self.states[0],
+                &self.options,
+                u32::try_from(iterations).unwrap_or(u32::MAX),
+            );
+        }
+        self.current = iterations;
+        self.current_lines()
+    }
+}
+
+/// 时间轴编辑器中被拖动的部分。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimelineDragHandle {
+    Start,
+    End,
+    /// 整体平移
+    Body,
+}
+
+/// 时间轴编辑器中正在进行的拖动。松开鼠标前只更新预览，不修改歌词。
+#[derive(Debug, Clone, Copy)]
+pub struct TimelineDrag {
+    pub target: TimingTarget,
+    pub handle: TimelineDragHandle,
+    /// 拖动开始时的起止时间
+    pub original: (u64, u64),
+    /// 拖动中预览的起止时间
+    pub current: (u64, u64),
+    /// 累计的拖动距离（毫秒）
+    offset_ms: f32,
+}
+
+impl TimelineDrag {
+    pub fn new(target: TimingTarget, handle: TimelineDragHandle, range: (u64, u64)) -> Self {
+        Self {
+            target,
+            handle,
+            original: range,
+            current: range,
+            offset_ms: 0.0,
+        }
+    }
+
+    /// 累加拖动距离并重新计算预览时间，`snap` 用于把边界吸附到相邻的时间点。
+    ///
+    /// 调整边界时开始时间始终早于结束时间；整体平移时优先吸附开始时间，其次是结束时间。
+    pub fn drag_by(&mut self, delta_ms: f32, snap: impl Fn(u64) -> u64) {
+        self.offset_ms += delta_ms;
+        let offset = self.offset_ms.round() as i64;
+        let (start, end) = self.original;
+        let shifted = |ms: u64| ms.saturating_add_signed(offset);
+
+        self.current = match self.handle {
+            TimelineDragHandle::Start => (snap(shifted(start)).min(end.saturating_sub(1)), end),
+            TimelineDragHandle::End => (start, snap(shifted(end)).max(start + 1)),
+            TimelineDragHandle::Body => {
+                let duration = end.saturating_sub(start);
+                let new_start = shifted(start);
+                let snapped_start = snap(new_start);
+                let new_start = if snapped_start != new_start {
+                    snapped_start
+                } else {
+                    snap(new_start + duration).saturating_sub(duration)
+                };
+                (new_start, new_start + duration)
+            }
+        };
+    }
+}
+
+/// 歌词预览面板的播放进度，按本地时钟推进，不依赖播放器。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreviewPlayback {
+    /// 暂停时的进度（毫秒）
+    position_ms: u64,
+    /// 正在播放时，开始计时的时刻
+    playing_since: Option<std::time::Instant>,
+}
+
+impl PreviewPlayback {
+    pub fn is_playing(&self) -> bool {
+        self.playing_since.is_some()
+    }
+
+    /// 当前进度（毫秒）。
+    pub fn position_ms(&self) -> u64 {
+        match self.playing_since {
+            Some(since) => self.position_ms + since.elapsed().as_millis() as u64,
+            None => self.position_ms,
+        }
+    }
+
+    pub fn play(&mut self) {
+        if self.playing_since.is_none() {
+            self.playing_since = Some(std::time::Instant::now());
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.position_ms = self.position_ms();
+        self.playing_since = None;
+    }
+
+    /// 跳转到指定进度，保持当前的播放或暂停状态。
+    pub fn seek(&mut self, position_ms: u64) {
+        self.position_ms = position_ms;
+        if self.playing_since.is_some() {
+            self.playing_since = Some(std::time::Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lrc_merge_wizard_languages() {
+        let mut wizard = LrcMergeWizard::default();
+        assert_eq!(
+            wizard.language(LrcContentType::Translation),
+            Some(DEFAULT_TRANSLATION_LANGUAGE)
+        );
+
+        *wizard.language_mut(LrcContentType::Translation) = " en ".to_string();
+        wizard.language_mut(LrcContentType::Romanization).clear();
+        assert_eq!(wizard.language(LrcContentType::Translation), Some("en"));
+        assert_eq!(wizard.language(LrcContentType::Romanization), None);
+    }
+
+    #[test]
+    fn test_parser_warning_levels_map_directly() {
+        let warnings = vec![
+            ParseWarning::error("LRC秒数无效 (行 3): '75'"),
+            ParseWarning::warning("第 1 行: 未能识别的行格式。"),
+            ParseWarning::hint("第 4 行: 内容只包含空格，已跳过。"),
+        ];
+        let converted = LyricWarning::from_parser_warnings(&warnings);
+        assert_eq!(
+            converted,
+            vec![
+                LyricWarning::new(WarningLevel::Error, "LRC秒数无效 (行 3): '75'"),
+                LyricWarning::new(WarningLevel::Warning, "第 1 行: 未能识别的行格式。"),
+                LyricWarning::new(WarningLevel::Hint, "第 4 行: 内容只包含空格，已跳过。"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parser_warning_level_ignores_message_wording() {
+        // 级别只由解析器给出，措辞里的“失败”“已忽略”等字样不影响归类
+        let warning = ParseWarning::hint("解析失败的注音已忽略。");
+        assert_eq!(LyricWarning::from(&warning).level, WarningLevel::Hint);
+    }
+
+    fn anchor(start_ms: u64, text: &str) -> LineNoteAnchor {
+        LineNoteAnchor {
+            start_ms,
+            text: text.to_string(),
+        }
+    }
+
+    fn notes(entries: &[(usize, &str)]) -> BTreeMap<usize, String> {
+        entries
+            .iter()
+            .map(|&(index, note)| (index, note.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_remap_line_notes_follows_lines_after_insertion() {
+        let previous = vec![anchor(1000, "第一句"), anchor(5000, "第二句")];
+        let current = vec![
+            anchor(0, ""),
+            anchor(1000, "第一句"),
+            anchor(5000, "第二句"),
+        ];
+        let remapped = remap_line_notes(&notes(&[(1, "存疑")]), &previous, &current);
+        assert_eq!(remapped, notes(&[(2, "存疑")]));
+    }
+
+    #[test]
+    fn test_remap_line_notes_picks_nearest_repeated_line() {
+        let previous = vec![
+            anchor(1000, "副歌"),
+            anchor(3000, "间奏前"),
+            anchor(9000, "副歌"),
+        ];
+        let current = vec![anchor(1000, "副歌"), anchor(9000, "副歌")];
+        let remapped = remap_line_notes(&notes(&[(2, "第二遍副歌")]), &previous, &current);
+        assert_eq!(remapped, notes(&[(1, "第二遍副歌")]));
+    }
+
+    #[test]
+    fn test_remap_line_notes_merges_notes_of_merged_lines() {
+        let previous = vec![anchor(1000, "前半"), anchor(2000, "后半")];
+        let current = vec![anchor(1000, "前半后半")];
+        let remapped =
+            remap_line_notes(&notes(&[(0, "备注一"), (1, "备注二")]), &previous, &current);
+        assert_eq!(remapped, notes(&[(0, "备注一\n备注二")]));
+    }
+
+    #[test]
+    fn test_remap_line_notes_keeps_notes_when_all_lines_removed() {
+        let previous = vec![anchor(1000, "第一句")];
+        let original = notes(&[(0, "存疑")]);
+        assert_eq!(remap_line_notes(&original, &previous, &[]), original);
+    }
+
+    #[test]
+    fn test_legacy_line_notes_convert_to_indices() {
+        let lines = vec![
+            anchor(1000, "第一句"),
+            anchor(1000, "同时开始"),
+            anchor(5000, "第二句"),
+        ];
+        let legacy: BTreeMap<u64, String> =
+            [(1000, "开头".to_string()), (5000, "结尾".to_string())]
+                .into_iter()
+                .collect();
+        assert_eq!(
+            line_notes_from_start_times(&legacy, &lines),
+            notes(&[(0, "开头"), (2, "结尾")])
+        );
+    }
+}
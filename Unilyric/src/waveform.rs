@@ -0,0 +1,169 @@
+//! 时间轴编辑器中作为对齐参考的音频波形。
+//!
+//! 音频文件在后台线程中完整解码，按固定时间桶计算峰值，并据此粗略检测起音点，
+//! 供时间轴拖动时吸附使用。
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use rodio::{Decoder, Source};
+
+/// 每个峰值桶覆盖的时长（毫秒）。
+pub const WAVEFORM_BUCKET_MS: u64 = 10;
+/// 检测起音点时参考的前置桶数量。
+const ONSET_WINDOW_BUCKETS: usize = 10;
+/// 峰值相对前置平均值的最小涨幅。
+const ONSET_MIN_RISE: f32 = 0.15;
+/// 两个起音点之间的最短间隔（毫秒）。
+const ONSET_MIN_GAP_MS: u64 = 120;
+
+/// 解码后的音频波形。
+#[derive(Debug, Clone)]
+pub struct Waveform {
+    /// 音频文件名，用于界面显示
+    pub file_name: String,
+    /// 每个桶内的峰值，范围 0.0 到 1.0
+    pub peaks: Vec<f32>,
+    /// 检测到的起音点（毫秒），升序排列
+    pub onsets: Vec<u64>,
+}
+
+impl Waveform {
+    /// 解码音频文件并计算波形。会阻塞直到整首音频解码完成。
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("无法打开音频文件: {e}"))?;
+        let decoder =
+            Decoder::new(BufReader::new(file)).map_err(|e| format!("无法解码音频文件: {e}"))?;
+
+        let samples_per_bucket = (u64::from(decoder.sample_rate())
+            * u64::from(decoder.channels().max(1))
+            * WAVEFORM_BUCKET_MS
+            / 1000)
+            .max(1) as usize;
+
+        let mut peaks = Vec::new();
+        let mut bucket_peak = 0u16;
+        let mut bucket_len = 0;
+        for sample in decoder {
+            bucket_peak = bucket_peak.max(sample.unsigned_abs());
+            bucket_len += 1;
+            if bucket_len == samples_per_bucket {
+                peaks.push((f32::from(bucket_peak) / f32::from(i16::MAX)).min(1.0));
+                bucket_peak = 0;
+                bucket_len = 0;
+            }
+        }
+        if bucket_len > 0 {
+            peaks.push((f32::from(bucket_peak) / f32::from(i16::MAX)).min(1.0));
+        }
+        if peaks.is_empty() {
+            return Err("音频文件中没有任何采样".to_string());
+        }
+
+        let onsets = detect_onsets(&peaks);
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(Self {
+            file_name,
+            peaks,
+            onsets,
+        })
+    }
+
+    /// 音频总时长（毫秒）。
+    pub fn duration_ms(&self) -> u64 {
+        self.peaks.len() as u64 * WAVEFORM_BUCKET_MS
+    }
+
+    /// 返回 `[start_ms, end_ms)` 范围内的最大峰值。
+    pub fn peak_between(&self, start_ms: u64, end_ms: u64) -> f32 {
+        let start = (start_ms / WAVEFORM_BUCKET_MS) as usize;
+        let end = (end_ms.div_ceil(WAVEFORM_BUCKET_MS) as usize)
+            .max(start + 1)
+            .min(self.peaks.len());
+        self.peaks
+            .get(start..end)
+            .map_or(0.0, |peaks| peaks.iter().copied().fold(0.0, f32::max))
+    }
+}
+
+/// 峰值明显高于之前一小段的平均值时视为一个起音点。
+fn detect_onsets(peaks: &[f32]) -> Vec<u64> {
+    let min_gap_buckets = (ONSET_MIN_GAP_MS / WAVEFORM_BUCKET_MS) as usize;
+    let mut onsets = Vec::new();
+    let mut last_onset: Option<usize> = None;
+
+    for index in ONSET_WINDOW_BUCKETS..peaks.len() {
+        let window = &peaks[index - ONSET_WINDOW_BUCKETS..index];
+        let average = window.iter().sum::<f32>() / ONSET_WINDOW_BUCKETS as f32;
+        let peak = peaks[index];
+        if peak - average < ONSET_MIN_RISE || peak < average * 1.5 {
+            continue;
+        }
+        if last_onset.is_some_and(|last| index - last < min_gap_buckets) {
+            continue;
+        }
+        onsets.push(index as u64 * WAVEFORM_BUCKET_MS);
+        last_onset = Some(index);
+    }
+    onsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造一段静音中在指定桶处有突起的峰值序列。
+    fn peaks_with_spikes(len: usize, spikes: &[(usize, f32)]) -> Vec<f32> {
+        let mut peaks = vec![0.0; len];
+        for &(index, peak) in spikes {
+            peaks[index] = peak;
+        }
+        peaks
+    }
+
+    #[test]
+    fn test_detect_onsets_finds_spikes_after_silence() {
+        let peaks = peaks_with_spikes(60, &[(5, 0.9), (20, 0.8), (40, 0.8)]);
+
+        // 第 5 个桶之前不足一个窗口，不参与检测
+        assert_eq!(detect_onsets(&peaks), vec![200, 400]);
+        assert!(detect_onsets(&[0.5; 30]).is_empty());
+    }
+
+    #[test]
+    fn test_detect_onsets_respects_minimum_gap() {
+        let peaks = peaks_with_spikes(40, &[(20, 0.8), (25, 0.9)]);
+
+        assert_eq!(detect_onsets(&peaks), vec![200]);
+    }
+
+    #[test]
+    fn test_detect_onsets_requires_relative_rise() {
+        // 涨幅超过阈值，但没有达到平均值的 1.5 倍
+        let mut peaks = vec![0.4; 20];
+        peaks[15] = 0.58;
+        assert!(detect_onsets(&peaks).is_empty());
+
+        peaks[15] = 0.65;
+        assert_eq!(detect_onsets(&peaks), vec![150]);
+    }
+
+    #[test]
+    fn test_peak_between() {
+        let waveform = Waveform {
+            file_name: String::new(),
+            peaks: vec![0.1, 0.5, 0.3, 0.9],
+            onsets: Vec::new(),
+        };
+
+        assert_eq!(waveform.duration_ms(), 40);
+        assert_eq!(waveform.peak_between(0, 20), 0.5);
+        assert_eq!(waveform.peak_between(25, 25), 0.3);
+        assert_eq!(waveform.peak_between(35, 1000), 0.9);
+        assert_eq!(waveform.peak_between(50, 60), 0.0);
+    }
+}
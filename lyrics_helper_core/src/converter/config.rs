@@ -2,7 +2,7 @@ use bitflags::bitflags;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::LyricFormat;
+use crate::{AgentType, LyricFormat};
 
 /// TTML 生成时的计时模式
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -148,6 +148,66 @@ pub struct LrcParsingOptions {
     /// 定义如何处理具有相同时间戳的多行歌词的策略。
     #[serde(default)]
     pub same_timestamp_strategy: LrcSameTimestampStrategy,
+    /// 为 `true` 时不把 `[offset:]` 应用到时间戳，而是作为元数据保留，生成 LRC 时会原样写回。
+    ///
+    /// 默认按 LRC 约定把偏移量应用到所有时间戳（正值使歌词提前显示），并移除该标签。
+    #[serde(default)]
+    pub keep_offset_tag: bool,
+}
+
+/// 保存输出文件时使用的换行符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineEnding {
+    /// `\n`，Linux 和 macOS 的习惯
+    Lf,
+    /// `\r\n`，Windows 的习惯
+    Crlf,
+    /// 与源文件保持一致，没有源文件或无法判断时不做改动
+    Keep,
+}
+
+impl Default for LineEnding {
+    /// 默认使用当前平台的换行符。
+    fn default() -> Self {
+        if cfg!(windows) { Self::Crlf } else { Self::Lf }
+    }
+}
+
+impl LineEnding {
+    /// 判断文本使用的换行符，以第一个换行为准。文本中没有换行时返回 `None`。
+    #[must_use]
+    pub fn detect(text: &str) -> Option<Self> {
+        let index = text.find('\n')?;
+        if text[..index].ends_with('\r') {
+            Some(Self::Crlf)
+        } else {
+            Some(Self::Lf)
+        }
+    }
+
+    /// 把文本中的 `\r\n` 和单独的 `\r` 统一为 `\n`。
+    #[must_use]
+    pub fn normalize(text: &str) -> String {
+        if text.contains('\r') {
+            text.replace("\r\n", "\n").replace('\r', "\n")
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// 按该换行符风格转换文本。`Keep` 时跟随 `source` 的换行符。
+    #[must_use]
+    pub fn apply(self, text: &str, source: Option<&str>) -> String {
+        let resolved = match self {
+            Self::Keep => source.and_then(Self::detect),
+            other => Some(other),
+        };
+        match resolved {
+            Some(Self::Crlf) => Self::normalize(text).replace('\n', "\r\n"),
+            Some(Self::Lf) => Self::normalize(text),
+            Some(Self::Keep) | None => text.to_string(),
+        }
+    }
 }
 
 /// 统一管理所有格式的转换选项
@@ -177,6 +237,12 @@ pub struct ConversionOptions {
     /// 辅助歌词（如翻译）的匹配策略
     #[serde(default)]
     pub matching_strategy: AuxiliaryLineMatchingStrategy,
+    /// 保存输出文件时使用的换行符
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    /// 保存输出文件时在开头添加 UTF-8 BOM
+    #[serde(default)]
+    pub add_bom: bool,
 }
 
 /// ASS 生成转换选项
@@ -203,6 +269,8 @@ bitflags! {
         const ENABLE_REGEX_STRIPPING  = 1 << 2;
         /// 正则表达式匹配区分大小写
         const REGEX_CASE_SENSITIVE    = 1 << 3;
+        /// 将头部紧接的短行（如只有人名的署名行）也视为元数据
+        const ENABLE_SHORT_LINE_HEURISTIC = 1 << 4;
     }
 }
 
@@ -212,6 +280,23 @@ impl Default for MetadataStripperFlags {
     }
 }
 
+bitflags! {
+    /// 元数据清理器作用的文本类型
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct MetadataStripperTargets: u8 {
+        /// 主歌词：匹配的行连同整行歌词一起移除
+        const MAIN        = 1 << 0;
+        /// 翻译：只移除匹配的翻译，不影响所在的歌词行
+        const TRANSLATION = 1 << 1;
+    }
+}
+
+impl Default for MetadataStripperTargets {
+    fn default() -> Self {
+        Self::MAIN
+    }
+}
+
 /// 元数据扫描行数的限制
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanLimitConfig {
@@ -242,6 +327,10 @@ fn default_header_scan_limit() -> ScanLimitConfig {
     }
 }
 
+const fn default_short_line_max_chars() -> usize {
+    12
+}
+
 fn default_footer_scan_limit() -> ScanLimitConfig {
     ScanLimitConfig {
         ratio: 0.2,
@@ -257,6 +346,10 @@ pub struct MetadataStripperOptions {
     #[serde(default)]
     pub flags: MetadataStripperFlags,
 
+    /// 清理器作用的文本类型，每种类型独立判断。
+    #[serde(default)]
+    pub targets: MetadataStripperTargets,
+
     /// 用于匹配头部/尾部块的关键词列表。
     #[serde(default)]
     pub keywords: Vec<String>,
@@ -274,16 +367,22 @@ pub struct MetadataStripperOptions {
     /// 尾部扫描的行数限制。
     #[serde(default = "default_footer_scan_limit")]
     pub footer_scan_limit: ScanLimitConfig,
+
+    /// 短行启发式规则中，被视为元数据的行的最大字符数（不含空白）。
+    #[serde(default = "default_short_line_max_chars")]
+    pub short_line_max_chars: usize,
 }
 
 impl Default for MetadataStripperOptions {
     fn default() -> Self {
         Self {
             flags: Default::default(),
+            targets: Default::default(),
             keywords: Vec::new(),
             regex_patterns: Vec::new(),
             header_scan_limit: default_header_scan_limit(),
             footer_scan_limit: default_footer_scan_limit(),
+            short_line_max_chars: default_short_line_max_chars(),
         }
     }
 }
@@ -348,6 +447,16 @@ pub struct ChineseConversionOptions {
     /// 指定转换模式，默认为直接替换
     #[serde(default)]
     pub mode: ChineseConversionMode,
+
+    /// 用户自定义词典（原词，替换词），在标准转换之后按最长匹配追加应用，
+    /// 用于修正内置词典未覆盖的词。
+    #[serde(default)]
+    pub custom_dictionary: Vec<(String, String)>,
+
+    /// 翻译轨道使用的 `OpenCC` 配置，与主歌词的 `config` 互相独立。
+    /// 当值为 `Some(config)` 时，直接替换主歌词和背景人声的所有已有翻译。
+    #[serde(default)]
+    pub translation_config: Option<ChineseConversionConfig>,
 }
 
 /// 简繁转换的模式
@@ -387,6 +496,16 @@ pub enum LrcEndTimeOutputMode {
     },
 }
 
+/// LRC 生成时时间标签中秒的小数位数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LrcTimestampPrecision {
+    /// 两位小数（百分之一秒），如 `[01:23.46]`，按四舍五入取整
+    Centiseconds,
+    /// [默认] 三位小数（毫秒），如 `[01:23.456]`
+    #[default]
+    Milliseconds,
+}
+
 /// LRC 生成选项
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 #[builder(setter(into), default)]
@@ -395,6 +514,12 @@ pub struct LrcGenerationOptions {
     pub sub_lines_output_mode: LrcSubLinesOutputMode,
     /// 控制行结束时间标记的输出方式
     pub end_time_output_mode: LrcEndTimeOutputMode,
+    /// 时间标签的精度
+    #[serde(default)]
+    pub timestamp_precision: LrcTimestampPrecision,
+    /// 增强模式：按增强型 LRC 格式输出逐字时间标签，等同于 `LyricFormat::EnhancedLrc`
+    #[serde(default)]
+    pub word_timing: bool,
 }
 
 impl Default for LrcGenerationOptions {
@@ -402,6 +527,8 @@ impl Default for LrcGenerationOptions {
         Self {
             sub_lines_output_mode: LrcSubLinesOutputMode::Ignore,
             end_time_output_mode: LrcEndTimeOutputMode::Never,
+            timestamp_precision: LrcTimestampPrecision::Milliseconds,
+            word_timing: false,
         }
     }
 }
@@ -410,9 +537,22 @@ impl Default for LrcGenerationOptions {
 // 9. 平滑优化选项
 // =============================================================================
 
+/// 背景人声轨道的平滑方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BackgroundSmoothingMode {
+    /// 不平滑背景人声
+    #[default]
+    Skip,
+    /// 与主歌词使用相同的参数
+    SameAsMain,
+    /// 使用单独的平滑因子和分组阈值，迭代次数与主歌词相同
+    Custom,
+}
+
 /// 控制平滑优化的选项。
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Builder)]
 #[builder(setter(into), default)]
+#[serde(default)]
 pub struct SyllableSmoothingOptions {
     /// 用于平滑的因子 (0.0 ~ 0.5)。
     pub factor: f64,
@@ -422,6 +562,14 @@ pub struct SyllableSmoothingOptions {
     pub gap_threshold_ms: u64,
     /// 组内平滑的次数。
     pub smoothing_iterations: u32,
+    /// 背景人声轨道的平滑方式。
+    pub background_mode: BackgroundSmoothingMode,
+    /// 背景人声使用单独参数时的平滑因子 (0.0 ~ 0.5)。
+    pub background_factor: f64,
+    /// 背景人声使用单独参数时的时长差异阈值（毫秒）。
+    pub background_duration_threshold_ms: u64,
+    /// 背景人声使用单独参数时的间隔阈值（毫秒）。
+    pub background_gap_threshold_ms: u64,
 }
 
 impl Default for SyllableSmoothingOptions {
@@ -431,6 +579,163 @@ impl Default for SyllableSmoothingOptions {
             duration_threshold_ms: 50,
             gap_threshold_ms: 100,
             smoothing_iterations: 5,
+            background_mode: BackgroundSmoothingMode::Skip,
+            background_factor: 0.1,
+            background_duration_threshold_ms: 80,
+            background_gap_threshold_ms: 100,
+        }
+    }
+}
+
+// =============================================================================
+// 10. 正则替换选项
+// =============================================================================
+
+/// 一条"查找正则 → 替换"规则。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegexReplaceRule {
+    /// 是否启用此规则。
+    pub enabled: bool,
+    /// 查找用的正则表达式。
+    pub pattern: String,
+    /// 替换文本，支持 `$1`、`${name}` 形式的捕获组引用。
+    pub replacement: String,
+}
+
+impl Default for RegexReplaceRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pattern: String::new(),
+            replacement: String::new(),
         }
     }
 }
+
+/// 控制正则替换处理器的选项。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegexReplaceOptions {
+    /// 按顺序依次应用的替换规则。
+    pub rules: Vec<RegexReplaceRule>,
+}
+
+// =============================================================================
+// 11. 智能换行选项
+// =============================================================================
+
+/// 控制智能换行处理器的选项。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LineWrapOptions {
+    /// 主歌词超过此字符数的行会被拆分。
+    pub max_chars: usize,
+}
+
+impl Default for LineWrapOptions {
+    fn default() -> Self {
+        Self { max_chars: 30 }
+    }
+}
+
+// =============================================================================
+// 12. 标点规范化选项
+// =============================================================================
+
+/// 标点规范化的目标宽度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PunctuationWidth {
+    /// 统一为全角标点。
+    #[default]
+    FullWidth,
+    /// 统一为半角标点。
+    HalfWidth,
+}
+
+/// 控制标点规范化处理器的选项。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PunctuationNormalizeOptions {
+    /// 标点统一的目标宽度。
+    pub target: PunctuationWidth,
+    /// 统一为全角时，只处理含有中日韩文字的行，避免改动纯西文歌词。
+    pub fullwidth_only_cjk_lines: bool,
+    /// 是否将全角数字转为半角。
+    pub halfwidth_digits: bool,
+    /// 是否将全角拉丁字母转为半角。
+    pub halfwidth_latin: bool,
+}
+
+impl Default for PunctuationNormalizeOptions {
+    fn default() -> Self {
+        Self {
+            target: PunctuationWidth::FullWidth,
+            fullwidth_only_cjk_lines: true,
+            halfwidth_digits: true,
+            halfwidth_latin: true,
+        }
+    }
+}
+
+// =============================================================================
+// 13. 演唱者识别选项
+// =============================================================================
+
+/// 一条行首括号标记到演唱者的映射规则，例如 `(合)` 对应合唱。
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BracketAgentRule {
+    /// 括号内的标记文本，如 `合`、`男`。
+    pub marker: String,
+    /// 分配的演唱者名称，留空时使用标记文本。
+    pub agent_name: String,
+    /// 分配的演唱者类型。
+    pub agent_type: AgentType,
+}
+
+impl BracketAgentRule {
+    /// 实际分配的演唱者名称。
+    #[must_use]
+    pub fn display_name(&self) -> &str {
+        let name = self.agent_name.trim();
+        if name.is_empty() {
+            self.marker.trim()
+        } else {
+            name
+        }
+    }
+}
+
+/// 控制演唱者识别器的选项。
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentRecognizerOptions {
+    /// 行首括号标记的映射规则，命中时优先于默认的“名称：”识别。
+    pub bracket_rules: Vec<BracketAgentRule>,
+    /// 置信度阈值 (0.0-1.0)。“名称：”标记的置信度低于该值时不视为演唱者标记，
+    /// 该行保留原文并沿用之前的演唱者。为 0 时不过滤。
+    pub min_confidence: f32,
+}
+
+// =============================================================================
+// 14. 音节重叠修复选项
+// =============================================================================
+
+/// 修复音节时间重叠的方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OverlapFixMode {
+    /// 把重叠音节的结束时间裁到下一个音节的开始时间。
+    #[default]
+    TrimToNextStart,
+    /// 在重叠的一段音节内，按各音节原时长的比例重新分配时间。
+    Redistribute,
+}
+
+/// 控制音节重叠修复处理器的选项。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyllableOverlapFixOptions {
+    /// 修复方式。
+    pub mode: OverlapFixMode,
+}
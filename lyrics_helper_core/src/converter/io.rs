@@ -70,6 +70,17 @@ pub enum BatchEntryStatus {
     Failed(String),
     /// 跳过转换，通常因为在配对逻辑中未能找到匹配的主歌词文件（针对辅助歌词文件）。
     SkippedNoMatch,
+    /// 试运行完成，转换成功但没有写入文件。
+    DryRun {
+        /// 正式运行时将写入的输出文件路径。
+        output_path: PathBuf,
+        /// 将写入的内容大小（字节）。
+        output_size: usize,
+        /// 输出路径上是否已存在文件，正式运行时会被覆盖。
+        overwrites_existing: bool,
+        /// 转换过程中产生的警告信息。
+        warnings: Vec<String>,
+    },
 }
 
 /// 批量转换配置的唯一标识符。
@@ -238,6 +249,48 @@ pub struct BatchInput {
     pub output_dir: PathBuf,
     /// 所有任务的目标输出格式。
     pub target_format: LyricFormat,
+    /// 主歌词候选文件的扩展名白名单（不含点，不区分大小写）。为空时不过滤。
+    #[serde(default)]
+    pub main_extension_whitelist: Vec<String>,
+    /// 主歌词与翻译、罗马音文件的配对规则。
+    #[serde(default)]
+    pub pairing: FilePairingOptions,
+}
+
+/// 批量转换时按文件名配对主歌词与翻译、罗马音文件的规则。
+///
+/// 文件名（不含扩展名）以某个后缀结尾时，去掉后缀后的部分即为所属主歌词的基础名，
+/// 例如 `song_tr.lrc` 会作为 `song.lrc` 的翻译。后缀匹配不区分大小写，
+/// 同时匹配多个后缀时取最长的一个。未匹配任何后缀的文件再按 `song.zh.lrc` 这样的语言标签配对。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilePairingOptions {
+    /// 是否自动配对。关闭时每个文件都作为独立的主歌词处理。
+    pub enabled: bool,
+    /// 翻译文件的文件名后缀，如 `_tr`、`.translation`。
+    pub translation_suffixes: Vec<String>,
+    /// 罗马音文件的文件名后缀，如 `_romaji`、`.romanization`。
+    pub romanization_suffixes: Vec<String>,
+}
+
+impl Default for FilePairingOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            translation_suffixes: ["_tr", "_translation", "_trans", ".tr", ".translation"]
+                .map(String::from)
+                .to_vec(),
+            romanization_suffixes: [
+                "_romaji",
+                "_romanization",
+                "_roma",
+                ".romaji",
+                ".romanization",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
 }
 
 /// 表示一个转换任务，可以是单个文件或批量处理。
@@ -265,4 +318,7 @@ pub struct FullConversionResult {
     pub output_lyrics: String,
     /// 在转换开始时从输入解析出的源数据。
     pub source_data: ParsedSourceData,
+    /// 生成目标格式时产生的警告，例如目标格式不支持的特性被降级或丢弃。
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
@@ -138,7 +138,10 @@ impl MetadataStore {
     /// 包括：
     /// 1. 移除每个值首尾的空白字符。
     /// 2. 移除完全为空的元数据条目。
-    /// 3. 对每个键的值列表进行排序和去重。
+    /// 3. 对每个键的值列表去重，保留每个值首次出现的位置。
+    ///
+    /// 值的顺序会被保留，因此调用方可以把优先的值（如用户固定的条目）放在前面，
+    /// 生成只能容纳单个值的标签时会使用第一个值。
     pub fn deduplicate_values(&mut self) {
         let mut keys_to_remove: Vec<CanonicalMetadataKey> = Vec::new();
         for (key, values) in &mut self.data {
@@ -152,8 +155,8 @@ impl MetadataStore {
                 continue;
             }
 
-            values.sort_unstable();
-            values.dedup();
+            let mut seen = HashSet::new();
+            values.retain(|v| seen.insert(v.clone()));
         }
 
         // 移除所有值都为空的键
@@ -176,49 +179,43 @@ impl MetadataStore {
 
     /// 生成通用的LRC元数据头部字符串。
     ///
-    /// 包括：
-    /// - ti
-    /// - ar
-    /// - al
-    /// - by
-    /// - language
-    /// - offset
+    /// 只写入 LRC 约定的标准标签：
+    /// - ti、al、language、offset、length：只写第一个值
+    /// - ar、au（词曲作者）、by：多个值使用 "/" 连接
     ///
-    /// 有多个值的，使用 "/" 连接（offset 除外）。
+    /// 平台 ID、ISRC 等其余元数据没有对应的 LRC 标签，不会写入。
     #[must_use]
     pub fn generate_lrc_header(&self) -> String {
         let mut output = String::new();
-        let mut written_keys: HashSet<&CanonicalMetadataKey> = HashSet::new();
-
-        // 定义LRC标签和对应的CanonicalMetadataKey的映射
-        let lrc_tags_to_write: Vec<(CanonicalMetadataKey, &str)> = vec![
-            (CanonicalMetadataKey::Title, "ti"),
-            (CanonicalMetadataKey::Artist, "ar"),
-            (CanonicalMetadataKey::Album, "al"),
-            (CanonicalMetadataKey::TtmlAuthorGithubLogin, "by"),
-            (CanonicalMetadataKey::Language, "language"),
-            (CanonicalMetadataKey::Offset, "offset"),
+
+        // 定义LRC标签和对应的CanonicalMetadataKey的映射，以及是否只写第一个值
+        let lrc_tags_to_write: Vec<(CanonicalMetadataKey, &str, bool)> = vec![
+            (CanonicalMetadataKey::Title, "ti", true),
+            (CanonicalMetadataKey::Artist, "ar", false),
+            (CanonicalMetadataKey::Album, "al", true),
+            (CanonicalMetadataKey::Songwriter, "au", false),
+            (CanonicalMetadataKey::TtmlAuthorGithubLogin, "by", false),
+            (CanonicalMetadataKey::Language, "language", true),
+            (CanonicalMetadataKey::Offset, "offset", true),
+            (
+                CanonicalMetadataKey::Custom("length".to_string()),
+                "length",
+                true,
+            ),
         ];
 
-        for (key_type, lrc_tag_name) in &lrc_tags_to_write {
-            if let Some(values) = self.data.get(key_type) {
-                if values.is_empty() {
-                    continue;
-                }
-
-                // 对所有非 offset 的键，都用 "/" 连接多个值
-                let value_to_write = if key_type != &CanonicalMetadataKey::Offset {
-                    values.join("/")
-                } else if let Some(first_value) = values.first() {
-                    first_value.clone()
-                } else {
-                    continue;
-                };
-
-                if !value_to_write.trim().is_empty() || *lrc_tag_name == "offset" {
-                    let _ = writeln!(output, "[{}:{}]", lrc_tag_name, value_to_write.trim());
-                    written_keys.insert(key_type);
-                }
+        for (key_type, lrc_tag_name, single_value) in &lrc_tags_to_write {
+            let Some(values) = self.data.get(key_type) else {
+                continue;
+            };
+            let value_to_write = if *single_value {
+                values.first().cloned().unwrap_or_default()
+            } else {
+                values.join("/")
+            };
+
+            if !value_to_write.trim().is_empty() || *lrc_tag_name == "offset" {
+                let _ = writeln!(output, "[{}:{}]", lrc_tag_name, value_to_write.trim());
             }
         }
         output
@@ -277,3 +274,62 @@ impl From<&ParsedSourceData> for MetadataStore {
         store
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deduplicate_values_keeps_first_occurrence_order() {
+        let mut store = MetadataStore::new();
+        store.set_multiple(
+            "artist",
+            vec![
+                " 乙 ".to_string(),
+                "甲".to_string(),
+                "乙".to_string(),
+                String::new(),
+                "甲".to_string(),
+            ],
+        );
+        store.set_multiple("album", vec!["  ".to_string()]);
+
+        store.deduplicate_values();
+
+        assert_eq!(
+            store.get_multiple_values(&CanonicalMetadataKey::Artist),
+            Some(&vec!["乙".to_string(), "甲".to_string()])
+        );
+        assert!(
+            store
+                .get_multiple_values(&CanonicalMetadataKey::Album)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_lrc_header_writes_only_standard_tags() {
+        let mut store = MetadataStore::new();
+        store.set_multiple("title", vec!["固定标题".to_string(), "标题".to_string()]);
+        store.set_multiple("artist", vec!["甲".to_string(), "乙".to_string()]);
+        store.set_single("songwriter", "丙");
+        store.set_single("offset", "-200");
+        store.set_single("length", "03:45");
+        store.set_single("ncmMusicId", "12345");
+        store.set_single("isrc", "USRC17607839");
+        store.set_single("mood", "happy");
+
+        assert_eq!(
+            store.generate_lrc_header(),
+            "[ti:固定标题]\n[ar:甲/乙]\n[au:丙]\n[offset:-200]\n[length:03:45]\n"
+        );
+    }
+
+    #[test]
+    fn test_lrc_header_is_empty_without_standard_tags() {
+        let mut store = MetadataStore::new();
+        store.set_single("qqMusicId", "001");
+
+        assert!(store.generate_lrc_header().is_empty());
+    }
+}
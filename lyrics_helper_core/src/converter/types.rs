@@ -1,4 +1,9 @@
-use std::{collections::HashMap, fmt, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::{LazyLock, RwLock},
+};
 
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
@@ -36,6 +41,12 @@ pub enum LyricFormat {
     Lqe,
     /// 酷狗 KRC 格式。
     Krc,
+    /// `WebVTT` 字幕格式。
+    Vtt,
+    /// Musixmatch `RichSync` JSON 格式。
+    Musixmatch,
+    /// `LyricsX` 的 LRCX 格式，以附加行携带翻译、罗马音和逐字时间。
+    Lrcx,
 }
 
 impl LyricFormat {
@@ -55,6 +66,9 @@ impl LyricFormat {
             LyricFormat::Spl => "spl",
             LyricFormat::Lqe => "lqe",
             LyricFormat::Krc => "krc",
+            LyricFormat::Vtt => "vtt",
+            LyricFormat::Musixmatch => "json",
+            LyricFormat::Lrcx => "lrcx",
         }
     }
 
@@ -68,13 +82,16 @@ impl LyricFormat {
             "JSON" => Some(LyricFormat::AppleMusicJson),
             "LYS" | "LYRICIFYSYLLABLE" => Some(LyricFormat::Lys),
             "LRC" => Some(LyricFormat::Lrc),
-            "ENHANCEDLRC" | "LRCX" | "ELRC" | "ALRC" => Some(LyricFormat::EnhancedLrc),
+            "ENHANCEDLRC" | "ELRC" | "ALRC" => Some(LyricFormat::EnhancedLrc),
             "QRC" => Some(LyricFormat::Qrc),
             "YRC" => Some(LyricFormat::Yrc),
             "LYL" | "LYRICIFYLINES" => Some(LyricFormat::Lyl),
             "SPL" => Some(LyricFormat::Spl),
             "LQE" | "LYRICIFYQUICKEXPORT" => Some(LyricFormat::Lqe),
             "KRC" => Some(LyricFormat::Krc),
+            "VTT" | "WEBVTT" => Some(LyricFormat::Vtt),
+            "MUSIXMATCH" | "RICHSYNC" => Some(LyricFormat::Musixmatch),
+            "LRCX" | "LYRICSX" => Some(LyricFormat::Lrcx),
             _ => None,
         }
     }
@@ -95,6 +112,9 @@ impl fmt::Display for LyricFormat {
             LyricFormat::Spl => write!(f, "SPL"),
             LyricFormat::Lqe => write!(f, "Lyricify Quick Export"),
             LyricFormat::Krc => write!(f, "KRC"),
+            LyricFormat::Vtt => write!(f, "WebVTT"),
+            LyricFormat::Musixmatch => write!(f, "JSON (Musixmatch)"),
+            LyricFormat::Lrcx => write!(f, "LRCX (LyricsX)"),
         }
     }
 }
@@ -244,6 +264,12 @@ impl AgentStore {
     }
 }
 
+/// 间奏标记行使用的歌曲组成部分名称。
+///
+/// 间奏标记是一个不含任何歌词内容、`song_part` 为此值的行，
+/// 在 TTML 中会被写为 `itunes:song-part="Interlude"` 的 `<div>` 内的空 `<p>`。
+pub const INTERLUDE_SONG_PART: &str = "Interlude";
+
 /// 歌词行结构，作为多个并行带注解轨道的容器。
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Builder)]
 #[builder(default)]
@@ -344,6 +370,28 @@ impl LyricLine {
         }
     }
 
+    /// 创建一个间奏标记行。
+    #[must_use]
+    pub fn new_interlude(start_ms: u64, end_ms: u64) -> Self {
+        Self {
+            start_ms,
+            end_ms,
+            song_part: Some(INTERLUDE_SONG_PART.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// 判断该行是否为间奏标记行，即标记为间奏且不含任何歌词内容。
+    #[must_use]
+    pub fn is_interlude(&self) -> bool {
+        self.song_part.as_deref() == Some(INTERLUDE_SONG_PART)
+            && self.tracks.iter().all(|track| {
+                track.content.is_empty()
+                    && track.translations.iter().all(LyricTrack::is_empty)
+                    && track.romanizations.iter().all(LyricTrack::is_empty)
+            })
+    }
+
     /// 返回一个迭代器，用于遍历所有指定内容类型的带注解轨道。
     pub fn tracks_by_type(
         &self,
@@ -646,20 +694,161 @@ impl CanonicalMetadataKey {
             Self::Custom(_) => 1000,
         }
     }
+
+    /// 检查值是否符合该键要求的格式。
+    ///
+    /// 只校验有固定格式的键（ISRC、语言代码、偏移量和部分平台 ID），
+    /// 其余键以及空值总是视为合法。
+    ///
+    /// # Errors
+    /// 值不合法时返回说明正确格式的提示。
+    pub fn validate_value(&self, value: &str) -> Result<(), &'static str> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(());
+        }
+
+        let is_valid = match self {
+            Self::Isrc => is_valid_isrc(value),
+            Self::Language => is_valid_language_tag(value),
+            Self::Offset => value.parse::<i64>().is_ok(),
+            Self::NcmMusicId | Self::AppleMusicId => value.chars().all(|c| c.is_ascii_digit()),
+            Self::SpotifyId => {
+                value.len() == 22 && value.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+            _ => true,
+        };
+        if is_valid {
+            return Ok(());
+        }
+
+        Err(match self {
+            Self::Isrc => {
+                "ISRC 应为 12 位：2 位国家代码 + 3 位登记者代码 + 2 位年份 + 5 位编号，如 USRC17607839"
+            }
+            Self::Language => "语言代码应为 BCP-47 格式，如 zh-Hans、ja、en-US",
+            Self::Offset => "偏移量应为整数毫秒，如 -200",
+            Self::NcmMusicId | Self::AppleMusicId => "ID 应只包含数字",
+            Self::SpotifyId => "Spotify ID 应为 22 位字母或数字",
+            _ => "格式不正确",
+        })
+    }
+}
+
+/// 检查 ISRC 格式，允许使用连字符分隔（如 `US-RC1-76-07839`）。
+fn is_valid_isrc(value: &str) -> bool {
+    let code: Vec<char> = value.chars().filter(|&c| c != '-').collect();
+    code.len() == 12
+        && code[..2].iter().all(char::is_ascii_alphabetic)
+        && code[2..5].iter().all(char::is_ascii_alphanumeric)
+        && code[5..].iter().all(char::is_ascii_digit)
+}
+
+/// 按 BCP-47 的基本结构检查语言标签：主语言子标签为 2-3 或 5-8 个字母，
+/// 其余子标签为 1-8 个字母或数字。
+fn is_valid_language_tag(value: &str) -> bool {
+    let mut subtags = value.split('-');
+    let primary_is_valid = subtags.next().is_some_and(|primary| {
+        matches!(primary.len(), 2..=3 | 5..=8) && primary.chars().all(|c| c.is_ascii_alphabetic())
+    });
+    primary_is_valid
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// 内置的元数据键别名表。
+///
+/// 别名均为规范化后的形式（小写，去除空格、下划线和连字符），
+/// 用于把不同来源中五花八门的同义键归一化到规范键。
+/// 含义有歧义的键（如 `name`）和仅表示作曲的键不收录，保留为自定义键以免丢失信息。
+static BUILTIN_METADATA_KEY_ALIASES: &[(&str, CanonicalMetadataKey)] = &[
+    ("songname", CanonicalMetadataKey::Title),
+    ("标题", CanonicalMetadataKey::Title),
+    ("歌名", CanonicalMetadataKey::Title),
+    ("曲名", CanonicalMetadataKey::Title),
+    ("singer", CanonicalMetadataKey::Artist),
+    ("singers", CanonicalMetadataKey::Artist),
+    ("performer", CanonicalMetadataKey::Artist),
+    ("歌手", CanonicalMetadataKey::Artist),
+    ("演唱", CanonicalMetadataKey::Artist),
+    ("艺术家", CanonicalMetadataKey::Artist),
+    ("albumname", CanonicalMetadataKey::Album),
+    ("专辑", CanonicalMetadataKey::Album),
+    ("author", CanonicalMetadataKey::Songwriter),
+    ("authors", CanonicalMetadataKey::Songwriter),
+    ("lyricist", CanonicalMetadataKey::Songwriter),
+    ("lyricists", CanonicalMetadataKey::Songwriter),
+    ("writer", CanonicalMetadataKey::Songwriter),
+    ("作词", CanonicalMetadataKey::Songwriter),
+    ("词曲", CanonicalMetadataKey::Songwriter),
+    ("语言", CanonicalMetadataKey::Language),
+    ("ncmid", CanonicalMetadataKey::NcmMusicId),
+    ("neteaseid", CanonicalMetadataKey::NcmMusicId),
+    ("qqid", CanonicalMetadataKey::QqMusicId),
+    ("appleid", CanonicalMetadataKey::AppleMusicId),
+];
+
+/// 运行时注册的额外别名，优先于内置别名表。
+static CUSTOM_METADATA_KEY_ALIASES: LazyLock<RwLock<HashMap<String, CanonicalMetadataKey>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// 将元数据键规范化为别名表使用的形式：小写，并去除空格、下划线和连字符。
+fn normalize_metadata_key(s: &str) -> String {
+    s.trim()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+impl CanonicalMetadataKey {
+    /// 注册一个额外的元数据键别名，之后 `from_str` 会把该别名解析为 `key`。
+    ///
+    /// 别名会被规范化（忽略大小写、空格、下划线和连字符），
+    /// 并优先于内置别名表。
+    pub fn register_alias(alias: &str, key: CanonicalMetadataKey) {
+        let normalized = normalize_metadata_key(alias);
+        if normalized.is_empty() {
+            return;
+        }
+        if let Ok(mut aliases) = CUSTOM_METADATA_KEY_ALIASES.write() {
+            aliases.insert(normalized, key);
+        }
+    }
+
+    /// 在别名表中查找规范化后的键。
+    fn from_alias(normalized: &str) -> Option<Self> {
+        if let Ok(aliases) = CUSTOM_METADATA_KEY_ALIASES.read()
+            && let Some(key) = aliases.get(normalized)
+        {
+            return Some(key.clone());
+        }
+
+        BUILTIN_METADATA_KEY_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == normalized)
+            .map(|(_, key)| key.clone())
+    }
 }
 
 impl FromStr for CanonicalMetadataKey {
     type Err = ParseCanonicalMetadataKeyError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        let normalized = normalize_metadata_key(s);
+        if let Some(key) = Self::from_alias(&normalized) {
+            return Ok(key);
+        }
+
+        match normalized.as_str() {
             "ti" | "title" | "musicname" => Ok(Self::Title),
             "ar" | "artist" | "artists" => Ok(Self::Artist),
             "al" | "album" => Ok(Self::Album),
             "by" | "ttmlauthorgithublogin" => Ok(Self::TtmlAuthorGithubLogin),
             "language" | "lang" => Ok(Self::Language),
             "offset" => Ok(Self::Offset),
-            "songwriter" | "songwriters" => Ok(Self::Songwriter),
+            "au" | "songwriter" | "songwriters" => Ok(Self::Songwriter),
             "ncmmusicid" => Ok(Self::NcmMusicId),
             "qqmusicid" => Ok(Self::QqMusicId),
             "spotifyid" => Ok(Self::SpotifyId),
@@ -676,6 +865,58 @@ impl FromStr for CanonicalMetadataKey {
 // 5. 处理与数据结构体
 //=============================================================================
 
+/// 解析警告的严重程度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParseWarningLevel {
+    /// 有内容因无法解析而丢失
+    Error,
+    /// 内容已保留，但可能与源文件的本意不一致
+    Warning,
+    /// 只跳过或忽略了无关紧要的内容
+    Hint,
+}
+
+/// 解析过程中产生的一条警告。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseWarning {
+    /// 严重程度，由产生警告的解析器决定
+    pub level: ParseWarningLevel,
+    /// 面向用户的警告文本
+    pub message: String,
+}
+
+impl ParseWarning {
+    /// 创建一条错误级别的警告。
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            level: ParseWarningLevel::Error,
+            message: message.into(),
+        }
+    }
+
+    /// 创建一条警告级别的警告。
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            level: ParseWarningLevel::Warning,
+            message: message.into(),
+        }
+    }
+
+    /// 创建一条提示级别的警告。
+    pub fn hint(message: impl Into<String>) -> Self {
+        Self {
+            level: ParseWarningLevel::Hint,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
 /// 存储从源文件解析出的、准备进行进一步处理或转换的歌词数据。
 /// 这是解析阶段的主要输出，也是后续处理和生成阶段的主要输入。
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
@@ -692,8 +933,8 @@ pub struct ParsedSourceData {
     pub agents: AgentStore,
     /// 指示源文件是否是逐行歌词（例如LRC）。
     pub is_line_timed_source: bool,
-    /// 解析过程中产生的警告信息列表。
-    pub warnings: Vec<String>,
+    /// 解析过程中产生的警告列表。
+    pub warnings: Vec<ParseWarning>,
     /// 指示输入的TTML 是否被格式化。
     /// 这影响空格和换行的处理。
     pub detected_formatted_ttml_input: Option<bool>,
@@ -730,3 +971,104 @@ pub mod lys_properties {
     /// 视图：右，人声：背景
     pub const BG_RIGHT: u8 = 8;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_key(s: &str) -> CanonicalMetadataKey {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_builtin_aliases_ignore_case_and_separators() {
+        assert_eq!(parse_key("Song Name"), CanonicalMetadataKey::Title);
+        assert_eq!(parse_key("song_name"), CanonicalMetadataKey::Title);
+        assert_eq!(parse_key("ALBUM-NAME"), CanonicalMetadataKey::Album);
+        assert_eq!(parse_key(" Singers "), CanonicalMetadataKey::Artist);
+        assert_eq!(parse_key("歌手"), CanonicalMetadataKey::Artist);
+        assert_eq!(parse_key("作词"), CanonicalMetadataKey::Songwriter);
+        assert_eq!(parse_key("Netease_ID"), CanonicalMetadataKey::NcmMusicId);
+    }
+
+    #[test]
+    fn test_ambiguous_keys_stay_custom() {
+        assert_eq!(
+            parse_key("name"),
+            CanonicalMetadataKey::Custom("name".to_string())
+        );
+        assert_eq!(
+            parse_key("作曲"),
+            CanonicalMetadataKey::Custom("作曲".to_string())
+        );
+        assert_eq!(
+            parse_key("Composer"),
+            CanonicalMetadataKey::Custom("Composer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_registered_alias_is_normalized() {
+        CanonicalMetadataKey::register_alias("Track Title X", CanonicalMetadataKey::Title);
+        assert_eq!(parse_key("track_title-x"), CanonicalMetadataKey::Title);
+        assert_eq!(parse_key("TRACKTITLEX"), CanonicalMetadataKey::Title);
+    }
+
+    #[test]
+    fn test_validate_value_accepts_well_formed_values() {
+        let cases = [
+            (CanonicalMetadataKey::Isrc, "USRC17607839"),
+            (CanonicalMetadataKey::Isrc, "US-RC1-76-07839"),
+            (CanonicalMetadataKey::Language, "ja"),
+            (CanonicalMetadataKey::Language, "zh-Hans"),
+            (CanonicalMetadataKey::Language, "en-US"),
+            (CanonicalMetadataKey::Language, "zh-Hant-TW"),
+            (CanonicalMetadataKey::Offset, "-200"),
+            (CanonicalMetadataKey::Offset, "150"),
+            (CanonicalMetadataKey::NcmMusicId, "1901371647"),
+            (CanonicalMetadataKey::AppleMusicId, "1440818839"),
+            (CanonicalMetadataKey::SpotifyId, "4uLU6hMCjMI75M1A2tKUQC"),
+            (CanonicalMetadataKey::Title, "任意 标题!"),
+            (CanonicalMetadataKey::QqMusicId, "003aQYLo2x8izP"),
+            (CanonicalMetadataKey::Custom("bpm".to_string()), "??"),
+        ];
+        for (key, value) in cases {
+            assert_eq!(key.validate_value(value), Ok(()), "{key:?} = {value:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_value_rejects_malformed_values() {
+        let cases = [
+            (CanonicalMetadataKey::Isrc, "USRC1760783"),
+            (CanonicalMetadataKey::Isrc, "12RC17607839"),
+            (CanonicalMetadataKey::Isrc, "USRC1760783X"),
+            (CanonicalMetadataKey::Language, "j"),
+            (CanonicalMetadataKey::Language, "jpan"),
+            (CanonicalMetadataKey::Language, "zh_Hans"),
+            (CanonicalMetadataKey::Language, "zh-"),
+            (CanonicalMetadataKey::Offset, "1.5"),
+            (CanonicalMetadataKey::Offset, "+-20"),
+            (CanonicalMetadataKey::NcmMusicId, "190137164a"),
+            (CanonicalMetadataKey::AppleMusicId, "-1"),
+            (CanonicalMetadataKey::SpotifyId, "4uLU6hMCjMI75M1A2tKUQ"),
+            (CanonicalMetadataKey::SpotifyId, "4uLU6hMCjMI75M1A2tKUQ-"),
+        ];
+        for (key, value) in cases {
+            assert!(key.validate_value(value).is_err(), "{key:?} = {value:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_value_trims_and_allows_empty() {
+        assert_eq!(CanonicalMetadataKey::Isrc.validate_value("   "), Ok(()));
+        assert_eq!(
+            CanonicalMetadataKey::Offset.validate_value(" -200 "),
+            Ok(())
+        );
+        assert_eq!(
+            CanonicalMetadataKey::Offset.validate_value("abc"),
+            Err("偏移量应为整数毫秒，如 -200")
+        );
+    }
+}
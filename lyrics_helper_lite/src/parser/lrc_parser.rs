@@ -1,6 +1,6 @@
 use crate::error::{FetcherError, Result};
 use lyrics_helper_core::{
-    AnnotatedTrack, ContentType, LyricFormat, LyricLine, LyricSyllable, LyricTrack,
+    AnnotatedTrack, ContentType, LyricFormat, LyricLine, LyricSyllable, LyricTrack, ParseWarning,
     ParsedSourceData, Word,
 };
 use regex::Regex;
@@ -43,7 +43,7 @@ struct TempLrcEntry {
 struct InitialParseResult {
     entries: Vec<TempLrcEntry>,
     metadata: HashMap<String, Vec<String>>,
-    warnings: Vec<String>,
+    warnings: Vec<ParseWarning>,
 }
 
 fn parse_lines_to_temp_entries(content: &str) -> Result<InitialParseResult> {
@@ -79,11 +79,11 @@ fn parse_lines_to_temp_entries(content: &str) -> Result<InitialParseResult> {
                 };
 
                 if seconds >= 60 {
-                    warnings.push(format!(
+                    warnings.push(ParseWarning::error(format!(
                         "Invalid seconds count (>= 60) in timestamp on line {}: '{}'",
                         line_num + 1,
                         line_str_trimmed
-                    ));
+                    )));
                     continue;
                 }
 
@@ -1,7 +1,7 @@
 use crate::error::Result;
 use lyrics_helper_core::{
     AnnotatedTrack, LyricFormat, LyricLine, LyricLineBuilder, LyricSyllable, LyricSyllableBuilder,
-    LyricTrack, ParsedSourceData, Word,
+    LyricTrack, ParseWarning, ParsedSourceData, Word,
 };
 use regex::Regex;
 use std::{collections::HashMap, sync::LazyLock};
@@ -18,7 +18,7 @@ static QRC_LINE_TIMESTAMP_REGEX: LazyLock<Regex> =
 pub fn parse_qrc(content: &str) -> Result<ParsedSourceData> {
     let mut raw_metadata: HashMap<String, Vec<String>> = HashMap::new();
     let mut final_lines: Vec<LyricLine> = Vec::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
 
     for line_str in content.lines() {
         let trimmed_line = line_str.trim();
@@ -29,7 +29,7 @@ pub fn parse_qrc(content: &str) -> Result<ParsedSourceData> {
         match parse_single_qrc_line(trimmed_line) {
             Ok(Some(line)) => final_lines.push(line),
             Ok(None) => (),
-            Err(e) => warnings.push(e.to_string()),
+            Err(e) => warnings.push(ParseWarning::error(e.to_string())),
         }
     }
 
@@ -4,16 +4,24 @@ use std::fmt::Write as FmtWrite;
 
 use lyrics_helper_core::{
     ContentType, ConvertError, LrcEndTimeOutputMode, LrcGenerationOptions, LrcSubLinesOutputMode,
-    LyricLine, LyricTrack, MetadataStore,
+    LrcTimestampPrecision, LyricLine, LyricTrack, MetadataStore,
 };
 
+use crate::converter::generators::enhanced_lrc_generator::generate_enhanced_lrc;
+
 /// LRC 生成的主入口函数。
 pub fn generate_lrc(
     lines: &[LyricLine],
     metadata_store: &MetadataStore,
     options: &LrcGenerationOptions,
 ) -> Result<String, ConvertError> {
+    // 增强模式即增强型 LRC 输出，交给专门的生成器处理
+    if options.word_timing {
+        return generate_enhanced_lrc(lines, metadata_store, options);
+    }
+
     let mut lrc_output = String::with_capacity(lines.len() * 50);
+    let precision = options.timestamp_precision;
 
     let lrc_header = metadata_store.generate_lrc_header();
     if !lrc_header.is_empty() {
@@ -33,7 +41,7 @@ pub fn generate_lrc(
         match options.sub_lines_output_mode {
             LrcSubLinesOutputMode::Ignore => {
                 if let Some(track) = main_annotated_track {
-                    write_track_as_line(&mut lrc_output, line.start_ms, &track.content)?;
+                    write_track_as_line(&mut lrc_output, line.start_ms, &track.content, precision)?;
                 }
             }
             LrcSubLinesOutputMode::MergeWithParentheses => {
@@ -42,11 +50,12 @@ pub fn generate_lrc(
                     line.start_ms,
                     main_annotated_track.map(|t| &t.content),
                     bg_annotated_track.map(|t| &t.content),
+                    precision,
                 )?;
             }
             LrcSubLinesOutputMode::SeparateLines => {
                 if let Some(track) = main_annotated_track {
-                    write_track_as_line(&mut lrc_output, line.start_ms, &track.content)?;
+                    write_track_as_line(&mut lrc_output, line.start_ms, &track.content, precision)?;
                 }
                 if let Some(track) = bg_annotated_track {
                     let bg_start_ms = track
@@ -57,7 +66,7 @@ pub fn generate_lrc(
                         .map(|s| s.start_ms)
                         .min()
                         .unwrap_or(line.start_ms);
-                    write_track_as_line(&mut lrc_output, bg_start_ms, &track.content)?;
+                    write_track_as_line(&mut lrc_output, bg_start_ms, &track.content, precision)?;
                 }
             }
         }
@@ -68,6 +77,7 @@ pub fn generate_lrc(
             line,
             options.end_time_output_mode,
             next_line_start_ms,
+            precision,
         )?;
     }
 
@@ -99,10 +109,11 @@ fn write_track_as_line(
     output: &mut String,
     start_ms: u64,
     track: &LyricTrack,
+    precision: LrcTimestampPrecision,
 ) -> Result<(), std::fmt::Error> {
     let text = get_text_from_track(track);
     if !text.trim().is_empty() {
-        writeln!(output, "{}{}", format_lrc_time_ms(start_ms), text)?;
+        writeln!(output, "{}{}", format_lrc_time(start_ms, precision), text)?;
     }
     Ok(())
 }
@@ -113,31 +124,23 @@ fn write_merged_line(
     line_start_ms: u64,
     main_track: Option<&LyricTrack>,
     bg_track: Option<&LyricTrack>,
+    precision: LrcTimestampPrecision,
 ) -> Result<(), std::fmt::Error> {
+    let timestamp = format_lrc_time(line_start_ms, precision);
     let main_text = main_track.map(get_text_from_track);
     let bg_text = bg_track.map(get_text_from_track);
 
     match (main_text, bg_text) {
         (Some(mt), Some(bt)) if !mt.trim().is_empty() && !bt.trim().is_empty() => {
             let merged_text = format!("{} ({})", mt.trim(), bt.trim());
-            writeln!(
-                output,
-                "{}{}",
-                format_lrc_time_ms(line_start_ms),
-                merged_text
-            )?;
+            writeln!(output, "{timestamp}{merged_text}")?;
         }
         (Some(mt), _) if !mt.trim().is_empty() => {
-            writeln!(output, "{}{}", format_lrc_time_ms(line_start_ms), mt)?;
+            writeln!(output, "{timestamp}{mt}")?;
         }
         (_, Some(bt)) if !bt.trim().is_empty() => {
             let merged_text = format!("({})", bt.trim());
-            writeln!(
-                output,
-                "{}{}",
-                format_lrc_time_ms(line_start_ms),
-                merged_text
-            )?;
+            writeln!(output, "{timestamp}{merged_text}")?;
         }
         _ => {}
     }
@@ -150,23 +153,25 @@ fn handle_end_time_output(
     current_line: &LyricLine,
     mode: LrcEndTimeOutputMode,
     next_line_start_ms: Option<u64>,
+    precision: LrcTimestampPrecision,
 ) -> Result<(), std::fmt::Error> {
     if current_line.end_ms == 0 {
         return Ok(());
     }
+    let end_timestamp = format_lrc_time(current_line.end_ms, precision);
 
     match mode {
         LrcEndTimeOutputMode::Never => { /* 什么也不做 */ }
         LrcEndTimeOutputMode::Always => {
-            writeln!(output, "{}", format_lrc_time_ms(current_line.end_ms))?;
+            writeln!(output, "{end_timestamp}")?;
         }
         LrcEndTimeOutputMode::OnLongPause { threshold_ms } => {
             if let Some(next_start) = next_line_start_ms {
                 if next_start.saturating_sub(current_line.end_ms) > threshold_ms {
-                    writeln!(output, "{}", format_lrc_time_ms(current_line.end_ms))?;
+                    writeln!(output, "{end_timestamp}")?;
                 }
             } else {
-                writeln!(output, "{}", format_lrc_time_ms(current_line.end_ms))?;
+                writeln!(output, "{end_timestamp}")?;
             }
         }
     }
@@ -188,3 +193,89 @@ pub fn format_lrc_time_ms(ms: u64) -> String {
     let milliseconds = ms % 1000;
     format!("[{minutes:02}:{seconds:02}.{milliseconds:03}]")
 }
+
+/// 按指定精度将毫秒时间格式化为 LRC 时间标签。
+///
+/// 两位小数的精度会四舍五入到最近的百分之一秒，进位会传递到秒和分钟。
+#[must_use]
+pub fn format_lrc_time(ms: u64, precision: LrcTimestampPrecision) -> String {
+    match precision {
+        LrcTimestampPrecision::Milliseconds => format_lrc_time_ms(ms),
+        LrcTimestampPrecision::Centiseconds => {
+            let total_centiseconds = (ms + 5) / 10;
+            let minutes = total_centiseconds / 6000;
+            let seconds = (total_centiseconds % 6000) / 100;
+            let centiseconds = total_centiseconds % 100;
+            format!("[{minutes:02}:{seconds:02}.{centiseconds:02}]")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{syl, timed_line};
+
+    #[test]
+    fn test_format_lrc_time_centiseconds_rounds_to_nearest() {
+        let precision = LrcTimestampPrecision::Centiseconds;
+        assert_eq!(format_lrc_time(0, precision), "[00:00.00]");
+        assert_eq!(format_lrc_time(4, precision), "[00:00.00]");
+        assert_eq!(format_lrc_time(5, precision), "[00:00.01]");
+        assert_eq!(format_lrc_time(12_344, precision), "[00:12.34]");
+        assert_eq!(format_lrc_time(12_345, precision), "[00:12.35]");
+    }
+
+    #[test]
+    fn test_format_lrc_time_centiseconds_carries_into_minutes() {
+        let precision = LrcTimestampPrecision::Centiseconds;
+        assert_eq!(format_lrc_time(999, precision), "[00:01.00]");
+        assert_eq!(format_lrc_time(59_994, precision), "[00:59.99]");
+        assert_eq!(format_lrc_time(59_995, precision), "[01:00.00]");
+        assert_eq!(format_lrc_time(3_599_995, precision), "[60:00.00]");
+    }
+
+    #[test]
+    fn test_format_lrc_time_milliseconds_is_exact() {
+        let precision = LrcTimestampPrecision::Milliseconds;
+        assert_eq!(format_lrc_time(5, precision), "[00:00.005]");
+        assert_eq!(format_lrc_time(59_995, precision), "[00:59.995]");
+        assert_eq!(format_lrc_time(61_001, precision), "[01:01.001]");
+    }
+
+    #[test]
+    fn test_word_timing_matches_enhanced_lrc_output() {
+        let lines = vec![timed_line(vec![
+            syl("Hello", 1000, 1500),
+            syl("world", 1500, 2000),
+        ])];
+        let metadata = MetadataStore::new();
+        let options = LrcGenerationOptions {
+            word_timing: true,
+            ..Default::default()
+        };
+
+        let lrc = generate_lrc(&lines, &metadata, &options).unwrap();
+        let enhanced = generate_enhanced_lrc(&lines, &metadata, &options).unwrap();
+
+        assert_eq!(lrc, enhanced);
+        assert!(lrc.contains("[00:01.000]<00:01.000>Hello<00:01.500>world<00:02.000>"));
+    }
+
+    #[test]
+    fn test_plain_lrc_drops_word_timing_by_default() {
+        let lines = vec![timed_line(vec![
+            syl("Hello", 1000, 1500),
+            syl("world", 1500, 2000),
+        ])];
+
+        let lrc = generate_lrc(
+            &lines,
+            &MetadataStore::new(),
+            &LrcGenerationOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(lrc, "[00:01.000]Helloworld\n");
+    }
+}
@@ -0,0 +1,199 @@
+//! LRCX（`LyricsX`）格式生成器。
+//!
+//! 翻译、罗马音和逐字时间以与主歌词行时间戳相同的 `[tr:语言]`、`[roma]`、`[tt]`
+//! 附加行输出。主歌词为空、只有翻译或罗马音的行输出为只有时间戳的主歌词行加附加行。
+//! 背景人声不被该格式支持。
+
+use std::fmt::Write as FmtWrite;
+
+use crate::converter::generators::lrc_generator::format_lrc_time;
+
+use lyrics_helper_core::{
+    ContentType, ConvertError, LrcTimestampPrecision, LyricLine, LyricTrack, MetadataStore,
+    TrackMetadataKey,
+};
+
+/// LRCX 生成的主入口函数。
+pub fn generate_lrcx(
+    lines: &[LyricLine],
+    metadata_store: &MetadataStore,
+) -> Result<String, ConvertError> {
+    let mut output = String::with_capacity(lines.len() * 80);
+
+    let header = metadata_store.generate_lrc_header();
+    if !header.is_empty() {
+        writeln!(output, "{}", header.trim_end_matches('\n'))?;
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let Some(main_track) = line
+            .tracks
+            .iter()
+            .find(|t| t.content_type == ContentType::Main)
+        else {
+            continue;
+        };
+        let text = main_track.content.text();
+        let has_attachments = main_track
+            .translations
+            .iter()
+            .chain(&main_track.romanizations)
+            .any(|track| !track.text().is_empty());
+        if text.is_empty() && !has_attachments {
+            continue;
+        }
+
+        let timestamp = format_lrc_time(line.start_ms, LrcTimestampPrecision::Milliseconds);
+        writeln!(output, "{timestamp}{text}")?;
+
+        for translation in &main_track.translations {
+            let translation_text = translation.text();
+            if translation_text.is_empty() {
+                continue;
+            }
+            match translation.metadata.get(&TrackMetadataKey::Language) {
+                Some(language) => {
+                    writeln!(output, "{timestamp}[tr:{language}]{translation_text}")?;
+                }
+                None => writeln!(output, "{timestamp}[tr]{translation_text}")?,
+            }
+        }
+        for romanization in &main_track.romanizations {
+            let romanization_text = romanization.text();
+            if !romanization_text.is_empty() {
+                writeln!(output, "{timestamp}[roma]{romanization_text}")?;
+            }
+        }
+        if !text.is_empty() && main_track.content.is_timed() {
+            writeln!(
+                output,
+                "{timestamp}[tt]{}",
+                build_time_tags(&main_track.content, line.start_ms)
+            )?;
+        }
+
+        // 与下一行之间有空隙时，用只有时间戳的空行标记本行结束
+        let next_start_ms = lines.get(i + 1).map(|l| l.start_ms);
+        if line.end_ms > line.start_ms && next_start_ms.is_none_or(|next| next > line.end_ms) {
+            writeln!(
+                output,
+                "{}",
+                format_lrc_time(line.end_ms, LrcTimestampPrecision::Milliseconds)
+            )?;
+        }
+    }
+
+    let trimmed_output = output.trim_end();
+    Ok(format!("{trimmed_output}\n"))
+}
+
+/// 构建 `[tt]` 附加行的内容，如 `<0,0><500,6><1200>`。
+///
+/// 每个音节输出相对行开始的偏移和它在整行文本中的字符索引，最后输出整行的时长。
+fn build_time_tags(track: &LyricTrack, line_start_ms: u64) -> String {
+    let mut tags = String::new();
+    let mut char_index = 0;
+    let mut last_end_ms = line_start_ms;
+
+    for syllable in track.syllables() {
+        let offset_ms = syllable.start_ms.saturating_sub(line_start_ms);
+        let _ = write!(tags, "<{offset_ms},{char_index}>");
+        char_index += syllable.text.chars().count();
+        if syllable.ends_with_space {
+            char_index += 1;
+        }
+        last_end_ms = last_end_ms.max(syllable.end_ms);
+    }
+
+    let _ = write!(tags, "<{}>", last_end_ms - line_start_ms);
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::parsers::lrcx_parser::parse_lrcx;
+    use crate::converter::test_utils::{spaced_syl, syl, text_line, timed_line};
+
+    fn with_translation(mut line: LyricLine, text: &str, language: Option<&str>) -> LyricLine {
+        let mut translation = text_line(line.start_ms, line.end_ms, text).tracks[0]
+            .content
+            .clone();
+        if let Some(language) = language {
+            translation
+                .metadata
+                .insert(TrackMetadataKey::Language, language.to_string());
+        }
+        line.tracks[0].translations.push(translation);
+        line
+    }
+
+    #[test]
+    fn test_generate_lrcx_attachment_lines() {
+        let lines = vec![
+            with_translation(
+                timed_line(vec![
+                    spaced_syl("Hello", 10000, 10500),
+                    syl("world", 10500, 11200),
+                ]),
+                "你好世界",
+                Some("zh-Hans"),
+            ),
+            with_translation(text_line(11200, 12000, "Next"), "下一句", None),
+        ];
+        let output = generate_lrcx(&lines, &MetadataStore::new()).unwrap();
+
+        assert_eq!(
+            output,
+            "[00:10.000]Hello world\n\
+             [00:10.000][tr:zh-Hans]你好世界\n\
+             [00:10.000][tt]<0,0><500,6><1200>\n\
+             [00:11.200]Next\n\
+             [00:11.200][tr]下一句\n\
+             [00:12.000]\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_lrcx_keeps_translation_of_empty_main_line() {
+        let lines = vec![
+            text_line(0, 1000, "A"),
+            with_translation(text_line(1000, 2000, ""), "间奏", None),
+            text_line(3000, 4000, "B"),
+        ];
+        let output = generate_lrcx(&lines, &MetadataStore::new()).unwrap();
+
+        assert_eq!(
+            output,
+            "[00:00.000]A\n\
+             [00:01.000]\n\
+             [00:01.000][tr]间奏\n\
+             [00:02.000]\n\
+             [00:03.000]B\n\
+             [00:04.000]\n"
+        );
+    }
+
+    #[test]
+    fn test_lrcx_round_trip() {
+        let content = "[00:10.000]Hello world\n\
+            [00:10.000][tr:zh-Hans]你好世界\n\
+            [00:10.000][tt]<0,0><500,6><1200>\n\
+            [00:11.200]\n\
+            [00:11.200][tr]间奏\n\
+            [00:11.200][roma]kansou\n\
+            [00:13.000]Next line\n\
+            [00:14.500]\n";
+        let data = parse_lrcx(content).unwrap();
+        assert!(data.warnings.is_empty());
+        assert_eq!(data.lines.len(), 3);
+        assert_eq!(data.lines[1].main_text().as_deref(), Some(""));
+        assert_eq!(
+            data.lines[1].main_track().unwrap().translations[0].text(),
+            "间奏"
+        );
+
+        let output = generate_lrcx(&data.lines, &MetadataStore::new()).unwrap();
+        assert_eq!(output, content);
+    }
+}
@@ -23,9 +23,10 @@ pub fn generate_lys(
             .find(|t| t.content_type == ContentType::Main)
             && !main_track.content.words.is_empty()
         {
-            let property = match line.agent.as_deref() {
-                Some("v2") => lys_properties::MAIN_RIGHT,
-                _ => lys_properties::MAIN_LEFT,
+            let property = match agent_view(line.agent.as_deref()) {
+                AgentView::Right => lys_properties::MAIN_RIGHT,
+                AgentView::Left => lys_properties::MAIN_LEFT,
+                AgentView::Unset => lys_properties::MAIN_UNSET,
             };
             write!(lys_output, "[{property}]")?;
             write_words_to_lys_string(&mut lys_output, &main_track.content.words, false)?;
@@ -39,11 +40,10 @@ pub fn generate_lys(
             .find(|t| t.content_type == ContentType::Background)
             && !bg_track.content.words.is_empty()
         {
-            let bg_property = match line.agent.as_deref() {
-                // agent "v2" -> 右。
-                Some("v2") => lys_properties::BG_RIGHT,
-                // 其他情况 -> 左。
-                _ => lys_properties::BG_LEFT,
+            let bg_property = match agent_view(line.agent.as_deref()) {
+                AgentView::Right => lys_properties::BG_RIGHT,
+                AgentView::Left => lys_properties::BG_LEFT,
+                AgentView::Unset => lys_properties::BG_UNSET,
             };
             write!(lys_output, "[{bg_property}]")?;
             write_words_to_lys_string(&mut lys_output, &bg_track.content.words, true)?;
@@ -54,6 +54,28 @@ pub fn generate_lys(
     Ok(lys_output)
 }
 
+/// LYS 属性中的视图位置。
+enum AgentView {
+    Left,
+    Right,
+    Unset,
+}
+
+/// 将内部的演唱者 ID 映射到 LYS 的视图位置。
+///
+/// 奇数编号的演唱者（v1、v3...）在左，偶数编号的在右，与 AMLL 的对唱分配一致；
+/// 合唱 (v1000) 不区分左右。没有演唱者或无法识别的 ID 视为左侧。
+fn agent_view(agent: Option<&str>) -> AgentView {
+    match agent.and_then(|id| id.strip_prefix('v')?.parse::<u32>().ok()) {
+        Some(CHORUS_AGENT_NUMBER) => AgentView::Unset,
+        Some(number) if number % 2 == 0 => AgentView::Right,
+        _ => AgentView::Left,
+    }
+}
+
+/// 合唱演唱者 `v1000` 的编号。
+const CHORUS_AGENT_NUMBER: u32 = 1000;
+
 fn write_words_to_lys_string(
     output: &mut String,
     words: &[Word],
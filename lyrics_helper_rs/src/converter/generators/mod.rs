@@ -6,8 +6,10 @@ pub mod enhanced_lrc_generator;
 pub mod krc_generator;
 pub mod lqe_generator;
 pub mod lrc_generator;
+pub mod lrcx_generator;
 pub mod lyricify_lines_generator;
 pub mod lys_generator;
 pub mod qrc_generator;
 pub mod spl_generator;
+pub mod vtt_generator;
 pub mod yrc_generator;
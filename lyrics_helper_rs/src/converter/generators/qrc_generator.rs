@@ -2,16 +2,22 @@
 
 use std::fmt::Write;
 
-use lyrics_helper_core::{ContentType, ConvertError, LyricLine, MetadataStore, Word};
+use lyrics_helper_core::{
+    CanonicalMetadataKey, ContentType, ConvertError, LyricLine, LyricSyllable, LyricTrack,
+    MetadataStore,
+};
 
 /// QRC 生成的主入口函数。
+///
+/// 没有逐字时间的行会按字符数把行时长分配给每个字（或英文单词），合成音节时间；
+/// 行本身也没有有效时长时，整行作为一个音节输出。
 pub fn generate_qrc(
     lines: &[LyricLine],
     metadata_store: &MetadataStore,
 ) -> Result<String, ConvertError> {
     let mut qrc_output = String::new();
 
-    writeln!(qrc_output, "{}", metadata_store.generate_lrc_header())?;
+    write_qrc_header(&mut qrc_output, metadata_store)?;
 
     for line in lines {
         // 主歌词行
@@ -21,12 +27,13 @@ pub fn generate_qrc(
             .find(|t| t.content_type == ContentType::Main)
             && !main_track.content.words.is_empty()
         {
+            let syllables = timed_syllables(&main_track.content, line.start_ms, line.end_ms);
             writeln!(
                 qrc_output,
                 "[{},{}]{}",
                 line.start_ms,
                 line.end_ms.saturating_sub(line.start_ms),
-                build_qrc_text_from_words(&main_track.content.words, false)?
+                build_qrc_text_from_syllables(&syllables, false)?
             )?;
         }
         // 背景人声行
@@ -53,12 +60,13 @@ pub fn generate_qrc(
                 .max()
                 .unwrap_or(line.end_ms);
 
+            let syllables = timed_syllables(&bg_track.content, bg_start_ms, bg_end_ms);
             writeln!(
                 qrc_output,
                 "[{},{}]{}",
                 bg_start_ms,
                 bg_end_ms.saturating_sub(bg_start_ms),
-                build_qrc_text_from_words(&bg_track.content.words, true)?
+                build_qrc_text_from_syllables(&syllables, true)?
             )?;
         }
     }
@@ -66,13 +74,108 @@ pub fn generate_qrc(
     Ok(qrc_output)
 }
 
-/// 辅助函数，将词语列表格式化为 QRC 行的文本部分。
-fn build_qrc_text_from_words(
-    words: &[Word],
+/// 写入元数据头。沿用 LRC 头部的生成规则保留所有元数据，并按 QRC 约定在没有偏移量时补上 `[offset:0]`。
+fn write_qrc_header(output: &mut String, metadata_store: &MetadataStore) -> std::fmt::Result {
+    output.push_str(&metadata_store.generate_lrc_header());
+    if metadata_store
+        .get_single_value(&CanonicalMetadataKey::Offset)
+        .is_none()
+    {
+        writeln!(output, "[offset:0]")?;
+    }
+    Ok(())
+}
+
+/// 返回轨道的逐字音节。轨道没有逐字时间时，在 `[start_ms, end_ms]` 内合成音节时间。
+fn timed_syllables(track: &LyricTrack, start_ms: u64, end_ms: u64) -> Vec<LyricSyllable> {
+    if track.is_timed() {
+        return track.syllables().cloned().collect();
+    }
+    synthesize_syllables(&track.text(), start_ms, end_ms)
+}
+
+/// 将一行文本拆分为音节，并按字符数把 `[start_ms, end_ms]` 分配给每个音节。
+///
+/// CJK 等非 ASCII 字符各自成为一个音节，连续的 ASCII 字母数字组成一个单词音节，
+/// 标点附加到前一个音节上。行没有有效时长时，整行作为一个音节输出。
+fn synthesize_syllables(text: &str, start_ms: u64, end_ms: u64) -> Vec<LyricSyllable> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    let whole_line = || {
+        vec![LyricSyllable {
+            text: text.to_string(),
+            start_ms,
+            end_ms: end_ms.max(start_ms),
+            ..Default::default()
+        }]
+    };
+    if end_ms <= start_ms {
+        return whole_line();
+    }
+
+    // (文本, 后面是否有空格)
+    let mut units: Vec<(String, bool)> = Vec::new();
+    let mut in_ascii_word = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if let Some(last) = units.last_mut() {
+                last.1 = true;
+            }
+            in_ascii_word = false;
+        } else if c.is_ascii_alphanumeric() || c == '\'' {
+            match units.last_mut() {
+                Some(last) if in_ascii_word => last.0.push(c),
+                _ => units.push((c.to_string(), false)),
+            }
+            in_ascii_word = true;
+        } else if c.is_ascii_punctuation() {
+            match units.last_mut() {
+                Some(last) if !last.1 => last.0.push(c),
+                _ => units.push((c.to_string(), false)),
+            }
+            in_ascii_word = false;
+        } else {
+            units.push((c.to_string(), false));
+            in_ascii_word = false;
+        }
+    }
+
+    let total_weight: u64 = units
+        .iter()
+        .map(|(text, _)| text.chars().count() as u64)
+        .sum();
+    if total_weight == 0 {
+        return whole_line();
+    }
+
+    let duration = end_ms - start_ms;
+    let mut consumed_weight = 0;
+    units
+        .into_iter()
+        .map(|(text, ends_with_space)| {
+            let syllable_start = start_ms + duration * consumed_weight / total_weight;
+            consumed_weight += text.chars().count() as u64;
+            let syllable_end = start_ms + duration * consumed_weight / total_weight;
+            LyricSyllable {
+                text,
+                start_ms: syllable_start,
+                end_ms: syllable_end,
+                ends_with_space,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// 辅助函数，将音节列表格式化为 QRC 行的文本部分。
+fn build_qrc_text_from_syllables(
+    syllables: &[LyricSyllable],
     is_background: bool,
 ) -> Result<String, std::fmt::Error> {
     let mut output = String::new();
-    let syllables: Vec<_> = words.iter().flat_map(|w| &w.syllables).collect();
     let total_syllable_count = syllables.len();
 
     for (i, syl) in syllables.iter().enumerate() {
@@ -106,3 +209,64 @@ fn build_qrc_text_from_words(
 
     Ok(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings(syllables: &[LyricSyllable]) -> Vec<(&str, u64, u64, bool)> {
+        syllables
+            .iter()
+            .map(|s| (s.text.as_str(), s.start_ms, s.end_ms, s.ends_with_space))
+            .collect()
+    }
+
+    #[test]
+    fn test_synthesize_syllables_empty_text() {
+        assert!(synthesize_syllables("", 0, 1000).is_empty());
+        assert!(synthesize_syllables("   ", 0, 1000).is_empty());
+    }
+
+    #[test]
+    fn test_synthesize_syllables_single_character() {
+        let syllables = synthesize_syllables(" 春 ", 100, 600);
+        assert_eq!(timings(&syllables), vec![("春", 100, 600, false)]);
+    }
+
+    #[test]
+    fn test_synthesize_syllables_splits_cjk_by_character() {
+        let syllables = synthesize_syllables("春よ夏", 0, 100);
+        assert_eq!(
+            timings(&syllables),
+            vec![
+                ("春", 0, 33, false),
+                ("よ", 33, 66, false),
+                ("夏", 66, 100, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_synthesize_syllables_weights_words_by_length() {
+        let syllables = synthesize_syllables("Hello world", 1000, 2100);
+        assert_eq!(
+            timings(&syllables),
+            vec![("Hello", 1000, 1550, true), ("world", 1550, 2100, false)]
+        );
+
+        let syllables = synthesize_syllables("Hi, 春", 0, 400);
+        assert_eq!(
+            timings(&syllables),
+            vec![("Hi,", 0, 300, true), ("春", 300, 400, false)]
+        );
+    }
+
+    #[test]
+    fn test_synthesize_syllables_without_duration_keeps_whole_line() {
+        let syllables = synthesize_syllables("春よ", 500, 500);
+        assert_eq!(timings(&syllables), vec![("春よ", 500, 500, false)]);
+
+        let syllables = synthesize_syllables("春よ", 500, 200);
+        assert_eq!(timings(&syllables), vec![("春よ", 500, 500, false)]);
+    }
+}
@@ -0,0 +1,224 @@
+//! `WebVTT` 格式生成器
+
+use std::fmt::Write;
+
+use lyrics_helper_core::{
+    CanonicalMetadataKey, ContentType, ConvertError, LyricLine, LyricSyllable, LyricTrack,
+    MetadataStore,
+};
+
+/// `WebVTT` 生成的主入口函数。
+///
+/// 每个歌词行生成一个 cue，逐字音节使用 `<HH:MM:SS.mmm>` 内联时间戳标签表示，
+/// 翻译作为 cue 的第二行文本。背景人声会作为独立的 cue 输出。
+pub fn generate_vtt(
+    lines: &[LyricLine],
+    metadata_store: &MetadataStore,
+) -> Result<String, ConvertError> {
+    let mut vtt_output = String::new();
+
+    writeln!(vtt_output, "WEBVTT")?;
+    writeln!(vtt_output)?;
+
+    write_metadata_note(&mut vtt_output, metadata_store)?;
+
+    for line in lines {
+        if let Some(main_track) = line
+            .tracks
+            .iter()
+            .find(|t| t.content_type == ContentType::Main)
+            && !main_track.content.is_empty()
+        {
+            let syllables: Vec<_> = main_track.content.syllables().collect();
+            let cue_end_ms = line.end_ms.max(line.start_ms + 1);
+
+            writeln!(
+                vtt_output,
+                "{} --> {}",
+                format_vtt_time(line.start_ms),
+                format_vtt_time(cue_end_ms)
+            )?;
+            writeln!(
+                vtt_output,
+                "{}",
+                build_cue_text(&syllables, line.start_ms, cue_end_ms)
+            )?;
+
+            if let Some(translation) = main_track
+                .translations
+                .iter()
+                .map(LyricTrack::text)
+                .find(|text| !text.is_empty())
+            {
+                writeln!(vtt_output, "{}", escape_vtt_text(&translation))?;
+            }
+            writeln!(vtt_output)?;
+        }
+
+        if let Some(bg_track) = line
+            .tracks
+            .iter()
+            .find(|t| t.content_type == ContentType::Background)
+            && let Some((bg_start_ms, bg_end_ms)) = bg_track.content.time_range()
+        {
+            let syllables: Vec<_> = bg_track.content.syllables().collect();
+            let bg_end_ms = bg_end_ms.max(bg_start_ms + 1);
+
+            writeln!(
+                vtt_output,
+                "{} --> {}",
+                format_vtt_time(bg_start_ms),
+                format_vtt_time(bg_end_ms)
+            )?;
+            writeln!(
+                vtt_output,
+                "{}",
+                build_cue_text(&syllables, bg_start_ms, bg_end_ms)
+            )?;
+            writeln!(vtt_output)?;
+        }
+    }
+
+    Ok(vtt_output)
+}
+
+/// 将常用的元数据写入一个 `NOTE` 注释块。
+fn write_metadata_note(
+    output: &mut String,
+    metadata_store: &MetadataStore,
+) -> Result<(), ConvertError> {
+    let entries: Vec<(&str, String)> = [
+        (CanonicalMetadataKey::Title, "Title"),
+        (CanonicalMetadataKey::Artist, "Artist"),
+        (CanonicalMetadataKey::Album, "Album"),
+    ]
+    .into_iter()
+    .filter_map(|(key, label)| {
+        metadata_store
+            .get_multiple_values(&key)
+            .filter(|values| !values.is_empty())
+            // NOTE 块中不允许出现 "-->"
+            .map(|values| (label, values.join(", ").replace("-->", "->")))
+    })
+    .collect();
+
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(output, "NOTE")?;
+    for (label, value) in entries {
+        writeln!(output, "{label}: {value}")?;
+    }
+    writeln!(output)?;
+    Ok(())
+}
+
+/// 构建 cue 的正文文本。
+///
+/// 除第一个音节外，每个音节前都会插入一个内联时间戳。
+/// 按照规范，内联时间戳必须严格位于 cue 的起止时间之间，越界的时间戳会被省略。
+fn build_cue_text(syllables: &[&LyricSyllable], cue_start_ms: u64, cue_end_ms: u64) -> String {
+    let mut text = String::new();
+
+    for (i, syllable) in syllables.iter().enumerate() {
+        if i > 0 && syllable.start_ms > cue_start_ms && syllable.start_ms < cue_end_ms {
+            text.push('<');
+            text.push_str(&format_vtt_time(syllable.start_ms));
+            text.push('>');
+        }
+        text.push_str(&escape_vtt_text(&syllable.text));
+        if syllable.ends_with_space {
+            text.push(' ');
+        }
+    }
+
+    text.trim_end().to_string()
+}
+
+/// 转义 cue 文本中的特殊字符。
+fn escape_vtt_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// 将毫秒时间格式化为 `HH:MM:SS.mmm`。
+fn format_vtt_time(ms: u64) -> String {
+    let hours = ms / 3_600_000;
+    let minutes = (ms % 3_600_000) / 60_000;
+    let seconds = (ms % 60_000) / 1000;
+    let milliseconds = ms % 1000;
+    format!("{hours:02}:{minutes:02}:{seconds:02}.{milliseconds:03}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lyrics_helper_core::{AnnotatedTrack, Word};
+
+    #[test]
+    fn test_format_vtt_time() {
+        assert_eq!(format_vtt_time(0), "00:00:00.000");
+        assert_eq!(format_vtt_time(61_005), "00:01:01.005");
+        assert_eq!(format_vtt_time(3_723_456), "01:02:03.456");
+    }
+
+    #[test]
+    fn test_output_starts_with_webvtt_header() {
+        let mut line = LyricLine::new(1000, 2500);
+        line.add_content_track(ContentType::Main, "Hello");
+        line.add_translation(ContentType::Main, "你好", None);
+
+        let vtt = generate_vtt(&[line], &MetadataStore::new()).unwrap();
+
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\n00:00:01.000 --> 00:00:02.500\nHello\n你好\n\n"
+        );
+    }
+
+    #[test]
+    fn test_metadata_note_follows_header() {
+        let mut metadata = MetadataStore::new();
+        metadata.set_single("title", "Song");
+
+        let vtt = generate_vtt(&[], &metadata).unwrap();
+
+        assert_eq!(vtt, "WEBVTT\n\nNOTE\nTitle: Song\n\n");
+    }
+
+    #[test]
+    fn test_syllables_get_inline_timestamps() {
+        let syllables = [("Hello ", 1000, 1500), ("world", 1500, 2000)]
+            .into_iter()
+            .map(|(text, start_ms, end_ms)| LyricSyllable {
+                text: text.trim_end().to_string(),
+                start_ms,
+                end_ms,
+                ends_with_space: text.ends_with(' '),
+                ..Default::default()
+            })
+            .collect();
+        let line = LyricLine {
+            start_ms: 1000,
+            end_ms: 2000,
+            tracks: vec![AnnotatedTrack {
+                content_type: ContentType::Main,
+                content: LyricTrack {
+                    words: vec![Word {
+                        syllables,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let vtt = generate_vtt(&[line], &MetadataStore::new()).unwrap();
+
+        assert!(vtt.contains("00:00:01.000 --> 00:00:02.000\nHello <00:00:01.500>world\n"));
+    }
+}
@@ -5,7 +5,15 @@ pub mod parsers;
 pub mod processors;
 pub mod utils;
 
-use std::{collections::HashMap, hash::BuildHasher};
+#[cfg(test)]
+mod test_utils;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+    iter::Peekable,
+    slice,
+};
 
 use crate::converter::processors::{
     batch_processor, chinese_conversion_processor::ChineseConversionProcessor,
@@ -13,9 +21,9 @@ use crate::converter::processors::{
 use tracing::{debug, warn};
 
 use lyrics_helper_core::{
-    AuxiliaryLineMatchingStrategy, ContentType, ConversionInput, ConversionOptions,
-    ConversionResult, ConversionTask, ConvertError, FullConversionResult, InputFile, LyricFormat,
-    LyricLine, LyricTrack, MetadataStore, ParsedSourceData, TrackMetadataKey,
+    AnnotatedTrack, AuxiliaryLineMatchingStrategy, ContentType, ConversionInput, ConversionOptions,
+    ConversionResult, ConversionTask, ConvertError, FullConversionResult, InputFile, LineEnding,
+    LyricFormat, LyricLine, LyricTrack, MetadataStore, ParsedSourceData, TrackMetadataKey,
 };
 use ttml_processor::{generate_ttml, parse_ttml};
 
@@ -44,7 +52,11 @@ pub fn process_conversion_task(
             Ok(ConversionResult::Single(full_result.output_lyrics))
         }
         ConversionTask::Batch(batch_input) => {
-            let file_groups = batch_processor::discover_and_pair_files(&batch_input.input_dir)?;
+            let file_groups = batch_processor::discover_and_pair_files(
+                &batch_input.input_dir,
+                &batch_input.main_extension_whitelist,
+                &batch_input.pairing,
+            )?;
             let (mut tasks, file_lookup) =
                 batch_processor::create_batch_tasks(file_groups, batch_input.target_format);
 
@@ -132,6 +144,9 @@ pub fn generate_from_parsed<S: BuildHasher>(
 
     metadata_store.deduplicate_values();
 
+    let warnings =
+        processors::feature_loss::feature_loss_warnings(&source_data.lines, target_format, options);
+
     let output_lyrics = match target_format {
         LyricFormat::Lrc => generators::lrc_generator::generate_lrc(
             &source_data.lines,
@@ -187,11 +202,24 @@ pub fn generate_from_parsed<S: BuildHasher>(
         LyricFormat::Lyl => {
             generators::lyricify_lines_generator::generate_lyl(&source_data.lines, &metadata_store)
         }
+        LyricFormat::Vtt => {
+            generators::vtt_generator::generate_vtt(&source_data.lines, &metadata_store)
+        }
+        LyricFormat::Musixmatch => Err(ConvertError::InvalidLyricFormat(
+            "暂不支持生成 Musixmatch JSON 格式".to_string(),
+        )),
+        LyricFormat::Lrcx => {
+            generators::lrcx_generator::generate_lrcx(&source_data.lines, &metadata_store)
+        }
     }?;
+    // 生成器内嵌的用户内容（如自定义的 ASS 头部）可能带有其他换行符，统一为 `\n`，
+    // 保存时再按 `options.line_ending` 转换
+    let output_lyrics = LineEnding::normalize(&output_lyrics);
 
     Ok(FullConversionResult {
         output_lyrics,
         source_data,
+        warnings,
     })
 }
 
@@ -208,6 +236,14 @@ pub fn parse_and_merge(
     input: &ConversionInput,
     options: &ConversionOptions,
 ) -> Result<ParsedSourceData, ConvertError> {
+    parse_and_merge_with_report(input, options).map(|(source, _)| source)
+}
+
+/// 与 [`parse_and_merge`] 相同，但同时返回翻译和罗马音的对齐情况。
+pub fn parse_and_merge_with_report(
+    input: &ConversionInput,
+    options: &ConversionOptions,
+) -> Result<(ParsedSourceData, MergeReport), ConvertError> {
     let mut main_parsed_source = parse_input_file(&input.main_lyric, options)?;
     let mut main_new_lines = main_parsed_source.lines; // 直接获取，因为类型已经是新的 LyricLine
     main_parsed_source.lines = vec![]; // 临时清空，最后会重新赋值
@@ -238,7 +274,7 @@ pub fn parse_and_merge(
             .extend(source.raw_metadata.clone());
     }
 
-    merge_tracks(
+    let report = merge_tracks(
         &mut main_new_lines,
         &translation_sources,
         &romanization_sources,
@@ -254,44 +290,52 @@ pub fn parse_and_merge(
 
     main_parsed_source.lines = main_new_lines;
 
-    Ok(main_parsed_source)
+    Ok((main_parsed_source, report))
+}
+
+/// 翻译和罗马音合并到主歌词时的对齐统计。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// 匹配到翻译的主歌词行数
+    pub matched_translation_lines: usize,
+    /// 没能在容差内对应到任何主歌词行、被丢弃的翻译行数
+    pub unmatched_translation_lines: usize,
+    /// 匹配到罗马音的主歌词行数
+    pub matched_romanization_lines: usize,
+    /// 没能在容差内对应到任何主歌词行、被丢弃的罗马音行数
+    pub unmatched_romanization_lines: usize,
+    /// 参与合并的主歌词行数
+    pub main_lines: usize,
+    /// 落在主歌词行之间的空隙、作为主歌词为空的单独行保留下来的行数
+    pub preserved_lines: usize,
 }
 
 /// 合并主歌词行与翻译、罗马音数据，将翻译和罗马音轨道按时间戳插入到主歌词行中。
+///
+/// 解析器通常会跳过主歌词的空行，与这些空行对应的翻译和罗马音会落在两行主歌词之间的空隙中。
+/// 这类辅助行不会被丢弃，而是作为主歌词为空的单独行插入，时间取自辅助行本身，
+/// 并截止到下一行主歌词开始。与主歌词行时间重叠却超出容差的辅助行仍视为无法对齐。
+///
+/// 返回对齐统计，可据此提示用户调整容差。
 pub fn merge_tracks(
-    main_lines: &mut [LyricLine],
+    main_lines: &mut Vec<LyricLine>,
     translations: &[(Vec<LyricLine>, ParsedSourceData, Option<String>)],
     romanizations: &[(Vec<LyricLine>, ParsedSourceData, Option<String>)],
     strategy: AuxiliaryLineMatchingStrategy,
-) {
-    // 辅助函数：从源数据中提取带时间戳的内容轨道
-    fn extract_content_tracks(
-        sources: &[(Vec<LyricLine>, ParsedSourceData, Option<String>)],
-    ) -> Vec<(u64, u64, LyricTrack)> {
-        let mut timed_tracks = Vec::new();
-
-        for (lines, _, lang) in sources {
-            for line in lines {
-                // 辅助文件的一行理论上只包含一个带主要内容的 AnnotatedTrack
-                if let Some(annotated_track) = line.tracks.first() {
-                    let mut content_track = annotated_track.content.clone();
-                    // 如果轨道本身没有语言标签，则使用文件级别的语言标签
-                    if let Some(l) = lang {
-                        content_track
-                            .metadata
-                            .entry(TrackMetadataKey::Language)
-                            .or_insert_with(|| l.clone());
-                    }
-                    timed_tracks.push((line.start_ms, line.end_ms, content_track));
-                }
-            }
-        }
-        timed_tracks.sort_by_key(|(start_ms, _, _)| *start_ms);
-        timed_tracks
-    }
-
+) -> MergeReport {
+    let mut report = MergeReport {
+        main_lines: main_lines
+            .iter()
+            .filter(|line| {
+                line.tracks
+                    .iter()
+                    .any(|at| at.content_type == ContentType::Main)
+            })
+            .count(),
+        ..Default::default()
+    };
     if translations.is_empty() && romanizations.is_empty() {
-        return;
+        return report;
     }
 
     let tolerance_ms = if let AuxiliaryLineMatchingStrategy::SortedSync { tolerance_ms } = strategy
@@ -307,60 +351,207 @@ pub fn merge_tracks(
 
     let mut trans_iter = translation_tracks.iter().peekable();
     let mut roman_iter = romanization_tracks.iter().peekable();
+    let mut consumed_translations = 0;
+    let mut consumed_romanizations = 0;
+    let mut gap_lines = GapLines::default();
+    let mut previous_end_ms = 0;
 
-    for main_line in main_lines.iter_mut() {
+    for (index, main_line) in main_lines.iter_mut().enumerate() {
         // 假设主歌词行中有一个我们将要合并到的主要内容轨道
         if let Some(main_annotated_track) = main_line
             .tracks
             .iter_mut()
             .find(|at| at.content_type == ContentType::Main)
         {
-            // 跳过所有时间上已经不可能匹配的旧翻译行
-            while let Some((start_ms, ..)) = trans_iter.peek() {
-                if *start_ms + tolerance_ms < main_line.start_ms {
-                    trans_iter.next();
-                } else {
-                    break; // 到达了可能匹配的窗口
-                }
-            }
+            let translations_before = main_annotated_track.translations.len();
+            let romanizations_before = main_annotated_track.romanizations.len();
+            // 跳过所有时间上已经不可能匹配的旧翻译行，空隙中的翻译行留待单独保留
+            let gap_tracks = take_gap_tracks(
+                &mut trans_iter,
+                main_line.start_ms,
+                previous_end_ms,
+                tolerance_ms,
+            );
+            consumed_translations += gap_lines.add(index, main_line.start_ms, gap_tracks, true);
             // 匹配并消耗所有在当前主行时间窗口内的翻译行
-            while let Some((start_ms, end_ms, track)) = trans_iter.peek() {
-                if start_ms.abs_diff(main_line.start_ms) <= tolerance_ms {
-                    main_annotated_track.translations.push((*track).clone());
-                    main_line.end_ms = main_line.end_ms.max(*end_ms);
-                    trans_iter.next(); // 匹配成功, 消耗掉
-                } else {
-                    // 由于列表已排序，此翻译行已在当前主行窗口之外，
-                    // 后续的翻译行更不可能匹配，故中断循环。
-                    // 迭代器指针将留在这里，供下一个主行匹配。
-                    break;
-                }
+            for (_, end_ms, track) in
+                take_matching_tracks(&mut trans_iter, main_line.start_ms, tolerance_ms)
+            {
+                main_annotated_track.translations.push(track.clone());
+                main_line.end_ms = main_line.end_ms.max(*end_ms);
+                consumed_translations += 1;
             }
 
-            while let Some((start_ms, ..)) = roman_iter.peek() {
-                if *start_ms + tolerance_ms < main_line.start_ms {
-                    roman_iter.next();
-                } else {
-                    break;
-                }
+            let gap_tracks = take_gap_tracks(
+                &mut roman_iter,
+                main_line.start_ms,
+                previous_end_ms,
+                tolerance_ms,
+            );
+            consumed_romanizations += gap_lines.add(index, main_line.start_ms, gap_tracks, false);
+            for (_, end_ms, track) in
+                take_matching_tracks(&mut roman_iter, main_line.start_ms, tolerance_ms)
+            {
+                main_annotated_track.romanizations.push(track.clone());
+                main_line.end_ms = main_line.end_ms.max(*end_ms);
+                consumed_romanizations += 1;
             }
-            while let Some((start_ms, end_ms, track)) = roman_iter.peek() {
-                if start_ms.abs_diff(main_line.start_ms) <= tolerance_ms {
-                    main_annotated_track.romanizations.push((*track).clone());
-                    main_line.end_ms = main_line.end_ms.max(*end_ms);
-                    roman_iter.next();
-                } else {
-                    break;
-                }
+
+            if main_annotated_track.translations.len() > translations_before {
+                report.matched_translation_lines += 1;
+            }
+            if main_annotated_track.romanizations.len() > romanizations_before {
+                report.matched_romanization_lines += 1;
             }
         }
+        previous_end_ms = previous_end_ms.max(main_line.end_ms);
     }
+
+    // 最后一行主歌词之后剩余的辅助行
+    let end_index = main_lines.len();
+    let gap_tracks = take_gap_tracks(&mut trans_iter, u64::MAX, previous_end_ms, tolerance_ms);
+    consumed_translations += gap_lines.add(end_index, u64::MAX, gap_tracks, true);
+    let gap_tracks = take_gap_tracks(&mut roman_iter, u64::MAX, previous_end_ms, tolerance_ms);
+    consumed_romanizations += gap_lines.add(end_index, u64::MAX, gap_tracks, false);
+
+    report.preserved_lines = gap_lines.insert_into(main_lines);
+
+    report.unmatched_translation_lines = translation_tracks.len() - consumed_translations;
+    report.unmatched_romanization_lines = romanization_tracks.len() - consumed_romanizations;
+    report
 }
 
 // ==========================================================
 //  辅助函数
 // ==========================================================
 
+/// 带时间范围的辅助行：（开始时间，结束时间，内容轨道）。
+type TimedTrack = (u64, u64, LyricTrack);
+
+/// 从辅助歌词中提取带时间戳的内容轨道，按开始时间排序。
+fn extract_content_tracks(
+    sources: &[(Vec<LyricLine>, ParsedSourceData, Option<String>)],
+) -> Vec<TimedTrack> {
+    let mut timed_tracks = Vec::new();
+
+    for (lines, _, lang) in sources {
+        for line in lines {
+            // 辅助文件的一行理论上只包含一个带主要内容的 AnnotatedTrack
+            if let Some(annotated_track) = line.tracks.first() {
+                let mut content_track = annotated_track.content.clone();
+                // 如果轨道本身没有语言标签，则使用文件级别的语言标签
+                if let Some(l) = lang {
+                    content_track
+                        .metadata
+                        .entry(TrackMetadataKey::Language)
+                        .or_insert_with(|| l.clone());
+                }
+                timed_tracks.push((line.start_ms, line.end_ms, content_track));
+            }
+        }
+    }
+    timed_tracks.sort_by_key(|(start_ms, _, _)| *start_ms);
+    timed_tracks
+}
+
+/// 取出所有开始时间与主歌词行相差不超过容差的辅助行。
+///
+/// 辅助行已按开始时间排序，遇到第一个超出窗口的行即停止，迭代器留在该行供下一行主歌词匹配。
+fn take_matching_tracks<'a>(
+    iter: &mut Peekable<slice::Iter<'a, TimedTrack>>,
+    line_start_ms: u64,
+    tolerance_ms: u64,
+) -> Vec<&'a TimedTrack> {
+    let mut matched = Vec::new();
+    while let Some(entry) =
+        iter.next_if(|(start_ms, ..)| start_ms.abs_diff(line_start_ms) <= tolerance_ms)
+    {
+        matched.push(entry);
+    }
+    matched
+}
+
+/// 取出所有开始于 `before_ms` 之前、已无法匹配的辅助行，
+/// 返回其中开始于上一行主歌词结束（`gap_start_ms`）之后、需要单独保留的行。
+fn take_gap_tracks<'a>(
+    iter: &mut Peekable<slice::Iter<'a, TimedTrack>>,
+    before_ms: u64,
+    gap_start_ms: u64,
+    tolerance_ms: u64,
+) -> Vec<&'a TimedTrack> {
+    let mut gap_tracks = Vec::new();
+    while let Some(entry) =
+        iter.next_if(|(start_ms, ..)| start_ms.saturating_add(tolerance_ms) < before_ms)
+    {
+        if entry.0 + tolerance_ms >= gap_start_ms {
+            gap_tracks.push(entry);
+        }
+    }
+    gap_tracks
+}
+
+/// 合并时收集的主歌词为空、只有翻译或罗马音的行。
+///
+/// 以（插入位置，开始时间）为键，开始时间相同的翻译和罗马音归入同一行。
+#[derive(Default)]
+struct GapLines(BTreeMap<(usize, u64), LyricLine>);
+
+impl GapLines {
+    /// 添加要插入到第 `index` 行主歌词之前的辅助行，结束时间不超过 `before_ms`。
+    ///
+    /// 返回添加的辅助行数。
+    fn add(
+        &mut self,
+        index: usize,
+        before_ms: u64,
+        gap_tracks: Vec<&TimedTrack>,
+        is_translation: bool,
+    ) -> usize {
+        let count = gap_tracks.len();
+        for (start_ms, end_ms, track) in gap_tracks {
+            let line = self
+                .0
+                .entry((index, *start_ms))
+                .or_insert_with(|| LyricLine {
+                    start_ms: *start_ms,
+                    end_ms: *start_ms,
+                    tracks: vec![AnnotatedTrack {
+                        content_type: ContentType::Main,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                });
+            line.end_ms = line.end_ms.max((*end_ms).min(before_ms));
+            let annotated_track = &mut line.tracks[0];
+            if is_translation {
+                annotated_track.translations.push(track.clone());
+            } else {
+                annotated_track.romanizations.push(track.clone());
+            }
+        }
+        count
+    }
+
+    /// 把收集到的行插入主歌词，返回插入的行数。
+    fn insert_into(self, main_lines: &mut Vec<LyricLine>) -> usize {
+        let count = self.0.len();
+        if count == 0 {
+            return 0;
+        }
+        let mut gap_lines = self.0.into_iter().peekable();
+        let mut merged_lines = Vec::with_capacity(main_lines.len() + count);
+        for (index, main_line) in std::mem::take(main_lines).into_iter().enumerate() {
+            while let Some((_, gap_line)) = gap_lines.next_if(|((i, _), _)| *i == index) {
+                merged_lines.push(gap_line);
+            }
+            merged_lines.push(main_line);
+        }
+        merged_lines.extend(gap_lines.map(|(_, gap_line)| gap_line));
+        *main_lines = merged_lines;
+        count
+    }
+}
+
 /// 根据指定的格式解析单个歌词文件内容。
 ///
 /// 这是一个底层的分派函数。
@@ -384,5 +575,161 @@ fn parse_input_file(
         LyricFormat::Spl => parsers::spl_parser::parse_spl(&file.content),
         LyricFormat::Lqe => parsers::lqe_parser::parse_lqe(&file.content, options),
         LyricFormat::Lyl => parsers::lyricify_lines_parser::parse_lyl(&file.content),
+        LyricFormat::Vtt => Err(ConvertError::InvalidLyricFormat(
+            "暂不支持解析 WebVTT 格式".to_string(),
+        )),
+        LyricFormat::Musixmatch => parsers::musixmatch_parser::parse_musixmatch(&file.content),
+        LyricFormat::Lrcx => parsers::lrcx_parser::parse_lrcx(&file.content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::text_line;
+
+    #[test]
+    fn test_merge_tracks_preserves_translation_of_empty_main_line() {
+        // 主歌词在 1000ms 处有一个被跳过的空行
+        let mut main_lines = vec![text_line(0, 1000, "A"), text_line(2000, 3000, "C")];
+        let translations = vec![(
+            vec![
+                text_line(0, 1000, "a"),
+                text_line(1000, 2500, "b"),
+                text_line(2000, 3000, "c"),
+            ],
+            ParsedSourceData::default(),
+            Some("zh-Hans".to_string()),
+        )];
+
+        let report = merge_tracks(
+            &mut main_lines,
+            &translations,
+            &[],
+            AuxiliaryLineMatchingStrategy::SortedSync { tolerance_ms: 20 },
+        );
+
+        assert_eq!(main_lines.len(), 3);
+        let gap_line = &main_lines[1];
+        assert_eq!((gap_line.start_ms, gap_line.end_ms), (1000, 2000));
+        assert_eq!(gap_line.main_text().as_deref(), Some(""));
+        assert_eq!(
+            gap_line.get_translation_by_lang("zh-Hans").unwrap().text(),
+            "b"
+        );
+        assert_eq!(report.preserved_lines, 1);
+        assert_eq!(report.matched_translation_lines, 2);
+        assert_eq!(report.unmatched_translation_lines, 0);
+    }
+
+    #[test]
+    fn test_merge_tracks_drops_misaligned_translation_inside_main_line() {
+        let mut main_lines = vec![text_line(0, 2000, "A"), text_line(2000, 3000, "B")];
+        let translations = vec![(
+            vec![text_line(0, 2000, "a"), text_line(500, 2000, "x")],
+            ParsedSourceData::default(),
+            None,
+        )];
+
+        let report = merge_tracks(
+            &mut main_lines,
+            &translations,
+            &[],
+            AuxiliaryLineMatchingStrategy::SortedSync { tolerance_ms: 20 },
+        );
+
+        assert_eq!(main_lines.len(), 2);
+        assert_eq!(report.preserved_lines, 0);
+        assert_eq!(report.unmatched_translation_lines, 1);
+    }
+
+    #[test]
+    fn test_parse_and_merge_with_report() {
+        let input = ConversionInput {
+            main_lyric: InputFile::new(
+                "[00:01.00]A\n[00:02.00]B\n[00:03.00]C\n".to_string(),
+                LyricFormat::Lrc,
+                None,
+                None,
+            ),
+            translations: vec![InputFile::new(
+                "[00:01.00]甲\n[00:01.50]错位\n[00:02.01]乙\n".to_string(),
+                LyricFormat::Lrc,
+                Some("en".to_string()),
+                None,
+            )],
+            romanizations: vec![InputFile::new(
+                "[00:03.00]shii\n".to_string(),
+                LyricFormat::Lrc,
+                Some("ko-Latn".to_string()),
+                None,
+            )],
+            target_format: LyricFormat::Ttml,
+            user_metadata_overrides: None,
+            additional_metadata: None,
+        };
+
+        let (source_data, report) =
+            parse_and_merge_with_report(&input, &ConversionOptions::default()).unwrap();
+
+        assert_eq!(
+            report,
+            MergeReport {
+                matched_translation_lines: 2,
+                unmatched_translation_lines: 1,
+                matched_romanization_lines: 1,
+                unmatched_romanization_lines: 0,
+                main_lines: 3,
+                preserved_lines: 0,
+            }
+        );
+        let lines = &source_data.lines;
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].get_translation_by_lang("en").unwrap().text(), "乙");
+        assert_eq!(
+            lines[2].get_romanization_by_lang("ko-Latn").unwrap().text(),
+            "shii"
+        );
+    }
+
+    #[test]
+    fn test_line_ending_apply() {
+        let output = "[00:01.00]A\n[00:02.00]B\n";
+
+        assert_eq!(
+            LineEnding::Crlf.apply(output, None),
+            "[00:01.00]A\r\n[00:02.00]B\r\n"
+        );
+        assert_eq!(LineEnding::Lf.apply("A\r\nB\rC\n", None), "A\nB\nC\n");
+        assert_eq!(
+            LineEnding::Keep.apply(output, Some("x\r\ny")),
+            "[00:01.00]A\r\n[00:02.00]B\r\n"
+        );
+        assert_eq!(LineEnding::Keep.apply(output, Some("x\ny")), output);
+        // 源文件只有一行时无法判断，保持生成的结果
+        assert_eq!(LineEnding::Keep.apply(output, Some("x")), output);
+    }
+
+    #[test]
+    fn test_generate_from_parsed_outputs_lf() {
+        let source_data = ParsedSourceData {
+            lines: vec![text_line(0, 1000, "A"), text_line(1000, 2000, "B")],
+            ..Default::default()
+        };
+        let options = ConversionOptions {
+            line_ending: LineEnding::Crlf,
+            ..Default::default()
+        };
+
+        let result = generate_from_parsed(
+            source_data,
+            LyricFormat::Lrc,
+            &options,
+            &None::<HashMap<String, Vec<String>>>,
+        )
+        .unwrap();
+
+        assert!(!result.output_lyrics.contains('\r'));
+        assert!(result.output_lyrics.contains("A\n"));
     }
 }
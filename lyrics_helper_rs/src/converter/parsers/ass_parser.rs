@@ -7,15 +7,15 @@ use std::sync::LazyLock;
 
 use lyrics_helper_core::{
     Agent, AgentStore, AgentType, AnnotatedTrack, ContentType, ConvertError, LyricFormat,
-    LyricLine, LyricSyllable, LyricSyllableBuilder, LyricTrack, ParsedSourceData, TrackMetadataKey,
-    Word,
+    LyricLine, LyricSyllable, LyricSyllableBuilder, LyricTrack, ParseWarning, ParsedSourceData,
+    TrackMetadataKey, Word,
 };
 
 use crate::converter::utils::process_syllable_text;
 
 struct ParserState {
     lines: Vec<LyricLine>,
-    warnings: Vec<String>,
+    warnings: Vec<ParseWarning>,
     agents: AgentStore,
     raw_metadata: HashMap<String, Vec<String>>,
     has_karaoke_tags: bool,
@@ -191,7 +191,7 @@ fn parse_actor(
     actor_str_input: &str,
     style: &str,
     line_num: usize,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> ParsedActorInfo {
     let mut actor_str = actor_str_input.to_string();
     let mut info = ParsedActorInfo::default();
@@ -219,16 +219,16 @@ fn parse_actor(
             let is_aux_style =
                 style == "ts" || style == "trans" || style == "roma" || style.contains("bg-");
             if !is_aux_style {
-                warnings.push(format!(
+                warnings.push(ParseWarning::warning(format!(
                 "第 {line_num} 行: 在非辅助行 (样式: '{style}') 上发现了 'x-lang:' 标签，该标签将被忽略。"
-            ));
+            )));
                 continue;
             }
 
             if info.lang_code.is_some() {
-                warnings.push(format!(
+                warnings.push(ParseWarning::warning(format!(
                     "第 {line_num} 行: 发现多个 'x-lang:' 标签，将使用最后一个。"
-                ));
+                )));
             }
             info.lang_code = Some(tag.trim_start_matches("x-lang:").to_string());
         } else if tag == "x-mark" {
@@ -253,10 +253,10 @@ fn parse_actor(
                 .iter()
                 .map(|(t, _, _)| (*t).to_string())
                 .collect();
-            warnings.push(format!(
+            warnings.push(ParseWarning::warning(format!(
                 "第 {line_num} 行: 发现冲突的角色标签 {:?}，将使用第一个 ('{}')。",
                 conflicting_tags, role_tags_found[0].0
-            ));
+            )));
         }
 
         if let Some((_, agent_id, agent_type)) = role_tags_found.first() {
@@ -267,9 +267,9 @@ fn parse_actor(
             info.agent_type = AgentType::Person;
         }
     } else if (style == "ts" || style == "trans" || style == "roma") && info.lang_code.is_none() {
-        warnings.push(format!(
+        warnings.push(ParseWarning::warning(format!(
             "第 {line_num} 行: 辅助行样式 '{style}' 缺少 'x-lang:' 标签，可能导致语言关联错误。"
-        ));
+        )));
     }
 
     info
@@ -382,7 +382,7 @@ fn handle_main_lyric_line(
 fn handle_aux_lyric_line(
     new_lines: &mut [LyricLine],
     has_karaoke_tags: bool,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
     caps: &regex::Captures,
     actor_info: ParsedActorInfo,
     parsed_style: &ParsedStyleInfo,
@@ -432,9 +432,9 @@ fn handle_aux_lyric_line(
                 }
                 line.end_ms = line.end_ms.max(calculated_end_ms);
             } else {
-                warnings.push(format!(
+                warnings.push(ParseWarning::error(format!(
                     "第 {subtitle_line_num} 行: 无法为样式找到匹配的 {target_content_type:?} 轨道进行附加，已忽略。"
-                ));
+                )));
             }
         } else {
             // 逐行歌词模式
@@ -458,9 +458,9 @@ fn handle_aux_lyric_line(
             line.end_ms = line.end_ms.max(end_ms);
         }
     } else {
-        warnings.push(format!(
+        warnings.push(ParseWarning::error(format!(
             "第 {subtitle_line_num} 行: 找到了一个辅助行，但它前面没有任何主歌词行可以附加，已忽略。"
-        ));
+        )));
     }
     Ok(())
 }
@@ -514,9 +514,9 @@ fn process_dialogue_line(
                 subtitle_line_num,
             )?;
         } else {
-            state.warnings.push(format!(
+            state.warnings.push(ParseWarning::warning(format!(
                 "第 {subtitle_line_num} 行: 样式 '{style}' 不受支持，已被忽略。"
-            ));
+            )));
         }
     }
     Ok(())
@@ -588,14 +588,14 @@ pub fn parse_ass(content: &str) -> Result<ParsedSourceData, ConvertError> {
             if line_type == "Dialogue"
                 && let Err(e) = process_dialogue_line(&mut state, &caps, subtitle_line_num)
             {
-                state
-                    .warnings
-                    .push(format!("第 {subtitle_line_num} 行处理失败: {e}"));
+                state.warnings.push(ParseWarning::error(format!(
+                    "第 {subtitle_line_num} 行处理失败: {e}"
+                )));
             }
         } else if in_events_section {
-            state.warnings.push(format!(
+            state.warnings.push(ParseWarning::error(format!(
                 "第 {subtitle_line_num} 行: 格式与预期的 ASS 事件格式不匹配，已跳过。"
-            ));
+            )));
         }
     }
 
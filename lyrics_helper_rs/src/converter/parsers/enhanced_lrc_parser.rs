@@ -5,6 +5,7 @@
 //! 2. `[line_time]<word_time>word<word_time>word...` (无末尾时间戳)
 //!
 //! 同时处理行起始时间与第一个词起始时间不一致的情况，并以后者为准。
+//! 第一个逐字时间标签之前的文字作为从行时间戳开始的音节。
 
 use regex::Regex;
 use std::collections::HashMap;
@@ -14,7 +15,7 @@ use crate::converter::utils::{normalize_text_whitespace, parse_and_store_metadat
 
 use lyrics_helper_core::{
     AnnotatedTrack, ContentType, ConvertError, LyricLine, LyricLineBuilder, LyricSyllable,
-    LyricSyllableBuilder, LyricTrack, ParsedSourceData, Word,
+    LyricSyllableBuilder, LyricTrack, ParseWarning, ParsedSourceData, Word,
 };
 
 /// 用于匹配行时间标签，例如 [00:12.34]
@@ -33,7 +34,7 @@ const DEFAULT_LINE_DURATION_MS: u64 = 5000;
 pub fn parse_enhanced_lrc(content: &str) -> Result<ParsedSourceData, ConvertError> {
     let mut lines: Vec<LyricLine> = Vec::new();
     let mut raw_metadata: HashMap<String, Vec<String>> = HashMap::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
 
     for (line_num, line_str) in content.lines().enumerate() {
         let line_num_one_based = line_num + 1;
@@ -47,9 +48,9 @@ pub fn parse_enhanced_lrc(content: &str) -> Result<ParsedSourceData, ConvertErro
 
         if let Some(line_time_match) = LINE_TIME_RE.find(line_str_trimmed) {
             let Ok(Some(line_start_ms)) = parse_lrc_time_tag(line_time_match.as_str()) else {
-                warnings.push(format!(
+                warnings.push(ParseWarning::error(format!(
                     "第 {line_num_one_based} 行: 无法解析行时间戳，已跳过。"
-                ));
+                )));
                 continue;
             };
 
@@ -60,6 +61,13 @@ pub fn parse_enhanced_lrc(content: &str) -> Result<ParsedSourceData, ConvertErro
                 &mut warnings,
                 line_num_one_based,
             );
+            if let Some(first_word_time) = syllables.first().map(|s| s.start_ms)
+                && line_start_ms != first_word_time
+            {
+                warnings.push(ParseWarning::warning(format!(
+                    "第 {line_num_one_based} 行: 行时间戳 [{line_start_ms}] 与第一个音节时间戳 <{first_word_time}> 不匹配，已以后者为准。"
+                )));
+            }
 
             let lyric_line = if !syllables.is_empty() {
                 // 如果行开始时间与第一个词的开始时间不同，使用第一个词的开始时间
@@ -123,11 +131,14 @@ pub fn parse_enhanced_lrc(content: &str) -> Result<ParsedSourceData, ConvertErro
     })
 }
 
-/// 从单行文本中解析出所有音节
-fn parse_syllables_from_line(
+/// 从单行文本中解析出所有音节。
+///
+/// 第一个时间标签之前的文字从 `line_start_ms` 开始。没有后续时间标签的最后一个音节
+/// 结束时间为 0 且没有时长，由调用方按行结束时间补全。
+pub(crate) fn parse_syllables_from_line(
     line_content: &str,
     line_start_ms: u64,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
     line_num: usize,
 ) -> Vec<LyricSyllable> {
     let time_tags: Vec<(u64, std::ops::Range<usize>)> = WORD_TIME_RE
@@ -140,19 +151,26 @@ fn parse_syllables_from_line(
         })
         .collect();
 
-    if time_tags.is_empty() {
+    let Some((first_word_time, first_range)) = time_tags.first() else {
         return Vec::new();
-    }
+    };
 
-    if let Some((first_word_time, _)) = time_tags.first()
-        && line_start_ms != *first_word_time
-    {
-        warnings.push(format!(
-                "第 {line_num} 行: 行时间戳 [{line_start_ms}] 与第一个音节时间戳 <{first_word_time}> 不匹配，已以后者为准。"
-            ));
+    let mut syllables = Vec::new();
+    let leading_slice = &line_content[..first_range.start];
+    let leading_text = normalize_text_whitespace(leading_slice);
+    if !leading_text.is_empty() {
+        syllables.push(
+            LyricSyllableBuilder::default()
+                .text(leading_text)
+                .start_ms(line_start_ms)
+                .end_ms(*first_word_time)
+                .duration_ms(first_word_time.saturating_sub(line_start_ms))
+                .ends_with_space(leading_slice.ends_with(' '))
+                .build()
+                .unwrap(),
+        );
     }
 
-    let mut syllables = Vec::new();
     for i in 0..time_tags.len() {
         let (current_time, current_range) = (&time_tags[i].0, &time_tags[i].1);
 
@@ -172,9 +190,9 @@ fn parse_syllables_from_line(
             if let Some(nt) = next_time
                 && nt < *current_time
             {
-                warnings.push(format!(
+                warnings.push(ParseWarning::warning(format!(
                     "第 {line_num} 行: 检测到时间戳乱序或回溯 (<{current_time}> -> <{nt}>)。"
-                ));
+                )));
             }
 
             let end_ms = next_time.unwrap_or(0);
@@ -202,7 +220,7 @@ fn parse_syllables_from_line(
 }
 
 /// 第二遍处理，修正所有行和音节的结束时间
-fn finalize_end_times(lines: &mut [LyricLine], _warnings: &mut [String]) {
+fn finalize_end_times(lines: &mut [LyricLine], _warnings: &mut [ParseWarning]) {
     // 首先按开始时间排序，确保时间线是正确的
     lines.sort_by_key(|line| line.start_ms);
     for i in 0..lines.len() {
@@ -288,4 +306,19 @@ mod tests {
         assert_eq!(syls1[1].start_ms, 10500);
         assert_eq!(line1.end_ms, 12500);
     }
+
+    #[test]
+    fn test_text_before_first_word_tag_starts_at_line_time() {
+        let data =
+            parse_enhanced_lrc("[00:03.00]Hi<00:03.40>gh<00:04.00>\n[00:05.00]Next").unwrap();
+
+        let timings: Vec<_> = data.lines[0].tracks[0]
+            .content
+            .syllables()
+            .map(|s| (s.text.as_str(), s.start_ms, s.end_ms))
+            .collect();
+        assert_eq!(timings, vec![("Hi", 3000, 3400), ("gh", 3400, 4000)]);
+        assert_eq!(data.lines[0].start_ms, 3000);
+        assert!(data.warnings.is_empty());
+    }
 }
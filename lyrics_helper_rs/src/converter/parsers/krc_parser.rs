@@ -13,7 +13,7 @@ use std::sync::LazyLock;
 
 use lyrics_helper_core::{
     AnnotatedTrack, ContentType, ConvertError, LyricFormat, LyricLine, LyricLineBuilder,
-    LyricSyllable, LyricTrack, ParsedSourceData, Word,
+    LyricSyllable, LyricTrack, ParseWarning, ParsedSourceData, Word,
 };
 
 /// 匹配 KRC 行级时间戳 `[start,duration]`
@@ -43,7 +43,7 @@ struct RawSyllable {
 pub fn parse_krc(content: &str) -> Result<ParsedSourceData, ConvertError> {
     let mut lines: Vec<LyricLine> = Vec::new();
     let mut raw_metadata: HashMap<String, Vec<String>> = HashMap::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
 
     let aux_data = extract_auxiliary_data_from_krc(content)?;
     let mut aux_line_index = 0;
@@ -93,7 +93,7 @@ fn parse_krc_line(
     line_num: usize,
     aux_data: &KrcAuxiliaryData,
     aux_line_index: usize,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<Option<LyricLine>, ConvertError> {
     if let Some(line_caps) = KRC_LINE_TIMESTAMP_REGEX.captures(trimmed_line) {
         let line_start_ms: u64 = line_caps["start"].parse()?;
@@ -113,7 +113,9 @@ fn parse_krc_line(
         }
 
         if raw_syllables.is_empty() {
-            warnings.push(format!("第 {line_num} 行: 未找到任何时间戳。"));
+            warnings.push(ParseWarning::warning(format!(
+                "第 {line_num} 行: 未找到任何时间戳。"
+            )));
             return Ok(None);
         }
 
@@ -131,7 +133,9 @@ fn parse_krc_line(
             .iter()
             .all(|w| w.syllables.is_empty())
         {
-            warnings.push(format!("第 {line_num} 行: 内容只包含空格，已跳过。"));
+            warnings.push(ParseWarning::hint(format!(
+                "第 {line_num} 行: 内容只包含空格，已跳过。"
+            )));
             return Ok(None);
         }
 
@@ -145,7 +149,9 @@ fn parse_krc_line(
 
         Ok(Some(line))
     } else {
-        warnings.push(format!("第 {line_num} 行: 未能识别的行格式。"));
+        warnings.push(ParseWarning::warning(format!(
+            "第 {line_num} 行: 未能识别的行格式。"
+        )));
         Ok(None)
     }
 }
@@ -155,7 +161,7 @@ fn process_raw_syllables_into_annotated_track(
     aux_data: &KrcAuxiliaryData,
     aux_line_index: usize,
     line_num: usize,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> AnnotatedTrack {
     let mut final_main_syllables: Vec<LyricSyllable> = Vec::new();
     let mut final_roma_syllables: Vec<LyricSyllable> = Vec::new();
@@ -165,11 +171,11 @@ fn process_raw_syllables_into_annotated_track(
     if let Some(texts) = romanization_texts
         && texts.len() != raw_syllables.len()
     {
-        warnings.push(format!(
+        warnings.push(ParseWarning::warning(format!(
             "第 {line_num} 行: 主歌词音节数 ({}) 与罗马音音节数 ({}) 不匹配，对齐可能不准确。",
             raw_syllables.len(),
             texts.len()
-        ));
+        )));
     }
 
     for (i, raw_syl) in raw_syllables.iter().enumerate() {
@@ -5,12 +5,15 @@ use std::cell::OnceCell;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 
-use crate::converter::utils::{normalize_text_whitespace, parse_and_store_metadata};
+use crate::converter::{
+    parsers::enhanced_lrc_parser::parse_syllables_from_line,
+    utils::{normalize_text_whitespace, parse_and_store_metadata},
+};
 
 use lyrics_helper_core::{
     AnnotatedTrack, ContentType, ConvertError, LrcLineRole, LrcParsingOptions,
     LrcSameTimestampStrategy, LyricFormat, LyricLine, LyricLineBuilder, LyricSyllable, LyricTrack,
-    ParsedSourceData, Word,
+    ParseWarning, ParsedSourceData, Word,
 };
 
 /// 用于匹配一个完整的 LRC 歌词行，捕获时间戳部分和文本部分
@@ -26,13 +29,28 @@ static LRC_TIMESTAMP_EXTRACT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 struct TempLrcEntry {
     timestamp_ms: u64,
     text: String,
+    /// 由内联时间标签拆分出的音节。普通 LRC 行为 `None`
+    syllables: Option<Vec<InlineSyllable>>,
+}
+
+/// 由内联时间标签拆分出的一个音节。
+///
+/// 时间相对于行内第一个行时间戳，这样同一行带有多个行时间戳时（重复的副歌）
+/// 每次出现都能得到正确的绝对时间。
+#[derive(Clone)]
+struct InlineSyllable {
+    text: String,
+    start_offset_ms: i64,
+    /// 为 `None` 时表示持续到行结束
+    end_offset_ms: Option<i64>,
+    ends_with_space: bool,
 }
 
 #[derive(Default)]
 struct InitialParseResult {
     entries: Vec<TempLrcEntry>,
     metadata: HashMap<String, Vec<String>>,
-    warnings: Vec<String>,
+    warnings: Vec<ParseWarning>,
 }
 
 const DEFAULT_LAST_LINE_DURATION_MS: u64 = 10000;
@@ -43,6 +61,9 @@ pub fn parse_lrc(
     options: &LrcParsingOptions,
 ) -> Result<ParsedSourceData, ConvertError> {
     let mut initial_result = parse_lines_to_temp_entries(content)?;
+    if !options.keep_offset_tag {
+        apply_offset_tag(&mut initial_result);
+    }
 
     initial_result.entries.sort_by_key(|e| e.timestamp_ms);
 
@@ -51,16 +72,54 @@ pub fn parse_lrc(
 
     initial_result.warnings.extend(processing_warnings);
 
+    let has_inline_timing = initial_result
+        .entries
+        .iter()
+        .any(|entry| entry.syllables.is_some());
+
     Ok(ParsedSourceData {
         lines: final_lyric_lines,
         raw_metadata: initial_result.metadata,
         source_format: LyricFormat::Lrc,
-        is_line_timed_source: true,
+        is_line_timed_source: !has_inline_timing,
         warnings: initial_result.warnings,
         ..Default::default()
     })
 }
 
+/// 按 LRC 约定把 `[offset:]` 应用到所有行时间戳，并移除该标签。
+///
+/// 正的偏移量使歌词提前显示，即实际时间为时间戳减去偏移量，最小为 0。
+/// 内联逐字时间相对于行时间戳保存，会随行一起平移。偏移量无法解析时保留标签并给出警告。
+fn apply_offset_tag(result: &mut InitialParseResult) {
+    let Some(key) = result
+        .metadata
+        .keys()
+        .find(|key| key.eq_ignore_ascii_case("offset"))
+        .cloned()
+    else {
+        return;
+    };
+    let value = result.metadata[&key]
+        .first()
+        .map(|v| v.trim().to_string())
+        .unwrap_or_default();
+
+    match value.parse::<i64>() {
+        Ok(offset_ms) => {
+            result.metadata.remove(&key);
+            for entry in &mut result.entries {
+                entry.timestamp_ms = entry
+                    .timestamp_ms
+                    .saturating_add_signed(offset_ms.saturating_neg());
+            }
+        }
+        Err(_) => result.warnings.push(ParseWarning::warning(format!(
+            "无法解析 LRC 偏移量 '{value}'，已作为元数据保留"
+        ))),
+    }
+}
+
 fn parse_lines_to_temp_entries(content: &str) -> Result<InitialParseResult, ConvertError> {
     let mut result = InitialParseResult::default();
 
@@ -75,7 +134,10 @@ fn parse_lines_to_temp_entries(content: &str) -> Result<InitialParseResult, Conv
         if let Some(line_caps) = LRC_LINE_REGEX.captures(line_str_trimmed) {
             let all_timestamps_str = line_caps.get(1).map_or("", |m| m.as_str());
             let raw_text_part = line_caps.get(2).map_or("", |m| m.as_str());
-            let text_part = normalize_text_whitespace(raw_text_part);
+
+            let mut first_timestamp_ms: Option<u64> = None;
+            let mut inline_syllables: Option<Vec<InlineSyllable>> = None;
+            let mut text_part = String::new();
 
             for ts_cap in LRC_TIMESTAMP_EXTRACT_REGEX.captures_iter(all_timestamps_str) {
                 let minutes: u64 = ts_cap[1].parse()?;
@@ -90,16 +152,31 @@ fn parse_lines_to_temp_entries(content: &str) -> Result<InitialParseResult, Conv
                 };
                 if let Ok(ms) = milliseconds {
                     if seconds < 60 {
+                        let timestamp_ms = (minutes * 60 + seconds) * 1000 + ms;
+                        if first_timestamp_ms.is_none() {
+                            first_timestamp_ms = Some(timestamp_ms);
+                            inline_syllables = parse_inline_syllables(
+                                raw_text_part,
+                                timestamp_ms,
+                                line_num + 1,
+                                &mut result.warnings,
+                            );
+                            text_part = inline_syllables.as_ref().map_or_else(
+                                || normalize_text_whitespace(raw_text_part),
+                                |syllables| inline_syllables_text(syllables),
+                            );
+                        }
                         result.entries.push(TempLrcEntry {
-                            timestamp_ms: (minutes * 60 + seconds) * 1000 + ms,
+                            timestamp_ms,
                             text: text_part.clone(),
+                            syllables: inline_syllables.clone(),
                         });
                     } else {
-                        result.warnings.push(format!(
+                        result.warnings.push(ParseWarning::error(format!(
                             "LRC秒数无效 (行 {}): '{}'",
                             line_num + 1,
                             seconds
-                        ));
+                        )));
                     }
                 }
             }
@@ -108,12 +185,50 @@ fn parse_lines_to_temp_entries(content: &str) -> Result<InitialParseResult, Conv
     Ok(result)
 }
 
+/// 用增强型 LRC 的规则将行文本中的内联时间标签拆分为音节。文本中没有内联时间标签时返回 `None`。
+///
+/// 音节时间转换为相对行时间戳的偏移，没有后续时间标签的最后一个音节持续到行结束。
+fn parse_inline_syllables(
+    raw_text: &str,
+    line_start_ms: u64,
+    line_num: usize,
+    warnings: &mut Vec<ParseWarning>,
+) -> Option<Vec<InlineSyllable>> {
+    let syllables = parse_syllables_from_line(raw_text, line_start_ms, warnings, line_num);
+    let last_index = syllables.len().checked_sub(1)?;
+
+    let offset = |time_ms: u64| time_ms as i64 - line_start_ms as i64;
+    let inline_syllables = syllables
+        .into_iter()
+        .enumerate()
+        .map(|(index, syllable)| InlineSyllable {
+            start_offset_ms: offset(syllable.start_ms),
+            end_offset_ms: syllable.duration_ms.map(|_| offset(syllable.end_ms)),
+            ends_with_space: syllable.ends_with_space && index < last_index,
+            text: syllable.text,
+        })
+        .collect();
+    Some(inline_syllables)
+}
+
+/// 将音节拼接为整行文本。
+fn inline_syllables_text(syllables: &[InlineSyllable]) -> String {
+    let mut text = String::new();
+    for syllable in syllables {
+        text.push_str(&syllable.text);
+        if syllable.ends_with_space {
+            text.push(' ');
+        }
+    }
+    text
+}
+
 fn process_timestamp_groups(
     temp_entries: &[TempLrcEntry],
     options: &LrcParsingOptions,
-) -> (Vec<LyricLine>, Vec<String>) {
+) -> (Vec<LyricLine>, Vec<ParseWarning>) {
     let mut final_lyric_lines: Vec<LyricLine> = Vec::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
     let primary_language_cache: OnceCell<heuristic_analyzer::PrimaryLanguage> = OnceCell::new();
 
     let mut i = 0;
@@ -174,7 +289,7 @@ fn handle_heuristic_strategy(
     start_ms: u64,
     end_ms: u64,
     primary_language_cache: &OnceCell<heuristic_analyzer::PrimaryLanguage>,
-) -> (Vec<AnnotatedTrack>, Vec<String>) {
+) -> (Vec<AnnotatedTrack>, Vec<ParseWarning>) {
     let warnings = vec![];
 
     let lang = *primary_language_cache.get_or_init(|| {
@@ -185,7 +300,29 @@ fn handle_heuristic_strategy(
     let line_texts: Vec<&str> = group_lines.iter().map(|e| e.text.as_str()).collect();
     let assignments = heuristic_analyzer::assign_roles(&line_texts, lang);
     let tracks = heuristic_analyzer::build_annotated_track(&assignments, start_ms, end_ms)
-        .map_or_else(Vec::new, |track| vec![track]);
+        .map_or_else(Vec::new, |mut track| {
+            // 启发式分析只处理文本，这里把带内联时间的行换回逐字轨道
+            let timed_track = |track: &LyricTrack| {
+                let text = track.text();
+                group_lines
+                    .iter()
+                    .find(|e| e.syllables.is_some() && e.text == text)
+                    .map(|entry| entry_track(entry, start_ms, end_ms))
+            };
+            if let Some(content) = timed_track(&track.content) {
+                track.content = content;
+            }
+            for aux in track
+                .translations
+                .iter_mut()
+                .chain(track.romanizations.iter_mut())
+            {
+                if let Some(timed) = timed_track(aux) {
+                    *aux = timed;
+                }
+            }
+            vec![track]
+        });
 
     (tracks, warnings)
 }
@@ -208,10 +345,10 @@ fn handle_first_is_main_strategy(
     let main_entry = meaningful_lines[0];
     let translations_entries = &meaningful_lines[1..];
 
-    let main_track = new_line_timed_track(main_entry.text.clone(), start_ms, end_ms);
+    let main_track = entry_track(main_entry, start_ms, end_ms);
     let translations = translations_entries
         .iter()
-        .map(|entry| new_line_timed_track(entry.text.clone(), start_ms, end_ms))
+        .map(|entry| entry_track(entry, start_ms, end_ms))
         .collect();
 
     vec![AnnotatedTrack {
@@ -231,7 +368,7 @@ fn handle_all_are_main_strategy(
         .iter()
         .filter(|e| !e.text.is_empty())
         .map(|entry| {
-            let main_track = new_line_timed_track(entry.text.clone(), start_ms, end_ms);
+            let main_track = entry_track(entry, start_ms, end_ms);
             AnnotatedTrack {
                 content_type: ContentType::Main,
                 content: main_track,
@@ -246,16 +383,16 @@ fn handle_use_role_order_strategy(
     roles: &[LrcLineRole],
     start_ms: u64,
     end_ms: u64,
-) -> (Vec<AnnotatedTrack>, Vec<String>) {
+) -> (Vec<AnnotatedTrack>, Vec<ParseWarning>) {
     let mut warnings = vec![];
 
     if group_lines.len() != roles.len() {
-        warnings.push(format!(
+        warnings.push(ParseWarning::warning(format!(
             "{}ms: 歌词行数（{}）与提供的角色数（{}）不匹配。",
             start_ms,
             group_lines.len(),
             roles.len()
-        ));
+        )));
     }
 
     let mut main_content: Option<LyricTrack> = None;
@@ -268,13 +405,13 @@ fn handle_use_role_order_strategy(
             continue; // 空行作为占位符, 直接跳过
         }
 
-        let track = new_line_timed_track(entry.text.clone(), start_ms, end_ms);
+        let track = entry_track(entry, start_ms, end_ms);
         match role {
             LrcLineRole::Main => {
                 if main_role_assigned {
-                    warnings.push(format!(
+                    warnings.push(ParseWarning::warning(format!(
                         "{start_ms}ms：指定了多个主歌词行。随后的主歌词行将被视为翻译行。"
-                    ));
+                    )));
                     translations.push(track);
                 } else {
                     main_content = Some(track);
@@ -287,15 +424,11 @@ fn handle_use_role_order_strategy(
     }
 
     if main_content.is_none() && !group_lines.iter().all(|e| e.text.is_empty()) {
-        warnings.push(format!(
+        warnings.push(ParseWarning::warning(format!(
             "{start_ms}ms: 未设置主歌词行。默认将第一行作为主歌词行。"
-        ));
+        )));
         if let Some(first_non_empty) = group_lines.iter().find(|e| !e.text.is_empty()) {
-            main_content = Some(new_line_timed_track(
-                first_non_empty.text.clone(),
-                start_ms,
-                end_ms,
-            ));
+            main_content = Some(entry_track(first_non_empty, start_ms, end_ms));
         }
     }
 
@@ -317,7 +450,7 @@ fn handle_strategy_for_group(
     end_ms: u64,
     options: &LrcParsingOptions,
     primary_language_cache: &OnceCell<heuristic_analyzer::PrimaryLanguage>,
-) -> (Vec<AnnotatedTrack>, Vec<String>) {
+) -> (Vec<AnnotatedTrack>, Vec<ParseWarning>) {
     match &options.same_timestamp_strategy {
         LrcSameTimestampStrategy::Heuristic => {
             handle_heuristic_strategy(group_lines, start_ms, end_ms, primary_language_cache)
@@ -336,6 +469,40 @@ fn handle_strategy_for_group(
     }
 }
 
+/// 为一个临时条目构建轨道。带内联时间标签的条目生成逐字音节，否则生成整行音节。
+fn entry_track(entry: &TempLrcEntry, start_ms: u64, end_ms: u64) -> LyricTrack {
+    let Some(inline_syllables) = &entry.syllables else {
+        return new_line_timed_track(entry.text.clone(), start_ms, end_ms);
+    };
+
+    let absolute = |offset_ms: i64| start_ms.saturating_add_signed(offset_ms);
+    let syllables = inline_syllables
+        .iter()
+        .map(|syllable| {
+            let syllable_start = absolute(syllable.start_offset_ms);
+            let syllable_end = syllable
+                .end_offset_ms
+                .map_or(end_ms, absolute)
+                .max(syllable_start);
+            LyricSyllable {
+                text: syllable.text.clone(),
+                start_ms: syllable_start,
+                end_ms: syllable_end,
+                duration_ms: Some(syllable_end - syllable_start),
+                ends_with_space: syllable.ends_with_space,
+            }
+        })
+        .collect();
+
+    LyricTrack {
+        words: vec![Word {
+            syllables,
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
 fn new_line_timed_track(text: String, start_ms: u64, end_ms: u64) -> LyricTrack {
     LyricTrack {
         words: vec![Word {
@@ -661,6 +828,7 @@ mod tests {
                 LrcLineRole::Romanization,
                 LrcLineRole::Translation,
             ]),
+            ..Default::default()
         };
         let parsed_data = parse_lrc(content, &options).unwrap();
         let track = &parsed_data.lines[0].tracks[0];
@@ -674,6 +842,7 @@ mod tests {
         let content = "[00:15.50]君が好きだと叫びたい\n[00:15.50]想大声说我爱你\n[00:15.50]Kimi ga suki da to sakebitai";
         let options = LrcParsingOptions {
             same_timestamp_strategy: LrcSameTimestampStrategy::Heuristic,
+            ..Default::default()
         };
         let parsed_data = parse_lrc(content, &options).unwrap();
         assert_eq!(parsed_data.lines.len(), 1);
@@ -698,6 +867,7 @@ mod tests {
         let content = "[01:05.10]能不能给我一首歌的时间\n[01:05.10]Can you give me the time of a song\n[01:05.10]Neng bu neng gei wo yi shou ge de shi jian";
         let options = LrcParsingOptions {
             same_timestamp_strategy: LrcSameTimestampStrategy::Heuristic,
+            ..Default::default()
         };
         let parsed_data = parse_lrc(content, &options).unwrap();
         assert_eq!(parsed_data.lines.len(), 1);
@@ -722,6 +892,7 @@ mod tests {
         let content = "[00:40.00]사랑해요\n[00:40.00]I love you\n[00:40.00]Saranghaeyo";
         let options = LrcParsingOptions {
             same_timestamp_strategy: LrcSameTimestampStrategy::Heuristic,
+            ..Default::default()
         };
         let parsed_data = parse_lrc(content, &options).unwrap();
         assert!(
@@ -749,6 +920,7 @@ mod tests {
         let content = "[00:33.00]Never gonna give you up\n[00:33.00]绝不放弃你";
         let options = LrcParsingOptions {
             same_timestamp_strategy: LrcSameTimestampStrategy::Heuristic,
+            ..Default::default()
         };
         let parsed_data = parse_lrc(content, &options).unwrap();
         assert!(
@@ -773,6 +945,7 @@ mod tests {
         let content = "[00:21.00]ありがとう\n[00:21.00]Thank you\n[00:21.00]Arigatou";
         let options = LrcParsingOptions {
             same_timestamp_strategy: LrcSameTimestampStrategy::Heuristic,
+            ..Default::default()
         };
         let parsed_data = parse_lrc(content, &options).unwrap();
         assert!(
@@ -794,4 +967,111 @@ mod tests {
             Some("Arigatou".to_string())
         );
     }
+
+    fn syllable_timings(track: &LyricTrack) -> Vec<(String, u64, u64)> {
+        track
+            .syllables()
+            .map(|s| (s.text.clone(), s.start_ms, s.end_ms))
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_word_timestamps_become_syllables() {
+        let content = "[00:10.00]<00:10.00>Hello <00:10.50>world<00:11.20>\n[00:12.00]Next";
+        let parsed_data = parse_lrc(content, &LrcParsingOptions::default()).unwrap();
+
+        assert!(!parsed_data.is_line_timed_source);
+        let track = &parsed_data.lines[0].tracks[0].content;
+        assert_eq!(track.text(), "Hello world");
+        assert_eq!(
+            syllable_timings(track),
+            vec![
+                ("Hello".to_string(), 10000, 10500),
+                ("world".to_string(), 10500, 11200),
+            ]
+        );
+        assert!(track.syllables().next().unwrap().ends_with_space);
+    }
+
+    #[test]
+    fn test_mixed_plain_and_inline_lines() {
+        let content = "[00:01.00]Plain line\n[00:03.00]Hi<00:03.40>gh<00:04.000>er\n[00:05.00]";
+        let parsed_data = parse_lrc(content, &LrcParsingOptions::default()).unwrap();
+
+        let plain = &parsed_data.lines[0].tracks[0].content;
+        assert_eq!(
+            syllable_timings(plain),
+            vec![("Plain line".to_string(), 1000, 3000)]
+        );
+
+        // 第一个标签之前的文字从行时间戳开始，最后一个音节持续到行结束
+        let timed = &parsed_data.lines[1].tracks[0].content;
+        assert_eq!(
+            syllable_timings(timed),
+            vec![
+                ("Hi".to_string(), 3000, 3400),
+                ("gh".to_string(), 3400, 4000),
+                ("er".to_string(), 4000, 5000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inline_timestamps_with_plain_translation() {
+        let content = "[00:20.00]<00:20.00>你<00:20.30>好<00:20.60>\n[00:20.00]Hello\n[00:22.00]";
+        let parsed_data = parse_lrc(content, &LrcParsingOptions::default()).unwrap();
+
+        let track = &parsed_data.lines[0].tracks[0];
+        assert_eq!(track.content.syllables().count(), 2);
+        assert_eq!(track.content.text(), "你好");
+        assert_eq!(
+            get_optional_track_text(&track.translations),
+            Some("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_inline_timestamps_follow_repeated_line_timestamps() {
+        let content = "[00:10.00][00:30.00]La<00:10.50>la<00:11.00>\n[00:40.00]";
+        let parsed_data = parse_lrc(content, &LrcParsingOptions::default()).unwrap();
+
+        let repeated = &parsed_data.lines[1].tracks[0].content;
+        assert_eq!(
+            syllable_timings(repeated),
+            vec![
+                ("La".to_string(), 30000, 30500),
+                ("la".to_string(), 30500, 31000)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_offset_tag_is_applied_to_timestamps() {
+        let content = "[offset:+500]\n[00:10.00]<00:10.00>Hi <00:10.40>there\n[00:12.00]Next";
+        let parsed_data = parse_lrc(content, &LrcParsingOptions::default()).unwrap();
+
+        assert!(!parsed_data.raw_metadata.contains_key("offset"));
+        assert_eq!(parsed_data.lines[0].start_ms, 9500);
+        assert_eq!(
+            syllable_timings(&parsed_data.lines[0].tracks[0].content),
+            vec![
+                ("Hi".to_string(), 9500, 9900),
+                ("there".to_string(), 9900, 11500),
+            ]
+        );
+        assert_eq!(parsed_data.lines[1].start_ms, 11500);
+    }
+
+    #[test]
+    fn test_offset_tag_can_be_kept_as_metadata() {
+        let content = "[offset:-300]\n[00:10.00]Line";
+        let options = LrcParsingOptions {
+            keep_offset_tag: true,
+            ..Default::default()
+        };
+        let parsed_data = parse_lrc(content, &options).unwrap();
+
+        assert_eq!(parsed_data.raw_metadata["offset"], vec!["-300".to_string()]);
+        assert_eq!(parsed_data.lines[0].start_ms, 10000);
+    }
 }
@@ -0,0 +1,388 @@
+//! LRCX（`LyricsX`）格式解析器。
+//!
+//! LRCX 在 LRC 的基础上，用与主歌词行时间戳相同的附加行携带额外信息：
+//! - `[00:10.000][tr:zh-Hans]翻译`：翻译，冒号后为可选的语言代码；
+//! - `[00:10.000][roma]romaji`：罗马音；
+//! - `[00:10.000][tt]<0,0><500,5><1200>`：逐字时间。每个 `<偏移,索引>` 表示从主歌词第
+//!   `索引` 个字符开始的音节在行开始后 `偏移` 毫秒开始，末尾的 `<偏移>` 为整行的时长。
+//!
+//! 只有时间戳而没有文本的行表示上一行的结束；如果后面紧跟着时间戳相同的附加行，它同时也是
+//! 一行主歌词为空、只有翻译或罗马音的行。振假名 (`[fu]`) 附加行会被忽略。
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::converter::parsers::enhanced_lrc_parser::parse_lrc_time_tag;
+use crate::converter::utils::{
+    normalize_text_whitespace, parse_and_store_metadata, process_syllable_text,
+};
+
+use lyrics_helper_core::{
+    AnnotatedTrack, ContentType, ConvertError, LyricFormat, LyricLine, LyricSyllable, LyricTrack,
+    ParseWarning, ParsedSourceData, TrackMetadataKey, Word,
+};
+
+/// 用于匹配行首的时间标签，例如 [00:12.34]
+static LINE_TIME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[\d{2,}:\d{2}[.:]\d{2,3}]").unwrap());
+/// 用于匹配时间标签之后的附加信息标签，例如 [tr:zh-Hans]、[tt]
+static ATTACHMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[(tt|tr|roma|fu)(?::([^\]]*))?]").unwrap());
+/// 用于匹配 [tt] 中的 <偏移,索引> 或表示时长的 <偏移>
+static INLINE_TIME_TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<(\d+)(?:,(\d+))?>").unwrap());
+
+const DEFAULT_LINE_DURATION_MS: u64 = 5000;
+
+/// 尚未确定结束时间的一行歌词及其附加信息。
+#[derive(Debug, Default)]
+struct PendingLine {
+    start_ms: u64,
+    text: String,
+    /// 由 [tt] 的时长或随后的空行给出的结束时间
+    end_ms: Option<u64>,
+    /// [tt] 中的 (相对行开始的偏移, 字符索引)
+    time_tags: Vec<(u64, usize)>,
+    /// (语言代码, 翻译文本)
+    translations: Vec<(Option<String>, String)>,
+    romanizations: Vec<String>,
+}
+
+/// 解析 LRCX 格式内容到 `ParsedSourceData` 结构。
+pub fn parse_lrcx(content: &str) -> Result<ParsedSourceData, ConvertError> {
+    let mut pending_lines: Vec<PendingLine> = Vec::new();
+    // 行开始时间到 `pending_lines` 下标的映射，用于把附加行挂到对应的主歌词行
+    let mut line_index_by_time: HashMap<u64, usize> = HashMap::new();
+    let mut raw_metadata: HashMap<String, Vec<String>> = HashMap::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
+    // 最近一个只有时间戳的行的时间
+    let mut last_bare_time_ms: Option<u64> = None;
+
+    for (line_num, line_str) in content.lines().enumerate() {
+        let line_num_one_based = line_num + 1;
+        let line_str_trimmed = line_str.trim();
+
+        if line_str_trimmed.is_empty()
+            || parse_and_store_metadata(line_str_trimmed, &mut raw_metadata)
+        {
+            continue;
+        }
+
+        let Some(time_match) = LINE_TIME_RE.find(line_str_trimmed) else {
+            warnings.push(ParseWarning::warning(format!(
+                "第 {line_num_one_based} 行: 无法识别的行，已跳过。"
+            )));
+            continue;
+        };
+        let Ok(Some(start_ms)) = parse_lrc_time_tag(time_match.as_str()) else {
+            warnings.push(ParseWarning::error(format!(
+                "第 {line_num_one_based} 行: 无法解析行时间戳，已跳过。"
+            )));
+            continue;
+        };
+        let rest = &line_str_trimmed[time_match.end()..];
+
+        if let Some(caps) = ATTACHMENT_RE.captures(rest) {
+            let index = match line_index_by_time.get(&start_ms) {
+                Some(&index) => index,
+                None if last_bare_time_ms == Some(start_ms) => {
+                    line_index_by_time.insert(start_ms, pending_lines.len());
+                    pending_lines.push(PendingLine {
+                        start_ms,
+                        ..Default::default()
+                    });
+                    pending_lines.len() - 1
+                }
+                None => {
+                    warnings.push(ParseWarning::error(format!(
+                        "第 {line_num_one_based} 行: 找不到时间戳相同的主歌词行，已跳过该附加行。"
+                    )));
+                    continue;
+                }
+            };
+            let attachment_text = &rest[caps.get(0).map_or(0, |m| m.end())..];
+            apply_attachment(
+                &mut pending_lines[index],
+                &caps[1],
+                caps.get(2).map(|m| m.as_str()),
+                attachment_text,
+                start_ms,
+            );
+            continue;
+        }
+
+        let text = rest.trim();
+        if text.is_empty() {
+            last_bare_time_ms = Some(start_ms);
+            // 空行标记上一行的结束
+            if let Some(last) = pending_lines.last_mut()
+                && last.end_ms.is_none()
+                && start_ms >= last.start_ms
+            {
+                last.end_ms = Some(start_ms);
+            }
+            continue;
+        }
+
+        last_bare_time_ms = None;
+        line_index_by_time.insert(start_ms, pending_lines.len());
+        pending_lines.push(PendingLine {
+            start_ms,
+            text: text.to_string(),
+            ..Default::default()
+        });
+    }
+
+    let has_word_timing = pending_lines.iter().any(|line| !line.time_tags.is_empty());
+    let lines = finalize_lines(pending_lines, &mut warnings);
+
+    Ok(ParsedSourceData {
+        lines,
+        raw_metadata,
+        warnings,
+        source_format: LyricFormat::Lrcx,
+        is_line_timed_source: !has_word_timing,
+        ..Default::default()
+    })
+}
+
+/// 把一个附加行的内容记录到对应的主歌词行。
+fn apply_attachment(
+    pending: &mut PendingLine,
+    kind: &str,
+    argument: Option<&str>,
+    attachment_text: &str,
+    start_ms: u64,
+) {
+    match kind {
+        "tt" => {
+            for tag in INLINE_TIME_TAG_RE.captures_iter(attachment_text) {
+                let Ok(offset_ms) = tag[1].parse::<u64>() else {
+                    continue;
+                };
+                match tag.get(2).and_then(|m| m.as_str().parse::<usize>().ok()) {
+                    Some(char_index) => pending.time_tags.push((offset_ms, char_index)),
+                    None => pending.end_ms = Some(start_ms + offset_ms),
+                }
+            }
+        }
+        "tr" => {
+            let language = argument
+                .map(|lang| lang.trim().to_string())
+                .filter(|lang| !lang.is_empty());
+            let text = normalize_text_whitespace(attachment_text);
+            if !text.is_empty() {
+                pending.translations.push((language, text));
+            }
+        }
+        "roma" => {
+            let text = normalize_text_whitespace(attachment_text);
+            if !text.is_empty() {
+                pending.romanizations.push(text);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 确定每行的结束时间并构建最终的歌词行。
+fn finalize_lines(
+    mut pending_lines: Vec<PendingLine>,
+    warnings: &mut Vec<ParseWarning>,
+) -> Vec<LyricLine> {
+    pending_lines.sort_by_key(|line| line.start_ms);
+    let next_starts: Vec<Option<u64>> = pending_lines
+        .iter()
+        .skip(1)
+        .map(|line| Some(line.start_ms))
+        .chain(std::iter::once(None))
+        .collect();
+
+    pending_lines
+        .into_iter()
+        .zip(next_starts)
+        .map(|(pending, next_start_ms)| {
+            let start_ms = pending.start_ms;
+            let end_ms = pending
+                .end_ms
+                .or(next_start_ms)
+                .unwrap_or(start_ms + DEFAULT_LINE_DURATION_MS)
+                .max(start_ms);
+
+            let content = if pending.text.is_empty() {
+                LyricTrack::default()
+            } else if pending.time_tags.is_empty() {
+                line_timed_track(pending.text, start_ms, end_ms)
+            } else {
+                word_timed_track(&pending.text, &pending.time_tags, start_ms, end_ms)
+                    .unwrap_or_else(|| {
+                        warnings.push(ParseWarning::warning(format!(
+                            "时间 {start_ms}ms 处的逐字时间标签与歌词不匹配，已按逐行处理。"
+                        )));
+                        line_timed_track(pending.text.clone(), start_ms, end_ms)
+                    })
+            };
+
+            let translations = pending
+                .translations
+                .into_iter()
+                .map(|(language, text)| {
+                    let mut track = line_timed_track(text, start_ms, end_ms);
+                    if let Some(language) = language {
+                        track.metadata.insert(TrackMetadataKey::Language, language);
+                    }
+                    track
+                })
+                .collect();
+            let romanizations = pending
+                .romanizations
+                .into_iter()
+                .map(|text| line_timed_track(text, start_ms, end_ms))
+                .collect();
+
+            LyricLine {
+                start_ms,
+                end_ms,
+                tracks: vec![AnnotatedTrack {
+                    content_type: ContentType::Main,
+                    content,
+                    translations,
+                    romanizations,
+                }],
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn line_timed_track(text: String, start_ms: u64, end_ms: u64) -> LyricTrack {
+    LyricTrack {
+        words: vec![Word {
+            syllables: vec![LyricSyllable {
+                text,
+                start_ms,
+                end_ms,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// 按 [tt] 中的字符索引把主歌词切分为音节。索引超出文本长度时返回 `None`。
+fn word_timed_track(
+    text: &str,
+    time_tags: &[(u64, usize)],
+    line_start_ms: u64,
+    line_end_ms: u64,
+) -> Option<LyricTrack> {
+    let mut tags = time_tags.to_vec();
+    tags.sort_by_key(|&(_, char_index)| char_index);
+    tags.dedup_by_key(|&mut (_, char_index)| char_index);
+
+    let char_count = text.chars().count();
+    if tags.iter().any(|&(_, char_index)| char_index > char_count) {
+        return None;
+    }
+    let byte_offset = |char_index: usize| {
+        text.char_indices()
+            .nth(char_index)
+            .map_or(text.len(), |(offset, _)| offset)
+    };
+
+    let mut syllables: Vec<LyricSyllable> = Vec::new();
+    for (i, &(offset_ms, char_index)) in tags.iter().enumerate() {
+        let next_tag = tags.get(i + 1);
+        let raw_text =
+            &text[byte_offset(char_index)..next_tag.map_or(text.len(), |t| byte_offset(t.1))];
+        let Some((clean_text, ends_with_space)) = process_syllable_text(raw_text, &mut syllables)
+        else {
+            continue;
+        };
+        let start_ms = line_start_ms + offset_ms;
+        let end_ms = next_tag
+            .map_or(line_end_ms, |&(next_offset_ms, _)| {
+                line_start_ms + next_offset_ms
+            })
+            .max(start_ms);
+        syllables.push(LyricSyllable {
+            text: clean_text,
+            start_ms,
+            end_ms,
+            duration_ms: Some(end_ms - start_ms),
+            ends_with_space,
+        });
+    }
+
+    // 第一个时间标签之前的文本不属于任何音节
+    if syllables.is_empty() || tags.first().is_some_and(|&(_, first)| first > 0) {
+        return None;
+    }
+
+    Some(LyricTrack {
+        words: vec![Word {
+            syllables,
+            ..Default::default()
+        }],
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lrcx_translation_and_word_timing() {
+        let content = "[ti:Song]\n\
+            [00:10.000]Hello world\n\
+            [00:10.000][tr:zh-Hans]你好世界\n\
+            [00:10.000][tt]<0,0><500,6><1200>\n\
+            [00:12.000]Next line\n\
+            [00:12.000][roma]nekusuto\n\
+            [00:14.500]";
+        let data = parse_lrcx(content).unwrap();
+
+        assert_eq!(data.raw_metadata.get("ti"), Some(&vec!["Song".to_string()]));
+        assert!(!data.is_line_timed_source);
+        assert_eq!(data.lines.len(), 2);
+
+        let first = &data.lines[0];
+        assert_eq!((first.start_ms, first.end_ms), (10000, 11200));
+        let main = first.main_track().unwrap();
+        let syllables: Vec<_> = main.content.syllables().collect();
+        assert_eq!(syllables.len(), 2);
+        assert_eq!(syllables[0].text, "Hello");
+        assert!(syllables[0].ends_with_space);
+        assert_eq!((syllables[0].start_ms, syllables[0].end_ms), (10000, 10500));
+        assert_eq!(syllables[1].text, "world");
+        assert_eq!((syllables[1].start_ms, syllables[1].end_ms), (10500, 11200));
+        assert_eq!(main.translations[0].text(), "你好世界");
+        assert_eq!(
+            main.translations[0]
+                .metadata
+                .get(&TrackMetadataKey::Language),
+            Some(&"zh-Hans".to_string())
+        );
+
+        let second = &data.lines[1];
+        assert_eq!((second.start_ms, second.end_ms), (12000, 14500));
+        assert_eq!(
+            second.main_track().unwrap().romanizations[0].text(),
+            "nekusuto"
+        );
+    }
+
+    #[test]
+    fn test_lrcx_invalid_time_tags_fall_back_to_line_timing() {
+        let content = "[00:01.00]短句\n[00:01.00][tt]<0,0><300,9>\n[00:03.00]下一句";
+        let data = parse_lrcx(content).unwrap();
+
+        assert_eq!(data.lines.len(), 2);
+        assert_eq!(data.lines[0].main_text().as_deref(), Some("短句"));
+        assert_eq!(data.lines[0].end_ms, 3000);
+        assert_eq!(data.warnings.len(), 1);
+    }
+}
@@ -8,7 +8,7 @@ use crate::converter::utils::normalize_text_whitespace;
 
 use lyrics_helper_core::{
     AnnotatedTrack, ContentType, ConvertError, LyricFormat, LyricLine, LyricLineBuilder,
-    LyricSyllableBuilder, LyricTrack, ParsedSourceData, Word,
+    LyricSyllableBuilder, LyricTrack, ParseWarning, ParsedSourceData, Word,
 };
 
 static LYL_LINE_REGEX: LazyLock<Regex> =
@@ -17,7 +17,7 @@ static LYL_LINE_REGEX: LazyLock<Regex> =
 /// 解析 LYL 格式内容到 `ParsedSourceData` 结构。
 pub fn parse_lyl(content: &str) -> Result<ParsedSourceData, ConvertError> {
     let mut lines: Vec<LyricLine> = Vec::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
 
     for (i, line_str) in content.lines().enumerate() {
         let line_num = i + 1;
@@ -38,9 +38,9 @@ pub fn parse_lyl(content: &str) -> Result<ParsedSourceData, ConvertError> {
             }
 
             if end_ms < start_ms {
-                warnings.push(format!(
+                warnings.push(ParseWarning::warning(format!(
                     "第 {line_num} 行: 结束时间 {end_ms}ms 在开始时间 {start_ms}ms 之前。"
-                ));
+                )));
             }
 
             let main_content_track = LyricTrack {
@@ -74,7 +74,9 @@ pub fn parse_lyl(content: &str) -> Result<ParsedSourceData, ConvertError> {
                 .unwrap();
             lines.push(line);
         } else {
-            warnings.push(format!("第 {line_num} 行: 未能识别的行格式。"));
+            warnings.push(ParseWarning::warning(format!(
+                "第 {line_num} 行: 未能识别的行格式。"
+            )));
         }
     }
 
@@ -114,6 +116,6 @@ mod tests {
         let parsed_data = parse_lyl(content).unwrap();
         assert_eq!(parsed_data.lines.len(), 2);
         assert_eq!(parsed_data.warnings.len(), 1);
-        assert!(parsed_data.warnings[0].contains("结束时间"));
+        assert!(parsed_data.warnings[0].message.contains("结束时间"));
     }
 }
@@ -4,8 +4,9 @@ use std::collections::HashMap;
 
 use crate::converter::utils::{parse_and_store_metadata, process_syllable_text};
 use lyrics_helper_core::{
-    AnnotatedTrack, ContentType, ConvertError, LyricFormat, LyricLine, LyricLineBuilder,
-    LyricSyllable, LyricSyllableBuilder, LyricTrack, ParsedSourceData, Word, lys_properties,
+    Agent, AgentStore, AgentType, AnnotatedTrack, ContentType, ConvertError, LyricFormat,
+    LyricLine, LyricLineBuilder, LyricSyllable, LyricSyllableBuilder, LyricTrack, ParseWarning,
+    ParsedSourceData, Word, lys_properties,
 };
 use regex::Regex;
 use std::sync::LazyLock;
@@ -103,7 +104,7 @@ fn parse_lys_line(line_str: &str, line_num: usize) -> Result<(u8, LyricLine), Co
 pub fn parse_lys(content: &str) -> Result<ParsedSourceData, ConvertError> {
     let mut lines: Vec<LyricLine> = Vec::new();
     let mut raw_metadata: HashMap<String, Vec<String>> = HashMap::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
 
     for (i, line_str) in content.lines().enumerate() {
         let line_num = i + 1;
@@ -134,9 +135,9 @@ pub fn parse_lys(content: &str) -> Result<ParsedSourceData, ConvertError> {
 
                         if main_line_has_bg {
                             // 如果主歌词行已有背景，则提升为新的主歌词行
-                            warnings.push(format!(
+                            warnings.push(ParseWarning::warning(format!(
                                 "第 {line_num} 行: 连续的背景行，将提升为新的主歌词行。"
-                            ));
+                            )));
                             parsed_line.agent.clone_from(&main_line.agent);
                             lines.push(parsed_line);
                         } else if let Some(mut bg_track) = parsed_line.tracks.pop() {
@@ -144,9 +145,9 @@ pub fn parse_lys(content: &str) -> Result<ParsedSourceData, ConvertError> {
                             main_line.tracks.push(bg_track);
                         }
                     } else {
-                        warnings.push(format!(
+                        warnings.push(ParseWarning::warning(format!(
                             "第 {line_num} 行: 背景行出现在任何主歌词行之前，将提升为主歌词行。"
-                        ));
+                        )));
                         parsed_line.agent = Some("v1".to_string());
                         lines.push(parsed_line);
                     }
@@ -160,9 +161,9 @@ pub fn parse_lys(content: &str) -> Result<ParsedSourceData, ConvertError> {
                         | lys_properties::MAIN_UNSET
                         | lys_properties::MAIN_LEFT => Some("v1".to_string()),
                         _ => {
-                            warnings.push(format!(
+                            warnings.push(ParseWarning::warning(format!(
                                 "第 {line_num} 行: 未定义的 LYS 属性值 `{property}`。"
-                            ));
+                            )));
                             Some("v1".to_string())
                         }
                     };
@@ -171,7 +172,9 @@ pub fn parse_lys(content: &str) -> Result<ParsedSourceData, ConvertError> {
                 }
             }
             Err(e) => {
-                warnings.push(format!("第 {line_num} 行: 解析失败，已跳过。错误: {e}"));
+                warnings.push(ParseWarning::error(format!(
+                    "第 {line_num} 行: 解析失败，已跳过。错误: {e}"
+                )));
             }
         }
     }
@@ -179,9 +182,23 @@ pub fn parse_lys(content: &str) -> Result<ParsedSourceData, ConvertError> {
     // 按开始时间对所有行进行排序
     lines.sort_by_key(|l| l.start_ms);
 
+    // LYS 只区分左右两侧，分别对应 v1 和 v2
+    let mut agents = AgentStore::new();
+    for agent_id in lines.iter().filter_map(|l| l.agent.as_deref()) {
+        agents
+            .agents_by_id
+            .entry(agent_id.to_string())
+            .or_insert_with(|| Agent {
+                id: agent_id.to_string(),
+                name: None,
+                agent_type: AgentType::Person,
+            });
+    }
+
     Ok(ParsedSourceData {
         lines,
         raw_metadata,
+        agents,
         warnings,
         source_format: LyricFormat::Lys,
         is_line_timed_source: false,
@@ -271,7 +288,7 @@ mod tests {
 
         assert_eq!(result.lines.len(), 2);
         assert_eq!(result.warnings.len(), 1);
-        assert!(result.warnings[0].contains("连续的背景行"));
+        assert!(result.warnings[0].message.contains("连续的背景行"));
 
         let line1 = &result.lines[0];
         assert_eq!(line1.agent, Some("v1".to_string()));
@@ -294,7 +311,11 @@ mod tests {
 
         assert_eq!(result.lines.len(), 1);
         assert_eq!(result.warnings.len(), 1);
-        assert!(result.warnings[0].contains("背景行出现在任何主歌词行之前"));
+        assert!(
+            result.warnings[0]
+                .message
+                .contains("背景行出现在任何主歌词行之前")
+        );
 
         let line = &result.lines[0];
         assert_eq!(line.agent, Some("v1".to_string()));
@@ -335,7 +356,7 @@ mod tests {
         );
 
         assert_eq!(result.warnings.len(), 1);
-        assert!(result.warnings[0].contains("解析失败"));
+        assert!(result.warnings[0].message.contains("解析失败"));
     }
 
     #[test]
@@ -366,4 +387,16 @@ mod tests {
         assert_eq!(syllables[1].text, "Word2");
         assert!(!syllables[1].ends_with_space);
     }
+
+    #[test]
+    fn test_agents_are_registered_for_both_sides() {
+        let content = "[4]Left(100,200)\n[5]Right(400,200)\n[8](Echo)(450,100)";
+        let result = parse_lys(content).unwrap();
+
+        let mut agent_ids: Vec<_> = result.agents.agents_by_id.keys().cloned().collect();
+        agent_ids.sort();
+        assert_eq!(agent_ids, vec!["v1".to_string(), "v2".to_string()]);
+        assert_eq!(result.lines[1].agent.as_deref(), Some("v2"));
+        assert!(result.lines[1].background_track().is_some());
+    }
 }
@@ -6,8 +6,10 @@ pub mod enhanced_lrc_parser;
 pub mod krc_parser;
 pub mod lqe_parser;
 pub mod lrc_parser;
+pub mod lrcx_parser;
 pub mod lyricify_lines_parser;
 pub mod lys_parser;
+pub mod musixmatch_parser;
 pub mod qrc_parser;
 pub mod spl_parser;
 pub mod yrc_parser;
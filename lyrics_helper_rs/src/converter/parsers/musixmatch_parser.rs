@@ -0,0 +1,304 @@
+//! Musixmatch `RichSync` JSON 格式解析器。
+//!
+//! 支持三种输入：
+//! - `richsync_body` 本身，即一个由行对象组成的 JSON 数组；
+//! - `track.richsync.get` 等接口的完整响应，其中 `richsync_body` 是内嵌的 JSON 字符串；
+//! - 自行组合的对象，可同时包含 `richsync_body` 与 `translations_list`。
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::converter::utils::process_syllable_text;
+use lyrics_helper_core::{
+    AnnotatedTrack, ContentType, ConvertError, LyricFormat, LyricLine, LyricSyllable, LyricTrack,
+    ParseWarning, ParsedSourceData, Word,
+};
+
+/// `RichSync` 中的一行歌词。
+#[derive(Debug, Deserialize)]
+struct RichSyncLine {
+    /// 行开始时间（秒）
+    ts: f64,
+    /// 行结束时间（秒）
+    te: f64,
+    /// 音节列表
+    #[serde(default)]
+    l: Vec<RichSyncSyllable>,
+    /// 整行文本
+    #[serde(default)]
+    x: String,
+}
+
+/// `RichSync` 中的一个音节。
+#[derive(Debug, Deserialize)]
+struct RichSyncSyllable {
+    /// 音节文本
+    c: String,
+    /// 相对行开始时间的偏移（秒）
+    o: f64,
+}
+
+/// 翻译列表中的一条逐行翻译。
+#[derive(Debug, Deserialize)]
+struct LineTranslation {
+    /// 原文
+    #[serde(default, alias = "matched_line")]
+    snippet: String,
+    /// 译文
+    #[serde(default)]
+    description: String,
+    /// 语言代码
+    #[serde(default)]
+    language: String,
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn seconds_to_ms(seconds: f64) -> u64 {
+    (seconds.max(0.0) * 1000.0).round() as u64
+}
+
+/// 在 JSON 树中递归查找第一个指定键的值。
+fn find_key<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map
+            .get(key)
+            .or_else(|| map.values().find_map(|v| find_key(v, key))),
+        Value::Array(items) => items.iter().find_map(|v| find_key(v, key)),
+        _ => None,
+    }
+}
+
+/// 从输入中取出 `RichSync` 行数组。
+fn extract_richsync_lines(root: &Value) -> Result<Vec<Value>, ConvertError> {
+    if let Value::Array(items) = root {
+        return Ok(items.clone());
+    }
+
+    match find_key(root, "richsync_body") {
+        Some(Value::String(body)) => {
+            let inner: Value = serde_json::from_str(body).map_err(|e| {
+                ConvertError::json_parse(e, "解析 richsync_body 内嵌 JSON 失败".to_string())
+            })?;
+            match inner {
+                Value::Array(items) => Ok(items),
+                _ => Err(ConvertError::InvalidJsonStructure(
+                    "richsync_body 应为行对象组成的数组".to_string(),
+                )),
+            }
+        }
+        Some(Value::Array(items)) => Ok(items.clone()),
+        Some(_) => Err(ConvertError::InvalidJsonStructure(
+            "richsync_body 既不是字符串也不是数组".to_string(),
+        )),
+        None => Err(ConvertError::InvalidJsonStructure(
+            "未找到 RichSync 歌词，输入应为行数组或包含 richsync_body 字段".to_string(),
+        )),
+    }
+}
+
+/// 从输入中取出逐行翻译，键为原文。
+fn extract_translations(root: &Value) -> HashMap<String, LineTranslation> {
+    let Some(Value::Array(items)) = find_key(root, "translations_list") else {
+        return HashMap::new();
+    };
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let translation = item.get("translation").unwrap_or(item);
+            LineTranslation::deserialize(translation).ok()
+        })
+        .filter(|t| !t.snippet.trim().is_empty() && !t.description.trim().is_empty())
+        .map(|t| (t.snippet.trim().to_string(), t))
+        .collect()
+}
+
+fn build_line(raw: &RichSyncLine) -> Option<LyricLine> {
+    let line_start_ms = seconds_to_ms(raw.ts);
+    let line_end_ms = seconds_to_ms(raw.te).max(line_start_ms);
+
+    let mut syllables: Vec<LyricSyllable> = Vec::new();
+    for (i, syllable) in raw.l.iter().enumerate() {
+        let start_ms = seconds_to_ms(raw.ts + syllable.o);
+        let end_ms = raw
+            .l
+            .get(i + 1)
+            .map_or(line_end_ms, |next| seconds_to_ms(raw.ts + next.o))
+            .max(start_ms);
+
+        if let Some((text, ends_with_space)) = process_syllable_text(&syllable.c, &mut syllables) {
+            syllables.push(LyricSyllable {
+                text,
+                start_ms,
+                end_ms,
+                ends_with_space,
+                ..Default::default()
+            });
+        }
+    }
+
+    if syllables.is_empty() {
+        let text = raw.x.trim();
+        if text.is_empty() {
+            return None;
+        }
+        syllables.push(LyricSyllable {
+            text: text.to_string(),
+            start_ms: line_start_ms,
+            end_ms: line_end_ms,
+            ..Default::default()
+        });
+    }
+
+    let mut line = LyricLine::new(line_start_ms, line_end_ms);
+    line.add_track(AnnotatedTrack {
+        content_type: ContentType::Main,
+        content: LyricTrack {
+            words: vec![Word {
+                syllables,
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    Some(line)
+}
+
+/// 粗略判断一段 JSON 是否为 Musixmatch `RichSync` 数据，用于区分同为 `.json` 的 Apple Music 歌词。
+#[must_use]
+pub fn looks_like_musixmatch(content: &str) -> bool {
+    let trimmed = content.trim_start();
+    content.contains("richsync_body")
+        || (trimmed.starts_with('[') && content.contains("\"ts\"") && content.contains("\"l\""))
+}
+
+/// 解析 Musixmatch `RichSync` JSON 格式的字符串内容。
+pub fn parse_musixmatch(content: &str) -> Result<ParsedSourceData, ConvertError> {
+    let root: Value = serde_json::from_str(content)
+        .map_err(|e| ConvertError::json_parse(e, "解析 Musixmatch JSON 失败".to_string()))?;
+
+    let raw_lines = extract_richsync_lines(&root)?;
+    let translations = extract_translations(&root);
+
+    let mut lines = Vec::with_capacity(raw_lines.len());
+    let mut warnings = Vec::new();
+
+    for (i, value) in raw_lines.into_iter().enumerate() {
+        let raw = RichSyncLine::deserialize(value).map_err(|e| {
+            ConvertError::InvalidJsonStructure(format!(
+                "第 {} 行 RichSync 数据格式错误: {e}",
+                i + 1
+            ))
+        })?;
+
+        let key = raw.x.trim().to_string();
+        let Some(mut line) = build_line(&raw) else {
+            continue;
+        };
+
+        if let Some(translation) = translations.get(&key) {
+            let language = Some(translation.language.as_str()).filter(|s| !s.is_empty());
+            line.add_translation(ContentType::Main, translation.description.trim(), language);
+        }
+
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        warnings.push(ParseWarning::warning("Musixmatch JSON 中没有可用的歌词行"));
+    }
+
+    Ok(ParsedSourceData {
+        lines,
+        source_format: LyricFormat::Musixmatch,
+        is_line_timed_source: false,
+        warnings,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn main_syllables(line: &LyricLine) -> Vec<&LyricSyllable> {
+        line.main_track()
+            .unwrap()
+            .content
+            .words
+            .iter()
+            .flat_map(|w| &w.syllables)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_raw_richsync_array() {
+        let content = r#"[
+            {"ts": 1.5, "te": 3.0, "l": [{"c": "Hello", "o": 0}, {"c": " ", "o": 0.6}, {"c": "world", "o": 0.8}], "x": "Hello world"},
+            {"ts": 4.0, "te": 5.0, "l": [], "x": ""}
+        ]"#;
+
+        let parsed = parse_musixmatch(content).unwrap();
+        assert_eq!(parsed.source_format, LyricFormat::Musixmatch);
+        assert_eq!(parsed.lines.len(), 1);
+
+        let line = &parsed.lines[0];
+        assert_eq!((line.start_ms, line.end_ms), (1500, 3000));
+
+        let syllables = main_syllables(line);
+        assert_eq!(syllables.len(), 2);
+        assert_eq!(syllables[0].text, "Hello");
+        assert_eq!((syllables[0].start_ms, syllables[0].end_ms), (1500, 2100));
+        assert!(syllables[0].ends_with_space);
+        assert_eq!(syllables[1].text, "world");
+        assert_eq!((syllables[1].start_ms, syllables[1].end_ms), (2300, 3000));
+    }
+
+    #[test]
+    fn test_parse_response_with_translations() {
+        let body = r#"[{"ts": 0.5, "te": 2.0, "l": [{"c": "Hi", "o": 0}], "x": "Hi"}]"#;
+        let content = serde_json::json!({
+            "message": {
+                "body": {
+                    "richsync": { "richsync_body": body },
+                    "translations_list": [
+                        { "translation": { "snippet": "Hi", "description": "你好", "language": "zh" } }
+                    ]
+                }
+            }
+        })
+        .to_string();
+
+        let parsed = parse_musixmatch(&content).unwrap();
+        assert_eq!(parsed.lines.len(), 1);
+
+        let main = parsed.lines[0].main_track().unwrap();
+        assert_eq!(main.translations.len(), 1);
+        assert_eq!(main.translations[0].words[0].syllables[0].text, "你好");
+    }
+
+    #[test]
+    fn test_parse_errors_are_descriptive() {
+        let err = parse_musixmatch(r#"{"foo": 1}"#).unwrap_err();
+        assert!(err.to_string().contains("richsync_body"));
+
+        let err = parse_musixmatch(r#"[{"ts": 1.0, "l": []}]"#).unwrap_err();
+        assert!(err.to_string().contains("第 1 行"));
+    }
+
+    #[test]
+    fn test_looks_like_musixmatch() {
+        assert!(looks_like_musixmatch(
+            r#"[{"ts": 1.0, "te": 2.0, "l": [], "x": ""}]"#
+        ));
+        assert!(looks_like_musixmatch(
+            r#"{"richsync": {"richsync_body": "[]"}}"#
+        ));
+        assert!(!looks_like_musixmatch(
+            r#"{"data": [{"id": "1", "attributes": {"ttml": "<tt/>"}}]}"#
+        ));
+    }
+}
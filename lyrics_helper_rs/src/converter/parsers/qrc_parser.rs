@@ -5,7 +5,8 @@
 use crate::converter::utils::{parse_and_store_metadata, process_syllable_text};
 use lyrics_helper_core::{
     AnnotatedTrack, ContentType, ConvertError, FuriganaSyllable, LyricFormat, LyricLine,
-    LyricLineBuilder, LyricSyllable, LyricSyllableBuilder, LyricTrack, ParsedSourceData, Word,
+    LyricLineBuilder, LyricSyllable, LyricSyllableBuilder, LyricTrack, ParseWarning,
+    ParsedSourceData, Word,
 };
 use regex::Regex;
 use std::{collections::HashMap, sync::LazyLock};
@@ -97,7 +98,7 @@ pub fn parse_qrc(content: &str) -> Result<ParsedSourceData, ConvertError> {
 fn parse_furigana_qrc(
     full_lyric_content: &str,
     kana_stream: &str,
-) -> Result<(Vec<MatchedWord>, Vec<String>), ConvertError> {
+) -> Result<(Vec<MatchedWord>, Vec<ParseWarning>), ConvertError> {
     let kana_tokens = tokenize_kana(kana_stream)?;
 
     let mut lyric_tokens: Vec<(LyricToken, usize)> = Vec::new();
@@ -116,7 +117,7 @@ fn parse_furigana_qrc(
     }
 
     let mut matched_words: Vec<MatchedWord> = Vec::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
     let mut lyric_idx = 0;
 
     for kana_token in &kana_tokens {
@@ -156,10 +157,10 @@ fn parse_furigana_qrc(
                 .map(|s| s.text.clone())
                 .collect();
 
-            warnings.push(format!(
+            warnings.push(ParseWarning::hint(format!(
                 "注音 '{}' (对应 {} 个汉字) 未能在歌词中找到匹配项，已忽略。",
                 orphan_kana_text, kana_token.char_count
-            ));
+            )));
         }
     }
 
@@ -394,7 +395,7 @@ fn parse_standard_qrc(
     lyric_content: &str,
     raw_metadata: HashMap<String, Vec<String>>,
 ) -> ParsedSourceData {
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
     let mut final_lines: Vec<LyricLine> = Vec::new();
     let mut pending_bg_line: Option<LyricLine> = None;
     let mut last_pushed_was_candidate = false;
@@ -404,10 +405,10 @@ fn parse_standard_qrc(
     for (current_line, is_candidate) in parsed_lines_iter {
         if is_candidate {
             if let Some(prev_bg_line) = pending_bg_line.take() {
-                warnings.push(format!(
+                warnings.push(ParseWarning::warning(format!(
                     "行 '{}' 与另一背景人声行相邻，当作主歌词处理。",
                     line_to_string(&prev_bg_line)
-                ));
+                )));
                 final_lines.push(prev_bg_line);
                 last_pushed_was_candidate = true;
             }
@@ -426,10 +427,10 @@ fn parse_standard_qrc(
                     }
                     last_line.tracks.push(bg_line.tracks.remove(0));
                 } else {
-                    warnings.push(format!(
+                    warnings.push(ParseWarning::warning(format!(
                         "背景人声行 '{}' 无法关联到上一行，当作主歌词处理。",
                         line_to_string(&bg_line)
-                    ));
+                    )));
                     final_lines.push(bg_line);
                 }
             }
@@ -451,10 +452,10 @@ fn parse_standard_qrc(
             }
             last_line.tracks.push(bg_line.tracks.remove(0));
         } else {
-            warnings.push(format!(
+            warnings.push(ParseWarning::warning(format!(
                 "行 '{}' 与另一背景人声行相邻（或无法合并），当作主歌词处理。",
                 line_to_string(&bg_line)
-            ));
+            )));
             final_lines.push(bg_line);
         }
     }
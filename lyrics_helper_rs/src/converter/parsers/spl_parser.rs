@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use crate::converter::utils::process_syllable_text;
 use lyrics_helper_core::{
     AnnotatedTrack, ContentType, ConvertError, LyricFormat, LyricLine, LyricSyllable,
-    LyricSyllableBuilder, LyricTrack, ParsedSourceData, Word,
+    LyricSyllableBuilder, LyricTrack, ParseWarning, ParsedSourceData, Word,
 };
 use regex::Regex;
 use std::sync::LazyLock;
@@ -117,7 +117,7 @@ fn parse_syllables(
 /// 如果 `LyricSyllableBuilder` 构建失败，会导致panic
 pub fn parse_spl(content: &str) -> Result<ParsedSourceData, ConvertError> {
     let mut lines: Vec<LyricLine> = Vec::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
     let mut spl_blocks: Vec<SplBlock> = Vec::new();
 
     // 将原始文本行构建成逻辑块 (SplBlock)
@@ -136,7 +136,9 @@ pub fn parse_spl(content: &str) -> Result<ParsedSourceData, ConvertError> {
                     if let Some(ts_content) = ts_cap.get(1) {
                         match parse_spl_timestamp_ms(ts_content.as_str()) {
                             Ok(ms) => current_block.start_times.push(ms),
-                            Err(e) => warnings.push(format!("第 {line_num} 行: {e}")),
+                            Err(e) => {
+                                warnings.push(ParseWarning::error(format!("第 {line_num} 行: {e}")))
+                            }
                         }
                     }
                 }
@@ -187,9 +189,9 @@ pub fn parse_spl(content: &str) -> Result<ParsedSourceData, ConvertError> {
             }
             spl_blocks.push(current_block);
         } else {
-            warnings.push(format!(
+            warnings.push(ParseWarning::warning(format!(
                 "第 {line_num} 行: 跳过无时间戳的孤立行 '{trimmed_line}'"
-            ));
+            )));
         }
     }
 
@@ -210,10 +212,10 @@ pub fn parse_spl(content: &str) -> Result<ParsedSourceData, ConvertError> {
         let is_word_timed = syllables.len() > 1;
 
         if block.start_times.len() > 1 && is_word_timed {
-            warnings.push(format!(
+            warnings.push(ParseWarning::warning(format!(
                 "在主歌词 '{}' 中同时使用了重复行和逐字歌词特性，这可能导致非预期的行为。",
                 block.main_text
-            ));
+            )));
         }
 
         for &start_ms in &block.start_times {
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use crate::converter::utils::process_syllable_text;
 use lyrics_helper_core::{
     AnnotatedTrack, ContentType, ConvertError, LyricFormat, LyricLine, LyricLineBuilder,
-    LyricSyllable, LyricSyllableBuilder, LyricTrack, ParsedSourceData, Word,
+    LyricSyllable, LyricSyllableBuilder, LyricTrack, ParseWarning, ParsedSourceData, Word,
 };
 use regex::Regex;
 use serde_json::Value;
@@ -97,7 +97,7 @@ fn parse_yrc_line(line_str: &str, line_num: usize) -> Result<LyricLine, ConvertE
 pub fn parse_yrc(content: &str) -> Result<ParsedSourceData, ConvertError> {
     let mut lines: Vec<LyricLine> = Vec::new();
     let mut raw_metadata: HashMap<String, Vec<String>> = HashMap::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
 
     for (i, line_str_raw) in content.lines().enumerate() {
         let line_num = i + 1;
@@ -138,9 +138,9 @@ pub fn parse_yrc(content: &str) -> Result<ParsedSourceData, ConvertError> {
                     }
                 }
             } else {
-                warnings.push(format!(
+                warnings.push(ParseWarning::error(format!(
                     "第 {line_num} 行: 看起来像 JSON 元数据但解析失败，已跳过。"
-                ));
+                )));
             }
             continue;
         }
@@ -152,11 +152,15 @@ pub fn parse_yrc(content: &str) -> Result<ParsedSourceData, ConvertError> {
                     lines.push(parsed_line);
                 }
                 Err(e) => {
-                    warnings.push(format!("第 {line_num} 行: 解析歌词行失败。错误: {e}"));
+                    warnings.push(ParseWarning::error(format!(
+                        "第 {line_num} 行: 解析歌词行失败。错误: {e}"
+                    )));
                 }
             }
         } else {
-            warnings.push(format!("第 {line_num} 行: 未能识别的行格式。"));
+            warnings.push(ParseWarning::warning(format!(
+                "第 {line_num} 行: 未能识别的行格式。"
+            )));
         }
     }
 
@@ -3,7 +3,10 @@
 use regex::Regex;
 use std::{borrow::Cow, collections::HashMap, sync::LazyLock};
 
-use lyrics_helper_core::{Agent, AgentType, ContentType, LyricLine, ParsedSourceData};
+use lyrics_helper_core::{
+    Agent, AgentRecognizerOptions, AgentType, BracketAgentRule, ContentType, LyricLine,
+    ParsedSourceData,
+};
 
 /// 正则表达式，用于匹配行首的演唱者标记。
 /// 支持全角/半角括号和冒号，以及无括号的情况。
@@ -14,8 +17,38 @@ static AGENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^\s*(?:\((.+?)\)|（(.+?)）|([^\s:()（）]+))\s*[:：]\s*").unwrap()
 });
 
+/// 正则表达式，用于匹配行首的括号标记，标记后可以跟一个冒号。
+/// 捕获组 1 到 4 依次为半角圆括号、全角圆括号、方括号和方头括号内的内容。
+static BRACKET_MARKER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:\((.+?)\)|（(.+?)）|\[(.+?)\]|【(.+?)】)\s*[:：]?\s*").unwrap()
+});
+
+/// 名称超过此字符数的“名称：”标记更可能是普通歌词。
+const MAX_CONFIDENT_NAME_CHARS: usize = 8;
+
+/// 行首识别到的演唱者标记。
+struct AgentMarker {
+    name: String,
+    agent_type: AgentType,
+    /// 标记前缀的字节长度
+    prefix_len: usize,
+    /// 该标记确实表示演唱者的置信度，范围 0.0 到 1.0
+    confidence: f32,
+}
+
 /// 接收一个 `ParsedSourceData`，识别其中的演唱者，并直接修改它。
 pub fn recognize_agents(data: &mut ParsedSourceData) {
+    recognize_agents_with_options(data, &AgentRecognizerOptions::default());
+}
+
+/// 按选项识别演唱者。
+///
+/// 行首的括号标记命中 `bracket_rules` 中的规则时，去掉该标记并分配规则对应的演唱者；
+/// 否则使用默认的“名称：”格式识别，置信度低于 `min_confidence` 的标记会被忽略。
+pub fn recognize_agents_with_options(
+    data: &mut ParsedSourceData,
+    options: &AgentRecognizerOptions,
+) {
     let original_lines = std::mem::take(&mut data.lines);
     let mut processed_lines = Vec::with_capacity(original_lines.len());
     let mut current_agent_id: Option<String> = None;
@@ -35,52 +68,50 @@ pub fn recognize_agents(data: &mut ParsedSourceData) {
     for mut line in original_lines {
         let full_text: String = get_text_from_main_track(&line).to_string();
 
-        if let Some(captures) = AGENT_REGEX.captures(&full_text) {
-            // 从多个捕获组中提取演唱者名称
-            let agent_name = (1..=3)
-                .find_map(|i| captures.get(i))
-                .map(|m| m.as_str().trim().to_string());
-
-            if let (Some(name), Some(full_match_capture)) = (agent_name, captures.get(0)) {
-                let full_match_str = full_match_capture.as_str();
-
-                let agent_id = name_to_id_map
-                    .entry(name.clone())
-                    .or_insert_with(|| {
-                        let new_id = format!("v{next_agent_id_num}");
-                        next_agent_id_num += 1;
-
-                        let new_agent = Agent {
-                            id: new_id.clone(),
-                            name: Some(name),
-                            agent_type: AgentType::Person,
-                        };
-                        data.agents.agents_by_id.insert(new_id.clone(), new_agent);
-
-                        new_id
-                    })
-                    .clone();
-
-                if let Some(remaining_text) = full_text.strip_prefix(full_match_str) {
-                    if remaining_text.trim().is_empty() {
-                        // 块模式: 如果标记后面没有文本，说明这只是一个标记行，用于标记后面行的演唱者
-                        // 更新当前演唱者，并跳过此行
-                        current_agent_id = Some(agent_id);
-                        continue;
-                    }
-                    // 行模式: 标记和歌词在同一行。
-                    line.agent = Some(agent_id.clone());
-                    current_agent_id = Some(agent_id); // 更新当前演唱者以备后续行继承
-                    clean_text_in_main_track(&mut line, full_match_str);
-                }
-            } else {
-                // 正则匹配成功，但未能提取出有效的演唱者名称（理论上不太可能发生）
-                line.agent.clone_from(&current_agent_id);
-            }
-        } else {
+        let marker = match_bracket_rule(&full_text, &options.bracket_rules)
+            .or_else(|| match_default_marker(&full_text, &name_to_id_map))
+            .filter(|marker| marker.confidence >= options.min_confidence);
+        let Some(AgentMarker {
+            name,
+            agent_type,
+            prefix_len,
+            ..
+        }) = marker
+        else {
             // 整行都不匹配演唱者标记的格式
             line.agent.clone_from(&current_agent_id);
+            processed_lines.push(line);
+            continue;
+        };
+
+        let agent_id = name_to_id_map
+            .entry(name.clone())
+            .or_insert_with(|| {
+                let new_id = format!("v{next_agent_id_num}");
+                next_agent_id_num += 1;
+
+                let new_agent = Agent {
+                    id: new_id.clone(),
+                    name: Some(name),
+                    agent_type,
+                };
+                data.agents.agents_by_id.insert(new_id.clone(), new_agent);
+
+                new_id
+            })
+            .clone();
+
+        let (prefix, remaining_text) = full_text.split_at(prefix_len);
+        if remaining_text.trim().is_empty() {
+            // 块模式: 如果标记后面没有文本，说明这只是一个标记行，用于标记后面行的演唱者
+            // 更新当前演唱者，并跳过此行
+            current_agent_id = Some(agent_id);
+            continue;
         }
+        // 行模式: 标记和歌词在同一行。
+        line.agent = Some(agent_id.clone());
+        current_agent_id = Some(agent_id); // 更新当前演唱者以备后续行继承
+        clean_text_in_main_track(&mut line, prefix);
 
         processed_lines.push(line);
     }
@@ -88,6 +119,72 @@ pub fn recognize_agents(data: &mut ParsedSourceData) {
     data.lines = processed_lines;
 }
 
+/// 匹配行首命中规则的括号标记。规则由用户明确配置，置信度总是 1.0。
+fn match_bracket_rule(text: &str, rules: &[BracketAgentRule]) -> Option<AgentMarker> {
+    if rules.is_empty() {
+        return None;
+    }
+    let captures = BRACKET_MARKER_REGEX.captures(text)?;
+    let marker = (1..=4).find_map(|i| captures.get(i))?.as_str().trim();
+    let rule = rules
+        .iter()
+        .find(|rule| !rule.marker.trim().is_empty() && rule.marker.trim() == marker)?;
+    Some(AgentMarker {
+        name: rule.display_name().to_string(),
+        agent_type: rule.agent_type.clone(),
+        prefix_len: captures.get(0)?.end(),
+        confidence: 1.0,
+    })
+}
+
+/// 匹配行首“名称：”格式的演唱者标记。`known_names` 为已识别的演唱者名称。
+fn match_default_marker(text: &str, known_names: &HashMap<String, String>) -> Option<AgentMarker> {
+    let captures = AGENT_REGEX.captures(text)?;
+    // 从多个捕获组中提取演唱者名称
+    let name_match = (1..=3).find_map(|i| captures.get(i))?;
+    let name = name_match.as_str().trim();
+    let prefix_len = captures.get(0)?.end();
+
+    let bracketed = captures.get(3).is_none();
+    let marker_only = text[prefix_len..].trim().is_empty();
+    let confidence =
+        default_marker_confidence(name, bracketed, marker_only, known_names.contains_key(name));
+
+    Some(AgentMarker {
+        name: name.to_string(),
+        agent_type: AgentType::Person,
+        prefix_len,
+        confidence,
+    })
+}
+
+/// 估计“名称：”标记确实表示演唱者的置信度。
+///
+/// 带括号、单独成行或此前已出现过的名称更可信；过长的名称或纯数字（如 `12:30`
+/// 这样的时间）更可能是普通歌词。
+fn default_marker_confidence(name: &str, bracketed: bool, marker_only: bool, known: bool) -> f32 {
+    let mut confidence = 0.5;
+    if bracketed {
+        confidence += 0.2;
+    }
+    if marker_only {
+        confidence += 0.2;
+    }
+    if known {
+        confidence += 0.2;
+    }
+    if name.chars().count() > MAX_CONFIDENT_NAME_CHARS {
+        confidence -= 0.3;
+    }
+    if name
+        .chars()
+        .all(|c| c.is_ascii_digit() || c.is_ascii_punctuation())
+    {
+        confidence -= 0.4;
+    }
+    f32::clamp(confidence, 0.0, 1.0)
+}
+
 /// 辅助函数：从 `LyricLine` 中获取用于匹配的纯文本。
 fn get_text_from_main_track(line: &LyricLine) -> Cow<'_, str> {
     line.tracks
@@ -312,6 +409,99 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_recognize_agents_bracket_rules() {
+        let options = AgentRecognizerOptions {
+            bracket_rules: vec![
+                BracketAgentRule {
+                    marker: "合".to_string(),
+                    agent_name: "合唱".to_string(),
+                    agent_type: AgentType::Group,
+                },
+                BracketAgentRule {
+                    marker: "男".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let mut data = ParsedSourceData {
+            lines: vec![
+                new_line("(合)一起唱"),
+                new_line("继续合唱"),
+                new_line("（男）"),
+                new_syllable_line(vec!["【", "男", "】", "男", "声"]),
+                new_line("(间奏)未配置的标记"),
+            ],
+            ..Default::default()
+        };
+
+        recognize_agents_with_options(&mut data, &options);
+
+        assert_eq!(data.lines.len(), 4, "纯标记行应被移除");
+
+        assert_eq!(data.lines[0].agent.as_deref(), Some("v1"));
+        assert_eq!(get_text_from_main_track(&data.lines[0]), "一起唱");
+        assert_eq!(data.lines[1].agent.as_deref(), Some("v1"));
+
+        assert_eq!(data.lines[2].agent.as_deref(), Some("v2"));
+        assert_eq!(get_text_from_main_track(&data.lines[2]), "男声");
+
+        assert_eq!(data.lines[3].agent.as_deref(), Some("v2"));
+        assert_eq!(
+            get_text_from_main_track(&data.lines[3]),
+            "(间奏)未配置的标记"
+        );
+
+        let chorus = data.agents.agents_by_id.get("v1").unwrap();
+        assert_eq!(chorus.name.as_deref(), Some("合唱"));
+        assert_eq!(chorus.agent_type, AgentType::Group);
+        assert_eq!(
+            data.agents.agents_by_id.get("v2").unwrap().name.as_deref(),
+            Some("男")
+        );
+    }
+
+    #[test]
+    fn test_recognize_agents_confidence_threshold() {
+        let options = AgentRecognizerOptions {
+            min_confidence: 0.5,
+            ..Default::default()
+        };
+        let mut data = ParsedSourceData {
+            lines: vec![
+                new_line("汪：摘一颗苹果"),
+                new_line("12:30 我们约在街角"),
+                new_line("Supercalifragilistic: the world"),
+            ],
+            ..Default::default()
+        };
+
+        recognize_agents_with_options(&mut data, &options);
+
+        assert_eq!(data.lines.len(), 3);
+        assert_eq!(data.lines[0].agent.as_deref(), Some("v1"));
+        assert_eq!(
+            data.lines[1].agent.as_deref(),
+            Some("v1"),
+            "低置信度的行应沿用之前的演唱者"
+        );
+        assert_eq!(
+            get_text_from_main_track(&data.lines[1]),
+            "12:30 我们约在街角"
+        );
+        assert_eq!(data.lines[2].agent.as_deref(), Some("v1"));
+        assert_eq!(
+            get_text_from_main_track(&data.lines[2]),
+            "Supercalifragilistic: the world",
+            "名称过长的标记应保留在歌词中"
+        );
+        assert_eq!(data.agents.agents_by_id.len(), 1);
+
+        assert!(default_marker_confidence("Supercalifragilistic", false, false, false) < 0.5);
+        assert!(default_marker_confidence("汪", false, false, true) > 0.5);
+    }
+
     #[test]
     fn test_recognize_agents_no_agents() {
         let mut data = ParsedSourceData {
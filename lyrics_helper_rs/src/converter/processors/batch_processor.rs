@@ -7,11 +7,11 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::converter::convert_single_lyric;
+use crate::converter::{generate_from_parsed, parse_and_merge};
 
 use lyrics_helper_core::{
     BatchConversionConfig, BatchEntryStatus, BatchFileId, BatchLoadedFile, ConversionInput,
-    ConversionOptions, ConvertError, InputFile, LyricFormat,
+    ConversionOptions, ConvertError, FilePairingOptions, InputFile, LyricFormat, ParsedSourceData,
 };
 
 /// 表示一组相关联的歌词文件（主歌词、翻译、罗马音）。
@@ -25,16 +25,31 @@ pub struct FileGroup {
     pub romanizations: Vec<PathBuf>,
 }
 
+/// 配对文件在文件组中的角色。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PairedRole {
+    Translation,
+    Romanization,
+}
+
 /// 扫描指定目录，根据文件名对歌词文件进行配对。
 ///
+/// 扩展名不属于任何已知歌词格式的文件（如图片、音频）会被直接忽略。
+/// 配对规则见 [`FilePairingOptions`]，翻译和罗马音文件不受扩展名白名单限制。
+///
 /// # 参数
 /// * `input_dir` - 要扫描的输入目录路径。
+/// * `main_extension_whitelist` - 主歌词候选文件的扩展名白名单，如 `["lrc", "ttml"]`。
+///   可以带前导点，不区分大小写。为空时不过滤。
+/// * `pairing` - 翻译和罗马音文件的配对规则。
 ///
 /// # 返回
 /// 成功时返回一个 `HashMap`，键是歌曲的基础名，值是配对好的 `FileGroup`。
 /// 失败时返回 `ConvertError`。
 pub fn discover_and_pair_files(
     input_dir: &Path,
+    main_extension_whitelist: &[String],
+    pairing: &FilePairingOptions,
 ) -> Result<HashMap<String, FileGroup>, ConvertError> {
     if !input_dir.is_dir() {
         return Err(ConvertError::Io(std::io::Error::new(
@@ -43,6 +58,12 @@ pub fn discover_and_pair_files(
         )));
     }
 
+    let whitelist: Vec<String> = main_extension_whitelist
+        .iter()
+        .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect();
+
     let mut file_groups: HashMap<String, FileGroup> = HashMap::new();
 
     for entry in fs::read_dir(input_dir)? {
@@ -50,22 +71,23 @@ pub fn discover_and_pair_files(
         let path = entry.path();
 
         if path.is_file()
+            && get_format_from_path(&path).is_some()
             && let Some(file_stem_str) = path.file_stem().and_then(|s| s.to_str())
         {
-            let (base_name, tag) = match file_stem_str.rsplit_once('.') {
-                Some((base, potential_tag)) if is_language_tag(potential_tag) => {
-                    (base.to_string(), Some(potential_tag.to_lowercase()))
-                }
-                _ => (file_stem_str.to_string(), None),
-            };
+            let (base_name, role) = split_paired_stem(file_stem_str, pairing);
 
-            let group = file_groups.entry(base_name).or_default();
+            if role.is_none() && !is_extension_whitelisted(&path, &whitelist) {
+                tracing::debug!("文件 {:?} 的扩展名不在白名单中，已忽略", path);
+                continue;
+            }
 
-            match tag.as_deref() {
-                Some(t) if t.contains("latn") || t == "romaji" || t == "roman" => {
+            let group = file_groups.entry(base_name.to_string()).or_default();
+
+            match role {
+                Some(PairedRole::Romanization) => {
                     group.romanizations.push(path);
                 }
-                Some(_) => {
+                Some(PairedRole::Translation) => {
                     group.translations.push(path);
                 }
                 None => {
@@ -86,6 +108,64 @@ pub fn discover_and_pair_files(
     Ok(file_groups)
 }
 
+/// 判断文件的扩展名是否在（已规范化的）白名单中。白名单为空时总是返回 `true`。
+fn is_extension_whitelisted(path: &Path, whitelist: &[String]) -> bool {
+    if whitelist.is_empty() {
+        return true;
+    }
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| whitelist.contains(&ext.to_lowercase()))
+}
+
+/// 按配对规则拆分文件名（不含扩展名），返回基础名和该文件作为翻译或罗马音时的角色。
+///
+/// 角色为 `None` 表示该文件是主歌词候选，此时基础名就是完整的文件名。
+fn split_paired_stem<'a>(
+    file_stem: &'a str,
+    pairing: &FilePairingOptions,
+) -> (&'a str, Option<PairedRole>) {
+    if !pairing.enabled {
+        return (file_stem, None);
+    }
+
+    let suffix_match = pairing
+        .translation_suffixes
+        .iter()
+        .map(|suffix| (suffix, PairedRole::Translation))
+        .chain(
+            pairing
+                .romanization_suffixes
+                .iter()
+                .map(|suffix| (suffix, PairedRole::Romanization)),
+        )
+        .filter_map(|(suffix, role)| {
+            let suffix = suffix.trim();
+            let cut = file_stem.len().checked_sub(suffix.len())?;
+            let tail = file_stem.get(cut..)?;
+            (!suffix.is_empty() && cut > 0 && tail.to_lowercase() == suffix.to_lowercase())
+                .then_some((cut, role))
+        })
+        .min_by_key(|&(cut, _)| cut);
+    if let Some((cut, role)) = suffix_match {
+        return (&file_stem[..cut], Some(role));
+    }
+
+    match file_stem.rsplit_once('.') {
+        Some((base, tag)) if is_language_tag(tag) => {
+            let tag = tag.to_lowercase();
+            let role =
+                if tag.contains("latn") || ["romaji", "roman", "roma"].contains(&tag.as_str()) {
+                    PairedRole::Romanization
+                } else {
+                    PairedRole::Translation
+                };
+            (base, Some(role))
+        }
+        _ => (file_stem, None),
+    }
+}
+
 /// 简单的辅助函数，用于判断一个字符串是否可能是一个语言标签。
 fn is_language_tag(tag: &str) -> bool {
     if ["romaji", "roman", "roma"].contains(&tag.to_lowercase().as_str()) {
@@ -146,6 +226,15 @@ pub fn create_batch_tasks<S: BuildHasher>(
     (tasks, file_lookup)
 }
 
+/// 批量转换一次运行的结束方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchRunOutcome {
+    /// 所有待处理的任务都已处理完毕。
+    Finished,
+    /// 因收到暂停请求而在任务之间中断，剩余任务保持原状态。
+    Paused,
+}
+
 /// 执行批量转换任务。
 ///
 /// 遍历任务列表，读取文件，调用核心转换逻辑，并将结果写入输出目录。
@@ -162,33 +251,87 @@ pub fn execute_batch_conversion<S: BuildHasher>(
     output_dir: &Path,
     options: &ConversionOptions,
 ) -> Result<(), ConvertError> {
+    execute_batch_conversion_pausable(
+        tasks,
+        file_lookup,
+        output_dir,
+        options,
+        false,
+        |path| fs::read_to_string(path),
+        || false,
+        |_| Ok(()),
+        |_| {},
+    )
+    .map(|_| ())
+}
+
+/// 开始新一轮批量转换前重置任务状态。
+///
+/// 除已完成的任务外，其余任务（包括上一轮试运行或失败的任务）都恢复为等待转换，
+/// 以免沿用过期的结果。从暂停处继续时不应调用此函数。
+pub fn reset_tasks_for_new_run(tasks: &mut [BatchConversionConfig]) {
+    for task in tasks {
+        if !matches!(task.status, BatchEntryStatus::Completed { .. }) {
+            task.status = BatchEntryStatus::Pending;
+        }
+    }
+}
+
+/// 执行批量转换任务，并在每个任务开始前检查是否需要暂停。
+///
+/// 已完成或已失败的任务会被跳过，因此暂停后再次调用即可从未处理的任务继续。
+/// 开始新一轮转换前应先调用 [`reset_tasks_for_new_run`]。
+///
+/// # 参数
+/// * `dry_run` - 为 `true` 时执行完整的转换流程但不写入任何文件，
+///   成功的任务会被标记为 [`BatchEntryStatus::DryRun`]，记录将要写入的路径和大小。
+/// * `read_text` - 读取输入歌词文件的文本内容，调用方可以在这里识别 GBK、Shift-JIS 等编码。
+/// * `should_pause` - 每个任务开始前调用，返回 `true` 时立即停止并返回 [`BatchRunOutcome::Paused`]。
+/// * `postprocess` - 每个任务解析合并完成后、生成目标格式前调用，用于应用额外的后处理器。
+///   返回错误时该任务标记为失败。
+/// * `on_task_finished` - 每个任务处理完毕（成功、失败或试运行完成）后以该任务调用，用于实时报告进度。
+///
+/// 其余参数同 [`execute_batch_conversion`]。
+#[allow(clippy::too_many_arguments)]
+pub fn execute_batch_conversion_pausable<S: BuildHasher>(
+    tasks: &mut [BatchConversionConfig],
+    file_lookup: &HashMap<BatchFileId, BatchLoadedFile, S>,
+    output_dir: &Path,
+    options: &ConversionOptions,
+    dry_run: bool,
+    read_text: impl Fn(&Path) -> std::io::Result<String>,
+    should_pause: impl Fn() -> bool,
+    postprocess: impl Fn(&mut ParsedSourceData) -> Result<(), ConvertError>,
+    mut on_task_finished: impl FnMut(&BatchConversionConfig),
+) -> Result<BatchRunOutcome, ConvertError> {
     // 确保输出目录存在
-    fs::create_dir_all(output_dir)?;
+    if !dry_run {
+        fs::create_dir_all(output_dir)?;
+    }
 
     for task in tasks.iter_mut() {
+        let already_processed = match task.status {
+            BatchEntryStatus::Completed { .. }
+            | BatchEntryStatus::Failed(_)
+            | BatchEntryStatus::SkippedNoMatch => true,
+            // 试运行的结果不算数，正式运行时需要重新转换
+            BatchEntryStatus::DryRun { .. } => dry_run,
+            _ => false,
+        };
+        if already_processed {
+            continue;
+        }
+
+        if should_pause() {
+            return Ok(BatchRunOutcome::Paused);
+        }
+
         task.status = BatchEntryStatus::Converting;
 
-        // 辅助闭包，用于读取文件并构建 InputFile
-        let read_and_build_input = |file_id: &BatchFileId| -> Result<InputFile, ConvertError> {
-            let loaded_file = file_lookup.get(file_id).ok_or_else(|| {
-                ConvertError::Internal(format!("文件ID {file_id:?} 未在查找表中找到"))
-            })?;
-
-            let content = fs::read_to_string(&loaded_file.path)?;
-            let format = get_format_from_path(&loaded_file.path).ok_or_else(|| {
-                ConvertError::InvalidLyricFormat(loaded_file.path.to_string_lossy().to_string())
-            })?;
-
-            Ok(InputFile {
-                content,
-                format,
-                // TODO: 从路径获取语言和文件名
-                language: None,
-                filename: Some(loaded_file.filename.clone()),
-            })
-        };
+        let read_and_build_input =
+            |file_id: &BatchFileId| read_input_file(file_lookup, *file_id, &read_text);
 
-        let conversion_result = (|| -> Result<String, ConvertError> {
+        let conversion_result = (|| -> Result<(String, Vec<String>), ConvertError> {
             // 读取主歌词文件
             let main_lyric = read_and_build_input(&task.main_lyric_id)?;
 
@@ -216,19 +359,42 @@ pub fn execute_batch_conversion<S: BuildHasher>(
                 additional_metadata: None,
             };
 
-            // 调用核心转换函数
-            convert_single_lyric(&conversion_input, options)
-                .map(|full_result| full_result.output_lyrics)
+            let mut source_data = parse_and_merge(&conversion_input, options)?;
+            postprocess(&mut source_data)?;
+
+            generate_from_parsed(
+                source_data,
+                conversion_input.target_format,
+                options,
+                &None::<HashMap<String, Vec<String>>>,
+            )
+            .map(|full_result| {
+                let output = finalize_output_text(
+                    &full_result.output_lyrics,
+                    &conversion_input.main_lyric.content,
+                    options,
+                );
+                (output, full_result.warnings)
+            })
         })();
 
         match conversion_result {
-            Ok(result_string) => {
+            Ok((result_string, warnings)) if dry_run => {
+                let output_path = output_dir.join(&task.output_filename_preview);
+                task.status = BatchEntryStatus::DryRun {
+                    overwrites_existing: output_path.exists(),
+                    output_size: result_string.len(),
+                    output_path,
+                    warnings,
+                };
+            }
+            Ok((result_string, warnings)) => {
                 let output_path = output_dir.join(&task.output_filename_preview);
                 match fs::write(&output_path, result_string) {
                     Ok(()) => {
                         task.status = BatchEntryStatus::Completed {
                             output_path,
-                            warnings: Vec::new(),
+                            warnings,
                         };
                     }
                     Err(e) => {
@@ -240,9 +406,46 @@ pub fn execute_batch_conversion<S: BuildHasher>(
                 task.status = BatchEntryStatus::Failed(e.to_string());
             }
         }
+        on_task_finished(task);
     }
 
-    Ok(())
+    Ok(BatchRunOutcome::Finished)
+}
+
+/// 读取查找表中的文件并构建 [`InputFile`]。
+fn read_input_file<S: BuildHasher>(
+    file_lookup: &HashMap<BatchFileId, BatchLoadedFile, S>,
+    file_id: BatchFileId,
+    read_text: impl Fn(&Path) -> std::io::Result<String>,
+) -> Result<InputFile, ConvertError> {
+    let loaded_file = file_lookup
+        .get(&file_id)
+        .ok_or_else(|| ConvertError::Internal(format!("文件ID {file_id:?} 未在查找表中找到")))?;
+
+    let content = read_text(&loaded_file.path)?;
+    let format = get_format_from_path(&loaded_file.path).ok_or_else(|| {
+        ConvertError::InvalidLyricFormat(loaded_file.path.to_string_lossy().to_string())
+    })?;
+
+    Ok(InputFile {
+        content,
+        format,
+        // TODO: 从路径获取语言和文件名
+        language: None,
+        filename: Some(loaded_file.filename.clone()),
+    })
+}
+
+/// UTF-8 字节顺序标记。
+const UTF8_BOM: char = '\u{feff}';
+
+/// 按保存选项转换换行符并添加 BOM，得到最终写入文件的文本。换行符为“保持”时跟随 `source`。
+fn finalize_output_text(output: &str, source: &str, options: &ConversionOptions) -> String {
+    let mut output = options.line_ending.apply(output, Some(source));
+    if options.add_bom && !output.starts_with(UTF8_BOM) {
+        output.insert(0, UTF8_BOM);
+    }
+    output
 }
 
 /// 从文件路径的扩展名推断歌词格式。
@@ -251,3 +454,248 @@ fn get_format_from_path(path: &Path) -> Option<LyricFormat> {
         .and_then(|s| s.to_str())
         .and_then(LyricFormat::from_string)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_paired_stem_by_suffix() {
+        let pairing = FilePairingOptions::default();
+
+        assert_eq!(split_paired_stem("song", &pairing), ("song", None));
+        assert_eq!(
+            split_paired_stem("song_TR", &pairing),
+            ("song", Some(PairedRole::Translation))
+        );
+        assert_eq!(
+            split_paired_stem("song_translation", &pairing),
+            ("song", Some(PairedRole::Translation))
+        );
+        assert_eq!(
+            split_paired_stem("song.romaji", &pairing),
+            ("song", Some(PairedRole::Romanization))
+        );
+        assert_eq!(
+            split_paired_stem("歌曲_roma", &pairing),
+            ("歌曲", Some(PairedRole::Romanization))
+        );
+        // 整个文件名就是后缀时不视为配对文件
+        assert_eq!(split_paired_stem("_tr", &pairing), ("_tr", None));
+    }
+
+    #[test]
+    fn test_split_paired_stem_falls_back_to_language_tag() {
+        let pairing = FilePairingOptions::default();
+
+        assert_eq!(
+            split_paired_stem("song.zh", &pairing),
+            ("song", Some(PairedRole::Translation))
+        );
+        assert_eq!(
+            split_paired_stem("song.roma", &pairing),
+            ("song", Some(PairedRole::Romanization))
+        );
+
+        let disabled = FilePairingOptions {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(split_paired_stem("song_tr", &disabled), ("song_tr", None));
+        assert_eq!(split_paired_stem("song.zh", &disabled), ("song.zh", None));
+    }
+
+    /// 在临时目录中准备一个可以转换的任务和一个主歌词文件缺失、必然失败的任务。
+    fn batch_fixture(
+        name: &str,
+    ) -> (
+        PathBuf,
+        Vec<BatchConversionConfig>,
+        HashMap<BatchFileId, BatchLoadedFile>,
+    ) {
+        let dir =
+            std::env::temp_dir().join(format!("lyrics_helper_batch_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("good.lrc"), "[00:01.00]hello\n").unwrap();
+
+        let mut file_lookup = HashMap::new();
+        let mut tasks = Vec::new();
+        for stem in ["missing", "good"] {
+            let file = BatchLoadedFile::new(dir.join(format!("{stem}.lrc")));
+            tasks.push(BatchConversionConfig::new(
+                file.id,
+                LyricFormat::Lrc,
+                format!("{stem}.out.lrc"),
+            ));
+            file_lookup.insert(file.id, file);
+        }
+        (dir, tasks, file_lookup)
+    }
+
+    fn run(
+        tasks: &mut [BatchConversionConfig],
+        file_lookup: &HashMap<BatchFileId, BatchLoadedFile>,
+        output_dir: &Path,
+        dry_run: bool,
+        should_pause: impl Fn() -> bool,
+    ) -> BatchRunOutcome {
+        execute_batch_conversion_pausable(
+            tasks,
+            file_lookup,
+            output_dir,
+            &ConversionOptions::default(),
+            dry_run,
+            |path| fs::read_to_string(path),
+            should_pause,
+            |_| Ok(()),
+            |_| {},
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_repeated_dry_run_reconverts_every_task() {
+        let (dir, mut tasks, file_lookup) = batch_fixture("dry_dry");
+        let output_dir = dir.join("out");
+
+        run(&mut tasks, &file_lookup, &output_dir, true, || false);
+        assert!(matches!(tasks[0].status, BatchEntryStatus::Failed(_)));
+        assert!(matches!(tasks[1].status, BatchEntryStatus::DryRun { .. }));
+
+        // 缺失的文件补上之后，再次试运行应得到新的结果
+        fs::write(dir.join("missing.lrc"), "[00:02.00]world\n").unwrap();
+        reset_tasks_for_new_run(&mut tasks);
+        run(&mut tasks, &file_lookup, &output_dir, true, || false);
+        assert!(matches!(tasks[0].status, BatchEntryStatus::DryRun { .. }));
+        assert!(matches!(tasks[1].status, BatchEntryStatus::DryRun { .. }));
+        assert!(!output_dir.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_dry_run_keeps_feature_loss_warnings() {
+        let dir = std::env::temp_dir().join(format!(
+            "lyrics_helper_batch_dry_warnings_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("timed.lys"), "[4]Hello(100,200) world(300,300)\n").unwrap();
+
+        let file = BatchLoadedFile::new(dir.join("timed.lys"));
+        let mut tasks = vec![BatchConversionConfig::new(
+            file.id,
+            LyricFormat::Lrc,
+            "timed.lrc".to_string(),
+        )];
+        let file_lookup = HashMap::from([(file.id, file)]);
+        run(&mut tasks, &file_lookup, &dir.join("out"), true, || false);
+
+        let BatchEntryStatus::DryRun { warnings, .. } = &tasks[0].status else {
+            panic!("试运行应成功: {:?}", tasks[0].status);
+        };
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("逐字时间"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_real_run_after_dry_run_retries_failed_tasks() {
+        let (dir, mut tasks, file_lookup) = batch_fixture("dry_real");
+        let output_dir = dir.join("out");
+
+        run(&mut tasks, &file_lookup, &output_dir, true, || false);
+        assert!(matches!(tasks[0].status, BatchEntryStatus::Failed(_)));
+
+        fs::write(dir.join("missing.lrc"), "[00:02.00]world\n").unwrap();
+        reset_tasks_for_new_run(&mut tasks);
+        run(&mut tasks, &file_lookup, &output_dir, false, || false);
+        assert!(
+            tasks
+                .iter()
+                .all(|task| matches!(task.status, BatchEntryStatus::Completed { .. }))
+        );
+        assert!(output_dir.join("missing.out.lrc").exists());
+        assert!(output_dir.join("good.out.lrc").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_batch_output_respects_bom_option() {
+        let (dir, mut tasks, file_lookup) = batch_fixture("bom");
+        let output_dir = dir.join("out");
+        let options = ConversionOptions {
+            add_bom: true,
+            ..Default::default()
+        };
+
+        execute_batch_conversion(&mut tasks, &file_lookup, &output_dir, &options).unwrap();
+        let output = fs::read_to_string(output_dir.join("good.out.lrc")).unwrap();
+        assert!(output.starts_with(UTF8_BOM));
+        assert!(!output[UTF8_BOM.len_utf8()..].starts_with(UTF8_BOM));
+
+        tasks[1].status = BatchEntryStatus::Pending;
+        run(&mut tasks, &file_lookup, &output_dir, false, || false);
+        let output = fs::read_to_string(output_dir.join("good.out.lrc")).unwrap();
+        assert!(!output.starts_with(UTF8_BOM));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pause_then_resume_continues_with_unprocessed_tasks() {
+        let (dir, mut tasks, file_lookup) = batch_fixture("pause_resume");
+        let output_dir = dir.join("out");
+
+        let started = std::cell::Cell::new(0);
+        let outcome = run(&mut tasks, &file_lookup, &output_dir, false, || {
+            started.set(started.get() + 1);
+            started.get() > 1
+        });
+        assert_eq!(outcome, BatchRunOutcome::Paused);
+        assert!(matches!(tasks[0].status, BatchEntryStatus::Failed(_)));
+        assert!(matches!(tasks[1].status, BatchEntryStatus::Pending));
+
+        // 继续时不重置状态，已失败的任务保持原样
+        let outcome = run(&mut tasks, &file_lookup, &output_dir, false, || false);
+        assert_eq!(outcome, BatchRunOutcome::Finished);
+        assert!(matches!(tasks[0].status, BatchEntryStatus::Failed(_)));
+        assert!(matches!(
+            tasks[1].status,
+            BatchEntryStatus::Completed { .. }
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_discover_and_pair_files_filters_main_by_whitelist() {
+        let dir = std::env::temp_dir().join(format!(
+            "lyrics_helper_whitelist_test_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["song.lrc", "other.ass", "song_tr.ass", "cover.jpg"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let pairing = FilePairingOptions::default();
+
+        let groups = discover_and_pair_files(&dir, &[".LRC".to_string()], &pairing).unwrap();
+        // 翻译文件不受白名单限制，other.ass 不是主歌词候选
+        assert_eq!(groups.len(), 1);
+        let song = &groups["song"];
+        assert_eq!(song.main_lyric, Some(dir.join("song.lrc")));
+        assert_eq!(song.translations, vec![dir.join("song_tr.ass")]);
+
+        // 白名单为空时不过滤，但仍忽略非歌词文件
+        let groups = discover_and_pair_files(&dir, &[], &pairing).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["other"].main_lyric, Some(dir.join("other.ass")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -7,7 +7,7 @@ use ferrous_opencc::OpenCC;
 use ferrous_opencc::config::BuiltinConfig as OpenccConfig;
 use lyrics_helper_core::{
     ChineseConversionConfig, ChineseConversionMode, ChineseConversionOptions, ContentType,
-    LyricLine,
+    LyricLine, LyricTrack, TrackMetadataKey,
 };
 use pinyin::ToPinyin;
 use std::sync::LazyLock;
@@ -108,7 +108,13 @@ impl ChineseConversionProcessor {
     /// # 参数
     /// * `lines` - 一个可变的歌词行切片，转换结果将直接写入其中。
     /// * `options` - 简繁转换的配置选项，决定是否执行以及执行何种模式的转换。
+    ///   主歌词和翻译分别按 `config` 与 `translation_config` 转换。
     pub fn process(lines: &mut [LyricLine], options: &ChineseConversionOptions) {
+        // 先转换已有的翻译，避免影响 `AddAsTranslation` 模式新增的翻译
+        if let Some(translation_config) = options.translation_config {
+            Self::replace_translations(lines, translation_config, &options.custom_dictionary);
+        }
+
         let Some(config) = options.config else {
             return;
         };
@@ -119,6 +125,47 @@ impl ChineseConversionProcessor {
             }
             ChineseConversionMode::Replace => {
                 Self::replace(lines, config);
+                Self::apply_dictionary_to_main_tracks(lines, &options.custom_dictionary);
+            }
+        }
+    }
+
+    /// 直接替换主歌词和背景人声的所有翻译，主歌词本身保持不变。
+    ///
+    /// 逐行的翻译整句替换，允许用语转换改变字数；逐字的翻译按音节转换。
+    /// 翻译的语言标签是中文，或没有语言标签但转换确实改变了文本时，更新为转换结果对应的标签。
+    fn replace_translations(
+        lines: &mut [LyricLine],
+        config: ChineseConversionConfig,
+        dictionary: &[(String, String)],
+    ) {
+        let target_lang_tag = config.deduce_lang_tag();
+        for track in lines
+            .iter_mut()
+            .flat_map(|line| line.tracks.iter_mut())
+            .filter(|at| matches!(at.content_type, ContentType::Main | ContentType::Background))
+            .flat_map(|at| at.translations.iter_mut())
+        {
+            let original_text = track.text();
+            if track.syllables().count() == 1 {
+                if let Some(syllable) = track.syllables_mut().next() {
+                    syllable.text = convert(&syllable.text, config);
+                }
+            } else {
+                Self::replace_track(track, config);
+            }
+            Self::apply_dictionary_to_track(track, dictionary);
+
+            if let Some(tag) = target_lang_tag {
+                let should_retag = match track.metadata.get(&TrackMetadataKey::Language) {
+                    Some(lang) => lang.to_ascii_lowercase().starts_with("zh"),
+                    None => track.text() != original_text,
+                };
+                if should_retag {
+                    track
+                        .metadata
+                        .insert(TrackMetadataKey::Language, tag.to_string());
+                }
             }
         }
     }
@@ -154,7 +201,10 @@ impl ChineseConversionProcessor {
                 let original_text = at.content.text();
 
                 if !original_text.is_empty() {
-                    let converted_text = convert(&original_text, config);
+                    let converted_text = apply_custom_dictionary(
+                        &convert(&original_text, config),
+                        &options.custom_dictionary,
+                    );
 
                     at.add_translation(&converted_text, target_lang_tag);
                 }
@@ -166,63 +216,67 @@ impl ChineseConversionProcessor {
         for line in lines.iter_mut() {
             for at in &mut line.tracks {
                 if at.content_type == ContentType::Main {
-                    let main_track = &mut at.content;
-                    for word in &mut main_track.words {
-                        let original_syllable_texts: Vec<String> =
-                            word.syllables.iter().map(|s| s.text.clone()).collect();
-                        let full_word_text = original_syllable_texts.join("");
-
-                        if full_word_text.is_empty() {
-                            continue;
-                        }
+                    Self::replace_track(&mut at.content, config);
+                }
+            }
+        }
+    }
+
+    /// 转换一个轨道的文本，尽量保持每个音节的字数和读音不变。
+    fn replace_track(track: &mut LyricTrack, config: ChineseConversionConfig) {
+        for word in &mut track.words {
+            let original_syllable_texts: Vec<String> =
+                word.syllables.iter().map(|s| s.text.clone()).collect();
+            let full_word_text = original_syllable_texts.join("");
+
+            if full_word_text.is_empty() {
+                continue;
+            }
+
+            let converted_full_text = convert(&full_word_text, config);
+
+            if pinyin_is_same(&full_word_text, &converted_full_text) {
+                let mut converted_chars = converted_full_text.chars();
+                for (i, original_text) in original_syllable_texts.iter().enumerate() {
+                    let char_count = original_text.chars().count();
+                    let new_syllable_text: String =
+                        converted_chars.by_ref().take(char_count).collect();
+                    if let Some(syllable) = word.syllables.get_mut(i) {
+                        syllable.text = new_syllable_text;
+                    }
+                }
+            } else {
+                warn!(
+                    "词组 '{}' 转换后读音或长度改变 ('{}')，回退到逐音节转换。",
+                    full_word_text, converted_full_text,
+                );
+
+                for syllable in &mut word.syllables {
+                    if syllable.text.is_empty() {
+                        continue;
+                    }
 
-                        let converted_full_text = convert(&full_word_text, config);
-
-                        if pinyin_is_same(&full_word_text, &converted_full_text) {
-                            let mut converted_chars = converted_full_text.chars();
-                            for (i, original_text) in original_syllable_texts.iter().enumerate() {
-                                let char_count = original_text.chars().count();
-                                let new_syllable_text: String =
-                                    converted_chars.by_ref().take(char_count).collect();
-                                if let Some(syllable) = word.syllables.get_mut(i) {
-                                    syllable.text = new_syllable_text;
-                                }
-                            }
+                    let original_text = &syllable.text;
+                    let converted_text_syllable = convert(original_text, config);
+
+                    if pinyin_is_same(original_text, &converted_text_syllable) {
+                        syllable.text = converted_text_syllable;
+                    } else {
+                        let char_by_char_converted: String = original_text
+                            .chars()
+                            .map(|c| {
+                                let mut char_str = [0u8; 4];
+                                convert(c.encode_utf8(&mut char_str), config)
+                            })
+                            .collect();
+
+                        if pinyin_is_same(original_text, &char_by_char_converted) {
+                            syllable.text = char_by_char_converted;
                         } else {
                             warn!(
-                                "词组 '{}' 转换后读音或长度改变 ('{}')，回退到逐音节转换。",
-                                full_word_text, converted_full_text,
+                                "音节 '{}' 转换后读音改变，逐字转换也无效。保留原文。",
+                                original_text
                             );
-
-                            for syllable in &mut word.syllables {
-                                if syllable.text.is_empty() {
-                                    continue;
-                                }
-
-                                let original_text = &syllable.text;
-                                let converted_text_syllable = convert(original_text, config);
-
-                                if pinyin_is_same(original_text, &converted_text_syllable) {
-                                    syllable.text = converted_text_syllable;
-                                } else {
-                                    let char_by_char_converted: String = original_text
-                                        .chars()
-                                        .map(|c| {
-                                            let mut char_str = [0u8; 4];
-                                            convert(c.encode_utf8(&mut char_str), config)
-                                        })
-                                        .collect();
-
-                                    if pinyin_is_same(original_text, &char_by_char_converted) {
-                                        syllable.text = char_by_char_converted;
-                                    } else {
-                                        warn!(
-                                            "音节 '{}' 转换后读音改变，逐字转换也无效。保留原文。",
-                                            original_text
-                                        );
-                                    }
-                                }
-                            }
                         }
                     }
                 }
@@ -231,6 +285,172 @@ impl ChineseConversionProcessor {
     }
 }
 
+impl ChineseConversionProcessor {
+    /// 在标准转换之后对主歌词应用自定义词典。
+    ///
+    /// 替换前后字数相同时按原有音节的字数重新分配文本；字数改变时只有单个音节的轨道
+    /// （逐行歌词）可以直接替换，逐字歌词会保留原文。
+    fn apply_dictionary_to_main_tracks(lines: &mut [LyricLine], dictionary: &[(String, String)]) {
+        if dictionary.is_empty() {
+            return;
+        }
+
+        for at in lines
+            .iter_mut()
+            .flat_map(|line| line.tracks.iter_mut())
+            .filter(|at| at.content_type == ContentType::Main)
+        {
+            Self::apply_dictionary_to_track(&mut at.content, dictionary);
+        }
+    }
+
+    fn apply_dictionary_to_track(track: &mut LyricTrack, dictionary: &[(String, String)]) {
+        if dictionary.is_empty() {
+            return;
+        }
+
+        let original_texts: Vec<String> = track.syllables().map(|s| s.text.clone()).collect();
+        let full_text = original_texts.concat();
+        let replaced_text = apply_custom_dictionary(&full_text, dictionary);
+        if replaced_text == full_text {
+            return;
+        }
+
+        if original_texts.len() == 1 {
+            if let Some(syllable) = track.syllables_mut().next() {
+                syllable.text = replaced_text;
+            }
+        } else if replaced_text.chars().count() == full_text.chars().count() {
+            let mut replaced_chars = replaced_text.chars();
+            for (syllable, original_text) in track.syllables_mut().zip(&original_texts) {
+                syllable.text = replaced_chars
+                    .by_ref()
+                    .take(original_text.chars().count())
+                    .collect();
+            }
+        } else {
+            warn!(
+                "自定义词典替换后字数改变 ('{}' -> '{}')，无法分配到逐字音节。保留原文。",
+                full_text, replaced_text
+            );
+        }
+    }
+}
+
+/// 解析自定义词典文件的内容。
+///
+/// 每行一个词条，原词与替换词之间用制表符、`=` 或空白分隔。替换词有多个候选时
+/// （`OpenCC` 的词典格式）只取第一个。空行和以 `#` 开头的注释行会被忽略。
+#[must_use]
+pub fn parse_custom_dictionary(content: &str) -> Vec<(String, String)> {
+    content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line
+                .split_once(['\t', '='])
+                .or_else(|| line.split_once(char::is_whitespace))?;
+            let key = key.trim();
+            let value = value.split_whitespace().next()?;
+            (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// 对文本应用自定义词典，每个位置优先匹配最长的原词。
+#[must_use]
+pub fn apply_custom_dictionary(text: &str, dictionary: &[(String, String)]) -> String {
+    if dictionary.is_empty() {
+        return text.to_string();
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(ch) = rest.chars().next() {
+        let longest_match = dictionary
+            .iter()
+            .filter(|(key, _)| !key.is_empty() && rest.starts_with(key.as_str()))
+            .max_by_key(|(key, _)| key.len());
+        if let Some((key, value)) = longest_match {
+            result.push_str(value);
+            rest = &rest[key.len()..];
+        } else {
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    result
+}
+
+/// 简繁转换前后主歌词文本的变化统计。
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConversionChangeStats {
+    /// 发生变化的字符数。
+    pub changed_chars: usize,
+    /// 文本发生变化的行数。
+    pub affected_lines: usize,
+    /// 转换前的字符总数。
+    pub total_chars_before: usize,
+    /// 转换后的字符总数。
+    pub total_chars_after: usize,
+}
+
+impl ConversionChangeStats {
+    /// 转换前后的字符总数是否不一致，通常意味着选错了转换配置。
+    #[must_use]
+    pub const fn char_count_changed(&self) -> bool {
+        self.total_chars_before != self.total_chars_after
+    }
+}
+
+/// 逐行比较转换前后的主歌词文本，统计变化的字符数和受影响的行数。
+///
+/// 字符按位置逐一比较，长度差异部分全部计为变化的字符。
+/// 两侧行数不一致时，多出的行同样计入统计。
+#[must_use]
+pub fn compare_conversion(before: &[LyricLine], after: &[LyricLine]) -> ConversionChangeStats {
+    let mut stats = ConversionChangeStats::default();
+    let line_count = before.len().max(after.len());
+
+    for index in 0..line_count {
+        let before_chars: Vec<char> = before
+            .get(index)
+            .map(main_content_text)
+            .unwrap_or_default()
+            .chars()
+            .collect();
+        let after_chars: Vec<char> = after
+            .get(index)
+            .map(main_content_text)
+            .unwrap_or_default()
+            .chars()
+            .collect();
+
+        let changed = before_chars
+            .iter()
+            .zip(&after_chars)
+            .filter(|(b, a)| b != a)
+            .count()
+            + before_chars.len().abs_diff(after_chars.len());
+
+        stats.total_chars_before += before_chars.len();
+        stats.total_chars_after += after_chars.len();
+        if changed > 0 {
+            stats.changed_chars += changed;
+            stats.affected_lines += 1;
+        }
+    }
+
+    stats
+}
+
+/// 拼接一行中所有主歌词轨道的文本。
+fn main_content_text(line: &LyricLine) -> String {
+    line.main_tracks().map(|t| t.content.text()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,6 +554,8 @@ mod tests {
             config: Some(ChineseConversionConfig::S2twp),
             mode: ChineseConversionMode::AddAsTranslation,
             target_lang_tag: None,
+            custom_dictionary: Vec::new(),
+            translation_config: None,
         };
 
         ChineseConversionProcessor::process(&mut lines, &options);
@@ -358,6 +580,8 @@ mod tests {
             config: Some(ChineseConversionConfig::S2t),
             mode: ChineseConversionMode::AddAsTranslation,
             target_lang_tag: Some("zh-Hant".to_string()),
+            custom_dictionary: Vec::new(),
+            translation_config: None,
         };
 
         ChineseConversionProcessor::process(&mut lines, &options);
@@ -384,8 +608,142 @@ mod tests {
             config: None,
             mode: ChineseConversionMode::AddAsTranslation,
             target_lang_tag: None,
+            custom_dictionary: Vec::new(),
+            translation_config: None,
         };
         ChineseConversionProcessor::process(&mut lines, &options);
         assert_eq!(lines[0].tracks[0].translations.len(), 0);
     }
+
+    #[test]
+    fn test_translation_config_leaves_main_text_unchanged() {
+        let mut line = new_track_line("繁體中文");
+        line.add_translation(ContentType::Main, "繁體翻譯", Some("zh-Hant"));
+        let mut lines = vec![line];
+        let options = ChineseConversionOptions {
+            translation_config: Some(ChineseConversionConfig::T2s),
+            ..Default::default()
+        };
+
+        ChineseConversionProcessor::process(&mut lines, &options);
+
+        assert_eq!(lines[0].main_text().unwrap(), "繁體中文");
+        let translation = lines[0]
+            .get_translation_by_lang("zh-Hans")
+            .expect("翻译的语言标签应更新为 zh-Hans");
+        assert_eq!(translation.text(), "繁体翻译");
+    }
+
+    #[test]
+    fn test_translation_config_retags_only_chinese_translations() {
+        let mut line = new_track_line("Main");
+        line.add_translation(ContentType::Main, "繁體翻譯", None);
+        line.add_translation(ContentType::Main, "English", None);
+        let mut lines = vec![line];
+        let options = ChineseConversionOptions {
+            translation_config: Some(ChineseConversionConfig::T2s),
+            ..Default::default()
+        };
+
+        ChineseConversionProcessor::process(&mut lines, &options);
+
+        let translations = &lines[0].tracks[0].translations;
+        assert_eq!(translations[0].text(), "繁体翻译");
+        assert_eq!(
+            translations[0].metadata.get(&TrackMetadataKey::Language),
+            Some(&"zh-Hans".to_string())
+        );
+        assert_eq!(translations[1].text(), "English");
+        assert_eq!(
+            translations[1].metadata.get(&TrackMetadataKey::Language),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compare_conversion_counts_changes() {
+        let before = vec![
+            new_track_line("简体中文"),
+            new_track_line("不变"),
+            new_track_line("后面"),
+        ];
+        let after = vec![
+            new_track_line("簡體中文"),
+            new_track_line("不变"),
+            new_track_line("後面"),
+        ];
+
+        let stats = compare_conversion(&before, &after);
+        assert_eq!(stats.changed_chars, 3);
+        assert_eq!(stats.affected_lines, 2);
+        assert_eq!(stats.total_chars_before, 8);
+        assert!(!stats.char_count_changed());
+    }
+
+    #[test]
+    fn test_compare_conversion_detects_length_change() {
+        let before = vec![new_track_line("头发"), new_track_line("多余")];
+        let after = vec![new_track_line("頭髮了")];
+
+        let stats = compare_conversion(&before, &after);
+        assert_eq!(stats.changed_chars, 5);
+        assert_eq!(stats.affected_lines, 2);
+        assert_eq!(stats.total_chars_before, 4);
+        assert_eq!(stats.total_chars_after, 3);
+        assert!(stats.char_count_changed());
+    }
+
+    #[test]
+    fn test_parse_custom_dictionary() {
+        let dictionary = parse_custom_dictionary(
+            "\u{feff}# 注释\n软件\t軟體 軟件\n\n服务器 = 伺服器\n进程 行程\n无效行\n",
+        );
+        assert_eq!(
+            dictionary,
+            vec![
+                ("软件".to_string(), "軟體".to_string()),
+                ("服务器".to_string(), "伺服器".to_string()),
+                ("进程".to_string(), "行程".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_dictionary_prefers_longest_match() {
+        let dictionary = vec![
+            ("服务".to_string(), "服務".to_string()),
+            ("服务器".to_string(), "伺服器".to_string()),
+        ];
+        assert_eq!(
+            apply_custom_dictionary("服务器的服务", &dictionary),
+            "伺服器的服務"
+        );
+    }
+
+    #[test]
+    fn test_custom_dictionary_is_applied_after_conversion() {
+        let mut lines = vec![
+            new_track_line("我的服务器"),
+            new_syllable_track_line(vec!["简", "体"]),
+        ];
+        let options = ChineseConversionOptions {
+            config: Some(ChineseConversionConfig::S2t),
+            mode: ChineseConversionMode::Replace,
+            custom_dictionary: vec![
+                ("服務器".to_string(), "伺服器".to_string()),
+                ("簡體".to_string(), "正體".to_string()),
+            ],
+            ..Default::default()
+        };
+
+        ChineseConversionProcessor::process(&mut lines, &options);
+
+        assert_eq!(lines[0].main_text().unwrap(), "我的伺服器");
+        let syllables: Vec<&str> = lines[1].tracks[0]
+            .content
+            .syllables()
+            .map(|s| s.text.as_str())
+            .collect();
+        assert_eq!(syllables, vec!["正", "體"]);
+    }
 }
@@ -0,0 +1,183 @@
+//! 检测转换到目标格式时会丢失的歌词特性。
+//!
+//! 源歌词带有逐字时间、翻译、罗马音或背景人声，而目标格式（在当前生成选项下）
+//! 无法表示这些内容时，生成器会静默地降级或丢弃它们。这里在生成前比较两者，
+//! 以便把丢失的特性作为警告告知用户。
+
+use lyrics_helper_core::{
+    ContentType, ConversionOptions, LrcSubLinesOutputMode, LyricFormat, LyricLine,
+};
+
+/// 目标格式可能无法保留的歌词特性。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LyricFeature {
+    /// 逐字时间
+    WordTiming,
+    /// 翻译
+    Translation,
+    /// 罗马音
+    Romanization,
+    /// 背景人声
+    BackgroundVocals,
+}
+
+impl LyricFeature {
+    const ALL: [Self; 4] = [
+        Self::WordTiming,
+        Self::Translation,
+        Self::Romanization,
+        Self::BackgroundVocals,
+    ];
+
+    /// 该特性无法保留时显示给用户的警告。
+    #[must_use]
+    pub fn loss_warning(self, target_format: LyricFormat) -> String {
+        match self {
+            Self::WordTiming => {
+                format!("目标格式 {target_format} 不支持逐字时间，已降级为逐行时间")
+            }
+            Self::Translation => format!("目标格式 {target_format} 不支持翻译，翻译已被丢弃"),
+            Self::Romanization => {
+                format!("目标格式 {target_format} 不支持罗马音，罗马音已被丢弃")
+            }
+            Self::BackgroundVocals => {
+                format!("目标格式 {target_format} 不支持背景人声，背景人声已被丢弃")
+            }
+        }
+    }
+
+    /// 歌词中是否包含该特性。
+    fn present_in(self, lines: &[LyricLine]) -> bool {
+        let tracks = || lines.iter().flat_map(|line| &line.tracks);
+        match self {
+            // 逐行歌词也可能被拆成多个音节，但它们的开始时间相同
+            Self::WordTiming => tracks().any(|track| {
+                let mut starts = track.content.syllables().map(|s| s.start_ms);
+                starts
+                    .next()
+                    .is_some_and(|first| starts.any(|start| start != first))
+            }),
+            Self::Translation => tracks()
+                .flat_map(|track| &track.translations)
+                .any(|t| !t.is_empty()),
+            Self::Romanization => tracks()
+                .flat_map(|track| &track.romanizations)
+                .any(|t| !t.is_empty()),
+            Self::BackgroundVocals => tracks().any(|track| {
+                track.content_type == ContentType::Background && !track.content.is_empty()
+            }),
+        }
+    }
+
+    /// 目标格式在给定的生成选项下能否保留该特性。
+    fn supported_by(self, target_format: LyricFormat, options: &ConversionOptions) -> bool {
+        match target_format {
+            LyricFormat::Ttml | LyricFormat::AppleMusicJson | LyricFormat::Ass => true,
+            LyricFormat::Lrc => match self {
+                Self::WordTiming => options.lrc.word_timing,
+                Self::BackgroundVocals => {
+                    options.lrc.sub_lines_output_mode != LrcSubLinesOutputMode::Ignore
+                }
+                Self::Translation | Self::Romanization => false,
+            },
+            LyricFormat::EnhancedLrc => match self {
+                Self::WordTiming => true,
+                Self::BackgroundVocals => {
+                    options.lrc.sub_lines_output_mode != LrcSubLinesOutputMode::Ignore
+                }
+                Self::Translation | Self::Romanization => false,
+            },
+            LyricFormat::Qrc | LyricFormat::Lys => {
+                matches!(self, Self::WordTiming | Self::BackgroundVocals)
+            }
+            LyricFormat::Krc | LyricFormat::Yrc => self == Self::WordTiming,
+            LyricFormat::Spl => matches!(self, Self::WordTiming | Self::Translation),
+            LyricFormat::Lrcx => self != Self::BackgroundVocals,
+            LyricFormat::Vtt => self != Self::Romanization,
+            LyricFormat::Lqe => match self {
+                Self::Translation | Self::Romanization => true,
+                // 主歌词区块使用的格式决定逐字时间和背景人声能否保留
+                Self::WordTiming | Self::BackgroundVocals => {
+                    let main_format = options.lqe.main_lyric_format;
+                    main_format != LyricFormat::Lqe && self.supported_by(main_format, options)
+                }
+            },
+            LyricFormat::Lyl | LyricFormat::Musixmatch => false,
+        }
+    }
+}
+
+/// 找出歌词中存在、但目标格式在给定选项下无法保留的特性。
+#[must_use]
+pub fn detect_lost_features(
+    lines: &[LyricLine],
+    target_format: LyricFormat,
+    options: &ConversionOptions,
+) -> Vec<LyricFeature> {
+    LyricFeature::ALL
+        .into_iter()
+        .filter(|feature| !feature.supported_by(target_format, options))
+        .filter(|feature| feature.present_in(lines))
+        .collect()
+}
+
+/// 为目标格式无法保留的特性生成警告文本。
+#[must_use]
+pub fn feature_loss_warnings(
+    lines: &[LyricLine],
+    target_format: LyricFormat,
+    options: &ConversionOptions,
+) -> Vec<String> {
+    detect_lost_features(lines, target_format, options)
+        .into_iter()
+        .map(|feature| feature.loss_warning(target_format))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{syl, text_line, timed_line};
+
+    #[test]
+    fn test_word_timed_with_translation_to_plain_lrc() {
+        let mut line = timed_line(vec![syl("Hel", 0, 500), syl("lo", 500, 1000)]);
+        line.add_translation(ContentType::Main, "你好", None);
+        let lines = vec![line];
+        let options = ConversionOptions::default();
+
+        assert_eq!(
+            detect_lost_features(&lines, LyricFormat::Lrc, &options),
+            vec![LyricFeature::WordTiming, LyricFeature::Translation]
+        );
+        assert!(detect_lost_features(&lines, LyricFormat::Ttml, &options).is_empty());
+        assert_eq!(
+            detect_lost_features(&lines, LyricFormat::Qrc, &options),
+            vec![LyricFeature::Translation]
+        );
+    }
+
+    #[test]
+    fn test_options_enable_features() {
+        let lines = vec![timed_line(vec![syl("Hel", 0, 500), syl("lo", 500, 1000)])];
+        let mut options = ConversionOptions::default();
+        options.lrc.word_timing = true;
+
+        assert!(detect_lost_features(&lines, LyricFormat::Lrc, &options).is_empty());
+
+        options.lqe.main_lyric_format = LyricFormat::Lyl;
+        assert_eq!(
+            detect_lost_features(&lines, LyricFormat::Lqe, &options),
+            vec![LyricFeature::WordTiming]
+        );
+    }
+
+    #[test]
+    fn test_line_timed_source_loses_nothing() {
+        let lines = vec![text_line(0, 500, "Hello")];
+        assert!(
+            detect_lost_features(&lines, LyricFormat::Lyl, &ConversionOptions::default())
+                .is_empty()
+        );
+    }
+}
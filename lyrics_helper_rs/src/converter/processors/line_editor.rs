@@ -0,0 +1,775 @@
+//! 歌词行的合并与拆分工具。
+//!
+//! 用于修正来源中被错误拆开的歌词行，或被错误合并在一起的两句歌词，
+//! 以及插入和删除间奏标记行。此外还提供时间轴编辑器所需的行与音节时间调整和吸附计算，
+//! 以及适配变速音频的整体时间缩放和首尾静音裁剪。
+
+use lyrics_helper_core::{AnnotatedTrack, ContentType, LyricLine, LyricSyllable, LyricTrack, Word};
+
+/// 将指定行与其下一行合并为一行。
+///
+/// 两行中相同类型的内容轨道会被拼接在一起（文本和音节），
+/// 翻译和罗马音轨道按顺序逐一拼接。合并后行的时间取两行时间的并集。
+/// 演唱者、歌曲组成部分等属性保留第一行的值。
+///
+/// # 返回
+/// 如果 `index` 没有下一行可供合并，返回 `false` 且不做任何修改。
+pub fn merge_with_next_line(lines: &mut Vec<LyricLine>, index: usize) -> bool {
+    if index + 1 >= lines.len() {
+        return false;
+    }
+
+    let next_line = lines.remove(index + 1);
+    let line = &mut lines[index];
+
+    line.start_ms = line.start_ms.min(next_line.start_ms);
+    line.end_ms = line.end_ms.max(next_line.end_ms);
+    if line.agent.is_none() {
+        line.agent = next_line.agent;
+    }
+    if line.song_part.is_none() {
+        line.song_part = next_line.song_part;
+    }
+
+    for next_track in next_line.tracks {
+        match line
+            .tracks
+            .iter_mut()
+            .find(|t| t.content_type == next_track.content_type)
+        {
+            Some(track) => merge_annotated_track(track, next_track),
+            None => line.tracks.push(next_track),
+        }
+    }
+
+    true
+}
+
+/// 在主歌词轨道的指定音节处将一行拆分为两行。
+///
+/// `syllable_index` 是主歌词轨道中（跨所有词的）音节序号，该音节及其之后的音节
+/// 会被移动到新的一行。背景人声与逐字的注解轨道按拆分点的时间分配到两行，
+/// 非逐字的翻译和罗马音无法可靠拆分，会保留在前一行。
+///
+/// # 返回
+/// 如果行不存在、没有主歌词轨道，或拆分点不在音节边界内（即拆分后某一行为空），
+/// 返回 `false` 且不做任何修改。
+pub fn split_line_at_syllable(
+    lines: &mut Vec<LyricLine>,
+    line_index: usize,
+    syllable_index: usize,
+) -> bool {
+    let Some(line) = lines.get_mut(line_index) else {
+        return false;
+    };
+    let Some(main_track_pos) = line
+        .tracks
+        .iter()
+        .position(|t| t.content_type == ContentType::Main && !t.content.is_empty())
+    else {
+        return false;
+    };
+
+    let main_content = &line.tracks[main_track_pos].content;
+    let syllable_count = main_content.syllables().count();
+    if syllable_index == 0 || syllable_index >= syllable_count {
+        return false;
+    }
+    let Some(split_ms) = main_content
+        .syllables()
+        .nth(syllable_index)
+        .map(|s| s.start_ms)
+    else {
+        return false;
+    };
+
+    let mut new_line = LyricLine {
+        agent: line.agent.clone(),
+        song_part: line.song_part.clone(),
+        ..Default::default()
+    };
+
+    for (pos, track) in line.tracks.iter_mut().enumerate() {
+        let tail_content = if pos == main_track_pos {
+            split_track_at_index(&mut track.content, syllable_index)
+        } else {
+            split_track_at_time(&mut track.content, split_ms)
+        };
+
+        let split_annotations = |annotations: &mut Vec<LyricTrack>| -> Vec<LyricTrack> {
+            annotations
+                .iter_mut()
+                .filter(|t| t.is_timed())
+                .map(|t| split_track_at_time(t, split_ms))
+                .filter(|t| !t.is_empty())
+                .collect()
+        };
+        let mut tail_translations = split_annotations(&mut track.translations);
+        let mut tail_romanizations = split_annotations(&mut track.romanizations);
+
+        // 整个轨道都被移到后一行时，其余注解也随之移动
+        if track.content.is_empty() {
+            tail_translations.extend(track.translations.drain(..).filter(|t| !t.is_empty()));
+            tail_romanizations.extend(track.romanizations.drain(..).filter(|t| !t.is_empty()));
+        }
+
+        if !tail_content.is_empty() {
+            new_line.tracks.push(AnnotatedTrack {
+                content_type: track.content_type,
+                content: tail_content,
+                translations: tail_translations,
+                romanizations: tail_romanizations,
+            });
+        }
+    }
+    line.tracks.retain(|t| !t.content.is_empty());
+
+    let (head_start, head_end) = line_time_range(line).unwrap_or((line.start_ms, split_ms));
+    let (tail_start, tail_end) = line_time_range(&new_line).unwrap_or((split_ms, line.end_ms));
+    new_line.start_ms = tail_start;
+    new_line.end_ms = tail_end;
+    line.start_ms = head_start;
+    line.end_ms = head_end;
+
+    lines.insert(line_index + 1, new_line);
+    true
+}
+
+/// 插入一个间奏标记行，按开始时间放在合适的位置。
+///
+/// # 返回
+/// 新插入行的索引。如果开始时间不早于结束时间，返回 `None` 且不做任何修改。
+pub fn insert_interlude(lines: &mut Vec<LyricLine>, start_ms: u64, end_ms: u64) -> Option<usize> {
+    if start_ms >= end_ms {
+        return None;
+    }
+
+    let index = lines.partition_point(|line| line.start_ms <= start_ms);
+    lines.insert(index, LyricLine::new_interlude(start_ms, end_ms));
+    Some(index)
+}
+
+/// 删除指定的间奏标记行。
+///
+/// # 返回
+/// 如果该行不存在或不是间奏标记行，返回 `false` 且不做任何修改。
+pub fn remove_interlude(lines: &mut Vec<LyricLine>, index: usize) -> bool {
+    if !lines.get(index).is_some_and(LyricLine::is_interlude) {
+        return false;
+    }
+    lines.remove(index);
+    true
+}
+
+/// 设置一行的开始和结束时间。
+///
+/// 如果时长不变（整体拖动），行内所有音节（包括背景人声和逐字的注解轨道）随之平移；
+/// 否则视为调整边界，只把超出新范围的音节截断到范围内。
+///
+/// # 返回
+/// 如果行不存在或开始时间不早于结束时间，返回 `false` 且不做任何修改。
+pub fn set_line_timing(lines: &mut [LyricLine], index: usize, start_ms: u64, end_ms: u64) -> bool {
+    if start_ms >= end_ms {
+        return false;
+    }
+    let Some(line) = lines.get_mut(index) else {
+        return false;
+    };
+
+    let is_move = end_ms - start_ms == line.end_ms.saturating_sub(line.start_ms);
+    let delta = i128::from(start_ms) - i128::from(line.start_ms);
+    for syllable in line_syllables_mut(line) {
+        if is_move {
+            syllable.start_ms = shift_ms(syllable.start_ms, delta);
+            syllable.end_ms = shift_ms(syllable.end_ms, delta);
+        } else {
+            syllable.start_ms = syllable.start_ms.clamp(start_ms, end_ms);
+            syllable.end_ms = syllable.end_ms.clamp(syllable.start_ms, end_ms);
+        }
+    }
+
+    line.start_ms = start_ms;
+    line.end_ms = end_ms;
+    true
+}
+
+/// 设置主歌词轨道中第 `syllable_index` 个音节的开始和结束时间。
+///
+/// 音节超出所在行的范围时，行的时间会扩展以包含它。
+///
+/// # 返回
+/// 如果行或音节不存在，或开始时间晚于结束时间，返回 `false` 且不做任何修改。
+pub fn set_syllable_timing(
+    lines: &mut [LyricLine],
+    line_index: usize,
+    syllable_index: usize,
+    start_ms: u64,
+    end_ms: u64,
+) -> bool {
+    if start_ms > end_ms {
+        return false;
+    }
+    let Some(line) = lines.get_mut(line_index) else {
+        return false;
+    };
+    let Some(syllable) = line
+        .tracks
+        .iter_mut()
+        .find(|t| t.content_type == ContentType::Main)
+        .and_then(|t| t.content.syllables_mut().nth(syllable_index))
+    else {
+        return false;
+    };
+
+    syllable.start_ms = start_ms;
+    syllable.end_ms = end_ms;
+    line.start_ms = line.start_ms.min(start_ms);
+    line.end_ms = line.end_ms.max(end_ms);
+    true
+}
+
+/// 时间轴上可以调整时间的对象。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimingTarget {
+    Line(usize),
+    /// 行索引与主歌词音节索引
+    Syllable(usize, usize),
+}
+
+/// 收集所有行与主歌词音节的开始和结束时间，作为时间轴拖动时的吸附点。
+///
+/// `exclude` 是正在拖动的对象，它自身的边界（对行而言还包括随行移动的音节）不会被收集。
+/// 结果已排序并去重。
+#[must_use]
+pub fn timing_boundaries(lines: &[LyricLine], exclude: Option<TimingTarget>) -> Vec<u64> {
+    let mut boundaries = Vec::new();
+    for (line_index, line) in lines.iter().enumerate() {
+        if exclude == Some(TimingTarget::Line(line_index)) {
+            continue;
+        }
+        boundaries.extend([line.start_ms, line.end_ms]);
+
+        let syllables = line
+            .tracks
+            .iter()
+            .filter(|t| t.content_type == ContentType::Main)
+            .flat_map(|t| t.content.syllables())
+            .enumerate();
+        for (syllable_index, syllable) in syllables {
+            if exclude != Some(TimingTarget::Syllable(line_index, syllable_index)) {
+                boundaries.extend([syllable.start_ms, syllable.end_ms]);
+            }
+        }
+    }
+    boundaries.sort_unstable();
+    boundaries.dedup();
+    boundaries
+}
+
+/// 将时间吸附到 `tolerance_ms` 范围内最近的边界上。
+///
+/// `boundaries` 必须已排序。范围内没有边界时原样返回。
+#[must_use]
+pub fn snap_to_boundary(value_ms: u64, boundaries: &[u64], tolerance_ms: u64) -> u64 {
+    let index = boundaries.partition_point(|&b| b < value_ms);
+    [index.checked_sub(1), Some(index)]
+        .into_iter()
+        .flatten()
+        .filter_map(|i| boundaries.get(i).copied())
+        .filter(|&b| b.abs_diff(value_ms) <= tolerance_ms)
+        .min_by_key(|&b| b.abs_diff(value_ms))
+        .unwrap_or(value_ms)
+}
+
+/// 将所有行和音节的时间戳乘以同一个比例，用于适配加速或减速后的音频版本。
+///
+/// 例如歌词对应原速音频、而要适配 1.25 倍速的版本时，比例应为 `0.8`。
+/// 音节的可选时长也按同一比例缩放。
+///
+/// # 返回
+/// 比例不是正的有限数时返回 `false` 且不做任何修改。
+pub fn scale_timings(lines: &mut [LyricLine], factor: f64) -> bool {
+    if !factor.is_finite() || factor <= 0.0 {
+        return false;
+    }
+
+    for line in lines {
+        line.start_ms = scale_ms(line.start_ms, factor);
+        line.end_ms = scale_ms(line.end_ms, factor);
+        for syllable in line_syllables_mut(line) {
+            syllable.start_ms = scale_ms(syllable.start_ms, factor);
+            syllable.end_ms = scale_ms(syllable.end_ms, factor);
+            syllable.duration_ms = syllable
+                .duration_ms
+                .map(|duration| scale_ms(duration, factor));
+        }
+    }
+    true
+}
+
+/// 首尾静音裁剪的结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SilenceTrim {
+    /// 时间轴整体平移的毫秒数，负数表示前移。
+    pub shift_ms: i64,
+    /// 被删除的开头和结尾间奏标记行数。
+    pub removed_lines: usize,
+    /// 结尾是否裁掉了最后一行音节之后的静音。
+    pub tail_trimmed: bool,
+}
+
+/// 裁剪歌词首尾的静音。
+///
+/// 删除开头的间奏标记行，然后整体平移时间轴，使第一行从 `start_at_ms` 开始，
+/// 行与行之间的相对间隔保持不变。`trim_tail` 为 `true` 时，还会删除结尾的间奏标记行，
+/// 并把最后一行的结束时间收紧到其最后一个音节的结束时间。
+/// 没有时间的音节（如逐行翻译中开始和结束均为 0 的音节）不参与平移。
+///
+/// # 返回
+/// 没有任何可裁剪的内容时返回 `None`。
+pub fn trim_silence(
+    lines: &mut Vec<LyricLine>,
+    start_at_ms: u64,
+    trim_tail: bool,
+) -> Option<SilenceTrim> {
+    let original_len = lines.len();
+    let leading = lines.iter().take_while(|line| line.is_interlude()).count();
+    lines.drain(..leading);
+    if trim_tail {
+        while lines.last().is_some_and(LyricLine::is_interlude) {
+            lines.pop();
+        }
+    }
+    let removed_lines = original_len - lines.len();
+
+    let tail_trimmed = if trim_tail
+        && let Some(last) = lines.last_mut()
+        && let Some((_, content_end)) = line_time_range(last)
+        && content_end > last.start_ms
+        && content_end < last.end_ms
+    {
+        last.end_ms = content_end;
+        true
+    } else {
+        false
+    };
+
+    let delta = lines.first().map_or(0, |first| {
+        i128::from(start_at_ms) - i128::from(first.start_ms)
+    });
+    if delta != 0 {
+        for line in lines.iter_mut() {
+            line.start_ms = shift_ms(line.start_ms, delta);
+            line.end_ms = shift_ms(line.end_ms, delta);
+            for syllable in line_syllables_mut(line) {
+                if syllable.start_ms == 0 && syllable.end_ms == 0 {
+                    continue;
+                }
+                syllable.start_ms = shift_ms(syllable.start_ms, delta);
+                syllable.end_ms = shift_ms(syllable.end_ms, delta);
+            }
+        }
+    }
+
+    (delta != 0 || removed_lines > 0 || tail_trimmed).then(|| SilenceTrim {
+        shift_ms: i64::try_from(delta).unwrap_or(i64::MIN),
+        removed_lines,
+        tail_trimmed,
+    })
+}
+
+/// 遍历一行中所有内容轨道与注解轨道的音节。
+fn line_syllables_mut(line: &mut LyricLine) -> impl Iterator<Item = &mut LyricSyllable> {
+    line.tracks.iter_mut().flat_map(|track| {
+        std::iter::once(&mut track.content)
+            .chain(track.translations.iter_mut())
+            .chain(track.romanizations.iter_mut())
+            .flat_map(LyricTrack::syllables_mut)
+    })
+}
+
+fn shift_ms(value: u64, delta: i128) -> u64 {
+    u64::try_from((i128::from(value) + delta).max(0)).unwrap_or(u64::MAX)
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn scale_ms(value: u64, factor: f64) -> u64 {
+    (value as f64 * factor).round() as u64
+}
+
+/// 将 `other` 的内容轨道和注解轨道拼接到 `track` 之后。
+fn merge_annotated_track(track: &mut AnnotatedTrack, other: AnnotatedTrack) {
+    append_track(&mut track.content, other.content);
+
+    let mut other_translations = other.translations.into_iter();
+    for translation in &mut track.translations {
+        if let Some(other_translation) = other_translations.next() {
+            append_track(translation, other_translation);
+        }
+    }
+    track.translations.extend(other_translations);
+
+    let mut other_romanizations = other.romanizations.into_iter();
+    for romanization in &mut track.romanizations {
+        if let Some(other_romanization) = other_romanizations.next() {
+            append_track(romanization, other_romanization);
+        }
+    }
+    track.romanizations.extend(other_romanizations);
+}
+
+/// 将 `other` 的所有词追加到 `track` 末尾。
+///
+/// 如果前后两段的衔接处都是 ASCII 字母或数字，会在两者之间补上空格。
+fn append_track(track: &mut LyricTrack, other: LyricTrack) {
+    let next_starts_with_word_char = other
+        .syllables()
+        .next()
+        .and_then(|s| s.text.chars().next())
+        .is_some_and(|c| c.is_ascii_alphanumeric());
+
+    if let Some(last) = track.syllables_mut().last()
+        && !last.ends_with_space
+        && next_starts_with_word_char
+        && last
+            .text
+            .chars()
+            .last()
+            .is_some_and(|c| c.is_ascii_alphanumeric())
+    {
+        last.ends_with_space = true;
+    }
+
+    track.words.extend(other.words);
+    for (key, value) in other.metadata {
+        track.metadata.entry(key).or_insert(value);
+    }
+}
+
+/// 从第 `syllable_index` 个音节处拆分轨道，返回拆分出的后半部分。
+fn split_track_at_index(track: &mut LyricTrack, syllable_index: usize) -> LyricTrack {
+    let mut seen = 0;
+    let mut tail_words = Vec::new();
+    let mut words = std::mem::take(&mut track.words).into_iter();
+
+    for mut word in words.by_ref() {
+        let len = word.syllables.len();
+        if seen + len <= syllable_index {
+            seen += len;
+            track.words.push(word);
+            continue;
+        }
+
+        let offset = syllable_index - seen;
+        if offset > 0 {
+            let tail_syllables = word.syllables.split_off(offset);
+            tail_words.push(Word {
+                syllables: tail_syllables,
+                furigana: None,
+            });
+            word.furigana = None;
+            track.words.push(word);
+        } else {
+            tail_words.push(word);
+        }
+        break;
+    }
+    tail_words.extend(words);
+
+    finish_split(track, tail_words)
+}
+
+/// 将开始时间不早于 `split_ms` 的音节拆分出去，返回拆分出的后半部分。
+fn split_track_at_time(track: &mut LyricTrack, split_ms: u64) -> LyricTrack {
+    let mut tail_words = Vec::new();
+
+    for word in &mut track.words {
+        let (head, tail): (Vec<LyricSyllable>, Vec<LyricSyllable>) =
+            std::mem::take(&mut word.syllables)
+                .into_iter()
+                .partition(|s| s.start_ms < split_ms);
+        word.syllables = head;
+        if !tail.is_empty() {
+            // 振假名无法按音节拆分，只有整个词都移动时才保留
+            let furigana = if word.syllables.is_empty() {
+                word.furigana.take()
+            } else {
+                word.furigana = None;
+                None
+            };
+            tail_words.push(Word {
+                syllables: tail,
+                furigana,
+            });
+        }
+    }
+    track.words.retain(|w| !w.syllables.is_empty());
+
+    finish_split(track, tail_words)
+}
+
+/// 清理拆分后前半部分末尾的空格，并用剩余的词构造后半部分轨道。
+fn finish_split(track: &mut LyricTrack, tail_words: Vec<Word>) -> LyricTrack {
+    if let Some(last) = track.syllables_mut().last() {
+        last.ends_with_space = false;
+    }
+
+    LyricTrack {
+        words: tail_words,
+        metadata: track.metadata.clone(),
+    }
+}
+
+/// 计算一行中所有内容轨道音节的时间范围。
+fn line_time_range(line: &LyricLine) -> Option<(u64, u64)> {
+    line.tracks
+        .iter()
+        .filter_map(|t| t.content.time_range())
+        .reduce(|(s1, e1), (s2, e2)| (s1.min(s2), e1.max(e2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::converter::test_utils::{spaced_syl, syl, timed_line, track};
+
+    #[test]
+    fn test_merge_with_next_line() {
+        let mut hello = timed_line(vec![syl("Hello", 1000, 2000)]);
+        hello.add_translation(ContentType::Main, "你好", None);
+        let mut world = timed_line(vec![syl("world", 2500, 3000)]);
+        world.add_translation(ContentType::Main, "世界", None);
+        let mut lines = vec![hello, world];
+
+        assert!(merge_with_next_line(&mut lines, 0));
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].start_ms, 1000);
+        assert_eq!(lines[0].end_ms, 3000);
+        assert_eq!(lines[0].main_text().as_deref(), Some("Hello world"));
+        assert_eq!(
+            lines[0].main_track().unwrap().translations[0].text(),
+            "你好世界"
+        );
+    }
+
+    #[test]
+    fn test_merge_last_line_is_noop() {
+        let mut lines = vec![timed_line(vec![syl("a", 0, 1000)])];
+        assert!(!merge_with_next_line(&mut lines, 0));
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn test_split_line_at_syllable() {
+        let mut source = timed_line(vec![
+            spaced_syl("Hello", 1000, 2000),
+            spaced_syl("brave", 2000, 3000),
+            syl("world", 3000, 4000),
+        ]);
+        source.add_translation(ContentType::Main, "你好勇敢的世界", None);
+        let mut lines = vec![source];
+
+        assert!(split_line_at_syllable(&mut lines, 0, 2));
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0].main_text().as_deref(), Some("Hello brave"));
+        assert_eq!((lines[0].start_ms, lines[0].end_ms), (1000, 3000));
+        assert!(
+            !lines[0]
+                .main_track()
+                .unwrap()
+                .content
+                .syllables()
+                .last()
+                .unwrap()
+                .ends_with_space
+        );
+        assert_eq!(lines[0].main_track().unwrap().translations.len(), 1);
+
+        assert_eq!(lines[1].main_text().as_deref(), Some("world"));
+        assert_eq!((lines[1].start_ms, lines[1].end_ms), (3000, 4000));
+        assert!(lines[1].main_track().unwrap().translations.is_empty());
+    }
+
+    #[test]
+    fn test_split_moves_background_by_time() {
+        let mut source = timed_line(vec![syl("a", 0, 1000), syl("b", 2000, 4000)]);
+        source.tracks.push(AnnotatedTrack {
+            content_type: ContentType::Background,
+            content: track(vec![syl("x", 500, 1500), syl("y", 2500, 3500)]),
+            ..Default::default()
+        });
+        let mut lines = vec![source];
+
+        assert!(split_line_at_syllable(&mut lines, 0, 1));
+        assert_eq!(lines[0].background_text().as_deref(), Some("x"));
+        assert_eq!(lines[1].background_text().as_deref(), Some("y"));
+        assert_eq!(lines[0].end_ms, 1500);
+    }
+
+    #[test]
+    fn test_split_at_boundary_is_rejected() {
+        let mut lines = vec![timed_line(vec![syl("a", 0, 1000), syl("b", 1000, 2000)])];
+        assert!(!split_line_at_syllable(&mut lines, 0, 0));
+        assert!(!split_line_at_syllable(&mut lines, 0, 2));
+        assert!(!split_line_at_syllable(&mut lines, 1, 1));
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_and_remove_interlude() {
+        let mut lines = vec![
+            timed_line(vec![syl("a", 0, 1000)]),
+            timed_line(vec![syl("b", 9000, 10000)]),
+        ];
+
+        assert_eq!(insert_interlude(&mut lines, 1000, 9000), Some(1));
+        assert!(lines[1].is_interlude());
+        assert_eq!((lines[1].start_ms, lines[1].end_ms), (1000, 9000));
+
+        assert!(!remove_interlude(&mut lines, 0));
+        assert!(remove_interlude(&mut lines, 1));
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_insert_interlude_rejects_empty_range() {
+        let mut lines = Vec::new();
+        assert_eq!(insert_interlude(&mut lines, 5000, 5000), None);
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn test_moving_line_shifts_syllables() {
+        let mut lines = vec![timed_line(vec![syl("a", 1000, 2000), syl("b", 2000, 3000)])];
+
+        assert!(set_line_timing(&mut lines, 0, 1500, 3500));
+        let starts: Vec<u64> = lines[0]
+            .main_track()
+            .unwrap()
+            .content
+            .syllables()
+            .map(|s| s.start_ms)
+            .collect();
+        assert_eq!(starts, vec![1500, 2500]);
+        assert_eq!(lines[0].end_ms, 3500);
+    }
+
+    #[test]
+    fn test_resizing_line_clamps_syllables() {
+        let mut lines = vec![timed_line(vec![syl("a", 1000, 2000), syl("b", 2000, 3000)])];
+
+        assert!(set_line_timing(&mut lines, 0, 1000, 2500));
+        let last = lines[0]
+            .main_track()
+            .unwrap()
+            .content
+            .syllables()
+            .last()
+            .unwrap();
+        assert_eq!((last.start_ms, last.end_ms), (2000, 2500));
+        assert!(!set_line_timing(&mut lines, 0, 2500, 2500));
+        assert!(!set_line_timing(&mut lines, 1, 0, 100));
+    }
+
+    #[test]
+    fn test_syllable_timing_extends_line() {
+        let mut lines = vec![timed_line(vec![syl("a", 1000, 1500), syl("b", 1500, 2000)])];
+
+        assert!(set_syllable_timing(&mut lines, 0, 1, 1600, 2400));
+        assert_eq!(lines[0].end_ms, 2400);
+        assert!(!set_syllable_timing(&mut lines, 0, 2, 0, 100));
+        assert!(!set_syllable_timing(&mut lines, 0, 0, 500, 100));
+    }
+
+    #[test]
+    fn test_snap_to_nearest_boundary() {
+        let lines = vec![
+            timed_line(vec![syl("a", 0, 1000)]),
+            timed_line(vec![syl("b", 1200, 2000)]),
+        ];
+        let boundaries = timing_boundaries(&lines, None);
+        assert_eq!(boundaries, vec![0, 1000, 1200, 2000]);
+        assert_eq!(
+            timing_boundaries(&lines, Some(TimingTarget::Line(1))),
+            vec![0, 1000]
+        );
+
+        assert_eq!(snap_to_boundary(1080, &boundaries, 100), 1000);
+        assert_eq!(snap_to_boundary(1150, &boundaries, 100), 1200);
+        assert_eq!(snap_to_boundary(1500, &boundaries, 100), 1500);
+    }
+
+    #[test]
+    fn test_scale_timings() {
+        let mut lines = vec![timed_line(vec![
+            LyricSyllable {
+                duration_ms: Some(500),
+                ..syl("a", 1000, 1500)
+            },
+            syl("b", 1500, 2000),
+        ])];
+
+        assert!(scale_timings(&mut lines, 0.8));
+        assert_eq!((lines[0].start_ms, lines[0].end_ms), (800, 1600));
+        let syllables: Vec<_> = lines[0].tracks[0].content.syllables().collect();
+        assert_eq!((syllables[0].start_ms, syllables[0].end_ms), (800, 1200));
+        assert_eq!(syllables[0].duration_ms, Some(400));
+        assert_eq!((syllables[1].start_ms, syllables[1].end_ms), (1200, 1600));
+
+        assert!(!scale_timings(&mut lines, 0.0));
+        assert!(!scale_timings(&mut lines, f64::NAN));
+        assert_eq!(lines[0].start_ms, 800);
+    }
+
+    #[test]
+    fn test_trim_silence_shifts_to_start() {
+        let mut first = timed_line(vec![syl("a", 20000, 21000)]);
+        first.tracks[0]
+            .translations
+            .push(track(vec![syl("甲", 0, 0)]));
+        let mut lines = vec![
+            LyricLine::new_interlude(0, 20000),
+            first,
+            timed_line(vec![syl("b", 23000, 24000)]),
+        ];
+
+        let trim = trim_silence(&mut lines, 500, false).unwrap();
+
+        assert_eq!(trim.shift_ms, -19500);
+        assert_eq!(trim.removed_lines, 1);
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[0].start_ms, lines[1].start_ms), (500, 3500));
+        let syllable = lines[1].tracks[0].content.syllables().next().unwrap();
+        assert_eq!((syllable.start_ms, syllable.end_ms), (3500, 4500));
+        let translation = lines[0].tracks[0].translations[0]
+            .syllables()
+            .next()
+            .unwrap();
+        assert_eq!((translation.start_ms, translation.end_ms), (0, 0));
+    }
+
+    #[test]
+    fn test_trim_silence_trims_tail() {
+        let mut lines = vec![
+            LyricLine {
+                end_ms: 9000,
+                ..timed_line(vec![syl("a", 0, 1000)])
+            },
+            LyricLine::new_interlude(9000, 30000),
+        ];
+
+        let trim = trim_silence(&mut lines, 0, true).unwrap();
+
+        assert_eq!(trim.shift_ms, 0);
+        assert!(trim.tail_trimmed);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].end_ms, 1000);
+        assert_eq!(trim_silence(&mut lines, 0, true), None);
+    }
+}
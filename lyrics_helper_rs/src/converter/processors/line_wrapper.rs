@@ -0,0 +1,252 @@
+//! 将过长的歌词行按标点或空格拆分为多行。
+//!
+//! 拆分点优先选在标点之后，其次是空格处，都没有时才在字数上限处硬拆分。
+//! 逐字歌词按音节拆分，保留每个音节原有的时间；逐行歌词按字数比例分配时间。
+
+use lyrics_helper_core::{ContentType, LineWrapOptions, LyricLine};
+
+use super::line_editor;
+
+/// 拆分点的优先级，数值越大越优先。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum BreakQuality {
+    None,
+    Space,
+    Punctuation,
+}
+
+/// 拆分所有主歌词超过字数上限的行。
+///
+/// 非逐字的翻译和罗马音无法可靠拆分，会保留在拆分出的第一行。
+///
+/// # 返回
+/// 新增的行数。
+pub fn wrap_long_lines(lines: &mut Vec<LyricLine>, options: &LineWrapOptions) -> usize {
+    if options.max_chars == 0 {
+        return 0;
+    }
+
+    let mut added_lines = 0;
+    let mut index = 0;
+    while index < lines.len() {
+        if split_line_once(lines, index, options.max_chars) {
+            added_lines += 1;
+        }
+        // 拆分出的后半部分在下一轮中继续检查
+        index += 1;
+    }
+    added_lines
+}
+
+/// 如果该行过长，将其拆分一次。
+fn split_line_once(lines: &mut Vec<LyricLine>, index: usize, max_chars: usize) -> bool {
+    let Some(track) = lines[index]
+        .tracks
+        .iter()
+        .find(|t| t.content_type == ContentType::Main)
+    else {
+        return false;
+    };
+
+    let syllables: Vec<_> = track.content.syllables().collect();
+    match syllables.as_slice() {
+        [] => false,
+        [syllable] => {
+            let text = syllable.text.trim().to_string();
+            split_line_timed(lines, index, &text, max_chars)
+        }
+        _ => {
+            let units: Vec<(usize, BreakQuality)> = syllables
+                .iter()
+                .map(|s| {
+                    let quality = if s.text.chars().last().is_some_and(is_break_punctuation) {
+                        BreakQuality::Punctuation
+                    } else if s.ends_with_space {
+                        BreakQuality::Space
+                    } else {
+                        BreakQuality::None
+                    };
+                    (
+                        s.text.chars().count() + usize::from(s.ends_with_space),
+                        quality,
+                    )
+                })
+                .collect();
+            find_break(&units, max_chars)
+                .is_some_and(|split_at| line_editor::split_line_at_syllable(lines, index, split_at))
+        }
+    }
+}
+
+/// 拆分只有一个音节的逐行歌词，按字数比例分配前后两行的时间。
+fn split_line_timed(
+    lines: &mut Vec<LyricLine>,
+    index: usize,
+    text: &str,
+    max_chars: usize,
+) -> bool {
+    let chars: Vec<char> = text.chars().collect();
+    let units: Vec<(usize, BreakQuality)> = chars
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let quality = if is_break_punctuation(c) {
+                BreakQuality::Punctuation
+            } else if chars.get(i + 1).is_some_and(|next| next.is_whitespace()) {
+                BreakQuality::Space
+            } else {
+                BreakQuality::None
+            };
+            (1, quality)
+        })
+        .collect();
+    let Some(split_at) = find_break(&units, max_chars) else {
+        return false;
+    };
+
+    let head: String = chars[..split_at]
+        .iter()
+        .collect::<String>()
+        .trim()
+        .to_string();
+    let tail: String = chars[split_at..]
+        .iter()
+        .collect::<String>()
+        .trim()
+        .to_string();
+    if head.is_empty() || tail.is_empty() {
+        return false;
+    }
+
+    let line = &mut lines[index];
+    let duration = line.end_ms.saturating_sub(line.start_ms);
+    let split_ms = line.start_ms
+        + u64::try_from(u128::from(duration) * split_at as u128 / chars.len() as u128)
+            .unwrap_or(duration);
+
+    let mut new_line = LyricLine {
+        agent: line.agent.clone(),
+        song_part: line.song_part.clone(),
+        ..LyricLine::new(split_ms, line.end_ms)
+    };
+    new_line.add_content_track(ContentType::Main, tail);
+
+    line.end_ms = split_ms;
+    if let Some(syllable) = line
+        .tracks
+        .iter_mut()
+        .find(|t| t.content_type == ContentType::Main)
+        .and_then(|t| t.content.syllables_mut().next())
+    {
+        syllable.text = head;
+        syllable.end_ms = split_ms;
+    }
+
+    lines.insert(index + 1, new_line);
+    true
+}
+
+/// 在总字数超过上限时选择拆分点，返回前半部分包含的单元数。
+///
+/// 在不超过上限的范围内，依次尝试最靠后的标点、空格，且前半部分不少于上限的三分之一；
+/// 都没有时在上限处硬拆分。
+fn find_break(units: &[(usize, BreakQuality)], max_chars: usize) -> Option<usize> {
+    let total: usize = units.iter().map(|(len, _)| len).sum();
+    if total <= max_chars || units.len() < 2 {
+        return None;
+    }
+
+    let mut candidates = Vec::new();
+    let mut head_len = 0;
+    for (count, &(len, quality)) in units[..units.len() - 1].iter().enumerate() {
+        head_len += len;
+        if head_len > max_chars {
+            break;
+        }
+        candidates.push((count + 1, head_len, quality));
+    }
+
+    let min_head = max_chars.div_ceil(3);
+    [BreakQuality::Punctuation, BreakQuality::Space]
+        .into_iter()
+        .find_map(|wanted| {
+            candidates
+                .iter()
+                .rev()
+                .find(|&&(_, len, quality)| quality == wanted && len >= min_head)
+        })
+        .or_else(|| candidates.last())
+        .map(|&(count, _, _)| count)
+        .or(Some(1))
+}
+
+const fn is_break_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        ',' | '.' | '!' | '?' | ';' | ':' | '，' | '。' | '、' | '！' | '？' | '；' | '：' | '…'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{spaced_syl, syl, text_line, timed_line};
+
+    #[test]
+    fn test_line_timed_split_at_punctuation() {
+        let mut lines = vec![text_line(0, 1000, "春风十里，不如你一笑倾城")];
+        let added = wrap_long_lines(&mut lines, &LineWrapOptions { max_chars: 8 });
+
+        assert_eq!(added, 1);
+        assert_eq!(lines[0].main_text().as_deref(), Some("春风十里，"));
+        assert_eq!(lines[1].main_text().as_deref(), Some("不如你一笑倾城"));
+        assert_eq!((lines[0].start_ms, lines[0].end_ms), (0, 416));
+        assert_eq!((lines[1].start_ms, lines[1].end_ms), (416, 1000));
+    }
+
+    #[test]
+    fn test_word_timed_split_prefers_space() {
+        let mut lines = vec![timed_line(vec![
+            spaced_syl("I", 0, 100),
+            spaced_syl("want", 100, 200),
+            spaced_syl("to", 200, 300),
+            spaced_syl("hold", 300, 400),
+            spaced_syl("your", 400, 500),
+            syl("hand", 500, 600),
+        ])];
+        let added = wrap_long_lines(&mut lines, &LineWrapOptions { max_chars: 14 });
+
+        assert_eq!(added, 1);
+        assert_eq!(lines[0].main_text().as_deref(), Some("I want to"));
+        assert_eq!(lines[1].main_text().as_deref(), Some("hold your hand"));
+        assert_eq!(lines[1].start_ms, 300);
+    }
+
+    #[test]
+    fn test_very_long_line_is_split_repeatedly() {
+        let mut lines = vec![text_line(
+            0,
+            3000,
+            "一二三四五六七八九十一二三四五六七八九十",
+        )];
+        let added = wrap_long_lines(&mut lines, &LineWrapOptions { max_chars: 8 });
+
+        assert_eq!(added, 2);
+        assert!(
+            lines
+                .iter()
+                .all(|l| l.main_text().unwrap().chars().count() <= 8)
+        );
+        assert_eq!(lines[2].end_ms, 3000);
+    }
+
+    #[test]
+    fn test_short_lines_are_untouched() {
+        let mut lines = vec![text_line(0, 1000, "短句")];
+        assert_eq!(
+            wrap_long_lines(&mut lines, &LineWrapOptions { max_chars: 8 }),
+            0
+        );
+        assert_eq!(lines.len(), 1);
+    }
+}
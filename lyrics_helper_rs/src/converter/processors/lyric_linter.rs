@@ -0,0 +1,297 @@
+//! 歌词有效性检查器。
+//!
+//! 对已解析的歌词行运行一组规则，找出时间轴重叠、音节时长异常、
+//! 空行过多、翻译缺失等常见问题。
+
+use lyrics_helper_core::{LyricLine, LyricTrack};
+
+/// 检查问题的严重程度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintSeverity {
+    /// 提示，可能是有意为之，但值得检查。
+    Hint,
+    /// 错误，几乎可以肯定是数据问题。
+    Error,
+}
+
+/// 检查器发现的一个问题。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// 严重程度。
+    pub severity: LintSeverity,
+    /// 问题所在的行索引（从 0 开始）。对整体性问题为 `None`。
+    pub line_index: Option<usize>,
+    /// 问题描述。
+    pub message: String,
+}
+
+/// 检查器的阈值配置。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LintOptions {
+    /// 逐字音节时长超过此值（毫秒）视为过长。
+    pub max_syllable_duration_ms: u64,
+    /// 逐字音节时长低于此值（毫秒）视为过短。
+    pub min_syllable_duration_ms: u64,
+    /// 空行占比超过此值视为过高。
+    pub max_empty_line_ratio: f64,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            max_syllable_duration_ms: 10_000,
+            min_syllable_duration_ms: 20,
+            max_empty_line_ratio: 0.3,
+        }
+    }
+}
+
+/// 对歌词行运行所有检查规则。
+///
+/// 返回的问题按行索引排序，整体性问题排在最前面。
+#[must_use]
+pub fn lint_lyrics(lines: &[LyricLine], options: &LintOptions) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    check_empty_line_ratio(lines, options, &mut issues);
+    check_translation_count(lines, &mut issues);
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.start_ms > line.end_ms {
+            issues.push(LintIssue {
+                severity: LintSeverity::Error,
+                line_index: Some(index),
+                message: format!(
+                    "行的开始时间 ({}ms) 晚于结束时间 ({}ms)",
+                    line.start_ms, line.end_ms
+                ),
+            });
+        }
+
+        for track in &line.tracks {
+            check_syllables(index, &track.content, options, &mut issues);
+        }
+    }
+
+    check_line_overlaps(lines, &mut issues);
+
+    issues.sort_by_key(|issue| issue.line_index.map_or(0, |i| i + 1));
+    issues
+}
+
+/// 检查同一演唱者的相邻行之间是否存在时间重叠。
+///
+/// 不同演唱者（对唱）的行重叠是正常的，不会被报告。
+#[allow(clippy::suspicious_operation_groupings)]
+fn check_line_overlaps(lines: &[LyricLine], issues: &mut Vec<LintIssue>) {
+    for (index, pair) in lines.windows(2).enumerate() {
+        let (current, next) = (&pair[0], &pair[1]);
+        if current.agent == next.agent && current.end_ms > next.start_ms {
+            issues.push(LintIssue {
+                severity: LintSeverity::Error,
+                line_index: Some(index),
+                message: format!(
+                    "与下一行时间重叠 {}ms (结束于 {}ms，下一行开始于 {}ms)",
+                    current.end_ms - next.start_ms,
+                    current.end_ms,
+                    next.start_ms
+                ),
+            });
+        }
+    }
+}
+
+/// 检查逐字轨道中的音节时长与顺序。
+fn check_syllables(
+    line_index: usize,
+    track: &LyricTrack,
+    options: &LintOptions,
+    issues: &mut Vec<LintIssue>,
+) {
+    if !track.is_timed() {
+        return;
+    }
+
+    let mut previous_end: Option<u64> = None;
+    for syllable in track.syllables() {
+        let text = syllable.text.trim();
+
+        if syllable.start_ms > syllable.end_ms {
+            issues.push(LintIssue {
+                severity: LintSeverity::Error,
+                line_index: Some(line_index),
+                message: format!("音节 '{text}' 的开始时间晚于结束时间"),
+            });
+        } else if !text.is_empty() {
+            let duration = syllable.duration();
+            if duration > options.max_syllable_duration_ms {
+                issues.push(LintIssue {
+                    severity: LintSeverity::Hint,
+                    line_index: Some(line_index),
+                    message: format!("音节 '{text}' 过长 ({duration}ms)"),
+                });
+            } else if duration < options.min_syllable_duration_ms {
+                issues.push(LintIssue {
+                    severity: LintSeverity::Hint,
+                    line_index: Some(line_index),
+                    message: format!("音节 '{text}' 过短 ({duration}ms)"),
+                });
+            }
+        }
+
+        if let Some(previous_end) = previous_end
+            && syllable.start_ms < previous_end
+        {
+            issues.push(LintIssue {
+                severity: LintSeverity::Error,
+                line_index: Some(line_index),
+                message: format!(
+                    "音节 '{text}' 与前一音节重叠 {}ms",
+                    previous_end - syllable.start_ms
+                ),
+            });
+        }
+        previous_end = Some(syllable.end_ms);
+    }
+}
+
+/// 检查主歌词为空的行所占比例。
+fn check_empty_line_ratio(lines: &[LyricLine], options: &LintOptions, issues: &mut Vec<LintIssue>) {
+    if lines.is_empty() {
+        return;
+    }
+
+    let empty_count = lines.iter().filter(|line| is_main_empty(line)).count();
+    let ratio = empty_count as f64 / lines.len() as f64;
+    if ratio > options.max_empty_line_ratio {
+        issues.push(LintIssue {
+            severity: LintSeverity::Hint,
+            line_index: None,
+            message: format!(
+                "空行比例过高: {empty_count}/{} ({:.0}%)",
+                lines.len(),
+                ratio * 100.0
+            ),
+        });
+    }
+}
+
+/// 检查翻译行数与主歌词行数是否一致。
+///
+/// 只有至少一行带有翻译时才会检查，并定位到第一行缺少翻译的歌词。
+fn check_translation_count(lines: &[LyricLine], issues: &mut Vec<LintIssue>) {
+    let main_lines: Vec<(usize, &LyricLine)> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !is_main_empty(line))
+        .collect();
+    let translated_count = main_lines
+        .iter()
+        .filter(|(_, line)| has_translation(line))
+        .count();
+
+    if translated_count == 0 || translated_count == main_lines.len() {
+        return;
+    }
+
+    let first_missing = main_lines
+        .iter()
+        .find(|(_, line)| !has_translation(line))
+        .map(|(index, _)| *index);
+
+    issues.push(LintIssue {
+        severity: LintSeverity::Hint,
+        line_index: first_missing,
+        message: format!(
+            "翻译行数 ({translated_count}) 与主歌词行数 ({}) 不符",
+            main_lines.len()
+        ),
+    });
+}
+
+fn is_main_empty(line: &LyricLine) -> bool {
+    line.main_text().is_none_or(|text| text.trim().is_empty())
+}
+
+fn has_translation(line: &LyricLine) -> bool {
+    line.main_tracks()
+        .flat_map(|track| &track.translations)
+        .any(|translation| !translation.text().trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{syl, text_line, timed_line};
+    use lyrics_helper_core::ContentType;
+
+    #[test]
+    fn test_clean_lyrics_have_no_issues() {
+        let lines = vec![
+            timed_line(vec![syl("a", 0, 500), syl("b", 500, 1000)]),
+            timed_line(vec![syl("c", 1000, 1500), syl("d", 1500, 2000)]),
+        ];
+        assert!(lint_lyrics(&lines, &LintOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_detects_line_overlap() {
+        let lines = vec![text_line(0, 2000, "a"), text_line(1500, 3000, "b")];
+        let issues = lint_lyrics(&lines, &LintOptions::default());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, LintSeverity::Error);
+        assert_eq!(issues[0].line_index, Some(0));
+    }
+
+    #[test]
+    fn test_overlap_between_agents_is_allowed() {
+        let mut first = text_line(0, 2000, "a");
+        first.agent = Some("v1".to_string());
+        let mut second = text_line(1500, 3000, "b");
+        second.agent = Some("v2".to_string());
+        assert!(lint_lyrics(&[first, second], &LintOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_detects_abnormal_syllable_durations() {
+        let lines = vec![timed_line(vec![
+            syl("long", 0, 15_000),
+            syl("short", 15_000, 15_005),
+            syl("ok", 15_005, 15_500),
+        ])];
+        let issues = lint_lyrics(&lines, &LintOptions::default());
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|i| i.severity == LintSeverity::Hint));
+    }
+
+    #[test]
+    fn test_detects_syllable_overlap() {
+        let lines = vec![timed_line(vec![syl("a", 0, 600), syl("b", 500, 1000)])];
+        let issues = lint_lyrics(&lines, &LintOptions::default());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, LintSeverity::Error);
+    }
+
+    #[test]
+    fn test_detects_high_empty_line_ratio() {
+        let lines = vec![
+            text_line(0, 1000, "a"),
+            text_line(1000, 2000, ""),
+            text_line(2000, 3000, ""),
+        ];
+        let issues = lint_lyrics(&lines, &LintOptions::default());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line_index, None);
+    }
+
+    #[test]
+    fn test_detects_translation_count_mismatch() {
+        let mut first = text_line(0, 1000, "a");
+        first.add_translation(ContentType::Main, "甲", None);
+        let second = text_line(1000, 2000, "b");
+
+        let issues = lint_lyrics(&[first, second], &LintOptions::default());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line_index, Some(1));
+    }
+}
@@ -0,0 +1,262 @@
+//! 歌词统计。
+//!
+//! 统计主歌词的行数、音节数、音节密度、最长/最短音节和翻译覆盖率，
+//! 并生成可读的文本报告，用于歌词分析。
+
+use std::fmt::Write;
+
+use lyrics_helper_core::{LyricLine, LyricTrack};
+
+/// 一个音节的统计信息。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyllableStat {
+    /// 音节所在的行索引（从 0 开始）。
+    pub line_index: usize,
+    pub text: String,
+    pub duration_ms: u64,
+}
+
+/// 歌词的统计结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LyricStats {
+    /// 总行数，包括间奏和空行。
+    pub total_lines: usize,
+    /// 含有主歌词内容的行数。
+    pub lyric_lines: usize,
+    /// 主歌词的音节数。
+    pub syllable_count: usize,
+    /// 背景人声的音节数。
+    pub background_syllable_count: usize,
+    /// 所有歌词行的演唱总时长（毫秒）。
+    pub sung_duration_ms: u64,
+    /// 最长的逐字音节。歌词不是逐字时为 `None`。
+    pub longest_syllable: Option<SyllableStat>,
+    /// 最短的逐字音节。歌词不是逐字时为 `None`。
+    pub shortest_syllable: Option<SyllableStat>,
+    /// 带有翻译的歌词行数。
+    pub translated_lines: usize,
+    /// 带有罗马音的歌词行数。
+    pub romanized_lines: usize,
+}
+
+impl LyricStats {
+    /// 平均每行的音节数。
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_syllables_per_line(&self) -> f64 {
+        if self.lyric_lines == 0 {
+            return 0.0;
+        }
+        self.syllable_count as f64 / self.lyric_lines as f64
+    }
+
+    /// 按演唱时长计算的每秒音节数。
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn syllables_per_second(&self) -> f64 {
+        if self.sung_duration_ms == 0 {
+            return 0.0;
+        }
+        self.syllable_count as f64 / (self.sung_duration_ms as f64 / 1000.0)
+    }
+
+    /// 翻译覆盖率（0.0 - 1.0）。
+    #[must_use]
+    pub fn translation_coverage(&self) -> f64 {
+        coverage(self.translated_lines, self.lyric_lines)
+    }
+
+    /// 罗马音覆盖率（0.0 - 1.0）。
+    #[must_use]
+    pub fn romanization_coverage(&self) -> f64 {
+        coverage(self.romanized_lines, self.lyric_lines)
+    }
+
+    /// 生成可读的文本报告。
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_report(&self) -> String {
+        let mut report = String::from("歌词统计报告\n");
+        let _ = writeln!(
+            report,
+            "总行数: {}（歌词行 {}，间奏或空行 {}）",
+            self.total_lines,
+            self.lyric_lines,
+            self.total_lines - self.lyric_lines
+        );
+        let _ = writeln!(
+            report,
+            "主歌词音节数: {}（背景人声音节 {}）",
+            self.syllable_count, self.background_syllable_count
+        );
+        let _ = writeln!(
+            report,
+            "平均每行音节: {:.2}",
+            self.average_syllables_per_line()
+        );
+        let _ = writeln!(
+            report,
+            "音节密度: {:.2} 个/秒（演唱时长 {:.1} 秒）",
+            self.syllables_per_second(),
+            self.sung_duration_ms as f64 / 1000.0
+        );
+
+        match (&self.longest_syllable, &self.shortest_syllable) {
+            (Some(longest), Some(shortest)) => {
+                for (label, stat) in [("最长音节", longest), ("最短音节", shortest)] {
+                    let _ = writeln!(
+                        report,
+                        "{label}: 「{}」 {}ms（第 {} 行）",
+                        stat.text,
+                        stat.duration_ms,
+                        stat.line_index + 1
+                    );
+                }
+            }
+            _ => report.push_str("最长/最短音节: 歌词不是逐字的，无法统计\n"),
+        }
+
+        for (label, covered, ratio) in [
+            ("翻译", self.translated_lines, self.translation_coverage()),
+            ("罗马音", self.romanized_lines, self.romanization_coverage()),
+        ] {
+            let _ = writeln!(
+                report,
+                "{label}覆盖率: {:.1}%（{covered}/{} 行）",
+                ratio * 100.0,
+                self.lyric_lines
+            );
+        }
+        report
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn coverage(covered: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    covered as f64 / total as f64
+}
+
+/// 统计歌词行。
+///
+/// 只有逐字歌词才统计最长和最短音节；时长相同时取先出现的音节。
+#[must_use]
+pub fn compute_stats(lines: &[LyricLine]) -> LyricStats {
+    let mut stats = LyricStats {
+        total_lines: lines.len(),
+        ..Default::default()
+    };
+
+    for (line_index, line) in lines.iter().enumerate() {
+        let main_tracks: Vec<_> = line
+            .main_tracks()
+            .filter(|track| !track.content.is_empty())
+            .collect();
+        stats.background_syllable_count += line
+            .background_tracks()
+            .map(|track| track.content.syllables().count())
+            .sum::<usize>();
+        if main_tracks.is_empty() {
+            continue;
+        }
+
+        stats.lyric_lines += 1;
+        stats.sung_duration_ms += line.end_ms.saturating_sub(line.start_ms);
+        if main_tracks
+            .iter()
+            .any(|track| track.translations.iter().any(|t| !t.is_empty()))
+        {
+            stats.translated_lines += 1;
+        }
+        if main_tracks
+            .iter()
+            .any(|track| track.romanizations.iter().any(|t| !t.is_empty()))
+        {
+            stats.romanized_lines += 1;
+        }
+
+        for track in main_tracks {
+            stats.syllable_count += track.content.syllables().count();
+            if track.content.is_timed() {
+                update_extremes(&mut stats, line_index, &track.content);
+            }
+        }
+    }
+
+    stats
+}
+
+fn update_extremes(stats: &mut LyricStats, line_index: usize, track: &LyricTrack) {
+    for syllable in track.syllables() {
+        let duration_ms = syllable.duration();
+        if duration_ms == 0 {
+            continue;
+        }
+        let stat = || SyllableStat {
+            line_index,
+            text: syllable.text.clone(),
+            duration_ms,
+        };
+        if stats
+            .longest_syllable
+            .as_ref()
+            .is_none_or(|longest| duration_ms > longest.duration_ms)
+        {
+            stats.longest_syllable = Some(stat());
+        }
+        if stats
+            .shortest_syllable
+            .as_ref()
+            .is_none_or(|shortest| duration_ms < shortest.duration_ms)
+        {
+            stats.shortest_syllable = Some(stat());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{syl, timed_line};
+    use lyrics_helper_core::ContentType;
+
+    #[test]
+    fn test_compute_stats_counts_syllables_and_coverage() {
+        let mut translated = timed_line(vec![syl("a", 0, 500), syl("b", 500, 1000)]);
+        translated.add_translation(ContentType::Main, "甲", None);
+        let lines = vec![
+            translated,
+            LyricLine::new_interlude(1000, 3000),
+            timed_line(vec![syl("c", 3000, 3100), syl("d", 3100, 4000)]),
+        ];
+
+        let stats = compute_stats(&lines);
+
+        assert_eq!(stats.total_lines, 3);
+        assert_eq!(stats.lyric_lines, 2);
+        assert_eq!(stats.syllable_count, 4);
+        assert_eq!(stats.sung_duration_ms, 2000);
+        assert!((stats.average_syllables_per_line() - 2.0).abs() < f64::EPSILON);
+        assert!((stats.syllables_per_second() - 2.0).abs() < f64::EPSILON);
+        assert!((stats.translation_coverage() - 0.5).abs() < f64::EPSILON);
+
+        let longest = stats.longest_syllable.unwrap();
+        assert_eq!((longest.text.as_str(), longest.line_index), ("d", 2));
+        let shortest = stats.shortest_syllable.unwrap();
+        assert_eq!((shortest.text.as_str(), shortest.duration_ms), ("c", 100));
+    }
+
+    #[test]
+    fn test_line_timed_lyrics_have_no_syllable_extremes() {
+        let mut line = LyricLine::new(0, 2000);
+        line.add_content_track(ContentType::Main, "整行歌词");
+
+        let stats = compute_stats(&[line]);
+
+        assert_eq!(stats.lyric_lines, 1);
+        assert!(stats.longest_syllable.is_none());
+        assert!(stats.to_report().contains("无法统计"));
+    }
+}
@@ -10,7 +10,7 @@ use regex::{Regex, RegexBuilder};
 use tracing::{debug, trace, warn};
 
 use crate::converter::LyricLine;
-use lyrics_helper_core::{MetadataStripperFlags, MetadataStripperOptions};
+use lyrics_helper_core::{MetadataStripperFlags, MetadataStripperOptions, MetadataStripperTargets};
 
 type RegexCacheKey = (String, bool); // (pattern, case_sensitive)
 type RegexCacheMap = HashMap<RegexCacheKey, Regex>;
@@ -83,6 +83,8 @@ struct StrippingRules<'a> {
     prepared_keywords: Cow<'a, [String]>,
     keyword_case_sensitive: bool,
     compiled_regexes: Vec<Regex>,
+    /// 启用短行启发式规则时，被视为元数据的行的最大字符数
+    short_line_max_chars: Option<usize>,
 }
 
 impl<'a> StrippingRules<'a> {
@@ -120,10 +122,16 @@ impl<'a> StrippingRules<'a> {
             Cow::Owned(options.keywords.iter().map(|k| k.to_lowercase()).collect())
         };
 
+        let short_line_max_chars = options
+            .flags
+            .contains(MetadataStripperFlags::ENABLE_SHORT_LINE_HEURISTIC)
+            .then_some(options.short_line_max_chars);
+
         Self {
             prepared_keywords,
             keyword_case_sensitive,
             compiled_regexes,
+            short_line_max_chars,
         }
     }
 
@@ -132,29 +140,32 @@ impl<'a> StrippingRules<'a> {
     }
 }
 
-fn line_matches_rules(line_to_check: &str, rules: &StrippingRules) -> bool {
-    let text_for_keyword_check = {
-        let mut text = line_to_check.trim();
-
-        // 处理意外包含了 LRC 标签的情况
-        // 这在我们的数据模型中不应该发生
-        if text.starts_with('[') && text.ends_with(']') {
-            text = &text[1..text.len() - 1];
-        } else if text.starts_with('[') {
-            if let Some(end_bracket_idx) = text.find(']') {
-                text = text[end_bracket_idx + 1..].trim_start();
-            }
-        // 某些奇怪的歌词可能会在前面加上背景人声或者演唱者标记之类的东西
-        // 通常不太可能又有这些东西又是元数据行
-        } else if text.starts_with('(') && text.ends_with(')') {
-            text = &text[1..text.len() - 1];
-        } else if text.starts_with('(')
-            && let Some(end_paren_idx) = text.find(')')
-        {
-            text = text[end_paren_idx + 1..].trim_start();
+/// 去掉行首可能存在的 LRC 标签或括号标记，得到用于关键词匹配的文本。
+fn text_for_keyword_check(line_to_check: &str) -> &str {
+    let mut text = line_to_check.trim();
+
+    // 处理意外包含了 LRC 标签的情况
+    // 这在我们的数据模型中不应该发生
+    if text.starts_with('[') && text.ends_with(']') {
+        text = &text[1..text.len() - 1];
+    } else if text.starts_with('[') {
+        if let Some(end_bracket_idx) = text.find(']') {
+            text = text[end_bracket_idx + 1..].trim_start();
         }
-        text
-    };
+    // 某些奇怪的歌词可能会在前面加上背景人声或者演唱者标记之类的东西
+    // 通常不太可能又有这些东西又是元数据行
+    } else if text.starts_with('(') && text.ends_with(')') {
+        text = &text[1..text.len() - 1];
+    } else if text.starts_with('(')
+        && let Some(end_paren_idx) = text.find(')')
+    {
+        text = text[end_paren_idx + 1..].trim_start();
+    }
+    text
+}
+
+fn line_matches_rules(line_to_check: &str, rules: &StrippingRules) -> bool {
+    let text_for_keyword_check = text_for_keyword_check(line_to_check);
 
     if !rules.prepared_keywords.is_empty() {
         let prepared_line: Cow<str> = if rules.keyword_case_sensitive {
@@ -185,6 +196,32 @@ fn line_matches_rules(line_to_check: &str, rules: &StrippingRules) -> bool {
     false
 }
 
+/// 判断一行是否像未被关键词匹配到的署名行：足够短、没有翻译，也没有句读等典型的歌词特征，
+/// 并且像“职务 人名”那样由分隔符分成前后两部分。
+fn looks_like_credit_line(line: &LyricLine, max_chars: usize) -> bool {
+    const LYRIC_PUNCTUATION: &[char] = &['，', '。', '！', '？', '!', '?', '…', '~', '～'];
+    const CREDIT_SEPARATORS: &[char] = &[':', '：', ' ', '\u{3000}', '/', '|', '-'];
+
+    let text = get_text(line);
+    let text = text.trim();
+    let char_count = text.chars().filter(|c| !c.is_whitespace()).count();
+    let has_translation = line
+        .main_track()
+        .is_some_and(|track| track.translations.iter().any(|t| !t.is_empty()));
+    let has_separator = text
+        .split_once(CREDIT_SEPARATORS)
+        .is_some_and(|(role, name)| !role.trim().is_empty() && !name.trim().is_empty());
+
+    char_count > 0
+        && char_count <= max_chars
+        && !has_translation
+        && !text.contains(LYRIC_PUNCTUATION)
+        && has_separator
+}
+
+/// 启发式规则最多向头部之后延伸的行数。
+const SHORT_LINE_HEURISTIC_MAX_LINES: usize = 2;
+
 fn find_first_lyric_line_index(lines: &[LyricLine], rules: &StrippingRules, limit: usize) -> usize {
     let mut last_matching_header_index: Option<usize> = None;
 
@@ -195,7 +232,24 @@ fn find_first_lyric_line_index(lines: &[LyricLine], rules: &StrippingRules, limi
         }
     }
 
-    last_matching_header_index.map_or(0, |idx| idx + 1)
+    let mut first_lyric_index = last_matching_header_index.map_or(0, |idx| idx + 1);
+
+    // 启发式规则只向后延伸紧接着头部的少数几行，避免误删正文中的短歌词。
+    // 没有匹配到头部时不启用，否则开头几句没有标点的短歌词会被当作署名删除
+    if last_matching_header_index.is_some()
+        && let Some(max_chars) = rules.short_line_max_chars
+    {
+        let heuristic_end = limit
+            .min(lines.len())
+            .min(first_lyric_index + SHORT_LINE_HEURISTIC_MAX_LINES);
+        while first_lyric_index < heuristic_end
+            && looks_like_credit_line(&lines[first_lyric_index], max_chars)
+        {
+            first_lyric_index += 1;
+        }
+    }
+
+    first_lyric_index
 }
 
 fn find_last_lyric_line_exclusive_index(
@@ -223,6 +277,142 @@ fn find_last_lyric_line_exclusive_index(
     first_matching_footer_index.unwrap_or(lines.len())
 }
 
+/// 计算翻译元数据所在的头部和尾部区块，返回（头部区块结束，尾部区块开始）的索引。
+///
+/// 头部区块是扫描范围内从第一行开始、连续没有正常翻译的行，遇到第一条不匹配规则的翻译即结束；
+/// 尾部区块从最后一行向前同理。没有翻译的行不会打断区块。
+fn translation_metadata_blocks(
+    lines: &[LyricLine],
+    rules: &StrippingRules,
+    header_limit: usize,
+    footer_limit: usize,
+) -> (usize, usize) {
+    let has_lyric_translation = |line: &LyricLine| {
+        line.tracks
+            .iter()
+            .flat_map(|track| &track.translations)
+            .any(|translation| {
+                let text = translation.text();
+                !text.trim().is_empty() && !line_matches_rules(&text, rules)
+            })
+    };
+
+    let header_limit = header_limit.min(lines.len());
+    let header_end = lines
+        .iter()
+        .take(header_limit)
+        .position(has_lyric_translation)
+        .unwrap_or(header_limit);
+
+    let footer_limit = footer_limit.min(lines.len());
+    let footer_len = lines
+        .iter()
+        .rev()
+        .take(footer_limit)
+        .position(has_lyric_translation)
+        .unwrap_or(footer_limit);
+
+    (header_end, (lines.len() - footer_len).max(header_end))
+}
+
+/// 移除头部和尾部区块内匹配规则的翻译，不影响主歌词行本身。
+///
+/// 翻译与主歌词分开判断：同一行的主歌词是正常歌词时，混入的署名翻译也会被移除。
+/// 与主歌词一样只处理连续的区块，正文中恰好匹配规则的翻译会被保留。
+fn strip_translation_metadata(
+    lines: &mut [LyricLine],
+    rules: &StrippingRules,
+    header_limit: usize,
+    footer_limit: usize,
+) -> usize {
+    let (header_end, footer_start) =
+        translation_metadata_blocks(lines, rules, header_limit, footer_limit);
+    let mut removed_count = 0;
+
+    for (index, line) in lines.iter_mut().enumerate() {
+        if index >= header_end && index < footer_start {
+            continue;
+        }
+        for track in &mut line.tracks {
+            let original_len = track.translations.len();
+            track
+                .translations
+                .retain(|translation| !line_matches_rules(&translation.text(), rules));
+            removed_count += original_len - track.translations.len();
+        }
+    }
+
+    removed_count
+}
+
+/// 未提供自定义规则时使用默认规则。
+fn effective_options(options: &MetadataStripperOptions) -> Cow<'_, MetadataStripperOptions> {
+    if options.keywords.is_empty() && options.regex_patterns.is_empty() {
+        debug!("[MetadataStripper] 未提供自定义规则，加载默认规则。");
+        let mut temp_options = options.clone();
+        temp_options.keywords = default_rules::keywords();
+        temp_options.regex_patterns = default_rules::regex_patterns();
+        Cow::Owned(temp_options)
+    } else {
+        Cow::Borrowed(options)
+    }
+}
+
+/// 从元数据行中提取出的一条候选元数据。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataLineCandidate {
+    /// 元数据行在歌词中的索引。
+    pub line_index: usize,
+    /// 冒号前的键，例如“作词”。
+    pub key: String,
+    /// 冒号后的值。
+    pub value: String,
+}
+
+/// 找出会被清理器识别为元数据的行，并将其中“键: 值”形式的行拆分为候选元数据。
+///
+/// 使用与 [`strip_descriptive_metadata_lines`] 相同的规则和扫描范围，
+/// 但不检查 `ENABLED` 标志，也不会修改歌词。
+#[must_use]
+pub fn extract_metadata_candidates(
+    lines: &[LyricLine],
+    options: &MetadataStripperOptions,
+) -> Vec<MetadataLineCandidate> {
+    let options_to_use = effective_options(options);
+    let rules = StrippingRules::new(&options_to_use);
+    if lines.is_empty() || !rules.has_rules() {
+        return Vec::new();
+    }
+
+    let header_limit = options_to_use.header_scan_limit.calculate(lines.len());
+    let footer_limit = options_to_use.footer_scan_limit.calculate(lines.len());
+    let first_lyric_index = find_first_lyric_line_index(lines, &rules, header_limit);
+    let last_lyric_exclusive_index =
+        find_last_lyric_line_exclusive_index(lines, first_lyric_index, &rules, footer_limit);
+
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index < first_lyric_index || *index >= last_lyric_exclusive_index)
+        .filter_map(|(line_index, line)| {
+            let line_text = get_text(line);
+            if !line_matches_rules(&line_text, &rules) {
+                return None;
+            }
+            let text = text_for_keyword_check(&line_text);
+            let separator_index = text.find([':', '：'])?;
+            let separator_len = text[separator_index..].chars().next()?.len_utf8();
+            let key = text[..separator_index].trim();
+            let value = text[separator_index + separator_len..].trim();
+            (!key.is_empty() && !value.is_empty()).then(|| MetadataLineCandidate {
+                line_index,
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+        })
+        .collect()
+}
+
 /// 从 `LyricLine` 列表中移除元数据行。
 pub fn strip_descriptive_metadata_lines(
     lines: &mut Vec<LyricLine>,
@@ -233,16 +423,7 @@ pub fn strip_descriptive_metadata_lines(
         return;
     }
 
-    let options_to_use: Cow<MetadataStripperOptions> =
-        if options.keywords.is_empty() && options.regex_patterns.is_empty() {
-            debug!("[MetadataStripper] 未提供自定义规则，加载默认规则。");
-            let mut temp_options = options.clone();
-            temp_options.keywords = default_rules::keywords();
-            temp_options.regex_patterns = default_rules::regex_patterns();
-            Cow::Owned(temp_options)
-        } else {
-            Cow::Borrowed(options)
-        };
+    let options_to_use = effective_options(options);
     let rules = StrippingRules::new(&options_to_use);
 
     if lines.is_empty() || !rules.has_rules() {
@@ -254,6 +435,20 @@ pub fn strip_descriptive_metadata_lines(
     let header_limit = options_to_use.header_scan_limit.calculate(original_count);
     let footer_limit = options_to_use.footer_scan_limit.calculate(original_count);
 
+    if options
+        .targets
+        .contains(MetadataStripperTargets::TRANSLATION)
+    {
+        let removed = strip_translation_metadata(lines, &rules, header_limit, footer_limit);
+        if removed > 0 {
+            debug!("[MetadataStripper] 移除了 {} 条元数据翻译。", removed);
+        }
+    }
+
+    if !options.targets.contains(MetadataStripperTargets::MAIN) {
+        return;
+    }
+
     let first_lyric_index = find_first_lyric_line_index(lines, &rules, header_limit);
 
     let last_lyric_exclusive_index =
@@ -280,7 +475,7 @@ mod tests {
     use super::*;
     use lyrics_helper_core::{
         AnnotatedTrack, ContentType, LyricLine, LyricSyllable, LyricTrack, MetadataStripperFlags,
-        MetadataStripperOptions, Word,
+        MetadataStripperOptions, ScanLimitConfig, Word,
     };
 
     fn create_test_lines(texts: &[&str]) -> Vec<LyricLine> {
@@ -431,4 +626,224 @@ mod tests {
         strip_descriptive_metadata_lines(&mut lines, &options);
         assert!(lines.is_empty());
     }
+
+    #[test]
+    fn test_extract_metadata_candidates() {
+        let lines = create_test_lines(&[
+            "作词：某人",
+            "[00:01.00] Composer : Someone",
+            "Lyric 1",
+            "Lyric 2",
+            "Source: Web",
+        ]);
+        let options = MetadataStripperOptions {
+            flags: MetadataStripperFlags::empty(),
+            keywords: vec![
+                "作词".to_string(),
+                "Composer".to_string(),
+                "Source".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let candidates = extract_metadata_candidates(&lines, &options);
+        let pairs: Vec<(usize, &str, &str)> = candidates
+            .iter()
+            .map(|c| (c.line_index, c.key.as_str(), c.value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (0, "作词", "某人"),
+                (1, "Composer", "Someone"),
+                (4, "Source", "Web"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_metadata_candidates_ignores_lyric_body() {
+        let lines = create_test_lines(&["Lyric 1", "Artist: inside lyrics", "Lyric 2", "Lyric 3"]);
+        let options = MetadataStripperOptions {
+            keywords: vec!["Artist".to_string()],
+            header_scan_limit: ScanLimitConfig {
+                ratio: 0.0,
+                min_lines: 1,
+                max_lines: 1,
+            },
+            footer_scan_limit: ScanLimitConfig {
+                ratio: 0.0,
+                min_lines: 1,
+                max_lines: 1,
+            },
+            ..Default::default()
+        };
+
+        assert!(extract_metadata_candidates(&lines, &options).is_empty());
+    }
+
+    fn add_translations(lines: &mut [LyricLine], translations: &[&str]) {
+        for (line, translation) in lines.iter_mut().zip(translations) {
+            line.add_translation(ContentType::Main, *translation, None);
+        }
+    }
+
+    fn translation_texts(lines: &[LyricLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.main_track()
+                    .and_then(|track| track.translations.first())
+                    .map(LyricTrack::text)
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_strip_translation_only() {
+        let mut lines = create_test_lines(&["Lyric 1", "Lyric 2", "Lyric 3"]);
+        add_translations(&mut lines, &["翻译：某人", "歌词 2", "歌词 3"]);
+        let options = MetadataStripperOptions {
+            flags: MetadataStripperFlags::ENABLED,
+            targets: MetadataStripperTargets::TRANSLATION,
+            keywords: vec!["翻译".to_string()],
+            ..Default::default()
+        };
+
+        strip_descriptive_metadata_lines(&mut lines, &options);
+        assert_eq!(
+            lines_to_texts(&lines),
+            vec!["Lyric 1", "Lyric 2", "Lyric 3"]
+        );
+        assert_eq!(translation_texts(&lines), vec!["", "歌词 2", "歌词 3"]);
+    }
+
+    #[test]
+    fn test_strip_main_and_translation_independently() {
+        let mut lines = create_test_lines(&["Artist: A", "Lyric 1", "Lyric 2"]);
+        add_translations(&mut lines, &["", "译者：某人", "歌词 2"]);
+        let options = MetadataStripperOptions {
+            flags: MetadataStripperFlags::ENABLED,
+            targets: MetadataStripperTargets::MAIN | MetadataStripperTargets::TRANSLATION,
+            keywords: vec!["Artist".to_string(), "译者".to_string()],
+            ..Default::default()
+        };
+
+        strip_descriptive_metadata_lines(&mut lines, &options);
+        assert_eq!(lines_to_texts(&lines), vec!["Lyric 1", "Lyric 2"]);
+        assert_eq!(translation_texts(&lines), vec!["", "歌词 2"]);
+    }
+
+    #[test]
+    fn test_short_line_heuristic_is_opt_in() {
+        let texts = [
+            "Singer Name",
+            "作词：某人",
+            "Composer X",
+            "这是第一句歌词，很长很长",
+            "短句",
+        ];
+        let options = MetadataStripperOptions {
+            flags: MetadataStripperFlags::ENABLED,
+            keywords: vec!["作词".to_string()],
+            ..Default::default()
+        };
+
+        let mut lines = create_test_lines(&texts);
+        strip_descriptive_metadata_lines(&mut lines, &options);
+        assert_eq!(
+            lines_to_texts(&lines),
+            vec!["Composer X", "这是第一句歌词，很长很长", "短句"]
+        );
+
+        let mut lines = create_test_lines(&texts);
+        strip_descriptive_metadata_lines(
+            &mut lines,
+            &MetadataStripperOptions {
+                flags: MetadataStripperFlags::ENABLED
+                    | MetadataStripperFlags::ENABLE_SHORT_LINE_HEURISTIC,
+                ..options
+            },
+        );
+        assert_eq!(
+            lines_to_texts(&lines),
+            vec!["这是第一句歌词，很长很长", "短句"]
+        );
+    }
+
+    #[test]
+    fn test_short_line_heuristic_needs_header() {
+        let texts = ["我走过", "你的城", "这是后面的歌词，很长很长"];
+        let mut lines = create_test_lines(&texts);
+        strip_descriptive_metadata_lines(
+            &mut lines,
+            &MetadataStripperOptions {
+                flags: MetadataStripperFlags::ENABLED
+                    | MetadataStripperFlags::ENABLE_SHORT_LINE_HEURISTIC,
+                keywords: vec!["作词".to_string()],
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(lines_to_texts(&lines), texts);
+    }
+
+    #[test]
+    fn test_short_line_heuristic_keeps_short_lyrics_after_header() {
+        let texts = ["作词：某人", "我走过", "你的城", "路过你的窗"];
+        let mut lines = create_test_lines(&texts);
+        strip_descriptive_metadata_lines(
+            &mut lines,
+            &MetadataStripperOptions {
+                flags: MetadataStripperFlags::ENABLED
+                    | MetadataStripperFlags::ENABLE_SHORT_LINE_HEURISTIC,
+                keywords: vec!["作词".to_string()],
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            lines_to_texts(&lines),
+            vec!["我走过", "你的城", "路过你的窗"]
+        );
+    }
+
+    #[test]
+    fn test_short_line_heuristic_only_looks_a_few_lines_ahead() {
+        let texts = ["作词：某人", "编曲 甲", "混音 乙", "母带 丙", "第一句"];
+        let mut lines = create_test_lines(&texts);
+        strip_descriptive_metadata_lines(
+            &mut lines,
+            &MetadataStripperOptions {
+                flags: MetadataStripperFlags::ENABLED
+                    | MetadataStripperFlags::ENABLE_SHORT_LINE_HEURISTIC,
+                keywords: vec!["作词".to_string()],
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(lines_to_texts(&lines), vec!["母带 丙", "第一句"]);
+    }
+
+    #[test]
+    fn test_strip_translation_keeps_matching_lines_inside_body() {
+        let mut lines = create_test_lines(&["Lyric 1", "Lyric 2", "Lyric 3", "Lyric 4"]);
+        add_translations(
+            &mut lines,
+            &["作词：某人", "第一句", "作词：是我写给你的歌", "最后一句"],
+        );
+        let options = MetadataStripperOptions {
+            flags: MetadataStripperFlags::ENABLED,
+            targets: MetadataStripperTargets::TRANSLATION,
+            keywords: vec!["作词".to_string()],
+            ..Default::default()
+        };
+
+        strip_descriptive_metadata_lines(&mut lines, &options);
+        assert_eq!(
+            translation_texts(&lines),
+            vec!["", "第一句", "作词：是我写给你的歌", "最后一句"]
+        );
+    }
 }
@@ -3,5 +3,14 @@
 pub mod agent_recognizer;
 pub mod batch_processor;
 pub mod chinese_conversion_processor;
+pub mod feature_loss;
+pub mod line_editor;
+pub mod line_wrapper;
+pub mod lyric_linter;
+pub mod lyric_stats;
 pub mod metadata_stripper;
+pub mod punctuation_normalizer;
+pub mod regex_replacer;
+pub mod repeated_lines;
+pub mod syllable_overlap_fixer;
 pub mod syllable_smoothing;
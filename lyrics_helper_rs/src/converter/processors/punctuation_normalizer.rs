@@ -0,0 +1,193 @@
+//! 统一主歌词中标点的全角/半角形式。
+//!
+//! 不同来源的歌词经常混用 `,` 与 `，`、`?` 与 `？` 等标点。此处理器按选项把它们统一为
+//! 同一种宽度，并可顺带把全角数字和拉丁字母转为半角。
+//!
+//! 半角的 `.` 和引号在转为全角时含义不明确（省略号、小数点、引号配对等），因此只在
+//! 转为半角的方向上处理 `。`、`、` 等字符。
+
+use lyrics_helper_core::{
+    ContentType, LyricLine, LyricTrack, PunctuationNormalizeOptions, PunctuationWidth,
+};
+
+/// 可以双向转换的 (半角, 全角) 标点对。
+const PUNCTUATION_PAIRS: &[(char, char)] = &[
+    (',', '，'),
+    ('!', '！'),
+    ('?', '？'),
+    (':', '：'),
+    (';', '；'),
+    ('(', '（'),
+    (')', '）'),
+    ('~', '～'),
+];
+
+/// 仅在转为半角时处理的全角字符。
+const HALFWIDTH_ONLY: &[(char, char)] = &[('。', '.'), ('．', '.'), ('、', ','), ('\u{3000}', ' ')];
+
+/// 全角 ASCII 字符与半角之间的码位差。
+const FULLWIDTH_OFFSET: u32 = 0xFEE0;
+
+fn to_halfwidth_ascii(c: char) -> char {
+    char::from_u32(c as u32 - FULLWIDTH_OFFSET).unwrap_or(c)
+}
+
+const fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{4E00}'..='\u{9FFF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{AC00}'..='\u{D7AF}')
+}
+
+fn convert_char(c: char, options: PunctuationNormalizeOptions, to_fullwidth: bool) -> char {
+    if options.halfwidth_digits && ('０'..='９').contains(&c) {
+        return to_halfwidth_ascii(c);
+    }
+    if options.halfwidth_latin && (('Ａ'..='Ｚ').contains(&c) || ('ａ'..='ｚ').contains(&c)) {
+        return to_halfwidth_ascii(c);
+    }
+
+    match options.target {
+        PunctuationWidth::FullWidth if to_fullwidth => PUNCTUATION_PAIRS
+            .iter()
+            .find(|(half, _)| *half == c)
+            .map_or(c, |(_, full)| *full),
+        PunctuationWidth::FullWidth => c,
+        PunctuationWidth::HalfWidth => PUNCTUATION_PAIRS
+            .iter()
+            .find(|(_, full)| *full == c)
+            .map(|(half, _)| *half)
+            .or_else(|| {
+                HALFWIDTH_ONLY
+                    .iter()
+                    .find(|(full, _)| *full == c)
+                    .map(|(_, half)| *half)
+            })
+            .unwrap_or(c),
+    }
+}
+
+fn is_fullwidth_punctuation(c: char) -> bool {
+    PUNCTUATION_PAIRS.iter().any(|(_, full)| *full == c)
+}
+
+/// 转换一段文本。转为全角时会去掉全角标点后紧跟的空格。
+fn convert_text(text: &str, options: PunctuationNormalizeOptions, to_fullwidth: bool) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut previous_converted_to_full = false;
+    for c in text.chars() {
+        if previous_converted_to_full && c == ' ' {
+            continue;
+        }
+        let converted = convert_char(c, options, to_fullwidth);
+        previous_converted_to_full =
+            to_fullwidth && converted != c && is_fullwidth_punctuation(converted);
+        result.push(converted);
+    }
+    result
+}
+
+/// 对所有行的主歌词应用标点规范化。
+///
+/// 翻译和音译不受影响。逐字歌词按音节分别转换，转为全角时若音节以刚转换出的全角标点结尾，
+/// 会同时清除其后的空格标记。
+///
+/// # 返回
+/// 文本发生变化的行数。
+pub fn normalize_punctuation(
+    lines: &mut [LyricLine],
+    options: &PunctuationNormalizeOptions,
+) -> usize {
+    let mut changed_lines = 0;
+    for line in lines {
+        let to_fullwidth = options.target == PunctuationWidth::FullWidth
+            && (!options.fullwidth_only_cjk_lines
+                || line
+                    .main_text()
+                    .is_some_and(|text| text.chars().any(is_cjk)));
+
+        let mut line_changed = false;
+        for track in line
+            .tracks
+            .iter_mut()
+            .filter(|t| t.content_type == ContentType::Main)
+        {
+            line_changed |= normalize_track(&mut track.content, *options, to_fullwidth);
+        }
+        if line_changed {
+            changed_lines += 1;
+        }
+    }
+    changed_lines
+}
+
+/// 对一个轨道应用规范化，返回文本是否发生了变化。
+fn normalize_track(
+    track: &mut LyricTrack,
+    options: PunctuationNormalizeOptions,
+    to_fullwidth: bool,
+) -> bool {
+    let mut changed = false;
+    for syllable in track.syllables_mut() {
+        let converted = convert_text(&syllable.text, options, to_fullwidth);
+        if converted == syllable.text {
+            continue;
+        }
+        if to_fullwidth && converted.ends_with(is_fullwidth_punctuation) {
+            syllable.ends_with_space = false;
+        }
+        syllable.text = converted;
+        changed = true;
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::text_line;
+
+    #[test]
+    fn test_fullwidth_only_touches_cjk_lines() {
+        let mut lines = vec![
+            text_line(0, 1000, "你好, 世界!"),
+            text_line(0, 1000, "Hello, world!"),
+        ];
+        let changed = normalize_punctuation(&mut lines, &PunctuationNormalizeOptions::default());
+
+        assert_eq!(changed, 1);
+        assert_eq!(lines[0].main_text().as_deref(), Some("你好，世界！"));
+        assert_eq!(lines[1].main_text().as_deref(), Some("Hello, world!"));
+    }
+
+    #[test]
+    fn test_halfwidth_with_digits_and_latin() {
+        let mut line = text_line(0, 1000, "ＡＢＣ，１２３。");
+        line.add_translation(ContentType::Main, "不变，", None);
+        let mut lines = vec![line];
+        let options = PunctuationNormalizeOptions {
+            target: PunctuationWidth::HalfWidth,
+            ..Default::default()
+        };
+
+        normalize_punctuation(&mut lines, &options);
+        assert_eq!(lines[0].main_text().as_deref(), Some("ABC,123."));
+        assert_eq!(
+            lines[0].main_track().unwrap().translations[0].text(),
+            "不变，"
+        );
+    }
+
+    #[test]
+    fn test_digits_are_kept_when_disabled() {
+        let mut lines = vec![text_line(0, 1000, "１２３")];
+        let options = PunctuationNormalizeOptions {
+            halfwidth_digits: false,
+            ..Default::default()
+        };
+
+        assert_eq!(normalize_punctuation(&mut lines, &options), 0);
+        assert_eq!(lines[0].main_text().as_deref(), Some("１２３"));
+    }
+}
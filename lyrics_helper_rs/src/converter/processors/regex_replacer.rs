@@ -0,0 +1,176 @@
+//! 按用户规则对主歌词文本进行正则替换。
+//!
+//! 规则按顺序依次作用于每一行的主歌词，适合统一引号、清理多余空格、替换特定词等固定的文本清洗需求。
+
+use lyrics_helper_core::{ContentType, ConvertError, LyricLine, LyricTrack, RegexReplaceOptions};
+use regex::Regex;
+
+/// 对所有行的主歌词依次应用已启用的替换规则。
+///
+/// 逐行歌词（轨道只有一个音节）对整行文本进行替换；逐字歌词无法在替换后
+/// 重新分配时间，因此对每个音节的文本分别替换，替换后为空的音节会被移除。
+///
+/// # 返回
+/// 文本发生变化的行数。任何一条启用的规则无法编译时返回错误，且不做任何修改。
+pub fn apply_regex_replacements(
+    lines: &mut [LyricLine],
+    options: &RegexReplaceOptions,
+) -> Result<usize, ConvertError> {
+    let rules = options
+        .rules
+        .iter()
+        .filter(|rule| rule.enabled && !rule.pattern.is_empty())
+        .map(|rule| {
+            Regex::new(&rule.pattern)
+                .map(|regex| (regex, rule.replacement.as_str()))
+                .map_err(ConvertError::new_parse)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if rules.is_empty() {
+        return Ok(0);
+    }
+
+    let replace_all = |text: &str| {
+        rules
+            .iter()
+            .fold(text.to_string(), |acc, (regex, replacement)| {
+                regex.replace_all(&acc, *replacement).into_owned()
+            })
+    };
+
+    let mut changed_lines = 0;
+    for line in lines {
+        let mut line_changed = false;
+        for track in line
+            .tracks
+            .iter_mut()
+            .filter(|t| t.content_type == ContentType::Main)
+        {
+            line_changed |= replace_in_track(&mut track.content, &replace_all);
+        }
+        if line_changed {
+            changed_lines += 1;
+        }
+    }
+
+    Ok(changed_lines)
+}
+
+/// 检查一条规则的查找正则能否编译，用于在界面中提示无效的规则。
+pub fn validate_pattern(pattern: &str) -> Result<(), ConvertError> {
+    Regex::new(pattern)
+        .map(|_| ())
+        .map_err(ConvertError::new_parse)
+}
+
+/// 对一个轨道应用替换，返回文本是否发生了变化。
+fn replace_in_track(track: &mut LyricTrack, replace_all: &impl Fn(&str) -> String) -> bool {
+    let is_line_timed = track.syllables().count() == 1;
+    let mut changed = false;
+
+    for syllable in track.syllables_mut() {
+        let replaced = replace_all(&syllable.text);
+        let replaced = if is_line_timed {
+            replaced.trim().to_string()
+        } else {
+            replaced
+        };
+        if replaced != syllable.text {
+            syllable.text = replaced;
+            changed = true;
+        }
+    }
+
+    if changed && !is_line_timed {
+        for word in &mut track.words {
+            word.syllables.retain(|s| !s.text.is_empty());
+        }
+        track.words.retain(|w| !w.syllables.is_empty());
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{syl, text_line, timed_line};
+    use lyrics_helper_core::RegexReplaceRule;
+
+    fn rule(pattern: &str, replacement: &str) -> RegexReplaceRule {
+        RegexReplaceRule {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rules_are_applied_in_order() {
+        let mut lines = vec![
+            text_line(0, 1000, "“Hello”   world"),
+            text_line(0, 1000, "unchanged"),
+        ];
+        let options = RegexReplaceOptions {
+            rules: vec![
+                rule("[“”]", "\""),
+                rule(r"\s{2,}", " "),
+                rule("world", "there"),
+            ],
+        };
+
+        let changed = apply_regex_replacements(&mut lines, &options).unwrap();
+        assert_eq!(changed, 1);
+        assert_eq!(lines[0].main_text().as_deref(), Some("\"Hello\" there"));
+        assert_eq!(lines[1].main_text().as_deref(), Some("unchanged"));
+    }
+
+    #[test]
+    fn test_disabled_rules_and_translations_are_untouched() {
+        let mut line = text_line(0, 1000, "abc");
+        line.add_translation(ContentType::Main, "abc", None);
+        let mut lines = vec![line];
+        let options = RegexReplaceOptions {
+            rules: vec![
+                RegexReplaceRule {
+                    enabled: false,
+                    ..rule("a", "x")
+                },
+                rule("b", "y"),
+            ],
+        };
+
+        apply_regex_replacements(&mut lines, &options).unwrap();
+        assert_eq!(lines[0].main_text().as_deref(), Some("ayc"));
+        assert_eq!(lines[0].main_track().unwrap().translations[0].text(), "abc");
+    }
+
+    #[test]
+    fn test_word_timed_syllables_are_replaced_individually() {
+        let mut lines = vec![timed_line(vec![
+            syl("la", 0, 100),
+            syl("~", 100, 200),
+            syl("la", 200, 300),
+        ])];
+        let options = RegexReplaceOptions {
+            rules: vec![rule("~", "")],
+        };
+
+        apply_regex_replacements(&mut lines, &options).unwrap();
+        let track = &lines[0].main_track().unwrap().content;
+        assert_eq!(track.syllables().count(), 2);
+        assert_eq!(track.text(), "lala");
+    }
+
+    #[test]
+    fn test_invalid_rule_is_rejected_without_changes() {
+        let mut lines = vec![text_line(0, 1000, "abc")];
+        let options = RegexReplaceOptions {
+            rules: vec![rule("a", "x"), rule("(", "")],
+        };
+
+        assert!(apply_regex_replacements(&mut lines, &options).is_err());
+        assert_eq!(lines[0].main_text().as_deref(), Some("abc"));
+    }
+}
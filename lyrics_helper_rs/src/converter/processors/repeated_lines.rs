@@ -0,0 +1,156 @@
+//! 重复行（如副歌）检测与逐字时间轴复用。
+//!
+//! 找出主歌词文本相同的行组，并支持把其中一行的逐字时间轴按相对行开始时间的偏移
+//! 复制到同组的其他行，免去逐字编辑时重复打轴。
+
+use std::collections::HashMap;
+
+use lyrics_helper_core::{ContentType, LyricLine};
+
+/// 一组主歌词文本相同的行。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepeatedLineGroup {
+    /// 规范化后的主歌词文本
+    pub text: String,
+    /// 组内各行的索引，升序排列
+    pub line_indices: Vec<usize>,
+}
+
+/// 规范化主歌词文本用于比较：去掉首尾空白，并把连续空白合并为一个空格。
+fn normalized_main_text(line: &LyricLine) -> Option<String> {
+    if line.is_interlude() {
+        return None;
+    }
+    let text = line.main_text()?;
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+/// 找出主歌词文本相同（忽略空白差异）的行组。
+///
+/// 只返回至少包含两行的组，按组内第一行的位置排序。间奏标记行和没有主歌词的行不参与比较。
+#[must_use]
+pub fn find_repeated_lines(lines: &[LyricLine]) -> Vec<RepeatedLineGroup> {
+    let mut groups: Vec<RepeatedLineGroup> = Vec::new();
+    let mut group_by_text: HashMap<String, usize> = HashMap::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let Some(text) = normalized_main_text(line) else {
+            continue;
+        };
+        if let Some(&group_index) = group_by_text.get(&text) {
+            groups[group_index].line_indices.push(index);
+        } else {
+            group_by_text.insert(text.clone(), groups.len());
+            groups.push(RepeatedLineGroup {
+                text,
+                line_indices: vec![index],
+            });
+        }
+    }
+
+    groups.retain(|group| group.line_indices.len() > 1);
+    groups
+}
+
+/// 把第 `source` 行主歌词的逐字时间轴复制到 `targets` 中的各行。
+///
+/// 每个目标行保留自己的开始时间，主歌词音节按相对源行开始时间的偏移重新排布，
+/// 行的结束时间也按源行的时长相应调整。目标行的翻译、罗马音和背景人声保持不变。
+/// 与源行主歌词文本不同的目标行以及源行本身会被跳过。
+///
+/// # 返回
+/// 实际更新的行数。源行不存在或没有主歌词时返回 0。
+pub fn copy_syllable_timing(lines: &mut [LyricLine], source: usize, targets: &[usize]) -> usize {
+    let Some(source_line) = lines.get(source) else {
+        return 0;
+    };
+    let Some(source_text) = normalized_main_text(source_line) else {
+        return 0;
+    };
+    let Some(source_track) = source_line.main_track() else {
+        return 0;
+    };
+    let source_content = source_track.content.clone();
+    let source_start_ms = source_line.start_ms;
+    let source_duration_ms = source_line.end_ms.saturating_sub(source_line.start_ms);
+
+    let mut updated = 0;
+    for &target in targets {
+        if target == source {
+            continue;
+        }
+        let Some(line) = lines.get_mut(target) else {
+            continue;
+        };
+        if normalized_main_text(line).as_deref() != Some(source_text.as_str()) {
+            continue;
+        }
+        let target_start_ms = line.start_ms;
+        let Some(track) = line
+            .tracks
+            .iter_mut()
+            .find(|track| track.content_type == ContentType::Main)
+        else {
+            continue;
+        };
+
+        let mut content = source_content.clone();
+        for syllable in content.syllables_mut() {
+            syllable.start_ms = target_start_ms + syllable.start_ms.saturating_sub(source_start_ms);
+            syllable.end_ms = target_start_ms + syllable.end_ms.saturating_sub(source_start_ms);
+        }
+        track.content = content;
+        line.end_ms = target_start_ms + source_duration_ms;
+        updated += 1;
+    }
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{syl, text_line, timed_line};
+
+    #[test]
+    fn test_find_repeated_lines() {
+        let lines = vec![
+            text_line(0, 1000, "la la"),
+            text_line(1000, 2000, "verse"),
+            text_line(2000, 3000, "la  la "),
+            LyricLine::new_interlude(3000, 4000),
+            text_line(4000, 5000, "la la"),
+        ];
+
+        let groups = find_repeated_lines(&lines);
+        assert_eq!(
+            groups,
+            vec![RepeatedLineGroup {
+                text: "la la".to_string(),
+                line_indices: vec![0, 2, 4],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_copy_syllable_timing_by_relative_offset() {
+        let mut lines = vec![
+            timed_line(vec![syl("ab", 1000, 1300), syl("cd", 1300, 2000)]),
+            text_line(5000, 5800, "abcd"),
+            text_line(9000, 9500, "other"),
+        ];
+
+        assert_eq!(copy_syllable_timing(&mut lines, 0, &[0, 1, 2, 7]), 1);
+
+        let copied: Vec<_> = lines[1]
+            .main_track()
+            .unwrap()
+            .content
+            .syllables()
+            .map(|s| (s.text.as_str(), s.start_ms, s.end_ms))
+            .collect();
+        assert_eq!(copied, vec![("ab", 5000, 5300), ("cd", 5300, 6000)]);
+        assert_eq!((lines[1].start_ms, lines[1].end_ms), (5000, 6000));
+        assert_eq!(lines[2].main_text().as_deref(), Some("other"));
+    }
+}
@@ -0,0 +1,183 @@
+//! 修复同一轨道内相邻音节的时间重叠。
+//!
+//! 音节的结束时间晚于下一个音节的开始时间时，播放器会同时高亮两个音节或显示错乱。
+//! 可以把结束时间裁到下一个音节开始，也可以在重叠的一段音节内按原时长的比例重新分配时间。
+//! 主歌词、背景人声以及逐字的翻译和罗马音轨道都会被分别处理。
+
+use lyrics_helper_core::{LyricLine, LyricTrack, OverlapFixMode, SyllableOverlapFixOptions};
+
+/// 返回一行中所有的歌词轨道，包括各内容轨道的翻译和罗马音。
+fn all_tracks(line: &LyricLine) -> impl Iterator<Item = &LyricTrack> {
+    line.tracks.iter().flat_map(|track| {
+        std::iter::once(&track.content)
+            .chain(&track.translations)
+            .chain(&track.romanizations)
+    })
+}
+
+/// 与 [`all_tracks`] 相同，但返回可变引用。
+fn all_tracks_mut(line: &mut LyricLine) -> impl Iterator<Item = &mut LyricTrack> {
+    line.tracks.iter_mut().flat_map(|track| {
+        std::iter::once(&mut track.content)
+            .chain(&mut track.translations)
+            .chain(&mut track.romanizations)
+    })
+}
+
+fn track_timings(track: &LyricTrack) -> Vec<(u64, u64)> {
+    track.syllables().map(|s| (s.start_ms, s.end_ms)).collect()
+}
+
+/// 把结束时间晚于下一个音节开始时间的音节裁短。
+fn trim_overlaps(timings: &mut [(u64, u64)]) {
+    for i in 1..timings.len() {
+        let next_start_ms = timings[i].0;
+        let (start_ms, end_ms) = &mut timings[i - 1];
+        if *end_ms > next_start_ms {
+            *end_ms = next_start_ms.max(*start_ms);
+        }
+    }
+}
+
+/// 在每段相互重叠的连续音节内，按原时长的比例重新分配时间。
+///
+/// 一段的时间范围从第一个音节的开始到其中最晚的结束时间，但不会超过这段之后下一个音节的开始时间。
+fn redistribute_overlaps(timings: &mut [(u64, u64)]) {
+    let mut run_start = 0;
+    while run_start < timings.len() {
+        let mut run_end = run_start;
+        while run_end + 1 < timings.len() && timings[run_end].1 > timings[run_end + 1].0 {
+            run_end += 1;
+        }
+
+        if run_end > run_start {
+            let span_start_ms = timings[run_start].0;
+            let latest_end_ms = timings[run_start..=run_end]
+                .iter()
+                .map(|&(_, end_ms)| end_ms)
+                .max()
+                .unwrap_or(span_start_ms);
+            let span_end_ms = timings
+                .get(run_end + 1)
+                .map_or(latest_end_ms, |&(next_start_ms, _)| {
+                    latest_end_ms.min(next_start_ms)
+                })
+                .max(span_start_ms);
+
+            let weights: Vec<u64> = timings[run_start..=run_end]
+                .iter()
+                .map(|&(start_ms, end_ms)| end_ms.saturating_sub(start_ms).max(1))
+                .collect();
+            let total_weight: u64 = weights.iter().sum();
+            let span_ms = span_end_ms - span_start_ms;
+
+            let mut elapsed_weight = 0;
+            for (offset, weight) in weights.iter().enumerate() {
+                let start_ms = span_start_ms + span_ms * elapsed_weight / total_weight;
+                elapsed_weight += weight;
+                let end_ms = span_start_ms + span_ms * elapsed_weight / total_weight;
+                timings[run_start + offset] = (start_ms, end_ms);
+            }
+        }
+
+        run_start = run_end + 1;
+    }
+}
+
+/// 计算修复后的音节时间，返回修复后的时间和被修改的音节数。
+fn fixed_timings(track: &LyricTrack, mode: OverlapFixMode) -> (Vec<(u64, u64)>, usize) {
+    let original = track_timings(track);
+    let mut fixed = original.clone();
+    match mode {
+        OverlapFixMode::TrimToNextStart => trim_overlaps(&mut fixed),
+        OverlapFixMode::Redistribute => redistribute_overlaps(&mut fixed),
+    }
+    let changed = original
+        .iter()
+        .zip(&fixed)
+        .filter(|(before, after)| before != after)
+        .count();
+    (fixed, changed)
+}
+
+/// 统计修复时将会修改的音节数量，不修改歌词。
+#[must_use]
+pub fn count_fixable_syllables(lines: &[LyricLine], options: &SyllableOverlapFixOptions) -> usize {
+    lines
+        .iter()
+        .flat_map(all_tracks)
+        .map(|track| fixed_timings(track, options.mode).1)
+        .sum()
+}
+
+/// 修复每行各轨道内相邻音节的时间重叠。
+///
+/// # 返回
+/// 被修改的音节数量。
+pub fn fix_syllable_overlaps(
+    lines: &mut [LyricLine],
+    options: &SyllableOverlapFixOptions,
+) -> usize {
+    let mut changed_total = 0;
+    for track in lines.iter_mut().flat_map(all_tracks_mut) {
+        let (fixed, changed) = fixed_timings(track, options.mode);
+        if changed == 0 {
+            continue;
+        }
+        for (syllable, (start_ms, end_ms)) in track.syllables_mut().zip(fixed) {
+            syllable.start_ms = start_ms;
+            syllable.end_ms = end_ms;
+        }
+        changed_total += changed;
+    }
+    changed_total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{syl, timed_line};
+
+    fn main_timings(line: &LyricLine) -> Vec<(u64, u64)> {
+        track_timings(&line.main_track().unwrap().content)
+    }
+
+    #[test]
+    fn test_trim_to_next_start() {
+        let mut lines = vec![timed_line(vec![
+            syl("a", 0, 500),
+            syl("b", 400, 800),
+            syl("c", 800, 1000),
+            syl("d", 900, 1200),
+        ])];
+        let options = SyllableOverlapFixOptions {
+            mode: OverlapFixMode::TrimToNextStart,
+        };
+
+        assert_eq!(count_fixable_syllables(&lines, &options), 2);
+        assert_eq!(fix_syllable_overlaps(&mut lines, &options), 2);
+        assert_eq!(
+            main_timings(&lines[0]),
+            vec![(0, 400), (400, 800), (800, 900), (900, 1200)]
+        );
+        assert_eq!(count_fixable_syllables(&lines, &options), 0);
+    }
+
+    #[test]
+    fn test_redistribute_by_duration() {
+        let mut lines = vec![timed_line(vec![
+            syl("a", 0, 600),
+            syl("b", 300, 900),
+            syl("c", 1000, 1200),
+        ])];
+        let options = SyllableOverlapFixOptions {
+            mode: OverlapFixMode::Redistribute,
+        };
+
+        assert_eq!(fix_syllable_overlaps(&mut lines, &options), 2);
+        assert_eq!(
+            main_timings(&lines[0]),
+            vec![(0, 450), (450, 900), (1000, 1200)]
+        );
+    }
+}
@@ -1,124 +1,354 @@
 //! 模仿 Apple Music 合并 CJK 字符再自动分词以平滑音节的功能，但更加平滑。
 
-use lyrics_helper_core::{ContentType, LyricLine, SyllableSmoothingOptions};
+use lyrics_helper_core::{
+    BackgroundSmoothingMode, ContentType, LyricLine, LyricSyllable, LyricTrack,
+    SyllableSmoothingOptions,
+};
 
 /// 对歌词行应用平滑优化
-#[allow(clippy::cast_possible_truncation)]
-#[allow(clippy::cast_sign_loss)]
-#[allow(clippy::cast_precision_loss)]
+///
+/// 主歌词轨道总是按 `options` 平滑；背景人声轨道按 `options.background_mode` 跳过、
+/// 使用相同参数或使用单独的参数平滑。
 pub fn apply_smoothing(lines: &mut [LyricLine], options: &SyllableSmoothingOptions) {
+    let background_options = background_options(options);
+
+    for line in lines {
+        for annotated_track in &mut line.tracks {
+            let Some(track_options) = track_options(
+                annotated_track.content_type,
+                options,
+                background_options.as_ref(),
+            ) else {
+                continue;
+            };
+            smooth_track(&mut annotated_track.content, track_options);
+        }
+    }
+}
+
+/// 计算 0 到 `max_iterations` 次迭代的每一个平滑结果，用于逐次回退或前进。
+///
+/// 返回值的第 `k` 项与以 `smoothing_iterations = k` 调用 [`apply_smoothing`] 的结果相同，
+/// 第 0 项即原始歌词。所有结果在同一轮迭代中依次记录，不会重复计算。
+#[must_use]
+pub fn smoothing_iteration_states(
+    lines: &[LyricLine],
+    options: &SyllableSmoothingOptions,
+    max_iterations: u32,
+) -> Vec<Vec<LyricLine>> {
+    let mut states = vec![lines.to_vec(); max_iterations as usize + 1];
+    let background_options = background_options(options);
+
+    for (line_index, line) in lines.iter().enumerate() {
+        for (track_index, annotated_track) in line.tracks.iter().enumerate() {
+            let Some(track_options) = track_options(
+                annotated_track.content_type,
+                options,
+                background_options.as_ref(),
+            ) else {
+                continue;
+            };
+            let track_options = SyllableSmoothingOptions {
+                smoothing_iterations: max_iterations,
+                ..*track_options
+            };
+            smooth_groups(
+                &annotated_track.content,
+                &track_options,
+                |iteration, word_index, start_index, durations| {
+                    let syllables = &mut states[iteration as usize][line_index].tracks[track_index]
+                        .content
+                        .words[word_index]
+                        .syllables;
+                    redistribute_group(
+                        &mut syllables[start_index..start_index + durations.len()],
+                        durations,
+                    );
+                },
+            );
+        }
+    }
+    states
+}
+
+/// 根据背景人声的平滑方式得到其平滑参数。不平滑背景人声时返回 `None`。
+const fn background_options(
+    options: &SyllableSmoothingOptions,
+) -> Option<SyllableSmoothingOptions> {
+    match options.background_mode {
+        BackgroundSmoothingMode::Skip => None,
+        BackgroundSmoothingMode::SameAsMain => Some(*options),
+        BackgroundSmoothingMode::Custom => Some(SyllableSmoothingOptions {
+            factor: options.background_factor,
+            duration_threshold_ms: options.background_duration_threshold_ms,
+            gap_threshold_ms: options.background_gap_threshold_ms,
+            ..*options
+        }),
+    }
+}
+
+/// 选出某一类轨道使用的平滑参数。不需要平滑时返回 `None`。
+const fn track_options<'a>(
+    content_type: ContentType,
+    options: &'a SyllableSmoothingOptions,
+    background_options: Option<&'a SyllableSmoothingOptions>,
+) -> Option<&'a SyllableSmoothingOptions> {
+    match content_type {
+        ContentType::Main => Some(options),
+        ContentType::Background => background_options,
+    }
+}
+
+/// 平滑一条轨道中每个词的音节时长。
+fn smooth_track(track: &mut LyricTrack, options: &SyllableSmoothingOptions) {
+    let mut smoothed_groups = Vec::new();
+    smooth_groups(
+        track,
+        options,
+        |iteration, word_index, start_index, durations| {
+            if iteration == options.smoothing_iterations {
+                smoothed_groups.push((word_index, start_index, durations.to_vec()));
+            }
+        },
+    );
+
+    for (word_index, start_index, durations) in smoothed_groups {
+        let syllables = &mut track.words[word_index].syllables;
+        redistribute_group(
+            &mut syllables[start_index..start_index + durations.len()],
+            &durations,
+        );
+    }
+}
+
+/// 对轨道中每个需要平滑的音节组迭代平滑其时长。
+///
+/// 每次迭代后以 (迭代次数, 词索引, 组内首个音节的索引, 当前时长) 调用 `on_iteration`，
+/// 迭代次数从 1 开始。
+#[allow(clippy::cast_precision_loss)]
+fn smooth_groups(
+    track: &LyricTrack,
+    options: &SyllableSmoothingOptions,
+    mut on_iteration: impl FnMut(u32, usize, usize, &[f64]),
+) {
     // 因子必须在 (0, 0.5] 范围内。大于0.5可能导致数值不稳定。
     if options.smoothing_iterations == 0 || !(0.0..=0.5).contains(&options.factor) {
         return;
     }
 
-    for line in lines {
-        if let Some(main_track) = line
-            .tracks
-            .iter_mut()
-            .find(|t| t.content_type == ContentType::Main)
-        {
-            for word in &mut main_track.content.words {
-                if word.syllables.len() < 2 {
-                    continue;
-                }
+    for (word_index, word) in track.words.iter().enumerate() {
+        if word.syllables.len() < 2 {
+            continue;
+        }
+
+        let mut start_index = 0;
+        while start_index < word.syllables.len() {
+            // 确定当前组的结束索引
+            let next_break = word.syllables[start_index..].windows(2).position(|w| {
+                let syl_a = &w[0];
+                let syl_b = &w[1];
+                let duration_a = syl_a.end_ms.saturating_sub(syl_a.start_ms);
+                let duration_b = syl_b.end_ms.saturating_sub(syl_b.start_ms);
+                let gap = syl_b.start_ms.saturating_sub(syl_a.end_ms);
+
+                duration_a.abs_diff(duration_b) > options.duration_threshold_ms
+                    || gap > options.gap_threshold_ms
+            });
+
+            let end_index = next_break.map_or(word.syllables.len() - 1, |break_pos| {
+                start_index + break_pos
+            });
+
+            // 如果有多个音节，就执行平滑处理
+            if end_index > start_index {
+                let group_len = end_index - start_index + 1;
+                let mut durations: Vec<f64> = word.syllables[start_index..=end_index]
+                    .iter()
+                    .map(|s| s.end_ms.saturating_sub(s.start_ms) as f64)
+                    .collect();
+                let mut next_durations = vec![0.0; group_len];
+
+                for iteration in 1..=options.smoothing_iterations {
+                    // 处理第一个元素
+                    let term = options.factor * durations[1];
+                    next_durations[0] = (1.0 - options.factor).mul_add(durations[0], term);
 
-                let mut start_index = 0;
-                while start_index < word.syllables.len() {
-                    // 确定当前组的结束索引
-                    let next_break = word.syllables[start_index..].windows(2).position(|w| {
-                        let syl_a = &w[0];
-                        let syl_b = &w[1];
-                        let duration_a = syl_a.end_ms.saturating_sub(syl_a.start_ms);
-                        let duration_b = syl_b.end_ms.saturating_sub(syl_b.start_ms);
-                        let gap = syl_b.start_ms.saturating_sub(syl_a.end_ms);
-
-                        duration_a.abs_diff(duration_b) > options.duration_threshold_ms
-                            || gap > options.gap_threshold_ms
-                    });
-
-                    let end_index = match next_break {
-                        Some(break_pos) => start_index + break_pos,
-                        None => word.syllables.len() - 1,
-                    };
-
-                    // 如果有多个音节，就执行平滑处理
-                    if end_index > start_index {
-                        let original_start_ms = word.syllables[start_index].start_ms;
-                        let original_end_ms = word.syllables[end_index].end_ms;
-
-                        let group_slice = &mut word.syllables[start_index..=end_index];
-                        let original_total_duration: f64 = group_slice
-                            .iter()
-                            .map(|s| s.end_ms.saturating_sub(s.start_ms) as f64)
-                            .sum();
-
-                        let group_len = group_slice.len();
-
-                        let original_gaps: Vec<u64> = group_slice
-                            .windows(2)
-                            .map(|w| w[1].start_ms.saturating_sub(w[0].end_ms))
-                            .collect();
-
-                        let mut durations: Vec<f64> = group_slice
-                            .iter()
-                            .map(|s| s.end_ms.saturating_sub(s.start_ms) as f64)
-                            .collect();
-                        let mut next_durations = vec![0.0; group_len];
-
-                        for _ in 0..options.smoothing_iterations {
-                            // 处理第一个元素
-                            let term = options.factor * durations[1];
-                            next_durations[0] = (1.0 - options.factor).mul_add(durations[0], term);
-
-                            // 处理中间元素
-                            for i in 1..group_len - 1 {
-                                let one_minus_2f = 2.0f64.mul_add(-options.factor, 1.0);
-                                let inner_term = one_minus_2f
-                                    .mul_add(durations[i], options.factor * durations[i - 1]);
-                                next_durations[i] =
-                                    options.factor.mul_add(durations[i + 1], inner_term);
-                            }
-
-                            // 处理最后一个元素
-                            let last_idx = group_len - 1;
-                            let term = options.factor * durations[last_idx - 1];
-                            next_durations[last_idx] =
-                                (1.0 - options.factor).mul_add(durations[last_idx], term);
-
-                            std::mem::swap(&mut durations, &mut next_durations);
-                        }
-
-                        // 重新分配时间戳
-                        let new_total_duration: f64 = durations.iter().sum();
-                        if new_total_duration > 1e-6 {
-                            let scale_factor = original_total_duration / new_total_duration;
-                            for d in &mut durations {
-                                *d *= scale_factor;
-                            }
-                        }
-
-                        let mut current_ms = original_start_ms;
-                        for i in 0..group_slice.len() {
-                            group_slice[i].start_ms = current_ms;
-                            let new_duration = durations[i].round() as u64;
-                            group_slice[i].end_ms = current_ms.saturating_add(new_duration);
-
-                            if let Some(gap) = original_gaps.get(i) {
-                                current_ms = group_slice[i].end_ms.saturating_add(*gap);
-                            }
-                        }
-
-                        // 校准一下最后的时间戳
-                        if let Some(last_syl_mut) = group_slice.last_mut() {
-                            last_syl_mut.end_ms = original_end_ms;
-                        }
+                    // 处理中间元素
+                    for i in 1..group_len - 1 {
+                        let one_minus_2f = 2.0f64.mul_add(-options.factor, 1.0);
+                        let inner_term =
+                            one_minus_2f.mul_add(durations[i], options.factor * durations[i - 1]);
+                        next_durations[i] = options.factor.mul_add(durations[i + 1], inner_term);
                     }
 
-                    // 从下一组继续
-                    start_index = end_index + 1;
+                    // 处理最后一个元素
+                    let last_idx = group_len - 1;
+                    let term = options.factor * durations[last_idx - 1];
+                    next_durations[last_idx] =
+                        (1.0 - options.factor).mul_add(durations[last_idx], term);
+
+                    std::mem::swap(&mut durations, &mut next_durations);
+                    on_iteration(iteration, word_index, start_index, &durations);
                 }
             }
+
+            // 从下一组继续
+            start_index = end_index + 1;
         }
     }
 }
+
+/// 按平滑后的时长重新分配一组音节的时间戳。
+///
+/// `group` 须为平滑前的音节，组的起止时间、总时长和音节间的间隔保持不变。
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::cast_precision_loss)]
+fn redistribute_group(group: &mut [LyricSyllable], durations: &[f64]) {
+    let original_start_ms = group[0].start_ms;
+    let original_end_ms = group[group.len() - 1].end_ms;
+    let original_total_duration: f64 = group
+        .iter()
+        .map(|s| s.end_ms.saturating_sub(s.start_ms) as f64)
+        .sum();
+    let original_gaps: Vec<u64> = group
+        .windows(2)
+        .map(|w| w[1].start_ms.saturating_sub(w[0].end_ms))
+        .collect();
+
+    // 重新分配时间戳
+    let new_total_duration: f64 = durations.iter().sum();
+    let scale_factor = if new_total_duration > 1e-6 {
+        original_total_duration / new_total_duration
+    } else {
+        1.0
+    };
+
+    let mut current_ms = original_start_ms;
+    for (i, syllable) in group.iter_mut().enumerate() {
+        syllable.start_ms = current_ms;
+        let new_duration = (durations[i] * scale_factor).round() as u64;
+        syllable.end_ms = current_ms.saturating_add(new_duration);
+
+        if let Some(gap) = original_gaps.get(i) {
+            current_ms = syllable.end_ms.saturating_add(*gap);
+        }
+    }
+
+    // 校准一下最后的时间戳
+    if let Some(last_syl_mut) = group.last_mut() {
+        last_syl_mut.end_ms = original_end_ms;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::test_utils::{syl, timed_line};
+
+    fn uneven_line() -> LyricLine {
+        timed_line(vec![
+            syl("a", 0, 100),
+            syl("a", 100, 160),
+            syl("a", 160, 300),
+            syl("a", 300, 350),
+        ])
+    }
+
+    #[test]
+    fn test_iteration_states_match_single_runs() {
+        let lines = vec![uneven_line()];
+        let options = SyllableSmoothingOptions {
+            factor: 0.2,
+            duration_threshold_ms: 200,
+            gap_threshold_ms: 100,
+            smoothing_iterations: 3,
+            ..Default::default()
+        };
+
+        let states = smoothing_iteration_states(&lines, &options, 3);
+        assert_eq!(states.len(), 4);
+        assert_eq!(states[0], lines);
+
+        for (iterations, state) in (0..=3).zip(&states) {
+            let mut expected = lines.clone();
+            apply_smoothing(
+                &mut expected,
+                &SyllableSmoothingOptions {
+                    smoothing_iterations: iterations,
+                    ..options
+                },
+            );
+            assert_eq!(state, &expected);
+        }
+        assert_ne!(states[1], states[3]);
+    }
+
+    #[test]
+    fn test_last_iteration_state_matches_apply_smoothing() {
+        let mut line = uneven_line();
+        let mut background = line.tracks[0].clone();
+        background.content_type = ContentType::Background;
+        line.tracks.push(background);
+        let lines = vec![line];
+        let options = SyllableSmoothingOptions {
+            factor: 0.3,
+            duration_threshold_ms: 200,
+            gap_threshold_ms: 100,
+            smoothing_iterations: 10,
+            background_mode: BackgroundSmoothingMode::Custom,
+            background_factor: 0.1,
+            background_duration_threshold_ms: 200,
+            background_gap_threshold_ms: 100,
+        };
+
+        let states = smoothing_iteration_states(&lines, &options, options.smoothing_iterations);
+
+        let mut expected = lines;
+        apply_smoothing(&mut expected, &options);
+        assert_eq!(states.last(), Some(&expected));
+    }
+
+    #[test]
+    fn test_background_smoothing_modes() {
+        let mut line = uneven_line();
+        let mut background = line.tracks[0].clone();
+        background.content_type = ContentType::Background;
+        line.tracks.push(background);
+        let original = vec![line];
+
+        let options = SyllableSmoothingOptions {
+            factor: 0.2,
+            duration_threshold_ms: 200,
+            gap_threshold_ms: 100,
+            smoothing_iterations: 3,
+            ..Default::default()
+        };
+
+        let mut skipped = original.clone();
+        apply_smoothing(&mut skipped, &options);
+        assert_ne!(skipped[0].tracks[0], original[0].tracks[0]);
+        assert_eq!(skipped[0].tracks[1], original[0].tracks[1]);
+
+        let mut same = original.clone();
+        apply_smoothing(
+            &mut same,
+            &SyllableSmoothingOptions {
+                background_mode: BackgroundSmoothingMode::SameAsMain,
+                ..options
+            },
+        );
+        assert_eq!(same[0].tracks[1].content, same[0].tracks[0].content);
+
+        let mut custom = original.clone();
+        apply_smoothing(
+            &mut custom,
+            &SyllableSmoothingOptions {
+                background_mode: BackgroundSmoothingMode::Custom,
+                background_factor: 0.0,
+                ..options
+            },
+        );
+        assert_eq!(custom[0].tracks[1], original[0].tracks[1]);
+    }
+}
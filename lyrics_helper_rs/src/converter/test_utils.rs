@@ -0,0 +1,55 @@
+//! 转换器单元测试共用的歌词构造函数。
+
+use lyrics_helper_core::{AnnotatedTrack, ContentType, LyricLine, LyricSyllable, LyricTrack, Word};
+
+/// 构造一个音节。
+pub fn syl(text: &str, start_ms: u64, end_ms: u64) -> LyricSyllable {
+    LyricSyllable {
+        text: text.to_string(),
+        start_ms,
+        end_ms,
+        ..Default::default()
+    }
+}
+
+/// 构造一个后面跟着空格的音节。
+pub fn spaced_syl(text: &str, start_ms: u64, end_ms: u64) -> LyricSyllable {
+    LyricSyllable {
+        ends_with_space: true,
+        ..syl(text, start_ms, end_ms)
+    }
+}
+
+/// 用一组音节构造只含一个单词的轨道。
+pub fn track(syllables: Vec<LyricSyllable>) -> LyricTrack {
+    LyricTrack {
+        words: vec![Word {
+            syllables,
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// 用一组音节构造一行逐字歌词，行的起止时间取自首尾音节。
+pub fn timed_line(syllables: Vec<LyricSyllable>) -> LyricLine {
+    let start_ms = syllables.first().map_or(0, |s| s.start_ms);
+    let end_ms = syllables.last().map_or(0, |s| s.end_ms);
+    LyricLine {
+        start_ms,
+        end_ms,
+        tracks: vec![AnnotatedTrack {
+            content_type: ContentType::Main,
+            content: track(syllables),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// 构造一行逐行歌词，整行文本是一个覆盖整行时间的音节。
+pub fn text_line(start_ms: u64, end_ms: u64, text: &str) -> LyricLine {
+    let mut line = LyricLine::new(start_ms, end_ms);
+    line.add_content_track(ContentType::Main, text);
+    line
+}
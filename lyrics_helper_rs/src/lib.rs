@@ -99,7 +99,8 @@ use crate::{
 use futures::{Future, StreamExt, future, stream};
 use lyrics_helper_core::{
     ComprehensiveSearchResult, ConversionInput, ConversionOptions, CoverSize, FullConversionResult,
-    FullLyricsResult, LyricFormat, LyricsAndMetadata, ParsedSourceData, SearchResult, Track,
+    FullLyricsResult, LyricFormat, LyricsAndMetadata, MatchType, ParsedSourceData, SearchResult,
+    Track,
 };
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
@@ -122,6 +123,8 @@ pub enum ProviderName {
     Kugou,
     /// AMLL TTML 数据库
     AmllTtmlDatabase,
+    /// 用户配置的自定义 HTTP 歌词源
+    CustomHttp,
 }
 
 impl ProviderName {
@@ -133,6 +136,7 @@ impl ProviderName {
             Self::Netease => "netease",
             Self::Kugou => "kugou",
             Self::AmllTtmlDatabase => "amll-ttml-database",
+            Self::CustomHttp => "custom-http",
         }
     }
 
@@ -144,6 +148,7 @@ impl ProviderName {
             "netease" => Some(Self::Netease),
             "kugou" => Some(Self::Kugou),
             "amll-ttml-database" => Some(Self::AmllTtmlDatabase),
+            "custom-http" => Some(Self::CustomHttp),
             _ => None,
         }
     }
@@ -156,6 +161,7 @@ impl ProviderName {
             Self::Netease,
             Self::Kugou,
             Self::AmllTtmlDatabase,
+            Self::CustomHttp,
         ]
     }
 
@@ -167,8 +173,15 @@ impl ProviderName {
             Self::Netease => "网易云音乐",
             Self::Kugou => "酷狗音乐",
             Self::AmllTtmlDatabase => "AMLL TTML 数据库",
+            Self::CustomHttp => "自定义源",
         }
     }
+
+    /// 该提供商是否能提供歌曲的播放链接，可用于试听
+    #[must_use]
+    pub const fn supports_song_link(&self) -> bool {
+        matches!(self, Self::QQMusic | Self::Netease | Self::Kugou)
+    }
 }
 
 impl std::fmt::Display for ProviderName {
@@ -184,7 +197,10 @@ impl std::str::FromStr for ProviderName {
         Self::try_from_str(s).ok_or_else(|| format!("不支持的提供商: {s}"))
     }
 }
+pub use crate::http::NetworkConfig;
 use crate::providers::amll_ttml_database::AmllTtmlDatabase;
+use crate::providers::custom_http::CustomHttpSource;
+pub use crate::providers::custom_http::CustomHttpSourceConfig;
 
 use crate::providers::{Provider, kugou::KugouMusic, netease::NeteaseClient, qq::QQMusic};
 
@@ -211,9 +227,19 @@ pub enum SearchMode {
     /// 参数是提供商的枚举值。
     Specific(ProviderName),
     /// 在指定的一个提供商子集中并行搜索。
+    ///
+    /// 列表的顺序即为优先级：当多个结果的匹配度相同时，排在前面的提供商优先。
     Subset(Vec<ProviderName>),
+    /// 按列表顺序逐个搜索指定的提供商。
+    ///
+    /// 全面搜索时，某个提供商的最佳匹配达到 [`ORDERED_FIRST_HIT_MATCH_TYPE`] 就不再搜索后面的提供商；
+    /// 都达不到时返回已搜索结果中匹配度最高的一个。
+    OrderedSubset(Vec<ProviderName>),
 }
 
+/// [`SearchMode::OrderedSubset`] 模式下提前结束搜索所需的最低匹配度。
+pub const ORDERED_FIRST_HIT_MATCH_TYPE: MatchType = MatchType::PrettyHigh;
+
 impl SearchMode {
     /// 创建一个搜索特定提供商的模式
     #[must_use]
@@ -227,6 +253,12 @@ impl SearchMode {
         Self::Subset(providers)
     }
 
+    /// 创建一个按顺序逐个搜索提供商子集的模式
+    #[must_use]
+    pub const fn ordered_subset(providers: Vec<ProviderName>) -> Self {
+        Self::OrderedSubset(providers)
+    }
+
     /// 创建一个只搜索网易云音乐的模式
     #[must_use]
     pub const fn netease_only() -> Self {
@@ -276,6 +308,8 @@ pub type SearchLyricsComprehensiveFuture<'a> =
 pub struct LyricsHelper {
     providers: Vec<Arc<dyn Provider + Send + Sync>>,
     http_clients: HashMap<ProviderName, Arc<dyn HttpClient>>,
+    custom_http_source: Option<CustomHttpSourceConfig>,
+    network_config: NetworkConfig,
 }
 
 impl Default for LyricsHelper {
@@ -294,9 +328,25 @@ impl LyricsHelper {
         Self {
             providers: Vec::new(),
             http_clients: HashMap::new(),
+            custom_http_source: None,
+            network_config: NetworkConfig::default(),
         }
     }
 
+    /// 设置自定义 HTTP 歌词源的配置。
+    ///
+    /// 需要在 `load_providers()` 之前调用才会生效；传入 `None` 或未填写 URL 模板的配置时不加载自定义源。
+    pub fn set_custom_http_source(&mut self, config: Option<CustomHttpSourceConfig>) {
+        self.custom_http_source = config;
+    }
+
+    /// 设置所有提供商共用的请求超时和代理配置。
+    ///
+    /// 需要在 `load_providers()` 之前调用才会生效。
+    pub fn set_network_config(&mut self, config: NetworkConfig) {
+        self.network_config = config;
+    }
+
     /// 为指定的提供商发起一个登录流程。
     ///
     /// # 参数
@@ -433,17 +483,28 @@ impl LyricsHelper {
     /// # 返回
     /// 如果所有提供商都成功或部分成功初始化，则返回 `Ok(())`。
     pub async fn load_providers(&mut self) -> Result<()> {
-        let provider_names = ProviderName::all();
+        let custom_http_source = self
+            .custom_http_source
+            .clone()
+            .filter(CustomHttpSourceConfig::is_configured);
+        let provider_names: Vec<_> = ProviderName::all()
+            .into_iter()
+            .filter(|name| *name != ProviderName::CustomHttp || custom_http_source.is_some())
+            .collect();
         let mut clients: HashMap<ProviderName, Arc<dyn HttpClient>> = HashMap::new();
         let mut provider_initializers = Vec::new();
 
         for name in &provider_names {
-            clients.insert(name.clone(), Arc::new(WreqClient::new()?));
+            clients.insert(
+                name.clone(),
+                Arc::new(WreqClient::with_config(self.network_config.clone())?),
+            );
         }
         self.http_clients = clients;
 
         for name in provider_names {
             let client = self.http_clients.get(&name).unwrap().clone();
+            let custom_http_source = custom_http_source.clone();
             let provider_future = async move {
                 let result: Result<Box<dyn Provider + Send + Sync>> = match name {
                     ProviderName::QQMusic => QQMusic::with_http_client(client)
@@ -458,6 +519,11 @@ impl LyricsHelper {
                     ProviderName::AmllTtmlDatabase => AmllTtmlDatabase::with_http_client(client)
                         .await
                         .map(|p| Box::new(p) as Box<_>),
+                    ProviderName::CustomHttp => custom_http_source
+                        .map(|config| Box::new(CustomHttpSource::new(client, config)) as Box<_>)
+                        .ok_or_else(|| {
+                            LyricsHelperError::ProviderNotSupported("custom-http".to_string())
+                        }),
                 };
                 (name, result)
             };
@@ -640,6 +706,62 @@ impl LyricsHelper {
         ))
     }
 
+    /// 获取一首歌曲开头部分的音频数据，用于试听。
+    ///
+    /// 先向提供商请求播放链接，再以 `Range` 请求只下载前 `max_bytes` 个字节，
+    /// 不支持 `Range` 的服务器返回完整文件时也会截断到 `max_bytes`。
+    ///
+    /// # 参数
+    /// * `provider_name` - 提供商的唯一名称, 例如 "qq" 或 "netease"。
+    /// * `song_id` - 在该提供商平台上的歌曲ID。
+    /// * `max_bytes` - 最多下载的字节数。
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_audio_preview<'a>(
+        &self,
+        provider_name: &'a str,
+        song_id: &'a str,
+        max_bytes: usize,
+    ) -> Result<Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send + 'a>>> {
+        if self.providers.is_empty() {
+            return Err(LyricsHelperError::ProvidersNotInitialized);
+        }
+
+        let provider = self
+            .providers
+            .iter()
+            .find(|p| p.name() == provider_name)
+            .cloned()
+            .ok_or_else(|| LyricsHelperError::ProviderNotSupported(provider_name.to_string()))?;
+        let client = ProviderName::try_from_str(provider_name)
+            .and_then(|name| self.http_clients.get(&name).cloned())
+            .ok_or_else(|| LyricsHelperError::ProviderNotSupported(provider_name.to_string()))?;
+
+        let song_id = song_id.to_string();
+
+        Ok(Box::pin(async move {
+            let url = provider.get_song_link(&song_id).await?;
+            let range = format!("bytes=0-{}", max_bytes.saturating_sub(1));
+            let response = client
+                .request_with_headers(
+                    http::HttpMethod::Get,
+                    &url,
+                    &[("Range", range.as_str())],
+                    None,
+                )
+                .await?;
+            if response.status >= 400 {
+                return Err(LyricsHelperError::Http(format!(
+                    "下载试听音频失败，状态码: {}",
+                    response.status
+                )));
+            }
+
+            let mut body = response.body;
+            body.truncate(max_bytes);
+            Ok(body)
+        }))
+    }
+
     /// 执行一次完整的、多文件的歌词转换。
     ///
     /// # 参数
@@ -657,6 +779,21 @@ impl LyricsHelper {
         Ok(converter::convert_single_lyric(input, &options)?)
     }
 
+    /// 在阻塞线程池中执行一次完整的歌词转换。
+    ///
+    /// 与 [`Self::convert_lyrics`] 相同，但解析和生成在 `spawn_blocking` 中运行，
+    /// 不会占用异步运行时的工作线程，适合处理很大的歌词文件。
+    pub async fn convert_lyrics_in_background(
+        input: ConversionInput,
+        options: ConversionOptions,
+    ) -> Result<FullConversionResult> {
+        tokio::task::spawn_blocking(move || {
+            converter::convert_single_lyric(&input, &options).map_err(LyricsHelperError::from)
+        })
+        .await
+        .map_err(|e| LyricsHelperError::Internal(e.to_string()))?
+    }
+
     /// 从已解析的数据生成歌词，跳过解析步骤。
     pub async fn generate_lyrics_from_parsed<S: BuildHasher + Send + 'static>(
         source_data: ParsedSourceData,
@@ -711,7 +848,7 @@ impl LyricsHelper {
 
         Ok(Box::pin(async move {
             match mode {
-                SearchMode::Ordered | SearchMode::Specific(_) => {
+                SearchMode::Ordered | SearchMode::Specific(_) | SearchMode::OrderedSubset(_) => {
                     search_ordered(&providers_to_search, &track_meta).await
                 }
                 SearchMode::Parallel | SearchMode::Subset(_) => {
@@ -754,10 +891,16 @@ impl LyricsHelper {
         );
 
         let track_meta = track_meta.clone();
+        let ordered = matches!(mode, SearchMode::OrderedSubset(_));
 
         Ok(Box::pin(async move {
-            search_comprehensive_unified(&providers_to_search, &track_meta, cancellation_token)
-                .await
+            if ordered {
+                search_comprehensive_ordered(&providers_to_search, &track_meta, cancellation_token)
+                    .await
+            } else {
+                search_comprehensive_unified(&providers_to_search, &track_meta, cancellation_token)
+                    .await
+            }
         }))
     }
 
@@ -889,12 +1032,18 @@ fn get_providers_for_mode(
                 ));
             }
         }
-        SearchMode::Subset(provider_names) => {
-            let name_strs: HashSet<_> = provider_names.iter().map(ProviderName::as_str).collect();
-            let selected_providers: Vec<_> = all_providers
+        SearchMode::Subset(provider_names) | SearchMode::OrderedSubset(provider_names) => {
+            // 按照 `provider_names` 的顺序选取提供商，以保留调用方指定的优先级
+            let mut seen = HashSet::new();
+            let selected_providers: Vec<_> = provider_names
                 .iter()
-                .filter(|p| name_strs.contains(p.name()))
-                .cloned()
+                .filter(|name| seen.insert(name.as_str()))
+                .filter_map(|name| {
+                    all_providers
+                        .iter()
+                        .find(|p| p.name() == name.as_str())
+                        .cloned()
+                })
                 .collect();
 
             if selected_providers.is_empty() && !provider_names.is_empty() {
@@ -1016,6 +1165,49 @@ async fn search_lyrics_parallel(
     }
 }
 
+/// 按顺序逐个提供商进行全面搜索，最佳匹配达到 [`ORDERED_FIRST_HIT_MATCH_TYPE`] 时立即返回。
+///
+/// 返回的候选项包含所有已搜索提供商的结果。
+async fn search_comprehensive_ordered(
+    providers: &[Arc<dyn Provider + Send + Sync>],
+    track_meta: &Track<'_>,
+    cancellation_token: Option<CancellationToken>,
+) -> Result<Option<ComprehensiveSearchResult>> {
+    let mut best: Option<ComprehensiveSearchResult> = None;
+    let mut all_candidates = Vec::new();
+
+    for provider in providers {
+        let Some(mut result) = search_comprehensive_unified(
+            std::slice::from_ref(provider),
+            track_meta,
+            cancellation_token.clone(),
+        )
+        .await?
+        else {
+            continue;
+        };
+        all_candidates.append(&mut result.all_search_candidates);
+
+        let match_type = result.primary_lyric_result.source_track.match_type;
+        let is_better = best
+            .as_ref()
+            .is_none_or(|best| match_type > best.primary_lyric_result.source_track.match_type);
+        if is_better {
+            best = Some(result);
+        }
+        if match_type >= ORDERED_FIRST_HIT_MATCH_TYPE {
+            tracing::info!("在 '{}' 找到足够匹配的歌词，搜索结束。", provider.name());
+            break;
+        }
+    }
+
+    Ok(best.map(|mut result| {
+        all_candidates.sort_by(|a, b| b.match_type.cmp(&a.match_type));
+        result.all_search_candidates = all_candidates;
+        result
+    }))
+}
+
 async fn search_comprehensive_unified(
     providers: &[Arc<dyn Provider + Send + Sync>],
     track_meta: &Track<'_>,
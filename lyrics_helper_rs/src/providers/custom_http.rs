@@ -0,0 +1,388 @@
+//! 此模块实现了由用户配置的自定义 HTTP 歌词源。
+//!
+//! 搜索和获取歌词都通过 URL 模板发起 GET 请求，搜索响应按配置的字段映射为 `SearchResult`，
+//! 歌词响应可以是歌词文本本身，也可以是包含歌词文本字段的 JSON。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    converter,
+    error::{LyricsHelperError, Result},
+    http::HttpClient,
+    providers::Provider,
+};
+
+use lyrics_helper_core::{
+    ConversionInput, ConversionOptions, CoverSize, FullLyricsResult, InputFile, LyricFormat,
+    RawLyrics, SearchResult, Track, model::generic,
+};
+
+/// 自定义歌词源的配置。
+///
+/// 字段路径以点分隔，例如 `data.songs`；数组元素可以用下标访问，例如 `lyrics.0.text`。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomHttpSourceConfig {
+    /// 搜索 URL 模板，可使用 `{title}`、`{artist}`、`{album}`、`{duration}` 占位符
+    pub search_url_template: String,
+    /// 获取歌词的 URL 模板，`{id}` 会被替换为搜索结果的 ID
+    pub lyrics_url_template: String,
+    /// 搜索结果数组在响应中的路径，为空时响应本身就是数组
+    pub results_path: String,
+    pub id_field: String,
+    pub title_field: String,
+    /// 艺术家字段，可以是字符串（以 `/` 分隔多位艺术家）、字符串数组或带 `name` 字段的对象数组
+    pub artists_field: String,
+    pub album_field: String,
+    /// 时长字段（毫秒）
+    pub duration_field: String,
+    /// 歌词文本在获取响应中的路径，为空时整个响应体就是歌词
+    pub lyrics_field: String,
+    pub lyrics_format: LyricFormat,
+}
+
+impl Default for CustomHttpSourceConfig {
+    fn default() -> Self {
+        Self {
+            search_url_template: String::new(),
+            lyrics_url_template: String::new(),
+            results_path: String::new(),
+            id_field: "id".to_string(),
+            title_field: "title".to_string(),
+            artists_field: "artists".to_string(),
+            album_field: "album".to_string(),
+            duration_field: "duration".to_string(),
+            lyrics_field: String::new(),
+            lyrics_format: LyricFormat::Lrc,
+        }
+    }
+}
+
+impl CustomHttpSourceConfig {
+    /// 搜索和获取歌词的 URL 模板是否都已填写。
+    #[must_use]
+    pub fn is_configured(&self) -> bool {
+        !self.search_url_template.trim().is_empty() && !self.lyrics_url_template.trim().is_empty()
+    }
+}
+
+/// 自定义 HTTP 歌词源的实现。
+pub struct CustomHttpSource {
+    http_client: Arc<dyn HttpClient>,
+    config: CustomHttpSourceConfig,
+}
+
+impl CustomHttpSource {
+    /// 使用给定的配置创建自定义源。
+    #[must_use]
+    pub fn new(http_client: Arc<dyn HttpClient>, config: CustomHttpSourceConfig) -> Self {
+        Self {
+            http_client,
+            config,
+        }
+    }
+
+    async fn get_ok(&self, url: &str) -> Result<String> {
+        let response = self.http_client.get(url).await?;
+        if response.status >= 400 {
+            return Err(LyricsHelperError::Http(format!(
+                "自定义源返回错误，状态码: {}",
+                response.status
+            )));
+        }
+        response.text()
+    }
+
+    fn unsupported(method: &str) -> LyricsHelperError {
+        LyricsHelperError::ProviderNotSupported(format!("custom-http 不支持 {method}"))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Provider for CustomHttpSource {
+    fn name(&self) -> &'static str {
+        "custom-http"
+    }
+
+    /// 自定义源需要配置才能工作，请使用 [`CustomHttpSource::new`] 创建。
+    async fn with_http_client(_http_client: Arc<dyn HttpClient>) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Err(LyricsHelperError::ProviderNotSupported(
+            "custom-http 需要配置，请使用 CustomHttpSource::new 创建".to_string(),
+        ))
+    }
+
+    async fn search_songs(&self, track: &Track<'_>) -> Result<Vec<SearchResult>> {
+        let title = track.title.unwrap_or_default();
+        if title.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let url = fill_search_url(&self.config.search_url_template, track);
+        tracing::debug!("[CustomHttp] 搜索: {}", url);
+        let body: Value = serde_json::from_str(&self.get_ok(&url).await?)?;
+
+        Ok(parse_search_results(&body, &self.config, self.name()))
+    }
+
+    #[allow(clippy::literal_string_with_formatting_args)]
+    async fn get_full_lyrics(&self, song_id: &str) -> Result<FullLyricsResult> {
+        let url = self
+            .config
+            .lyrics_url_template
+            .replace("{id}", &encode_component(song_id));
+        tracing::info!("[CustomHttp] 获取歌词: {}", url);
+        let body = self.get_ok(&url).await?;
+
+        let content = if self.config.lyrics_field.trim().is_empty() {
+            body
+        } else {
+            let json: Value = serde_json::from_str(&body)?;
+            value_at_path(&json, &self.config.lyrics_field)
+                .and_then(Value::as_str)
+                .map(String::from)
+                .ok_or(LyricsHelperError::LyricNotFound)?
+        };
+        if content.trim().is_empty() {
+            return Err(LyricsHelperError::LyricNotFound);
+        }
+
+        let format = self.config.lyrics_format;
+        let conversion_input = ConversionInput {
+            main_lyric: InputFile {
+                content: content.clone(),
+                format,
+                language: None,
+                filename: Some(song_id.to_string()),
+            },
+            translations: vec![],
+            romanizations: vec![],
+            target_format: LyricFormat::default(),
+            user_metadata_overrides: None,
+            additional_metadata: None,
+        };
+
+        let mut parsed_data =
+            converter::parse_and_merge(&conversion_input, &ConversionOptions::default())
+                .map_err(|e| LyricsHelperError::Parser(e.to_string()))?;
+        parsed_data.source_name = self.name().to_string();
+
+        Ok(FullLyricsResult {
+            parsed: parsed_data,
+            raw: RawLyrics {
+                format: format.to_extension_str().to_string(),
+                content,
+                translation: None,
+                romanization: None,
+            },
+        })
+    }
+
+    async fn get_album_info(&self, _album_id: &str) -> Result<generic::Album> {
+        Err(Self::unsupported("get_album_info"))
+    }
+
+    async fn get_album_songs(
+        &self,
+        _album_id: &str,
+        _page: u32,
+        _page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        Err(Self::unsupported("get_album_songs"))
+    }
+
+    async fn get_singer_songs(
+        &self,
+        _singer_id: &str,
+        _page: u32,
+        _page_size: u32,
+    ) -> Result<Vec<generic::Song>> {
+        Err(Self::unsupported("get_singer_songs"))
+    }
+
+    async fn get_playlist(&self, _playlist_id: &str) -> Result<generic::Playlist> {
+        Err(Self::unsupported("get_playlist"))
+    }
+
+    async fn get_song_info(&self, _song_id: &str) -> Result<generic::Song> {
+        Err(Self::unsupported("get_song_info"))
+    }
+
+    async fn get_song_link(&self, _song_id: &str) -> Result<String> {
+        Err(Self::unsupported("get_song_link"))
+    }
+
+    async fn get_album_cover_url(&self, _album_id: &str, _size: CoverSize) -> Result<String> {
+        Err(Self::unsupported("get_album_cover_url"))
+    }
+}
+
+fn encode_component(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// 用歌曲信息填充搜索 URL 模板，缺失的信息替换为空字符串。
+#[allow(clippy::literal_string_with_formatting_args)]
+fn fill_search_url(template: &str, track: &Track<'_>) -> String {
+    let artist = track.artists.map(|a| a.join(" ")).unwrap_or_default();
+    let duration = track.duration.map(|d| d.to_string()).unwrap_or_default();
+    template
+        .replace(
+            "{title}",
+            &encode_component(track.title.unwrap_or_default()),
+        )
+        .replace("{artist}", &encode_component(&artist))
+        .replace(
+            "{album}",
+            &encode_component(track.album.unwrap_or_default()),
+        )
+        .replace("{duration}", &duration)
+}
+
+/// 按点分隔的路径取出 JSON 中的值，数字段用作数组下标。空路径返回值本身。
+fn value_at_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| match current {
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => current.get(segment),
+        })
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_artists(value: &Value) -> Vec<generic::Artist> {
+    let names: Vec<String> = match value {
+        Value::String(s) => s.split('/').map(|name| name.trim().to_string()).collect(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| {
+                value_to_string(item).or_else(|| item.get("name")?.as_str().map(String::from))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    names
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .map(|name| generic::Artist {
+            id: String::new(),
+            name,
+        })
+        .collect()
+}
+
+/// 按配置的字段映射把搜索响应转换为搜索结果，缺少 ID 或标题的条目会被跳过。
+fn parse_search_results(
+    body: &Value,
+    config: &CustomHttpSourceConfig,
+    provider_name: &str,
+) -> Vec<SearchResult> {
+    let Some(Value::Array(items)) = value_at_path(body, &config.results_path) else {
+        tracing::warn!(
+            "[CustomHttp] 在路径 '{}' 处未找到结果数组。",
+            config.results_path
+        );
+        return vec![];
+    };
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let provider_id = value_at_path(item, &config.id_field).and_then(value_to_string)?;
+            let title = value_at_path(item, &config.title_field).and_then(value_to_string)?;
+            Some(SearchResult {
+                provider_id,
+                title,
+                artists: value_at_path(item, &config.artists_field)
+                    .map(parse_artists)
+                    .unwrap_or_default(),
+                album: value_at_path(item, &config.album_field).and_then(value_to_string),
+                duration: value_at_path(item, &config.duration_field).and_then(Value::as_u64),
+                provider_name: provider_name.to_string(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_search_url_encodes_placeholders() {
+        let artists = ["A", "B&C"];
+        let track = Track {
+            title: Some("晴天 x"),
+            artists: Some(&artists),
+            album: None,
+            duration: Some(269_000),
+        };
+
+        let url = fill_search_url(
+            "https://example.com/search?q={title}&a={artist}&al={album}&d={duration}",
+            &track,
+        );
+        assert_eq!(
+            url,
+            "https://example.com/search?q=%E6%99%B4%E5%A4%A9+x&a=A+B%26C&al=&d=269000"
+        );
+    }
+
+    #[test]
+    fn test_value_at_path() {
+        let json: Value =
+            serde_json::from_str(r#"{"data":{"songs":[{"lrc":{"text":"[00:01.00]a"}}]}}"#).unwrap();
+
+        assert_eq!(
+            value_at_path(&json, "data.songs.0.lrc.text").and_then(Value::as_str),
+            Some("[00:01.00]a")
+        );
+        assert!(value_at_path(&json, "data.songs.1").is_none());
+        assert_eq!(value_at_path(&json, ""), Some(&json));
+    }
+
+    #[test]
+    fn test_parse_search_results_maps_fields() {
+        let body: Value = serde_json::from_str(
+            r#"{"result":[
+                {"sid":42,"name":"晴天","singer":[{"name":"周杰伦"}],"album":"叶惠美","ms":269000},
+                {"sid":"x1","name":"七里香","singer":"周杰伦/Someone"},
+                {"name":"没有 ID"}
+            ]}"#,
+        )
+        .unwrap();
+        let config = CustomHttpSourceConfig {
+            results_path: "result".to_string(),
+            id_field: "sid".to_string(),
+            title_field: "name".to_string(),
+            artists_field: "singer".to_string(),
+            duration_field: "ms".to_string(),
+            ..Default::default()
+        };
+
+        let results = parse_search_results(&body, &config, "custom-http");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].provider_id, "42");
+        assert_eq!(results[0].artists[0].name, "周杰伦");
+        assert_eq!(results[0].album.as_deref(), Some("叶惠美"));
+        assert_eq!(results[0].duration, Some(269_000));
+        assert_eq!(results[1].provider_id, "x1");
+        assert_eq!(results[1].artists.len(), 2);
+        assert_eq!(results[1].provider_name, "custom-http");
+    }
+}
@@ -3,6 +3,7 @@
 //! 该模块定义了与 Providers 进行交互的核心抽象。
 
 pub mod amll_ttml_database;
+pub mod custom_http;
 pub mod kugou;
 pub mod login;
 // pub mod musixmatch;
@@ -240,12 +240,9 @@ fn write_line_timed_translations<W: std::io::Write>(
     Ok(())
 }
 
-/// 写入所有 <amll:meta> 元素。
-fn write_amll_metadata<W: std::io::Write>(
-    writer: &mut Writer<W>,
-    metadata_store: &MetadataStore,
-) -> Result<(), ConvertError> {
-    let keys_map = [
+/// 有专门 `<amll:meta>` 键名的规范元数据键。
+fn amll_meta_keys() -> [(CanonicalMetadataKey, &'static str); 10] {
+    [
         (CanonicalMetadataKey::Title, "musicName"),
         (CanonicalMetadataKey::Artist, "artists"),
         (CanonicalMetadataKey::Album, "album"),
@@ -259,8 +256,32 @@ fn write_amll_metadata<W: std::io::Write>(
             CanonicalMetadataKey::TtmlAuthorGithubLogin,
             "ttmlAuthorGithubLogin",
         ),
-    ];
+    ]
+}
+
+/// 判断自定义元数据键是否应作为 `<amll:meta>` 写出。
+fn is_writable_custom_key(key: &str) -> bool {
+    !key.eq_ignore_ascii_case("agent") && !key.eq_ignore_ascii_case("xml:lang_root")
+}
 
+/// 判断是否会写出任何 `<amll:meta>` 元素，用于决定是否声明 `amll` 命名空间。
+pub(super) fn has_amll_metadata(metadata_store: &MetadataStore) -> bool {
+    let known_keys = amll_meta_keys();
+    metadata_store.get_all_data().iter().any(|(key, values)| {
+        let is_amll_key = match key {
+            CanonicalMetadataKey::Custom(s) => is_writable_custom_key(s),
+            _ => known_keys.iter().any(|(known, _)| known == key),
+        };
+        is_amll_key && values.iter().any(|v| !v.trim().is_empty())
+    })
+}
+
+/// 写入所有 <amll:meta> 元素。
+fn write_amll_metadata<W: std::io::Write>(
+    writer: &mut Writer<W>,
+    metadata_store: &MetadataStore,
+) -> Result<(), ConvertError> {
+    let keys_map = amll_meta_keys();
     let mut written_keys = std::collections::HashSet::new();
 
     for (key, amll_key_name) in &keys_map {
@@ -283,7 +304,7 @@ fn write_amll_metadata<W: std::io::Write>(
         if !written_keys.contains(key)
             && let CanonicalMetadataKey::Custom(s) = key
         {
-            if s.eq_ignore_ascii_case("agent") || s.eq_ignore_ascii_case("xml:lang_root") {
+            if !is_writable_custom_key(s) {
                 continue;
             }
             custom_metadata.push((s.as_str(), values));
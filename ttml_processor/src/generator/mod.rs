@@ -78,22 +78,7 @@ fn generate_ttml_inner<W: std::io::Write>(
         "http://music.apple.com/lyric-ttml-internal".to_string(),
     ));
 
-    let amll_keys_to_check_for_namespace = [
-        CanonicalMetadataKey::Title,
-        CanonicalMetadataKey::Artist,
-        CanonicalMetadataKey::Album,
-        CanonicalMetadataKey::Isrc,
-        CanonicalMetadataKey::AppleMusicId,
-        CanonicalMetadataKey::NcmMusicId,
-        CanonicalMetadataKey::QqMusicId,
-        CanonicalMetadataKey::SpotifyId,
-        CanonicalMetadataKey::TtmlAuthorGithub,
-        CanonicalMetadataKey::TtmlAuthorGithubLogin,
-    ];
-    if amll_keys_to_check_for_namespace
-        .iter()
-        .any(|key| metadata_store.get_multiple_values(key).is_some())
-    {
+    if head::has_amll_metadata(metadata_store) {
         namespace_attrs.push(("xmlns:amll", "http://www.example.com/ns/amll".to_string()));
     }
 
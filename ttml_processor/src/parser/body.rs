@@ -16,7 +16,7 @@ use super::{
     },
 };
 use lyrics_helper_core::{
-    AnnotatedTrack, ContentType, ConvertError, LyricLine, LyricSyllable, LyricTrack,
+    AnnotatedTrack, ContentType, ConvertError, LyricLine, LyricSyllable, LyricTrack, ParseWarning,
     TrackMetadataKey, Word,
 };
 use quick_xml::{
@@ -35,7 +35,7 @@ pub(super) fn handle_p_event(
     state: &mut TtmlParserState,
     reader: &Reader<&[u8]>,
     lines: &mut Vec<LyricLine>,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     match event {
         Event::Start(e) if e.local_name().as_ref() == TAG_SPAN => {
@@ -53,7 +53,9 @@ pub(super) fn handle_p_event(
 
                 u32::from_str_radix(code_point_str, radix).map_or_else(
                     |_| {
-                        warnings.push(format!("无法解析无效的XML数字实体 '&{entity_name};'"));
+                        warnings.push(ParseWarning::error(format!(
+                            "无法解析无效的XML数字实体 '&{entity_name};'"
+                        )));
                         '\0'
                     },
                     |code_point| char::from_u32(code_point).unwrap_or('\0'),
@@ -66,7 +68,9 @@ pub(super) fn handle_p_event(
                     "quot" => '"',
                     "apos" => '\'',
                     _ => {
-                        warnings.push(format!("忽略了未知的XML实体 '&{entity_name};'"));
+                        warnings.push(ParseWarning::warning(format!(
+                            "忽略了未知的XML实体 '&{entity_name};'"
+                        )));
                         '\0'
                     }
                 }
@@ -84,7 +88,7 @@ pub(super) fn handle_p_event(
         }
         Event::End(e) => match e.local_name().as_ref() {
             TAG_BR => {
-                warnings.push(format!(
+                warnings.push(ParseWarning::hint(format!(
                     "在 <p> ({}ms-{}ms) 中发现并忽略了一个 <br/> 标签。",
                     state
                         .body_state
@@ -96,7 +100,7 @@ pub(super) fn handle_p_event(
                         .current_p_element_data
                         .as_ref()
                         .map_or(0, |d| d.end_ms)
-                ));
+                )));
             }
             TAG_P => {
                 handle_p_end(state, lines, warnings);
@@ -116,7 +120,7 @@ pub(super) fn handle_p_event(
 pub(super) fn handle_p_end(
     state: &mut TtmlParserState,
     lines: &mut Vec<LyricLine>,
-    warnings: &mut [String],
+    warnings: &mut [ParseWarning],
 ) {
     if let Some(mut p_data) = state.body_state.current_p_element_data.take() {
         if let Some(key) = &p_data.itunes_key {
@@ -183,7 +187,7 @@ fn process_span_start(
     e: &BytesStart,
     state: &mut TtmlParserState,
     reader: &Reader<&[u8]>,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     // 进入新的 span 前，清空文本缓冲区
     state.text_buffer.clear();
@@ -303,7 +307,7 @@ fn handle_general_text(text_slice: &str, state: &mut TtmlParserState) {
 /// 处理 `</span>` 结束事件的分发器。
 fn process_span_end(
     state: &mut TtmlParserState,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     // 从堆栈中弹出刚刚结束的 span 的上下文
     if let Some(ended_span_ctx) = state.body_state.span_stack.pop() {
@@ -336,7 +340,7 @@ fn handle_generic_span_end(
     state: &mut TtmlParserState,
     ctx: &SpanContext,
     text: &str,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     if let (Some(start_ms), Some(end_ms)) = (ctx.start_ms, ctx.end_ms) {
         if text.is_empty() {
@@ -382,12 +386,12 @@ fn handle_generic_span_end(
         }
 
         if start_ms > end_ms {
-            warnings.push(format!(
+            warnings.push(ParseWarning::warning(format!(
                 "音节 '{}' 的时间戳无效 (start_ms {} > end_ms {}), 但仍会创建音节。",
                 text.escape_debug(),
                 start_ms,
                 end_ms
-            ));
+            )));
         }
 
         let target_annotated_track =
@@ -424,10 +428,10 @@ fn handle_generic_span_end(
                 p_data.line_text_accumulator.push_str(text.trim());
             }
         } else {
-            warnings.push(format!(
+            warnings.push(ParseWarning::error(format!(
                 "逐字模式下，span缺少时间信息，文本 '{}' 被忽略。",
                 text.trim().escape_debug()
-            ));
+            )));
         }
     }
     Ok(())
@@ -563,7 +567,7 @@ fn handle_background_span_end(
     state: &mut TtmlParserState,
     ctx: &SpanContext,
     text: &str, // 背景容器直接包含的文本
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     let p_data = state
         .body_state
@@ -617,14 +621,14 @@ fn handle_background_span_end(
                         was_background: true,
                     };
                 } else {
-                    warnings.push(format!("<span ttm:role='x-bg'> 直接包含文本 '{}'，但其内部已有音节，此直接文本被忽略。", trimmed_text.escape_debug()));
+                    warnings.push(ParseWarning::warning(format!("<span ttm:role='x-bg'> 直接包含文本 '{}'，但其内部已有音节，此直接文本被忽略。", trimmed_text.escape_debug())));
                 }
             }
         } else {
-            warnings.push(format!(
+            warnings.push(ParseWarning::error(format!(
                 "<span ttm:role='x-bg'> 直接包含文本 '{}'，但缺少时间信息，忽略。",
                 trimmed_text.escape_debug()
-            ));
+            )));
         }
     }
     Ok(())
@@ -641,7 +645,7 @@ fn finalize_p_element(
     mut p_data: CurrentPElementData,
     lines: &mut Vec<LyricLine>,
     state: &mut TtmlParserState,
-    _warnings: &mut [String],
+    _warnings: &mut [ParseWarning],
 ) {
     // 步骤 1: 如果是逐行模式且没有音节，则根据累积的文本创建主轨道
     create_main_track_from_accumulator_if_needed(&mut p_data, state);
@@ -662,14 +666,14 @@ fn finalize_p_element(
     let max_track_end_ms = recalculate_line_end_ms(&new_line);
     new_line.end_ms = new_line.end_ms.max(max_track_end_ms);
 
-    // 步骤 4: 确保行不为空，然后添加到结果列表中
+    // 步骤 4: 确保行不为空，然后添加到结果列表中。间奏标记行本身就是空的，需要保留
     let is_empty = new_line.tracks.iter().all(|at| {
         at.content.words.iter().all(|w| w.syllables.is_empty())
             && at.translations.is_empty()
             && at.romanizations.is_empty()
     });
 
-    if !is_empty {
+    if !is_empty || new_line.is_interlude() {
         lines.push(new_line);
     }
 }
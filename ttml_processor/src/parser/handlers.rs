@@ -13,7 +13,9 @@ use super::{
     state::{BodyParseState, CurrentPElementData, MetadataParseState, TtmlParserState},
     utils::{get_string_attribute, get_time_attribute},
 };
-use lyrics_helper_core::{ConvertError, LyricLine, TtmlParsingOptions, TtmlTimingMode};
+use lyrics_helper_core::{
+    ConvertError, LyricLine, ParseWarning, TtmlParsingOptions, TtmlTimingMode,
+};
 use quick_xml::{
     Reader,
     events::{BytesStart, Event},
@@ -26,7 +28,7 @@ pub(super) fn handle_global_event(
     state: &mut TtmlParserState,
     reader: &Reader<&[u8]>,
     raw_metadata: &mut HashMap<String, Vec<String>>,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
     has_timed_span_tags: bool,
     options: &TtmlParsingOptions,
 ) -> Result<(), ConvertError> {
@@ -106,7 +108,7 @@ fn process_tt_start(
     raw_metadata: &mut HashMap<String, Vec<String>>,
     reader: &Reader<&[u8]>,
     has_timed_span_tags: bool,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
     options: &TtmlParsingOptions,
 ) -> Result<(), ConvertError> {
     if let Some(forced_mode) = options.force_timing_mode {
@@ -122,10 +124,10 @@ fn process_tt_start(
         } else if !has_timed_span_tags {
             state.is_line_timing_mode = true;
             state.detected_line_mode = true;
-            warnings.push(
+            warnings.push(ParseWarning::warning(
                 "未找到带时间戳的 <span> 标签且未指定 itunes:timing 模式，切换到逐行歌词模式。"
                     .to_string(),
-            );
+            ));
         }
     }
 
@@ -157,23 +159,25 @@ pub(super) fn attempt_recovery_from_error(
     state: &mut TtmlParserState,
     reader: &Reader<&[u8]>,
     lines: &mut Vec<LyricLine>,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
     error: &quick_xml::errors::Error,
 ) {
     let position = reader.error_position();
-    warnings.push(format!("TTML 格式错误，位置 {position}: {error}。"));
+    warnings.push(ParseWarning::error(format!(
+        "TTML 格式错误，位置 {position}: {error}。"
+    )));
 
     if state.body_state.in_p {
         // 错误发生在 <p> 标签内部
         // 尝试抢救当前行的数据，然后跳出这个<p>
-        warnings.push(format!(
+        warnings.push(ParseWarning::error(format!(
             "错误发生在 <p> 元素内部 (开始于 {}ms)。尝试恢复已经解析的数据。",
             state
                 .body_state
                 .current_p_element_data
                 .as_ref()
                 .map_or(0, |d| d.start_ms)
-        ));
+        )));
 
         // 处理和保存当前 <p> 中已经累积的数据
         // 把current_p_element_data中的内容（即使不完整）转换成一个 LyricLine
@@ -184,15 +188,18 @@ pub(super) fn attempt_recovery_from_error(
     } else if state.in_metadata {
         // 错误发生在 <metadata> 内部
         // 元数据太复杂了，简单地放弃所有数据好了
-        warnings.push("错误发生在 <metadata> 块内部。放弃所有元数据。".to_string());
+        warnings.push(ParseWarning::error(
+            "错误发生在 <metadata> 块内部。放弃所有元数据。",
+        ));
         state.in_metadata = false;
         state.metadata_state = MetadataParseState::default();
     } else {
         // 错误发生在全局作用域
         // 可能是 <body> 或 <div> 标签损坏。恢复的把握较小。
         // 我们重置所有 body 相关的状态，期望能找到下一个有效的 <p>。
-        warnings
-            .push("错误发生在全局作用域。将重置解析器状态，尝试寻找下一个有效元素。".to_string());
+        warnings.push(ParseWarning::error(
+            "错误发生在全局作用域。将重置解析器状态，尝试寻找下一个有效元素。",
+        ));
         state.body_state = BodyParseState::default();
     }
 }
@@ -8,7 +8,9 @@ use super::{
     state::{AuxTrackType, MetadataContext, SpanContext, SpanRole, TtmlParserState},
     utils::{get_attribute_with_aliases, get_string_attribute, get_time_attribute},
 };
-use lyrics_helper_core::{Agent, AgentType, ConvertError, LyricTrack, TrackMetadataKey, Word};
+use lyrics_helper_core::{
+    Agent, AgentType, ConvertError, LyricTrack, ParseWarning, TrackMetadataKey, Word,
+};
 use quick_xml::{
     Reader,
     events::{BytesStart, BytesText, Event},
@@ -27,7 +29,7 @@ pub(super) fn handle_metadata_event(
     reader: &mut Reader<&[u8]>,
     state: &mut TtmlParserState,
     raw_metadata: &mut HashMap<String, Vec<String>>,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     match event {
         Event::Start(e) => handle_metadata_start_tag(e, reader, state, raw_metadata, warnings),
@@ -46,7 +48,7 @@ fn handle_metadata_start_tag(
     reader: &mut Reader<&[u8]>,
     state: &mut TtmlParserState,
     raw_metadata: &mut HashMap<String, Vec<String>>,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     let meta_state = &mut state.metadata_state;
 
@@ -104,7 +106,7 @@ fn process_agent_start_in_metadata(
     e: &BytesStart,
     reader: &Reader<&[u8]>,
     state: &mut TtmlParserState,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     let id_opt = get_string_attribute(e, reader, &[ATTR_XML_ID])?;
     if let Some(id) = id_opt {
@@ -123,7 +125,9 @@ fn process_agent_start_in_metadata(
         state.agent_store.agents_by_id.insert(id.clone(), agent);
         state.metadata_state.context = MetadataContext::InAgent { id: Some(id) };
     } else {
-        warnings.push("发现一个没有 xml:id 的 <ttm:agent> 标签，已忽略。".to_string());
+        warnings.push(ParseWarning::hint(
+            "发现一个没有 xml:id 的 <ttm:agent> 标签，已忽略。",
+        ));
     }
     Ok(())
 }
@@ -178,7 +182,7 @@ fn process_span_start_in_metadata(
     e: &BytesStart,
     reader: &Reader<&[u8]>,
     state: &mut TtmlParserState,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(), ConvertError> {
     let meta_state = &mut state.metadata_state;
     if matches!(meta_state.context, MetadataContext::InAuxiliaryText { .. }) {
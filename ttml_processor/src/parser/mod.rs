@@ -17,7 +17,7 @@ use tracing::error;
 
 use self::state::{FormatDetection, TtmlParserState};
 use lyrics_helper_core::{
-    ConvertError, LyricFormat, LyricLine, ParsedSourceData, TtmlParsingOptions,
+    ConvertError, LyricFormat, LyricLine, ParseWarning, ParsedSourceData, TtmlParsingOptions,
 };
 
 /// 解析 TTML 格式的歌词文件。
@@ -52,7 +52,7 @@ pub fn parse_ttml(
 
     let mut lines: Vec<LyricLine> = Vec::with_capacity(content.matches("<p").count());
     let mut raw_metadata: HashMap<String, Vec<String>> = HashMap::new();
-    let mut warnings: Vec<String> = Vec::new();
+    let mut warnings: Vec<ParseWarning> = Vec::new();
 
     // 初始化解析状态机
     let mut state = TtmlParserState {
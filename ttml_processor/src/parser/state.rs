@@ -62,7 +62,8 @@ impl TtmlParserState {
     /// 这个方法会：
     /// 1. 检查值是否为已知的 ID。
     /// 2. 如果不是 ID，则检查是否为已知的名称。
-    /// 3. 如果两者都不是，则创建一个新的 Agent 记录和 ID。
+    /// 3. 如果值本身形如 ID（如 "v2"）但未在头部声明，则直接以该 ID 注册。
+    /// 4. 否则视为演唱者名称，创建一个新的 Agent 记录和不与已有 ID 冲突的新 ID。
     pub(super) fn resolve_agent_id(&mut self, agent_attr_val: Option<String>) -> Option<String> {
         let val = agent_attr_val?;
 
@@ -74,8 +75,25 @@ impl TtmlParserState {
             return Some(existing_id.clone());
         }
 
-        self.agent_counter += 1;
-        let new_id = format!("v{}", self.agent_counter);
+        if is_agent_id_like(&val) {
+            self.agent_store.agents_by_id.insert(
+                val.clone(),
+                Agent {
+                    id: val.clone(),
+                    name: None,
+                    agent_type: AgentType::Person,
+                },
+            );
+            return Some(val);
+        }
+
+        let new_id = loop {
+            self.agent_counter += 1;
+            let candidate = format!("v{}", self.agent_counter);
+            if !self.agent_store.agents_by_id.contains_key(&candidate) {
+                break candidate;
+            }
+        };
 
         self.agent_name_to_id_map
             .insert(val.clone(), new_id.clone());
@@ -93,6 +111,12 @@ impl TtmlParserState {
     }
 }
 
+/// 判断 agent 属性值是否为 Apple Music 风格的 ID（`v` 后跟数字，如 "v1"、"v1000"）。
+fn is_agent_id_like(val: &str) -> bool {
+    val.strip_prefix('v')
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(super) enum AuxTrackType {
     Translation,
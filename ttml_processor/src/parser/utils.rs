@@ -3,7 +3,7 @@
 //! 该模块提供了一系列用于处理 TTML 特定数据格式的辅助函数，
 //! 例如时间戳解析、属性提取和文本清理。
 
-use lyrics_helper_core::ConvertError;
+use lyrics_helper_core::{ConvertError, ParseWarning};
 use quick_xml::{Reader, events::BytesStart};
 
 /// 解析 TTML 时间字符串到毫秒。
@@ -211,15 +211,15 @@ pub(super) fn get_time_attribute(
     e: &BytesStart,
     reader: &Reader<&[u8]>,
     attr_names: &[&[u8]],
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<Option<u64>, ConvertError> {
     (get_string_attribute(e, reader, attr_names)?).map_or(Ok(None), |value_str| {
         match parse_ttml_time_to_ms(&value_str) {
             Ok(ms) => Ok(Some(ms)),
             Err(err) => {
-                warnings.push(format!(
+                warnings.push(ParseWarning::error(format!(
                     "时间戳 '{value_str}' 解析失败 ({err}). 该时间戳将被忽略."
-                ));
+                )));
                 Ok(None)
             }
         }
@@ -159,12 +159,15 @@ fn test_warning_generation_for_recoverable_issues() {
     assert!(!result.warnings.is_empty(), "应该产生警告");
 
     assert!(
-        result.warnings.iter().any(|w| w.contains("<br/>")),
+        result.warnings.iter().any(|w| w.message.contains("<br/>")),
         "应该警告 br 标签"
     );
 
     assert!(
-        result.warnings.iter().any(|w| w.contains("时间戳无效")),
+        result
+            .warnings
+            .iter()
+            .any(|w| w.message.contains("时间戳无效")),
         "应该警告时间戳无效"
     );
 }
@@ -709,3 +712,80 @@ fn test_parse_space_only_span_as_whitespace_in_metadata() {
         "翻译音节 'One' 后面不应有空格"
     );
 }
+
+#[test]
+fn test_apple_music_agents_and_background_round_trip() {
+    let content = r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata" xmlns:itunes="http://music.apple.com/lyric-ttml-internal" itunes:timing="Word" xml:lang="en">
+  <head>
+    <metadata>
+      <ttm:agent type="person" xml:id="v1"/>
+      <ttm:agent type="person" xml:id="v2"/>
+    </metadata>
+  </head>
+  <body>
+    <div>
+      <p begin="1.000" end="2.000" ttm:agent="v1" itunes:key="L1"><span begin="1.000" end="2.000">Hello</span></p>
+      <p begin="2.000" end="4.000" ttm:agent="v2" itunes:key="L2"><span begin="2.000" end="3.000">World</span><span ttm:role="x-bg"><span begin="3.000" end="4.000">(ooh)</span></span></p>
+      <p begin="4.000" end="5.000" ttm:agent="v3" itunes:key="L3"><span begin="4.000" end="5.000">Again</span></p>
+    </div>
+  </body>
+</tt>"#;
+    let parsed = parse_ttml(content, &TtmlParsingOptions::default()).unwrap();
+
+    let agents: Vec<_> = parsed.lines.iter().map(|l| l.agent.as_deref()).collect();
+    assert_eq!(agents, vec![Some("v1"), Some("v2"), Some("v3")]);
+    assert!(
+        parsed.agents.agents_by_id.contains_key("v3"),
+        "未声明的 ID 应按原样注册"
+    );
+
+    let bg_syllables = get_syllables_from_line(&parsed.lines[1], ContentType::Background);
+    assert_eq!(bg_syllables.len(), 1);
+    assert_eq!(bg_syllables[0].text, "ooh");
+
+    let options = TtmlGenerationOptionsBuilder::default()
+        .timing_mode(TtmlTimingMode::Word)
+        .build()
+        .unwrap();
+    let generated = generate_ttml(
+        &parsed.lines,
+        &MetadataStore::new(),
+        &parsed.agents,
+        &options,
+    )
+    .unwrap();
+    let reparsed = parse_ttml(&generated, &TtmlParsingOptions::default()).unwrap();
+
+    let reparsed_agents: Vec<_> = reparsed.lines.iter().map(|l| l.agent.as_deref()).collect();
+    assert_eq!(reparsed_agents, agents);
+    let reparsed_bg = get_syllables_from_line(&reparsed.lines[1], ContentType::Background);
+    assert_eq!(reparsed_bg.len(), 1);
+    assert_eq!(reparsed_bg[0].text, "ooh");
+}
+
+#[test]
+fn test_interlude_marker_round_trip() {
+    let mut first = LyricLine::new(1000, 2000);
+    first.add_content_track(ContentType::Main, "Hello");
+    let mut second = LyricLine::new(9000, 10000);
+    second.add_content_track(ContentType::Main, "World");
+    let lines = vec![first, LyricLine::new_interlude(2000, 9000), second];
+
+    let generated = generate_ttml(
+        &lines,
+        &MetadataStore::new(),
+        &AgentStore::new(),
+        &TtmlGenerationOptions::default(),
+    )
+    .unwrap();
+    assert!(generated.contains(r#"itunes:song-part="Interlude""#));
+
+    let reparsed = parse_ttml(&generated, &TtmlParsingOptions::default()).unwrap();
+    assert_eq!(reparsed.lines.len(), 3);
+    assert!(reparsed.lines[1].is_interlude());
+    assert_eq!(
+        (reparsed.lines[1].start_ms, reparsed.lines[1].end_ms),
+        (2000, 9000)
+    );
+    assert!(!reparsed.lines[2].is_interlude());
+}